@@ -56,6 +56,7 @@ fn function_declaration_program() -> Program {
         statements: vec![
             Stmt::FunctionDeclaration {
                 name: "add".to_string(),
+                type_params: vec![],
                 params: vec![("a".to_string(), None, None), ("b".to_string(), None, None)],
                 rest_param: None,
                 return_type: None,
@@ -73,6 +74,7 @@ fn function_declaration_program() -> Program {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
             },
             Stmt::Expression(
                 Expr::Call(
@@ -6,6 +6,7 @@
 
 use crate::opcode::OpCode;
 use crate::value::Value;
+use std::collections::BTreeSet;
 
 /// Um chunk de bytecode
 ///
@@ -98,6 +99,18 @@ impl Chunk {
         self.lines.get(index).copied()
     }
 
+    /// Retorna todas as linhas de código fonte que geraram opcodes, incluindo as
+    /// das funções guardadas como constantes (usado para cobertura de código)
+    pub fn source_lines(&self) -> BTreeSet<usize> {
+        let mut lines: BTreeSet<usize> = self.lines.iter().copied().filter(|&l| l > 0).collect();
+        for constant in &self.constants {
+            if let Value::Function(function) = constant {
+                lines.extend(function.chunk.source_lines());
+            }
+        }
+        lines
+    }
+
     /// Retorna uma constante pelo índice
     pub fn get_constant(&self, index: u8) -> Option<&Value> {
         self.constants.get(index as usize)
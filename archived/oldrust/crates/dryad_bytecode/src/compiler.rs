@@ -273,11 +273,18 @@ impl Compiler {
                 params,
                 body,
                 location,
+                is_generator,
                 ..
-            } => self.compile_function_declaration(name, params, *body, location.line),
+            } => self.compile_function_declaration(
+                name,
+                params,
+                *body,
+                is_generator,
+                location.line,
+            ),
 
             // Stmt::Print foi removido - print é tratado como função nativa
-            Stmt::ClassDeclaration(name, superclass, _interfaces, members, loc) => {
+            Stmt::ClassDeclaration(name, _type_params, superclass, _interfaces, members, _is_abstract, loc) => {
                 self.compile_class_declaration(name, superclass, members, loc.line)
             }
 
@@ -291,6 +298,12 @@ impl Compiler {
                 Ok(())
             }
 
+            // Blocos de teste são executados apenas pelo `dryad test`
+            Stmt::Test(..) => Ok(()),
+
+            // Aliases de tipo só existem para o checker
+            Stmt::TypeAlias(..) => Ok(()),
+
             // Statements não implementados ainda
             _ => {
                 // Para statements não suportados
@@ -355,6 +368,8 @@ impl Compiler {
                     let idx = self.make_constant(Value::String(name), line)?;
                     self.emit_op(OpCode::SetGlobal(idx), line);
                 }
+                // Set* deixa o valor na pilha; como statement, ele é descartado
+                self.emit_op(OpCode::Pop, line);
             }
             _ => {
                 return Err(format!("Padrões em atribuição ainda não suportados"));
@@ -371,16 +386,16 @@ impl Compiler {
         value: Expr,
         line: usize,
     ) -> Result<(), String> {
-        // Compila o objeto, valor e índice
+        // Compila o objeto e o valor
         self.compile_expression(object)?;
         self.compile_expression(value)?;
 
-        // TODO: Implementar set property
-        // Por enquanto, apenas deixamos os valores na pilha
-        self.emit_op(OpCode::Pop, line);
+        let idx = self.make_constant(Value::String(property), line)?;
+        self.emit_op(OpCode::SetProperty(idx), line);
+        // SetProperty deixa o valor na pilha; como statement, ele é descartado
         self.emit_op(OpCode::Pop, line);
 
-        Err(format!("Atribuição de propriedade ainda não implementada"))
+        Ok(())
     }
 
     fn compile_index_assignment(
@@ -395,8 +410,9 @@ impl Compiler {
         self.compile_expression(index)?;
         self.compile_expression(value)?;
 
-        // TODO: Implementar SetIndex
         self.emit_op(OpCode::SetIndex, line);
+        // SetIndex deixa o valor na pilha; como statement, ele é descartado
+        self.emit_op(OpCode::Pop, line);
 
         Ok(())
     }
@@ -619,53 +635,55 @@ impl Compiler {
     ) -> Result<(), String> {
         self.begin_scope();
 
-        // Compila o iterable e armazena em variável local temporária
+        // Converte o iterable em iterador e o guarda em variável local temporária
         self.compile_expression(iterable)?;
-        self.add_local("__iterable".to_string());
-
-        // Inicializa índice em 0
-        let zero_idx = self.make_constant(Value::Number(0.0), line)?;
-        self.emit_op(OpCode::Constant(zero_idx), line);
-        self.add_local("__index".to_string());
+        self.emit_op(OpCode::GetIter, line);
+        self.add_local("__iter".to_string());
+        let iter_slot = self.resolve_local("__iter").unwrap();
 
-        // Marca início do loop
+        // Marca início do loop: obtém o próximo valor ou sai quando o iterador se esgota
         let loop_start = self.current_chunk.len();
+        self.emit_op(OpCode::GetLocal(iter_slot), line);
+        let exit_jump = self.emit_jump(OpCode::IterNext(0), line);
 
-        // Verifica se índice < tamanho do array
-        // Compila: __index < len(__iterable)
-        self.emit_op(
-            OpCode::GetLocal(self.resolve_local("__index").unwrap()),
-            line,
-        );
-
-        // Para obter o tamanho, precisamos de um opcode de len
-        // Por enquanto, vamos usar uma abordagem diferente:
-        // Tentamos acessar o índice e se falhar (nil), saímos do loop
-        self.emit_op(
-            OpCode::GetLocal(self.resolve_local("__iterable").unwrap()),
-            line,
-        );
-        self.emit_op(
-            OpCode::GetLocal(self.resolve_local("__index").unwrap()),
-            line,
-        );
-        self.emit_op(OpCode::Index, line);
-
-        // Se o resultado for nil, sai do loop
-        let exit_jump = self.emit_jump(OpCode::JumpIfFalse(0), line);
-        self.emit_op(OpCode::Pop, line); // Remove o valor nil/falso
+        // break/continue removem apenas as variáveis do padrão, mantendo o iterador
+        self.loop_stack.push(LoopInfo {
+            loop_type: LoopType::While,
+            start_pos: loop_start,
+            continue_target: loop_start,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+            scope_depth: self.locals.len(),
+        });
 
-        // Novo escopo para a variável do padrão
+        // Novo escopo para as variáveis do padrão
         self.begin_scope();
 
-        // Atribui à variável do padrão
         match pattern {
             Pattern::Identifier(name) => {
-                // Duplica o valor do topo (elemento atual)
-                self.emit_op(OpCode::Dup, line);
-                // Adiciona como variável local
+                // O valor atual já está no topo da pilha
                 self.add_local(name);
             }
+            Pattern::Tuple(items) | Pattern::Array(items) => {
+                // Desestrutura `(k, v)` / `[a, b]` por índice
+                self.add_local("__item".to_string());
+                let item_slot = self.resolve_local("__item").unwrap();
+                for (i, item) in items.into_iter().enumerate() {
+                    let name = match item {
+                        Pattern::Identifier(name) => name,
+                        _ => {
+                            return Err(
+                                "Padrões aninhados em foreach ainda não suportados".to_string()
+                            )
+                        }
+                    };
+                    self.emit_op(OpCode::GetLocal(item_slot), line);
+                    let idx = self.make_constant(Value::Number(i as f64), line)?;
+                    self.emit_op(OpCode::Constant(idx), line);
+                    self.emit_op(OpCode::Index, line);
+                    self.add_local(name);
+                }
+            }
             _ => {
                 return Err("Padrões complexos em foreach ainda não suportados".to_string());
             }
@@ -674,32 +692,22 @@ impl Compiler {
         // Compila o corpo
         self.compile_statement(body)?;
 
-        // Remove a variável do padrão (fecha o escopo do corpo)
+        // Remove as variáveis do padrão (fecha o escopo do corpo)
         self.end_scope(line);
 
-        // Incrementa o índice: __index = __index + 1
-        self.emit_op(
-            OpCode::GetLocal(self.resolve_local("__index").unwrap()),
-            line,
-        );
-        let one_idx = self.make_constant(Value::Number(1.0), line)?;
-        self.emit_op(OpCode::Constant(one_idx), line);
-        self.emit_op(OpCode::Add, line);
-        self.emit_op(
-            OpCode::SetLocal(self.resolve_local("__index").unwrap()),
-            line,
-        );
-        self.emit_op(OpCode::Pop, line);
-
         // Loop de volta
         let offset = self.current_chunk.len() - loop_start + 1;
         self.emit_op(OpCode::Loop(offset as u16), line);
 
-        // Patch do exit jump
+        // Iterador esgotado e breaks continuam aqui
         self.patch_jump(exit_jump);
-        self.emit_op(OpCode::Pop, line); // Remove o valor que fez o jump
+        if let Some(loop_info) = self.loop_stack.pop() {
+            for break_jump in loop_info.breaks {
+                self.patch_jump(break_jump);
+            }
+        }
 
-        // Limpa as variáveis temporárias
+        // Limpa o iterador
         self.end_scope(line);
 
         Ok(())
@@ -772,6 +780,7 @@ impl Compiler {
         name: String,
         params: Vec<(String, Option<Type>, Option<Expr>)>,
         body: Stmt,
+        is_generator: bool,
         line: usize,
     ) -> Result<(), String> {
         // Cria um novo compilador para a função (com referência ao pai)
@@ -819,6 +828,7 @@ impl Compiler {
             chunk: function_chunk,
             upvalue_count,
             upvalue_info,
+            is_generator,
         };
 
         // Emite instrução para criar a closure
@@ -861,7 +871,7 @@ impl Compiler {
         if let Some(super_name) = superclass {
             let super_idx = self.make_constant(crate::value::Value::String(super_name), line)?;
             self.emit_op(OpCode::GetGlobal(super_idx), line);
-            // TODO: Implementar herança completa (super)
+            self.emit_op(OpCode::Inherit, line);
         }
 
         // Compila métodos
@@ -874,8 +884,14 @@ impl Compiler {
                     visibility,
                     is_static,
                     is_async,
+                    is_abstract,
                     return_type,
                 } => {
+                    // Métodos abstratos não têm corpo; a subclasse fornece a implementação
+                    if is_abstract {
+                        continue;
+                    }
+
                     // Compila o método como uma função
                     let mut method_compiler = Compiler::new();
                     method_compiler.scope_depth = 1;
@@ -902,6 +918,7 @@ impl Compiler {
                         chunk: method_compiler.current_chunk,
                         upvalue_count: 0,
                         upvalue_info: Vec::new(),
+                        is_generator: false,
                     };
 
                     // Emite o método
@@ -945,6 +962,7 @@ impl Compiler {
                         chunk: getter_compiler.current_chunk,
                         upvalue_count: 0,
                         upvalue_info: Vec::new(),
+                        is_generator: false,
                     };
 
                     let method_idx = self.make_constant(
@@ -983,6 +1001,7 @@ impl Compiler {
                         chunk: setter_compiler.current_chunk,
                         upvalue_count: 0,
                         upvalue_info: Vec::new(),
+                        is_generator: false,
                     };
 
                     let method_idx = self.make_constant(
@@ -1067,6 +1086,56 @@ impl Compiler {
                 self.compile_object_literal(properties, loc.line)
             }
 
+            Expr::Range(start, end, inclusive, loc) => {
+                self.compile_expression(*start)?;
+                self.compile_expression(*end)?;
+                self.emit_op(OpCode::Range(inclusive), loc.line);
+                Ok(())
+            }
+
+            Expr::Yield(value, loc) => {
+                match value {
+                    Some(value) => self.compile_expression(*value)?,
+                    None => self.emit_op(OpCode::Nil, loc.line),
+                }
+                // O valor enviado por `next(v)` fica no topo da pilha ao retomar
+                self.emit_op(OpCode::Yield, loc.line);
+                Ok(())
+            }
+
+            Expr::OptionalChain(inner, _) => {
+                // Todo `?.` que encontrar nil pula para o fim da cadeia, deixando nil na pilha
+                let mut exits = Vec::new();
+                self.compile_chain(*inner, &mut exits)?;
+                for exit in exits {
+                    self.patch_jump(exit);
+                }
+                Ok(())
+            }
+
+            expr @ (Expr::OptionalPropertyAccess(..)
+            | Expr::OptionalMethodCall(..)
+            | Expr::OptionalIndex(..)) => {
+                let mut exits = Vec::new();
+                self.compile_chain(expr, &mut exits)?;
+                for exit in exits {
+                    self.patch_jump(exit);
+                }
+                Ok(())
+            }
+
+            Expr::NullishCoalescing(left, right, loc) => {
+                // a ?? b: mantém `a` a menos que seja nil
+                self.compile_expression(*left)?;
+                let rhs_jump = self.emit_jump(OpCode::JumpIfNil(0), loc.line);
+                let end_jump = self.emit_jump(OpCode::Jump(0), loc.line);
+                self.patch_jump(rhs_jump);
+                self.emit_op(OpCode::Pop, loc.line);
+                self.compile_expression(*right)?;
+                self.patch_jump(end_jump);
+                Ok(())
+            }
+
             // Expressões não implementadas
             _ => Err(format!(
                 "Expressão ainda não suportada pelo bytecode: {:?}",
@@ -1093,6 +1162,26 @@ impl Compiler {
                 let idx = self.make_constant(Value::String(s), line)?;
                 self.emit_op(OpCode::Constant(idx), line);
             }
+            // A VM só tem números f64: inteiros exatos em f64 viram Number
+            Literal::Int(n) if n.unsigned_abs() <= (1u64 << 53) => {
+                let idx = self.make_constant(Value::Number(n as f64), line)?;
+                self.emit_op(OpCode::Constant(idx), line);
+            }
+            Literal::Int(n) => {
+                return Err(format!(
+                    "Literal int {}i não é representável sem perda na VM; use o interpretador",
+                    n
+                ))
+            }
+            Literal::BigInt(_) | Literal::Decimal(_) => {
+                return Err(
+                    "Literais bigint/decimal ainda não são suportados pelo bytecode; use o interpretador"
+                        .to_string(),
+                )
+            }
+            Literal::Regex(..) => {
+                return Err("Literais de regex ainda não são suportados pelo bytecode; use o interpretador".to_string())
+            }
         }
         Ok(())
     }
@@ -1146,6 +1235,7 @@ impl Compiler {
             "^" => self.emit_op(OpCode::BitXor, line),
             "<<" => self.emit_op(OpCode::ShiftLeft, line),
             ">>" => self.emit_op(OpCode::ShiftRight, line),
+            "instanceof" => self.emit_op(OpCode::InstanceOf, line),
             _ => return Err(format!("Operador binário não suportado: {}", op)),
         }
 
@@ -1159,6 +1249,7 @@ impl Compiler {
             "-" => self.emit_op(OpCode::Negate, line),
             "!" => self.emit_op(OpCode::Not, line),
             "~" => self.emit_op(OpCode::BitNot, line),
+            "typeof" => self.emit_op(OpCode::TypeOf, line),
             _ => return Err(format!("Operador unário não suportado: {}", op)),
         }
 
@@ -1337,6 +1428,51 @@ impl Compiler {
         Ok(())
     }
 
+    /// Compila um elo de uma cadeia opcional, registrando em `exits` os saltos
+    /// de curto-circuito emitidos por cada `?.`
+    fn compile_chain(&mut self, expr: Expr, exits: &mut Vec<usize>) -> Result<(), String> {
+        let optional = matches!(
+            expr,
+            Expr::OptionalPropertyAccess(..) | Expr::OptionalMethodCall(..) | Expr::OptionalIndex(..)
+        );
+        match expr {
+            Expr::OptionalPropertyAccess(object, property, loc)
+            | Expr::PropertyAccess(object, property, loc) => {
+                self.compile_chain(*object, exits)?;
+                if optional {
+                    exits.push(self.emit_jump(OpCode::JumpIfNil(0), loc.line));
+                }
+                let idx = self.make_constant(Value::String(property), loc.line)?;
+                self.emit_op(OpCode::GetProperty(idx), loc.line);
+                Ok(())
+            }
+            Expr::OptionalMethodCall(object, method, args, loc)
+            | Expr::MethodCall(object, method, args, loc) => {
+                self.compile_chain(*object, exits)?;
+                if optional {
+                    exits.push(self.emit_jump(OpCode::JumpIfNil(0), loc.line));
+                }
+                for arg in args.iter() {
+                    self.compile_expression(arg.clone())?;
+                }
+                let idx = self.make_constant(Value::String(method), loc.line)?;
+                self.emit_op(OpCode::Constant(idx), loc.line);
+                self.emit_op(OpCode::Invoke(args.len() as u8), loc.line);
+                Ok(())
+            }
+            Expr::OptionalIndex(array, index, loc) | Expr::Index(array, index, loc) => {
+                self.compile_chain(*array, exits)?;
+                if optional {
+                    exits.push(self.emit_jump(OpCode::JumpIfNil(0), loc.line));
+                }
+                self.compile_expression(*index)?;
+                self.emit_op(OpCode::Index, loc.line);
+                Ok(())
+            }
+            _ => self.compile_expression(expr),
+        }
+    }
+
     fn compile_tuple_access(&mut self, tuple: Expr, idx: usize, line: usize) -> Result<(), String> {
         // Compila o tuple
         self.compile_expression(tuple)?;
@@ -1381,8 +1517,9 @@ impl Compiler {
             self.compile_expression(arg.clone())?;
         }
 
-        // Invoca o método
+        // Invoca o método (Invoke desempilha o nome antes dos argumentos)
         let idx = self.make_constant(Value::String(method), line)?;
+        self.emit_op(OpCode::Constant(idx), line);
         self.emit_op(OpCode::Invoke(args.len() as u8), line);
 
         Ok(())
@@ -1557,6 +1694,8 @@ impl Compiler {
                 OpCode::Jump(ref mut off) => *off = jump as u16,
                 OpCode::JumpIfFalse(ref mut off) => *off = jump as u16,
                 OpCode::JumpIfTrue(ref mut off) => *off = jump as u16,
+                OpCode::JumpIfNil(ref mut off) => *off = jump as u16,
+                OpCode::IterNext(ref mut off) => *off = jump as u16,
                 _ => {}
             }
         }
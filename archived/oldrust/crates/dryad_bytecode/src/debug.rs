@@ -104,6 +104,9 @@ impl Disassembler {
             OpCode::JumpIfTrue(offset_val) => {
                 Self::print_jump_instruction("JUMP_IF_TRUE", *offset_val, offset)
             }
+            OpCode::JumpIfNil(offset_val) => {
+                Self::print_jump_instruction("JUMP_IF_NIL", *offset_val, offset)
+            }
             OpCode::Loop(offset_val) => Self::print_loop_instruction("LOOP", *offset_val, offset),
             OpCode::Break => Self::print_simple_instruction("BREAK", offset),
             OpCode::Continue => Self::print_simple_instruction("CONTINUE", offset),
@@ -115,6 +118,7 @@ impl Disassembler {
             OpCode::GetUpvalue(idx) => Self::print_byte_instruction("GET_UPVALUE", *idx, offset),
             OpCode::SetUpvalue(idx) => Self::print_byte_instruction("SET_UPVALUE", *idx, offset),
             OpCode::CloseUpvalue => Self::print_simple_instruction("CLOSE_UPVALUE", offset),
+            OpCode::Yield => Self::print_simple_instruction("YIELD", offset),
 
             // Objetos
             OpCode::Class(idx) => Self::print_byte_instruction("CLASS", *idx, offset),
@@ -124,6 +128,9 @@ impl Disassembler {
             OpCode::SetProperty(idx) => Self::print_byte_instruction("SET_PROPERTY", *idx, offset),
             OpCode::This => Self::print_simple_instruction("THIS", offset),
             OpCode::Super(idx) => Self::print_byte_instruction("SUPER", *idx, offset),
+            OpCode::Inherit => Self::print_simple_instruction("INHERIT", offset),
+            OpCode::InstanceOf => Self::print_simple_instruction("INSTANCE_OF", offset),
+            OpCode::TypeOf => Self::print_simple_instruction("TYPE_OF", offset),
 
             // Coleções
             OpCode::Array(count) => Self::print_short_instruction("ARRAY", *count, offset),
@@ -132,6 +139,15 @@ impl Disassembler {
             OpCode::Tuple(count) => Self::print_byte_instruction("TUPLE", *count, offset),
             OpCode::TupleAccess(idx) => Self::print_byte_instruction("TUPLE_ACCESS", *idx, offset),
             OpCode::Object(count) => Self::print_short_instruction("OBJECT", *count, offset),
+            OpCode::Range(inclusive) => {
+                Self::print_byte_instruction("RANGE", *inclusive as u8, offset)
+            }
+
+            // Iteração
+            OpCode::GetIter => Self::print_simple_instruction("GET_ITER", offset),
+            OpCode::IterNext(offset_val) => {
+                Self::print_jump_instruction("ITER_NEXT", *offset_val, offset)
+            }
 
             // Pilha
             OpCode::Pop => Self::print_simple_instruction("POP", offset),
@@ -148,8 +164,8 @@ impl Disassembler {
 
             // Exceções
             OpCode::TryBegin(catch_off, finally_off) => {
-                Self::print_short_instruction("TRY_BEGIN", *catch_off as u16, offset);
-                offset + 5 // 1 + 2 + 2 bytes
+                println!("{:16} {:4} {:4}", "TRY_BEGIN", catch_off, finally_off);
+                offset + 1
             }
             OpCode::TryEnd => Self::print_simple_instruction("TRY_END", offset),
             OpCode::Throw => Self::print_simple_instruction("THROW", offset),
@@ -160,6 +176,7 @@ impl Disassembler {
         }
     }
 
+    // Cada opcode ocupa uma posição em `Chunk::code` (operandos ficam no próprio enum)
     fn print_simple_instruction(name: &str, offset: usize) -> usize {
         println!("{}", name);
         offset + 1
@@ -167,12 +184,12 @@ impl Disassembler {
 
     fn print_byte_instruction(name: &str, byte: u8, offset: usize) -> usize {
         println!("{:16} {:4}", name, byte);
-        offset + 2
+        offset + 1
     }
 
     fn print_short_instruction(name: &str, short: u16, offset: usize) -> usize {
         println!("{:16} {:6}", name, short);
-        offset + 3
+        offset + 1
     }
 
     fn print_constant_instruction(name: &str, chunk: &Chunk, idx: u16, offset: usize) -> usize {
@@ -184,19 +201,19 @@ impl Disassembler {
             print!("<invalid>");
         }
         println!("'");
-        offset + if idx <= u8::MAX as u16 { 2 } else { 3 }
+        offset + 1
     }
 
     fn print_jump_instruction(name: &str, jump_offset: u16, offset: usize) -> usize {
-        let target = offset + 3 + jump_offset as usize;
+        let target = offset + 1 + jump_offset as usize;
         println!("{:16} {:4} -> {}", name, jump_offset, target);
-        offset + 3
+        offset + 1
     }
 
     fn print_loop_instruction(name: &str, loop_offset: u16, offset: usize) -> usize {
-        let target = offset + 3 - loop_offset as usize;
+        let target = (offset + 1).saturating_sub(loop_offset as usize);
         println!("{:16} {:4} -> {}", name, loop_offset, target);
-        offset + 3
+        offset + 1
     }
 }
 
@@ -217,3 +234,33 @@ impl DebugChunk for Chunk {
         Disassembler::disassemble_instruction(self, offset)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::Value;
+
+    #[test]
+    fn test_disassembler_visits_every_instruction() {
+        // Cada opcode ocupa uma posição, com ou sem operandos: pular posições
+        // escondia as instruções seguintes a jumps, constantes e TRY_BEGIN
+        let mut chunk = Chunk::empty();
+        let idx = chunk.add_constant(Value::Number(1.0)).unwrap();
+        chunk.push_op(OpCode::TryBegin(4, 0), 1);
+        chunk.push_op(OpCode::Constant(idx), 1);
+        chunk.push_op(OpCode::Jump(2), 2);
+        chunk.push_op(OpCode::GetLocal(0), 2);
+        chunk.push_op(OpCode::Pop, 2);
+        chunk.push_op(OpCode::TryEnd, 3);
+        chunk.push_op(OpCode::Loop(6), 3);
+        chunk.push_op(OpCode::Return, 4);
+
+        let mut visited = Vec::new();
+        let mut offset = 0;
+        while offset < chunk.len() {
+            visited.push(offset);
+            offset = Disassembler::disassemble_instruction(&chunk, offset);
+        }
+        assert_eq!(visited, (0..chunk.len()).collect::<Vec<_>>());
+    }
+}
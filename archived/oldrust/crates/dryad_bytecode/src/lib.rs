@@ -58,7 +58,7 @@ pub use compiler::Compiler;
 pub use debug::{DebugChunk, Disassembler};
 pub use opcode::{OpCode, OpCodeCategory};
 pub use value::{Function, Heap, HeapId, NativeFn, Object, Value};
-pub use vm::{InterpretResult, VM};
+pub use vm::{InterpretResult, VmCoverage, VmProfile, VM};
 
 /// Versão da crate
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
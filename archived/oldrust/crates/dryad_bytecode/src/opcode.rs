@@ -108,6 +108,8 @@ pub enum OpCode {
     JumpIfFalse(u16),
     /// Pula para frente se o topo da pilha for verdadeiro
     JumpIfTrue(u16),
+    /// Pula para frente se o topo da pilha for nil (usado por `?.` e `??`)
+    JumpIfNil(u16),
     /// Pula para trás (para loops) - offset de 16 bits
     Loop(u16),
     /// Break - sai de um loop
@@ -130,6 +132,8 @@ pub enum OpCode {
     SetUpvalue(u8),
     /// Fecha upvalues até um certo índice
     CloseUpvalue,
+    /// Suspende o gerador atual entregando o topo da pilha a quem chamou `next()`
+    Yield,
 
     // ============================================
     // Classes e Objetos
@@ -148,6 +152,12 @@ pub enum OpCode {
     This,
     /// Carrega 'super'
     Super(u8),
+    /// Herda os métodos da superclasse no topo da pilha para a classe abaixo dela
+    Inherit,
+    /// Verifica se o valor é instância da classe no topo da pilha (instanceof)
+    InstanceOf,
+    /// Substitui o topo da pilha pelo nome do seu tipo (typeof)
+    TypeOf,
 
     // ============================================
     // Exceções
@@ -178,6 +188,16 @@ pub enum OpCode {
     TupleAccess(u8),
     /// Cria um novo objeto (número de pares chave-valor)
     Object(u16),
+    /// Cria um intervalo preguiçoso a partir de início e fim (true = inclusivo)
+    Range(bool),
+
+    // ============================================
+    // Iteração
+    // ============================================
+    /// Substitui o topo da pilha por um iterador sobre ele
+    GetIter,
+    /// Desempilha um iterador e empilha o próximo valor, ou pula (offset) se esgotado
+    IterNext(u16),
 
     // ============================================
     // Manipulação de Pilha
@@ -243,6 +263,7 @@ impl OpCode {
             OpCode::Jump(_) => "JUMP",
             OpCode::JumpIfFalse(_) => "JUMP_IF_FALSE",
             OpCode::JumpIfTrue(_) => "JUMP_IF_TRUE",
+            OpCode::JumpIfNil(_) => "JUMP_IF_NIL",
             OpCode::Loop(_) => "LOOP",
             OpCode::Break => "BREAK",
             OpCode::Continue => "CONTINUE",
@@ -252,6 +273,7 @@ impl OpCode {
             OpCode::GetUpvalue(_) => "GET_UPVALUE",
             OpCode::SetUpvalue(_) => "SET_UPVALUE",
             OpCode::CloseUpvalue => "CLOSE_UPVALUE",
+            OpCode::Yield => "YIELD",
             OpCode::Class(_) => "CLASS",
             OpCode::Method(_) => "METHOD",
             OpCode::Invoke(_) => "INVOKE",
@@ -259,12 +281,18 @@ impl OpCode {
             OpCode::SetProperty(_) => "SET_PROPERTY",
             OpCode::This => "THIS",
             OpCode::Super(_) => "SUPER",
+            OpCode::Inherit => "INHERIT",
+            OpCode::InstanceOf => "INSTANCE_OF",
+            OpCode::TypeOf => "TYPE_OF",
             OpCode::Array(_) => "ARRAY",
             OpCode::Index => "INDEX",
             OpCode::SetIndex => "SET_INDEX",
             OpCode::Tuple(_) => "TUPLE",
             OpCode::TupleAccess(_) => "TUPLE_ACCESS",
             OpCode::Object(_) => "OBJECT",
+            OpCode::Range(_) => "RANGE",
+            OpCode::GetIter => "GET_ITER",
+            OpCode::IterNext(_) => "ITER_NEXT",
             OpCode::Pop => "POP",
             OpCode::PopN(_) => "POP_N",
             OpCode::Dup => "DUP",
@@ -296,6 +324,7 @@ impl OpCode {
             OpCode::Jump(_) => 3,
             OpCode::JumpIfFalse(_) => 3,
             OpCode::JumpIfTrue(_) => 3,
+            OpCode::JumpIfNil(_) => 3,
             OpCode::Loop(_) => 3,
             OpCode::Call(_) => 2,
             OpCode::Closure(_) => 2,
@@ -311,6 +340,8 @@ impl OpCode {
             OpCode::Tuple(_) => 2,
             OpCode::TupleAccess(_) => 2,
             OpCode::Object(_) => 3,
+            OpCode::Range(_) => 2,
+            OpCode::IterNext(_) => 3,
             OpCode::PopN(_) => 2,
             OpCode::DupN(_) => 2,
             OpCode::TryBegin(_, _) => 5, // 1 + 2 + 2 bytes
@@ -334,6 +365,7 @@ pub enum OpCodeCategory {
     Functions,
     Objects,
     Collections,
+    Iteration,
     Stack,
     Exceptions,
     Misc,
@@ -374,6 +406,7 @@ impl OpCode {
             OpCode::Jump(_)
             | OpCode::JumpIfFalse(_)
             | OpCode::JumpIfTrue(_)
+            | OpCode::JumpIfNil(_)
             | OpCode::Loop(_)
             | OpCode::Break
             | OpCode::Continue => OpCodeCategory::ControlFlow,
@@ -382,20 +415,26 @@ impl OpCode {
             | OpCode::Closure(_)
             | OpCode::GetUpvalue(_)
             | OpCode::SetUpvalue(_)
-            | OpCode::CloseUpvalue => OpCodeCategory::Functions,
+            | OpCode::CloseUpvalue
+            | OpCode::Yield => OpCodeCategory::Functions,
             OpCode::Class(_)
             | OpCode::Method(_)
             | OpCode::Invoke(_)
             | OpCode::GetProperty(_)
             | OpCode::SetProperty(_)
             | OpCode::This
-            | OpCode::Super(_) => OpCodeCategory::Objects,
+            | OpCode::Super(_)
+            | OpCode::Inherit
+            | OpCode::InstanceOf
+            | OpCode::TypeOf => OpCodeCategory::Objects,
             OpCode::Array(_)
             | OpCode::Index
             | OpCode::SetIndex
             | OpCode::Tuple(_)
             | OpCode::TupleAccess(_)
-            | OpCode::Object(_) => OpCodeCategory::Collections,
+            | OpCode::Object(_)
+            | OpCode::Range(_) => OpCodeCategory::Collections,
+            OpCode::GetIter | OpCode::IterNext(_) => OpCodeCategory::Iteration,
             OpCode::Pop | OpCode::PopN(_) | OpCode::Dup | OpCode::DupN(_) | OpCode::Swap => {
                 OpCodeCategory::Stack
             }
@@ -77,6 +77,63 @@ pub enum Object {
     Map(HashMap<String, Value>),
     /// Upvalue - variável capturada por closure
     Upvalue(RefCell<Upvalue>),
+    /// Gerador criado ao chamar uma `function*`
+    Generator(Generator),
+    /// Intervalo preguiçoso (`início..fim` ou `início..=fim`)
+    Range {
+        start: f64,
+        end: f64,
+        inclusive: bool,
+    },
+    /// Iterador ativo de um `for-in`
+    Iterator(IterState),
+}
+
+/// Estado de execução de um gerador
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeneratorStatus {
+    /// Criado, corpo ainda não executado
+    Created,
+    /// Pausado em um `yield`
+    Suspended,
+    /// Executando (dentro de `next()`)
+    Running,
+    /// Corpo terminou ou lançou exceção
+    Done,
+}
+
+/// Bloco try ativo de um gerador suspenso, relativo ao início do seu frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuspendedTry {
+    pub catch_ip: usize,
+    pub finally_ip: Option<usize>,
+    /// Altura da pilha quando o try começou, relativa ao `stack_start` do frame
+    pub stack_offset: usize,
+}
+
+/// Frame suspenso de uma `function*`
+///
+/// Ao encontrar `yield`, a VM move os slots do frame para `stack` e guarda o IP;
+/// `next()` empilha tudo de volta e continua a execução do ponto onde parou.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    pub function: Rc<Function>,
+    /// Locais e temporários do frame enquanto suspenso
+    pub stack: Vec<Value>,
+    pub ip: usize,
+    pub try_frames: Vec<SuspendedTry>,
+    pub status: GeneratorStatus,
+}
+
+/// Estado de um iterador criado por `GetIter`
+#[derive(Debug, Clone)]
+pub enum IterState {
+    /// Valores já materializados (arrays, tuplas, caracteres, chaves de mapa)
+    Values(Vec<Value>, usize),
+    /// Próximo número de um intervalo
+    Range { next: f64, end: f64, inclusive: bool },
+    /// Gerador retomado a cada passo
+    Generator(HeapId),
 }
 
 impl PartialEq for Object {
@@ -128,6 +185,8 @@ pub struct Function {
     pub upvalue_count: usize,
     /// Informações sobre upvalues capturados
     pub upvalue_info: Vec<UpvalueInfo>,
+    /// Declarada com `function*`: chamar cria um gerador em vez de executar o corpo
+    pub is_generator: bool,
 }
 
 /// Chunk de bytecode (importado de chunk.rs)
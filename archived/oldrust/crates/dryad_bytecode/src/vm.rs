@@ -6,9 +6,15 @@
 
 use crate::chunk::Chunk;
 use crate::opcode::OpCode;
-use crate::value::{Function, Heap, NativeFn, Object, Value};
-use std::collections::HashMap;
+use crate::value::{
+    Function, Generator, GeneratorStatus, Heap, HeapId, IterState, NativeFn, Object,
+    SuspendedTry, Value,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 /// Resultado da interpretação
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -21,6 +27,31 @@ pub enum InterpretResult {
     RuntimeError,
 }
 
+/// Contadores de cobertura coletados pela VM a partir da tabela de linhas dos chunks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VmCoverage {
+    /// Linha -> número de vezes que a execução entrou na linha
+    pub lines: BTreeMap<usize, u64>,
+    /// Linha de um salto condicional -> [vezes com condição verdadeira, vezes com condição falsa]
+    pub branches: BTreeMap<usize, [u64; 2]>,
+}
+
+/// Amostras coletadas pela VM para o profiler
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VmProfile {
+    /// Pilha de nomes de função (da base para o topo) -> número de amostras
+    pub stacks: HashMap<Vec<String>, u64>,
+    /// (função, linha) -> número de objetos alocados no heap
+    pub allocations: HashMap<(String, usize), u64>,
+}
+
+/// Estado do profiler durante a execução
+struct VmProfiler {
+    /// Ticks do amostrador ainda não consumidos
+    ticks: Arc<AtomicU64>,
+    profile: VmProfile,
+}
+
 /// Frame de chamada para funções
 #[derive(Debug)]
 struct CallFrame {
@@ -32,6 +63,8 @@ struct CallFrame {
     stack_start: usize,
     /// Upvalues capturados por esta closure
     upvalues: Vec<crate::value::HeapId>,
+    /// Gerador dono deste frame (frames retomados por `next()`)
+    generator: Option<HeapId>,
 }
 
 /// Frame para tratamento de exceções (try/catch)
@@ -54,6 +87,7 @@ impl CallFrame {
             ip: 0,
             stack_start,
             upvalues: Vec::new(),
+            generator: None,
         }
     }
 
@@ -101,6 +135,17 @@ pub struct VM {
     max_frames: usize,
     /// Frames de try/catch
     try_frames: Vec<TryFrame>,
+    /// Cobertura de código (None = desativada)
+    coverage: Option<VmCoverage>,
+    /// Última (profundidade de frame, linha) contada, para contar entradas na linha e não opcodes
+    last_covered: Option<(usize, usize)>,
+    /// Profiler por amostragem (None = desativado)
+    profiler: Option<VmProfiler>,
+    /// Frames nesta profundidade ou abaixo pertencem a quem retomou o gerador atual;
+    /// seus try frames não capturam exceções lançadas dentro do gerador
+    frame_floor: usize,
+    /// Exceção que escapou de um gerador, relançada no ponto onde ele foi retomado
+    pending_exception: Option<Value>,
 }
 
 impl VM {
@@ -114,6 +159,11 @@ impl VM {
             debug_mode: false,
             max_frames: 1000,
             try_frames: Vec::new(),
+            coverage: None,
+            last_covered: None,
+            profiler: None,
+            frame_floor: 0,
+            pending_exception: None,
         };
 
         // Adiciona funções nativas
@@ -130,6 +180,88 @@ impl VM {
         self.debug_mode = debug;
     }
 
+    /// Ativa a coleta de cobertura de linhas e branches
+    pub fn enable_coverage(&mut self) {
+        self.coverage = Some(VmCoverage::default());
+        self.last_covered = None;
+    }
+
+    /// Retorna a cobertura coletada (se ativada), desativando a coleta
+    pub fn take_coverage(&mut self) -> Option<VmCoverage> {
+        self.coverage.take()
+    }
+
+    fn record_line(&mut self) {
+        let Some(coverage) = self.coverage.as_mut() else {
+            return;
+        };
+        let Some(frame) = self.frames.last() else {
+            return;
+        };
+        let Some(line) = frame.function.get_line(frame.ip).filter(|&l| l > 0) else {
+            return;
+        };
+        let key = (self.frames.len(), line);
+        if self.last_covered != Some(key) {
+            *coverage.lines.entry(line).or_insert(0) += 1;
+            self.last_covered = Some(key);
+        }
+    }
+
+    fn record_branch(&mut self, condition: bool) {
+        let line = self.frames.last().and_then(|frame| frame.current_line());
+        if let (Some(coverage), Some(line)) = (self.coverage.as_mut(), line) {
+            let counts = coverage.branches.entry(line).or_insert([0, 0]);
+            counts[if condition { 0 } else { 1 }] += 1;
+        }
+    }
+
+    /// Ativa o profiler por amostragem. Um amostrador externo incrementa `ticks`
+    /// a cada intervalo; a VM registra a pilha atual na próxima instrução,
+    /// com peso igual ao número de ticks acumulados.
+    pub fn enable_profiling(&mut self, ticks: Arc<AtomicU64>) {
+        self.profiler = Some(VmProfiler {
+            ticks,
+            profile: VmProfile::default(),
+        });
+    }
+
+    /// Retorna as amostras coletadas (se ativado), desativando o profiler
+    pub fn take_profile(&mut self) -> Option<VmProfile> {
+        self.profiler.take().map(|profiler| profiler.profile)
+    }
+
+    fn sample_stack(&mut self) {
+        let Some(profiler) = self.profiler.as_mut() else {
+            return;
+        };
+        if profiler.ticks.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let weight = profiler.ticks.swap(0, Ordering::Relaxed);
+        let stack: Vec<String> = self
+            .frames
+            .iter()
+            .map(|frame| frame.function.name.clone())
+            .collect();
+        *profiler.profile.stacks.entry(stack).or_insert(0) += weight;
+    }
+
+    /// Aloca no heap, contando a alocação no local atual quando o profiler está ativo
+    fn allocate(&mut self, object: Object) -> crate::value::HeapId {
+        if let Some(profiler) = self.profiler.as_mut() {
+            if let Some(frame) = self.frames.last() {
+                let line = frame.current_line().unwrap_or(0);
+                *profiler
+                    .profile
+                    .allocations
+                    .entry((frame.function.name.clone(), line))
+                    .or_insert(0) += 1;
+            }
+        }
+        self.heap.allocate(object)
+    }
+
     /// Define o limite máximo de frames
     pub fn set_max_frames(&mut self, max: usize) {
         self.max_frames = max;
@@ -159,9 +291,15 @@ impl VM {
     /// Refatorado para evitar problemas de borrow checker em Rust 1.93+
     /// Usa padrão read-execute-update ao invés de passar &mut frame para execute_op
     fn run(&mut self) -> Result<(), String> {
+        self.run_until(0)
+    }
+
+    /// Executa até que restem apenas `depth` frames (0 = programa inteiro).
+    /// Usado também para retomar geradores a partir de `next()` e `for-in`.
+    fn run_until(&mut self, depth: usize) -> Result<(), String> {
         loop {
-            // Se não há frames, terminamos
-            if self.frames.is_empty() {
+            // Se não há frames acima da profundidade alvo, terminamos
+            if self.frames.len() <= depth {
                 break;
             }
 
@@ -179,6 +317,19 @@ impl VM {
                 self.debug_stack();
             }
 
+            if self.coverage.is_some() {
+                if matches!(op, OpCode::Loop(_)) {
+                    // O salto de volta não é uma execução da linha, mas a próxima volta é
+                    self.last_covered = None;
+                } else {
+                    self.record_line();
+                }
+            }
+
+            if self.profiler.is_some() {
+                self.sample_stack();
+            }
+
             // Avança IP ANTES de executar (para que jumps funcionem)
             if let Some(frame) = self.frames.last_mut() {
                 frame.ip += 1;
@@ -193,8 +344,8 @@ impl VM {
                     // Continua normalmente
                 }
                 ExecutionControl::Return => {
-                    // Se retornar do frame principal, terminamos
-                    if self.frames.is_empty() {
+                    // Se retornar do frame principal (ou do gerador retomado), terminamos
+                    if self.frames.len() <= depth {
                         break;
                     }
                 }
@@ -242,36 +393,45 @@ impl VM {
             OpCode::Add => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.add(&b)?);
+                let result = self.binary_with_overload("+", a, b, Value::add)?;
+                self.push(result);
             }
 
             OpCode::Subtract => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.subtract(&b)?);
+                let result = self.binary_with_overload("-", a, b, Value::subtract)?;
+                self.push(result);
             }
 
             OpCode::Multiply => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.multiply(&b)?);
+                let result = self.binary_with_overload("*", a, b, Value::multiply)?;
+                self.push(result);
             }
 
             OpCode::Divide => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.divide(&b)?);
+                let result = self.binary_with_overload("/", a, b, Value::divide)?;
+                self.push(result);
             }
 
             OpCode::Modulo => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.modulo(&b)?);
+                let result = self.binary_with_overload("%", a, b, Value::modulo)?;
+                self.push(result);
             }
 
             OpCode::Negate => {
                 let a = self.pop()?;
-                self.push(a.negate()?);
+                let result = match self.find_method(&a, "operator neg") {
+                    Some(method) => self.call_method_sync(a, method, Vec::new())?,
+                    None => a.negate()?,
+                };
+                self.push(result);
             }
 
             // ============================================
@@ -280,31 +440,37 @@ impl VM {
             OpCode::Equal => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(Value::Boolean(a == b));
+                let result =
+                    self.binary_with_overload("==", a, b, |a, b| Ok(Value::Boolean(a == b)))?;
+                self.push(result);
             }
 
             OpCode::Greater => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.greater(&b)?);
+                let result = self.binary_with_overload(">", a, b, Value::greater)?;
+                self.push(result);
             }
 
             OpCode::Less => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.less(&b)?);
+                let result = self.binary_with_overload("<", a, b, Value::less)?;
+                self.push(result);
             }
 
             OpCode::GreaterEqual => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.greater_equal(&b)?);
+                let result = self.binary_with_overload(">=", a, b, Value::greater_equal)?;
+                self.push(result);
             }
 
             OpCode::LessEqual => {
                 let b = self.pop()?;
                 let a = self.pop()?;
-                self.push(a.less_equal(&b)?);
+                let result = self.binary_with_overload("<=", a, b, Value::less_equal)?;
+                self.push(result);
             }
 
             // ============================================
@@ -427,7 +593,9 @@ impl VM {
             }
 
             OpCode::JumpIfFalse(offset) => {
-                if !self.peek(0)?.is_truthy() {
+                let condition = self.peek(0)?.is_truthy();
+                self.record_branch(condition);
+                if !condition {
                     let new_ip =
                         self.current_frame_ip().ok_or("Sem frame atual")? + *offset as usize;
                     self.set_frame_ip(new_ip);
@@ -435,7 +603,19 @@ impl VM {
             }
 
             OpCode::JumpIfTrue(offset) => {
-                if self.peek(0)?.is_truthy() {
+                let condition = self.peek(0)?.is_truthy();
+                self.record_branch(condition);
+                if condition {
+                    let new_ip =
+                        self.current_frame_ip().ok_or("Sem frame atual")? + *offset as usize;
+                    self.set_frame_ip(new_ip);
+                }
+            }
+
+            OpCode::JumpIfNil(offset) => {
+                let condition = matches!(self.peek(0)?, Value::Nil);
+                self.record_branch(condition);
+                if condition {
                     let new_ip =
                         self.current_frame_ip().ok_or("Sem frame atual")? + *offset as usize;
                     self.set_frame_ip(new_ip);
@@ -462,12 +642,24 @@ impl VM {
                 let callee = self.peek(*arg_count as usize)?;
 
                 match callee {
+                    Value::Function(function) if function.is_generator => {
+                        self.create_generator(Rc::clone(function), *arg_count)?;
+                    }
                     Value::Function(function) => {
                         self.call_function(Rc::clone(function), *arg_count)?;
                     }
                     Value::NativeFunction(native_fn) => {
                         self.call_native(*native_fn, *arg_count)?;
                     }
+                    Value::Object(class_id)
+                        if self
+                            .heap
+                            .get(*class_id)
+                            .is_some_and(|obj| matches!(&*obj.borrow(), Object::Class { .. })) =>
+                    {
+                        let class_id = *class_id;
+                        self.instantiate(class_id, *arg_count)?;
+                    }
                     _ => {
                         return Err(format!("Não é possível chamar '{}'", callee.type_name()));
                     }
@@ -480,6 +672,12 @@ impl VM {
                 while self.stack.len() > frame.stack_start {
                     self.stack.pop();
                 }
+                if let Some(generator_id) = frame.generator {
+                    // `return` (explícito ou implícito) encerra o gerador
+                    let depth = self.frames.len();
+                    self.try_frames.retain(|t| t.frame_depth <= depth);
+                    self.finish_generator(generator_id);
+                }
                 if !self.frames.is_empty() {
                     self.pop()?;
                 }
@@ -487,6 +685,45 @@ impl VM {
                 return Ok(ExecutionControl::Return);
             }
 
+            OpCode::Yield => {
+                let value = self.pop()?;
+                let generator_id = self
+                    .frames
+                    .last()
+                    .and_then(|frame| frame.generator)
+                    .ok_or("'yield' só pode ser usado dentro de uma function*")?;
+                let frame = self.frames.pop().ok_or("Não há frame para suspender")?;
+                let depth = self.frames.len();
+
+                // Try frames abertos no gerador são guardados relativos ao frame
+                let mut try_frames = Vec::new();
+                while self.try_frames.last().is_some_and(|t| t.frame_depth > depth) {
+                    let try_frame = self.try_frames.pop().unwrap();
+                    try_frames.push(SuspendedTry {
+                        catch_ip: try_frame.catch_ip,
+                        finally_ip: try_frame.finally_ip,
+                        stack_offset: try_frame.stack_start - frame.stack_start,
+                    });
+                }
+                try_frames.reverse();
+
+                let saved = self.stack.split_off(frame.stack_start);
+                // Remove o slot do chamador empilhado por resume_generator
+                self.pop()?;
+
+                if let Some(obj) = self.heap.get(generator_id) {
+                    if let Object::Generator(generator) = &mut *obj.borrow_mut() {
+                        generator.stack = saved;
+                        generator.ip = frame.ip;
+                        generator.try_frames = try_frames;
+                        generator.status = GeneratorStatus::Suspended;
+                    }
+                }
+
+                self.push(value);
+                return Ok(ExecutionControl::Return);
+            }
+
             OpCode::Closure(upvalue_count) => {
                 // A função já está no topo da pilha (colocada por Constant)
                 let function_value = self.peek(0)?.clone();
@@ -648,7 +885,7 @@ impl VM {
                 };
 
                 // Cria a classe no heap
-                let class_id = self.heap.allocate(Object::Class {
+                let class_id = self.allocate(Object::Class {
                     name: class_name,
                     methods: std::collections::HashMap::new(),
                     superclass: None,
@@ -681,6 +918,29 @@ impl VM {
                 }
             }
 
+            OpCode::Inherit => {
+                // Superclasse no topo, subclasse logo abaixo
+                let superclass = self.pop()?;
+                let inherited = match self.heap_object(&superclass) {
+                    Some(object) => match &*object.borrow() {
+                        Object::Class { methods, .. } => methods.clone(),
+                        _ => return Err("Superclasse deve ser uma classe".to_string()),
+                    },
+                    None => return Err("Superclasse deve ser uma classe".to_string()),
+                };
+                let Value::Object(superclass_id) = superclass else {
+                    return Err("Superclasse deve ser uma classe".to_string());
+                };
+
+                if let Some(class) = self.heap_object(self.peek(0)?) {
+                    if let Object::Class { methods, superclass, .. } = &mut *class.borrow_mut() {
+                        // Os métodos da subclasse são definidos depois e sobrescrevem estes
+                        methods.extend(inherited);
+                        *superclass = Some(superclass_id);
+                    }
+                }
+            }
+
             OpCode::Invoke(arg_count) => {
                 // Pega o método da instância
                 let method_name_value = self.pop()?;
@@ -692,6 +952,18 @@ impl VM {
                 // Pega a instância
                 let instance = self.peek(*arg_count as usize)?;
 
+                if let Value::Object(generator_id) = instance {
+                    let generator_id = *generator_id;
+                    let is_generator = self
+                        .heap
+                        .get(generator_id)
+                        .is_some_and(|obj| matches!(&*obj.borrow(), Object::Generator(_)));
+                    if is_generator {
+                        return self.invoke_generator(generator_id, &method_name, *arg_count);
+                    }
+                }
+
+                let instance = self.peek(*arg_count as usize)?;
                 if let Value::Object(instance_id) = instance {
                     if let Some(obj) = self.heap.get(*instance_id) {
                         let obj_ref = obj.borrow();
@@ -718,7 +990,7 @@ impl VM {
                                             drop(obj_ref);
 
                                             if let Some(method) = method {
-                                                self.call_function(method, *arg_count)?;
+                                                self.call_method(method, *arg_count)?;
                                             } else {
                                                 return Err(format!(
                                                     "Método '{}' não encontrado na classe '{}'",
@@ -852,7 +1124,7 @@ impl VM {
 
                                     if let Some(method) = method_found {
                                         let closure_id =
-                                            self.heap.allocate(Object::Closure(method, vec![]));
+                                            self.allocate(Object::Closure(method, vec![]));
                                         self.push(Value::Object(closure_id));
                                     } else {
                                         self.push(Value::Nil);
@@ -885,81 +1157,27 @@ impl VM {
                 let value = self.pop()?;
                 let object = self.pop()?;
 
-                if let Value::Object(object_id) = object {
-                    if let Some(obj) = self.heap.get(object_id) {
-                        match &*obj.borrow() {
-                            Object::Instance { class_name, .. } => {
-                                let setter_name = format!("__set_{}", prop_name);
-                                let mut setter_found: Option<Rc<Function>> = None;
-                                let mut current_class_name = Some(class_name.clone());
-
-                                while let Some(class_name_str) = current_class_name {
-                                    if let Some(Value::Object(class_id)) =
-                                        self.globals.get(&class_name_str)
-                                    {
-                                        if let Some(class_obj) = self.heap.get(*class_id) {
-                                            let class_ref = class_obj.borrow();
-                                            if let Object::Class {
-                                                methods,
-                                                superclass,
-                                                ..
-                                            } = &*class_ref
-                                            {
-                                                if let Some(setter) = methods.get(&setter_name) {
-                                                    setter_found = Some(Rc::clone(setter));
-                                                    break;
-                                                }
-                                                current_class_name =
-                                                    superclass.as_ref().and_then(|super_id| {
-                                                        self.heap.get(*super_id).and_then(
-                                                            |super_obj| {
-                                                                let super_ref = super_obj.borrow();
-                                                                if let Object::Class {
-                                                                    name, ..
-                                                                } = &*super_ref
-                                                                {
-                                                                    Some(name.clone())
-                                                                } else {
-                                                                    None
-                                                                }
-                                                            },
-                                                        )
-                                                    });
-                                            } else {
-                                                break;
-                                            }
-                                        } else {
-                                            break;
-                                        }
-                                    } else {
-                                        break;
-                                    }
-                                }
+                let Value::Object(object_id) = object else {
+                    return Err("Apenas objetos têm propriedades".to_string());
+                };
+                let obj = self
+                    .heap
+                    .get(object_id)
+                    .ok_or("Objeto inválido no heap")?;
 
-                                if let Some(setter) = setter_found {
-                                    self.push(Value::Object(object_id));
-                                    self.push(value.clone());
-                                    self.call_function(setter, 1)?;
-                                } else {
-                                    let mut obj_ref = obj.borrow_mut();
-                                    if let Object::Instance { fields, .. } = &mut *obj_ref {
-                                        fields.insert(prop_name, value.clone());
-                                    }
-                                }
-                            }
-                            Object::Map(map) => {
-                                let mut obj_ref = obj.borrow_mut();
-                                if let Object::Map(map_mut) = &mut *obj_ref {
-                                    map_mut.insert(prop_name, value.clone());
-                                }
-                            }
-                            _ => return Err("Objeto não suporta propriedades".to_string()),
+                if let Some(setter) = self.find_method(&object, &format!("__set_{}", prop_name)) {
+                    // O retorno do setter é descartado em favor do valor atribuído
+                    self.call_method_sync(object, setter, vec![value.clone()])?;
+                } else {
+                    match &mut *obj.borrow_mut() {
+                        Object::Instance { fields, .. } => {
+                            fields.insert(prop_name, value.clone());
                         }
-                    } else {
-                        return Err("Objeto inválido no heap".to_string());
+                        Object::Map(map) => {
+                            map.insert(prop_name, value.clone());
+                        }
+                        _ => return Err("Objeto não suporta propriedades".to_string()),
                     }
-                } else {
-                    return Err("Apenas objetos têm propriedades".to_string());
                 }
 
                 self.push(value);
@@ -994,6 +1212,22 @@ impl VM {
                 }
             }
 
+            OpCode::InstanceOf => {
+                let class = self.pop()?;
+                let value = self.pop()?;
+                let class_name = self
+                    .class_name(&class)
+                    .ok_or("O lado direito de 'instanceof' deve ser uma classe")?;
+                let result = self.is_instance_of(&value, &class_name);
+                self.push(Value::Boolean(result));
+            }
+
+            OpCode::TypeOf => {
+                let value = self.pop()?;
+                let name = self.type_of(&value);
+                self.push(Value::String(name.to_string()));
+            }
+
             // ============================================
             // Coleções
             // ============================================
@@ -1006,7 +1240,7 @@ impl VM {
                 elements.reverse(); // Ordem correta
 
                 // Aloca no heap
-                let array_id = self.heap.allocate(Object::Array(elements));
+                let array_id = self.allocate(Object::Array(elements));
                 self.push(Value::Object(array_id));
             }
 
@@ -1014,6 +1248,12 @@ impl VM {
                 let index_val = self.pop()?;
                 let collection = self.pop()?;
 
+                if let Some(method) = self.find_method(&collection, "operator[]") {
+                    let result = self.call_method_sync(collection, method, vec![index_val])?;
+                    self.push(result);
+                    return Ok(ExecutionControl::Continue);
+                }
+
                 let index = match index_val {
                     Value::Number(n) => n as usize,
                     _ => return Err("Índice deve ser um número".to_string()),
@@ -1070,6 +1310,12 @@ impl VM {
                 let index_val = self.pop()?;
                 let collection = self.pop()?;
 
+                if let Some(method) = self.find_method(&collection, "operator[]=") {
+                    self.call_method_sync(collection, method, vec![index_val, value.clone()])?;
+                    self.push(value);
+                    return Ok(ExecutionControl::Continue);
+                }
+
                 let index = match index_val {
                     Value::Number(n) => n as usize,
                     _ => return Err("Índice deve ser um número".to_string()),
@@ -1131,7 +1377,7 @@ impl VM {
                 elements.reverse(); // Ordem correta
 
                 // Aloca no heap
-                let tuple_id = self.heap.allocate(Object::Tuple(elements));
+                let tuple_id = self.allocate(Object::Tuple(elements));
                 self.push(Value::Object(tuple_id));
             }
 
@@ -1153,10 +1399,100 @@ impl VM {
                 }
 
                 // Aloca no heap
-                let obj_id = self.heap.allocate(Object::Map(properties));
+                let obj_id = self.allocate(Object::Map(properties));
                 self.push(Value::Object(obj_id));
             }
 
+            OpCode::Range(inclusive) => {
+                let end = self.pop()?;
+                let start = self.pop()?;
+                let (start, end) = match (&start, &end) {
+                    (Value::Number(start), Value::Number(end)) => (*start, *end),
+                    _ => {
+                        return Err(format!(
+                            "Limites de intervalo devem ser números: {} .. {}",
+                            start.type_name(),
+                            end.type_name()
+                        ))
+                    }
+                };
+                let range_id = self.allocate(Object::Range {
+                    start,
+                    end,
+                    inclusive: *inclusive,
+                });
+                self.push(Value::Object(range_id));
+            }
+
+            // ============================================
+            // Iteração
+            // ============================================
+            OpCode::GetIter => {
+                let iterable = self.pop()?;
+                let state = self.make_iterator(iterable)?;
+                let iterator_id = self.allocate(Object::Iterator(state));
+                self.push(Value::Object(iterator_id));
+            }
+
+            OpCode::IterNext(offset) => {
+                let iterator = self.pop()?;
+                let iterator_id = match iterator {
+                    Value::Object(id) => id,
+                    _ => return Err("ITER_NEXT requer um iterador".to_string()),
+                };
+                let obj = self
+                    .heap
+                    .get(iterator_id)
+                    .ok_or("Objeto inválido no heap")?;
+
+                // (próximo valor, gerador a retomar)
+                let (next, generator) = match &mut *obj.borrow_mut() {
+                    Object::Iterator(IterState::Values(items, index)) => {
+                        let next = items.get(*index).cloned();
+                        if next.is_some() {
+                            *index += 1;
+                        }
+                        (next, None)
+                    }
+                    Object::Iterator(IterState::Range {
+                        next,
+                        end,
+                        inclusive,
+                    }) => {
+                        let has_next = if *inclusive { *next <= *end } else { *next < *end };
+                        if has_next {
+                            let value = *next;
+                            *next += 1.0;
+                            (Some(Value::Number(value)), None)
+                        } else {
+                            (None, None)
+                        }
+                    }
+                    Object::Iterator(IterState::Generator(generator_id)) => {
+                        (None, Some(*generator_id))
+                    }
+                    _ => return Err("ITER_NEXT requer um iterador".to_string()),
+                };
+
+                let next = match generator {
+                    Some(generator_id) => match self.resume_generator(generator_id, Value::Nil)? {
+                        Ok((_, true)) => None,
+                        Ok((value, false)) => Some(value),
+                        Err(exception) => return self.handle_exception(exception),
+                    },
+                    None => next,
+                };
+
+                match next {
+                    Some(value) => self.push(value),
+                    None => {
+                        if let Some(frame) = self.frames.last_mut() {
+                            frame.jump(*offset);
+                        }
+                    }
+                }
+            }
+
             OpCode::TupleAccess(idx) => {
                 let tuple = self.pop()?;
                 let index = *idx as usize;
@@ -1229,11 +1565,13 @@ impl VM {
             // ============================================
             OpCode::Print => {
                 let value = self.pop()?;
+                let value = self.display_value(value)?;
                 print!("{}", value);
             }
 
             OpCode::PrintLn => {
                 let value = self.pop()?;
+                let value = self.display_value(value)?;
                 println!("{}", value);
             }
 
@@ -1284,7 +1622,7 @@ impl VM {
                 fields.insert("message".to_string(), Value::String(msg));
                 fields.insert("type".to_string(), Value::String("Exception".to_string()));
 
-                let exception_id = self.heap.allocate(Object::Instance {
+                let exception_id = self.allocate(Object::Instance {
                     class_name: "Exception".to_string(),
                     fields,
                 });
@@ -1389,6 +1727,244 @@ impl VM {
         Ok(())
     }
 
+    /// Chama um método: a pilha tem `[receptor, args...]` e o receptor é
+    /// duplicado para ocupar o slot 0 (`this`) do novo frame
+    fn call_method(&mut self, method: Rc<Function>, arg_count: u8) -> Result<(), String> {
+        if method.arity != arg_count as usize {
+            return Err(format!(
+                "Método {} espera {} argumentos, mas recebeu {}",
+                method.name, method.arity, arg_count
+            ));
+        }
+        if self.frames.len() >= self.max_frames {
+            return Err("Stack overflow: muitas chamadas recursivas".to_string());
+        }
+
+        let receiver_slot = self.stack.len() - arg_count as usize - 1;
+        let receiver = self.stack[receiver_slot].clone();
+        self.stack.insert(receiver_slot + 1, receiver);
+        self.frames
+            .push(CallFrame::new(method.chunk.clone(), receiver_slot + 1));
+        Ok(())
+    }
+
+    /// Executa um método até o retorno e devolve o resultado (usado por
+    /// construtores, operadores sobrecarregados e `toString()`)
+    fn call_method_sync(
+        &mut self,
+        receiver: Value,
+        method: Rc<Function>,
+        args: Vec<Value>,
+    ) -> Result<Value, String> {
+        let depth = self.frames.len();
+        let arg_count = args.len() as u8;
+        self.push(receiver);
+        for arg in args {
+            self.push(arg);
+        }
+        self.call_method(method, arg_count)?;
+        self.run_until(depth)?;
+        self.pop()
+    }
+
+    /// Procura um método na classe de uma instância, subindo pelas superclasses
+    fn find_method(&self, value: &Value, name: &str) -> Option<Rc<Function>> {
+        let Value::Object(id) = value else {
+            return None;
+        };
+        let mut class_name = match &*self.heap.get(*id)?.borrow() {
+            Object::Instance { class_name, .. } => class_name.clone(),
+            _ => return None,
+        };
+        loop {
+            let Some(Value::Object(class_id)) = self.globals.get(&class_name) else {
+                return None;
+            };
+            let class_obj = self.heap.get(*class_id)?;
+            let class_ref = class_obj.borrow();
+            let Object::Class {
+                methods,
+                superclass,
+                ..
+            } = &*class_ref
+            else {
+                return None;
+            };
+            if let Some(method) = methods.get(name) {
+                return Some(Rc::clone(method));
+            }
+            let super_obj = self.heap.get((*superclass)?)?;
+            class_name = match &*super_obj.borrow() {
+                Object::Class { name, .. } => name.clone(),
+                _ => return None,
+            };
+        }
+    }
+
+    /// `valor instanceof Classe`: a classe da instância ou uma de suas ancestrais
+    fn is_instance_of(&self, value: &Value, class_name: &str) -> bool {
+        let Value::Object(id) = value else {
+            return false;
+        };
+        let Some(object) = self.heap.get(*id) else {
+            return false;
+        };
+        let mut current = match &*object.borrow() {
+            Object::Instance { class_name, .. } => class_name.clone(),
+            _ => return false,
+        };
+        let mut visited = std::collections::HashSet::new();
+        while visited.insert(current.clone()) {
+            if current == class_name {
+                return true;
+            }
+            let superclass = match self.globals.get(&current).and_then(|class| self.heap_object(class)) {
+                Some(class) => match &*class.borrow() {
+                    Object::Class { superclass: Some(superclass), .. } => Value::Object(*superclass),
+                    _ => return false,
+                },
+                None => return false,
+            };
+            match self.class_name(&superclass) {
+                Some(name) => current = name,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    fn heap_object(&self, value: &Value) -> Option<Rc<RefCell<Object>>> {
+        match value {
+            Value::Object(id) => self.heap.get(*id),
+            _ => None,
+        }
+    }
+
+    /// Nome da classe quando o valor é uma classe
+    fn class_name(&self, value: &Value) -> Option<String> {
+        match &*self.heap_object(value)?.borrow() {
+            Object::Class { name, .. } => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Nome do tipo como retornado por `typeof`, com os mesmos nomes do interpretador
+    fn type_of(&self, value: &Value) -> &'static str {
+        match value {
+            Value::Nil => "null",
+            Value::Boolean(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Function(_) | Value::NativeFunction(_) => "function",
+            Value::Object(_) => match self.heap_object(value) {
+                Some(object) => match &*object.borrow() {
+                    Object::Instance { .. } => "instance",
+                    Object::Class { .. } => "class",
+                    Object::Array(_) => "array",
+                    Object::Tuple(_) => "tuple",
+                    Object::Closure(..) => "function",
+                    Object::Generator(_) => "generator",
+                    Object::Range { .. } => "range",
+                    _ => "object",
+                },
+                None => "object",
+            },
+        }
+    }
+
+    /// `new Classe(args)`: cria a instância e executa `init`, se houver
+    fn instantiate(&mut self, class_id: HeapId, arg_count: u8) -> Result<(), String> {
+        let mut args = Vec::with_capacity(arg_count as usize);
+        for _ in 0..arg_count {
+            args.push(self.pop()?);
+        }
+        args.reverse();
+        // Remove a classe da pilha
+        self.pop()?;
+
+        let class_name = match self.heap.get(class_id).map(|obj| obj.borrow().clone()) {
+            Some(Object::Class { name, .. }) => name,
+            _ => return Err("Classe inválida no heap".to_string()),
+        };
+        let instance_id = self.allocate(Object::Instance {
+            class_name,
+            fields: HashMap::new(),
+        });
+        let instance = Value::Object(instance_id);
+
+        match self.find_method(&instance, "init") {
+            Some(init) => {
+                self.call_method_sync(instance.clone(), init, args)?;
+            }
+            None if !args.is_empty() => {
+                return Err(format!(
+                    "Classe sem 'init' não recebe argumentos (recebeu {})",
+                    args.len()
+                ));
+            }
+            None => {}
+        }
+
+        self.push(instance);
+        Ok(())
+    }
+
+    /// Operador binário sobrecarregado pela classe do operando esquerdo
+    /// (`operator +(other)`, ...); `>`, `<=` e `>=` são derivados de
+    /// `operator <` e `operator ==` quando não declarados. Sem sobrecarga,
+    /// aplica a operação primitiva.
+    fn binary_with_overload(
+        &mut self,
+        operator: &str,
+        a: Value,
+        b: Value,
+        primitive: fn(&Value, &Value) -> Result<Value, String>,
+    ) -> Result<Value, String> {
+        if let Some(method) = self.find_method(&a, &format!("operator{}", operator)) {
+            return self.call_method_sync(a, method, vec![b]);
+        }
+
+        if operator == "+" {
+            // Concatenação com string usa o toString() da instância
+            if let (Value::String(_), _) | (_, Value::String(_)) = (&a, &b) {
+                let a = self.display_value(a)?;
+                let b = self.display_value(b)?;
+                return primitive(&a, &b);
+            }
+        }
+
+        if matches!(operator, ">" | "<=" | ">=") {
+            if let Some(less) = self.find_method(&a, "operator<") {
+                let less = self
+                    .call_method_sync(a.clone(), less, vec![b.clone()])?
+                    .is_truthy();
+                let equal = match (operator, self.find_method(&a, "operator==")) {
+                    (">=", _) => false,
+                    (_, Some(equal)) => self.call_method_sync(a, equal, vec![b])?.is_truthy(),
+                    (_, None) => a == b,
+                };
+                return Ok(Value::Boolean(match operator {
+                    ">" => !less && !equal,
+                    "<=" => less || equal,
+                    _ => !less,
+                }));
+            }
+        }
+
+        primitive(&a, &b)
+    }
+
+    /// Converte uma instância em texto pelo `toString()` da classe, se houver
+    fn display_value(&mut self, value: Value) -> Result<Value, String> {
+        match self.find_method(&value, "toString") {
+            Some(method) => match self.call_method_sync(value, method, Vec::new())? {
+                Value::String(text) => Ok(Value::String(text)),
+                other => Ok(Value::String(other.to_string())),
+            },
+            None => Ok(value),
+        }
+    }
+
     /// Chama uma função nativa
     fn call_native(&mut self, native_fn: NativeFn, arg_count: u8) -> Result<(), String> {
         // Pega os argumentos da pilha
@@ -1401,6 +1977,12 @@ impl VM {
         // Remove a função da pilha
         self.pop()?;
 
+        // Instâncias com toString() chegam às nativas (ex.: print) como texto
+        let args = args
+            .into_iter()
+            .map(|arg| self.display_value(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+
         // Chama a função nativa
         let result = native_fn(&args)?;
 
@@ -1410,14 +1992,237 @@ impl VM {
         Ok(())
     }
 
+    // ============================================
+    // Geradores e Iteradores
+    // ============================================
+
+    /// Chamar uma `function*` não executa o corpo: os argumentos viram
+    /// os slots iniciais de um gerador suspenso
+    fn create_generator(&mut self, function: Rc<Function>, arg_count: u8) -> Result<(), String> {
+        if function.arity != arg_count as usize {
+            return Err(format!(
+                "Função {} espera {} argumentos, mas recebeu {}",
+                function.name, function.arity, arg_count
+            ));
+        }
+
+        let args = self.stack.split_off(self.stack.len() - arg_count as usize);
+        // Remove a função da pilha
+        self.pop()?;
+
+        let generator_id = self.allocate(Object::Generator(Generator {
+            function,
+            stack: args,
+            ip: 0,
+            try_frames: Vec::new(),
+            status: GeneratorStatus::Created,
+        }));
+        self.push(Value::Object(generator_id));
+        Ok(())
+    }
+
+    /// Retoma um gerador até o próximo `yield` ou o fim do corpo, retornando
+    /// `(valor, done)`. `Ok(Err(exceção))` indica uma exceção que escapou do gerador.
+    fn resume_generator(
+        &mut self,
+        generator_id: HeapId,
+        sent: Value,
+    ) -> Result<Result<(Value, bool), Value>, String> {
+        let obj = self
+            .heap
+            .get(generator_id)
+            .ok_or("Gerador inválido no heap")?;
+        let (function, saved, ip, try_frames, status) = {
+            let mut obj_ref = obj.borrow_mut();
+            let generator = match &mut *obj_ref {
+                Object::Generator(generator) => generator,
+                _ => return Err("Objeto não é um gerador".to_string()),
+            };
+            match generator.status {
+                GeneratorStatus::Done => return Ok(Ok((Value::Nil, true))),
+                GeneratorStatus::Running => {
+                    return Err(format!(
+                        "Gerador '{}' já está em execução",
+                        generator.function.name
+                    ))
+                }
+                GeneratorStatus::Created | GeneratorStatus::Suspended => {}
+            }
+            let status = generator.status;
+            generator.status = GeneratorStatus::Running;
+            (
+                Rc::clone(&generator.function),
+                std::mem::take(&mut generator.stack),
+                generator.ip,
+                std::mem::take(&mut generator.try_frames),
+                status,
+            )
+        };
+
+        if self.frames.len() >= self.max_frames {
+            return Err("Stack overflow: muitas chamadas recursivas".to_string());
+        }
+
+        let depth = self.frames.len();
+        let base = self.stack.len();
+
+        // Slot do chamador, removido pelo Return/Yield do gerador
+        self.push(Value::Nil);
+        let stack_start = self.stack.len();
+        self.stack.extend(saved);
+        if status == GeneratorStatus::Suspended {
+            // Resultado da expressão `yield` que suspendeu o gerador
+            self.push(sent);
+        }
+        for try_frame in try_frames {
+            self.try_frames.push(TryFrame {
+                catch_ip: try_frame.catch_ip,
+                finally_ip: try_frame.finally_ip,
+                stack_start: stack_start + try_frame.stack_offset,
+                frame_depth: depth + 1,
+            });
+        }
+
+        let mut frame = CallFrame::new(function.chunk.clone(), stack_start);
+        frame.ip = ip;
+        frame.generator = Some(generator_id);
+        self.frames.push(frame);
+
+        let floor = std::mem::replace(&mut self.frame_floor, depth);
+        let result = self.run_until(depth);
+        self.frame_floor = floor;
+
+        match result {
+            Ok(()) => {
+                let value = self.pop()?;
+                let done = matches!(
+                    &*obj.borrow(),
+                    Object::Generator(Generator {
+                        status: GeneratorStatus::Done,
+                        ..
+                    })
+                );
+                Ok(Ok((value, done)))
+            }
+            Err(err) => {
+                // Exceção ou erro escapou do corpo: o gerador termina
+                self.frames.truncate(depth);
+                self.stack.truncate(base);
+                self.try_frames.retain(|t| t.frame_depth <= depth);
+                self.finish_generator(generator_id);
+                match self.pending_exception.take() {
+                    Some(exception) => Ok(Err(exception)),
+                    None => Err(err),
+                }
+            }
+        }
+    }
+
+    /// Marca um gerador como encerrado, liberando os slots guardados
+    fn finish_generator(&mut self, generator_id: HeapId) {
+        if let Some(obj) = self.heap.get(generator_id) {
+            if let Object::Generator(generator) = &mut *obj.borrow_mut() {
+                generator.status = GeneratorStatus::Done;
+                generator.stack.clear();
+                generator.try_frames.clear();
+            }
+        }
+    }
+
+    /// Métodos de geradores: `next(v?)` retorna `{ value, done }`
+    fn invoke_generator(
+        &mut self,
+        generator_id: HeapId,
+        method_name: &str,
+        arg_count: u8,
+    ) -> Result<ExecutionControl, String> {
+        if method_name != "next" {
+            return Err(format!("Método '{}' não existe em geradores", method_name));
+        }
+        if arg_count > 1 {
+            return Err(format!(
+                "next() espera no máximo 1 argumento, mas recebeu {}",
+                arg_count
+            ));
+        }
+
+        let sent = if arg_count == 1 { self.pop()? } else { Value::Nil };
+        // Remove o próprio gerador
+        self.pop()?;
+
+        match self.resume_generator(generator_id, sent)? {
+            Ok((value, done)) => {
+                let mut fields = HashMap::new();
+                fields.insert("value".to_string(), value);
+                fields.insert("done".to_string(), Value::Boolean(done));
+                let result_id = self.allocate(Object::Map(fields));
+                self.push(Value::Object(result_id));
+                Ok(ExecutionControl::Continue)
+            }
+            Err(exception) => self.handle_exception(exception),
+        }
+    }
+
+    /// Cria o estado de iteração de um `for-in`
+    fn make_iterator(&self, iterable: Value) -> Result<IterState, String> {
+        let id = match iterable {
+            Value::String(s) => {
+                let chars = s.chars().map(|c| Value::String(c.to_string())).collect();
+                return Ok(IterState::Values(chars, 0));
+            }
+            Value::Object(id) => id,
+            other => {
+                return Err(format!(
+                    "Valor do tipo '{}' não é iterável",
+                    other.type_name()
+                ))
+            }
+        };
+
+        let obj = self.heap.get(id).ok_or("Objeto inválido no heap")?;
+        let obj_ref = obj.borrow();
+        match &*obj_ref {
+            Object::Array(items) | Object::Tuple(items) => Ok(IterState::Values(items.clone(), 0)),
+            Object::Map(map) => {
+                // Objetos literais iteram suas chaves em ordem
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                let keys = keys.into_iter().map(|k| Value::String(k.clone())).collect();
+                Ok(IterState::Values(keys, 0))
+            }
+            Object::Range {
+                start,
+                end,
+                inclusive,
+            } => Ok(IterState::Range {
+                next: *start,
+                end: *end,
+                inclusive: *inclusive,
+            }),
+            Object::Generator(_) => Ok(IterState::Generator(id)),
+            Object::Iterator(state) => Ok(state.clone()),
+            Object::Instance { class_name, .. } => Err(format!(
+                "Instância de '{}' não é iterável pela VM",
+                class_name
+            )),
+            _ => Err("Valor não é iterável".to_string()),
+        }
+    }
+
     // ============================================
     // Tratamento de Exceções
     // ============================================
 
     /// Trata uma exceção lançada
     fn handle_exception(&mut self, exception: Value) -> Result<ExecutionControl, String> {
-        // Procura um try frame que possa lidar com a exceção
-        while let Some(try_frame) = self.try_frames.pop() {
+        // Procura um try frame que possa lidar com a exceção. Dentro de um gerador
+        // retomado, apenas os try frames do próprio gerador são considerados.
+        while self
+            .try_frames
+            .last()
+            .is_some_and(|t| t.frame_depth > self.frame_floor)
+        {
+            let try_frame = self.try_frames.pop().unwrap();
             // Restaura o estado da pilha
             while self.stack.len() > try_frame.stack_start {
                 self.stack.pop();
@@ -1437,7 +2242,10 @@ impl VM {
             return Ok(ExecutionControl::Continue);
         }
 
-        // Se não encontrou handler, propaga o erro
+        // Se não encontrou handler, propaga o erro (relançado onde o gerador foi retomado)
+        if self.frame_floor > 0 {
+            self.pending_exception = Some(exception.clone());
+        }
         Err(format!("Exceção não capturada: {:?}", exception))
     }
 
@@ -0,0 +1,75 @@
+// crates/dryad_bytecode/tests/codegen_tests.rs
+//! Testes do código gerado pelo compilador para statements e chamadas
+
+use dryad_bytecode::{Compiler, InterpretResult, Value, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+
+fn run(source: &str) -> (VM, InterpretResult) {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let mut vm = VM::new();
+    let result = vm.interpret(chunk);
+    (vm, result)
+}
+
+fn global(vm: &VM, name: &str) -> Value {
+    vm.get_global(name).cloned().unwrap()
+}
+
+#[test]
+fn test_assignment_statement_discards_its_value() {
+    // Sem o `Pop` após o Set*, cada atribuição deixava o valor na pilha e
+    // deslocava os slots das variáveis locais declaradas depois dela
+    let (vm, result) = run(r#"
+        function f() {
+            let a = 1;
+            a = 2;
+            let b = 3;
+            return a * 10 + b;
+        }
+        let total = 0;
+        let i = 0;
+        while (i < 50) {
+            total = total + 2;
+            i = i + 1;
+        }
+        let r = f();
+    "#);
+
+    assert_eq!(result, InterpretResult::Ok);
+    assert_eq!(global(&vm, "r"), Value::Number(23.0));
+    assert_eq!(global(&vm, "total"), Value::Number(100.0));
+    // A pilha termina como a de um programa sem atribuições
+    let (empty, _) = run("let total = 0;");
+    assert_eq!(vm.stack_size(), empty.stack_size());
+}
+
+#[test]
+fn test_method_call_pushes_method_name() {
+    // `Invoke` desempilha o nome do método antes dos argumentos
+    let (vm, result) = run(r#"
+        class Calculadora {
+            function somar(a, b) {
+                return a + b;
+            }
+        }
+        let c = new Calculadora();
+        let r = c.somar(1, 2);
+        let s = c?.somar(3, 4);
+    "#);
+
+    assert_eq!(result, InterpretResult::Ok);
+    assert_eq!(global(&vm, "r"), Value::Number(3.0));
+    assert_eq!(global(&vm, "s"), Value::Number(7.0));
+}
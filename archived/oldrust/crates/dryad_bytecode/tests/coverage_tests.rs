@@ -0,0 +1,47 @@
+// crates/dryad_bytecode/tests/coverage_tests.rs
+//! Testes para a coleta de cobertura de linhas e branches da VM
+
+use dryad_bytecode::{Compiler, InterpretResult, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+
+fn compile(source: &str) -> dryad_bytecode::Chunk {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    Compiler::new().compile(program).unwrap()
+}
+
+#[test]
+fn test_vm_coverage_counts_lines_and_branches() {
+    let source = "let total = 0;\nlet i = 0;\nwhile (i < 3) {\n    i = i + 1;\n}\nif (i > 10) {\n    total = 1;\n}\n";
+    let chunk = compile(source);
+    let executable = chunk.source_lines();
+
+    let mut vm = VM::new();
+    vm.enable_coverage();
+    assert_eq!(vm.interpret(chunk), InterpretResult::Ok);
+    let coverage = vm.take_coverage().unwrap();
+
+    assert!(executable.contains(&7));
+    assert_eq!(coverage.lines.get(&1), Some(&1));
+    assert_eq!(coverage.lines.get(&4), Some(&3));
+    // Linha dentro do if nunca executada
+    assert_eq!(coverage.lines.get(&7), None);
+    assert_eq!(coverage.branches.get(&3), Some(&[3, 1]));
+    assert_eq!(coverage.branches.get(&6), Some(&[0, 1]));
+}
+
+#[test]
+fn test_vm_coverage_disabled_by_default() {
+    let mut vm = VM::new();
+    assert_eq!(vm.interpret(compile("let x = 1;\n")), InterpretResult::Ok);
+    assert!(vm.take_coverage().is_none());
+}
@@ -20,6 +20,7 @@ fn test_simple_function_declaration() {
     let program = Program {
         statements: vec![Stmt::FunctionDeclaration {
             name: "add".to_string(),
+            type_params: vec![],
             params: vec![
                 ("a".to_string(), Some(Type::Number), None),
                 ("b".to_string(), Some(Type::Number), None),
@@ -39,6 +40,7 @@ fn test_simple_function_declaration() {
             )),
             location: dummy_loc(),
             is_async: false,
+            is_generator: false,
             rest_param: None,
         }],
     };
@@ -63,6 +65,7 @@ fn test_function_call() {
             // Declaração da função
             Stmt::FunctionDeclaration {
                 name: "add".to_string(),
+                type_params: vec![],
                 params: vec![
                     ("a".to_string(), Some(Type::Number), None),
                     ("b".to_string(), Some(Type::Number), None),
@@ -82,6 +85,7 @@ fn test_function_call() {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
                 rest_param: None,
             },
             // var x = add(1, 2);
@@ -123,6 +127,7 @@ fn test_function_with_local_variables() {
         statements: vec![
             Stmt::FunctionDeclaration {
                 name: "multiply".to_string(),
+                type_params: vec![],
                 params: vec![
                     ("x".to_string(), Some(Type::Number), None),
                     ("y".to_string(), Some(Type::Number), None),
@@ -152,6 +157,7 @@ fn test_function_with_local_variables() {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
                 rest_param: None,
             },
             // var z = multiply(3, 4);
@@ -195,6 +201,7 @@ fn test_recursive_function_sum_to() {
         statements: vec![
             Stmt::FunctionDeclaration {
                 name: "sum_to".to_string(),
+                type_params: vec![],
                 params: vec![("n".to_string(), Some(Type::Number), None)],
                 return_type: Some(Type::Number),
                 body: Box::new(Stmt::Block(
@@ -238,6 +245,7 @@ fn test_recursive_function_sum_to() {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
                 rest_param: None,
             },
             Stmt::VarDeclaration(
@@ -274,6 +282,7 @@ fn test_nested_function_calls() {
         statements: vec![
             Stmt::FunctionDeclaration {
                 name: "multiply".to_string(),
+                type_params: vec![],
                 params: vec![
                     ("x".to_string(), Some(Type::Number), None),
                     ("y".to_string(), Some(Type::Number), None),
@@ -293,10 +302,12 @@ fn test_nested_function_calls() {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
                 rest_param: None,
             },
             Stmt::FunctionDeclaration {
                 name: "add".to_string(),
+                type_params: vec![],
                 params: vec![
                     ("a".to_string(), Some(Type::Number), None),
                     ("b".to_string(), Some(Type::Number), None),
@@ -316,6 +327,7 @@ fn test_nested_function_calls() {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
                 rest_param: None,
             },
             Stmt::VarDeclaration(
@@ -383,6 +395,7 @@ fn test_function_parameter_access_stack_start() {
         statements: vec![
             Stmt::FunctionDeclaration {
                 name: "add_three".to_string(),
+                type_params: vec![],
                 params: vec![
                     ("a".to_string(), Some(Type::Number), None),
                     ("b".to_string(), Some(Type::Number), None),
@@ -408,6 +421,7 @@ fn test_function_parameter_access_stack_start() {
                 )),
                 location: dummy_loc(),
                 is_async: false,
+                is_generator: false,
                 rest_param: None,
             },
             Stmt::VarDeclaration(
@@ -0,0 +1,142 @@
+// crates/dryad_bytecode/tests/generator_tests.rs
+//! Testes para iteração (for-in, intervalos) e geradores (function*/yield) na VM
+
+use dryad_bytecode::{Compiler, InterpretResult, Value, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+
+fn run(source: &str) -> (VM, InterpretResult) {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let mut vm = VM::new();
+    let result = vm.interpret(chunk);
+    (vm, result)
+}
+
+fn global(source: &str, name: &str) -> Value {
+    let (vm, result) = run(source);
+    assert_eq!(result, InterpretResult::Ok);
+    vm.get_global(name).cloned().unwrap()
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_foreach_over_ranges() {
+    let source = r#"
+        let out = "";
+        for (i in 0..4) { out = out + i; }
+        let n = 3;
+        for (i in 1..=n) { out = out + i; }
+    "#;
+    assert_eq!(global(source, "out"), text("0123123"));
+
+    let (_, result) = run("for (i in 0..\"a\") { }");
+    assert_eq!(result, InterpretResult::RuntimeError);
+}
+
+#[test]
+fn test_foreach_keeps_falsy_elements_and_destructures() {
+    let source = r#"
+        let count = 0;
+        for (x in [0, false, null, ""]) { count = count + 1; }
+        let keys = "";
+        for (k in { b: 2, a: 1 }) { keys = keys + k; }
+        let total = 0;
+        for ((name, n) in [("a", 1), ("b", 2)]) { total = total + n; }
+    "#;
+    assert_eq!(global(source, "count"), Value::Number(4.0));
+    assert_eq!(global(source, "keys"), text("ab"));
+    assert_eq!(global(source, "total"), Value::Number(3.0));
+}
+
+#[test]
+fn test_generator_in_foreach_with_break() {
+    let source = r#"
+        function* naturals() {
+            let n = 0;
+            while (true) {
+                yield n;
+                n = n + 1;
+            }
+        }
+        let sum = 0;
+        for (n in naturals()) {
+            if (n > 4) { break; }
+            sum = sum + n;
+        }
+    "#;
+    assert_eq!(global(source, "sum"), Value::Number(10.0));
+}
+
+#[test]
+fn test_generator_next_sends_values() {
+    let source = r#"
+        function* acumulador(limite) {
+            let total = 0;
+            while (total < limite) {
+                let x = yield total;
+                total = total + x;
+            }
+            return "fim";
+        }
+        let g = acumulador(20);
+        g.next();
+        g.next(5);
+        let a = g.next(10).value;
+        let last = g.next(10);
+        let fim = last.value;
+        let done = last.done;
+        let after = g.next().done;
+    "#;
+    assert_eq!(global(source, "a"), Value::Number(15.0));
+    assert_eq!(global(source, "fim"), text("fim"));
+    assert_eq!(global(source, "done"), Value::Boolean(true));
+    assert_eq!(global(source, "after"), Value::Boolean(true));
+}
+
+#[test]
+fn test_generator_exceptions() {
+    // try/catch dentro do gerador sobrevive às suspensões
+    let source = r#"
+        function* passos() {
+            try {
+                yield "a";
+                throw "falhou";
+            } catch (e) {
+                yield "catch";
+            }
+            yield "depois";
+        }
+        let out = "";
+        for (p in passos()) { out = out + p + " "; }
+    "#;
+    assert_eq!(global(source, "out"), text("a catch depois "));
+
+    // Exceção que escapa do gerador é capturada onde ele foi retomado
+    let source = r#"
+        function* falha() {
+            yield 1;
+            throw "boom";
+        }
+        let msg = "";
+        try {
+            for (x in falha()) { msg = msg + x; }
+        } catch (e) {
+            msg = msg + e;
+        }
+    "#;
+    assert_eq!(global(source, "msg"), text("1boom"));
+}
@@ -2,7 +2,7 @@
 //! End-to-end integration test for bytecode compiler
 //! Tests a realistic program combining multiple language features
 
-use dryad_bytecode::{Compiler, InterpretResult, VM};
+use dryad_bytecode::{Compiler, InterpretResult, Value, VM};
 use dryad_errors::SourceLocation;
 use dryad_parser::ast::{ClassMember, Expr, Literal, Program, Stmt, Type, Visibility};
 
@@ -41,6 +41,7 @@ fn test_e2e_realistic_oop_program() {
             // Class definition
             Stmt::ClassDeclaration(
                 "Account".to_string(),
+                vec![],
                 None,
                 vec![],
                 vec![
@@ -49,6 +50,7 @@ fn test_e2e_realistic_oop_program() {
                         visibility: Visibility::Public,
                         is_static: false,
                         is_async: false,
+                        is_abstract: false,
                         name: "__init__".to_string(),
                         params: vec![("balance".to_string(), Some(Type::Number), None)],
                         return_type: None,
@@ -73,6 +75,7 @@ fn test_e2e_realistic_oop_program() {
                         visibility: Visibility::Public,
                         is_static: false,
                         is_async: false,
+                        is_abstract: false,
                         name: "deposit".to_string(),
                         params: vec![("amount".to_string(), Some(Type::Number), None)],
                         return_type: None,
@@ -98,6 +101,7 @@ fn test_e2e_realistic_oop_program() {
                         visibility: Visibility::Public,
                         is_static: false,
                         is_async: false,
+                        is_abstract: false,
                         name: "withdraw".to_string(),
                         params: vec![("amount".to_string(), Some(Type::Number), None)],
                         return_type: None,
@@ -138,6 +142,7 @@ fn test_e2e_realistic_oop_program() {
                         )),
                     },
                 ],
+                false,
                 dummy_loc(),
             ),
             // Create instance: let account = new Account(1000);
@@ -382,7 +387,7 @@ fn test_e2e_array_operations() {
     //
     // let numbers = [1, 2, 3, 4, 5];
     // let sum = 0;
-    // for (i in numbers) {
+    // for (i in 0..5) {
     //     sum = sum + numbers[i];
     // }
     // print sum;  // Should output: 15
@@ -412,12 +417,19 @@ fn test_e2e_array_operations() {
                 Some(Expr::Literal(Literal::Number(0.0), dummy_loc())),
                 dummy_loc(),
             ),
-            // for-in loop
+            // for-in loop: for-in sobre um array produz os elementos, então os
+            // índices vêm de um intervalo
             Stmt::ForEach(
                 dryad_parser::ast::Pattern::Identifier("i".to_string()),
-                Expr::Variable("numbers".to_string(), dummy_loc()),
+                Expr::Range(
+                    Box::new(Expr::Literal(Literal::Number(0.0), dummy_loc())),
+                    Box::new(Expr::Literal(Literal::Number(5.0), dummy_loc())),
+                    false,
+                    dummy_loc(),
+                ),
                 Box::new(Stmt::Block(
-                    vec![Stmt::Expression(
+                    vec![Stmt::Assignment(
+                        dryad_parser::ast::Pattern::Identifier("sum".to_string()),
                         Expr::Binary(
                             Box::new(Expr::Variable("sum".to_string(), dummy_loc())),
                             "+".to_string(),
@@ -453,4 +465,5 @@ fn test_e2e_array_operations() {
     let mut vm = VM::new();
     let result = vm.interpret(chunk.unwrap());
     assert_eq!(result, InterpretResult::Ok);
+    assert_eq!(vm.get_global("sum"), Some(&Value::Number(15.0)));
 }
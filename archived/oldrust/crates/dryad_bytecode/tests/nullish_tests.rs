@@ -0,0 +1,72 @@
+// crates/dryad_bytecode/tests/nullish_tests.rs
+//! Testes para encadeamento opcional (`?.`) e coalescência nula (`??`, `??=`) na VM
+
+use dryad_bytecode::{Compiler, InterpretResult, Value, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+
+fn run(source: &str) -> (VM, InterpretResult) {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let mut vm = VM::new();
+    let result = vm.interpret(chunk);
+    (vm, result)
+}
+
+fn global(source: &str, name: &str) -> Value {
+    let (vm, result) = run(source);
+    assert_eq!(result, InterpretResult::Ok);
+    vm.get_global(name).cloned().unwrap()
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_optional_chain_short_circuits() {
+    let source = r#"
+        let user = { profile: { name: "Ana" } };
+        let vazio = null;
+        let nome = user?.profile?.name;
+        let nada = vazio?.profile.name;
+        let lista = [1, 2];
+        let segundo = lista?.[1];
+        let semIndice = vazio?.[0];
+        function explode() { throw "avaliado"; }
+        let curto = vazio?.metodo(explode()).outro;
+    "#;
+    assert_eq!(global(source, "nome"), text("Ana"));
+    assert_eq!(global(source, "nada"), Value::Nil);
+    assert_eq!(global(source, "segundo"), Value::Number(2.0));
+    assert_eq!(global(source, "semIndice"), Value::Nil);
+    assert_eq!(global(source, "curto"), Value::Nil);
+}
+
+#[test]
+fn test_nullish_coalescing_and_assignment() {
+    let source = r#"
+        let a = null ?? "padrão";
+        let b = 0 ?? 1;
+        let c = false ?? true;
+        let config = null;
+        config ??= "default";
+        let porta = 8080;
+        porta ??= 80;
+    "#;
+    assert_eq!(global(source, "a"), text("padrão"));
+    assert_eq!(global(source, "b"), Value::Number(0.0));
+    assert_eq!(global(source, "c"), Value::Boolean(false));
+    assert_eq!(global(source, "config"), text("default"));
+    assert_eq!(global(source, "porta"), Value::Number(8080.0));
+}
@@ -0,0 +1,127 @@
+// crates/dryad_bytecode/tests/operator_tests.rs
+//! Testes para sobrecarga de operadores em classes na VM
+
+use dryad_bytecode::{Compiler, InterpretResult, Value, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+
+const VECTOR: &str = r#"
+    class Vec2 {
+        function init(x, y) {
+            this.x = x;
+            this.y = y;
+        }
+        operator +(other) { return new Vec2(this.x + other.x, this.y + other.y); }
+        operator -(other) { return new Vec2(this.x - other.x, this.y - other.y); }
+        operator *(k) { return new Vec2(this.x * k, this.y * k); }
+        operator -() { return new Vec2(-this.x, -this.y); }
+        operator ==(other) { return this.x == other.x && this.y == other.y; }
+        operator <(other) { return this.x * this.x + this.y * this.y < other.x * other.x + other.y * other.y; }
+        operator [](i) {
+            if (i == 0) { return this.x; }
+            return this.y;
+        }
+        operator []=(i, v) {
+            if (i == 0) { this.x = v; } else { this.y = v; }
+        }
+        function toString() { return "(" + this.x + ", " + this.y + ")"; }
+    }
+"#;
+
+fn run(source: &str) -> (VM, InterpretResult) {
+    let source = format!("{}\n{}", VECTOR, source);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let mut vm = VM::new();
+    let result = vm.interpret(chunk);
+    (vm, result)
+}
+
+fn global(source: &str, name: &str) -> Value {
+    let (vm, result) = run(source);
+    assert_eq!(result, InterpretResult::Ok);
+    vm.get_global(name).cloned().unwrap()
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_arithmetic_operators_dispatch_to_methods() {
+    let source = r#"
+        let a = new Vec2(1, 2);
+        let b = new Vec2(3, 4);
+        let soma = (a + b).toString();
+        let diferenca = (b - a).toString();
+        let escala = (a * 3).toString();
+        let negado = (-a).toString();
+    "#;
+    assert_eq!(global(source, "soma"), text("(4, 6)"));
+    assert_eq!(global(source, "diferenca"), text("(2, 2)"));
+    assert_eq!(global(source, "escala"), text("(3, 6)"));
+    assert_eq!(global(source, "negado"), text("(-1, -2)"));
+}
+
+#[test]
+fn test_comparisons_derive_from_equal_and_less() {
+    let source = r#"
+        let a = new Vec2(1, 2);
+        let b = new Vec2(3, 4);
+        let igual = a == new Vec2(1, 2);
+        let diferente = a != b;
+        let menor = a < b;
+        let maior = a > b;
+        let menor_igual = a <= new Vec2(1, 2);
+        let maior_igual = b >= a;
+    "#;
+    assert_eq!(global(source, "igual"), Value::Boolean(true));
+    assert_eq!(global(source, "diferente"), Value::Boolean(true));
+    assert_eq!(global(source, "menor"), Value::Boolean(true));
+    assert_eq!(global(source, "maior"), Value::Boolean(false));
+    assert_eq!(global(source, "menor_igual"), Value::Boolean(true));
+    assert_eq!(global(source, "maior_igual"), Value::Boolean(true));
+}
+
+#[test]
+fn test_index_operators() {
+    let source = r#"
+        let v = new Vec2(1, 2);
+        v[0] = 10;
+        let x = v[0];
+        let y = v[1];
+    "#;
+    assert_eq!(global(source, "x"), Value::Number(10.0));
+    assert_eq!(global(source, "y"), Value::Number(2.0));
+}
+
+#[test]
+fn test_string_concatenation_uses_to_string() {
+    let source = r#"
+        let v = new Vec2(1, 2);
+        let antes = "v = " + v;
+        let depois = "[" + v + "]";
+    "#;
+    assert_eq!(global(source, "antes"), text("v = (1, 2)"));
+    assert_eq!(global(source, "depois"), text("[(1, 2)]"));
+}
+
+#[test]
+fn test_compound_assignment_uses_operator() {
+    let source = r#"
+        let v = new Vec2(1, 1);
+        v += new Vec2(2, 3);
+        let resultado = v.toString();
+    "#;
+    assert_eq!(global(source, "resultado"), text("(3, 4)"));
+}
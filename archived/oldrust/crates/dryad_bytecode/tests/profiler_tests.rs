@@ -0,0 +1,49 @@
+// crates/dryad_bytecode/tests/profiler_tests.rs
+//! Testes para as amostras e contagens de alocação do profiler da VM
+
+use dryad_bytecode::{Compiler, InterpretResult, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+fn compile(source: &str) -> dryad_bytecode::Chunk {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    Compiler::new().compile(program).unwrap()
+}
+
+#[test]
+fn test_vm_profile_counts_allocations_per_line() {
+    let source = "let i = 0;\nwhile (i < 4) {\n    let xs = [i, i];\n    i = i + 1;\n}\n";
+    let mut vm = VM::new();
+    vm.enable_profiling(Arc::new(AtomicU64::new(0)));
+    assert_eq!(vm.interpret(compile(source)), InterpretResult::Ok);
+
+    let profile = vm.take_profile().unwrap();
+    assert_eq!(
+        profile.allocations.get(&("script".to_string(), 3)),
+        Some(&4)
+    );
+    assert!(profile.stacks.is_empty());
+}
+
+#[test]
+fn test_vm_profile_consumes_pending_ticks() {
+    let ticks = Arc::new(AtomicU64::new(3));
+    let mut vm = VM::new();
+    vm.enable_profiling(Arc::clone(&ticks));
+    assert_eq!(vm.interpret(compile("let x = 1;\n")), InterpretResult::Ok);
+
+    let profile = vm.take_profile().unwrap();
+    assert_eq!(profile.stacks.get(&vec!["script".to_string()]), Some(&3));
+    assert_eq!(ticks.load(Ordering::Relaxed), 0);
+}
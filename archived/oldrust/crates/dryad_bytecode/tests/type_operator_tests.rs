@@ -0,0 +1,78 @@
+// crates/dryad_bytecode/tests/type_operator_tests.rs
+//! Testes para os operadores typeof e instanceof na VM
+
+use dryad_bytecode::{Compiler, InterpretResult, Value, VM};
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+
+const SHAPES: &str = r#"
+    class Shape {}
+    class Circle extends Shape {}
+    class Square extends Shape {}
+"#;
+
+fn run(source: &str) -> (VM, InterpretResult) {
+    let source = format!("{}\n{}", SHAPES, source);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let mut vm = VM::new();
+    let result = vm.interpret(chunk);
+    (vm, result)
+}
+
+fn global(source: &str, name: &str) -> Value {
+    let (vm, result) = run(source);
+    assert_eq!(result, InterpretResult::Ok);
+    vm.get_global(name).cloned().unwrap()
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_typeof_tags() {
+    let source = r#"
+        let numero = typeof 1;
+        let texto = typeof "a";
+        let logico = typeof false;
+        let nulo = typeof null;
+        let instancia = typeof new Circle();
+    "#;
+    assert_eq!(global(source, "numero"), text("number"));
+    assert_eq!(global(source, "texto"), text("string"));
+    assert_eq!(global(source, "logico"), text("boolean"));
+    assert_eq!(global(source, "nulo"), text("null"));
+    assert_eq!(global(source, "instancia"), text("instance"));
+}
+
+#[test]
+fn test_instanceof_follows_inheritance() {
+    let source = r#"
+        let c = new Circle();
+        let propria = c instanceof Circle;
+        let herdada = c instanceof Shape;
+        let outra = c instanceof Square;
+        let primitivo = 1 instanceof Shape;
+    "#;
+    assert_eq!(global(source, "propria"), Value::Boolean(true));
+    assert_eq!(global(source, "herdada"), Value::Boolean(true));
+    assert_eq!(global(source, "outra"), Value::Boolean(false));
+    assert_eq!(global(source, "primitivo"), Value::Boolean(false));
+}
+
+#[test]
+fn test_instanceof_requires_class() {
+    let (_, result) = run("let x = new Circle() instanceof 42;");
+    assert_eq!(result, InterpretResult::RuntimeError);
+}
@@ -1,22 +1,42 @@
 use dryad_errors::DryadError;
-use dryad_parser::ast::{ClassMember, Expr, ObjectProperty, Program, Stmt, Type};
-use std::collections::HashMap;
+use dryad_parser::ast::{
+    ClassMember, Expr, Literal, MatchArm, ObjectProperty, ObjectTypeField, Pattern, Program, Stmt, Type, TypeParam,
+};
+use dryad_errors::SourceLocation;
+use std::collections::{HashMap, HashSet};
 
 pub struct TypeChecker {
     scopes: Vec<HashMap<String, Type>>,
     errors: Vec<DryadError>,
     classes: HashMap<String, ClassType>,
     interfaces: HashMap<String, InterfaceType>,
+    enums: HashMap<String, Vec<(String, usize)>>, // enum -> (variante, número de campos)
+    in_generator: bool, // dentro do corpo de uma function*
+    generic_functions: HashMap<String, Vec<TypeParam>>, // função -> parâmetros de tipo
+    type_scopes: Vec<HashMap<String, Type>>, // parâmetro de tipo em escopo -> limite (ou any)
+    aliases: HashMap<String, (Vec<TypeParam>, Type)>, // type Nome<T> = tipo
+}
+
+/// Tipos de uma variável conforme a condição seja verdadeira ou falsa
+struct Narrowing {
+    name: String,
+    when_true: Type,
+    when_false: Type,
 }
 
 struct ClassType {
+    type_params: Vec<TypeParam>,
     parent: Option<String>,
     interfaces: Vec<String>,
     members: HashMap<String, Type>, // For simplicity, map member names to types
+    arities: HashMap<String, (usize, usize)>, // método -> (obrigatórios, total)
+    abstract_methods: HashSet<String>,
+    is_abstract: bool,
 }
 
 struct InterfaceType {
     methods: HashMap<String, Type>,
+    defaults: HashSet<String>, // métodos com implementação padrão
 }
 
 impl TypeChecker {
@@ -26,10 +46,19 @@ impl TypeChecker {
             errors: Vec::new(),
             classes: HashMap::new(),
             interfaces: HashMap::new(),
+            enums: HashMap::new(),
+            in_generator: false,
+            generic_functions: HashMap::new(),
+            type_scopes: Vec::new(),
+            aliases: HashMap::new(),
         }
     }
 
     pub fn check(&mut self, program: &Program) -> Result<(), Vec<DryadError>> {
+        // O checker pode ser reutilizado entre programas (ex: REPL), então
+        // apenas os erros desta verificação são reportados
+        self.errors.clear();
+
         for stmt in &program.statements {
             self.check_stmt(stmt);
         }
@@ -41,6 +70,14 @@ impl TypeChecker {
         }
     }
 
+    /// Infere o tipo de uma expressão usando as declarações já verificadas
+    pub fn infer_type(&mut self, expr: &Expr) -> Type {
+        let saved_errors = self.errors.len();
+        let t = self.check_expr(expr);
+        self.errors.truncate(saved_errors);
+        t
+    }
+
     fn check_stmt(&mut self, stmt: &Stmt) {
         match stmt {
             Stmt::VarDeclaration(name, var_type, initializer, _location) => {
@@ -48,17 +85,19 @@ impl TypeChecker {
 
                 if let Some(var_name) = name.identifier_name() {
                     if let Some(expected_type) = var_type {
-                        if let Some(actual_type) = init_type {
+                        let expected_type = &self.resolve_annotation(expected_type);
+                        if let (Some(actual_type), Some(init)) = (init_type, initializer) {
+                            let actual_type = self.contextual_type(expected_type, init, actual_type);
                             if !self.is_assignable(expected_type, &actual_type) {
                                 self.errors.push(DryadError::new(
                                     3001,
-                                    &format!("Tipo incompatível na variável '{}'. Esperado {:?}, encontrado {:?}", var_name, expected_type, actual_type)
+                                    &format!("Tipo incompatível na variável '{}'. Esperado {}, encontrado {}", var_name, expected_type, actual_type)
                                 ));
                             }
                         }
                         self.define(var_name.clone(), expected_type.clone());
                     } else if let Some(actual_type) = init_type {
-                        self.define(var_name.clone(), actual_type);
+                        self.define(var_name.clone(), widen_inferred(actual_type));
                     } else {
                         self.define(var_name.clone(), Type::Any);
                     }
@@ -69,15 +108,17 @@ impl TypeChecker {
 
                 if let Some(const_name) = name.identifier_name() {
                     if let Some(expected_type) = const_type {
+                        let expected_type = &self.resolve_annotation(expected_type);
+                        let init_type = self.contextual_type(expected_type, initializer, init_type);
                         if !self.is_assignable(expected_type, &init_type) {
                             self.errors.push(DryadError::new(
                                 3002,
-                                &format!("Tipo incompatível na constante '{}'. Esperado {:?}, encontrado {:?}", const_name, expected_type, init_type)
+                                &format!("Tipo incompatível na constante '{}'. Esperado {}, encontrado {}", const_name, expected_type, init_type)
                             ));
                         }
                         self.define(const_name.clone(), expected_type.clone());
                     } else {
-                        self.define(const_name.clone(), init_type);
+                        self.define(const_name.clone(), widen_inferred(init_type));
                     }
                 }
             }
@@ -91,65 +132,198 @@ impl TypeChecker {
             Stmt::Expression(expr, _location) => {
                 self.check_expr(expr);
             }
+            Stmt::Test(_, body, _, _location) => {
+                self.check_stmt(body);
+            }
             Stmt::FunctionDeclaration {
                 name,
+                type_params,
                 params,
                 return_type,
                 body,
                 location: _,
-                is_async: _,
+                is_async,
+                is_generator,
                 rest_param: _,
             } => {
-                // Pre-define function for recursion
-                // For now, simplify function type representation
-                // For now, simplify function representation in checker
-                self.define(
-                    name.clone(),
-                    Type::Function(Vec::new(), Box::new(Type::Any)),
-                );
+                // Pre-define function for recursion. A assinatura mantém os parâmetros
+                // de tipo livres (`T`); cada chamada os instancia por inferência
+                let mut outer = self.type_scope_bindings();
+                for type_param in type_params {
+                    outer.remove(&type_param.name);
+                }
+                let param_types = params
+                    .iter()
+                    .map(|(_, t, _)| substitute(&self.expand_aliases(t.as_ref().unwrap_or(&Type::Any)), &outer))
+                    .collect();
+                let ret_type = match return_type {
+                    Some(t) if !is_async && !is_generator => substitute(&self.expand_aliases(t), &outer),
+                    _ => Type::Any,
+                };
+                self.define(name.clone(), Type::Function(param_types, Box::new(ret_type)));
+                if type_params.is_empty() {
+                    self.generic_functions.remove(name);
+                } else {
+                    self.generic_functions.insert(name.clone(), type_params.clone());
+                }
 
                 self.begin_scope();
+                self.begin_type_params(type_params);
                 for (param_name, param_type, _) in params {
-                    self.define(param_name.clone(), param_type.clone().unwrap_or(Type::Any));
+                    let param_type = self.resolve_annotation(param_type.as_ref().unwrap_or(&Type::Any));
+                    self.define(param_name.clone(), param_type);
                 }
+                let enclosing = std::mem::replace(&mut self.in_generator, *is_generator);
                 self.check_stmt(body);
+                self.in_generator = enclosing;
                 // TODO: Check if all return paths match return_type
+                self.type_scopes.pop();
                 self.end_scope();
             }
-            Stmt::ClassDeclaration(name, parent, interfaces, members, _location) => {
+            Stmt::ClassDeclaration(name, type_params, parent, interfaces, members, is_abstract, location) => {
                 let mut member_types = HashMap::new();
+                let mut arities = HashMap::new();
+                let mut abstract_methods = HashSet::new();
                 for member in members {
                     match member {
-                        ClassMember::Method { name, params, return_type, .. } => {
-                            let param_types: Vec<Type> = params.iter().map(|(_, t, _)| t.clone().unwrap_or(Type::Any)).collect();
-                            let ret_type = return_type.clone().unwrap_or(Type::Any);
+                        ClassMember::Method { name, params, return_type, is_abstract, .. } => {
+                            let param_types: Vec<Type> = params
+                                .iter()
+                                .map(|(_, t, _)| self.expand_aliases(t.as_ref().unwrap_or(&Type::Any)))
+                                .collect();
+                            let ret_type = self.expand_aliases(return_type.as_ref().unwrap_or(&Type::Any));
                             member_types.insert(name.clone(), Type::Function(param_types, Box::new(ret_type)));
+                            let required = params.iter().filter(|(_, _, d)| d.is_none()).count();
+                            arities.insert(name.clone(), (required, params.len()));
+                            if *is_abstract {
+                                abstract_methods.insert(name.clone());
+                            }
                         }
                         ClassMember::Property(_, _, name, prop_type, _) => {
-                            member_types.insert(name.clone(), prop_type.clone().unwrap_or(Type::Any));
+                            member_types.insert(name.clone(), self.expand_aliases(prop_type.as_ref().unwrap_or(&Type::Any)));
                         }
                         _ => {}
                     }
                 }
                 self.classes.insert(name.clone(), ClassType {
+                    type_params: type_params.clone(),
                     parent: parent.clone(),
                     interfaces: interfaces.clone(),
                     members: member_types,
+                    arities,
+                    abstract_methods,
+                    is_abstract: *is_abstract,
                 });
+                self.check_interface_conformance(name, interfaces, location);
+                if !is_abstract {
+                    let missing = self.unimplemented_abstract_methods(name);
+                    if !missing.is_empty() {
+                        self.errors.push(DryadError::new(
+                            3016,
+                            &format!(
+                                "Classe '{}' não implementa os métodos abstratos: {} (linha {})",
+                                name,
+                                missing.iter().map(|m| format!("'{}'", m)).collect::<Vec<_>>().join(", "),
+                                location.line
+                            ),
+                        ));
+                    }
+                }
                 self.define(name.clone(), Type::Class(name.clone()));
             }
-            Stmt::InterfaceDeclaration(name, members, _location) => {
+            Stmt::InterfaceDeclaration(name, _type_params, members, _location) => {
                 let mut methods = HashMap::new();
+                let mut defaults = HashSet::new();
                 for member in members {
                     if let dryad_parser::ast::InterfaceMember::Method(m) = member {
-                        let param_types: Vec<Type> = m.params.iter().map(|(_, t, _)| t.clone().unwrap_or(Type::Any)).collect();
-                        let ret_type = m.return_type.clone().unwrap_or(Type::Any);
+                        let param_types: Vec<Type> = m
+                            .params
+                            .iter()
+                            .map(|(_, t, _)| self.expand_aliases(t.as_ref().unwrap_or(&Type::Any)))
+                            .collect();
+                        let ret_type = self.expand_aliases(m.return_type.as_ref().unwrap_or(&Type::Any));
                         methods.insert(m.name.clone(), Type::Function(param_types, Box::new(ret_type)));
+                        if m.body.is_some() {
+                            defaults.insert(m.name.clone());
+                        }
                     }
                 }
-                self.interfaces.insert(name.clone(), InterfaceType { methods });
+                self.interfaces.insert(name.clone(), InterfaceType { methods, defaults });
                 self.define(name.clone(), Type::Class(name.clone())); // Interfaces also act as types
             }
+            Stmt::TypeAlias(name, type_params, aliased, _location) => {
+                self.aliases.insert(name.clone(), (type_params.clone(), aliased.clone()));
+            }
+            Stmt::EnumDeclaration(name, variants, location) => {
+                let mut seen = HashSet::new();
+                for variant in variants {
+                    if !seen.insert(variant.name.as_str()) {
+                        self.errors.push(DryadError::new(
+                            3007,
+                            &format!(
+                                "Variante '{}' declarada mais de uma vez no enum '{}' (linha {})",
+                                variant.name, name, location.line
+                            ),
+                        ));
+                    }
+                }
+                let variants = variants
+                    .iter()
+                    .map(|v| (v.name.clone(), v.fields.len()))
+                    .collect();
+                self.enums.insert(name.clone(), variants);
+                self.define(name.clone(), Type::Class(name.clone()));
+            }
+            Stmt::Export(inner, _location) => self.check_stmt(inner),
+            Stmt::Return(Some(expr), _location) | Stmt::Throw(expr, _location) => {
+                self.check_expr(expr);
+            }
+            Stmt::If(condition, then_stmt, _location) | Stmt::While(condition, then_stmt, _location) => {
+                self.check_expr(condition);
+                match self.narrowing(condition) {
+                    Some(narrowing) => {
+                        self.check_narrowed(&narrowing.name, narrowing.when_true, then_stmt);
+                        // `if (x == null) { return; }`: depois do if, x não é mais null
+                        if matches!(stmt, Stmt::If(..)) && always_exits(then_stmt) {
+                            self.define(narrowing.name, narrowing.when_false);
+                        }
+                    }
+                    None => self.check_stmt(then_stmt),
+                }
+            }
+            Stmt::IfElse(condition, then_stmt, else_stmt, _location) => {
+                self.check_expr(condition);
+                match self.narrowing(condition) {
+                    Some(narrowing) => {
+                        self.check_narrowed(&narrowing.name, narrowing.when_true, then_stmt);
+                        self.check_narrowed(&narrowing.name, narrowing.when_false, else_stmt);
+                    }
+                    None => {
+                        self.check_stmt(then_stmt);
+                        self.check_stmt(else_stmt);
+                    }
+                }
+            }
+            Stmt::ForEach(pattern, iterable, body, _location) => {
+                self.check_expr(iterable);
+                self.begin_scope();
+                self.define_pattern(pattern);
+                self.check_stmt(body);
+                self.end_scope();
+            }
+            Stmt::Assignment(Pattern::Identifier(name), expr, _location) => {
+                let t = self.check_expr(expr);
+                // Atribuir um valor a uma variável inicializada com null a torna não-null
+                if self.resolve(name) == Some(&Type::Null) {
+                    if let Some(scope) = self.scopes.iter_mut().rev().find(|s| s.contains_key(name)) {
+                        scope.insert(name.clone(), t);
+                    }
+                }
+            }
+            Stmt::VarDeclaration(_, _, Some(expr), _location)
+            | Stmt::Assignment(_, expr, _location) => {
+                self.check_expr(expr);
+            }
             _ => {
                 // Implement other statements as needed
             }
@@ -160,17 +334,45 @@ impl TypeChecker {
         match expr {
             Expr::Literal(lit, _location) => match lit {
                 dryad_parser::ast::Literal::Number(_) => Type::Number,
+                dryad_parser::ast::Literal::Int(_) => Type::Int,
+                dryad_parser::ast::Literal::BigInt(_) => Type::BigInt,
+                dryad_parser::ast::Literal::Decimal(_) => Type::Decimal,
                 dryad_parser::ast::Literal::String(_) => Type::String,
                 dryad_parser::ast::Literal::Bool(_) => Type::Bool,
                 dryad_parser::ast::Literal::Null => Type::Null,
+                dryad_parser::ast::Literal::Regex(..) => Type::Any,
             },
             Expr::Variable(name, _location) => self.resolve(name).cloned().unwrap_or(Type::Any),
             Expr::Binary(left, op, right, _location) => {
                 let lt = self.check_expr(left);
-                let rt = self.check_expr(right);
+                // `x != null && x.prop`: o lado direito só roda com a condição verdadeira
+                // (ou falsa, em `||`)
+                let rt = match self.narrowing(left) {
+                    Some(narrowing) if op == "&&" || op == "||" => {
+                        let narrowed = if op == "&&" { narrowing.when_true } else { narrowing.when_false };
+                        self.begin_scope();
+                        self.define(narrowing.name, narrowed);
+                        let rt = self.check_expr(right);
+                        self.end_scope();
+                        rt
+                    }
+                    _ => self.check_expr(right),
+                };
+
+                if op == "instanceof" {
+                    return Type::Bool;
+                }
+
+                // `operator +(other)` etc. declarados na classe do operando esquerdo
+                if let Some(t) = self.operator_type(&lt, op) {
+                    return t;
+                }
 
                 match op.as_str() {
                     "+" | "-" | "*" | "/" | "%" | "**" => {
+                        if let Some(t) = promoted_numeric(&lt, &rt) {
+                            return t;
+                        }
                         if lt != Type::Number || rt != Type::Number {
                             // If it's '+', it could be string concatenation
                             if op == "+" && (lt == Type::String || rt == Type::String) {
@@ -194,44 +396,749 @@ impl TypeChecker {
                     _ => Type::Any,
                 }
             }
-            Expr::Call(callee, _args, _location) => {
-                self.check_expr(callee);
-                // TODO: Check argument types against function signature
-                Type::Any
+            Expr::Call(callee, args, location) => {
+                let callee_type = self.check_expr(callee);
+                let arg_types: Vec<Type> = args.iter().map(|arg| self.check_expr(arg)).collect();
+                let Type::Function(params, ret) = callee_type else {
+                    return Type::Any;
+                };
+                let (name, type_params) = match callee.as_ref() {
+                    Expr::Variable(name, _) => (
+                        name.clone(),
+                        self.generic_functions.get(name).cloned().unwrap_or_default(),
+                    ),
+                    _ => ("<função>".to_string(), Vec::new()),
+                };
+                let bindings = self.check_call(&name, &type_params, &params, args, &arg_types, location);
+                substitute(&ret, &bindings)
             }
-            Expr::PropertyAccess(object, property, _location) => {
-                let obj_type = self.check_expr(object);
-                if let Type::Class(class_name) = obj_type {
-                    if let Some(cls) = self.classes.get(&class_name) {
-                        if let Some(t) = cls.members.get(property) {
-                            return t.clone();
-                        }
+            Expr::PropertyAccess(object, property, location) if self.enum_of(object).is_some() => {
+                let enum_name = self.enum_of(object).unwrap_or_default();
+                self.check_variant_construction(&enum_name, property, 0, location);
+                Type::Class(enum_name)
+            }
+            Expr::MethodCall(object, method, args, location) if self.enum_of(object).is_some() => {
+                let enum_name = self.enum_of(object).unwrap_or_default();
+                for arg in args { self.check_expr(arg); }
+                self.check_variant_construction(&enum_name, method, args.len(), location);
+                Type::Class(enum_name)
+            }
+            Expr::Match(target, arms, location) => {
+                let target_type = self.check_expr(target);
+                let target_name = match target.as_ref() {
+                    Expr::Variable(name, _) => Some(name.clone()),
+                    _ => None,
+                };
+                for arm in arms {
+                    self.begin_scope();
+                    // Em `"GET" => ...` a variável casada vale pelo tipo do padrão
+                    if let (Some(name), Some(narrowed)) = (&target_name, narrow_by_pattern(&target_type, &arm.pattern)) {
+                        self.define(name.clone(), narrowed);
+                    }
+                    self.define_pattern(&arm.pattern);
+                    if let Some(guard) = &arm.guard {
+                        self.check_expr(guard);
                     }
+                    self.check_stmt(&arm.body);
+                    self.end_scope();
                 }
+                self.check_match_arms(arms, location);
+                self.check_literal_exhaustiveness(&target_type, arms, location);
                 Type::Any
             }
-            Expr::MethodCall(object, method, args, _location) => {
+            Expr::PropertyAccess(object, property, location) => {
+                self.check_not_null(object, property, location);
                 let obj_type = self.check_expr(object);
-                for arg in args { self.check_expr(arg); }
-                if let Type::Class(class_name) = obj_type {
-                    if let Some(cls) = self.classes.get(&class_name) {
-                        if let Some(t) = cls.members.get(method) {
-                            if let Type::Function(_, ret) = t {
-                                return (**ret).clone();
-                            }
-                        }
+                if let Type::Object(fields) = &obj_type
+                    && !fields.iter().any(|field| &field.name == property)
+                {
+                    self.errors.push(DryadError::new(
+                        3013,
+                        &format!(
+                            "Propriedade '{}' não existe no tipo {} (linha {})",
+                            property, obj_type, location.line
+                        ),
+                    ));
+                }
+                self.member_type(&obj_type, property).unwrap_or(Type::Any)
+            }
+            Expr::MethodCall(object, method, args, location) => {
+                self.check_not_null(object, method, location);
+                let obj_type = self.check_expr(object);
+                let arg_types: Vec<Type> = args.iter().map(|arg| self.check_expr(arg)).collect();
+                match self.member_type(&obj_type, method) {
+                    Some(Type::Function(params, ret)) if matches!(obj_type, Type::Generic(..)) => {
+                        // Em `Box<number>` os parâmetros de `T` já estão instanciados
+                        let name = format!("{}.{}", obj_type, method);
+                        self.check_call(&name, &[], &params, args, &arg_types, location);
+                        *ret
+                    }
+                    Some(Type::Function(_, ret)) => *ret,
+                    _ => Type::Any,
+                }
+            }
+            Expr::ClassInstantiation(name, args, location) => {
+                let arg_types: Vec<Type> = args.iter().map(|arg| self.check_expr(arg)).collect();
+                if self.classes.get(name).is_some_and(|cls| cls.is_abstract) {
+                    self.errors.push(DryadError::new(
+                        3015,
+                        &format!(
+                            "Classe abstrata '{}' não pode ser instanciada (linha {})",
+                            name, location.line
+                        ),
+                    ));
+                }
+                let Some(type_params) = self.classes.get(name).map(|cls| cls.type_params.clone()) else {
+                    return Type::Class(name.clone());
+                };
+                if type_params.is_empty() {
+                    return Type::Class(name.clone());
+                }
+                // Argumentos de tipo inferidos a partir dos parâmetros de `init`
+                let params = match self.classes[name].members.get("init") {
+                    Some(Type::Function(params, _)) => params.clone(),
+                    _ => Vec::new(),
+                };
+                let bindings = self.check_call(name, &type_params, &params, args, &arg_types, location);
+                Type::Generic(
+                    name.clone(),
+                    type_params.iter().map(|p| bindings[&p.name].clone()).collect(),
+                )
+            }
+            Expr::Array(elements, _location) => {
+                let element_types: Vec<Type> = elements.iter().map(|e| self.check_expr(e)).collect();
+                Type::Array(Box::new(union_of(element_types)))
+            }
+            Expr::ObjectLiteral(properties, _location) => {
+                let fields = properties
+                    .iter()
+                    .map(|property| match property {
+                        ObjectProperty::Property(name, value) => ObjectTypeField {
+                            name: name.clone(),
+                            field_type: self.check_expr(value),
+                            optional: false,
+                        },
+                        ObjectProperty::Method { name, params, return_type, .. } => ObjectTypeField {
+                            name: name.clone(),
+                            field_type: Type::Function(
+                                params.iter().map(|(_, t, _)| t.clone().unwrap_or(Type::Any)).collect(),
+                                Box::new(return_type.clone().unwrap_or(Type::Any)),
+                            ),
+                            optional: false,
+                        },
+                    })
+                    .collect();
+                Type::Object(fields)
+            }
+            Expr::Unary(op, operand, _location) => {
+                let operand_type = self.check_expr(operand);
+                match op.as_str() {
+                    "typeof" => Type::String,
+                    "!" => Type::Bool,
+                    "-" if promoted_numeric(&operand_type, &operand_type).is_some() => operand_type,
+                    _ => Type::Any,
+                }
+            }
+            Expr::Range(start, end, inclusive, location) => {
+                for bound in [start, end] {
+                    let t = self.check_expr(bound);
+                    if t != Type::Number && t != Type::Any {
+                        self.errors.push(DryadError::new(
+                            3003,
+                            &format!(
+                                "Limites do intervalo '{}' devem ser números, encontrado {:?} (linha {})",
+                                if *inclusive { "..=" } else { ".." },
+                                t,
+                                location.line
+                            ),
+                        ));
                     }
                 }
                 Type::Any
             }
-            Expr::ClassInstantiation(name, args, _location) => {
+            Expr::Index(array, index, location) => {
+                self.check_not_null(array, "[]", location);
+                self.check_expr(array);
+                self.check_expr(index);
+                Type::Any
+            }
+            Expr::OptionalChain(inner, _location) => self.check_expr(inner),
+            Expr::OptionalPropertyAccess(object, _, _location) => {
+                self.check_expr(object);
+                Type::Any
+            }
+            Expr::OptionalMethodCall(object, _, args, _location) => {
+                self.check_expr(object);
                 for arg in args { self.check_expr(arg); }
-                Type::Class(name.clone())
+                Type::Any
+            }
+            Expr::OptionalIndex(array, index, _location) => {
+                self.check_expr(array);
+                self.check_expr(index);
+                Type::Any
+            }
+            Expr::NullishCoalescing(left, right, _location) => {
+                let lt = self.check_expr(left);
+                let rt = self.check_expr(right);
+                match lt {
+                    Type::Null => rt,
+                    Type::Any => Type::Any,
+                    t => t,
+                }
+            }
+            Expr::Yield(value, location) => {
+                if !self.in_generator {
+                    self.errors.push(DryadError::new(
+                        3009,
+                        &format!("'yield' fora de uma function* (linha {})", location.line),
+                    ));
+                }
+                if let Some(value) = value {
+                    self.check_expr(value);
+                }
+                Type::Any
             }
             _ => Type::Any,
         }
     }
 
+    /// Nome do enum quando a expressão é a própria declaração (`Shape` em `Shape.Circle(1)`)
+    fn enum_of(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Variable(name, _) if self.enums.contains_key(name) => {
+                match self.resolve(name) {
+                    Some(Type::Class(class_name)) if class_name == name => Some(name.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn check_variant_construction(
+        &mut self,
+        enum_name: &str,
+        variant: &str,
+        field_count: usize,
+        location: &SourceLocation,
+    ) {
+        let arity = self.enums.get(enum_name).and_then(|variants| {
+            variants
+                .iter()
+                .find(|(name, _)| name == variant)
+                .map(|(_, arity)| *arity)
+        });
+        match arity {
+            None => self.errors.push(DryadError::new(
+                3006,
+                &format!(
+                    "Enum '{}' não tem a variante '{}' (linha {})",
+                    enum_name, variant, location.line
+                ),
+            )),
+            Some(arity) if arity != field_count => self.errors.push(DryadError::new(
+                3006,
+                &format!(
+                    "Variante '{}.{}' espera {} campo(s), recebeu {} (linha {})",
+                    enum_name, variant, arity, field_count, location.line
+                ),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    /// Enum de um padrão de construtor: o qualificado ou o único enum que declara a variante
+    fn resolve_pattern_enum(&self, qualifier: &Option<String>, variant: &str) -> Option<String> {
+        if let Some(enum_name) = qualifier {
+            return self.enums.contains_key(enum_name).then(|| enum_name.clone());
+        }
+        let mut candidates = self
+            .enums
+            .iter()
+            .filter(|(_, variants)| variants.iter().any(|(name, _)| name == variant))
+            .map(|(name, _)| name.clone());
+        match (candidates.next(), candidates.next()) {
+            (Some(enum_name), None) => Some(enum_name),
+            _ => None,
+        }
+    }
+
+    /// Verifica braços inalcançáveis e, em matches sobre enums, se todas as variantes são cobertas
+    fn check_match_arms(&mut self, arms: &[MatchArm], location: &SourceLocation) {
+        let mut matched_enum: Option<String> = None;
+        let mut covered: HashSet<String> = HashSet::new();
+        let mut catch_all = false;
+
+        for arm in arms {
+            let line = arm.location.line;
+            if catch_all {
+                self.errors.push(DryadError::new(
+                    3005,
+                    &format!("Braço inalcançável no match (linha {}): um braço anterior já casa qualquer valor", line),
+                ));
+                continue;
+            }
+
+            match &arm.pattern {
+                Pattern::Constructor(qualifier, variant, fields) => {
+                    let Some(enum_name) = self.resolve_pattern_enum(qualifier, variant) else {
+                        self.errors.push(DryadError::new(
+                            3006,
+                            &format!("Variante '{}' desconhecida no padrão (linha {})", variant, line),
+                        ));
+                        continue;
+                    };
+                    let arity = self.enums[&enum_name]
+                        .iter()
+                        .find(|(name, _)| name == variant)
+                        .map(|(_, arity)| *arity);
+                    match arity {
+                        None => {
+                            self.errors.push(DryadError::new(
+                                3006,
+                                &format!("Enum '{}' não tem a variante '{}' (linha {})", enum_name, variant, line),
+                            ));
+                            continue;
+                        }
+                        Some(arity) if !fields.is_empty() && fields.len() != arity => {
+                            self.errors.push(DryadError::new(
+                                3006,
+                                &format!(
+                                    "Padrão '{}.{}' tem {} campo(s), a variante tem {} (linha {})",
+                                    enum_name, variant, fields.len(), arity, line
+                                ),
+                            ));
+                        }
+                        Some(_) => {}
+                    }
+                    matched_enum.get_or_insert(enum_name);
+
+                    if covered.contains(variant) {
+                        self.errors.push(DryadError::new(
+                            3005,
+                            &format!("Braço inalcançável no match (linha {}): variante '{}' já coberta", line, variant),
+                        ));
+                    } else if arm.guard.is_none() && fields.iter().all(is_irrefutable) {
+                        covered.insert(variant.clone());
+                    }
+                }
+                Pattern::Identifier(name) if self.resolve_pattern_enum(&None, name).is_some() => {
+                    let enum_name = self.resolve_pattern_enum(&None, name).unwrap_or_default();
+                    self.errors.push(DryadError::new(
+                        3008,
+                        &format!(
+                            "O padrão '{}' cria uma variável que casa qualquer valor (linha {}); para a variante use '{}.{}'",
+                            name, line, enum_name, name
+                        ),
+                    ));
+                    if arm.guard.is_none() {
+                        catch_all = true;
+                    }
+                }
+                pattern if arm.guard.is_none() && is_irrefutable(pattern) => catch_all = true,
+                _ => {}
+            }
+        }
+
+        if catch_all {
+            return;
+        }
+        if let Some(enum_name) = matched_enum {
+            let missing: Vec<String> = self.enums[&enum_name]
+                .iter()
+                .filter(|(name, _)| !covered.contains(name))
+                .map(|(name, _)| format!("{}.{}", enum_name, name))
+                .collect();
+            if !missing.is_empty() {
+                self.errors.push(DryadError::new(
+                    3004,
+                    &format!(
+                        "Match não exaustivo (linha {}): variantes não cobertas: {}",
+                        location.line,
+                        missing.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Estreitamento por `x != null`, `typeof x == "string"`, `x instanceof C`
+    /// (em qualquer ordem, também negados com `!`)
+    fn narrowing(&self, condition: &Expr) -> Option<Narrowing> {
+        if let Expr::Unary(op, inner, _) = condition
+            && op == "!"
+        {
+            let narrowing = self.narrowing(inner)?;
+            return Some(Narrowing {
+                name: narrowing.name,
+                when_true: narrowing.when_false,
+                when_false: narrowing.when_true,
+            });
+        }
+        let Expr::Binary(left, op, right, _) = condition else {
+            return None;
+        };
+        let current = |name: &String| self.resolve(name).cloned().unwrap_or(Type::Any);
+
+        if op == "instanceof" {
+            let (Expr::Variable(name, _), Expr::Variable(class_name, _)) = (left.as_ref(), right.as_ref()) else {
+                return None;
+            };
+            let t = current(name);
+            let is_instance = |m: &Type| self.class_of(m).is_some_and(|c| self.is_subtype(&c, class_name));
+            let when_true = match &t {
+                Type::Union(members) => {
+                    let matching: Vec<Type> = members.iter().filter(|m| is_instance(m)).cloned().collect();
+                    if matching.is_empty() { Type::Class(class_name.clone()) } else { union_of(matching) }
+                }
+                t if is_instance(t) => t.clone(),
+                _ => Type::Class(class_name.clone()),
+            };
+            let when_false = match &t {
+                Type::Union(members) => union_of(members.iter().filter(|m| !is_instance(m)).cloned().collect()),
+                t => t.clone(),
+            };
+            return Some(Narrowing { name: name.clone(), when_true, when_false });
+        }
+
+        let equal = match op.as_str() {
+            "==" => true,
+            "!=" => false,
+            _ => return None,
+        };
+        let (when_equal, when_different, name) = match (left.as_ref(), right.as_ref()) {
+            (Expr::Variable(name, _), Expr::Literal(Literal::Null, _))
+            | (Expr::Literal(Literal::Null, _), Expr::Variable(name, _)) => {
+                let t = current(name);
+                let non_null = match &t {
+                    // Uma variável iniciada com null é considerada atribuída depois do teste
+                    Type::Null => Type::Any,
+                    Type::Union(members) => union_of(members.iter().filter(|m| **m != Type::Null).cloned().collect()),
+                    t => t.clone(),
+                };
+                let null = match &t {
+                    Type::Union(members) if members.contains(&Type::Null) => Type::Null,
+                    t => t.clone(),
+                };
+                (null, non_null, name)
+            }
+            (Expr::Unary(typeof_op, operand, _), Expr::Literal(Literal::String(tag), _))
+            | (Expr::Literal(Literal::String(tag), _), Expr::Unary(typeof_op, operand, _))
+                if typeof_op == "typeof" =>
+            {
+                let Expr::Variable(name, _) = operand.as_ref() else {
+                    return None;
+                };
+                let t = current(name);
+                let when_equal = match &t {
+                    Type::Union(members) => {
+                        let matching: Vec<Type> = members.iter().filter(|m| has_tag(m, tag)).cloned().collect();
+                        if matching.is_empty() { type_for_tag(tag) } else { union_of(matching) }
+                    }
+                    Type::Any => type_for_tag(tag),
+                    t => t.clone(),
+                };
+                let when_different = match &t {
+                    Type::Union(members) => union_of(members.iter().filter(|m| !has_tag(m, tag)).cloned().collect()),
+                    t => t.clone(),
+                };
+                (when_equal, when_different, name)
+            }
+            _ => return None,
+        };
+        let (when_true, when_false) = if equal { (when_equal, when_different) } else { (when_different, when_equal) };
+        Some(Narrowing { name: name.clone(), when_true, when_false })
+    }
+
+    /// Nome da classe de um tipo de instância
+    fn class_of(&self, t: &Type) -> Option<String> {
+        match t {
+            Type::Class(name) | Type::Generic(name, _) if self.classes.contains_key(name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Match sobre uma união de literais (`"GET" | "POST"`) precisa cobrir todos
+    fn check_literal_exhaustiveness(&mut self, target_type: &Type, arms: &[MatchArm], location: &SourceLocation) {
+        let Type::Union(members) = target_type else {
+            return;
+        };
+        if !members.iter().all(|m| matches!(m, Type::Literal(_) | Type::Null)) {
+            return;
+        }
+        if arms.iter().any(|arm| arm.guard.is_none() && is_irrefutable(&arm.pattern)) {
+            return;
+        }
+        let covered: Vec<Type> = arms
+            .iter()
+            .filter(|arm| arm.guard.is_none())
+            .filter_map(|arm| match &arm.pattern {
+                Pattern::Literal(Literal::Null) => Some(Type::Null),
+                Pattern::Literal(lit) => Some(Type::Literal(lit.clone())),
+                _ => None,
+            })
+            .collect();
+        let missing: Vec<String> = members
+            .iter()
+            .filter(|m| !covered.contains(m))
+            .map(|m| m.to_string())
+            .collect();
+        if !missing.is_empty() {
+            self.errors.push(DryadError::new(
+                3004,
+                &format!(
+                    "Match não exaustivo (linha {}): valores não cobertos: {}",
+                    location.line,
+                    missing.join(", ")
+                ),
+            ));
+        }
+    }
+
+    /// Tipo de um literal à luz do tipo esperado: `"GET"` é do tipo literal
+    /// `"GET"` quando o destino menciona tipos literais, em objetos e arrays também
+    fn contextual_type(&mut self, expected: &Type, expr: &Expr, actual: Type) -> Type {
+        match expr {
+            Expr::Literal(lit, _)
+                if !matches!(lit, Literal::Null | Literal::Regex(..)) && mentions_literal(expected) =>
+            {
+                Type::Literal(lit.clone())
+            }
+            Expr::ObjectLiteral(properties, _) => {
+                let (Type::Object(mut fields), Some(expected_fields)) = (actual.clone(), object_fields(expected)) else {
+                    return actual;
+                };
+                for field in &mut fields {
+                    let value = properties.iter().find_map(|p| match p {
+                        ObjectProperty::Property(name, value) if *name == field.name => Some(value),
+                        _ => None,
+                    });
+                    let expected_field = expected_fields.iter().find(|f| f.name == field.name);
+                    if let (Some(value), Some(expected_field)) = (value, expected_field) {
+                        let field_type = std::mem::replace(&mut field.field_type, Type::Any);
+                        field.field_type = self.contextual_type(&expected_field.field_type, value, field_type);
+                    }
+                }
+                Type::Object(fields)
+            }
+            Expr::Array(elements, _) => {
+                let Some(expected_element) = array_element(expected) else {
+                    return actual;
+                };
+                let element_types = elements
+                    .iter()
+                    .map(|e| {
+                        let t = self.infer_type(e);
+                        self.contextual_type(&expected_element, e, t)
+                    })
+                    .collect();
+                Type::Array(Box::new(union_of(element_types)))
+            }
+            _ => actual,
+        }
+    }
+
+    /// Expande aliases (`type Id = string`) dentro de um tipo anotado; aliases
+    /// recursivos ficam como referência nomeada no ponto de recursão
+    fn expand_aliases(&self, t: &Type) -> Type {
+        self.expand_aliases_with(t, &mut Vec::new())
+    }
+
+    fn expand_aliases_with(&self, t: &Type, expanding: &mut Vec<String>) -> Type {
+        let (name, args) = match t {
+            Type::Class(name) => (name, &[][..]),
+            Type::Generic(name, args) => (name, args.as_slice()),
+            _ => return map_type(t, &mut |inner| self.expand_aliases_with(inner, expanding)),
+        };
+        match self.aliases.get(name) {
+            Some((type_params, aliased)) if !expanding.contains(name) => {
+                let bindings = type_params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| {
+                        let arg = args.get(i).map(|a| self.expand_aliases_with(a, expanding));
+                        (p.name.clone(), arg.unwrap_or(Type::Any))
+                    })
+                    .collect();
+                expanding.push(name.clone());
+                let expanded = self.expand_aliases_with(&substitute(aliased, &bindings), expanding);
+                expanding.pop();
+                expanded
+            }
+            _ => map_type(t, &mut |inner| self.expand_aliases_with(inner, expanding)),
+        }
+    }
+
+    /// Tipo de retorno do `operator<op>` da classe (ou de uma ancestral)
+    fn operator_type(&self, operand: &Type, op: &str) -> Option<Type> {
+        let Type::Class(mut class_name) = operand.clone() else {
+            return None;
+        };
+        let method = format!("operator{}", op);
+        let mut visited = HashSet::new();
+        while visited.insert(class_name.clone()) {
+            let cls = self.classes.get(&class_name)?;
+            match cls.members.get(&method) {
+                Some(Type::Function(_, ret)) => return Some((**ret).clone()),
+                Some(_) => return Some(Type::Any),
+                None => class_name = cls.parent.clone()?,
+            }
+        }
+        None
+    }
+
+    /// Membro de uma classe (ou ancestral) com os parâmetros de tipo da classe
+    /// substituídos pelos argumentos de `Box<number>`; sem argumentos viram any
+    fn member_type(&self, obj_type: &Type, member: &str) -> Option<Type> {
+        let (mut class_name, mut args) = match obj_type {
+            Type::Object(fields) => {
+                let field = fields.iter().find(|f| f.name == member)?;
+                return Some(if field.optional {
+                    union_of(vec![field.field_type.clone(), Type::Null])
+                } else {
+                    field.field_type.clone()
+                });
+            }
+            Type::Class(name) => (name.clone(), Vec::new()),
+            Type::Generic(name, args) => (name.clone(), args.clone()),
+            _ => return None,
+        };
+        let mut visited = HashSet::new();
+        while visited.insert(class_name.clone()) {
+            let cls = self.classes.get(&class_name)?;
+            if let Some(t) = cls.members.get(member) {
+                let bindings = cls
+                    .type_params
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (p.name.clone(), args.get(i).cloned().unwrap_or(Type::Any)))
+                    .collect();
+                return Some(substitute(t, &bindings));
+            }
+            class_name = cls.parent.clone()?;
+            // Argumentos da superclasse são apagados em `extends Base<T>`
+            args = Vec::new();
+        }
+        None
+    }
+
+    /// Infere os argumentos de tipo de uma chamada e verifica os limites e os
+    /// argumentos contra a assinatura instanciada
+    fn check_call(
+        &mut self,
+        name: &str,
+        type_params: &[TypeParam],
+        params: &[Type],
+        arg_exprs: &[Expr],
+        args: &[Type],
+        location: &SourceLocation,
+    ) -> HashMap<String, Type> {
+        let names: HashSet<&str> = type_params.iter().map(|p| p.name.as_str()).collect();
+        let mut bindings = HashMap::new();
+        for (param, arg) in params.iter().zip(args) {
+            infer(param, arg, &names, &mut bindings);
+        }
+        for type_param in type_params {
+            bindings.entry(type_param.name.clone()).or_insert(Type::Any);
+        }
+
+        for type_param in type_params {
+            let Some(bound) = &type_param.bound else { continue };
+            let actual = &bindings[&type_param.name];
+            let bound = substitute(bound, &bindings);
+            if !self.is_assignable(&bound, actual) {
+                self.errors.push(DryadError::new(
+                    3012,
+                    &format!(
+                        "Tipo {} não satisfaz o limite '{} extends {}' em '{}' (linha {})",
+                        actual, type_param.name, bound, name, location.line
+                    ),
+                ));
+            }
+        }
+        for (i, ((param, arg), arg_expr)) in params.iter().zip(args).zip(arg_exprs).enumerate() {
+            let expected = substitute(param, &bindings);
+            let arg = &self.contextual_type(&expected, arg_expr, arg.clone());
+            if !self.is_assignable(&expected, arg) {
+                self.errors.push(DryadError::new(
+                    3011,
+                    &format!(
+                        "Argumento {} de '{}' incompatível: esperado {}, encontrado {} (linha {})",
+                        i + 1, name, expected, arg, location.line
+                    ),
+                ));
+            }
+        }
+        bindings
+    }
+
+    /// Parâmetros de tipo visíveis no corpo atual, mapeados para o limite (ou any)
+    fn type_scope_bindings(&self) -> HashMap<String, Type> {
+        self.type_scopes.iter().flatten().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+
+    fn begin_type_params(&mut self, type_params: &[TypeParam]) {
+        let scope = type_params
+            .iter()
+            .map(|p| (p.name.clone(), p.bound.clone().unwrap_or(Type::Any)))
+            .collect();
+        self.type_scopes.push(scope);
+    }
+
+    /// Anotação com aliases expandidos; dentro de um corpo genérico `T` vale pelo seu limite
+    fn resolve_annotation(&self, t: &Type) -> Type {
+        let t = self.expand_aliases(t);
+        if self.type_scopes.is_empty() {
+            return t;
+        }
+        substitute(&t, &self.type_scope_bindings())
+    }
+
+    /// Verifica `stmt` com `name` estreitado para `narrowed`
+    fn check_narrowed(&mut self, name: &str, narrowed: Type, stmt: &Stmt) {
+        self.begin_scope();
+        self.define(name.to_string(), narrowed);
+        self.check_stmt(stmt);
+        self.end_scope();
+    }
+
+    /// Acesso com `.`/`[]` a uma variável que pode ser null
+    fn check_not_null(&mut self, object: &Expr, member: &str, location: &SourceLocation) {
+        if let Expr::Variable(name, _) = object {
+            let nullable = match self.resolve(name) {
+                Some(Type::Null) => true,
+                Some(Type::Union(members)) => members.contains(&Type::Null),
+                _ => false,
+            };
+            if nullable {
+                self.errors.push(DryadError::new(
+                    3010,
+                    &format!(
+                        "'{}' pode ser null ao acessar '{}'; use '?.' ou verifique antes (linha {})",
+                        name, member, location.line
+                    ),
+                ));
+            }
+        }
+    }
+
+    fn define_pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Identifier(name) | Pattern::Rest(name) => self.define(name.clone(), Type::Any),
+            Pattern::Array(patterns) | Pattern::Tuple(patterns) | Pattern::Constructor(_, _, patterns) => {
+                for p in patterns {
+                    self.define_pattern(p);
+                }
+            }
+            Pattern::Object(entries) => {
+                for (_, p) in entries {
+                    self.define_pattern(p);
+                }
+            }
+            Pattern::Literal(_) | Pattern::Wildcard => {}
+        }
+    }
+
     fn is_assignable(&self, target: &Type, source: &Type) -> bool {
         if target == &Type::Any || source == &Type::Any {
             return true;
@@ -240,6 +1147,49 @@ impl TypeChecker {
             return true;
         }
 
+        match (target, source) {
+            (Type::Class(name), _) | (_, Type::Class(name)) if self.aliases.contains_key(name) => {
+                return self.is_assignable(&self.expand_aliases(target), &self.expand_aliases(source));
+            }
+            (_, Type::Union(members)) => return members.iter().all(|m| self.is_assignable(target, m)),
+            (Type::Union(members), _) => return members.iter().any(|m| self.is_assignable(m, source)),
+            (_, Type::Literal(lit)) => return *target == literal_base(lit),
+            (Type::Object(fields), Type::Object(source_fields)) => {
+                return fields.iter().all(|field| match source_fields.iter().find(|f| f.name == field.name) {
+                    Some(source_field) => {
+                        (field.optional || !source_field.optional)
+                            && self.is_assignable(&field.field_type, &source_field.field_type)
+                    }
+                    None => field.optional,
+                });
+            }
+            // Instâncias têm campos dinâmicos: só membros declarados são comparados
+            (Type::Object(fields), Type::Class(_) | Type::Generic(..)) => {
+                return fields.iter().all(|field| match self.member_type(source, &field.name) {
+                    Some(member) => self.is_assignable(&field.field_type, &member),
+                    None => true,
+                });
+            }
+            (Type::Array(target), Type::Array(source)) => return self.is_assignable(target, source),
+            // Argumentos de tipo são invariantes: `Box<number>` não é um `Box<string>`
+            (Type::Generic(target_name, target_args), Type::Generic(source_name, source_args))
+                if target_name == source_name =>
+            {
+                return target_args.len() == source_args.len()
+                    && target_args
+                        .iter()
+                        .zip(source_args)
+                        .all(|(t, s)| self.is_assignable(t, s) && self.is_assignable(s, t));
+            }
+            // Entre tipos distintos os argumentos são apagados (`implements Comparable<T>`)
+            (Type::Generic(target_name, _), Type::Generic(source_name, _))
+            | (Type::Generic(target_name, _), Type::Class(source_name))
+            | (Type::Class(target_name), Type::Generic(source_name, _)) => {
+                return self.is_subtype(source_name, target_name);
+            }
+            _ => {}
+        }
+
         // Check inheritance and interfaces
         if let (Type::Class(target_name), Type::Class(source_name)) = (target, source) {
             if self.is_subtype(source_name, target_name) {
@@ -271,6 +1221,88 @@ impl TypeChecker {
         false
     }
 
+    /// Aridade (obrigatórios, total) do método na classe ou na superclasse mais próxima
+    fn method_arity(&self, class_name: &str, method: &str) -> Option<(usize, usize)> {
+        let mut current = Some(class_name);
+        let mut visited = HashSet::new();
+        while let Some(name) = current {
+            if !visited.insert(name) {
+                break;
+            }
+            let cls = self.classes.get(name)?;
+            if let Some(arity) = cls.arities.get(method) {
+                return Some(*arity);
+            }
+            current = cls.parent.as_deref();
+        }
+        None
+    }
+
+    /// Métodos abstratos cuja declaração mais próxima na hierarquia ainda é abstrata
+    fn unimplemented_abstract_methods(&self, class_name: &str) -> Vec<String> {
+        let mut resolved = HashSet::new();
+        let mut missing = Vec::new();
+        let mut current = Some(class_name);
+        while let Some(name) = current {
+            let Some(cls) = self.classes.get(name) else { break };
+            let mut declared: Vec<&String> = cls.arities.keys().collect();
+            declared.sort();
+            for method in declared {
+                if resolved.insert(method.clone()) && cls.abstract_methods.contains(method) {
+                    missing.push(method.clone());
+                }
+            }
+            current = cls.parent.as_deref().filter(|p| *p != class_name);
+        }
+        missing
+    }
+
+    /// Métodos de interface ausentes ou com aridade incompatível; implementações
+    /// padrão da interface passam a ser membros da classe
+    fn check_interface_conformance(&mut self, class_name: &str, interfaces: &[String], location: &SourceLocation) {
+        let mut problems = Vec::new();
+        for interface_name in interfaces {
+            let Some(interface) = self.interfaces.get(interface_name) else { continue };
+            let mut methods: Vec<(&String, &Type)> = interface.methods.iter().collect();
+            methods.sort_by_key(|(name, _)| *name);
+            let mut defaults = Vec::new();
+            for (method, method_type) in methods {
+                let expected = match method_type {
+                    Type::Function(params, _) => params.len(),
+                    _ => 0,
+                };
+                match self.method_arity(class_name, method) {
+                    Some((required, total)) if expected < required || expected > total => problems.push(format!(
+                        "'{}' de '{}' espera {} parâmetro(s), mas a classe declara {}",
+                        method, interface_name, expected, total
+                    )),
+                    Some(_) => {}
+                    None if interface.defaults.contains(method) => {
+                        defaults.push((method.clone(), method_type.clone(), expected));
+                    }
+                    None => problems.push(format!("'{}' de '{}' não implementado", method, interface_name)),
+                }
+            }
+            if let Some(cls) = self.classes.get_mut(class_name) {
+                for (method, method_type, arity) in defaults {
+                    cls.members.insert(method.clone(), method_type);
+                    cls.arities.insert(method, (arity, arity));
+                }
+            }
+        }
+        if !problems.is_empty() {
+            self.errors.push(DryadError::new(
+                3014,
+                &format!(
+                    "Classe '{}' não satisfaz suas interfaces: {} (linha {})",
+                    class_name,
+                    problems.join("; "),
+                    location.line
+                ),
+            ));
+        }
+    }
+
     fn is_subtype_interface(&self, interface_name: &str, target: &str) -> bool {
         if interface_name == target {
             return true;
@@ -302,3 +1334,222 @@ impl TypeChecker {
         None
     }
 }
+
+/// Tipo resultante de uma operação aritmética entre tipos numéricos: o mais largo
+/// entre int < bigint < decimal; `number` (literais sem sufixo) adota o tipo exato
+fn promoted_numeric(left: &Type, right: &Type) -> Option<Type> {
+    let rank = |t: &Type| match t {
+        Type::Number => Some(0),
+        Type::Int => Some(1),
+        Type::BigInt => Some(2),
+        Type::Decimal => Some(3),
+        _ => None,
+    };
+    let widest = match (rank(left), rank(right)) {
+        (Some(a), Some(b)) => a.max(b),
+        (Some(a), None) | (None, Some(a)) if a > 0 && (left == &Type::Any || right == &Type::Any) => a,
+        _ => return None,
+    };
+    Some(match widest {
+        1 => Type::Int,
+        2 => Type::BigInt,
+        3 => Type::Decimal,
+        _ => Type::Number,
+    })
+}
+
+/// Substitui parâmetros de tipo (`Class("T")`) pelos tipos ligados
+fn substitute(t: &Type, bindings: &HashMap<String, Type>) -> Type {
+    match t {
+        Type::Class(name) => bindings.get(name).cloned().unwrap_or_else(|| t.clone()),
+        _ => map_type(t, &mut |inner| substitute(inner, bindings)),
+    }
+}
+
+/// Aplica `f` aos tipos diretamente contidos em `t`
+fn map_type(t: &Type, f: &mut dyn FnMut(&Type) -> Type) -> Type {
+    match t {
+        Type::Array(inner) => Type::Array(Box::new(f(inner))),
+        Type::Tuple(items) => Type::Tuple(items.iter().map(&mut *f).collect()),
+        Type::Function(params, ret) => {
+            let params = params.iter().map(&mut *f).collect();
+            Type::Function(params, Box::new(f(ret)))
+        }
+        Type::Generic(name, args) => Type::Generic(name.clone(), args.iter().map(&mut *f).collect()),
+        Type::Union(members) => union_of(members.iter().map(&mut *f).collect()),
+        Type::Object(fields) => Type::Object(
+            fields
+                .iter()
+                .map(|field| ObjectTypeField { field_type: f(&field.field_type), ..field.clone() })
+                .collect(),
+        ),
+        _ => t.clone(),
+    }
+}
+
+/// União normalizada: achatada, sem repetições; `any` absorve o resto e uma
+/// união vazia (ramo impossível) vira `any`
+fn union_of(types: Vec<Type>) -> Type {
+    let mut members: Vec<Type> = Vec::new();
+    for t in types {
+        let flattened = match t {
+            Type::Union(inner) => inner,
+            Type::Any => return Type::Any,
+            t => vec![t],
+        };
+        for t in flattened {
+            if !members.contains(&t) {
+                members.push(t);
+            }
+        }
+    }
+    match members.len() {
+        0 => Type::Any,
+        1 => members.remove(0),
+        _ => Type::Union(members),
+    }
+}
+
+/// Variáveis sem anotação não herdam o formato de um objeto literal: objetos
+/// são dinâmicos e ganham campos depois
+fn widen_inferred(t: Type) -> Type {
+    match t {
+        Type::Object(_) => Type::Any,
+        t => t,
+    }
+}
+
+fn literal_base(lit: &Literal) -> Type {
+    match lit {
+        Literal::Number(_) => Type::Number,
+        Literal::Int(_) => Type::Int,
+        Literal::BigInt(_) => Type::BigInt,
+        Literal::Decimal(_) => Type::Decimal,
+        Literal::String(_) => Type::String,
+        Literal::Bool(_) => Type::Bool,
+        Literal::Null => Type::Null,
+        // Como padrão de `match`, uma regex casa strings
+        Literal::Regex(..) => Type::String,
+    }
+}
+
+fn mentions_literal(t: &Type) -> bool {
+    match t {
+        Type::Literal(_) => true,
+        Type::Union(members) => members.iter().any(mentions_literal),
+        _ => false,
+    }
+}
+
+fn object_fields(t: &Type) -> Option<&[ObjectTypeField]> {
+    match t {
+        Type::Object(fields) => Some(fields),
+        Type::Union(members) => members.iter().find_map(object_fields),
+        _ => None,
+    }
+}
+
+fn array_element(t: &Type) -> Option<Type> {
+    match t {
+        Type::Array(inner) => Some((**inner).clone()),
+        Type::Union(members) => members.iter().find_map(array_element),
+        _ => None,
+    }
+}
+
+/// Se `typeof` de um valor do tipo `t` pode resultar em `tag`
+fn has_tag(t: &Type, tag: &str) -> bool {
+    match t {
+        Type::Literal(lit) => has_tag(&literal_base(lit), tag),
+        Type::String => tag == "string",
+        Type::Number => tag == "number",
+        Type::Int => tag == "int",
+        Type::BigInt => tag == "bigint",
+        Type::Decimal => tag == "decimal",
+        Type::Bool => tag == "boolean",
+        Type::Null => tag == "null",
+        Type::Array(_) => tag == "array",
+        Type::Tuple(_) => tag == "tuple",
+        Type::Object(_) => tag == "object",
+        Type::Function(..) => tag == "function" || tag == "lambda",
+        Type::Class(_) | Type::Generic(..) => tag == "instance" || tag == "variant",
+        _ => false,
+    }
+}
+
+/// Tipo garantido por `typeof x == tag`
+fn type_for_tag(tag: &str) -> Type {
+    match tag {
+        "string" => Type::String,
+        "number" => Type::Number,
+        "int" => Type::Int,
+        "bigint" => Type::BigInt,
+        "decimal" => Type::Decimal,
+        "boolean" => Type::Bool,
+        "null" => Type::Null,
+        "array" => Type::Array(Box::new(Type::Any)),
+        _ => Type::Any,
+    }
+}
+
+/// Tipo do valor casado por um padrão quando o alvo é uma união
+fn narrow_by_pattern(target: &Type, pattern: &Pattern) -> Option<Type> {
+    let Type::Union(members) = target else {
+        return None;
+    };
+    match pattern {
+        Pattern::Literal(Literal::Null) => Some(Type::Null),
+        Pattern::Literal(lit) if members.contains(&Type::Literal(lit.clone())) => Some(Type::Literal(lit.clone())),
+        Pattern::Literal(lit) => Some(literal_base(lit)),
+        Pattern::Constructor(Some(enum_name), _, _) => Some(Type::Class(enum_name.clone())),
+        _ => None,
+    }
+}
+
+/// Liga os parâmetros de tipo em `names` casando a estrutura do parâmetro com a
+/// do argumento; a primeira ligação concreta vence
+fn infer(param: &Type, arg: &Type, names: &HashSet<&str>, bindings: &mut HashMap<String, Type>) {
+    let pairs = |params: &[Type], args: &[Type], bindings: &mut HashMap<String, Type>| {
+        for (p, a) in params.iter().zip(args) {
+            infer(p, a, names, bindings);
+        }
+    };
+    match (param, arg) {
+        (Type::Class(name), _)
+            if names.contains(name.as_str())
+                && *arg != Type::Any
+                && matches!(bindings.get(name), None | Some(Type::Any)) =>
+        {
+            bindings.insert(name.clone(), arg.clone());
+        }
+        // `T?` casa com o argumento não-null
+        (Type::Union(ps), _) if *arg != Type::Null => {
+            for p in ps.iter().filter(|p| **p != Type::Null) {
+                infer(p, arg, names, bindings);
+            }
+        }
+        (Type::Array(p), Type::Array(a)) => infer(p, a, names, bindings),
+        (Type::Tuple(ps), Type::Tuple(args)) => pairs(ps, args, bindings),
+        (Type::Function(ps, pr), Type::Function(args, ar)) => {
+            pairs(ps, args, bindings);
+            infer(pr, ar, names, bindings);
+        }
+        (Type::Generic(pn, ps), Type::Generic(an, args)) if pn == an => pairs(ps, args, bindings),
+        _ => {}
+    }
+}
+
+/// Blocos que sempre saem do fluxo atual (return/throw/break/continue)
+fn always_exits(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Return(..) | Stmt::Throw(..) | Stmt::Break(..) | Stmt::Continue(..) => true,
+        Stmt::Block(statements, _) => statements.last().is_some_and(always_exits),
+        Stmt::IfElse(_, then_stmt, else_stmt, _) => always_exits(then_stmt) && always_exits(else_stmt),
+        _ => false,
+    }
+}
+
+/// Padrões que casam qualquer valor
+fn is_irrefutable(pattern: &Pattern) -> bool {
+    matches!(pattern, Pattern::Identifier(_) | Pattern::Wildcard)
+}
@@ -0,0 +1,72 @@
+// crates/dryad_checker/tests/abstract_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<(u16, String)> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| (e.code(), e.message().to_string())).collect(),
+    }
+}
+
+#[test]
+fn test_conforming_hierarchy() {
+    let source = r#"
+        interface Shape {
+            function area(): number;
+            function describe(): string { return "forma"; }
+        }
+        abstract class Polygon implements Shape {
+            abstract function area(): number;
+        }
+        class Square extends Polygon {
+            function area(): number { return 4; }
+        }
+        let s: Shape = new Square();
+        let d: string = s.describe();
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+}
+
+#[test]
+fn test_interface_conformance() {
+    let errors = check(r#"
+        interface I { function f(a, b); function g(); }
+        class C implements I { function f(a) { return a; } }
+    "#);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].0, 3014);
+    assert!(errors[0].1.contains("'f' de 'I' espera 2"), "{}", errors[0].1);
+    assert!(errors[0].1.contains("'g' de 'I' não implementado"), "{}", errors[0].1);
+
+    // Métodos herdados contam para a interface
+    let errors = check(r#"
+        interface I { function f(); }
+        class A { function f() { } }
+        class B extends A implements I { }
+    "#);
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn test_abstract_rules() {
+    let errors = check(r#"
+        abstract class A { abstract function f(); abstract function g(); }
+        class B extends A { function g() { } }
+        let a = new A();
+    "#);
+    let codes: Vec<u16> = errors.iter().map(|(code, _)| *code).collect();
+    assert_eq!(codes, vec![3016, 3015], "{:?}", errors);
+    assert!(errors[0].1.contains("'f'") && !errors[0].1.contains("'g'"), "{}", errors[0].1);
+}
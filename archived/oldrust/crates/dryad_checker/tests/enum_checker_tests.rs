@@ -0,0 +1,94 @@
+// crates/dryad_checker/tests/enum_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+const SHAPE: &str = "enum Shape { Circle(r), Rect(w, h), Empty }\nlet s = Shape.Circle(1);\n";
+
+#[test]
+fn test_exhaustive_match_is_accepted() {
+    let input = format!(
+        "{}let a = match s {{ Shape.Circle(r) => r, Rect(w, h) => w, Shape.Empty => 0 }};",
+        SHAPE
+    );
+    assert!(check(&input).is_empty());
+
+    let input = format!("{}let a = match s {{ Shape.Circle(r) => r, _ => 0 }};", SHAPE);
+    assert!(check(&input).is_empty());
+}
+
+#[test]
+fn test_non_exhaustive_match() {
+    let input = format!("{}let a = match s {{ Shape.Circle(r) => r }};", SHAPE);
+    let errors = check(&input);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("Match não exaustivo"));
+    assert!(errors[0].contains("Shape.Rect, Shape.Empty"));
+}
+
+#[test]
+fn test_guarded_or_refutable_arms_do_not_cover_variant() {
+    let input = format!(
+        "{}let a = match s {{ Shape.Circle(r) if r > 0 => r, Shape.Rect(1, h) => h, Shape.Empty => 0 }};",
+        SHAPE
+    );
+    let errors = check(&input);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("Shape.Circle, Shape.Rect"));
+}
+
+#[test]
+fn test_unreachable_arms() {
+    let input = format!(
+        "{}let a = match s {{ _ => 0, Shape.Empty => 1 }};\nlet b = match s {{ Circle(r) => r, Shape.Circle(x) => x, _ => 0 }};",
+        SHAPE
+    );
+    let errors = check(&input);
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|e| e.contains("Braço inalcançável")));
+}
+
+#[test]
+fn test_unknown_variants_and_arity() {
+    let input = format!(
+        "{}let a = Shape.Triangle(1);\nlet b = Shape.Rect(1);\nlet c = match s {{ Shape.Rect(w) => w, _ => 0 }};",
+        SHAPE
+    );
+    let errors = check(&input);
+    assert_eq!(errors.len(), 3);
+    assert!(errors[0].contains("não tem a variante 'Triangle'"));
+    assert!(errors[1].contains("espera 2 campo(s), recebeu 1"));
+    assert!(errors[2].contains("tem 1 campo(s), a variante tem 2"));
+}
+
+#[test]
+fn test_bare_unit_variant_pattern_is_reported() {
+    let input = format!("{}let a = match s {{ Empty => 0 }};", SHAPE);
+    let errors = check(&input);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("use 'Shape.Empty'"));
+}
+
+#[test]
+fn test_duplicate_variant() {
+    let errors = check("enum Cor { Azul, Azul }");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("declarada mais de uma vez"));
+}
@@ -0,0 +1,39 @@
+// crates/dryad_checker/tests/generator_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_yield_only_inside_generators() {
+    assert!(check("function* g() { let x = yield 1; yield x; }").is_empty());
+
+    let errors = check("function f() {\n  yield 1;\n}");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("'yield' fora de uma function* (linha 2)"), "{:?}", errors);
+}
+
+#[test]
+fn test_range_bounds_must_be_numbers() {
+    assert!(check("let n = 3;\nfor (i in 0..n) { print(i); }").is_empty());
+
+    let errors = check("for (i in 0..\"dez\") { }");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("Limites do intervalo '..' devem ser números"), "{:?}", errors);
+}
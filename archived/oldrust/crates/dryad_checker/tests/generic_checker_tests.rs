@@ -0,0 +1,113 @@
+// crates/dryad_checker/tests/generic_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_call_site_inference() {
+    let source = r#"
+        function first<T>(xs: T[]): T { return xs[0]; }
+        let n: number = first([1, 2, 3]);
+        let s: string = first(["a", "b"]);
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        function first<T>(xs: T[]): T { return xs[0]; }
+        let s: string = first([1, 2, 3]);
+    "#);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+}
+
+#[test]
+fn test_conflicting_type_arguments() {
+    let errors = check(r#"
+        function pair<T>(a: T, b: T): T[] { return [a, b]; }
+        let ok = pair(1, 2);
+        let bad = pair(1, "dois");
+    "#);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].contains("Argumento 2 de 'pair'"), "{:?}", errors);
+}
+
+#[test]
+fn test_bounds_are_checked() {
+    let source = r#"
+        interface Comparable<T> {
+            function compareTo(other: T): number;
+        }
+        class Money implements Comparable<Money> {
+            function compareTo(other: Money): number { return 0; }
+        }
+        function max<T extends Comparable<T>>(a: T, b: T): T { return a; }
+        let m: Money = max(new Money(), new Money());
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        interface Comparable<T> {
+            function compareTo(other: T): number;
+        }
+        function max<T extends Comparable<T>>(a: T, b: T): T { return a; }
+        max(1, 2);
+    "#);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].contains("não satisfaz o limite"), "{:?}", errors);
+}
+
+#[test]
+fn test_generic_class_members() {
+    let source = r#"
+        class Box<T> {
+            function init(value: T) { this.value = value; }
+            function unwrap(): T { return this.value; }
+            function replace(value: T) { this.value = value; }
+        }
+        let b: Box<number> = new Box(5);
+        let n: number = b.unwrap();
+        b.replace(6);
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        class Box<T> {
+            function init(value: T) { this.value = value; }
+            function unwrap(): T { return this.value; }
+            function replace(value: T) { this.value = value; }
+        }
+        let b = new Box(5);
+        let s: string = b.unwrap();
+        b.replace("seis");
+        let c: Box<string> = new Box(7);
+    "#);
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+}
+
+#[test]
+fn test_type_params_inside_generic_body() {
+    let source = r#"
+        function identity<T>(x: T): T {
+            let y: T = x;
+            return y;
+        }
+        let s: string = identity("a");
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+}
@@ -0,0 +1,48 @@
+// crates/dryad_checker/tests/nullish_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_access_on_possibly_null_variable() {
+    let errors = check("let user = null;\nprint(user.name);");
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].contains("'user' pode ser null ao acessar 'name'; use '?.' ou verifique antes (linha 2)"),
+        "{:?}",
+        errors
+    );
+
+    assert!(check("let user = null;\nprint(user?.name);\nprint(user?.[0]);").is_empty());
+}
+
+#[test]
+fn test_narrowing_after_null_checks() {
+    assert!(check("let u = null;\nif (u != null) { print(u.name); }").is_empty());
+    assert!(check("let u = null;\nif (u == null) { print(0); } else { print(u.name); }").is_empty());
+    assert!(check("let u = null;\nlet ok = u != null && u.ativo;").is_empty());
+    assert!(check("function f() {\n  let u = null;\n  if (u == null) { return 0; }\n  return u.id;\n}").is_empty());
+    assert!(check("let u = null;\nu ??= { name: \"Ana\" };\nprint(u.name);").is_empty());
+
+    // O estreitamento não vaza para fora do bloco verificado
+    let errors = check("let u = null;\nif (u != null) { print(u.a); }\nprint(u.b);");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("acessar 'b'"), "{:?}", errors);
+}
@@ -0,0 +1,36 @@
+// crates/dryad_checker/tests/numeric_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_suffixed_literals_have_their_own_types() {
+    assert!(check("let a: int = 1i; let b: bigint = 1n; let c: decimal = 1.5d;").is_empty());
+    assert_eq!(check("let a: int = 1.5d;").len(), 1);
+    assert_eq!(check("let a: bigint = 1;").len(), 1);
+}
+
+#[test]
+fn test_arithmetic_promotes_to_widest_type() {
+    assert!(check("let a: int = 1i + 2;").is_empty());
+    assert!(check("let b: bigint = 1i * 2n;").is_empty());
+    assert!(check("let c: decimal = 2n + 0.5d;").is_empty());
+    assert_eq!(check("let d: int = 1i + 1n;").len(), 1);
+}
@@ -0,0 +1,54 @@
+// crates/dryad_checker/tests/operator_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_overloaded_operator_is_accepted() {
+    let source = r#"
+        class Money {
+            operator +(other): Money { return this; }
+        }
+        class Euro extends Money {}
+        let total: Money = new Money() + new Money();
+        let mixed = new Euro() + new Money();
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+}
+
+#[test]
+fn test_operator_return_type_is_used() {
+    let source = r#"
+        class Money {
+            operator *(factor): Money { return this; }
+        }
+        let n: number = new Money() * 2;
+    "#;
+    let errors = check(source);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].contains("Tipo incompatível na variável 'n'"), "{:?}", errors);
+}
+
+#[test]
+fn test_missing_operator_is_reported() {
+    let errors = check("class Point {}\nlet p = new Point() * 2;");
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].contains("Operação '*' não pode ser aplicada"), "{:?}", errors);
+}
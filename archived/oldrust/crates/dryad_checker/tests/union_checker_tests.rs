@@ -0,0 +1,141 @@
+// crates/dryad_checker/tests/union_checker_tests.rs
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::Parser;
+
+fn check(input: &str) -> Vec<String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    match TypeChecker::new().check(&program) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(|e| e.message().to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_union_assignability() {
+    let source = r#"
+        let id: number | string = 1;
+        id = "abc";
+        let name: string? = null;
+        name = "Ana";
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        let id: number | string = true;
+        let n: number = id;
+    "#);
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+}
+
+#[test]
+fn test_literal_unions_and_aliases() {
+    let source = r#"
+        type Direction = "left" | "right";
+        let d: Direction = "left";
+        function turn(dir: Direction): string { return dir; }
+        turn("right");
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        type Direction = "left" | "right";
+        let d: Direction = "up";
+        function turn(dir: Direction): string { return dir; }
+        turn("down");
+    "#);
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+}
+
+#[test]
+fn test_object_shapes() {
+    let source = r#"
+        type Person = { name: string, age?: number };
+        let p: Person = { name: "Ana" };
+        let q: Person = { name: "Bia", age: 30 };
+        let n: string = p.name;
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        type Person = { name: string, age?: number };
+        let p: Person = { age: 30 };
+        let q: Person = { name: "Bia" };
+        let a: number = q.age;
+        let e = q.email;
+    "#);
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("email")), "{:?}", errors);
+}
+
+#[test]
+fn test_narrowing() {
+    let source = r#"
+        class Cat { function meow(): string { return "miau"; } }
+        function describe(x: number | string): string {
+            if (typeof x == "string") {
+                let s: string = x;
+                return s;
+            }
+            let n: number = x;
+            return "numero";
+        }
+        function greet(name: string?): string {
+            if (name != null) {
+                let s: string = name;
+                return s;
+            }
+            return "anonimo";
+        }
+        function sound(pet: Cat | null): string {
+            if (pet instanceof Cat) {
+                return pet.meow();
+            }
+            return "";
+        }
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        function describe(x: number | string): string {
+            let s: string = x;
+            return s;
+        }
+    "#);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+}
+
+#[test]
+fn test_literal_match_exhaustiveness() {
+    let source = r#"
+        type Direction = "left" | "right";
+        function turn(dir: Direction): number {
+            return match (dir) {
+                "left" => -1,
+                "right" => 1
+            };
+        }
+    "#;
+    assert!(check(source).is_empty(), "{:?}", check(source));
+
+    let errors = check(r#"
+        type Direction = "left" | "right" | "up";
+        function turn(dir: Direction): number {
+            return match (dir) {
+                "left" => -1,
+                "right" => 1
+            };
+        }
+    "#);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(errors[0].contains("up"), "{:?}", errors);
+}
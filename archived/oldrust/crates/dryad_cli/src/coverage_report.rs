@@ -0,0 +1,166 @@
+// crates/dryad_cli/src/coverage_report.rs
+//! Relatórios de cobertura do `dryad test --coverage`: `lcov.info`, um `index.html`
+//! com o resumo por arquivo e uma página por arquivo com as linhas anotadas.
+
+use dryad_runtime::coverage::{Coverage, FileCoverage};
+use std::fs;
+use std::path::Path;
+
+/// Escreve `lcov.info`, `index.html` e as páginas por arquivo em `dir`.
+pub fn write_reports(coverage: &Coverage, dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Erro ao criar diretório de cobertura '{}': {}", dir.display(), e))?;
+
+    write(&dir.join("lcov.info"), &coverage.to_lcov())?;
+    write(&dir.join("index.html"), &index_html(coverage))?;
+    for (file, file_coverage) in &coverage.files {
+        let source = fs::read_to_string(file).unwrap_or_default();
+        write(
+            &dir.join(page_name(file)),
+            &file_html(file, file_coverage, &source),
+        )?;
+    }
+    Ok(())
+}
+
+/// Resumo em texto para o terminal
+pub fn print_summary(coverage: &Coverage) {
+    println!("\ncobertura:");
+    println!("{:<50} {:>10} {:>10}", "arquivo", "linhas", "branches");
+    for (file, c) in &coverage.files {
+        println!(
+            "{:<50} {:>10} {:>10}",
+            file.display().to_string(),
+            percent(c.lines_hit(), c.lines_found()),
+            percent(c.branches_hit(), c.branches_found())
+        );
+    }
+    let (lines_hit, lines_found, branches_hit, branches_found) = totals(coverage);
+    println!(
+        "{:<50} {:>10} {:>10}",
+        "total",
+        percent(lines_hit, lines_found),
+        percent(branches_hit, branches_found)
+    );
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Erro ao escrever '{}': {}", path.display(), e))
+}
+
+fn totals(coverage: &Coverage) -> (usize, usize, usize, usize) {
+    coverage.files.values().fold((0, 0, 0, 0), |acc, c| {
+        (
+            acc.0 + c.lines_hit(),
+            acc.1 + c.lines_found(),
+            acc.2 + c.branches_hit(),
+            acc.3 + c.branches_found(),
+        )
+    })
+}
+
+fn percent(hit: usize, found: usize) -> String {
+    if found == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}%", hit as f64 * 100.0 / found as f64)
+    }
+}
+
+/// Nome da página HTML de um arquivo: o caminho com separadores trocados por `_`
+fn page_name(file: &Path) -> String {
+    let flat: String = file
+        .display()
+        .to_string()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect();
+    format!("{}.html", flat.trim_start_matches(['.', '_']))
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+table{border-collapse:collapse}td,th{padding:2px 10px;text-align:left}\
+th{border-bottom:1px solid #999}.num{text-align:right}\
+pre{margin:0}.hit{background:#dfd}.miss{background:#fdd}.partial{background:#ffd}\
+.ln{color:#888;text-align:right;user-select:none}.cnt{color:#555;text-align:right}";
+
+fn index_html(coverage: &Coverage) -> String {
+    let mut rows = String::new();
+    for (file, c) in &coverage.files {
+        rows.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td class=\"num\">{}</td><td class=\"num\">{}/{}</td><td class=\"num\">{}</td><td class=\"num\">{}/{}</td></tr>\n",
+            html_escape(&page_name(file)),
+            html_escape(&file.display().to_string()),
+            percent(c.lines_hit(), c.lines_found()),
+            c.lines_hit(),
+            c.lines_found(),
+            percent(c.branches_hit(), c.branches_found()),
+            c.branches_hit(),
+            c.branches_found()
+        ));
+    }
+    let (lines_hit, lines_found, branches_hit, branches_found) = totals(coverage);
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Cobertura Dryad</title><style>{}</style></head><body>\n\
+<h1>Cobertura</h1>\n<table>\n<tr><th>Arquivo</th><th>Linhas</th><th></th><th>Branches</th><th></th></tr>\n{}\
+<tr><th>Total</th><th class=\"num\">{}</th><th class=\"num\">{}/{}</th><th class=\"num\">{}</th><th class=\"num\">{}/{}</th></tr>\n</table>\n</body></html>\n",
+        STYLE,
+        rows,
+        percent(lines_hit, lines_found),
+        lines_hit,
+        lines_found,
+        percent(branches_hit, branches_found),
+        branches_hit,
+        branches_found
+    )
+}
+
+fn file_html(file: &Path, coverage: &FileCoverage, source: &str) -> String {
+    let mut rows = String::new();
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let branch = coverage.branches.get(&line);
+        let (class, count) = match coverage.lines.get(&line) {
+            Some(0) => ("miss", "0".to_string()),
+            Some(hits) => {
+                // Linha executada mas com algum lado da decisão nunca tomado
+                let partial = branch.is_some_and(|counts| counts.contains(&0));
+                (if partial { "partial" } else { "hit" }, hits.to_string())
+            }
+            None => ("", String::new()),
+        };
+        let title = branch
+            .map(|counts| format!(" title=\"verdadeiro: {}, falso: {}\"", counts[0], counts[1]))
+            .unwrap_or_default();
+        rows.push_str(&format!(
+            "<tr class=\"{}\"{}><td class=\"ln\">{}</td><td class=\"cnt\">{}</td><td><pre>{}</pre></td></tr>\n",
+            class,
+            title,
+            line,
+            count,
+            html_escape(text)
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{name}</title><style>{style}</style></head><body>\n\
+<p><a href=\"index.html\">&larr; índice</a></p>\n<h1>{name}</h1>\n\
+<p>Linhas: {lines} ({lh}/{lf}) &middot; Branches: {branches} ({bh}/{bf})</p>\n<table>\n{rows}</table>\n</body></html>\n",
+        name = html_escape(&file.display().to_string()),
+        style = STYLE,
+        lines = percent(coverage.lines_hit(), coverage.lines_found()),
+        lh = coverage.lines_hit(),
+        lf = coverage.lines_found(),
+        branches = percent(coverage.branches_hit(), coverage.branches_found()),
+        bh = coverage.branches_hit(),
+        bf = coverage.branches_found(),
+        rows = rows
+    )
+}
@@ -6,9 +6,11 @@ use dryad_lexer::Token;
 use dryad_parser::Parser as DryadParser;
 use dryad_runtime::Interpreter;
 use std::fs;
-use std::io::{self, Write};
 
 mod oak_adapter;
+mod coverage_report;
+mod repl;
+mod test_runner;
 use oak_adapter::OakModuleResolver;
 
 #[derive(Parser)]
@@ -43,6 +45,47 @@ enum Commands {
         /// Usa compilação JIT para funções quentes (experimental)
         #[arg(long)]
         jit: bool,
+        /// Verifica em runtime os tipos anotados (parâmetros, retornos, propriedades e `let`)
+        #[arg(long)]
+        strict_types: bool,
+        /// Amostra a pilha de chamadas durante a execução e mostra o tempo por função
+        #[arg(long)]
+        profile: bool,
+        /// Intervalo de amostragem do profiler, em milissegundos
+        #[arg(long, default_value_t = 1)]
+        profile_interval: u64,
+        /// Arquivo de pilhas no formato folded (para flamegraph.pl/inferno)
+        #[arg(long, default_value = "dryad-profile.folded")]
+        profile_out: String,
+    },
+    /// Executa os testes (arquivos *_test.dryad e blocos `test "nome" { ... }`)
+    Test {
+        /// Arquivos ou diretórios de teste (padrão: diretório atual)
+        paths: Vec<String>,
+        /// Executa apenas os testes cujo nome completo contém este texto
+        #[arg(short, long)]
+        filter: Option<String>,
+        /// Número de testes executados em paralelo (padrão: número de CPUs)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// Gera um relatório JUnit XML no caminho informado
+        #[arg(long)]
+        junit: Option<String>,
+        /// Coleta cobertura de linhas e branches (lcov + HTML)
+        #[arg(long)]
+        coverage: bool,
+        /// Diretório dos relatórios de cobertura
+        #[arg(long, default_value = "coverage")]
+        coverage_dir: String,
+        /// Executa os testes na VM de bytecode
+        #[arg(long)]
+        compile: bool,
+        /// Permite operações inseguras (ex: native_set_env)
+        #[arg(long)]
+        allow_unsafe: bool,
+        /// Permite execução de comandos do sistema (ex: native_exec)
+        #[arg(long)]
+        allow_exec: bool,
     },
     /// Inicia o modo interativo (REPL)
     Repl,
@@ -72,7 +115,15 @@ fn main() {
             sandbox,
             compile,
             jit,
+            strict_types,
+            profile,
+            profile_interval,
+            profile_out,
         }) => {
+            let profile = profile.then(|| ProfileOptions {
+                interval: std::time::Duration::from_millis((*profile_interval).max(1)),
+                output: std::path::PathBuf::from(profile_out),
+            });
             if let Err(e) = run_file(
                 file,
                 *verbose,
@@ -81,13 +132,49 @@ fn main() {
                 sandbox.as_deref(),
                 *compile,
                 *jit,
+                *strict_types,
+                profile,
             ) {
                 eprintln!("Erro: {}", e);
                 std::process::exit(1);
             }
         }
+        Some(Commands::Test {
+            paths,
+            filter,
+            jobs,
+            junit,
+            coverage,
+            coverage_dir,
+            compile,
+            allow_unsafe,
+            allow_exec,
+        }) => {
+            let options = test_runner::TestOptions {
+                paths: paths.clone(),
+                filter: filter.clone(),
+                jobs: jobs.unwrap_or_else(|| {
+                    std::thread::available_parallelism()
+                        .map(|n| n.get())
+                        .unwrap_or(1)
+                }),
+                junit: junit.as_ref().map(std::path::PathBuf::from),
+                allow_unsafe: *allow_unsafe,
+                allow_exec: *allow_exec,
+                compile: *compile,
+                coverage_dir: coverage.then(|| std::path::PathBuf::from(coverage_dir)),
+            };
+            match test_runner::run_tests(&options) {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(e) => {
+                    eprintln!("Erro: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
         Some(Commands::Repl) => {
-            if let Err(e) = run_repl() {
+            if let Err(e) = repl::run_repl() {
                 eprintln!("Erro no REPL: {}", e);
                 std::process::exit(1);
             }
@@ -112,7 +199,7 @@ fn main() {
         None => {
             // Se não houver subcomando, tenta executar main.dryad
             if std::path::Path::new("main.dryad").exists() {
-                if let Err(e) = run_file("main.dryad", false, false, false, None, false, false) {
+                if let Err(e) = run_file("main.dryad", false, false, false, None, false, false, false, None) {
                     eprintln!("Erro: {}", e);
                     std::process::exit(1);
                 }
@@ -125,6 +212,13 @@ fn main() {
     }
 }
 
+/// Opções do `dryad run --profile`
+struct ProfileOptions {
+    interval: std::time::Duration,
+    output: std::path::PathBuf,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn run_file(
     filename: &str,
     verbose: bool,
@@ -133,6 +227,8 @@ fn run_file(
     sandbox: Option<&str>,
     compile: bool,
     jit: bool,
+    strict_types: bool,
+    profile: Option<ProfileOptions>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let source = fs::read_to_string(filename)
         .map_err(|e| format!("Erro ao ler arquivo '{}': {}", filename, e))?;
@@ -199,8 +295,24 @@ fn run_file(
         println!("Modo: JIT Compiler (experimental)");
         interpreter.set_jit_mode(true);
     }
+    interpreter.set_strict_types(strict_types);
 
-    let result = interpreter.execute(&program)?;
+    if let Some(options) = &profile {
+        interpreter.enable_profiling(options.interval);
+    }
+
+    let result = interpreter.execute(&program);
+
+    // O perfil é gravado mesmo quando o script termina com erro
+    if let (Some(options), Some(collected)) = (&profile, interpreter.take_profile()) {
+        fs::write(&options.output, collected.folded()).map_err(|e| {
+            format!("Erro ao escrever '{}': {}", options.output.display(), e)
+        })?;
+        eprintln!("\n{}", collected.report(20));
+        eprintln!("pilhas (folded) gravadas em {}", options.output.display());
+    }
+
+    let result = result?;
 
     if verbose {
         println!("\n=== RESULTADO ===");
@@ -212,83 +324,6 @@ fn run_file(
     Ok(())
 }
 
-fn run_repl() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Dryad v{} - REPL Interativo", env!("CARGO_PKG_VERSION"));
-    println!("Digite 'exit' para sair, 'help' para ajuda");
-
-    let mut interpreter = Interpreter::new();
-    // Configurar o resolver (Oak)
-    interpreter.set_resolver(Box::new(OakModuleResolver));
-
-    loop {
-        print!("dryad> ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-
-        let input = input.trim();
-
-        match input {
-            "exit" | "quit" => {
-                println!("Tchau!");
-                break;
-            }
-            "help" => {
-                println!("Comandos disponíveis:");
-                println!("  exit, quit - Sair do REPL");
-                println!("  help       - Mostrar esta ajuda");
-                println!("  clear      - Limpar variáveis");
-                println!("\nDigite código Dryad para executar.");
-                continue;
-            }
-            "clear" => {
-                interpreter = Interpreter::new();
-                interpreter.set_resolver(Box::new(OakModuleResolver));
-                println!("Variáveis limpas.");
-                continue;
-            }
-            "" => continue,
-            _ => {}
-        }
-
-        // Processa o código
-        match process_repl_input(input, &mut interpreter) {
-            Ok(result) => {
-                if !result.is_empty() && result != "null" {
-                    println!("=> {}", result);
-                }
-            }
-            Err(e) => println!("Erro: {}", e),
-        }
-    }
-
-    Ok(())
-}
-
-fn process_repl_input(
-    input: &str,
-    interpreter: &mut Interpreter,
-) -> Result<String, Box<dyn std::error::Error>> {
-    let mut lexer = Lexer::new(input);
-    let mut tokens = vec![];
-
-    loop {
-        let token = lexer.next_token()?;
-        if matches!(token.token, Token::Eof) {
-            tokens.push(token);
-            break;
-        }
-        tokens.push(token);
-    }
-
-    let mut parser = DryadParser::new(tokens);
-    let program = parser.parse()?;
-
-    let result = interpreter.execute(&program)?;
-    Ok(result)
-}
-
 fn check_file(filename: &str) -> Result<(), Box<dyn std::error::Error>> {
     let source = fs::read_to_string(filename)
         .map_err(|e| format!("Erro ao ler arquivo '{}': {}", filename, e))?;
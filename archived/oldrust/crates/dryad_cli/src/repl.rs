@@ -0,0 +1,454 @@
+// crates/dryad_cli/src/repl.rs
+//! REPL interativo do Dryad
+//!
+//! Baseado em `rustyline`: histórico persistente em `~/.dryad_history`,
+//! continuação automática quando chaves/parênteses/colchetes estão abertos,
+//! completação de globais e funções nativas e comandos de introspecção
+//! iniciados por `:`.
+
+use crate::oak_adapter::OakModuleResolver;
+use dryad_bytecode::{Compiler, DebugChunk};
+use dryad_checker::TypeChecker;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::ast::Program;
+use dryad_parser::Parser as DryadParser;
+use dryad_runtime::inspect::InspectOptions;
+use dryad_runtime::{Interpreter, Value};
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+const PROMPT: &str = "dryad> ";
+const CONTINUATION_PROMPT: &str = "   ... ";
+const HISTORY_FILE: &str = ".dryad_history";
+
+/// Palavras-chave oferecidas na completação
+const KEYWORDS: &[&str] = &[
+    "let", "const", "if", "else", "function", "fn", "class", "return", "for", "while", "do",
+    "break", "continue", "import", "export", "use", "try", "catch", "finally", "throw", "in",
+    "this", "super", "static", "public", "private", "protected", "extends", "async", "await",
+    "thread", "mutex", "as", "from", "match", "new", "interface", "implements", "get", "set",
+    "namespace", "true", "false", "null",
+];
+
+/// Comandos especiais do REPL
+const COMMANDS: &[(&str, &str)] = &[
+    (":help", "Mostrar esta ajuda"),
+    (":quit", "Sair do REPL (também :exit)"),
+    (":clear", "Reiniciar o interpretador (limpa variáveis)"),
+    (":type <expr>", "Mostrar o tipo inferido pelo checker"),
+    (":ast <código>", "Mostrar a AST do código"),
+    (":bytecode <código>", "Mostrar o bytecode compilado (disassembly)"),
+    (":load <arquivo>", "Carregar e executar um arquivo no contexto atual"),
+    (":native", "Listar categorias nativas (ativas marcadas com *)"),
+];
+
+/// Helper do rustyline responsável pela completação
+#[derive(Default)]
+struct ReplHelper {
+    /// Nomes conhecidos (globais, classes, funções nativas ativas)
+    names: Vec<String>,
+}
+
+impl ReplHelper {
+    fn refresh(&mut self, interpreter: &Interpreter) {
+        let mut names: Vec<String> = interpreter
+            .env
+            .variables
+            .keys()
+            .chain(interpreter.env.constants.keys())
+            .chain(interpreter.env.classes.keys())
+            .cloned()
+            .collect();
+        names.extend(interpreter.list_active_native_functions());
+        names.extend(KEYWORDS.iter().map(|k| k.to_string()));
+        names.sort();
+        names.dedup();
+        self.names = names;
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let start = before
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let word = &before[start..];
+
+        if word.is_empty() {
+            return Ok((pos, Vec::new()));
+        }
+
+        let candidates: Vec<Pair> = if word.starts_with(':') {
+            COMMANDS
+                .iter()
+                .map(|(cmd, _)| cmd.split_whitespace().next().unwrap_or(cmd))
+                .filter(|cmd| cmd.starts_with(word))
+                .map(|cmd| Pair {
+                    display: cmd.to_string(),
+                    replacement: cmd.to_string(),
+                })
+                .collect()
+        } else {
+            self.names
+                .iter()
+                .filter(|name| name.starts_with(word))
+                .map(|name| Pair {
+                    display: name.clone(),
+                    replacement: name.clone(),
+                })
+                .collect()
+        };
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Verifica se a entrada está completa (delimitadores balanceados, strings e
+/// comentários de bloco fechados). Entradas incompletas continuam na próxima linha.
+///
+/// A entrada passa pelo próprio `Lexer`, então `/` é regex ou divisão pela
+/// mesma regra da execução e delimitadores dentro de strings, comentários e
+/// regex (`/\(/`, `/[(]/`) não contam. Outros erros léxicos encerram a
+/// entrada para que sejam reportados.
+pub fn is_input_complete(source: &str) -> bool {
+    let mut lexer = Lexer::new(source);
+    let mut depth: i32 = 0;
+
+    loop {
+        match lexer.next_token() {
+            Ok(token) => match token.token {
+                Token::Eof => break,
+                Token::Symbol('(' | '[' | '{') | Token::TemplateStart | Token::InterpolationStart => depth += 1,
+                Token::Symbol(')' | ']' | '}') | Token::TemplateEnd | Token::InterpolationEnd => depth -= 1,
+                _ => {}
+            },
+            // E1002/E1003: string, template ou comentário de bloco sem fechamento
+            Err(e) => return !matches!(e.code(), 1002 | 1003),
+        }
+    }
+
+    // Delimitadores fechados a mais são erro de sintaxe, não continuação
+    depth <= 0
+}
+
+/// Estado da sessão interativa
+struct ReplSession {
+    interpreter: Interpreter,
+    checker: TypeChecker,
+    color: bool,
+}
+
+impl ReplSession {
+    fn new() -> Self {
+        Self {
+            interpreter: new_interpreter(),
+            checker: TypeChecker::new(),
+            color: std::io::stdout().is_terminal(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.interpreter = new_interpreter();
+        self.checker = TypeChecker::new();
+    }
+
+    /// Processa um comando `:algo`. Retorna `false` quando o REPL deve encerrar.
+    fn run_command(&mut self, input: &str) -> bool {
+        let (command, arg) = match input.split_once(char::is_whitespace) {
+            Some((cmd, rest)) => (cmd, rest.trim()),
+            None => (input, ""),
+        };
+
+        match command {
+            ":quit" | ":exit" | ":q" => return false,
+            ":help" | ":h" => print_help(),
+            ":clear" => {
+                self.reset();
+                println!("Variáveis limpas.");
+            }
+            ":type" | ":t" => match parse_expression(arg) {
+                Ok(expr) => println!("{}", self.checker.infer_type(&expr)),
+                Err(e) => println!("Erro: {}", e),
+            },
+            ":ast" => match parse_source(arg) {
+                Ok(program) => println!("{:#?}", program),
+                Err(e) => println!("Erro: {}", e),
+            },
+            ":bytecode" | ":bc" => match parse_source(arg) {
+                Ok(program) => match Compiler::new().compile(program) {
+                    Ok(chunk) => chunk.disassemble("repl"),
+                    Err(e) => println!("Erro de compilação bytecode: {}", e),
+                },
+                Err(e) => println!("Erro: {}", e),
+            },
+            ":load" | ":l" => {
+                if arg.is_empty() {
+                    println!("Uso: :load <arquivo>");
+                } else {
+                    self.load_file(arg);
+                }
+            }
+            ":native" => {
+                let active = self.interpreter.list_active_native_categories();
+                let mut categories = self.interpreter.native_registry.manager.list_categories();
+                categories.sort();
+                for category in categories {
+                    let marker = if active.contains(&category) { "*" } else { " " };
+                    println!(" {} {}", marker, category);
+                }
+            }
+            _ => println!("Comando desconhecido: {} (digite :help)", command),
+        }
+
+        true
+    }
+
+    fn load_file(&mut self, path: &str) {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                println!("Erro ao ler arquivo '{}': {}", path, e);
+                return;
+            }
+        };
+
+        self.interpreter.set_current_file(PathBuf::from(path));
+        match self.eval(&source) {
+            Ok(_) => println!("Arquivo '{}' carregado.", path),
+            Err(e) => println!("Erro: {}", e),
+        }
+    }
+
+    fn eval(&mut self, source: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let program = parse_source(source)?;
+
+        // O checker só acompanha as declarações para o :type; erros de tipo
+        // não impedem a execução no REPL
+        let _ = self.checker.check(&program);
+
+        Ok(self.interpreter.execute_and_return_value(&program)?)
+    }
+
+    fn print_result(&self, value: &Value) {
+        if matches!(value, Value::Null) {
+            return;
+        }
+
+        let options = InspectOptions {
+            colors: self.color,
+            ..InspectOptions::default()
+        };
+        println!("=> {}", self.interpreter.inspect_value(value, &options));
+    }
+}
+
+fn new_interpreter() -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_resolver(Box::new(OakModuleResolver));
+    interpreter
+}
+
+fn tokenize(source: &str) -> Result<Vec<dryad_lexer::TokenWithLocation>, Box<dyn std::error::Error>> {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = vec![];
+
+    loop {
+        let token = lexer.next_token()?;
+        let is_eof = matches!(token.token, Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_source(source: &str) -> Result<Program, Box<dyn std::error::Error>> {
+    let mut parser = DryadParser::new(tokenize(source)?);
+    Ok(parser.parse()?)
+}
+
+fn parse_expression(
+    source: &str,
+) -> Result<dryad_parser::ast::Expr, Box<dyn std::error::Error>> {
+    let mut parser = DryadParser::new(tokenize(source)?);
+    Ok(parser.parse_expression()?)
+}
+
+fn print_help() {
+    println!("Comandos disponíveis:");
+    for (command, description) in COMMANDS {
+        println!("  {:20} {}", command, description);
+    }
+    println!("\nDigite código Dryad para executar. Blocos com chaves abertas continuam");
+    println!("na linha seguinte; Tab completa nomes de variáveis e funções nativas.");
+}
+
+fn history_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(HISTORY_FILE))
+}
+
+pub fn run_repl() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Dryad v{} - REPL Interativo", env!("CARGO_PKG_VERSION"));
+    println!("Digite ':help' para ajuda e ':quit' para sair");
+
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new()?;
+    let mut session = ReplSession::new();
+
+    let mut helper = ReplHelper::default();
+    helper.refresh(&session.interpreter);
+    editor.set_helper(Some(helper));
+
+    let history = history_path();
+    if let Some(path) = &history {
+        let _ = editor.load_history(path);
+    }
+
+    let mut buffer = String::new();
+
+    loop {
+        let prompt = if buffer.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                // Ctrl-C descarta a entrada pendente
+                buffer.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        if buffer.is_empty() {
+            let trimmed = line.trim();
+            match trimmed {
+                "" => continue,
+                // Comandos legados sem ':'
+                "exit" | "quit" => break,
+                "help" => {
+                    print_help();
+                    continue;
+                }
+                "clear" => {
+                    session.reset();
+                    println!("Variáveis limpas.");
+                    continue;
+                }
+                _ if trimmed.starts_with(':') => {
+                    let _ = editor.add_history_entry(trimmed);
+                    if !session.run_command(trimmed) {
+                        break;
+                    }
+                    if let Some(helper) = editor.helper_mut() {
+                        helper.refresh(&session.interpreter);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+        } else {
+            buffer.push('\n');
+        }
+
+        buffer.push_str(&line);
+
+        if !is_input_complete(&buffer) {
+            continue;
+        }
+
+        let input = std::mem::take(&mut buffer);
+        let _ = editor.add_history_entry(input.trim());
+
+        match session.eval(&input) {
+            Ok(value) => session.print_result(&value),
+            Err(e) => println!("Erro: {}", e),
+        }
+
+        if let Some(helper) = editor.helper_mut() {
+            helper.refresh(&session.interpreter);
+        }
+    }
+
+    if let Some(path) = &history {
+        let _ = editor.save_history(path);
+    }
+
+    println!("Tchau!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_input_complete;
+
+    #[test]
+    fn test_balanced_input_is_complete() {
+        assert!(is_input_complete("let x = 1;"));
+        assert!(is_input_complete("function f(a) { return a; }"));
+        assert!(is_input_complete("let s = \"{\";"));
+    }
+
+    #[test]
+    fn test_open_delimiters_continue() {
+        assert!(!is_input_complete("class A {"));
+        assert!(!is_input_complete("foo(1,"));
+        assert!(!is_input_complete("let a = [1, 2"));
+        assert!(!is_input_complete("let s = \"abc"));
+        assert!(!is_input_complete("/* comentário"));
+    }
+
+    #[test]
+    fn test_delimiters_in_regex_literals_are_ignored() {
+        assert!(is_input_complete(r"let r = /\(/;"));
+        assert!(is_input_complete("let r = /[(]/;"));
+        assert!(is_input_complete("/[(]/.test(\"(\")"));
+        assert!(!is_input_complete("let r = /[(]/.test(x"));
+        // Depois de um valor `/` é divisão
+        assert!(!is_input_complete("let x = a / (b"));
+    }
+
+    #[test]
+    fn test_template_interpolation_continues() {
+        assert!(!is_input_complete("let s = `valor: ${x"));
+        assert!(is_input_complete("let s = `valor: ${x}`;"));
+    }
+
+    #[test]
+    fn test_comments_are_ignored() {
+        assert!(is_input_complete("let x = 1; // {"));
+        assert!(!is_input_complete("function f() { // }"));
+    }
+}
@@ -0,0 +1,593 @@
+// crates/dryad_cli/src/test_runner.rs
+//! Executor de testes (`dryad test`).
+//!
+//! Descobre arquivos `*_test.dryad` e blocos `test "nome" { ... }`. Cada teste roda
+//! em um `Interpreter` novo: o restante do arquivo (imports, diretivas, funções
+//! auxiliares) é executado antes do corpo do teste. Arquivos sem blocos `test`
+//! contam como um único teste.
+
+use crate::coverage_report;
+use crate::oak_adapter::OakModuleResolver;
+use dryad_errors::DryadError;
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::ast::{Program, Stmt};
+use dryad_parser::Parser as DryadParser;
+use dryad_runtime::coverage::Coverage;
+use dryad_runtime::native_modules::debug::{take_assertion_failure, AssertionFailure};
+use dryad_runtime::Interpreter;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const TEST_SUFFIX: &str = "_test.dryad";
+const IGNORED_DIRS: &[&str] = &["target", "node_modules", "oak_modules", ".git"];
+
+pub struct TestOptions {
+    pub paths: Vec<String>,
+    pub filter: Option<String>,
+    pub jobs: usize,
+    pub junit: Option<PathBuf>,
+    pub allow_unsafe: bool,
+    pub allow_exec: bool,
+    /// Executa os testes na VM de bytecode em vez do interpretador de árvore
+    pub compile: bool,
+    /// Diretório onde gravar os relatórios de cobertura (None = sem cobertura)
+    pub coverage_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestStatus {
+    Passed,
+    Failed {
+        message: String,
+        assertion: Option<AssertionFailure>,
+    },
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub file: PathBuf,
+    pub name: String,
+    pub status: TestStatus,
+    pub duration: Duration,
+}
+
+/// Um teste a executar: o arquivo, o código que o precede e o corpo do bloco.
+struct TestCase {
+    file: PathBuf,
+    name: String,
+    prelude: Vec<Stmt>,
+    body: Option<Stmt>,
+    skip: bool,
+}
+
+/// Executa os testes e retorna `true` se nenhum falhou.
+pub fn run_tests(options: &TestOptions) -> Result<bool, String> {
+    let files = discover_files(&options.paths)?;
+    if files.is_empty() {
+        println!("Nenhum arquivo de teste encontrado (*{})", TEST_SUFFIX);
+        return Ok(true);
+    }
+
+    let mut cases = Vec::new();
+    let mut load_errors = Vec::new();
+    let mut coverage = Coverage::new();
+    for file in &files {
+        match parse_file(file) {
+            Ok(program) => {
+                // Todas as linhas do arquivo entram no relatório, mesmo as de testes filtrados
+                coverage.register_program(file, &program);
+                cases.extend(load_cases(file, program));
+            }
+            Err(message) => load_errors.push(TestResult {
+                file: file.clone(),
+                name: "<carregamento>".to_string(),
+                status: TestStatus::Failed {
+                    message,
+                    assertion: None,
+                },
+                duration: Duration::ZERO,
+            }),
+        }
+    }
+
+    if let Some(filter) = &options.filter {
+        cases.retain(|case| full_name(&case.file, &case.name).contains(filter.as_str()));
+    }
+
+    let color = std::io::stdout().is_terminal();
+    let started = Instant::now();
+    let mut results = load_errors;
+    for result in &results {
+        print_result_line(result, color);
+    }
+    let (case_results, case_coverage) = run_cases(cases, options, color);
+    results.extend(case_results);
+    coverage.merge(&case_coverage);
+    let elapsed = started.elapsed();
+
+    print_failures(&results, color);
+    print_summary(&results, elapsed, color);
+
+    if let Some(dir) = &options.coverage_dir {
+        coverage_report::print_summary(&coverage);
+        coverage_report::write_reports(&coverage, dir)?;
+        println!("relatórios de cobertura em {}", dir.display());
+    }
+
+    if let Some(path) = &options.junit {
+        fs::write(path, junit_xml(&results))
+            .map_err(|e| format!("Erro ao escrever relatório JUnit '{}': {}", path.display(), e))?;
+    }
+
+    Ok(!results
+        .iter()
+        .any(|r| matches!(r.status, TestStatus::Failed { .. })))
+}
+
+fn full_name(file: &Path, name: &str) -> String {
+    format!("{} > {}", file.display(), name)
+}
+
+/// Coleta os arquivos de teste. Diretórios são percorridos recursivamente em busca de
+/// `*_test.dryad`; arquivos passados explicitamente são aceitos com qualquer nome.
+pub fn discover_files(paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let roots: Vec<PathBuf> = if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths.iter().map(PathBuf::from).collect()
+    };
+
+    let mut files = Vec::new();
+    for root in roots {
+        if root.is_file() {
+            files.push(root);
+        } else if root.is_dir() {
+            collect_test_files(&root, &mut files)?;
+        } else {
+            return Err(format!("Caminho de teste não encontrado: {}", root.display()));
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn collect_test_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Erro ao ler diretório '{}': {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !IGNORED_DIRS.contains(&name.as_str()) {
+                collect_test_files(&path, files)?;
+            }
+        } else if name.ends_with(TEST_SUFFIX) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn parse_file(file: &Path) -> Result<Program, String> {
+    let source = fs::read_to_string(file)
+        .map_err(|e| format!("Erro ao ler arquivo '{}': {}", file.display(), e))?;
+
+    let mut lexer = Lexer::new_with_file(&source, file.to_path_buf());
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().map_err(|e| e.to_string())?;
+        let is_eof = matches!(token.token, Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+
+    DryadParser::new(tokens).parse().map_err(|e| e.to_string())
+}
+
+fn load_cases(file: &Path, program: Program) -> Vec<TestCase> {
+    let (tests, prelude): (Vec<Stmt>, Vec<Stmt>) = program
+        .statements
+        .into_iter()
+        .partition(|stmt| matches!(stmt, Stmt::Test(..)));
+
+    if tests.is_empty() {
+        // Arquivo de teste "clássico": o arquivo inteiro é o teste
+        return vec![TestCase {
+            file: file.to_path_buf(),
+            name: file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            prelude,
+            body: None,
+            skip: false,
+        }];
+    }
+
+    tests
+        .into_iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Test(name, body, skip, _) => Some(TestCase {
+                file: file.to_path_buf(),
+                name,
+                prelude: prelude.clone(),
+                body: Some(*body),
+                skip,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn run_cases(
+    cases: Vec<TestCase>,
+    options: &TestOptions,
+    color: bool,
+) -> (Vec<TestResult>, Coverage) {
+    let total = cases.len();
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<TestResult>>> = Mutex::new(vec![None; total]);
+    let coverage = Mutex::new(Coverage::new());
+    let workers = options.jobs.clamp(1, total.max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(case) = cases.get(index) else {
+                    break;
+                };
+                let (result, case_coverage) = run_case(case, options);
+                print_result_line(&result, color);
+                if let Some(case_coverage) = case_coverage {
+                    coverage.lock().unwrap().merge(&case_coverage);
+                }
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    (
+        results.into_inner().unwrap().into_iter().flatten().collect(),
+        coverage.into_inner().unwrap(),
+    )
+}
+
+fn run_case(case: &TestCase, options: &TestOptions) -> (TestResult, Option<Coverage>) {
+    let started = Instant::now();
+    if case.skip {
+        return (
+            TestResult {
+                file: case.file.clone(),
+                name: case.name.clone(),
+                status: TestStatus::Skipped,
+                duration: started.elapsed(),
+            },
+            None,
+        );
+    }
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_resolver(Box::new(OakModuleResolver));
+    interpreter.set_allow_unsafe(options.allow_unsafe);
+    interpreter.set_allow_exec(options.allow_exec);
+    interpreter.set_current_file(case.file.clone());
+    if options.compile {
+        interpreter.set_compile_mode(true);
+    }
+    if options.coverage_dir.is_some() {
+        interpreter.enable_coverage();
+    }
+
+    // Descarta uma falha de asserção residual de um teste anterior nesta thread
+    take_assertion_failure();
+    let status = match execute_case(&mut interpreter, case, options) {
+        Ok(()) => TestStatus::Passed,
+        Err(error) => TestStatus::Failed {
+            message: failure_message(&error),
+            assertion: take_assertion_failure(),
+        },
+    };
+
+    let result = TestResult {
+        file: case.file.clone(),
+        name: case.name.clone(),
+        status,
+        duration: started.elapsed(),
+    };
+    (result, interpreter.take_coverage())
+}
+
+fn execute_case(
+    interpreter: &mut Interpreter,
+    case: &TestCase,
+    options: &TestOptions,
+) -> Result<(), DryadError> {
+    if options.compile {
+        // A VM executa um programa inteiro: o corpo do teste vira o último statement
+        let mut statements = case.prelude.clone();
+        statements.extend(case.body.clone());
+        return interpreter.execute(&Program { statements }).map(|_| ());
+    }
+
+    interpreter.execute(&Program {
+        statements: case.prelude.clone(),
+    })?;
+
+    match &case.body {
+        Some(body) => interpreter.execute_test(&case.name, body),
+        None => Ok(()),
+    }
+}
+
+fn failure_message(error: &DryadError) -> String {
+    let location = error.location();
+    if location.line > 0 {
+        format!("{} (linha {})", error.message(), location.line)
+    } else {
+        error.message().to_string()
+    }
+}
+
+fn paint(color: bool, code: &str, text: &str) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn print_result_line(result: &TestResult, color: bool) {
+    let label = match result.status {
+        TestStatus::Passed => paint(color, "32", "ok"),
+        TestStatus::Failed { .. } => paint(color, "31", "FALHOU"),
+        TestStatus::Skipped => paint(color, "33", "ignorado"),
+    };
+    println!(
+        "test {} ... {} ({:.2?})",
+        full_name(&result.file, &result.name),
+        label,
+        result.duration
+    );
+}
+
+fn print_failures(results: &[TestResult], color: bool) {
+    let failures: Vec<&TestResult> = results
+        .iter()
+        .filter(|r| matches!(r.status, TestStatus::Failed { .. }))
+        .collect();
+    if failures.is_empty() {
+        return;
+    }
+
+    println!("\nfalhas:");
+    for result in failures {
+        if let TestStatus::Failed { message, assertion } = &result.status {
+            println!("\n---- {} ----", full_name(&result.file, &result.name));
+            println!("{}", message);
+            if let Some(assertion) = assertion {
+                println!(
+                    "\n{} {}",
+                    paint(color, "31", "- esperado"),
+                    paint(color, "32", "+ obtido")
+                );
+                for line in diff_lines(&assertion.expected, &assertion.actual) {
+                    match line {
+                        DiffLine::Same(text) => println!("  {}", text),
+                        DiffLine::Expected(text) => {
+                            println!("{}", paint(color, "31", &format!("- {}", text)))
+                        }
+                        DiffLine::Actual(text) => {
+                            println!("{}", paint(color, "32", &format!("+ {}", text)))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn print_summary(results: &[TestResult], elapsed: Duration, color: bool) {
+    let passed = count(results, |s| matches!(s, TestStatus::Passed));
+    let failed = count(results, |s| matches!(s, TestStatus::Failed { .. }));
+    let skipped = count(results, |s| matches!(s, TestStatus::Skipped));
+
+    let verdict = if failed == 0 {
+        paint(color, "32", "ok")
+    } else {
+        paint(color, "31", "FALHOU")
+    };
+    println!(
+        "\nresultado: {}. {} passaram; {} falharam; {} ignorados; concluído em {:.2?}",
+        verdict, passed, failed, skipped, elapsed
+    );
+}
+
+fn count(results: &[TestResult], predicate: impl Fn(&TestStatus) -> bool) -> usize {
+    results.iter().filter(|r| predicate(&r.status)).count()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Expected(&'a str),
+    Actual(&'a str),
+}
+
+/// Diff linha a linha (LCS) entre o valor esperado e o obtido.
+pub fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<DiffLine<'a>> {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] = tamanho da maior subsequência comum de a[i..] e b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            diff.push(DiffLine::Same(a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::Expected(a[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Actual(b[j]));
+            j += 1;
+        }
+    }
+    diff.extend(a[i..].iter().map(|line| DiffLine::Expected(line)));
+    diff.extend(b[j..].iter().map(|line| DiffLine::Actual(line)));
+    diff
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Sequências de cor ANSI e outros controles não são válidos em XML 1.0
+            c if c.is_control() && c != '\n' && c != '\t' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Relatório no formato JUnit XML: um `<testsuite>` por arquivo.
+pub fn junit_xml(results: &[TestResult]) -> String {
+    let mut files: Vec<&PathBuf> = results.iter().map(|r| &r.file).collect();
+    files.dedup();
+
+    let total_time: f64 = results.iter().map(|r| r.duration.as_secs_f64()).sum();
+    let failures = count(results, |s| matches!(s, TestStatus::Failed { .. }));
+    let skipped = count(results, |s| matches!(s, TestStatus::Skipped));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"dryad\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+        results.len(),
+        failures,
+        skipped,
+        total_time
+    ));
+
+    for file in files {
+        let suite: Vec<&TestResult> = results.iter().filter(|r| &r.file == file).collect();
+        let suite_name = xml_escape(&file.display().to_string());
+        let suite_time: f64 = suite.iter().map(|r| r.duration.as_secs_f64()).sum();
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            suite_name,
+            suite.len(),
+            suite
+                .iter()
+                .filter(|r| matches!(r.status, TestStatus::Failed { .. }))
+                .count(),
+            suite
+                .iter()
+                .filter(|r| matches!(r.status, TestStatus::Skipped))
+                .count(),
+            suite_time
+        ));
+
+        for result in suite {
+            let open = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                xml_escape(&result.name),
+                suite_name,
+                result.duration.as_secs_f64()
+            );
+            match &result.status {
+                TestStatus::Passed => xml.push_str(&format!("{}/>\n", open)),
+                TestStatus::Skipped => xml.push_str(&format!("{}>\n      <skipped/>\n    </testcase>\n", open)),
+                TestStatus::Failed { message, assertion } => {
+                    let mut details = message.clone();
+                    if let Some(assertion) = assertion {
+                        details.push_str(&format!(
+                            "\nesperado: {}\nobtido: {}",
+                            assertion.expected, assertion.actual
+                        ));
+                    }
+                    xml.push_str(&format!(
+                        "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                        open,
+                        xml_escape(message),
+                        xml_escape(&details)
+                    ));
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines_marks_changed_lines() {
+        let diff = diff_lines("[\n  1,\n  2\n]", "[\n  1,\n  3\n]");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("["),
+                DiffLine::Same("  1,"),
+                DiffLine::Expected("  2"),
+                DiffLine::Actual("  3"),
+                DiffLine::Same("]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_junit_xml_escapes_and_counts() {
+        let results = vec![
+            TestResult {
+                file: PathBuf::from("a_test.dryad"),
+                name: "soma <simples>".to_string(),
+                status: TestStatus::Passed,
+                duration: Duration::from_millis(5),
+            },
+            TestResult {
+                file: PathBuf::from("a_test.dryad"),
+                name: "falha".to_string(),
+                status: TestStatus::Failed {
+                    message: "Assertion Error: \"x\"".to_string(),
+                    assertion: None,
+                },
+                duration: Duration::ZERO,
+            },
+        ];
+        let xml = junit_xml(&results);
+        assert!(xml.contains("tests=\"2\" failures=\"1\" skipped=\"0\""));
+        assert!(xml.contains("name=\"soma &lt;simples&gt;\""));
+        assert!(xml.contains("<failure message=\"Assertion Error: &quot;x&quot;\">"));
+    }
+}
@@ -58,6 +58,15 @@ pub const fn e1006() -> ErrorDef {
     }
 }
 
+pub const fn e1007() -> ErrorDef {
+    ErrorDef {
+        code: 1007,
+        category: ErrorCategory::Lexer,
+        message: "Invalid regular expression literal",
+        suggestion: Some("Valid regular expression flags are i, m, s, x and U, each at most once"),
+    }
+}
+
 // =============================================================================
 // PARSER ERRORS (2000-2999)
 // =============================================================================
@@ -1007,6 +1016,132 @@ pub const fn e2106() -> ErrorDef {
     }
 }
 
+pub const fn e2120() -> ErrorDef {
+    ErrorDef {
+        code: 2120,
+        category: ErrorCategory::Parser,
+        message: "Expected '{' after test name",
+        suggestion: Some("Use: test \"descrição\" { ... }"),
+    }
+}
+
+pub const fn e2121() -> ErrorDef {
+    ErrorDef {
+        code: 2121,
+        category: ErrorCategory::Parser,
+        message: "Expected test name string after 'test'",
+        suggestion: Some("Use: test \"descrição\" { ... } ou test.skip \"descrição\" { ... }"),
+    }
+}
+
+pub const fn e2122() -> ErrorDef {
+    ErrorDef {
+        code: 2122,
+        category: ErrorCategory::Parser,
+        message: "Expected enum name after 'enum'",
+        suggestion: Some("Use: enum Nome { Variante, Outra(campo) }"),
+    }
+}
+
+pub const fn e2123() -> ErrorDef {
+    ErrorDef {
+        code: 2123,
+        category: ErrorCategory::Parser,
+        message: "Expected '{' after enum name",
+        suggestion: Some("Use: enum Nome { Variante, Outra(campo) }"),
+    }
+}
+
+pub const fn e2124() -> ErrorDef {
+    ErrorDef {
+        code: 2124,
+        category: ErrorCategory::Parser,
+        message: "Expected variant name in enum declaration",
+        suggestion: Some("Variants are identifiers, optionally followed by fields: Circle(r)"),
+    }
+}
+
+pub const fn e2125() -> ErrorDef {
+    ErrorDef {
+        code: 2125,
+        category: ErrorCategory::Parser,
+        message: "Expected field name or ')' in enum variant",
+        suggestion: Some("Use: Variante(campo1, campo2)"),
+    }
+}
+
+pub const fn e2126() -> ErrorDef {
+    ErrorDef {
+        code: 2126,
+        category: ErrorCategory::Parser,
+        message: "Expected '}' after enum variants",
+        suggestion: Some("Separate variants with ',' and close the enum with '}'"),
+    }
+}
+
+pub const fn e2127() -> ErrorDef {
+    ErrorDef {
+        code: 2127,
+        category: ErrorCategory::Parser,
+        message: "Expected ')' after constructor pattern fields",
+        suggestion: Some("Use: Variante(a, b) => ..."),
+    }
+}
+
+pub const fn e2128() -> ErrorDef {
+    ErrorDef {
+        code: 2128,
+        category: ErrorCategory::Parser,
+        message: "Invalid operator overload declaration",
+        suggestion: Some("Overloadable operators: + - * / % ** == != < <= > >= [] []= (use 'operator -()' for unary minus)"),
+    }
+}
+
+pub const fn e2129() -> ErrorDef {
+    ErrorDef {
+        code: 2129,
+        category: ErrorCategory::Parser,
+        message: "Expected '>' after type arguments",
+        suggestion: Some("Close the type argument list: Box<number>, Map<string, T>"),
+    }
+}
+
+pub const fn e2130() -> ErrorDef {
+    ErrorDef {
+        code: 2130,
+        category: ErrorCategory::Parser,
+        message: "Expected type parameter name",
+        suggestion: Some("Use: <T>, <K, V> or <T extends Comparable<T>>"),
+    }
+}
+
+pub const fn e2131() -> ErrorDef {
+    ErrorDef {
+        code: 2131,
+        category: ErrorCategory::Parser,
+        message: "Invalid type alias declaration",
+        suggestion: Some("Use: type Name = string | number;"),
+    }
+}
+
+pub const fn e2132() -> ErrorDef {
+    ErrorDef {
+        code: 2132,
+        category: ErrorCategory::Parser,
+        message: "Invalid object type member",
+        suggestion: Some("Use: { name: string, age?: number }"),
+    }
+}
+
+pub const fn e2133() -> ErrorDef {
+    ErrorDef {
+        code: 2133,
+        category: ErrorCategory::Parser,
+        message: "Invalid abstract member",
+        suggestion: Some("Use: abstract function area(): number; inside an abstract class"),
+    }
+}
+
 pub const fn e4001() -> ErrorDef {
     ErrorDef {
         code: 4001,
@@ -1220,6 +1355,114 @@ pub const fn e3040() -> ErrorDef {
     }
 }
 
+pub const fn e3041() -> ErrorDef {
+    ErrorDef {
+        code: 3041,
+        category: ErrorCategory::Runtime,
+        message: "Invalid yield",
+        suggestion: Some("Use yield inside a function* as a statement, in 'let x = yield v;' or in 'x = yield v;'"),
+    }
+}
+
+pub const fn e3042() -> ErrorDef {
+    ErrorDef {
+        code: 3042,
+        category: ErrorCategory::Runtime,
+        message: "Generator already running",
+        suggestion: Some("A generator cannot call next() on itself"),
+    }
+}
+
+pub const fn e3043() -> ErrorDef {
+    ErrorDef {
+        code: 3043,
+        category: ErrorCategory::Runtime,
+        message: "Invalid iterator",
+        suggestion: Some("next() must return an object like { value: v, done: false }"),
+    }
+}
+
+pub const fn e3044() -> ErrorDef {
+    ErrorDef {
+        code: 3044,
+        category: ErrorCategory::Runtime,
+        message: "Invalid range",
+        suggestion: Some("Range bounds must be numbers: 0..10 or 1..=n"),
+    }
+}
+
+pub const fn e3045() -> ErrorDef {
+    ErrorDef {
+        code: 3045,
+        category: ErrorCategory::Runtime,
+        message: "Integer overflow",
+        suggestion: Some("The result does not fit in int (i64); use a bigint literal like 10n or bigint(x)"),
+    }
+}
+
+pub const fn e3046() -> ErrorDef {
+    ErrorDef {
+        code: 3046,
+        category: ErrorCategory::Runtime,
+        message: "Invalid numeric conversion",
+        suggestion: Some("int(), bigint(), decimal() and float() accept numbers or numeric strings"),
+    }
+}
+
+pub const fn e3047() -> ErrorDef {
+    ErrorDef {
+        code: 3047,
+        category: ErrorCategory::Runtime,
+        message: "Invalid Map or Set operation",
+        suggestion: Some("Keys must be primitives, tuples, enum variants or objects; Map() takes an iterable of [key, value] pairs"),
+    }
+}
+
+pub const fn e3048() -> ErrorDef {
+    ErrorDef {
+        code: 3048,
+        category: ErrorCategory::Runtime,
+        message: "Right-hand side of 'instanceof' is not a class",
+        suggestion: Some("Use: value instanceof ClassName"),
+    }
+}
+
+pub const fn e3049() -> ErrorDef {
+    ErrorDef {
+        code: 3049,
+        category: ErrorCategory::Runtime,
+        message: "Abstract class cannot be instantiated",
+        suggestion: Some("Instantiate a concrete subclass that implements the abstract methods"),
+    }
+}
+
+pub const fn e3050() -> ErrorDef {
+    ErrorDef {
+        code: 3050,
+        category: ErrorCategory::Runtime,
+        message: "Abstract method not implemented",
+        suggestion: Some("Implement every abstract method or declare the class as abstract"),
+    }
+}
+
+pub const fn e3051() -> ErrorDef {
+    ErrorDef {
+        code: 3051,
+        category: ErrorCategory::Runtime,
+        message: "Invalid date/time operation",
+        suggestion: Some("Dates use ISO 8601/RFC 3339 or strftime formats; time zones are IANA names like \"America/Sao_Paulo\""),
+    }
+}
+
+pub const fn e3052() -> ErrorDef {
+    ErrorDef {
+        code: 3052,
+        category: ErrorCategory::Runtime,
+        message: "Invalid regular expression",
+        suggestion: Some("Check the pattern syntax; look-around and backreferences are not supported"),
+    }
+}
+
 pub const fn e3081() -> ErrorDef {
     ErrorDef {
         code: 3081,
@@ -1481,6 +1724,15 @@ pub const fn e4003() -> ErrorDef {
     }
 }
 
+pub const fn e4004() -> ErrorDef {
+    ErrorDef {
+        code: 4004,
+        category: ErrorCategory::Type,
+        message: "Value does not match declared type",
+        suggestion: Some("Pass a value of the annotated type or widen the annotation (e.g. number | string)"),
+    }
+}
+
 // =============================================================================
 // MODULE ERRORS (6000-6999)
 // =============================================================================
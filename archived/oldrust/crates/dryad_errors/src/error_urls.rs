@@ -12,6 +12,7 @@ pub fn get_error_documentation_url(error_code: u16) -> String {
         1004 => format!("{}#e1004-invalid-number-format", base_url),
         1005 => format!("{}#e1005-invalid-escape-sequence", base_url),
         1006 => format!("{}#e1006-invalid-native-directive", base_url),
+        1007 => format!("{}#e1007-invalid-regular-expression-literal", base_url),
 
         // ✅ Implemented Parser Errors (2000-2999)
         2001 => format!("{}#e2001-unexpected-token", base_url),
@@ -35,6 +36,12 @@ pub fn get_error_documentation_url(error_code: u16) -> String {
         2049 => format!("{}#e2049-missing-index-closing-bracket", base_url),
         2116 => format!("{}#e2116-import-error", base_url),
         2117 => format!("{}#e2117-template-string-parse-error", base_url),
+        2128 => format!("{}#e2128-invalid-operator-overload", base_url),
+        2129 => format!("{}#e2129-unclosed-type-arguments", base_url),
+        2130 => format!("{}#e2130-missing-type-parameter-name", base_url),
+        2131 => format!("{}#e2131-invalid-type-alias", base_url),
+        2132 => format!("{}#e2132-invalid-object-type-member", base_url),
+        2133 => format!("{}#e2133-invalid-abstract-member", base_url),
 
         // ✅ Implemented Runtime Errors (3000-3999)
         3000 => format!("{}#e3000-runtime-error", base_url),
@@ -52,6 +59,18 @@ pub fn get_error_documentation_url(error_code: u16) -> String {
         3023 => format!("{}#e3023-super-not-implemented", base_url),
         3034 => format!("{}#e3034-invalid-property-assignment", base_url),
         3040 => format!("{}#e3040-stack-overflow", base_url),
+        3041 => format!("{}#e3041-invalid-yield", base_url),
+        3042 => format!("{}#e3042-generator-already-running", base_url),
+        3043 => format!("{}#e3043-invalid-iterator", base_url),
+        3044 => format!("{}#e3044-invalid-range", base_url),
+        3045 => format!("{}#e3045-integer-overflow", base_url),
+        3046 => format!("{}#e3046-invalid-numeric-conversion", base_url),
+        3047 => format!("{}#e3047-invalid-map-or-set-operation", base_url),
+        3048 => format!("{}#e3048-invalid-instanceof-target", base_url),
+        3049 => format!("{}#e3049-abstract-class-instantiation", base_url),
+        3050 => format!("{}#e3050-abstract-method-not-implemented", base_url),
+        3051 => format!("{}#e3051-invalid-date-time-operation", base_url),
+        3052 => format!("{}#e3052-invalid-regular-expression", base_url),
         3104 => format!("{}#e3104-native-function-error", base_url),
         3105 => format!("{}#e3105-promise-error", base_url),
         3106 => format!("{}#e3106-runtime-type-error", base_url),
@@ -59,6 +78,7 @@ pub fn get_error_documentation_url(error_code: u16) -> String {
         // 🟡 Planned Type Errors (4000-4999)
         4001 => format!("{}#e4001-incompatible-types", base_url),
         4002 => format!("{}#e4002-invalid-conversion", base_url),
+        4004 => format!("{}#e4004-declared-type-mismatch", base_url),
 
         // 🟡 Planned I/O Errors (5000-5999)
         5001 => format!("{}#e5001-file-not-found", base_url),
@@ -134,6 +154,11 @@ pub fn get_error_suggestions(error_code: u16) -> Vec<String> {
             "Valid modules: console_io, file_io, http, crypto, etc.".to_string(),
             "Use only alphanumeric characters and underscores".to_string(),
         ],
+        1007 => vec![
+            "Valid flags: i, m, s, x, U".to_string(),
+            "Do not repeat a flag: /a+b/i instead of /a+b/ii".to_string(),
+            "Global matching has no flag: use find_all or replace_all".to_string(),
+        ],
 
         // ✅ Implemented Parser Errors
         2001 => vec![
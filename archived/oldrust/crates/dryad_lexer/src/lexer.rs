@@ -14,6 +14,8 @@ pub struct Lexer<'a> {
     file_path: Option<PathBuf>,
     template_nesting: Vec<usize>,
     brace_level: usize,
+    /// Se um `/` na posição atual inicia uma regex (e não uma divisão)
+    regex_allowed: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -29,6 +31,7 @@ impl<'a> Lexer<'a> {
             file_path: None,
             template_nesting: Vec::new(),
             brace_level: 0,
+            regex_allowed: true,
         }
     }
 
@@ -55,6 +58,25 @@ impl<'a> Lexer<'a> {
     }
 
     pub fn next_token(&mut self) -> Result<TokenWithLocation, DryadError> {
+        let token = self.scan_token()?;
+        self.regex_allowed = Self::regex_may_follow(&token.token);
+        Ok(token)
+    }
+
+    /// `/` inicia uma regex onde se espera um valor: no início, depois de
+    /// operadores, `(`, `[`, `{`, `,`, `;`, `=`, `=>` e palavras-chave como
+    /// `return`. Depois de um valor (`x`, `2`, `)`, `]`, `}`) é divisão.
+    fn regex_may_follow(token: &Token) -> bool {
+        match token {
+            Token::Operator(op) => op != "++" && op != "--",
+            Token::Symbol(symbol) => !matches!(symbol, ')' | ']' | '}'),
+            Token::Keyword(keyword) => !matches!(keyword.as_str(), "this" | "super"),
+            Token::Arrow | Token::TemplateStart | Token::InterpolationStart => true,
+            _ => false,
+        }
+    }
+
+    fn scan_token(&mut self) -> Result<TokenWithLocation, DryadError> {
         if let Some(&level) = self.template_nesting.last() {
             if self.brace_level == level {
                 return self.template_content();
@@ -150,6 +172,8 @@ impl<'a> Lexer<'a> {
                     self.line_comment()
                 } else if self.peek() == '*' {
                     self.block_comment()
+                } else if self.regex_allowed && self.regex_closes() {
+                    self.regex_literal(start_location)
                 } else if self.peek() == '=' {
                     self.advance();
                     Ok(TokenWithLocation {
@@ -330,10 +354,56 @@ impl<'a> Lexer<'a> {
                     ))
                 }
             }
-            '.' => Ok(TokenWithLocation {
-                token: Token::Symbol('.'),
-                location: start_location,
-            }),
+            '.' => {
+                // Intervalos: `a..b` (exclusivo) e `a..=b` (inclusivo); `...` continua como três '.'
+                if self.peek() == '.' && self.peek_next() != '.' {
+                    self.advance();
+                    if self.peek() == '=' {
+                        self.advance();
+                        return Ok(TokenWithLocation {
+                            token: Token::Operator("..=".to_string()),
+                            location: start_location,
+                        });
+                    }
+                    return Ok(TokenWithLocation {
+                        token: Token::Operator("..".to_string()),
+                        location: start_location,
+                    });
+                }
+                Ok(TokenWithLocation {
+                    token: Token::Symbol('.'),
+                    location: start_location,
+                })
+            }
+            '?' => {
+                // `?.` (navegação segura), `??` (coalescência nula), `??=` e `?`
+                if self.peek() == '.' {
+                    self.advance();
+                    Ok(TokenWithLocation {
+                        token: Token::Operator("?.".to_string()),
+                        location: start_location,
+                    })
+                } else if self.peek() == '?' {
+                    self.advance();
+                    if self.peek() == '=' {
+                        self.advance();
+                        return Ok(TokenWithLocation {
+                            token: Token::Operator("??=".to_string()),
+                            location: start_location,
+                        });
+                    }
+                    Ok(TokenWithLocation {
+                        token: Token::Operator("??".to_string()),
+                        location: start_location,
+                    })
+                } else {
+                    // `?` isolado: tipos opcionais (`string?`, `{ age?: number }`)
+                    Ok(TokenWithLocation {
+                        token: Token::Symbol('?'),
+                        location: start_location,
+                    })
+                }
+            }
             '{' => {
                 self.brace_level += 1;
                 Ok(TokenWithLocation {
@@ -465,7 +535,23 @@ impl<'a> Lexer<'a> {
                 "Index error while processing number",
                 SourceLocation::unknown(),
             )
-        })?;
+        })?
+        .to_string();
+        if let Some(suffix) = self.numeric_suffix(&['i', 'n', 'd']) {
+            let location = self.current_location();
+            let token = if suffix == 'd' {
+                Token::Decimal(text.to_string())
+            } else if text.contains('.') {
+                return Err(DryadError::from_catalog_fmt(
+                    error_catalog::e1004(),
+                    &format!("Suffix '{}' requires an integer literal: '{}{}'", suffix, text, suffix),
+                    location,
+                ));
+            } else {
+                Self::integer_token(&text, 10, suffix)?
+            };
+            return Ok(TokenWithLocation { token, location });
+        }
         let location = self.current_location();
         match text.parse::<f64>() {
             Ok(value) => Ok(TokenWithLocation {
@@ -480,6 +566,55 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Consome um sufixo numérico (`42i`, `42n`, `1.5d`) se ele não iniciar um identificador
+    fn numeric_suffix(&mut self, allowed: &[char]) -> Option<char> {
+        let suffix = self.peek();
+        let after = self.peek_next();
+        if allowed.contains(&suffix) && !(after.is_alphanumeric() || after == '_') {
+            self.advance();
+            Some(suffix)
+        } else {
+            None
+        }
+    }
+
+    /// Token de um literal inteiro com sufixo `i` (i64) ou `n` (bigint)
+    fn integer_token(digits: &str, radix: u32, suffix: char) -> Result<Token, DryadError> {
+        if suffix == 'i' {
+            return i64::from_str_radix(digits, radix).map(Token::Int).map_err(|_| {
+                DryadError::from_catalog_fmt(
+                    error_catalog::e1004(),
+                    &format!("Integer literal '{}i' does not fit in int (i64); use the 'n' suffix", digits),
+                    SourceLocation::unknown(),
+                )
+            });
+        }
+        if radix == 10 {
+            let digits = digits.trim_start_matches('0');
+            return Ok(Token::BigInt(if digits.is_empty() { "0" } else { digits }.to_string()));
+        }
+        // Converte os dígitos da base para decimal (o lexer não depende de bigint)
+        let mut decimal: Vec<u32> = vec![0];
+        for c in digits.chars() {
+            let mut carry = c.to_digit(radix).unwrap_or(0);
+            for d in decimal.iter_mut() {
+                let v = *d * radix + carry;
+                *d = v % 10;
+                carry = v / 10;
+            }
+            while carry > 0 {
+                decimal.push(carry % 10);
+                carry /= 10;
+            }
+        }
+        while decimal.len() > 1 && decimal.last() == Some(&0) {
+            decimal.pop();
+        }
+        Ok(Token::BigInt(
+            decimal.iter().rev().map(|d| char::from_digit(*d, 10).unwrap_or('0')).collect(),
+        ))
+    }
+
     fn string(&mut self, delimiter: char) -> Result<TokenWithLocation, DryadError> {
         let mut value = String::new();
 
@@ -582,7 +717,7 @@ impl<'a> Lexer<'a> {
             | "catch" | "finally" | "throw" | "in" | "this" | "super" | "static" | "public"
             | "private" | "protected" | "extends" | "async" | "await" | "thread" | "mutex"
             | "as" | "from" | "match" | "new" | "interface" | "implements" | "get" | "set"
-            | "namespace" => Token::Keyword(text.to_string()),
+            | "namespace" | "enum" | "yield" | "abstract" => Token::Keyword(text.to_string()),
             "true" => Token::Boolean(true),
             "false" => Token::Boolean(false),
             "null" => Token::Literal("null".to_string()),
@@ -597,7 +732,7 @@ impl<'a> Lexer<'a> {
         while !self.is_at_end() && self.peek() != '\n' {
             self.advance();
         }
-        self.next_token()
+        self.scan_token()
     }
 
     fn block_comment(&mut self) -> Result<TokenWithLocation, DryadError> {
@@ -606,7 +741,7 @@ impl<'a> Lexer<'a> {
             if self.peek() == '*' && self.peek_next() == '/' {
                 self.advance(); // *
                 self.advance(); // /
-                return self.next_token();
+                return self.scan_token();
             }
             self.advance();
         }
@@ -616,6 +751,67 @@ impl<'a> Lexer<'a> {
         ))
     }
 
+    /// Há uma `/` de fechamento na mesma linha? Sem ela a `/` é divisão
+    /// (`/=` no início, `a * /` incompleto) e o parser aponta o erro
+    fn regex_closes(&self) -> bool {
+        let mut chars = self.chars.clone();
+        let mut in_class = false;
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\n' => return false,
+                '\\' if chars.next().is_none_or(|escaped| escaped == '\n') => return false,
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// `/padrão/flags`; o padrão é guardado como escrito (`\/` inclusive),
+    /// e uma `/` dentro de `[...]` não encerra o literal. Só é chamado depois
+    /// de `regex_closes`, que garante o fechamento na mesma linha
+    fn regex_literal(&mut self, location: SourceLocation) -> Result<TokenWithLocation, DryadError> {
+        let mut pattern = String::new();
+        let mut in_class = false;
+        loop {
+            match self.advance() {
+                '\\' => {
+                    pattern.push('\\');
+                    pattern.push(self.advance());
+                }
+                '/' if !in_class => break,
+                ch => {
+                    match ch {
+                        '[' => in_class = true,
+                        ']' => in_class = false,
+                        _ => {}
+                    }
+                    pattern.push(ch);
+                }
+            }
+        }
+
+        let mut flags = String::new();
+        while self.peek().is_ascii_alphanumeric() {
+            let flag = self.advance();
+            if !"imsxU".contains(flag) || flags.contains(flag) {
+                return Err(DryadError::from_catalog_fmt(
+                    error_catalog::e1007(),
+                    &format!("Invalid regular expression flag '{}'", flag),
+                    location,
+                ));
+            }
+            flags.push(flag);
+        }
+
+        Ok(TokenWithLocation {
+            token: Token::Regex(pattern, flags),
+            location,
+        })
+    }
+
     fn binary_number(&mut self) -> Result<TokenWithLocation, DryadError> {
         self.advance(); // b
         let start_pos = self.position;
@@ -634,6 +830,9 @@ impl<'a> Lexer<'a> {
             ));
         }
 
+        let digits_end = self.position;
+        let suffix = self.numeric_suffix(&['i', 'n']);
+
         if !self.is_at_end() && (self.peek().is_ascii_digit() || self.peek().is_ascii_alphabetic())
         {
             return Err(DryadError::from_catalog_fmt(
@@ -643,7 +842,7 @@ impl<'a> Lexer<'a> {
             ));
         }
 
-        let text = self.safe_slice(start_pos, self.position).ok_or_else(|| {
+        let text = self.safe_slice(start_pos, digits_end).ok_or_else(|| {
             DryadError::from_catalog_fmt(
                 error_catalog::e1004(),
                 "Index error in binary number",
@@ -651,6 +850,10 @@ impl<'a> Lexer<'a> {
             )
         })?;
         let location = self.current_location();
+        if let Some(suffix) = suffix {
+            let token = Self::integer_token(text, 2, suffix)?;
+            return Ok(TokenWithLocation { token, location });
+        }
         match u64::from_str_radix(text, 2) {
             Ok(value) => Ok(TokenWithLocation {
                 token: Token::Number(value as f64),
@@ -682,6 +885,9 @@ impl<'a> Lexer<'a> {
             ));
         }
 
+        let digits_end = self.position;
+        let suffix = self.numeric_suffix(&['i', 'n']);
+
         if !self.is_at_end() && (self.peek().is_ascii_digit() || self.peek().is_ascii_alphabetic())
         {
             return Err(DryadError::from_catalog_fmt(
@@ -691,7 +897,7 @@ impl<'a> Lexer<'a> {
             ));
         }
 
-        let text = self.safe_slice(start_pos, self.position).ok_or_else(|| {
+        let text = self.safe_slice(start_pos, digits_end).ok_or_else(|| {
             DryadError::from_catalog_fmt(
                 error_catalog::e1004(),
                 "Index error in octal number",
@@ -699,6 +905,10 @@ impl<'a> Lexer<'a> {
             )
         })?;
         let location = self.current_location();
+        if let Some(suffix) = suffix {
+            let token = Self::integer_token(text, 8, suffix)?;
+            return Ok(TokenWithLocation { token, location });
+        }
         match u64::from_str_radix(text, 8) {
             Ok(value) => Ok(TokenWithLocation {
                 token: Token::Number(value as f64),
@@ -730,6 +940,9 @@ impl<'a> Lexer<'a> {
             ));
         }
 
+        let digits_end = self.position;
+        let suffix = self.numeric_suffix(&['i', 'n']);
+
         if !self.is_at_end() && self.peek().is_ascii_alphabetic() {
             return Err(DryadError::from_catalog_fmt(
                 error_catalog::e1004(),
@@ -738,7 +951,7 @@ impl<'a> Lexer<'a> {
             ));
         }
 
-        let text = self.safe_slice(start_pos, self.position).ok_or_else(|| {
+        let text = self.safe_slice(start_pos, digits_end).ok_or_else(|| {
             DryadError::from_catalog_fmt(
                 error_catalog::e1004(),
                 "Index error in hexadecimal number",
@@ -746,6 +959,10 @@ impl<'a> Lexer<'a> {
             )
         })?;
         let location = self.current_location();
+        if let Some(suffix) = suffix {
+            let token = Self::integer_token(text, 16, suffix)?;
+            return Ok(TokenWithLocation { token, location });
+        }
         match u64::from_str_radix(text, 16) {
             Ok(value) => Ok(TokenWithLocation {
                 token: Token::Number(value as f64),
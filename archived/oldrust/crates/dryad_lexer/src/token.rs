@@ -20,9 +20,13 @@ pub enum Token {
 
     // Literals
     Number(f64),
+    Int(i64),        // 42i
+    BigInt(String),  // 42n (dígitos decimais)
+    Decimal(String), // 1.50d (texto exato do literal)
     String(String),
     Boolean(bool),
     Literal(String), // Para null e outros literais especiais
+    Regex(String, String), // /padrão/flags
 
     // Palavras-chave
     Keyword(String),
@@ -0,0 +1,51 @@
+// crates/dryad_lexer/tests/nullish_operators_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+
+fn tokens(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token.token);
+    }
+    tokens
+}
+
+#[test]
+fn test_tokenize_optional_chaining_and_nullish_operators() {
+    assert_eq!(
+        tokens("a?.b ?? c"),
+        vec![
+            Token::Identifier("a".to_string()),
+            Token::Operator("?.".to_string()),
+            Token::Identifier("b".to_string()),
+            Token::Operator("??".to_string()),
+            Token::Identifier("c".to_string()),
+        ]
+    );
+    assert_eq!(
+        tokens("x ??= 1"),
+        vec![
+            Token::Identifier("x".to_string()),
+            Token::Operator("??=".to_string()),
+            Token::Number(1.0),
+        ]
+    );
+}
+
+#[test]
+fn test_lone_question_mark_is_a_symbol() {
+    // `?` isolado marca tipos opcionais: `string?`, `{ age?: number }`
+    assert_eq!(
+        tokens("string? ?:"),
+        vec![
+            Token::Identifier("string".to_string()),
+            Token::Symbol('?'),
+            Token::Symbol('?'),
+            Token::Symbol(':'),
+        ]
+    );
+}
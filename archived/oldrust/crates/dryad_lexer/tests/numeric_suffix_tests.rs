@@ -0,0 +1,67 @@
+// crates/dryad_lexer/tests/numeric_suffix_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+
+fn tokens(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token.token);
+    }
+    tokens
+}
+
+#[test]
+fn test_int_bigint_and_decimal_suffixes() {
+    assert_eq!(
+        tokens("42i 42n 1.50d 42"),
+        vec![
+            Token::Int(42),
+            Token::BigInt("42".to_string()),
+            Token::Decimal("1.50".to_string()),
+            Token::Number(42.0),
+        ]
+    );
+}
+
+#[test]
+fn test_suffixes_on_prefixed_literals() {
+    assert_eq!(
+        tokens("0xFFi 0b101n 0o17i"),
+        vec![
+            Token::Int(255),
+            Token::BigInt("5".to_string()),
+            Token::Int(15),
+        ]
+    );
+}
+
+#[test]
+fn test_bigint_literal_beyond_i64() {
+    assert_eq!(
+        tokens("123456789012345678901234567890n"),
+        vec![Token::BigInt("123456789012345678901234567890".to_string())]
+    );
+}
+
+#[test]
+fn test_suffix_errors() {
+    // Não cabe em i64
+    assert!(Lexer::new("9223372036854775808i").next_token().is_err());
+    // Sufixo inteiro em literal fracionário
+    assert!(Lexer::new("1.5i").next_token().is_err());
+    assert!(Lexer::new("1.5n").next_token().is_err());
+}
+
+#[test]
+fn test_suffix_letter_followed_by_identifier_is_not_a_suffix() {
+    // A letra só é sufixo quando não continua um identificador
+    assert_eq!(
+        tokens("2in"),
+        vec![Token::Number(2.0), Token::Keyword("in".to_string())]
+    );
+    assert_eq!(tokens("9223372036854775807i"), vec![Token::Int(i64::MAX)]);
+}
@@ -0,0 +1,126 @@
+// crates/dryad_lexer/tests/regex_literal_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+
+fn tokens(input: &str) -> Vec<Token> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.token == Token::Eof {
+            break;
+        }
+        tokens.push(token.token);
+    }
+    tokens
+}
+
+fn regex(pattern: &str, flags: &str) -> Token {
+    Token::Regex(pattern.to_string(), flags.to_string())
+}
+
+#[test]
+fn test_regex_literal_with_flags() {
+    assert_eq!(
+        tokens(r"let r = /ab+c/im;"),
+        vec![
+            Token::Keyword("let".to_string()),
+            Token::Identifier("r".to_string()),
+            Token::Symbol('='),
+            regex("ab+c", "im"),
+            Token::Symbol(';'),
+        ]
+    );
+}
+
+#[test]
+fn test_slash_after_value_is_division() {
+    assert_eq!(
+        tokens("a / b / 2"),
+        vec![
+            Token::Identifier("a".to_string()),
+            Token::Operator("/".to_string()),
+            Token::Identifier("b".to_string()),
+            Token::Operator("/".to_string()),
+            Token::Number(2.0),
+        ]
+    );
+    assert_eq!(
+        tokens("(x) / 2; x++ / 3"),
+        vec![
+            Token::Symbol('('),
+            Token::Identifier("x".to_string()),
+            Token::Symbol(')'),
+            Token::Operator("/".to_string()),
+            Token::Number(2.0),
+            Token::Symbol(';'),
+            Token::Identifier("x".to_string()),
+            Token::Operator("++".to_string()),
+            Token::Operator("/".to_string()),
+            Token::Number(3.0),
+        ]
+    );
+}
+
+#[test]
+fn test_regex_after_operators_and_keywords() {
+    assert_eq!(
+        tokens(r"f(/\d+/, [/a|b/]) || /=x/; return /y/"),
+        vec![
+            Token::Identifier("f".to_string()),
+            Token::Symbol('('),
+            regex(r"\d+", ""),
+            Token::Symbol(','),
+            Token::Symbol('['),
+            regex("a|b", ""),
+            Token::Symbol(']'),
+            Token::Symbol(')'),
+            Token::Operator("||".to_string()),
+            regex("=x", ""),
+            Token::Symbol(';'),
+            Token::Keyword("return".to_string()),
+            regex("y", ""),
+        ]
+    );
+}
+
+#[test]
+fn test_escaped_slash_and_class_keep_literal_open() {
+    assert_eq!(tokens(r"/a\/b[/]c/"), vec![regex(r"a\/b[/]c", "")]);
+}
+
+#[test]
+fn test_comments_still_win_over_regex() {
+    assert_eq!(
+        tokens("// comentário\n/* bloco */ /x/s"),
+        vec![regex("x", "s")]
+    );
+}
+
+#[test]
+fn test_invalid_regex_literals() {
+    for source in ["/abc/g", "/abc/ii", "x = /a[/]b/q"] {
+        let mut lexer = Lexer::new(source);
+        let error = std::iter::from_fn(|| Some(lexer.next_token()))
+            .find_map(|token| token.err())
+            .unwrap();
+        assert_eq!(error.code(), 1007, "{}", source);
+    }
+}
+
+#[test]
+fn test_unclosed_slash_stays_an_operator() {
+    assert_eq!(
+        tokens("/= x"),
+        vec![Token::Operator("/=".to_string()), Token::Identifier("x".to_string())]
+    );
+    assert_eq!(
+        tokens("x = / 2\n/"),
+        vec![
+            Token::Identifier("x".to_string()),
+            Token::Symbol('='),
+            Token::Operator("/".to_string()),
+            Token::Number(2.0),
+            Token::Operator("/".to_string()),
+        ]
+    );
+}
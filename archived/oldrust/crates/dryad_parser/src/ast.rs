@@ -9,10 +9,32 @@ pub enum Type {
     Bool,
     Null,
     Any,
+    Int,
+    BigInt,
+    Decimal,
     Array(Box<Type>),
     Tuple(Vec<Type>),
     Function(Vec<Type>, Box<Type>), // (params) -> return
-    Class(String),
+    Class(String), // nome de classe, interface, enum ou parâmetro de tipo (`T`)
+    Generic(String, Vec<Type>), // Box<number>, Comparable<T>
+    Union(Vec<Type>),           // string | number; `T?` é `T | null`
+    Literal(Literal),           // "GET", 42, true
+    Object(Vec<ObjectTypeField>), // { name: string, age?: number }
+}
+
+/// Campo de um tipo objeto estrutural: `name: string` ou `age?: number`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectTypeField {
+    pub name: String,
+    pub field_type: Type,
+    pub optional: bool,
+}
+
+/// Parâmetro de tipo genérico: `T` ou `T extends Comparable<T>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParam {
+    pub name: String,
+    pub bound: Option<Type>,
 }
 
 impl fmt::Display for Type {
@@ -23,6 +45,9 @@ impl fmt::Display for Type {
             Type::Bool => write!(f, "bool"),
             Type::Null => write!(f, "null"),
             Type::Any => write!(f, "any"),
+            Type::Int => write!(f, "int"),
+            Type::BigInt => write!(f, "bigint"),
+            Type::Decimal => write!(f, "decimal"),
             Type::Array(inner) => write!(f, "{}[]", inner),
             Type::Tuple(elements) => {
                 write!(f, "(")?;
@@ -45,6 +70,45 @@ impl fmt::Display for Type {
                 write!(f, ") -> {}", ret)
             }
             Type::Class(name) => write!(f, "{}", name),
+            Type::Generic(name, args) => {
+                write!(f, "{}<", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ">")
+            }
+            Type::Union(members) => {
+                for (i, member) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " | ")?;
+                    }
+                    write!(f, "{}", member)?;
+                }
+                Ok(())
+            }
+            Type::Literal(Literal::String(s)) => write!(f, "{:?}", s),
+            Type::Literal(Literal::Number(n)) => write!(f, "{}", n),
+            Type::Literal(Literal::Int(n)) => write!(f, "{}i", n),
+            Type::Literal(Literal::BigInt(n)) => write!(f, "{}n", n),
+            Type::Literal(Literal::Decimal(n)) => write!(f, "{}d", n),
+            Type::Literal(Literal::Regex(pattern, flags)) => write!(f, "/{}/{}", pattern, flags),
+            Type::Literal(Literal::Bool(b)) => write!(f, "{}", b),
+            Type::Literal(Literal::Null) => write!(f, "null"),
+            Type::Object(fields) if fields.is_empty() => write!(f, "{{}}"),
+            Type::Object(fields) => {
+                write!(f, "{{ ")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    let optional = if field.optional { "?" } else { "" };
+                    write!(f, "{}{}: {}", field.name, optional, field.field_type)?;
+                }
+                write!(f, " }}")
+            }
         }
     }
 }
@@ -83,12 +147,14 @@ pub enum Stmt {
     NativeDirective(String, SourceLocation),   // #<module_name>
     FunctionDeclaration {
         name: String,
+        type_params: Vec<TypeParam>, // function first<T>(xs: T[]): T
         params: Vec<(String, Option<Type>, Option<Expr>)>,
         rest_param: Option<String>,
         return_type: Option<Type>,
         body: Box<Stmt>,
         location: SourceLocation,
         is_async: bool,
+        is_generator: bool, // function* nome() { ... yield ... }
     },
     ThreadFunctionDeclaration {
         name: String,
@@ -98,16 +164,21 @@ pub enum Stmt {
     },
     ClassDeclaration(
         String,
+        Vec<TypeParam>,
         Option<String>,
         Vec<String>,
         Vec<ClassMember>,
+        bool, // abstract class
         SourceLocation,
-    ), // class Name [extends Parent] [implements Interfaces] { members... }
-    InterfaceDeclaration(String, Vec<InterfaceMember>, SourceLocation), // interface Name { methods... }
+    ), // [abstract] class Name[<T>] [extends Parent] [implements Interfaces] { members... }
+    InterfaceDeclaration(String, Vec<TypeParam>, Vec<InterfaceMember>, SourceLocation), // interface Name[<T>] { methods... }
+    EnumDeclaration(String, Vec<EnumVariant>, SourceLocation), // enum Name { A, B(x, y) }
+    TypeAlias(String, Vec<TypeParam>, Type, SourceLocation),   // type Name[<T>] = tipo
     Export(Box<Stmt>, SourceLocation),                                  // export statement
     Use(String, SourceLocation),                                        // use "module/path"
     Import(ImportKind, String, SourceLocation),                         // import statement
     Namespace(String, Vec<Stmt>, SourceLocation),                       // namespace Name { ... }
+    Test(String, Box<Stmt>, bool, SourceLocation), // test "nome" { ... } / test.skip "nome" { ... } (nome, corpo, skip)
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -151,6 +222,13 @@ pub enum Expr {
     Match(Box<Expr>, Vec<MatchArm>, SourceLocation),          // match expr { pat => body, ... }
     Spread(Box<Expr>, SourceLocation),                         // ...expr
     Try(Box<Expr>, SourceLocation),                            // expr?
+    Range(Box<Expr>, Box<Expr>, bool, SourceLocation),         // início..fim / início..=fim (inclusivo)
+    Yield(Option<Box<Expr>>, SourceLocation),                  // yield [expr]
+    OptionalChain(Box<Expr>, SourceLocation),                  // cadeia com `?.`: null curto-circuita a cadeia inteira
+    OptionalPropertyAccess(Box<Expr>, String, SourceLocation), // object?.property
+    OptionalMethodCall(Box<Expr>, String, Vec<Expr>, SourceLocation), // object?.method(args...)
+    OptionalIndex(Box<Expr>, Box<Expr>, SourceLocation),       // array?.[index]
+    NullishCoalescing(Box<Expr>, Box<Expr>, SourceLocation),   // a ?? b
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -162,6 +240,7 @@ pub enum Pattern {
     Tuple(Vec<Pattern>),
     Object(Vec<(String, Pattern)>),
     Rest(String), // ...rest
+    Constructor(Option<String>, String, Vec<Pattern>), // [Enum.]Variante(padrões...)
 }
 
 impl Pattern {
@@ -185,9 +264,13 @@ pub struct MatchArm {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     Number(f64),
+    Int(i64),        // 42i
+    BigInt(String),  // 42n
+    Decimal(String), // 1.50d
     String(String),
     Bool(bool),
     Null,
+    Regex(String, String), // /padrão/flags
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -212,6 +295,7 @@ pub enum ClassMember {
         visibility: Visibility,
         is_static: bool,
         is_async: bool,
+        is_abstract: bool, // abstract function nome(); — sem corpo (bloco vazio)
         name: String,
         params: Vec<(String, Option<Type>, Option<Expr>)>,
         return_type: Option<Type>,
@@ -246,11 +330,18 @@ impl Default for Visibility {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+    pub name: String,
+    pub fields: Vec<(String, Option<Type>)>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct InterfaceMethod {
     pub name: String,
     pub params: Vec<(String, Option<Type>, Option<Expr>)>,
     pub return_type: Option<Type>,
+    pub body: Option<Box<Stmt>>, // implementação padrão, usada quando a classe não a define
 }
 
 #[derive(Debug, Clone, PartialEq)]
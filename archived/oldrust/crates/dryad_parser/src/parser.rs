@@ -1,7 +1,7 @@
 // crates/dryad_parser/src/parser.rs
 use crate::ast::{
-    ClassMember, Expr, ImportKind, InterfaceMember, InterfaceMethod, Literal, MatchArm, Pattern,
-    Program, Stmt, Visibility,
+    ClassMember, EnumVariant, Expr, ImportKind, InterfaceMember, InterfaceMethod, Literal,
+    MatchArm, ObjectTypeField, Pattern, Program, Stmt, Type, TypeParam, Visibility,
 };
 use dryad_errors::{error_catalog, DryadError, SourceLocation};
 use dryad_lexer::{
@@ -9,6 +9,9 @@ use dryad_lexer::{
     Lexer,
 };
 
+/// Parâmetro de método: nome, tipo anotado e valor padrão
+type MethodParam = (String, Option<Type>, Option<Expr>);
+
 pub struct Parser {
     tokens: Vec<TokenWithLocation>,
     position: usize,
@@ -119,14 +122,26 @@ impl Parser {
                     Ok(Some(Stmt::Expression(expr, self.current_location())))
                 }
             }
-            Token::Keyword(keyword) if keyword == "class" => Ok(Some(self.class_declaration()?)),
+            Token::Keyword(keyword) if keyword == "class" || keyword == "abstract" => {
+                Ok(Some(self.class_declaration()?))
+            }
             Token::Keyword(keyword) if keyword == "interface" => {
                 Ok(Some(self.interface_declaration()?))
             }
+            Token::Keyword(keyword) if keyword == "enum" => Ok(Some(self.enum_declaration()?)),
             Token::Keyword(keyword) if keyword == "export" => Ok(Some(self.export_statement()?)),
             Token::Keyword(keyword) if keyword == "import" => Ok(Some(self.import_statement()?)),
             Token::Keyword(keyword) if keyword == "use" => Ok(Some(self.use_statement()?)),
             Token::Keyword(keyword) if keyword == "return" => Ok(Some(self.return_statement()?)),
+            Token::Identifier(name) if name == "test" && self.is_test_declaration() => {
+                Ok(Some(self.test_declaration()?))
+            }
+            // `type` é contextual: só declara um alias quando seguido de um nome
+            Token::Identifier(name)
+                if name == "type" && matches!(self.peek_next(), Token::Identifier(_)) =>
+            {
+                Ok(Some(self.type_alias_declaration()?))
+            }
             _ => {
                 // Verifica se é assignment (identificador seguido de = ou +=, -=, etc.)
                 if let Token::Identifier(_) = self.peek() {
@@ -142,7 +157,7 @@ impl Parser {
                             Ok(Some(stmt))
                         }
                         Token::Operator(op)
-                            if op == "+=" || op == "-=" || op == "*=" || op == "/=" =>
+                            if op == "+=" || op == "-=" || op == "*=" || op == "/=" || op == "??=" =>
                         {
                             // É um assignment composto, volta e processa
                             self.position = checkpoint;
@@ -336,7 +351,11 @@ impl Parser {
     }
 
     fn assignment(&mut self) -> Result<Expr, DryadError> {
-        let expr = self.logical_or()?;
+        if matches!(self.peek(), Token::Keyword(k) if k == "yield") {
+            return self.yield_expression();
+        }
+
+        let expr = self.nullish_coalescing()?;
 
         if matches!(self.peek(), Token::Symbol('=')) {
             self.advance(); // consume '='
@@ -360,6 +379,36 @@ impl Parser {
         Ok(expr)
     }
 
+    fn yield_expression(&mut self) -> Result<Expr, DryadError> {
+        let location = self.current_location();
+        self.advance(); // consume 'yield'
+
+        // `yield` sem valor quando o próximo token encerra a expressão
+        let has_value = !matches!(
+            self.peek(),
+            Token::Symbol(';' | ')' | ']' | '}' | ',') | Token::Eof
+        );
+        let value = if has_value {
+            Some(Box::new(self.assignment()?))
+        } else {
+            None
+        };
+
+        Ok(Expr::Yield(value, location))
+    }
+
+    fn nullish_coalescing(&mut self) -> Result<Expr, DryadError> {
+        let mut expr = self.logical_or()?;
+
+        while self.match_operator("??") {
+            let location = self.current_location();
+            let right = self.logical_or()?;
+            expr = Expr::NullishCoalescing(Box::new(expr), Box::new(right), location);
+        }
+
+        Ok(expr)
+    }
+
     fn logical_or(&mut self) -> Result<Expr, DryadError> {
         let mut expr = self.logical_and()?;
 
@@ -439,18 +488,39 @@ impl Parser {
     }
 
     fn comparison(&mut self) -> Result<Expr, DryadError> {
-        let mut expr = self.shift()?;
+        let mut expr = self.range()?;
 
-        while self.match_any_operator(&[">", ">=", "<", "<="]) {
+        loop {
             let location = self.current_location();
-            let operator = self.previous_operator().unwrap();
-            let right = self.shift()?;
+            let operator = if self.match_any_operator(&[">", ">=", "<", "<="]) {
+                self.previous_operator().unwrap()
+            } else if matches!(self.peek(), Token::Identifier(name) if name == "instanceof") {
+                self.advance(); // consume 'instanceof'
+                "instanceof".to_string()
+            } else {
+                break;
+            };
+            let right = self.range()?;
             expr = Expr::Binary(Box::new(expr), operator, Box::new(right), location);
         }
 
         Ok(expr)
     }
 
+    fn range(&mut self) -> Result<Expr, DryadError> {
+        let expr = self.shift()?;
+
+        // Intervalos não são associativos: `a..b..c` é erro de sintaxe
+        if self.match_any_operator(&["..", "..="]) {
+            let location = self.current_location();
+            let inclusive = self.previous_operator().unwrap() == "..=";
+            let end = self.shift()?;
+            return Ok(Expr::Range(Box::new(expr), Box::new(end), inclusive, location));
+        }
+
+        Ok(expr)
+    }
+
     fn shift(&mut self) -> Result<Expr, DryadError> {
         let mut expr = self.term()?;
 
@@ -512,6 +582,18 @@ impl Parser {
             return Ok(Expr::Unary(operator, Box::new(right), location));
         }
 
+        // `typeof x` é contextual: `typeof` continua válido como nome comum
+        if matches!(self.peek(), Token::Identifier(name) if name == "typeof")
+            && !matches!(
+                self.peek_next(),
+                Token::Operator(_) | Token::Symbol(';' | ')' | ',' | '.' | '=' | ']' | '}' | ':') | Token::Eof
+            )
+        {
+            self.advance(); // consume 'typeof'
+            let operand = self.unary()?;
+            return Ok(Expr::Unary("typeof".to_string(), Box::new(operand), location));
+        }
+
         // Pré-incremento e pré-decremento
         if self.match_any_operator(&["++"]) {
             let expr = self.unary()?;
@@ -527,7 +609,10 @@ impl Parser {
     }
 
     fn postfix(&mut self) -> Result<Expr, DryadError> {
+        let chain_location = self.current_location();
         let mut expr = self.primary()?;
+        // Uma cadeia com `?.` é envolvida em OptionalChain para curto-circuitar por inteiro
+        let mut is_optional_chain = false;
 
         loop {
             let location = self.current_location();
@@ -562,7 +647,8 @@ impl Parser {
                             self.advance();
                             expr = Expr::TupleAccess(Box::new(expr), index, location);
                         }
-                        Token::Identifier(property_name) => {
+                        // Palavras-chave também servem como nome de propriedade: map.set(k, v)
+                        Token::Identifier(property_name) | Token::Keyword(property_name) => {
                             // Acesso a propriedade ou chamada de método
                             let name = property_name.clone();
                             self.advance();
@@ -606,6 +692,35 @@ impl Parser {
                         }
                     }
                 }
+                // Navegação segura: expr?.property, expr?.method(args...), expr?.[index]
+                Token::Operator(op) if op == "?." => {
+                    self.advance(); // consome '?.'
+                    is_optional_chain = true;
+                    match self.peek() {
+                        Token::Symbol('[') => {
+                            self.advance(); // consome '['
+                            let index = self.expression()?;
+                            if !matches!(self.peek(), Token::Symbol(']')) {
+                                return Err(DryadError::from_catalog(error_catalog::e2071(), self.current_location()));
+                            }
+                            self.advance(); // consome ']'
+                            expr = Expr::OptionalIndex(Box::new(expr), Box::new(index), location);
+                        }
+                        Token::Identifier(property_name) | Token::Keyword(property_name) => {
+                            let name = property_name.clone();
+                            self.advance();
+                            if matches!(self.peek(), Token::Symbol('(')) {
+                                let args = self.method_arguments()?;
+                                expr = Expr::OptionalMethodCall(Box::new(expr), name, args, location);
+                            } else {
+                                expr = Expr::OptionalPropertyAccess(Box::new(expr), name, location);
+                            }
+                        }
+                        _ => {
+                            return Err(DryadError::from_catalog(error_catalog::e2072(), self.current_location()));
+                        }
+                    }
+                }
                 // Chamada de função: expr(args...)
                 Token::Symbol('(') => {
                     self.advance(); // consome '('
@@ -661,9 +776,39 @@ impl Parser {
             }
         }
 
+        if is_optional_chain {
+            expr = Expr::OptionalChain(Box::new(expr), chain_location);
+        }
+
         Ok(expr)
     }
 
+    /// Argumentos de uma chamada de método: `(arg1, arg2, ...)`
+    fn method_arguments(&mut self) -> Result<Vec<Expr>, DryadError> {
+        self.advance(); // consume '('
+
+        let mut args = Vec::new();
+        if !matches!(self.peek(), Token::Symbol(')')) {
+            loop {
+                args.push(self.expression()?);
+
+                match self.peek() {
+                    Token::Symbol(',') => {
+                        self.advance(); // consume comma
+                    }
+                    Token::Symbol(')') => break,
+                    _ => return Err(DryadError::from_catalog(error_catalog::e2073(), self.current_location())),
+                }
+            }
+        }
+
+        if !matches!(self.advance(), Token::Symbol(')')) {
+            return Err(DryadError::from_catalog(error_catalog::e2074(), self.current_location()));
+        }
+
+        Ok(args)
+    }
+
     fn primary(&mut self) -> Result<Expr, DryadError> {
         let location = self.current_location();
         match &self.peek() {
@@ -677,6 +822,26 @@ impl Parser {
                 self.advance();
                 Ok(Expr::Literal(Literal::Number(val), location))
             }
+            Token::Int(value) => {
+                let val = *value;
+                self.advance();
+                Ok(Expr::Literal(Literal::Int(val), location))
+            }
+            Token::BigInt(digits) => {
+                let val = digits.clone();
+                self.advance();
+                Ok(Expr::Literal(Literal::BigInt(val), location))
+            }
+            Token::Decimal(text) => {
+                let val = text.clone();
+                self.advance();
+                Ok(Expr::Literal(Literal::Decimal(val), location))
+            }
+            Token::Regex(pattern, flags) => {
+                let val = Literal::Regex(pattern.clone(), flags.clone());
+                self.advance();
+                Ok(Expr::Literal(val, location))
+            }
             Token::String(value) => {
                 let val = value.clone();
                 self.advance();
@@ -1158,6 +1323,20 @@ impl Parser {
                     location,
                 ))
             }
+            Token::Operator(op) if op == "??=" => {
+                // x ??= value  =>  x = x ?? value
+                let value = self.expression()?;
+                let assignment_value = Expr::NullishCoalescing(
+                    Box::new(Expr::Variable(name.clone(), self.current_location())),
+                    Box::new(value),
+                    self.current_location(),
+                );
+                Ok(Stmt::Assignment(
+                    Pattern::Identifier(name),
+                    assignment_value,
+                    location,
+                ))
+            }
             _ => Err(DryadError::from_catalog(error_catalog::e2013(), self.current_location())),
         }
     }
@@ -1173,14 +1352,22 @@ impl Parser {
                 let func_stmt = self.function_declaration()?;
                 Ok(Stmt::Export(Box::new(func_stmt), location))
             }
-            Token::Keyword(keyword) if keyword == "class" => {
+            Token::Keyword(keyword) if keyword == "class" || keyword == "abstract" => {
                 let class_stmt = self.class_declaration()?;
                 Ok(Stmt::Export(Box::new(class_stmt), location))
             }
+            Token::Keyword(keyword) if keyword == "enum" => {
+                let enum_stmt = self.enum_declaration()?;
+                Ok(Stmt::Export(Box::new(enum_stmt), location))
+            }
             Token::Keyword(keyword) if keyword == "let" => {
                 let var_stmt = self.var_declaration()?;
                 Ok(Stmt::Export(Box::new(var_stmt), location))
             }
+            Token::Identifier(name) if name == "type" => {
+                let alias_stmt = self.type_alias_declaration()?;
+                Ok(Stmt::Export(Box::new(alias_stmt), location))
+            }
             _ => Err(DryadError::from_catalog(error_catalog::e4001(), self.current_location())),
         }
     }
@@ -1327,13 +1514,19 @@ impl Parser {
 
             // Se o próximo token é 'in', é um foreach
             if matches!(self.peek(), Token::Keyword(ref k) if k == "in") {
-                return self.foreach_statement(var);
+                return self.foreach_statement(Pattern::Identifier(var));
             } else {
                 // Não é foreach, volta para posição anterior para fazer for tradicional
                 self.position = saved_position;
             }
         }
 
+        // Foreach com desestruturação: for ((k, v) in ...) / for ([a, b] in ...)
+        if matches!(self.peek(), Token::Symbol('(' | '[')) {
+            let pattern = self.parse_pattern()?;
+            return self.foreach_statement(pattern);
+        }
+
         // Parse traditional for loop: for (init; condition; update) { body }
         // Parse initialization (opcional)
         let init = if matches!(self.peek(), Token::Symbol(';')) {
@@ -1450,7 +1643,7 @@ impl Parser {
         Ok(Stmt::For(init, condition, update, body, location))
     }
 
-    fn foreach_statement(&mut self, var_name: String) -> Result<Stmt, DryadError> {
+    fn foreach_statement(&mut self, pattern: Pattern) -> Result<Stmt, DryadError> {
         let location = self.current_location();
         // Já temos o var_name, agora consume 'in'
         if !matches!(self.peek(), Token::Keyword(ref k) if k == "in") {
@@ -1476,7 +1669,7 @@ impl Parser {
         let body = Box::new(self.block_statement()?);
 
         Ok(Stmt::ForEach(
-            Pattern::Identifier(var_name),
+            pattern,
             iterable,
             body,
             location,
@@ -1487,11 +1680,18 @@ impl Parser {
         let location = self.current_location();
         self.advance(); // consume 'function'
 
+        // function* declara um gerador
+        let is_generator = matches!(self.peek(), Token::Operator(op) if op == "*");
+        if is_generator {
+            self.advance(); // consume '*'
+        }
+
         // Parse function name
         let name = match self.advance() {
             Token::Identifier(n) => n.clone(),
             _ => return Err(DryadError::from_catalog(error_catalog::e2012(), self.current_location())),
         };
+        let type_params = self.parse_type_params()?;
 
         // Expect opening parenthesis
         if !matches!(self.advance(), Token::Symbol('(')) {
@@ -1555,12 +1755,14 @@ impl Parser {
 
         Ok(Stmt::FunctionDeclaration {
             name,
+            type_params,
             params,
             rest_param: None,
             return_type,
             body,
             location,
             is_async: false,
+            is_generator,
         })
     }
 
@@ -1579,6 +1781,7 @@ impl Parser {
             Token::Identifier(n) => n.clone(),
             _ => return Err(DryadError::from_catalog(error_catalog::e2018(), self.current_location())),
         };
+        let type_params = self.parse_type_params()?;
 
         // Expect opening parenthesis
         if !matches!(self.advance(), Token::Symbol('(')) {
@@ -1644,12 +1847,14 @@ impl Parser {
 
         Ok(Stmt::FunctionDeclaration {
             name,
+            type_params,
             params,
             rest_param: None,
             return_type,
             body,
             location,
             is_async: true,
+            is_generator: false,
         })
     }
 
@@ -1740,6 +1945,13 @@ impl Parser {
 
     fn class_declaration(&mut self) -> Result<Stmt, DryadError> {
         let location = self.current_location();
+        let is_abstract = matches!(self.peek(), Token::Keyword(k) if k == "abstract");
+        if is_abstract {
+            self.advance(); // consume 'abstract'
+            if !matches!(self.peek(), Token::Keyword(k) if k == "class") {
+                return Err(DryadError::from_catalog(error_catalog::e2133(), self.current_location()));
+            }
+        }
         self.advance(); // consume 'class'
 
         // Parse class name
@@ -1753,26 +1965,24 @@ impl Parser {
                 return Err(DryadError::from_catalog(error_catalog::e2087(), self.current_location()))
             }
         };
+        let type_params = self.parse_type_params()?;
 
         // Check for inheritance (extends)
         let (parent, interfaces) = if matches!(self.peek(), Token::Keyword(k) if k == "extends") {
             self.advance(); // consume 'extends'
-            match self.peek() {
-                Token::Identifier(parent_name) => {
-                    let parent = parent_name.clone();
-                    self.advance();
-
+            match self.parse_supertype_name()? {
+                Some(parent) => {
                     // Check for implements after extends
-                    let interfaces = self.parse_implements_list();
+                    let interfaces = self.parse_implements_list()?;
                     (Some(parent), interfaces)
                 }
-                _ => {
+                None => {
                     return Err(DryadError::from_catalog(error_catalog::e2088(), self.current_location()))
                 }
             }
         } else if matches!(self.peek(), Token::Keyword(k) if k == "implements") {
             // Implements without extends
-            let interfaces = self.parse_implements_list();
+            let interfaces = self.parse_implements_list()?;
             (None, interfaces)
         } else {
             (None, Vec::new())
@@ -1787,7 +1997,13 @@ impl Parser {
         // Parse class members
         let mut members = Vec::new();
         while !matches!(self.peek(), Token::Symbol('}') | Token::Eof) {
-            members.push(self.class_member()?);
+            let member_location = self.current_location();
+            let member = self.class_member()?;
+            // Métodos abstratos só existem em classes abstratas
+            if !is_abstract && matches!(member, ClassMember::Method { is_abstract: true, .. }) {
+                return Err(DryadError::from_catalog(error_catalog::e2133(), member_location));
+            }
+            members.push(member);
         }
 
         // Expect closing brace
@@ -1797,34 +2013,28 @@ impl Parser {
         self.advance(); // consume '}'
 
         Ok(Stmt::ClassDeclaration(
-            name, parent, interfaces, members, location,
+            name, type_params, parent, interfaces, members, is_abstract, location,
         ))
     }
 
-    fn parse_implements_list(&mut self) -> Vec<String> {
+    fn parse_implements_list(&mut self) -> Result<Vec<String>, DryadError> {
         let mut interfaces = Vec::new();
 
         if matches!(self.peek(), Token::Keyword(k) if k == "implements") {
             self.advance(); // consume 'implements'
 
-            loop {
-                match self.peek() {
-                    Token::Identifier(id) => {
-                        interfaces.push(id.clone());
-                        self.advance();
+            while let Some(interface) = self.parse_supertype_name()? {
+                interfaces.push(interface);
 
-                        if matches!(self.peek(), Token::Symbol(',')) {
-                            self.advance(); // consume ','
-                        } else {
-                            break;
-                        }
-                    }
-                    _ => break,
+                if matches!(self.peek(), Token::Symbol(',')) {
+                    self.advance(); // consume ','
+                } else {
+                    break;
                 }
             }
         }
 
-        interfaces
+        Ok(interfaces)
     }
 
     fn interface_declaration(&mut self) -> Result<Stmt, DryadError> {
@@ -1843,6 +2053,7 @@ impl Parser {
                 return Err(DryadError::from_catalog(error_catalog::e2105(), self.current_location()))
             }
         };
+        let type_params = self.parse_type_params()?;
 
         // Expect opening brace
         if !matches!(self.peek(), Token::Symbol('{')) {
@@ -1862,7 +2073,91 @@ impl Parser {
         }
         self.advance(); // consume '}'
 
-        Ok(Stmt::InterfaceDeclaration(name, members, location))
+        Ok(Stmt::InterfaceDeclaration(name, type_params, members, location))
+    }
+
+    fn enum_declaration(&mut self) -> Result<Stmt, DryadError> {
+        let location = self.current_location();
+
+        self.advance(); // consume 'enum'
+
+        let name = match self.peek() {
+            Token::Identifier(id) => {
+                let name = id.clone();
+                self.advance();
+                name
+            }
+            _ => return Err(DryadError::from_catalog(error_catalog::e2122(), self.current_location())),
+        };
+
+        if !matches!(self.peek(), Token::Symbol('{')) {
+            return Err(DryadError::from_catalog(error_catalog::e2123(), self.current_location()));
+        }
+        self.advance(); // consume '{'
+
+        let mut variants = Vec::new();
+        while !matches!(self.peek(), Token::Symbol('}') | Token::Eof) {
+            let variant_name = match self.peek() {
+                Token::Identifier(id) => {
+                    let name = id.clone();
+                    self.advance();
+                    name
+                }
+                _ => {
+                    return Err(DryadError::from_catalog(error_catalog::e2124(), self.current_location()))
+                }
+            };
+
+            // Campos opcionais: Variante(campo, campo: tipo)
+            let mut fields = Vec::new();
+            if matches!(self.peek(), Token::Symbol('(')) {
+                self.advance(); // consume '('
+                while !matches!(self.peek(), Token::Symbol(')')) {
+                    let field_name = match self.peek() {
+                        Token::Identifier(id) => {
+                            let name = id.clone();
+                            self.advance();
+                            name
+                        }
+                        _ => {
+                            return Err(DryadError::from_catalog(error_catalog::e2125(), self.current_location()))
+                        }
+                    };
+                    let field_type = if matches!(self.peek(), Token::Symbol(':')) {
+                        self.advance(); // consume ':'
+                        Some(self.parse_type()?)
+                    } else {
+                        None
+                    };
+                    fields.push((field_name, field_type));
+
+                    if matches!(self.peek(), Token::Symbol(',')) {
+                        self.advance(); // consume ','
+                    } else if !matches!(self.peek(), Token::Symbol(')')) {
+                        return Err(DryadError::from_catalog(error_catalog::e2125(), self.current_location()));
+                    }
+                }
+                self.advance(); // consume ')'
+            }
+
+            variants.push(EnumVariant {
+                name: variant_name,
+                fields,
+            });
+
+            if matches!(self.peek(), Token::Symbol(',')) {
+                self.advance(); // consume ','
+            } else {
+                break;
+            }
+        }
+
+        if !matches!(self.peek(), Token::Symbol('}')) {
+            return Err(DryadError::from_catalog(error_catalog::e2126(), self.current_location()));
+        }
+        self.advance(); // consume '}'
+
+        Ok(Stmt::EnumDeclaration(name, variants, location))
     }
 
     fn interface_member(&mut self) -> Result<InterfaceMember, DryadError> {
@@ -1929,18 +2224,65 @@ impl Parser {
             None
         };
 
-        // Expect semicolon
-        if matches!(self.peek(), Token::Symbol(';')) {
-            self.advance(); // consume ';'
-        }
+        // Implementação padrão opcional; sem ela, ';' é opcional
+        let body = if matches!(self.peek(), Token::Symbol('{')) {
+            Some(Box::new(self.block_statement()?))
+        } else {
+            if matches!(self.peek(), Token::Symbol(';')) {
+                self.advance(); // consume ';'
+            }
+            None
+        };
 
         Ok(InterfaceMember::Method(InterfaceMethod {
             name,
             params,
             return_type,
+            body,
         }))
     }
 
+    /// Lista de parâmetros de um método: `(a: number, b = 1)`
+    fn method_params(&mut self) -> Result<Vec<MethodParam>, DryadError> {
+        if !matches!(self.peek(), Token::Symbol('(')) {
+            return Err(DryadError::from_catalog(error_catalog::e2092(), self.current_location()));
+        }
+        self.advance(); // consume '('
+
+        let mut params = Vec::new();
+        while !matches!(self.peek(), Token::Symbol(')')) {
+            match self.advance() {
+                Token::Identifier(param_name) => {
+                    let name = param_name.clone();
+                    let param_type = if matches!(self.peek(), Token::Symbol(':')) {
+                        self.advance(); // consume ':'
+                        Some(self.parse_type()?)
+                    } else {
+                        None
+                    };
+
+                    let default_value = if matches!(self.peek(), Token::Symbol('=')) {
+                        self.advance(); // consume '='
+                        Some(self.expression()?)
+                    } else {
+                        None
+                    };
+
+                    params.push((name, param_type, default_value));
+
+                    if matches!(self.peek(), Token::Symbol(',')) {
+                        self.advance(); // consome ','
+                    } else if !matches!(self.peek(), Token::Symbol(')')) {
+                        return Err(DryadError::from_catalog(error_catalog::e2093(), self.current_location()));
+                    }
+                }
+                _ => return Err(DryadError::from_catalog(error_catalog::e2094(), self.current_location())),
+            }
+        }
+        self.advance(); // consume ')'
+        Ok(params)
+    }
+
     fn class_member(&mut self) -> Result<ClassMember, DryadError> {
         // Parse visibility (default is public)
         let visibility = self.parse_visibility();
@@ -1953,6 +2295,47 @@ impl Parser {
             false
         };
 
+        // abstract function nome(params): tipo;
+        if matches!(self.peek(), Token::Keyword(k) if k == "abstract") {
+            let location = self.current_location();
+            self.advance(); // consume 'abstract'
+            if is_static || !matches!(self.peek(), Token::Keyword(k) if k == "function") {
+                return Err(DryadError::from_catalog(error_catalog::e2133(), location));
+            }
+            self.advance(); // consume 'function'
+
+            let name = match self.peek() {
+                Token::Identifier(id) => {
+                    let name = id.clone();
+                    self.advance();
+                    name
+                }
+                _ => return Err(DryadError::from_catalog(error_catalog::e2091(), self.current_location())),
+            };
+            let params = self.method_params()?;
+            let return_type = if matches!(self.peek(), Token::Symbol(':')) {
+                self.advance(); // consume ':'
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            if !matches!(self.peek(), Token::Symbol(';')) {
+                return Err(DryadError::from_catalog(error_catalog::e2133(), self.current_location()));
+            }
+            self.advance(); // consume ';'
+
+            return Ok(ClassMember::Method {
+                visibility,
+                is_static,
+                is_async: false,
+                is_abstract: true,
+                name,
+                params,
+                return_type,
+                body: Box::new(Stmt::Block(Vec::new(), location)),
+            });
+        }
+
         // Parse member type (function, async function, or property)
         match self.peek() {
             Token::Keyword(k) if k == "async" => {
@@ -2026,6 +2409,7 @@ impl Parser {
                         visibility,
                         is_static,
                         is_async: true,
+                        is_abstract: false,
                         name,
                         params,
                         return_type,
@@ -2049,44 +2433,61 @@ impl Parser {
                 };
 
                 // Parse parameters
-                if !matches!(self.peek(), Token::Symbol('(')) {
-                    return Err(DryadError::from_catalog(error_catalog::e2092(), self.current_location()));
-                }
-                self.advance(); // consume '('
+                let params = self.method_params()?;
 
-                let mut params = Vec::new();
-                while !matches!(self.peek(), Token::Symbol(')')) {
-                    match self.advance() {
-                        Token::Identifier(param_name) => {
-                            let name = param_name.clone();
-                            let param_type = if matches!(self.peek(), Token::Symbol(':')) {
-                                self.advance(); // consume ':'
-                                Some(self.parse_type()?)
-                            } else {
-                                None
-                            };
-
-                            let default_value = if matches!(self.peek(), Token::Symbol('=')) {
-                                self.advance(); // consume '='
-                                Some(self.expression()?)
-                            } else {
-                                None
-                            };
+                // Parse return type
+                let return_type = if matches!(self.peek(), Token::Symbol(':')) {
+                    self.advance(); // consume ':'
+                    Some(self.parse_type()?)
+                } else {
+                    None
+                };
 
-                            params.push((name, param_type, default_value));
+                // Parse method body
+                let body = Box::new(self.block_statement()?);
 
-                            if matches!(self.peek(), Token::Symbol(',')) {
-                                self.advance(); // consome ','
-                            } else if !matches!(self.peek(), Token::Symbol(')')) {
-                                return Err(DryadError::from_catalog(error_catalog::e2093(), self.current_location()));
-                            }
+                Ok(ClassMember::Method {
+                    visibility,
+                    is_static,
+                    is_async: false,
+                    is_abstract: false,
+                    name,
+                    params,
+                    return_type,
+                    body,
+                })
+            }
+            // operator +(other) { ... } / operator [](i) { ... } / operator -() { ... }
+            Token::Identifier(id)
+                if id == "operator"
+                    && matches!(self.peek_next(), Token::Operator(_) | Token::Symbol('[')) =>
+            {
+                let location = self.current_location();
+                self.advance(); // consume 'operator'
+                let symbol = match self.advance() {
+                    Token::Operator(op) => op.clone(),
+                    Token::Symbol('[') => {
+                        if !matches!(self.advance(), Token::Symbol(']')) {
+                            return Err(DryadError::from_catalog(error_catalog::e2128(), location));
+                        }
+                        if matches!(self.peek(), Token::Symbol('=')) {
+                            self.advance(); // consume '='
+                            "[]=".to_string()
+                        } else {
+                            "[]".to_string()
                         }
-                        _ => return Err(DryadError::from_catalog(error_catalog::e2094(), self.current_location())),
                     }
-                }
-                self.advance(); // consume ')'
+                    _ => return Err(DryadError::from_catalog(error_catalog::e2128(), location)),
+                };
+
+                let params = self.method_params()?;
+                let name = match (symbol.as_str(), params.len()) {
+                    ("-", 0) => "operator neg".to_string(),
+                    ("+" | "-" | "*" | "/" | "%" | "**" | "==" | "!=" | "<" | "<=" | ">" | ">=" | "[]", 1)
+                    | ("[]=", 2) => format!("operator{}", symbol),
+                    _ => return Err(DryadError::from_catalog(error_catalog::e2128(), location)),
+                };
 
-                // Parse return type
                 let return_type = if matches!(self.peek(), Token::Symbol(':')) {
                     self.advance(); // consume ':'
                     Some(self.parse_type()?)
@@ -2094,13 +2495,13 @@ impl Parser {
                     None
                 };
 
-                // Parse method body
                 let body = Box::new(self.block_statement()?);
 
                 Ok(ClassMember::Method {
                     visibility,
                     is_static,
                     is_async: false,
+                    is_abstract: false,
                     name,
                     params,
                     return_type,
@@ -2294,32 +2695,46 @@ impl Parser {
     }
 
     fn parse_type(&mut self) -> Result<crate::ast::Type, DryadError> {
-        let token = self.advance();
-        match token {
-            Token::Identifier(name) => {
-                let name_str = name.clone();
-                match name_str.as_str() {
-                    "number" => Ok(crate::ast::Type::Number),
-                    "string" => Ok(crate::ast::Type::String),
-                    "bool" => Ok(crate::ast::Type::Bool),
-                    "null" => Ok(crate::ast::Type::Null),
-                    "any" => Ok(crate::ast::Type::Any),
-                    _ => {
-                        // Check for array suffix: type[]
-                        if matches!(self.peek(), Token::Symbol('['))
-                            && matches!(self.peek_next(), Token::Symbol(']'))
-                        {
-                            self.advance(); // [
-                            self.advance(); // ]
-                            Ok(crate::ast::Type::Array(Box::new(crate::ast::Type::Class(
-                                name_str,
-                            ))))
-                        } else {
-                            Ok(crate::ast::Type::Class(name_str))
-                        }
+        // Uniões: string | number | null
+        let first = self.parse_type_operand()?;
+        if !matches!(self.peek(), Token::Operator(op) if op == "|") {
+            return Ok(first);
+        }
+        let mut members = vec![first];
+        while matches!(self.peek(), Token::Operator(op) if op == "|") {
+            self.advance(); // consume '|'
+            members.push(self.parse_type_operand()?);
+        }
+        Ok(crate::ast::Type::Union(members))
+    }
+
+    /// Um membro de união: tipo primitivo, nomeado, literal, tupla ou objeto,
+    /// seguido dos sufixos `[]` e `?`
+    fn parse_type_operand(&mut self) -> Result<crate::ast::Type, DryadError> {
+        let token = self.advance().clone();
+        let mut parsed = match token {
+            Token::Identifier(name) => match name.as_str() {
+                "number" => crate::ast::Type::Number,
+                "string" => crate::ast::Type::String,
+                "bool" => crate::ast::Type::Bool,
+                "null" => crate::ast::Type::Null,
+                "any" => crate::ast::Type::Any,
+                "int" => crate::ast::Type::Int,
+                "bigint" => crate::ast::Type::BigInt,
+                "decimal" => crate::ast::Type::Decimal,
+                // Box<number>, Map<string, T>
+                _ if matches!(self.peek(), Token::Operator(op) if op == "<") => {
+                    self.advance(); // consume '<'
+                    let mut args = vec![self.parse_type()?];
+                    while matches!(self.peek(), Token::Symbol(',')) {
+                        self.advance(); // consume ','
+                        args.push(self.parse_type()?);
                     }
+                    self.expect_type_close()?;
+                    crate::ast::Type::Generic(name, args)
                 }
-            }
+                _ => crate::ast::Type::Class(name),
+            },
             Token::Symbol('(') => {
                 // Parse tuple type: (type1, type2)
                 let mut types = Vec::new();
@@ -2336,10 +2751,155 @@ impl Parser {
                 if !matches!(self.advance(), Token::Symbol(')')) {
                     return Err(DryadError::from_catalog(error_catalog::e2100(), self.current_location()));
                 }
-                Ok(crate::ast::Type::Tuple(types))
+                // `(string | number)[]`: um único tipo entre parênteses apenas agrupa
+                if types.len() == 1 {
+                    types.remove(0)
+                } else {
+                    crate::ast::Type::Tuple(types)
+                }
+            }
+            Token::Symbol('{') => crate::ast::Type::Object(self.parse_object_type()?),
+            Token::Literal(ref value) if value == "null" => crate::ast::Type::Null,
+            // Tipos literais: "GET", 200, true
+            Token::String(s) => crate::ast::Type::Literal(Literal::String(s)),
+            Token::Number(n) => crate::ast::Type::Literal(Literal::Number(n)),
+            Token::Int(n) => crate::ast::Type::Literal(Literal::Int(n)),
+            Token::BigInt(n) => crate::ast::Type::Literal(Literal::BigInt(n)),
+            Token::Decimal(n) => crate::ast::Type::Literal(Literal::Decimal(n)),
+            Token::Boolean(b) => crate::ast::Type::Literal(Literal::Bool(b)),
+            Token::Operator(ref op) if op == "-" && matches!(self.peek(), Token::Number(_)) => {
+                let Token::Number(n) = self.advance().clone() else { unreachable!() };
+                crate::ast::Type::Literal(Literal::Number(-n))
+            }
+            _ => return Err(DryadError::from_catalog_fmt(error_catalog::e2101(), &format!("Tipo inválido: {:?}", token), self.current_location())),
+        };
+
+        // Sufixos: type[], type[][], type? (= type | null)
+        loop {
+            if matches!(self.peek(), Token::Symbol('['))
+                && matches!(self.peek_next(), Token::Symbol(']'))
+            {
+                self.advance(); // [
+                self.advance(); // ]
+                parsed = crate::ast::Type::Array(Box::new(parsed));
+            } else if matches!(self.peek(), Token::Symbol('?')) {
+                self.advance(); // ?
+                parsed = crate::ast::Type::Union(vec![parsed, crate::ast::Type::Null]);
+            } else {
+                break;
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Campos de um tipo objeto após o `{`: `name: string, age?: number }`
+    fn parse_object_type(&mut self) -> Result<Vec<ObjectTypeField>, DryadError> {
+        let mut fields = Vec::new();
+        while !matches!(self.peek(), Token::Symbol('}')) {
+            let name = match self.advance().clone() {
+                Token::Identifier(name) | Token::String(name) | Token::Keyword(name) => name,
+                _ => return Err(DryadError::from_catalog(error_catalog::e2132(), self.current_location())),
+            };
+            let optional = matches!(self.peek(), Token::Symbol('?'));
+            if optional {
+                self.advance(); // consume '?'
+            }
+            if !matches!(self.advance(), Token::Symbol(':')) {
+                return Err(DryadError::from_catalog(error_catalog::e2132(), self.current_location()));
             }
-            _ => Err(DryadError::from_catalog_fmt(error_catalog::e2101(), &format!("Tipo inválido: {:?}", token), self.current_location())),
+            let field_type = self.parse_type()?;
+            fields.push(ObjectTypeField { name, field_type, optional });
+            if matches!(self.peek(), Token::Symbol(',') | Token::Symbol(';')) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        if !matches!(self.advance(), Token::Symbol('}')) {
+            return Err(DryadError::from_catalog(error_catalog::e2132(), self.current_location()));
+        }
+        Ok(fields)
+    }
+
+    /// `type Nome[<T>] = tipo;`
+    fn type_alias_declaration(&mut self) -> Result<Stmt, DryadError> {
+        let location = self.current_location();
+        self.advance(); // consume 'type'
+
+        let name = match self.advance() {
+            Token::Identifier(name) => name.clone(),
+            _ => return Err(DryadError::from_catalog(error_catalog::e2131(), self.current_location())),
+        };
+        let type_params = self.parse_type_params()?;
+        if !matches!(self.advance(), Token::Symbol('=')) {
+            return Err(DryadError::from_catalog(error_catalog::e2131(), self.current_location()));
+        }
+        let aliased = self.parse_type()?;
+
+        self.consume_semicolon()?;
+        Ok(Stmt::TypeAlias(name, type_params, aliased, location))
+    }
+
+    /// Fecha uma lista `<...>` de tipos; `>>` e `>>>` (tipos aninhados como
+    /// `Box<Box<T>>`) são consumidos um `>` por vez
+    fn expect_type_close(&mut self) -> Result<(), DryadError> {
+        let rest = match self.peek() {
+            Token::Operator(op) if op == ">" => None,
+            Token::Operator(op) if op == ">>" => Some(Token::Operator(">".to_string())),
+            Token::Operator(op) if op == ">>>" => Some(Token::Operator(">>".to_string())),
+            Token::Operator(op) if op == ">=" => Some(Token::Symbol('=')),
+            _ => return Err(DryadError::from_catalog(error_catalog::e2129(), self.current_location())),
+        };
+        match rest {
+            Some(token) => self.tokens[self.position].token = token,
+            None => {
+                self.advance();
+            }
+        }
+        Ok(())
+    }
+
+    /// Parâmetros de tipo opcionais de uma declaração: `<T, U extends Comparable<U>>`
+    fn parse_type_params(&mut self) -> Result<Vec<TypeParam>, DryadError> {
+        let mut type_params = Vec::new();
+        if !matches!(self.peek(), Token::Operator(op) if op == "<") {
+            return Ok(type_params);
+        }
+        self.advance(); // consume '<'
+        loop {
+            let name = match self.advance() {
+                Token::Identifier(name) => name.clone(),
+                _ => return Err(DryadError::from_catalog(error_catalog::e2130(), self.current_location())),
+            };
+            let bound = if matches!(self.peek(), Token::Keyword(k) if k == "extends") {
+                self.advance(); // consume 'extends'
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            type_params.push(TypeParam { name, bound });
+
+            if matches!(self.peek(), Token::Symbol(',')) {
+                self.advance(); // consume ','
+            } else {
+                break;
+            }
+        }
+        self.expect_type_close()?;
+        Ok(type_params)
+    }
+
+    /// Nome de uma superclasse ou interface em `extends`/`implements`; os
+    /// argumentos de tipo (`Comparable<Money>`) são apagados, pois classes e
+    /// interfaces são relacionadas pelo nome
+    fn parse_supertype_name(&mut self) -> Result<Option<String>, DryadError> {
+        if !matches!(self.peek(), Token::Identifier(_)) {
+            return Ok(None);
         }
+        Ok(match self.parse_type()? {
+            crate::ast::Type::Class(name) | crate::ast::Type::Generic(name, _) => Some(name),
+            _ => None,
+        })
     }
 
     // Métodos auxiliares
@@ -2441,6 +3001,52 @@ impl Parser {
         }
     }
 
+    fn peek_at(&self, offset: usize) -> &Token {
+        match self.tokens.get(self.position + offset) {
+            Some(token) => &token.token,
+            None => &Token::Eof,
+        }
+    }
+
+    // `test` não é palavra reservada: só inicia um bloco de teste quando seguido
+    // de uma string (`test "nome" {`) ou de `.skip` (`test.skip "nome" {`).
+    fn is_test_declaration(&self) -> bool {
+        match self.peek_at(1) {
+            Token::String(_) => matches!(self.peek_at(2), Token::Symbol('{')),
+            Token::Symbol('.') => {
+                matches!(self.peek_at(2), Token::Identifier(name) if name == "skip")
+                    && matches!(self.peek_at(3), Token::String(_))
+            }
+            _ => false,
+        }
+    }
+
+    fn test_declaration(&mut self) -> Result<Stmt, DryadError> {
+        let location = self.current_location();
+        self.advance(); // consome 'test'
+
+        let skip = if matches!(self.peek(), Token::Symbol('.')) {
+            self.advance(); // consome '.'
+            self.advance(); // consome 'skip'
+            true
+        } else {
+            false
+        };
+
+        let name = match self.peek() {
+            Token::String(name) => name.clone(),
+            _ => return Err(DryadError::from_catalog(error_catalog::e2121(), self.current_location())),
+        };
+        self.advance();
+
+        if !matches!(self.peek(), Token::Symbol('{')) {
+            return Err(DryadError::from_catalog(error_catalog::e2120(), self.current_location()));
+        }
+        let body = Box::new(self.block_statement()?);
+
+        Ok(Stmt::Test(name, body, skip, location))
+    }
+
     fn previous(&self) -> &Token {
         if self.position > 0 {
             &self.tokens[self.position - 1].token
@@ -3058,6 +3664,27 @@ impl Parser {
                 self.advance();
                 Ok(Pattern::Literal(Literal::Number(val)))
             }
+            Token::Int(v) => {
+                let val = *v;
+                self.advance();
+                Ok(Pattern::Literal(Literal::Int(val)))
+            }
+            Token::BigInt(v) => {
+                let val = v.clone();
+                self.advance();
+                Ok(Pattern::Literal(Literal::BigInt(val)))
+            }
+            Token::Decimal(v) => {
+                let val = v.clone();
+                self.advance();
+                Ok(Pattern::Literal(Literal::Decimal(val)))
+            }
+            // Casa strings que contêm um trecho reconhecido pela regex
+            Token::Regex(pattern, flags) => {
+                let val = Literal::Regex(pattern.clone(), flags.clone());
+                self.advance();
+                Ok(Pattern::Literal(val))
+            }
             Token::String(v) => {
                 let val = v.clone();
                 self.advance();
@@ -3070,6 +3697,25 @@ impl Parser {
             Token::Identifier(name) => {
                 let text = name.clone();
                 self.advance();
+
+                // Padrão de construtor: Variante(...), Enum.Variante ou Enum.Variante(...)
+                let qualified = matches!(self.peek(), Token::Symbol('.'))
+                    && matches!(self.peek_next(), Token::Identifier(_));
+                if qualified {
+                    self.advance(); // consume '.'
+                    let variant = match self.peek() {
+                        Token::Identifier(variant) => variant.clone(),
+                        _ => unreachable!(),
+                    };
+                    self.advance();
+                    let fields = self.parse_constructor_fields()?;
+                    return Ok(Pattern::Constructor(Some(text), variant, fields));
+                }
+                if matches!(self.peek(), Token::Symbol('(')) {
+                    let fields = self.parse_constructor_fields()?;
+                    return Ok(Pattern::Constructor(None, text, fields));
+                }
+
                 if text == "_" {
                     Ok(Pattern::Wildcard)
                 } else {
@@ -3155,4 +3801,28 @@ impl Parser {
             _ => Err(DryadError::from_catalog_fmt(error_catalog::e2042(), &format!("Padrão inválido: {:?}", self.peek()), self.current_location())),
         }
     }
+
+    /// Campos de um padrão de construtor: `(p1, p2)`; sem parênteses, nenhum campo
+    fn parse_constructor_fields(&mut self) -> Result<Vec<Pattern>, DryadError> {
+        let mut fields = Vec::new();
+        if !matches!(self.peek(), Token::Symbol('(')) {
+            return Ok(fields);
+        }
+        self.advance(); // consume '('
+        if !matches!(self.peek(), Token::Symbol(')')) {
+            loop {
+                fields.push(self.parse_pattern()?);
+                if matches!(self.peek(), Token::Symbol(',')) {
+                    self.advance();
+                    continue;
+                }
+                break;
+            }
+        }
+        if !matches!(self.peek(), Token::Symbol(')')) {
+            return Err(DryadError::from_catalog(error_catalog::e2127(), self.current_location()));
+        }
+        self.advance(); // consume ')'
+        Ok(fields)
+    }
 }
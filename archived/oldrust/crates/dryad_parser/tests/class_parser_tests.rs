@@ -43,7 +43,7 @@ fn test_class_declaration_parsing() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, _, members, _, _) = &program.statements[0] {
             assert_eq!(name, "Pessoa");
             assert!(parent.is_none());
             assert_eq!(members.len(), 1);
@@ -90,7 +90,7 @@ fn test_class_with_inheritance() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(name, parent, _, _, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, _, _, _, _) = &program.statements[0] {
             assert_eq!(name, "Estudante");
             assert_eq!(parent.as_ref().unwrap(), "Pessoa");
         } else {
@@ -125,7 +125,7 @@ fn test_class_with_visibility_modifiers() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             assert_eq!(members.len(), 3);
 
             // Check visibility modifiers
@@ -171,7 +171,7 @@ fn test_class_with_static_methods() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             assert_eq!(members.len(), 2);
 
             for member in members {
@@ -205,7 +205,7 @@ fn test_class_with_properties() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             assert_eq!(members.len(), 3);
 
             // Check first property
@@ -257,7 +257,7 @@ fn test_empty_class() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(name, parent, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, _, members, _, _) = &program.statements[0] {
             assert_eq!(name, "EmptyClass");
             assert!(parent.is_none());
             assert!(members.is_empty());
@@ -295,7 +295,7 @@ fn test_class_with_multiple_members() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             assert_eq!(members.len(), 4);
 
             // First: property
@@ -463,7 +463,7 @@ fn test_class_with_async_methods() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             assert_eq!(members.len(), 1);
 
             if let ClassMember::Method { is_async, name, .. } = &members[0] {
@@ -496,7 +496,7 @@ fn test_super_keyword() {
     );
 
     if let Ok(program) = result {
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             if let ClassMember::Method { body, .. } = &members[0] {
                 if let Stmt::Block(statements, _) = body.as_ref() {
                     if let Stmt::Expression(expr, _) = &statements[0] {
@@ -524,3 +524,106 @@ fn test_super_keyword() {
         }
     }
 }
+
+#[test]
+fn test_operator_methods() {
+    let code = r#"
+        class Vec2 {
+            operator +(other) { return this; }
+            operator -() { return this; }
+            operator -(other) { return this; }
+            operator <=(other) { return true; }
+            operator [](i) { return i; }
+            operator []=(i, v) { }
+            operator: number = 1;
+        }
+    "#;
+
+    let program = parse_dryad_code(code).expect("Failed to parse operator methods");
+    let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] else {
+        panic!("Expected class declaration");
+    };
+
+    let names: Vec<(&str, usize)> = members
+        .iter()
+        .filter_map(|member| match member {
+            ClassMember::Method { name, params, .. } => Some((name.as_str(), params.len())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("operator+", 1),
+            ("operator neg", 0),
+            ("operator-", 1),
+            ("operator<=", 1),
+            ("operator[]", 1),
+            ("operator[]=", 2),
+        ]
+    );
+    // `operator` continua válido como nome de propriedade
+    assert!(matches!(&members[6], ClassMember::Property(_, _, name, _, _) if name == "operator"));
+}
+
+#[test]
+fn test_invalid_operator_methods() {
+    for code in [
+        "class A { operator &&(other) { } }",
+        "class A { operator +(a, b) { } }",
+        "class A { operator []=(i) { } }",
+    ] {
+        let result = parse_dryad_code(code);
+        assert!(
+            matches!(&result, Err(e) if e.contains("2128")),
+            "Expected E2128 for {}: {:?}",
+            code,
+            result
+        );
+    }
+}
+
+#[test]
+fn test_abstract_class_and_methods() {
+    let code = r#"
+        abstract class Shape {
+            abstract function area(): number;
+            function describe() { return "área " + this.area(); }
+        }
+        export abstract class Base { }
+    "#;
+    let program = parse_dryad_code(code).unwrap();
+
+    let Stmt::ClassDeclaration(name, _, _, _, members, is_abstract, _) = &program.statements[0] else {
+        panic!("Expected class declaration");
+    };
+    assert_eq!(name, "Shape");
+    assert!(*is_abstract);
+    assert!(matches!(
+        &members[0],
+        ClassMember::Method { name, is_abstract: true, return_type: Some(Type::Number), .. } if name == "area"
+    ));
+    assert!(matches!(&members[1], ClassMember::Method { is_abstract: false, .. }));
+    assert!(matches!(
+        &program.statements[1],
+        Stmt::Export(inner, _) if matches!(**inner, Stmt::ClassDeclaration(_, _, _, _, _, true, _))
+    ));
+}
+
+#[test]
+fn test_invalid_abstract_members() {
+    for code in [
+        "class A { abstract function f(); }",
+        "abstract class A { abstract function f() { return 1; } }",
+        "abstract class A { static abstract function f(); }",
+        "abstract function f() { }",
+    ] {
+        let result = parse_dryad_code(code);
+        assert!(
+            matches!(&result, Err(e) if e.contains("2133")),
+            "Expected E2133 for {}: {:?}",
+            code,
+            result
+        );
+    }
+}
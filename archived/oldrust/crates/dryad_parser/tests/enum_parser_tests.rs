@@ -0,0 +1,106 @@
+// crates/dryad_parser/tests/enum_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+fn match_patterns(program: &Program) -> Vec<Pattern> {
+    match &program.statements[0] {
+        Stmt::Expression(Expr::Match(_, arms, _), _) => {
+            arms.iter().map(|arm| arm.pattern.clone()).collect()
+        }
+        other => panic!("Esperado match, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_enum_declaration_with_fields() {
+    let program = parse_dryad_code("enum Shape { Circle(r), Rect(w, h: number), Empty }").unwrap();
+
+    match &program.statements[0] {
+        Stmt::EnumDeclaration(name, variants, _) => {
+            assert_eq!(name, "Shape");
+            assert_eq!(variants.len(), 3);
+            assert_eq!(variants[0].name, "Circle");
+            assert_eq!(variants[0].fields, vec![("r".to_string(), None)]);
+            assert_eq!(
+                variants[1].fields,
+                vec![
+                    ("w".to_string(), None),
+                    ("h".to_string(), Some(Type::Number))
+                ]
+            );
+            assert!(variants[2].fields.is_empty());
+        }
+        other => panic!("Esperado EnumDeclaration, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_enum_trailing_comma_and_export() {
+    let program = parse_dryad_code("export enum Cor { Vermelho, Verde, }").unwrap();
+    match &program.statements[0] {
+        Stmt::Export(inner, _) => {
+            assert!(matches!(inner.as_ref(), Stmt::EnumDeclaration(name, variants, _) if name == "Cor" && variants.len() == 2))
+        }
+        other => panic!("Esperado Export, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_constructor_patterns() {
+    let code = r#"
+        match s {
+            Shape.Circle(r) => r,
+            Rect(w, _) => w,
+            Shape.Empty => 0,
+            outro => 1
+        }
+    "#;
+    let patterns = match_patterns(&parse_dryad_code(code).unwrap());
+
+    assert_eq!(
+        patterns[0],
+        Pattern::Constructor(
+            Some("Shape".to_string()),
+            "Circle".to_string(),
+            vec![Pattern::Identifier("r".to_string())]
+        )
+    );
+    assert_eq!(
+        patterns[1],
+        Pattern::Constructor(
+            None,
+            "Rect".to_string(),
+            vec![Pattern::Identifier("w".to_string()), Pattern::Wildcard]
+        )
+    );
+    assert_eq!(
+        patterns[2],
+        Pattern::Constructor(Some("Shape".to_string()), "Empty".to_string(), vec![])
+    );
+    assert_eq!(patterns[3], Pattern::Identifier("outro".to_string()));
+}
+
+#[test]
+fn test_enum_declaration_errors() {
+    assert!(parse_dryad_code("enum { A }").is_err());
+    assert!(parse_dryad_code("enum Shape Circle").is_err());
+    assert!(parse_dryad_code("enum Shape { Circle(1) }").is_err());
+    assert!(parse_dryad_code("enum Shape { A B }").is_err());
+}
@@ -0,0 +1,100 @@
+// crates/dryad_parser/tests/generator_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+#[test]
+fn test_generator_function_declaration() {
+    let program = parse_dryad_code("function* contar(n) { yield n; let x = yield; }").unwrap();
+
+    match &program.statements[0] {
+        Stmt::FunctionDeclaration {
+            name,
+            is_generator,
+            body,
+            ..
+        } => {
+            assert_eq!(name, "contar");
+            assert!(*is_generator);
+            match body.as_ref() {
+                Stmt::Block(statements, _) => {
+                    assert!(matches!(
+                        &statements[0],
+                        Stmt::Expression(Expr::Yield(Some(_), _), _)
+                    ));
+                    assert!(matches!(
+                        &statements[1],
+                        Stmt::VarDeclaration(_, _, Some(Expr::Yield(None, _)), _)
+                    ));
+                }
+                other => panic!("Esperado bloco, encontrado {:?}", other),
+            }
+        }
+        other => panic!("Esperado function*, encontrado {:?}", other),
+    }
+
+    match &parse_dryad_code("function normal() { }").unwrap().statements[0] {
+        Stmt::FunctionDeclaration { is_generator, .. } => assert!(!*is_generator),
+        other => panic!("Esperado função, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_range_expressions() {
+    let program = parse_dryad_code("0..n + 1; 1..=10;").unwrap();
+
+    match &program.statements[0] {
+        Stmt::Expression(Expr::Range(start, end, inclusive, _), _) => {
+            assert!(matches!(start.as_ref(), Expr::Literal(Literal::Number(n), _) if *n == 0.0));
+            assert!(matches!(end.as_ref(), Expr::Binary(_, op, _, _) if op == "+"));
+            assert!(!*inclusive);
+        }
+        other => panic!("Esperado intervalo, encontrado {:?}", other),
+    }
+    assert!(matches!(
+        &program.statements[1],
+        Stmt::Expression(Expr::Range(_, _, true, _), _)
+    ));
+
+    // Números com ponto decimal continuam sendo números
+    let program = parse_dryad_code("1.5;").unwrap();
+    assert!(matches!(
+        &program.statements[0],
+        Stmt::Expression(Expr::Literal(Literal::Number(n), _), _) if *n == 1.5
+    ));
+}
+
+#[test]
+fn test_foreach_with_destructuring_pattern() {
+    let program = parse_dryad_code("for ((k, v) in obj.entries()) { print(k); }").unwrap();
+
+    match &program.statements[0] {
+        Stmt::ForEach(Pattern::Tuple(items), iterable, _, _) => {
+            assert_eq!(
+                items,
+                &vec![
+                    Pattern::Identifier("k".to_string()),
+                    Pattern::Identifier("v".to_string())
+                ]
+            );
+            assert!(matches!(iterable, Expr::MethodCall(_, method, _, _) if method == "entries"));
+        }
+        other => panic!("Esperado for-in, encontrado {:?}", other),
+    }
+}
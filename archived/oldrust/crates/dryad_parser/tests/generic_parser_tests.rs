@@ -0,0 +1,119 @@
+// crates/dryad_parser/tests/generic_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+fn generic(name: &str, args: Vec<Type>) -> Type {
+    Type::Generic(name.to_string(), args)
+}
+
+#[test]
+fn test_generic_function_declaration() {
+    let program = parse_dryad_code("function first<T>(xs: T[]): T { return xs[0]; }").unwrap();
+
+    match &program.statements[0] {
+        Stmt::FunctionDeclaration { name, type_params, params, return_type, .. } => {
+            assert_eq!(name, "first");
+            assert_eq!(type_params.len(), 1);
+            assert_eq!(type_params[0].name, "T");
+            assert_eq!(type_params[0].bound, None);
+            assert_eq!(params[0].1, Some(Type::Array(Box::new(Type::Class("T".to_string())))));
+            assert_eq!(return_type, &Some(Type::Class("T".to_string())));
+        }
+        other => panic!("Esperado função, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_type_param_bounds() {
+    let program =
+        parse_dryad_code("function max<T extends Comparable<T>, U>(a: T, b: U) { return a; }").unwrap();
+
+    let Stmt::FunctionDeclaration { type_params, .. } = &program.statements[0] else {
+        panic!("Esperado função");
+    };
+    assert_eq!(type_params.len(), 2);
+    assert_eq!(
+        type_params[0].bound,
+        Some(generic("Comparable", vec![Type::Class("T".to_string())]))
+    );
+    assert_eq!(type_params[1].name, "U");
+    assert_eq!(type_params[1].bound, None);
+}
+
+#[test]
+fn test_generic_class_and_interface() {
+    let program = parse_dryad_code(
+        r#"
+        interface Comparable<T> {
+            function compareTo(other: T): number;
+        }
+        class Money<C> extends Base<C> implements Comparable<Money<C>> {}
+    "#,
+    )
+    .unwrap();
+
+    match &program.statements[0] {
+        Stmt::InterfaceDeclaration(name, type_params, _, _) => {
+            assert_eq!(name, "Comparable");
+            assert_eq!(type_params[0].name, "T");
+        }
+        other => panic!("Esperado interface, encontrado {:?}", other),
+    }
+    match &program.statements[1] {
+        Stmt::ClassDeclaration(name, type_params, parent, interfaces, _, _, _) => {
+            assert_eq!(name, "Money");
+            assert_eq!(type_params[0].name, "C");
+            // Argumentos de supertipos são apagados
+            assert_eq!(parent.as_deref(), Some("Base"));
+            assert_eq!(interfaces, &vec!["Comparable".to_string()]);
+        }
+        other => panic!("Esperado classe, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_nested_type_arguments() {
+    let program = parse_dryad_code(
+        "let a: Box<Box<number>> = null; let b: Map<string, Box<Box<T>>>= null; let c: number[][] = [];",
+    )
+    .unwrap();
+
+    let annotation = |i: usize| match &program.statements[i] {
+        Stmt::VarDeclaration(_, t, _, _) => t.clone().unwrap(),
+        other => panic!("Esperado let, encontrado {:?}", other),
+    };
+    assert_eq!(annotation(0), generic("Box", vec![generic("Box", vec![Type::Number])]));
+    assert_eq!(
+        annotation(1),
+        generic(
+            "Map",
+            vec![Type::String, generic("Box", vec![generic("Box", vec![Type::Class("T".to_string())])])]
+        )
+    );
+    assert_eq!(annotation(2), Type::Array(Box::new(Type::Array(Box::new(Type::Number)))));
+    assert_eq!(annotation(0).to_string(), "Box<Box<number>>");
+}
+
+#[test]
+fn test_invalid_type_arguments() {
+    assert!(parse_dryad_code("let a: Box<number = null;").is_err());
+    assert!(parse_dryad_code("function f<>(x) {}").is_err());
+    assert!(parse_dryad_code("class Box<T extends> {}").is_err());
+}
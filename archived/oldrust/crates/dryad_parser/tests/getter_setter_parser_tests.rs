@@ -42,7 +42,7 @@ fn test_class_with_getter() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, _, members, _, _) = &program.statements[0] {
             assert_eq!(name, "Foo");
             assert!(parent.is_none());
             assert_eq!(members.len(), 1);
@@ -93,7 +93,7 @@ fn test_class_with_setter() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, _, members, _, _) = &program.statements[0] {
             assert_eq!(name, "Foo");
             assert!(parent.is_none());
             assert_eq!(members.len(), 1);
@@ -152,7 +152,7 @@ fn test_class_with_getter_and_setter() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, _, members, _, _) = &program.statements[0] {
             assert_eq!(name, "Person");
             assert!(parent.is_none());
             assert_eq!(members.len(), 2);
@@ -231,7 +231,7 @@ fn test_getter_setter_with_visibility_modifiers() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(_, _, _, members, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(_, _, _, _, members, _, _) = &program.statements[0] {
             assert_eq!(members.len(), 2);
 
             // Check getter visibility is public
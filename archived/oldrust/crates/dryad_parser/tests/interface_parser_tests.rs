@@ -41,7 +41,7 @@ fn test_basic_interface_declaration() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::InterfaceDeclaration(name, members, _) = &program.statements[0] {
+        if let Stmt::InterfaceDeclaration(name, _, members, _) = &program.statements[0] {
             assert_eq!(name, "Drawable");
             assert_eq!(members.len(), 1);
 
@@ -75,7 +75,7 @@ fn test_interface_with_multiple_methods() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::InterfaceDeclaration(name, members, _) = &program.statements[0] {
+        if let Stmt::InterfaceDeclaration(name, _, members, _) = &program.statements[0] {
             assert_eq!(name, "Shape");
             assert_eq!(members.len(), 2);
 
@@ -115,7 +115,7 @@ fn test_interface_with_typed_parameters() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::InterfaceDeclaration(name, members, _) = &program.statements[0] {
+        if let Stmt::InterfaceDeclaration(name, _, members, _) = &program.statements[0] {
             assert_eq!(name, "Comparable");
             assert_eq!(members.len(), 1);
 
@@ -149,7 +149,7 @@ fn test_class_with_implements_clause() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, interfaces, _, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, interfaces, _, _, _) = &program.statements[0] {
             assert_eq!(name, "Circle");
             assert!(parent.is_none());
             assert_eq!(interfaces.len(), 1);
@@ -178,7 +178,7 @@ fn test_class_with_extends_and_implements() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, interfaces, _, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, interfaces, _, _, _) = &program.statements[0] {
             assert_eq!(name, "Circle");
             assert_eq!(parent.as_ref().unwrap(), "Shape");
             assert_eq!(interfaces.len(), 1);
@@ -207,7 +207,7 @@ fn test_class_with_multiple_implements() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::ClassDeclaration(name, parent, interfaces, _, _) = &program.statements[0] {
+        if let Stmt::ClassDeclaration(name, _, parent, interfaces, _, _, _) = &program.statements[0] {
             assert_eq!(name, "MyClass");
             assert!(parent.is_none());
             assert_eq!(interfaces.len(), 2);
@@ -238,7 +238,7 @@ fn test_interface_with_return_type() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::InterfaceDeclaration(name, members, _) = &program.statements[0] {
+        if let Stmt::InterfaceDeclaration(name, _, members, _) = &program.statements[0] {
             assert_eq!(name, "Calculator");
             assert_eq!(members.len(), 1);
 
@@ -273,7 +273,7 @@ fn test_interface_with_multiple_params() {
     if let Ok(program) = result {
         assert_eq!(program.statements.len(), 1);
 
-        if let Stmt::InterfaceDeclaration(name, members, _) = &program.statements[0] {
+        if let Stmt::InterfaceDeclaration(name, _, members, _) = &program.statements[0] {
             assert_eq!(name, "Logger");
             assert_eq!(members.len(), 1);
 
@@ -287,3 +287,25 @@ fn test_interface_with_multiple_params() {
         }
     }
 }
+
+#[test]
+fn test_interface_default_method() {
+    let code = r#"
+        interface Named {
+            function name(): string;
+            function greet() {
+                return "Olá " + this.name();
+            }
+        }
+    "#;
+
+    let program = parse_dryad_code(code).unwrap();
+    let Stmt::InterfaceDeclaration(_, _, members, _) = &program.statements[0] else {
+        panic!("Expected interface declaration");
+    };
+    let InterfaceMember::Method(required) = &members[0];
+    let InterfaceMember::Method(default) = &members[1];
+    assert!(required.body.is_none());
+    assert_eq!(default.name, "greet");
+    assert!(matches!(default.body.as_deref(), Some(Stmt::Block(stmts, _)) if stmts.len() == 1));
+}
@@ -0,0 +1,67 @@
+// crates/dryad_parser/tests/numeric_literal_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+fn expression(input: &str) -> Expr {
+    match parse_dryad_code(input).unwrap().statements.remove(0) {
+        Stmt::Expression(expr, _) => expr,
+        other => panic!("Esperado expressão, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_suffixed_literals() {
+    assert!(matches!(expression("42i;"), Expr::Literal(Literal::Int(42), _)));
+    assert!(matches!(
+        expression("10n;"),
+        Expr::Literal(Literal::BigInt(ref digits), _) if digits == "10"
+    ));
+    assert!(matches!(
+        expression("0.10d;"),
+        Expr::Literal(Literal::Decimal(ref text), _) if text == "0.10"
+    ));
+}
+
+#[test]
+fn test_suffixed_literals_in_binary_expression() {
+    match expression("1.5d + 2i;") {
+        Expr::Binary(left, op, right, _) => {
+            assert_eq!(op, "+");
+            assert!(matches!(*left, Expr::Literal(Literal::Decimal(_), _)));
+            assert!(matches!(*right, Expr::Literal(Literal::Int(2), _)));
+        }
+        other => panic!("Esperado expressão binária, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_numeric_type_annotations() {
+    let program = parse_dryad_code("let a: int = 1i; let b: bigint = 1n; let c: decimal = 1d;").unwrap();
+    let types: Vec<Type> = program
+        .statements
+        .iter()
+        .map(|stmt| match stmt {
+            Stmt::VarDeclaration(_, Some(t), _, _) => t.clone(),
+            other => panic!("Esperado declaração tipada, encontrado {:?}", other),
+        })
+        .collect();
+    assert_eq!(types, vec![Type::Int, Type::BigInt, Type::Decimal]);
+    assert_eq!(Type::BigInt.to_string(), "bigint");
+}
@@ -0,0 +1,90 @@
+// crates/dryad_parser/tests/optional_chaining_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+fn expression(input: &str) -> Expr {
+    match parse_dryad_code(input).unwrap().statements.remove(0) {
+        Stmt::Expression(expr, _) => expr,
+        other => panic!("Esperado expressão, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_optional_chain_wraps_whole_chain() {
+    // a?.b.c(1)?.[0] -> OptionalChain(OptionalIndex(MethodCall(PropertyAccess(OptionalPropertyAccess(a, b), c)...)))
+    match expression("a?.b.c(1)?.[0];") {
+        Expr::OptionalChain(inner, _) => match *inner {
+            Expr::OptionalIndex(object, _, _) => match *object {
+                Expr::MethodCall(object, method, args, _) => {
+                    assert_eq!(method, "c");
+                    assert_eq!(args.len(), 1);
+                    assert!(matches!(
+                        *object,
+                        Expr::OptionalPropertyAccess(ref base, ref name, _)
+                            if name == "b" && matches!(base.as_ref(), Expr::Variable(v, _) if v == "a")
+                    ));
+                }
+                other => panic!("Esperado chamada de método, encontrado {:?}", other),
+            },
+            other => panic!("Esperado índice opcional, encontrado {:?}", other),
+        },
+        other => panic!("Esperado cadeia opcional, encontrado {:?}", other),
+    }
+
+    assert!(matches!(
+        expression("user?.save();"),
+        Expr::OptionalChain(inner, _)
+            if matches!(inner.as_ref(), Expr::OptionalMethodCall(_, m, args, _) if m == "save" && args.is_empty())
+    ));
+
+    // Cadeias sem `?.` continuam como antes
+    assert!(matches!(expression("a.b;"), Expr::PropertyAccess(..)));
+}
+
+#[test]
+fn test_nullish_coalescing_precedence() {
+    // `??` liga mais fraco que `||` e associa à esquerda
+    match expression("a ?? b || c ?? d;") {
+        Expr::NullishCoalescing(left, right, _) => {
+            assert!(matches!(left.as_ref(), Expr::NullishCoalescing(..)));
+            assert!(matches!(right.as_ref(), Expr::Variable(name, _) if name == "d"));
+            match left.as_ref() {
+                Expr::NullishCoalescing(_, middle, _) => {
+                    assert!(matches!(middle.as_ref(), Expr::Binary(_, op, _, _) if op == "||"));
+                }
+                _ => unreachable!(),
+            }
+        }
+        other => panic!("Esperado '??', encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_nullish_assignment_desugars() {
+    let program = parse_dryad_code("x ??= 10;").unwrap();
+    match &program.statements[0] {
+        Stmt::Assignment(Pattern::Identifier(name), Expr::NullishCoalescing(left, right, _), _) => {
+            assert_eq!(name, "x");
+            assert!(matches!(left.as_ref(), Expr::Variable(v, _) if v == "x"));
+            assert!(matches!(right.as_ref(), Expr::Literal(Literal::Number(n), _) if *n == 10.0));
+        }
+        other => panic!("Esperado atribuição '??=', encontrado {:?}", other),
+    }
+}
@@ -319,4 +319,20 @@ mod parser_tests {
             _ => panic!("Esperado expressão de adição aninhada"),
         }
     }
+
+    #[test]
+    fn test_keyword_as_property_name() {
+        // `set`, `get` e outras palavras-chave são aceitas depois de '.' e '?.'
+        match parse_expression("cache.set(1, 2)").unwrap() {
+            Expr::MethodCall(_, method, args, _) => {
+                assert_eq!(method, "set");
+                assert_eq!(args.len(), 2);
+            }
+            _ => panic!("Esperado chamada de método"),
+        }
+        assert!(matches!(
+            parse_expression("cache?.get").unwrap(),
+            Expr::OptionalChain(..)
+        ));
+    }
 }
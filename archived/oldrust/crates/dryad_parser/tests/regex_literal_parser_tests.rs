@@ -0,0 +1,67 @@
+// crates/dryad_parser/tests/regex_literal_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+fn expression(input: &str) -> Expr {
+    match parse_dryad_code(input).unwrap().statements.remove(0) {
+        Stmt::Expression(expr, _) => expr,
+        other => panic!("Esperado expressão, encontrado {:?}", other),
+    }
+}
+
+fn is_regex(expr: &Expr, pattern: &str, flags: &str) -> bool {
+    matches!(expr, Expr::Literal(Literal::Regex(p, f), _) if p == pattern && f == flags)
+}
+
+#[test]
+fn test_regex_literal_as_method_receiver() {
+    match expression(r#"/(\w+)@/i.test("a@b");"#) {
+        Expr::MethodCall(receiver, method, args, _) => {
+            assert!(is_regex(&receiver, r"(\w+)@", "i"));
+            assert_eq!(method, "test");
+            assert_eq!(args.len(), 1);
+        }
+        other => panic!("Esperado chamada de método, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_division_between_regex_free_operands() {
+    match expression("total / count / 2;") {
+        Expr::Binary(left, op, _, _) => {
+            assert_eq!(op, "/");
+            assert!(matches!(*left, Expr::Binary(_, ref inner, _, _) if inner == "/"));
+        }
+        other => panic!("Esperado divisão, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_regex_pattern_in_match_arm() {
+    match expression(r#"match s { /^\d+$/ => "número", _ => "texto" };"#) {
+        Expr::Match(_, arms, _) => {
+            assert!(matches!(
+                &arms[0].pattern,
+                Pattern::Literal(Literal::Regex(p, f)) if p == r"^\d+$" && f.is_empty()
+            ));
+        }
+        other => panic!("Esperado match, encontrado {:?}", other),
+    }
+}
@@ -0,0 +1,65 @@
+use dryad_lexer::{Lexer, Token, TokenWithLocation};
+use dryad_parser::{ast::Stmt, Parser};
+
+fn parse_tokens(source: &str) -> Vec<TokenWithLocation> {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        match lexer.next_token() {
+            Ok(token) => {
+                if matches!(token.token, Token::Eof) {
+                    tokens.push(token);
+                    break;
+                }
+                tokens.push(token);
+            }
+            Err(_) => break,
+        }
+    }
+    tokens
+}
+
+#[test]
+fn test_parse_test_block() {
+    let tokens = parse_tokens(r#"test "soma" { let x = 1 + 1; }"#);
+    let program = Parser::new(tokens).parse().unwrap();
+
+    assert_eq!(program.statements.len(), 1);
+    match &program.statements[0] {
+        Stmt::Test(name, body, skip, _) => {
+            assert_eq!(name, "soma");
+            assert!(!skip);
+            assert!(matches!(**body, Stmt::Block(ref stmts, _) if stmts.len() == 1));
+        }
+        other => panic!("Esperado Stmt::Test, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_skipped_test_block() {
+    let tokens = parse_tokens(r#"test.skip "pendente" { }"#);
+    let program = Parser::new(tokens).parse().unwrap();
+
+    assert!(matches!(
+        &program.statements[0],
+        Stmt::Test(name, _, true, _) if name == "pendente"
+    ));
+}
+
+#[test]
+fn test_test_is_still_a_valid_identifier() {
+    let tokens = parse_tokens("let test = 1; test = test + 1; test.skip;");
+    let program = Parser::new(tokens).parse().unwrap();
+
+    assert_eq!(program.statements.len(), 3);
+    assert!(!program
+        .statements
+        .iter()
+        .any(|stmt| matches!(stmt, Stmt::Test(..))));
+}
+
+#[test]
+fn test_test_block_requires_body() {
+    let tokens = parse_tokens(r#"test.skip "sem corpo";"#);
+    assert!(Parser::new(tokens).parse().is_err());
+}
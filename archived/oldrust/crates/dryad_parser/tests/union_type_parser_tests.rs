@@ -0,0 +1,126 @@
+// crates/dryad_parser/tests/union_type_parser_tests.rs
+
+use dryad_lexer::{Lexer, Token};
+use dryad_parser::{ast::*, Parser};
+
+fn parse_dryad_code(input: &str) -> Result<Program, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok(tok) if tok.token == Token::Eof => break,
+            Ok(token) => tokens.push(token),
+            Err(e) => return Err(format!("Lexer error: {:?}", e)),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("Parser error: {:?}", e))
+}
+
+fn annotation(program: &Program, i: usize) -> Type {
+    match &program.statements[i] {
+        Stmt::VarDeclaration(_, t, _, _) => t.clone().unwrap(),
+        other => panic!("Esperado let, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_union_and_optional_types() {
+    let program = parse_dryad_code(
+        "let a: number | string = 1; let b: string? = null; let c: (number | null)[] = [];",
+    )
+    .unwrap();
+
+    assert_eq!(annotation(&program, 0), Type::Union(vec![Type::Number, Type::String]));
+    assert_eq!(annotation(&program, 1), Type::Union(vec![Type::String, Type::Null]));
+    assert_eq!(
+        annotation(&program, 2),
+        Type::Array(Box::new(Type::Union(vec![Type::Number, Type::Null])))
+    );
+    assert_eq!(annotation(&program, 0).to_string(), "number | string");
+}
+
+#[test]
+fn test_literal_types() {
+    let program = parse_dryad_code(r#"let dir: "left" | "right" = "left"; let one: 1 | -1 = 1;"#).unwrap();
+
+    assert_eq!(
+        annotation(&program, 0),
+        Type::Union(vec![
+            Type::Literal(Literal::String("left".to_string())),
+            Type::Literal(Literal::String("right".to_string())),
+        ])
+    );
+    assert_eq!(annotation(&program, 0).to_string(), r#""left" | "right""#);
+    assert_eq!(annotation(&program, 1).to_string(), "1 | -1");
+}
+
+#[test]
+fn test_object_shape_types() {
+    let program = parse_dryad_code("let p: { name: string, age?: number } = null;").unwrap();
+
+    assert_eq!(
+        annotation(&program, 0),
+        Type::Object(vec![
+            ObjectTypeField { name: "name".to_string(), field_type: Type::String, optional: false },
+            ObjectTypeField { name: "age".to_string(), field_type: Type::Number, optional: true },
+        ])
+    );
+    assert_eq!(annotation(&program, 0).to_string(), "{ name: string, age?: number }");
+    assert!(parse_dryad_code("let p: { name string } = null;").is_err());
+}
+
+#[test]
+fn test_type_alias_declaration() {
+    let program = parse_dryad_code(
+        r#"
+        type Direction = "left" | "right";
+        type Pair<T> = { first: T, second: T };
+        export type Id = number | string;
+    "#,
+    )
+    .unwrap();
+
+    match &program.statements[0] {
+        Stmt::TypeAlias(name, type_params, aliased, _) => {
+            assert_eq!(name, "Direction");
+            assert!(type_params.is_empty());
+            assert!(matches!(aliased, Type::Union(members) if members.len() == 2));
+        }
+        other => panic!("Esperado alias, encontrado {:?}", other),
+    }
+    match &program.statements[1] {
+        Stmt::TypeAlias(name, type_params, _, _) => {
+            assert_eq!(name, "Pair");
+            assert_eq!(type_params[0].name, "T");
+        }
+        other => panic!("Esperado alias, encontrado {:?}", other),
+    }
+    assert!(parse_dryad_code("type Id number;").is_err());
+}
+
+#[test]
+fn test_typeof_and_instanceof_expressions() {
+    let program = parse_dryad_code(
+        r#"
+        let a = typeof x == "string";
+        let b = p instanceof Point;
+        let typeof = 1;
+    "#,
+    )
+    .unwrap();
+
+    match &program.statements[0] {
+        Stmt::VarDeclaration(_, _, Some(Expr::Binary(left, op, _, _)), _) => {
+            assert_eq!(op, "==");
+            assert!(matches!(left.as_ref(), Expr::Unary(op, _, _) if op == "typeof"));
+        }
+        other => panic!("Esperado comparação, encontrado {:?}", other),
+    }
+    match &program.statements[1] {
+        Stmt::VarDeclaration(_, _, Some(Expr::Binary(_, op, _, _)), _) => assert_eq!(op, "instanceof"),
+        other => panic!("Esperado instanceof, encontrado {:?}", other),
+    }
+}
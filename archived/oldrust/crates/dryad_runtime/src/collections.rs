@@ -0,0 +1,124 @@
+//! Coleções `Map` e `Set` do runtime.
+//!
+//! Ambas guardam a ordem de inserção e aceitam chaves de qualquer valor
+//! hashável: primitivos, tuplas e variantes de enum são comparados pelo
+//! conteúdo; arrays, objetos, instâncias e demais valores do heap, pela
+//! identidade (o `HeapId`).
+
+use crate::heap::{Heap, HeapId, ManagedObject};
+use crate::numeric::Decimal;
+use crate::value::Value;
+use indexmap::IndexMap;
+use num_bigint_dig::BigInt;
+
+/// Entradas de um `Map`: chave normalizada -> (chave original, valor)
+pub type MapEntries = IndexMap<MapKey, (Value, Value)>;
+
+/// Elementos de um `Set`: chave normalizada -> valor original
+pub type SetEntries = IndexMap<MapKey, Value>;
+
+/// Forma hashável de um valor usado como chave
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Null,
+    Bool(bool),
+    /// Qualquer número inteiro: `1`, `1i`, `1n` e `1.0d` são a mesma chave
+    Integer(BigInt),
+    /// Número fracionário, na forma decimal mais curta (`0.1` e `0.10d` coincidem)
+    Fraction(String),
+    /// NaN e infinitos, pelos bits do f64
+    Float(u64),
+    String(String),
+    Tuple(Vec<MapKey>),
+    Variant(String, String, Vec<MapKey>),
+    /// Datas, durações e fusos pelo valor; `DateTime` pelo instante, em qualquer fuso
+    Temporal(&'static str, String),
+    /// Valores do heap comparados por identidade
+    Identity(HeapId),
+}
+
+impl MapKey {
+    /// Calcula a chave de um valor; falha para valores que não podem ser chave
+    /// (funções, threads, promises...)
+    pub fn of(value: &Value, heap: &Heap) -> Result<MapKey, String> {
+        match value {
+            Value::Null => Ok(MapKey::Null),
+            Value::Bool(b) => Ok(MapKey::Bool(*b)),
+            Value::String(s) => Ok(MapKey::String(s.clone())),
+            Value::Number(n) if !n.is_finite() => {
+                let n = if n.is_nan() { f64::NAN } else { *n };
+                Ok(MapKey::Float(n.to_bits()))
+            }
+            Value::Number(n) => Ok(decimal_key(
+                Decimal::from_f64(*n).ok_or_else(|| format!("Número inválido como chave: {}", n))?,
+            )),
+            Value::Int(i) => Ok(MapKey::Integer(BigInt::from(*i))),
+            Value::BigInt(b) => Ok(MapKey::Integer(b.clone())),
+            Value::Decimal(d) => Ok(decimal_key(d.clone())),
+            Value::Tuple(id) => match heap.get(*id) {
+                Some(ManagedObject::Tuple(items)) => items
+                    .iter()
+                    .map(|item| MapKey::of(item, heap))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(MapKey::Tuple),
+                _ => Err("Referência de tupla inválida".to_string()),
+            },
+            Value::Variant(id) => match heap.get(*id) {
+                Some(ManagedObject::Variant {
+                    enum_name,
+                    variant,
+                    fields,
+                }) => fields
+                    .iter()
+                    .map(|(_, field)| MapKey::of(field, heap))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|fields| MapKey::Variant(enum_name.clone(), variant.clone(), fields)),
+                _ => Err("Referência de variante inválida".to_string()),
+            },
+            Value::DateTime(dt) => Ok(MapKey::Temporal(
+                "datetime",
+                dt.instant.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
+            )),
+            Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) => {
+                Ok(MapKey::Temporal(value.type_name(), value.to_string()))
+            }
+            Value::Array(id)
+            | Value::Object(id)
+            | Value::Instance(id)
+            | Value::Class(id)
+            | Value::Lambda(id)
+            | Value::Enum(id)
+            | Value::Generator(id)
+            | Value::Map(id)
+            | Value::Set(id) => Ok(MapKey::Identity(*id)),
+            other => Err(format!(
+                "'{}' não pode ser usado como chave de Map/Set",
+                other.to_string()
+            )),
+        }
+    }
+}
+
+fn decimal_key(decimal: Decimal) -> MapKey {
+    if decimal.is_integer() {
+        return MapKey::Integer(decimal.trunc());
+    }
+    let text = decimal.to_string();
+    MapKey::Fraction(text.trim_end_matches('0').to_string())
+}
+
+/// Valores referenciados pelas entradas (para o GC)
+pub fn map_values(entries: &MapEntries) -> impl Iterator<Item = &Value> {
+    entries.values().flat_map(|(key, value)| [key, value])
+}
+
+/// Texto de uma chave de `Map` ao converter para objeto JSON
+pub fn json_key(key: &Value) -> Option<String> {
+    match key {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(_) | Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) | Value::Bool(_) => {
+            Some(key.to_string())
+        }
+        _ => None,
+    }
+}
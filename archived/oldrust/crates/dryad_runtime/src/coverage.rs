@@ -0,0 +1,241 @@
+// crates/dryad_runtime/src/coverage.rs
+//! Cobertura de linhas e branches dos scripts Dryad.
+//!
+//! O interpretador registra cada statement executado (no mesmo ponto que
+//! `check_debug_hooks`) e cada decisão de `if`/`while`/`for`/`do-while`. As linhas
+//! executáveis são conhecidas a partir da AST (`register_program`), para que linhas
+//! nunca executadas apareçam com zero execuções no relatório.
+
+use dryad_parser::ast::{ClassMember, Program, Stmt};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileCoverage {
+    /// Linha -> número de execuções (0 = executável mas nunca executada)
+    pub lines: BTreeMap<usize, u64>,
+    /// Linha do ponto de decisão -> [vezes verdadeiro, vezes falso]
+    pub branches: BTreeMap<usize, [u64; 2]>,
+}
+
+impl FileCoverage {
+    pub fn lines_found(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|&&hits| hits > 0).count()
+    }
+
+    /// Cada ponto de decisão conta como dois branches (verdadeiro e falso)
+    pub fn branches_found(&self) -> usize {
+        self.branches.len() * 2
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.branches
+            .values()
+            .map(|counts| counts.iter().filter(|&&c| c > 0).count())
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Coverage {
+    pub files: BTreeMap<PathBuf, FileCoverage>,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra as linhas executáveis e os pontos de decisão de um programa.
+    /// Statements com `location.file` próprio são atribuídos a esse arquivo.
+    pub fn register_program(&mut self, file: &Path, program: &Program) {
+        for stmt in &program.statements {
+            self.register_stmt(file, stmt);
+        }
+    }
+
+    fn register_stmt(&mut self, file: &Path, stmt: &Stmt) {
+        // Blocos não são contados: a linha do `{` não é uma instrução
+        if let Stmt::Block(statements, _) = stmt {
+            for s in statements {
+                self.register_stmt(file, s);
+            }
+            return;
+        }
+
+        let mut target = file.to_path_buf();
+        if let Some(location) = stmt_location(stmt) {
+            if let Some(own_file) = &location.file {
+                target = own_file.clone();
+            }
+            if location.line > 0 {
+                let entry = self.files.entry(target.clone()).or_default();
+                entry.lines.entry(location.line).or_insert(0);
+                if is_branch_point(stmt) {
+                    entry.branches.entry(location.line).or_insert([0, 0]);
+                }
+            }
+        }
+
+        match stmt {
+            Stmt::If(_, then_stmt, _) => self.register_stmt(&target, then_stmt),
+            Stmt::IfElse(_, then_stmt, else_stmt, _) => {
+                self.register_stmt(&target, then_stmt);
+                self.register_stmt(&target, else_stmt);
+            }
+            Stmt::While(_, body, _) | Stmt::DoWhile(body, _, _) | Stmt::ForEach(_, _, body, _) => {
+                self.register_stmt(&target, body)
+            }
+            Stmt::For(_, _, _, body, _) => self.register_stmt(&target, body),
+            Stmt::Try(try_block, catch_clause, finally_block, _) => {
+                self.register_stmt(&target, try_block);
+                if let Some((_, catch_block)) = catch_clause {
+                    self.register_stmt(&target, catch_block);
+                }
+                if let Some(finally_block) = finally_block {
+                    self.register_stmt(&target, finally_block);
+                }
+            }
+            Stmt::FunctionDeclaration { body, .. }
+            | Stmt::ThreadFunctionDeclaration { body, .. }
+            | Stmt::Test(_, body, _, _) => self.register_stmt(&target, body),
+            Stmt::ClassDeclaration(_, _, _, _, members, _, _) => {
+                for member in members {
+                    match member {
+                        ClassMember::Method { body, .. }
+                        | ClassMember::Getter { body, .. }
+                        | ClassMember::Setter { body, .. } => self.register_stmt(&target, body),
+                        ClassMember::Property(..) => {}
+                    }
+                }
+            }
+            Stmt::Export(inner, _) => self.register_stmt(&target, inner),
+            Stmt::Namespace(_, statements, _) => {
+                for s in statements {
+                    self.register_stmt(&target, s);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Registra linhas executáveis conhecidas por outra fonte (ex.: tabela de linhas do bytecode)
+    pub fn register_lines(&mut self, file: &Path, lines: impl IntoIterator<Item = usize>) {
+        let entry = self.files.entry(file.to_path_buf()).or_default();
+        for line in lines {
+            entry.lines.entry(line).or_insert(0);
+        }
+    }
+
+    pub fn hit_line(&mut self, file: &Path, line: usize) {
+        if line == 0 {
+            return;
+        }
+        let entry = self.files.entry(file.to_path_buf()).or_default();
+        *entry.lines.entry(line).or_insert(0) += 1;
+    }
+
+    pub fn hit_branch(&mut self, file: &Path, line: usize, condition: bool) {
+        if line == 0 {
+            return;
+        }
+        let entry = self.files.entry(file.to_path_buf()).or_default();
+        let counts = entry.branches.entry(line).or_insert([0, 0]);
+        counts[if condition { 0 } else { 1 }] += 1;
+    }
+
+    /// Soma contadores de uma coleta para um arquivo (ex.: cobertura da VM)
+    pub fn merge_file(&mut self, file: &Path, other: &FileCoverage) {
+        let entry = self.files.entry(file.to_path_buf()).or_default();
+        for (line, hits) in &other.lines {
+            *entry.lines.entry(*line).or_insert(0) += hits;
+        }
+        for (line, counts) in &other.branches {
+            let target = entry.branches.entry(*line).or_insert([0, 0]);
+            target[0] += counts[0];
+            target[1] += counts[1];
+        }
+    }
+
+    pub fn merge(&mut self, other: &Coverage) {
+        for (file, coverage) in &other.files {
+            self.merge_file(file, coverage);
+        }
+    }
+
+    /// Relatório no formato lcov (`lcov.info`)
+    pub fn to_lcov(&self) -> String {
+        let mut out = String::new();
+        for (file, coverage) in &self.files {
+            out.push_str("TN:\n");
+            out.push_str(&format!("SF:{}\n", file.display()));
+            for (block, (line, counts)) in coverage.branches.iter().enumerate() {
+                let never_evaluated = counts[0] == 0 && counts[1] == 0;
+                for (branch, count) in counts.iter().enumerate() {
+                    let taken = if never_evaluated {
+                        "-".to_string()
+                    } else {
+                        count.to_string()
+                    };
+                    out.push_str(&format!("BRDA:{},{},{},{}\n", line, block, branch, taken));
+                }
+            }
+            out.push_str(&format!("BRF:{}\n", coverage.branches_found()));
+            out.push_str(&format!("BRH:{}\n", coverage.branches_hit()));
+            for (line, hits) in &coverage.lines {
+                out.push_str(&format!("DA:{},{}\n", line, hits));
+            }
+            out.push_str(&format!("LF:{}\n", coverage.lines_found()));
+            out.push_str(&format!("LH:{}\n", coverage.lines_hit()));
+            out.push_str("end_of_record\n");
+        }
+        out
+    }
+}
+
+fn is_branch_point(stmt: &Stmt) -> bool {
+    matches!(
+        stmt,
+        Stmt::If(..) | Stmt::IfElse(..) | Stmt::While(..) | Stmt::DoWhile(..)
+    ) || matches!(stmt, Stmt::For(_, Some(_), _, _, _))
+}
+
+pub(crate) fn stmt_location(stmt: &Stmt) -> Option<&dryad_errors::SourceLocation> {
+    let location = match stmt {
+        Stmt::Expression(_, loc)
+        | Stmt::VarDeclaration(_, _, _, loc)
+        | Stmt::ConstDeclaration(_, _, _, loc)
+        | Stmt::Assignment(_, _, loc)
+        | Stmt::PropertyAssignment(_, _, _, loc)
+        | Stmt::IndexAssignment(_, _, _, loc)
+        | Stmt::If(_, _, loc)
+        | Stmt::IfElse(_, _, _, loc)
+        | Stmt::While(_, _, loc)
+        | Stmt::DoWhile(_, _, loc)
+        | Stmt::Break(loc)
+        | Stmt::Continue(loc)
+        | Stmt::For(_, _, _, _, loc)
+        | Stmt::ForEach(_, _, _, loc)
+        | Stmt::Try(_, _, _, loc)
+        | Stmt::Throw(_, loc)
+        | Stmt::Return(_, loc)
+        | Stmt::NativeDirective(_, loc)
+        | Stmt::ClassDeclaration(_, _, _, _, _, _, loc)
+        | Stmt::Export(_, loc)
+        | Stmt::Use(_, loc)
+        | Stmt::Import(_, _, loc)
+        | Stmt::InterfaceDeclaration(_, _, _, loc)
+        | Stmt::EnumDeclaration(_, _, loc)
+        | Stmt::TypeAlias(_, _, _, loc)
+        | Stmt::Namespace(_, _, loc) => loc,
+        Stmt::FunctionDeclaration { location, .. }
+        | Stmt::ThreadFunctionDeclaration { location, .. } => location,
+        // Blocos e testes só agrupam statements; quem conta são os statements internos
+        Stmt::Block(..) | Stmt::Test(..) => return None,
+    };
+    Some(location)
+}
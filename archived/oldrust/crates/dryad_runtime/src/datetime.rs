@@ -0,0 +1,782 @@
+// crates/dryad_runtime/src/datetime.rs
+//! Datas e horas de primeira classe do `#<time>`: `DateTime` (instante + fuso),
+//! `Date` (dia do calendário), `Duration` (intervalo exato) e `TimeZone`.
+//!
+//! Um `DateTime` guarda o instante em UTC e o fuso em que é exibido; campos
+//! como `hour` e `day` são calculados nesse fuso, consultando a base IANA
+//! embutida (chrono-tz) para horário de verão. `+`/`-` com uma `Duration`
+//! andam tempo exato; `add_days`/`add_months` andam no calendário local.
+
+use crate::heap::{Heap, ManagedObject};
+use crate::value::Value;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{
+    DateTime as ChronoDateTime, Datelike, FixedOffset, Local, Months, NaiveDate, NaiveDateTime,
+    NaiveTime, Offset, SecondsFormat, TimeDelta, TimeZone as _, Timelike, Utc,
+};
+use chrono_tz::Tz;
+use std::cmp::Ordering;
+use std::fmt::Write;
+
+/// Fuso de um `DateTime`/`TimeZone`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    Utc,
+    /// Fuso do sistema, com as regras de horário de verão dele
+    Local,
+    /// Deslocamento fixo, ex.: `-03:00`
+    Fixed(FixedOffset),
+    /// Fuso da base IANA, ex.: `America/Sao_Paulo`
+    Iana(Tz),
+}
+
+impl Zone {
+    /// "UTC", "local", um deslocamento ("-03:00", "+0530") ou um nome IANA
+    pub fn parse(name: &str) -> Result<Zone, String> {
+        let name = name.trim();
+        match name {
+            "UTC" | "utc" | "Z" => return Ok(Zone::Utc),
+            "local" | "Local" => return Ok(Zone::Local),
+            _ => {}
+        }
+        if let Some(offset) = parse_offset(name) {
+            return Ok(Zone::Fixed(offset));
+        }
+        name.parse::<Tz>()
+            .map(Zone::Iana)
+            .map_err(|_| format!("Fuso horário desconhecido: '{}' (use um nome IANA como \"Europe/Lisbon\")", name))
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Zone::Utc => "UTC".to_string(),
+            Zone::Local => "local".to_string(),
+            Zone::Fixed(offset) => offset.to_string(),
+            Zone::Iana(tz) => tz.name().to_string(),
+        }
+    }
+
+    pub fn offset_at(&self, instant: &ChronoDateTime<Utc>) -> FixedOffset {
+        match self {
+            Zone::Utc => Utc.fix(),
+            Zone::Local => instant.with_timezone(&Local).offset().fix(),
+            Zone::Fixed(offset) => *offset,
+            Zone::Iana(tz) => instant.with_timezone(tz).offset().fix(),
+        }
+    }
+
+    /// Instante de uma hora local. Na hora repetida ao fim do horário de verão
+    /// vale a primeira ocorrência; a hora pulada no início não existe.
+    fn instant_of(&self, local: NaiveDateTime) -> Result<ChronoDateTime<Utc>, String> {
+        let instant = match self {
+            Zone::Utc => Some(local.and_utc()),
+            Zone::Local => Local.from_local_datetime(&local).earliest().map(|dt| dt.with_timezone(&Utc)),
+            Zone::Fixed(offset) => offset.from_local_datetime(&local).single().map(|dt| dt.with_timezone(&Utc)),
+            Zone::Iana(tz) => tz.from_local_datetime(&local).earliest().map(|dt| dt.with_timezone(&Utc)),
+        };
+        instant.ok_or_else(|| {
+            format!(
+                "{} não existe no fuso {} (mudança de horário de verão)",
+                local.format("%Y-%m-%dT%H:%M:%S"),
+                self.name()
+            )
+        })
+    }
+}
+
+/// `+03:00`, `-0530` ou `+03`
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+    let sign = match text.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let rest = &text[1..];
+    let (hours, minutes) = match (rest.len(), rest.find(':')) {
+        (2, None) => (rest, "00"),
+        (4, None) => rest.split_at(2),
+        (5, Some(2)) => (&rest[..2], &rest[3..]),
+        _ => return None,
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Instante com o fuso em que é exibido
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTime {
+    pub instant: ChronoDateTime<Utc>,
+    pub zone: Zone,
+}
+
+impl DateTime {
+    pub fn new(instant: ChronoDateTime<Utc>, zone: Zone) -> Self {
+        DateTime { instant, zone }
+    }
+
+    pub fn now(zone: Zone) -> Self {
+        DateTime::new(Utc::now(), zone)
+    }
+
+    pub fn from_local(local: NaiveDateTime, zone: Zone) -> Result<Self, String> {
+        Ok(DateTime::new(zone.instant_of(local)?, zone))
+    }
+
+    /// Milissegundos desde a época Unix
+    pub fn from_timestamp_ms(ms: f64, zone: Zone) -> Result<Self, String> {
+        let delta = delta_from_secs(ms / 1000.0)?;
+        ChronoDateTime::UNIX_EPOCH
+            .checked_add_signed(delta)
+            .map(|instant| DateTime::new(instant, zone))
+            .ok_or_else(|| format!("Timestamp fora do intervalo suportado: {}", ms))
+    }
+
+    pub fn offset(&self) -> FixedOffset {
+        self.zone.offset_at(&self.instant)
+    }
+
+    pub fn local(&self) -> NaiveDateTime {
+        self.instant.with_timezone(&self.offset()).naive_local()
+    }
+
+    /// RFC 3339 com a precisão necessária: `2024-03-10T09:30:00-03:00`
+    pub fn to_rfc3339(&self) -> String {
+        self.instant
+            .with_timezone(&self.offset())
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    }
+
+    /// Formato strftime (`%Y-%m-%d %H:%M`); `%Z` é a abreviação do fuso
+    pub fn format(&self, pattern: &str) -> Result<String, String> {
+        let items = strftime_items(pattern)?;
+        let mut out = String::new();
+        let written = match self.zone {
+            Zone::Iana(tz) => write!(out, "{}", self.instant.with_timezone(&tz).format_with_items(items.iter())),
+            _ => write!(out, "{}", self.instant.with_timezone(&self.offset()).format_with_items(items.iter())),
+        };
+        written.map(|_| out).map_err(|_| format!("Formato '{}' não pode ser aplicado a um DateTime", pattern))
+    }
+
+    /// Troca a hora local mantendo o fuso (usado pelo calendário)
+    fn with_local(&self, local: NaiveDateTime) -> Result<Self, String> {
+        DateTime::from_local(local, self.zone)
+    }
+
+    fn add(&self, delta: TimeDelta) -> Result<Self, String> {
+        self.instant
+            .checked_add_signed(delta)
+            .map(|instant| DateTime::new(instant, self.zone))
+            .ok_or_else(|| "DateTime fora do intervalo suportado".to_string())
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_rfc3339())
+    }
+}
+
+fn strftime_items(pattern: &str) -> Result<Vec<Item<'_>>, String> {
+    let items: Vec<Item> = StrftimeItems::new(pattern).collect();
+    if items.contains(&Item::Error) {
+        return Err(format!("Formato inválido: '{}'", pattern));
+    }
+    Ok(items)
+}
+
+fn format_date(date: &NaiveDate, pattern: &str) -> Result<String, String> {
+    let items = strftime_items(pattern)?;
+    let mut out = String::new();
+    write!(out, "{}", date.format_with_items(items.iter()))
+        .map(|_| out)
+        .map_err(|_| format!("Formato '{}' usa campos de hora, que uma Date não tem", pattern))
+}
+
+// ===== Leitura =====
+
+/// Sem `pattern`: RFC 3339, ISO 8601 sem deslocamento (hora local em `zone`,
+/// padrão UTC), só a data (meia-noite) ou RFC 2822. Com `pattern`, strftime.
+/// Um deslocamento presente no texto define o fuso, a menos que `zone` seja dado.
+pub fn parse_datetime(text: &str, pattern: Option<&str>, zone: Option<Zone>) -> Result<DateTime, String> {
+    let text = text.trim();
+    let with_offset = |parsed: ChronoDateTime<FixedOffset>| {
+        let offset = *parsed.offset();
+        let zone = zone.unwrap_or(if offset.local_minus_utc() == 0 { Zone::Utc } else { Zone::Fixed(offset) });
+        DateTime::new(parsed.with_timezone(&Utc), zone)
+    };
+    let local_zone = zone.unwrap_or(Zone::Utc);
+    let midnight = |date: NaiveDate| DateTime::from_local(date.and_time(NaiveTime::MIN), local_zone);
+
+    if let Some(pattern) = pattern {
+        strftime_items(pattern)?;
+        if let Ok(parsed) = ChronoDateTime::parse_from_str(text, pattern) {
+            return Ok(with_offset(parsed));
+        }
+        if let Ok(local) = NaiveDateTime::parse_from_str(text, pattern) {
+            return DateTime::from_local(local, local_zone);
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(text, pattern) {
+            return midnight(date);
+        }
+        return Err(format!("'{}' não corresponde ao formato '{}'", text, pattern));
+    }
+
+    if let Ok(parsed) = ChronoDateTime::parse_from_rfc3339(text) {
+        return Ok(with_offset(parsed));
+    }
+    for pattern in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%d %H:%M"] {
+        if let Ok(local) = NaiveDateTime::parse_from_str(text, pattern) {
+            return DateTime::from_local(local, local_zone);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return midnight(date);
+    }
+    if let Ok(parsed) = ChronoDateTime::parse_from_rfc2822(text) {
+        return Ok(with_offset(parsed));
+    }
+    Err(format!("Data/hora inválida: '{}' (esperado RFC 3339, ex.: 2024-03-10T09:30:00-03:00)", text))
+}
+
+/// `2024-03-10` por padrão, ou o formato strftime dado
+pub fn parse_date(text: &str, pattern: Option<&str>) -> Result<NaiveDate, String> {
+    let pattern = pattern.unwrap_or("%Y-%m-%d");
+    strftime_items(pattern)?;
+    NaiveDate::parse_from_str(text.trim(), pattern)
+        .map_err(|_| format!("'{}' não é uma data no formato '{}'", text, pattern))
+}
+
+/// ISO 8601 (`PT1H30M`, `P2DT3H`, `P1W`) ou compacto (`1h30m`, `250ms`, `2d 4h`)
+pub fn parse_duration(text: &str) -> Result<TimeDelta, String> {
+    let trimmed = text.trim();
+    let (negative, body) = match trimmed.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let invalid = || format!("Duração inválida: '{}' (use \"PT1H30M\" ou \"1h30m\")", text);
+    let seconds = match body.strip_prefix(['P', 'p']) {
+        Some(iso) => parse_iso_duration(iso).map_err(|e| e.unwrap_or_else(invalid))?,
+        None => parse_compact_duration(body).ok_or_else(invalid)?,
+    };
+    let delta = delta_from_secs(seconds)?;
+    Ok(if negative { -delta } else { delta })
+}
+
+/// Corpo depois do `P`; `Err(Some(..))` para anos/meses, que não têm duração fixa
+fn parse_iso_duration(body: &str) -> Result<f64, Option<String>> {
+    let mut seconds = 0.0;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut components = 0;
+    for c in body.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            unit => {
+                let value: f64 = number.parse().map_err(|_| None)?;
+                number.clear();
+                components += 1;
+                seconds += value
+                    * match (in_time, unit) {
+                        (false, 'W') => 604_800.0,
+                        (false, 'D') => 86_400.0,
+                        (true, 'H') => 3_600.0,
+                        (true, 'M') => 60.0,
+                        (true, 'S') => 1.0,
+                        (false, 'Y') | (false, 'M') => {
+                            return Err(Some(
+                                "Anos e meses não têm duração fixa; use add_months()/add_years()".to_string(),
+                            ))
+                        }
+                        _ => return Err(None),
+                    };
+            }
+        }
+    }
+    if !number.is_empty() || components == 0 {
+        return Err(None);
+    }
+    Ok(seconds)
+}
+
+fn parse_compact_duration(body: &str) -> Option<f64> {
+    let mut seconds = 0.0;
+    let mut rest = body.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let value: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+        let factor = match &rest[..unit_len] {
+            "w" => 604_800.0,
+            "d" => 86_400.0,
+            "h" => 3_600.0,
+            "m" | "min" => 60.0,
+            "s" => 1.0,
+            "ms" => 1e-3,
+            "us" | "µs" => 1e-6,
+            "ns" => 1e-9,
+            _ => return None,
+        };
+        seconds += value * factor;
+        rest = rest[unit_len..].trim_start();
+    }
+    Some(seconds)
+}
+
+/// `TimeDelta` a partir de segundos fracionários (precisão de nanossegundos)
+pub fn delta_from_secs(seconds: f64) -> Result<TimeDelta, String> {
+    // TimeDelta guarda até ~2.9e11 anos; o limite prático aqui é o do f64
+    if !seconds.is_finite() || seconds.abs() >= 9.2e15 {
+        return Err(format!("Duração fora do intervalo suportado: {} s", seconds));
+    }
+    let whole = seconds.floor();
+    let nanos = (((seconds - whole) * 1e9).round() as u32).min(999_999_999);
+    TimeDelta::new(whole as i64, nanos).ok_or_else(|| format!("Duração fora do intervalo suportado: {} s", seconds))
+}
+
+pub fn delta_secs(delta: &TimeDelta) -> f64 {
+    delta.num_seconds() as f64 + delta.subsec_nanos() as f64 / 1e9
+}
+
+/// `PT1H30M`, `P2DT3H`, `PT0.25S`, `-PT5M`
+pub fn duration_iso(delta: &TimeDelta) -> String {
+    if delta.is_zero() {
+        return "PT0S".to_string();
+    }
+    let sign = if *delta < TimeDelta::zero() { "-" } else { "" };
+    let delta = delta.abs();
+    let total = delta.num_seconds();
+    let nanos = delta.subsec_nanos();
+    let (days, hours, minutes, seconds) = (total / 86_400, total % 86_400 / 3_600, total % 3_600 / 60, total % 60);
+
+    let mut out = format!("{}P", sign);
+    if days > 0 {
+        out.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 {
+        out.push('T');
+        if hours > 0 {
+            out.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            out.push_str(&format!("{}M", minutes));
+        }
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            out.push_str(&format!("{}.{}S", seconds, fraction.trim_end_matches('0')));
+        } else if seconds > 0 {
+            out.push_str(&format!("{}S", seconds));
+        }
+    }
+    out
+}
+
+// ===== Calendário =====
+
+pub fn is_leap_year(year: i32) -> bool {
+    NaiveDate::from_ymd_opt(year, 2, 29).is_some()
+}
+
+pub fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next = first.checked_add_months(Months::new(1))?;
+    Some((next - first).num_days() as u32)
+}
+
+fn add_months(date: NaiveDate, months: i64) -> Result<NaiveDate, String> {
+    let amount = Months::new(months.unsigned_abs().min(u32::MAX as u64) as u32);
+    let result = if months >= 0 { date.checked_add_months(amount) } else { date.checked_sub_months(amount) };
+    // Dias que não existem no mês de destino viram o último dia (31/01 + 1 mês = 29/02)
+    result.ok_or_else(|| "Data fora do intervalo suportado".to_string())
+}
+
+fn add_years(date: NaiveDate, years: i64) -> Result<NaiveDate, String> {
+    let months = years.checked_mul(12).ok_or_else(|| "Data fora do intervalo suportado".to_string())?;
+    add_months(date, months)
+}
+
+fn add_days(date: NaiveDate, days: i64) -> Result<NaiveDate, String> {
+    TimeDelta::try_days(days)
+        .and_then(|delta| date.checked_add_signed(delta))
+        .ok_or_else(|| "Data fora do intervalo suportado".to_string())
+}
+
+/// Início da unidade ("minute", "hour", "day", "week", "month", "year");
+/// semanas começam na segunda-feira (ISO 8601)
+fn start_of(local: NaiveDateTime, unit: &str) -> Result<NaiveDateTime, String> {
+    let date = local.date();
+    let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN);
+    Ok(match unit {
+        "minute" => local.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(local),
+        "hour" => date.and_hms_opt(local.hour(), 0, 0).unwrap_or(local),
+        "day" => midnight(date),
+        "week" => midnight(date - TimeDelta::days(date.weekday().num_days_from_monday() as i64)),
+        "month" => midnight(date.with_day(1).unwrap_or(date)),
+        "year" => midnight(NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap_or(date)),
+        _ => {
+            return Err(format!(
+                "Unidade inválida '{}' (use \"minute\", \"hour\", \"day\", \"week\", \"month\" ou \"year\")",
+                unit
+            ))
+        }
+    })
+}
+
+// ===== Operadores =====
+
+pub fn is_temporal(value: &Value) -> bool {
+    matches!(value, Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_))
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => Some(*n),
+        Value::Int(i) => Some(*i as f64),
+        _ => None,
+    }
+}
+
+/// Operadores aritméticos e de ordem envolvendo datas; `None` quando nenhum
+/// operando é temporal (ou quando é concatenação com string)
+pub fn binary(operator: &str, left: &Value, right: &Value) -> Option<Result<Value, String>> {
+    if !is_temporal(left) && !is_temporal(right)
+        || matches!(left, Value::String(_))
+        || matches!(right, Value::String(_))
+        || matches!(operator, "==" | "!=" | "&&" | "||" | "!")
+    {
+        return None;
+    }
+    let unsupported = || {
+        Err(format!(
+            "Operador '{}' não se aplica a {} e {}",
+            operator,
+            left.type_name(),
+            right.type_name()
+        ))
+    };
+    Some(match operator {
+        "<" | ">" | "<=" | ">=" => match compare(left, right) {
+            Some(ordering) => Ok(Value::Bool(match operator {
+                "<" => ordering == Ordering::Less,
+                ">" => ordering == Ordering::Greater,
+                "<=" => ordering != Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            })),
+            None => unsupported(),
+        },
+        "+" => match (left, right) {
+            (Value::DateTime(dt), Value::Duration(d)) | (Value::Duration(d), Value::DateTime(dt)) => {
+                dt.add(*d).map(Value::DateTime)
+            }
+            (Value::Date(date), Value::Duration(d)) | (Value::Duration(d), Value::Date(date)) => {
+                whole_days(d).and_then(|days| add_days(*date, days)).map(Value::Date)
+            }
+            (Value::Duration(a), Value::Duration(b)) => a
+                .checked_add(b)
+                .map(Value::Duration)
+                .ok_or_else(|| "Duração fora do intervalo suportado".to_string()),
+            _ => unsupported(),
+        },
+        "-" => match (left, right) {
+            (Value::DateTime(dt), Value::Duration(d)) => dt.add(-*d).map(Value::DateTime),
+            (Value::DateTime(a), Value::DateTime(b)) => Ok(Value::Duration(a.instant - b.instant)),
+            (Value::Date(date), Value::Duration(d)) => whole_days(d).and_then(|days| add_days(*date, -days)).map(Value::Date),
+            (Value::Date(a), Value::Date(b)) => Ok(Value::Duration(*a - *b)),
+            (Value::Duration(a), Value::Duration(b)) => a
+                .checked_sub(b)
+                .map(Value::Duration)
+                .ok_or_else(|| "Duração fora do intervalo suportado".to_string()),
+            _ => unsupported(),
+        },
+        "*" => match (left, right) {
+            (Value::Duration(d), other) | (other, Value::Duration(d)) if number(other).is_some() => {
+                delta_from_secs(delta_secs(d) * number(other).unwrap_or(0.0)).map(Value::Duration)
+            }
+            _ => unsupported(),
+        },
+        "/" => match (left, right) {
+            (Value::Duration(a), Value::Duration(b)) => {
+                if b.is_zero() {
+                    Err("Divisão de duração por zero".to_string())
+                } else {
+                    Ok(Value::Number(delta_secs(a) / delta_secs(b)))
+                }
+            }
+            (Value::Duration(d), other) => match number(other) {
+                Some(n) if n != 0.0 => delta_from_secs(delta_secs(d) / n).map(Value::Duration),
+                Some(_) => Err("Divisão de duração por zero".to_string()),
+                None => unsupported(),
+            },
+            _ => unsupported(),
+        },
+        _ => unsupported(),
+    })
+}
+
+fn whole_days(delta: &TimeDelta) -> Result<i64, String> {
+    if delta.subsec_nanos() != 0 || delta.num_seconds() % 86_400 != 0 {
+        return Err("Somar a uma Date exige dias inteiros; use date.at() para obter um DateTime".to_string());
+    }
+    Ok(delta.num_days())
+}
+
+pub fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::DateTime(a), Value::DateTime(b)) => Some(a.instant.cmp(&b.instant)),
+        (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+        (Value::Duration(a), Value::Duration(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+/// Igualdade para `==`: `DateTime`s iguais são o mesmo instante, em qualquer fuso
+pub fn equals(left: &Value, right: &Value) -> Option<bool> {
+    match (left, right) {
+        (Value::TimeZone(a), Value::TimeZone(b)) => Some(a.name() == b.name()),
+        (a, b) if is_temporal(a) && is_temporal(b) => Some(compare(a, b) == Some(Ordering::Equal)),
+        _ => None,
+    }
+}
+
+pub fn negate(value: &Value) -> Option<Value> {
+    match value {
+        Value::Duration(d) => Some(Value::Duration(-*d)),
+        _ => None,
+    }
+}
+
+/// Texto usado em concatenação, JSON e `print`
+pub fn to_text(value: &Value) -> Option<String> {
+    match value {
+        Value::DateTime(dt) => Some(dt.to_rfc3339()),
+        Value::Date(date) => Some(date.format("%Y-%m-%d").to_string()),
+        Value::Duration(d) => Some(duration_iso(d)),
+        Value::TimeZone(zone) => Some(zone.name()),
+        _ => None,
+    }
+}
+
+/// Forma do `inspect`: `DateTime(2024-03-10T09:30:00-03:00 America/Sao_Paulo)`
+pub fn inspect(value: &Value) -> Option<String> {
+    match value {
+        Value::DateTime(dt) => Some(match dt.zone {
+            Zone::Iana(_) | Zone::Local => format!("DateTime({} {})", dt.to_rfc3339(), dt.zone.name()),
+            _ => format!("DateTime({})", dt.to_rfc3339()),
+        }),
+        Value::Date(_) => Some(format!("Date({})", to_text(value)?)),
+        Value::Duration(_) => Some(format!("Duration({})", to_text(value)?)),
+        Value::TimeZone(zone) => Some(format!("TimeZone({})", zone.name())),
+        _ => None,
+    }
+}
+
+// ===== Propriedades e métodos =====
+
+fn date_property(date: &NaiveDate, name: &str) -> Option<Value> {
+    let iso = date.iso_week();
+    Some(Value::Number(match name {
+        "year" => date.year() as f64,
+        "month" => date.month() as f64,
+        "day" => date.day() as f64,
+        // 1 = segunda ... 7 = domingo (ISO 8601)
+        "weekday" => date.weekday().number_from_monday() as f64,
+        "day_of_year" => date.ordinal() as f64,
+        "iso_week" => iso.week() as f64,
+        "iso_year" => iso.year() as f64,
+        "days_in_month" => days_in_month(date.year(), date.month())? as f64,
+        "is_leap_year" => return Some(Value::Bool(is_leap_year(date.year()))),
+        _ => return None,
+    }))
+}
+
+pub fn property(value: &Value, name: &str) -> Result<Value, String> {
+    let found = match value {
+        Value::DateTime(dt) => {
+            let local = dt.local();
+            match name {
+                "hour" => Some(Value::Number(local.hour() as f64)),
+                "minute" => Some(Value::Number(local.minute() as f64)),
+                "second" => Some(Value::Number(local.second() as f64)),
+                "millisecond" => Some(Value::Number((local.nanosecond() / 1_000_000) as f64)),
+                "timestamp" => Some(Value::Number(dt.instant.timestamp_millis() as f64)),
+                "offset" => Some(Value::Duration(TimeDelta::seconds(dt.offset().local_minus_utc() as i64))),
+                "timezone" => Some(Value::TimeZone(dt.zone)),
+                "date" => Some(Value::Date(local.date())),
+                _ => date_property(&local.date(), name),
+            }
+        }
+        Value::Date(date) => date_property(date, name),
+        Value::Duration(d) => {
+            let seconds = delta_secs(d);
+            match name {
+                "ms" => Some(Value::Number(seconds * 1e3)),
+                "seconds" => Some(Value::Number(seconds)),
+                "minutes" => Some(Value::Number(seconds / 60.0)),
+                "hours" => Some(Value::Number(seconds / 3_600.0)),
+                "days" => Some(Value::Number(seconds / 86_400.0)),
+                "weeks" => Some(Value::Number(seconds / 604_800.0)),
+                "is_negative" => Some(Value::Bool(*d < TimeDelta::zero())),
+                _ => None,
+            }
+        }
+        Value::TimeZone(zone) => match name {
+            "name" => Some(Value::String(zone.name())),
+            _ => None,
+        },
+        _ => None,
+    };
+    found.ok_or_else(|| format!("Propriedade '{}' não encontrada em {}", name, value.type_name()))
+}
+
+fn int_arg(args: &[Value], index: usize, method: &str) -> Result<i64, String> {
+    match args.get(index).and_then(number) {
+        Some(n) if n.fract() == 0.0 => Ok(n as i64),
+        _ => Err(format!("{}() espera um número inteiro", method)),
+    }
+}
+
+fn str_arg<'a>(args: &'a [Value], index: usize, method: &str) -> Result<&'a str, String> {
+    match args.get(index) {
+        Some(Value::String(s)) => Ok(s),
+        _ => Err(format!("{}() espera uma string", method)),
+    }
+}
+
+/// Fuso de um argumento: string ("America/Sao_Paulo") ou `TimeZone`
+pub fn zone_arg(value: Option<&Value>) -> Result<Option<Zone>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(name)) => Zone::parse(name).map(Some),
+        Some(Value::TimeZone(zone)) => Ok(Some(*zone)),
+        Some(other) => Err(format!("Fuso deve ser uma string ou TimeZone, encontrado {}", other.type_name())),
+    }
+}
+
+/// Campos de `with({ year, month, day, hour, minute, second, millisecond })`
+fn with_fields(local: NaiveDateTime, fields: &Value, heap: &Heap) -> Result<NaiveDateTime, String> {
+    let Value::Object(id) = fields else {
+        return Err("with() espera um objeto, ex.: { hour: 9, minute: 0 }".to_string());
+    };
+    let Some(ManagedObject::Object { properties, .. }) = heap.get(*id) else {
+        return Err("with() espera um objeto".to_string());
+    };
+    let field = |name: &str, current: u32| -> Result<u32, String> {
+        match properties.get(name) {
+            None => Ok(current),
+            Some(value) => match number(value) {
+                Some(n) if n.fract() == 0.0 && n >= 0.0 => Ok(n as u32),
+                _ => Err(format!("with(): '{}' deve ser um inteiro não negativo", name)),
+            },
+        }
+    };
+    let year = match properties.get("year") {
+        None => local.year(),
+        Some(value) => number(value).filter(|n| n.fract() == 0.0).ok_or("with(): 'year' deve ser um inteiro")? as i32,
+    };
+    for key in properties.keys() {
+        if !["year", "month", "day", "hour", "minute", "second", "millisecond"].contains(&key.as_str()) {
+            return Err(format!("with(): campo desconhecido '{}'", key));
+        }
+    }
+    let (month, day) = (field("month", local.month())?, field("day", local.day())?);
+    let date = NaiveDate::from_ymd_opt(year, month, day)
+        .ok_or_else(|| format!("Data inválida: {:04}-{:02}-{:02}", year, month, day))?;
+    let millisecond = field("millisecond", local.nanosecond() / 1_000_000)?;
+    let time = NaiveTime::from_hms_milli_opt(
+        field("hour", local.hour())?,
+        field("minute", local.minute())?,
+        field("second", local.second())?,
+        millisecond,
+    )
+    .ok_or("with(): hora inválida")?;
+    Ok(date.and_time(time))
+}
+
+pub fn method(value: &Value, name: &str, args: &[Value], heap: &Heap) -> Result<Value, String> {
+    match value {
+        Value::DateTime(dt) => match name {
+            "format" => dt.format(str_arg(args, 0, name)?).map(Value::String),
+            "to_iso" | "to_string" => Ok(Value::String(dt.to_rfc3339())),
+            "to_timezone" => match zone_arg(args.first())? {
+                Some(zone) => Ok(Value::DateTime(DateTime::new(dt.instant, zone))),
+                None => Err("to_timezone() espera um fuso".to_string()),
+            },
+            "to_utc" => Ok(Value::DateTime(DateTime::new(dt.instant, Zone::Utc))),
+            "add" | "sub" => match args.first() {
+                Some(Value::Duration(d)) => dt.add(if name == "add" { *d } else { -*d }).map(Value::DateTime),
+                _ => Err(format!("{}() espera uma Duration", name)),
+            },
+            "add_days" => {
+                let local = dt.local();
+                let date = add_days(local.date(), int_arg(args, 0, name)?)?;
+                dt.with_local(date.and_time(local.time())).map(Value::DateTime)
+            }
+            "add_months" | "add_years" => {
+                let local = dt.local();
+                let amount = int_arg(args, 0, name)?;
+                let date = if name == "add_years" { add_years(local.date(), amount)? } else { add_months(local.date(), amount)? };
+                dt.with_local(date.and_time(local.time())).map(Value::DateTime)
+            }
+            "diff" => match args.first() {
+                Some(Value::DateTime(other)) => Ok(Value::Duration(dt.instant - other.instant)),
+                _ => Err("diff() espera um DateTime".to_string()),
+            },
+            "start_of" => dt.with_local(start_of(dt.local(), str_arg(args, 0, name)?)?).map(Value::DateTime),
+            "with" => match args.first() {
+                Some(fields) => dt.with_local(with_fields(dt.local(), fields, heap)?).map(Value::DateTime),
+                None => Err("with() espera um objeto".to_string()),
+            },
+            _ => Err(format!("Método '{}' não encontrado em datetime", name)),
+        },
+        Value::Date(date) => match name {
+            "format" => format_date(date, str_arg(args, 0, name)?).map(Value::String),
+            "to_iso" | "to_string" => Ok(Value::String(date.format("%Y-%m-%d").to_string())),
+            "add_days" => add_days(*date, int_arg(args, 0, name)?).map(Value::Date),
+            "add_months" => add_months(*date, int_arg(args, 0, name)?).map(Value::Date),
+            "add_years" => add_years(*date, int_arg(args, 0, name)?).map(Value::Date),
+            "diff" => match args.first() {
+                Some(Value::Date(other)) => Ok(Value::Duration(*date - *other)),
+                _ => Err("diff() espera uma Date".to_string()),
+            },
+            "start_of" => Ok(Value::Date(start_of(date.and_time(NaiveTime::MIN), str_arg(args, 0, name)?)?.date())),
+            // date.at(9, 30, "America/Sao_Paulo"): hora local no fuso (padrão UTC)
+            "at" => {
+                let zone_index = args.iter().position(|arg| matches!(arg, Value::String(_) | Value::TimeZone(_)));
+                let zone = zone_arg(zone_index.map(|i| &args[i]))?.unwrap_or(Zone::Utc);
+                let parts = &args[..zone_index.unwrap_or(args.len())];
+                let part = |i: usize| if i < parts.len() { int_arg(parts, i, "at") } else { Ok(0) };
+                let time = NaiveTime::from_hms_milli_opt(part(0)? as u32, part(1)? as u32, part(2)? as u32, part(3)? as u32)
+                    .ok_or("at(): hora inválida")?;
+                DateTime::from_local(date.and_time(time), zone).map(Value::DateTime)
+            }
+            _ => Err(format!("Método '{}' não encontrado em date", name)),
+        },
+        Value::Duration(d) => match name {
+            "to_iso" | "to_string" => Ok(Value::String(duration_iso(d))),
+            "abs" => Ok(Value::Duration(d.abs())),
+            _ => Err(format!("Método '{}' não encontrado em duration", name)),
+        },
+        Value::TimeZone(zone) => match name {
+            // Deslocamento em relação a UTC no instante dado (padrão: agora)
+            "offset_at" => {
+                let instant = match args.first() {
+                    None => Utc::now(),
+                    Some(Value::DateTime(dt)) => dt.instant,
+                    Some(_) => return Err("offset_at() espera um DateTime".to_string()),
+                };
+                Ok(Value::Duration(TimeDelta::seconds(zone.offset_at(&instant).local_minus_utc() as i64)))
+            }
+            "to_string" => Ok(Value::String(zone.name())),
+            _ => Err(format!("Método '{}' não encontrado em timezone", name)),
+        },
+        _ => Err(format!("Método '{}' não encontrado", name)),
+    }
+}
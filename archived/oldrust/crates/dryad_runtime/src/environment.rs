@@ -1,5 +1,5 @@
 use crate::value::Value;
-use dryad_parser::ast::InterfaceMember;
+use dryad_parser::ast::{InterfaceMember, Type};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -12,6 +12,8 @@ pub struct Environment {
     pub current_class: Option<String>,
     pub imported_modules: HashMap<String, HashMap<String, Value>>,
     pub call_stack_vars: Vec<HashMap<String, Value>>,
+    /// Tipos declarados de variáveis e parâmetros (apenas com `--strict-types`)
+    pub variable_types: HashMap<String, Type>,
 }
 
 impl Environment {
@@ -25,6 +27,7 @@ impl Environment {
             current_class: None,
             imported_modules: HashMap::new(),
             call_stack_vars: Vec::new(),
+            variable_types: HashMap::new(),
         }
     }
 
@@ -74,5 +77,6 @@ impl Environment {
         self.current_class = None;
         self.imported_modules.clear();
         self.call_stack_vars.clear();
+        self.variable_types.clear();
     }
 }
@@ -1,4 +1,6 @@
-use crate::value::{ClassGetter, ClassMethod, ClassProperty, ClassSetter, ObjectMethod, Value};
+use crate::collections::{self, MapEntries, SetEntries};
+use crate::iteration::GeneratorState;
+use crate::value::{ClassGetter, ClassMethod, ClassProperty, ClassSetter, ObjectMethod, Signature, Value};
 use dryad_parser::ast::Expr;
 use std::collections::HashMap;
 
@@ -19,6 +21,7 @@ pub enum ManagedObject {
         params: Vec<(String, Option<Expr>)>,
         body: Expr,
         closure: HashMap<String, Value>,
+        signature: Signature,
     },
     Class {
         name: String,
@@ -28,6 +31,7 @@ pub enum ManagedObject {
         properties: HashMap<String, ClassProperty>,
         getters: HashMap<String, ClassGetter>,
         setters: HashMap<String, ClassSetter>,
+        is_abstract: bool,
     },
     Instance {
         class_name: String,
@@ -37,6 +41,18 @@ pub enum ManagedObject {
         properties: HashMap<String, Value>,
         methods: HashMap<String, ObjectMethod>,
     },
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<String>)>, // (variante, nomes dos campos)
+    },
+    Variant {
+        enum_name: String,
+        variant: String,
+        fields: Vec<(String, Value)>,
+    },
+    Generator(GeneratorState),
+    Map(MapEntries),
+    Set(SetEntries),
 }
 
 pub struct Heap {
@@ -45,6 +61,9 @@ pub struct Heap {
     allocation_count: usize,
     gc_threshold: usize,
     pub gc_stats: GcStats,
+    /// Alocações por local (id definido pelo profiler); `None` = contagem desativada
+    allocation_sites: Option<HashMap<usize, u64>>,
+    current_site: Option<usize>,
 }
 
 impl Heap {
@@ -55,6 +74,8 @@ impl Heap {
             allocation_count: 0,
             gc_threshold: 1000, // Default: trigger GC every 1000 allocations
             gc_stats: GcStats::default(),
+            allocation_sites: None,
+            current_site: None,
         }
     }
 
@@ -75,9 +96,28 @@ impl Heap {
         self.next_id += 1;
         self.objects.insert(id, (obj, false));
         self.allocation_count += 1;
+        if let (Some(sites), Some(site)) = (self.allocation_sites.as_mut(), self.current_site) {
+            *sites.entry(site).or_insert(0) += 1;
+        }
         id
     }
 
+    /// Passa a contar alocações por local (usado por `dryad run --profile`)
+    pub fn enable_allocation_sites(&mut self) {
+        self.allocation_sites = Some(HashMap::new());
+    }
+
+    /// Define o local ao qual as próximas alocações são atribuídas, retornando o anterior
+    pub fn set_allocation_site(&mut self, site: Option<usize>) -> Option<usize> {
+        std::mem::replace(&mut self.current_site, site)
+    }
+
+    /// Retorna as contagens por local, desativando a contagem
+    pub fn take_allocation_sites(&mut self) -> Option<HashMap<usize, u64>> {
+        self.current_site = None;
+        self.allocation_sites.take()
+    }
+
     pub fn get(&self, id: HeapId) -> Option<&ManagedObject> {
         self.objects.get(&id).map(|(obj, _)| obj)
     }
@@ -158,6 +198,27 @@ impl Heap {
                     self.trace_value(val, worklist);
                 }
             }
+            ManagedObject::Enum { .. } => {}
+            ManagedObject::Variant { fields, .. } => {
+                for (_, val) in fields {
+                    self.trace_value(val, worklist);
+                }
+            }
+            ManagedObject::Generator(state) => {
+                for val in state.values() {
+                    self.trace_value(val, worklist);
+                }
+            }
+            ManagedObject::Map(entries) => {
+                for val in collections::map_values(entries) {
+                    self.trace_value(val, worklist);
+                }
+            }
+            ManagedObject::Set(entries) => {
+                for val in entries.values() {
+                    self.trace_value(val, worklist);
+                }
+            }
         }
     }
 
@@ -168,7 +229,12 @@ impl Heap {
             | Value::Lambda(id)
             | Value::Class(id)
             | Value::Instance(id)
-            | Value::Object(id) => {
+            | Value::Object(id)
+            | Value::Enum(id)
+            | Value::Variant(id)
+            | Value::Generator(id)
+            | Value::Map(id)
+            | Value::Set(id) => {
                 worklist.push(*id);
             }
             Value::Promise {
@@ -0,0 +1,432 @@
+use crate::heap::{Heap, HeapId, ManagedObject};
+use crate::datetime;
+use crate::value::Value;
+use std::collections::HashMap;
+
+/// Opções de formatação usadas por `inspect`, pelo `print`, pelo REPL e pelo debugger.
+#[derive(Debug, Clone)]
+pub struct InspectOptions {
+    /// Profundidade máxima de aninhamento; estruturas mais profundas viram `[Array]`, `{...}` etc.
+    pub depth: usize,
+    /// Número máximo de elementos/propriedades exibidos por coleção.
+    pub max_items: usize,
+    /// Comprimento máximo de strings aninhadas (`None` = sem limite).
+    pub max_string_length: Option<usize>,
+    /// Largura a partir da qual coleções são quebradas em várias linhas.
+    pub break_length: usize,
+    /// Aplica cores ANSI.
+    pub colors: bool,
+    /// Coloca aspas em strings de nível superior (strings aninhadas sempre têm aspas).
+    pub quote_strings: bool,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self {
+            depth: 4,
+            max_items: 100,
+            max_string_length: None,
+            break_length: 80,
+            colors: false,
+            quote_strings: true,
+        }
+    }
+}
+
+impl InspectOptions {
+    /// Opções usadas por `print`/`println`: strings de nível superior sem aspas.
+    pub fn display() -> Self {
+        Self {
+            quote_strings: false,
+            ..Self::default()
+        }
+    }
+
+    /// Lê opções a partir de um objeto Dryad, ex.: `{ depth: 2, maxItems: 10, colors: true }`.
+    /// Chaves desconhecidas são ignoradas.
+    pub fn from_value(value: &Value, heap: &Heap) -> Result<Self, String> {
+        let mut options = Self::default();
+        let properties = match value {
+            Value::Null => return Ok(options),
+            Value::Object(id) => match heap.get(*id) {
+                Some(ManagedObject::Object { properties, .. }) => properties,
+                _ => return Err("Opções de inspect devem ser um objeto".to_string()),
+            },
+            _ => return Err("Opções de inspect devem ser um objeto".to_string()),
+        };
+
+        for (key, val) in properties {
+            match (key.as_str(), val) {
+                ("depth", Value::Number(n)) => options.depth = n.max(0.0) as usize,
+                ("maxItems", Value::Number(n)) => options.max_items = n.max(0.0) as usize,
+                ("maxStringLength", Value::Number(n)) => {
+                    options.max_string_length = Some(n.max(0.0) as usize)
+                }
+                ("maxStringLength", Value::Null) => options.max_string_length = None,
+                ("breakLength", Value::Number(n)) => options.break_length = n.max(0.0) as usize,
+                ("colors", Value::Bool(b)) => options.colors = *b,
+                ("quoteStrings", Value::Bool(b)) => options.quote_strings = *b,
+                ("depth" | "maxItems" | "maxStringLength" | "breakLength", _) => {
+                    return Err(format!("Opção '{}' deve ser um número", key))
+                }
+                ("colors" | "quoteStrings", _) => {
+                    return Err(format!("Opção '{}' deve ser um boolean", key))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const NUMBER: &str = "\x1b[33m";
+const STRING: &str = "\x1b[32m";
+const BOOL: &str = "\x1b[35m";
+const NULL: &str = "\x1b[90m";
+const SPECIAL: &str = "\x1b[36m";
+const ERROR: &str = "\x1b[31m";
+
+/// Formata um valor seguindo as referências do heap.
+pub fn inspect(value: &Value, heap: &Heap, options: &InspectOptions) -> String {
+    let mut inspector = Inspector {
+        heap,
+        options,
+        ancestors: Vec::new(),
+    };
+    match value {
+        Value::String(s) if !options.quote_strings => s.clone(),
+        _ => inspector.format(value, 0, 0),
+    }
+}
+
+/// Forma usada pelo `print`: igual a `inspect` mas sem aspas na string de nível superior.
+pub fn display(value: &Value, heap: &Heap) -> String {
+    inspect(value, heap, &InspectOptions::display())
+}
+
+struct Inspector<'a> {
+    heap: &'a Heap,
+    options: &'a InspectOptions,
+    /// Objetos atualmente sendo formatados (caminho da raiz até aqui), para detectar ciclos.
+    ancestors: Vec<HeapId>,
+}
+
+impl<'a> Inspector<'a> {
+    fn paint(&self, color: &str, text: String) -> String {
+        if self.options.colors {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text
+        }
+    }
+
+    fn format(&mut self, value: &Value, depth: usize, indent: usize) -> String {
+        match value {
+            Value::Number(_) => self.paint(NUMBER, value.to_string()),
+            // Sufixo distingue os tipos exatos de um number comum (42i, 42n, 1.50d)
+            Value::Int(i) => self.paint(NUMBER, format!("{}i", i)),
+            Value::BigInt(b) => self.paint(NUMBER, format!("{}n", b)),
+            Value::Decimal(d) => self.paint(NUMBER, format!("{}d", d)),
+            Value::Bool(b) => self.paint(BOOL, b.to_string()),
+            Value::Null => self.paint(NULL, "null".to_string()),
+            Value::String(s) => self.paint(STRING, self.quote(s)),
+            Value::Exception(msg) => self.paint(ERROR, format!("Exception({})", self.quote(msg))),
+            Value::Function { name, .. } => self.paint(SPECIAL, format!("[function {}]", name)),
+            Value::AsyncFunction { name, .. } => {
+                self.paint(SPECIAL, format!("[async function {}]", name))
+            }
+            Value::ThreadFunction { name, .. } => {
+                self.paint(SPECIAL, format!("[thread function {}]", name))
+            }
+            Value::GeneratorFunction { name, .. } => {
+                self.paint(SPECIAL, format!("[function* {}]", name))
+            }
+            Value::Range { .. } => self.paint(NUMBER, value.to_string()),
+            Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) => {
+                self.paint(SPECIAL, datetime::inspect(value).unwrap_or_default())
+            }
+            Value::Regex(regexp) => self.paint(SPECIAL, regexp.literal()),
+            Value::Thread { id, is_running } => self.paint(
+                SPECIAL,
+                format!("Thread {{ id: {}, running: {} }}", id, is_running),
+            ),
+            Value::Mutex { id, locked } => self.paint(
+                SPECIAL,
+                format!("Mutex {{ id: {}, locked: {} }}", id, locked),
+            ),
+            Value::Promise {
+                resolved, value, ..
+            } => match (resolved, value) {
+                (true, Some(inner)) => {
+                    let inner = self.format(inner, depth + 1, indent);
+                    format!("Promise {{ {} }}", inner)
+                }
+                (true, None) => "Promise { null }".to_string(),
+                (false, _) => format!("Promise {{ {} }}", self.paint(SPECIAL, "<pending>".to_string())),
+            },
+            Value::Result(ok, inner) => {
+                let inner = self.format(inner, depth + 1, indent);
+                if *ok {
+                    format!("Ok({})", inner)
+                } else {
+                    format!("{}({})", self.paint(ERROR, "Err".to_string()), inner)
+                }
+            }
+            Value::Array(id)
+            | Value::Tuple(id)
+            | Value::Lambda(id)
+            | Value::Class(id)
+            | Value::Instance(id)
+            | Value::Object(id)
+            | Value::Enum(id)
+            | Value::Variant(id)
+            | Value::Generator(id)
+            | Value::Map(id)
+            | Value::Set(id) => self.format_heap(value, *id, depth, indent),
+        }
+    }
+
+    fn quote(&self, s: &str) -> String {
+        match self.options.max_string_length {
+            Some(max) if s.chars().count() > max => {
+                let truncated: String = s.chars().take(max).collect();
+                let rest = s.chars().count() - max;
+                format!("{:?}... {} mais", truncated, rest)
+            }
+            _ => format!("{:?}", s),
+        }
+    }
+
+    fn format_heap(&mut self, value: &Value, id: HeapId, depth: usize, indent: usize) -> String {
+        let heap = self.heap;
+        let obj = match heap.get(id) {
+            Some(obj) => obj,
+            None => return self.paint(ERROR, format!("<referência inválida {}>", id)),
+        };
+
+        match obj {
+            ManagedObject::Lambda { params, .. } => {
+                let names: Vec<&str> = params.iter().map(|(n, _)| n.as_str()).collect();
+                return self.paint(SPECIAL, format!("[lambda ({})]", names.join(", ")));
+            }
+            ManagedObject::Class { name, parent, .. } => {
+                let text = match parent {
+                    Some(parent) => format!("[class {} extends {}]", name, parent),
+                    None => format!("[class {}]", name),
+                };
+                return self.paint(SPECIAL, text);
+            }
+            ManagedObject::Enum { name, .. } => {
+                return self.paint(SPECIAL, format!("[enum {}]", name));
+            }
+            ManagedObject::Generator(state) => {
+                return self.paint(SPECIAL, format!("[generator {}]", state.name));
+            }
+            ManagedObject::Variant {
+                enum_name,
+                variant,
+                fields,
+            } if fields.is_empty() => return format!("{}.{}", enum_name, variant),
+            _ => {}
+        }
+
+        if self.ancestors.contains(&id) {
+            return self.paint(SPECIAL, "[Circular]".to_string());
+        }
+
+        if depth >= self.options.depth {
+            let text = match obj {
+                ManagedObject::Array(_) => "[Array]".to_string(),
+                ManagedObject::Tuple(_) => "(Tuple)".to_string(),
+                ManagedObject::Instance { class_name, .. } => format!("{} {{...}}", class_name),
+                ManagedObject::Variant {
+                    enum_name, variant, ..
+                } => format!("{}.{}(...)", enum_name, variant),
+                ManagedObject::Map(entries) => format!("Map({}) {{...}}", entries.len()),
+                ManagedObject::Set(entries) => format!("Set({}) {{...}}", entries.len()),
+                _ => "{...}".to_string(),
+            };
+            return self.paint(SPECIAL, text);
+        }
+
+        self.ancestors.push(id);
+        let child_indent = indent + 2;
+        let (prefix, open, close, entries, omitted) = match obj {
+            ManagedObject::Array(items) | ManagedObject::Tuple(items) => {
+                let shown = items.len().min(self.options.max_items);
+                let entries: Vec<String> = items[..shown]
+                    .iter()
+                    .map(|item| self.format(item, depth + 1, child_indent))
+                    .collect();
+                let (open, close) = if matches!(value, Value::Tuple(_)) {
+                    ("(", ")")
+                } else {
+                    ("[", "]")
+                };
+                (String::new(), open, close, entries, items.len() - shown)
+            }
+            ManagedObject::Instance {
+                class_name,
+                properties,
+            } => {
+                let (entries, omitted) = self.format_properties(properties, depth, child_indent);
+                (format!("{} ", class_name), "{", "}", entries, omitted)
+            }
+            ManagedObject::Object {
+                properties,
+                methods,
+            } => {
+                let (mut entries, omitted) =
+                    self.format_properties(properties, depth, child_indent);
+                let mut method_names: Vec<&String> = methods.keys().collect();
+                method_names.sort();
+                for name in method_names {
+                    if entries.len() >= self.options.max_items {
+                        break;
+                    }
+                    entries.push(format!("{}: {}", name, self.paint(SPECIAL, "[method]".to_string())));
+                }
+                (String::new(), "{", "}", entries, omitted)
+            }
+            ManagedObject::Variant {
+                enum_name,
+                variant,
+                fields,
+            } => {
+                let shown = fields.len().min(self.options.max_items);
+                let entries: Vec<String> = fields[..shown]
+                    .iter()
+                    .map(|(_, field)| self.format(field, depth + 1, child_indent))
+                    .collect();
+                (
+                    format!("{}.{}", enum_name, variant),
+                    "(",
+                    ")",
+                    entries,
+                    fields.len() - shown,
+                )
+            }
+            ManagedObject::Map(map) => {
+                let shown = map.len().min(self.options.max_items);
+                let entries: Vec<String> = map
+                    .values()
+                    .take(shown)
+                    .map(|(key, value)| {
+                        format!(
+                            "{} => {}",
+                            self.format(key, depth + 1, child_indent),
+                            self.format(value, depth + 1, child_indent)
+                        )
+                    })
+                    .collect();
+                (format!("Map({}) ", map.len()), "{", "}", entries, map.len() - shown)
+            }
+            ManagedObject::Set(set) => {
+                let shown = set.len().min(self.options.max_items);
+                let entries: Vec<String> = set
+                    .values()
+                    .take(shown)
+                    .map(|item| self.format(item, depth + 1, child_indent))
+                    .collect();
+                (format!("Set({}) ", set.len()), "{", "}", entries, set.len() - shown)
+            }
+            ManagedObject::Lambda { .. }
+            | ManagedObject::Class { .. }
+            | ManagedObject::Enum { .. }
+            | ManagedObject::Generator(_) => {
+                unreachable!()
+            }
+        };
+        self.ancestors.pop();
+
+        self.join(&prefix, open, close, entries, omitted, indent)
+    }
+
+    fn format_properties(
+        &mut self,
+        properties: &HashMap<String, Value>,
+        depth: usize,
+        indent: usize,
+    ) -> (Vec<String>, usize) {
+        let mut keys: Vec<&String> = properties.keys().collect();
+        keys.sort();
+        let shown = keys.len().min(self.options.max_items);
+        let entries = keys[..shown]
+            .iter()
+            .map(|key| {
+                let formatted = self.format(&properties[*key], depth + 1, indent);
+                format!("{}: {}", format_key(key), formatted)
+            })
+            .collect();
+        (entries, keys.len() - shown)
+    }
+
+    fn join(
+        &self,
+        prefix: &str,
+        open: &str,
+        close: &str,
+        mut entries: Vec<String>,
+        omitted: usize,
+        indent: usize,
+    ) -> String {
+        if omitted > 0 {
+            entries.push(format!("... {} mais", omitted));
+        }
+        if entries.is_empty() {
+            return format!("{}{}{}", prefix, open, close);
+        }
+
+        // Objetos usam espaços internos (`{ a: 1 }`), arrays e tuplas não (`[1, 2]`).
+        let pad = if open == "{" { " " } else { "" };
+        let single = format!("{}{}{}{}{}{}", prefix, open, pad, entries.join(", "), pad, close);
+        let fits = visible_len(&single) + indent <= self.options.break_length;
+        if fits && !single.contains('\n') {
+            return single;
+        }
+
+        let inner = " ".repeat(indent + 2);
+        let lines: Vec<String> = entries.iter().map(|e| format!("{}{}", inner, e)).collect();
+        format!(
+            "{}{}\n{}\n{}{}",
+            prefix,
+            open,
+            lines.join(",\n"),
+            " ".repeat(indent),
+            close
+        )
+    }
+}
+
+fn format_key(key: &str) -> String {
+    let is_identifier = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if is_identifier {
+        key.to_string()
+    } else {
+        format!("{:?}", key)
+    }
+}
+
+/// Comprimento visível, ignorando sequências de escape ANSI.
+fn visible_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in s.chars() {
+        if in_escape {
+            if c == 'm' {
+                in_escape = false;
+            }
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            len += 1;
+        }
+    }
+    len
+}
@@ -2,16 +2,28 @@
 use crate::debug::{DebugCommand, DebugEvent, ExecutionMode, SharedDebugState};
 use crate::environment::Environment;
 use crate::heap::{Heap, HeapId, ManagedObject};
+use crate::collections::{MapEntries, MapKey, SetEntries};
+use crate::coverage::{Coverage, FileCoverage};
+use crate::datetime;
+use crate::regexp::{self, Regexp};
+use crate::profiler::{AllocationSite, Profile, Profiler};
+use crate::inspect::{self, InspectOptions};
+use crate::iteration::{
+    pattern_names, stmt_contains_yield, GeneratorFrame, GeneratorState, GeneratorStatus,
+    GeneratorStep, TryStage, Unwind, ValueIter, YieldTarget,
+};
 use crate::native_modules::NativeModuleManager;
+use crate::numeric::{self, NumericError, Promoted};
 use crate::native_registry::NativeRegistry;
 pub use crate::value::{
-    ClassGetter, ClassMethod, ClassProperty, ClassSetter, FlowControl, ObjectMethod, Value,
+    ClassGetter, ClassMethod, ClassProperty, ClassSetter, FlowControl, ObjectMethod, Signature,
+    Value,
 };
 use dryad_bytecode::{Chunk, Compiler, InterpretResult as BytecodeInterpretResult, VM};
 use dryad_errors::{error_catalog, DryadError, SourceLocation, StackFrame, StackTrace};
 use dryad_parser::ast::{
     ClassMember, Expr, ImportKind, InterfaceMember, Literal, MatchArm, ObjectProperty, Pattern,
-    Program, Stmt, Visibility,
+    Program, Stmt, Type, Visibility,
 };
 use serde_json::{self, Value as JsonValue};
 use std::collections::HashMap;
@@ -49,6 +61,14 @@ pub struct Interpreter {
     compile_mode: bool,
     jit_mode: bool,
     pending_return_value: Option<Value>,
+    coverage: Option<Coverage>,
+    profiler: Option<Profiler>,
+    /// Iteráveis, iteradores e geradores em uso (raízes extras do GC)
+    iteration_roots: Vec<Value>,
+    /// Verifica os tipos anotados em runtime (`dryad run --strict-types`)
+    strict_types: bool,
+    /// Aliases declarados com `type Nome = ...`
+    type_aliases: HashMap<String, Type>,
 }
 
 const MAX_RECURSION_DEPTH: usize = 1000;
@@ -73,6 +93,11 @@ impl Interpreter {
             compile_mode: false,
             jit_mode: false,
             pending_return_value: None,
+            coverage: None,
+            profiler: None,
+            iteration_roots: Vec::new(),
+            strict_types: false,
+            type_aliases: HashMap::new(),
         }
     }
 
@@ -92,10 +117,39 @@ impl Interpreter {
         self.native_registry.manager.set_allow_exec(allow);
     }
 
+    /// Ativa a coleta de cobertura de linhas e branches (`dryad test --coverage`)
+    pub fn enable_coverage(&mut self) {
+        self.coverage = Some(Coverage::new());
+    }
+
+    /// Retorna a cobertura coletada até aqui, desativando a coleta
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+
+    /// Ativa o profiler por amostragem (`dryad run --profile`)
+    pub fn enable_profiling(&mut self, interval: std::time::Duration) {
+        self.heap.enable_allocation_sites();
+        self.profiler = Some(Profiler::start(interval));
+    }
+
+    /// Encerra o profiler e retorna as amostras e alocações coletadas
+    pub fn take_profile(&mut self) -> Option<Profile> {
+        let profiler = self.profiler.take()?;
+        let heap_sites = self.heap.take_allocation_sites().unwrap_or_default();
+        Some(profiler.finish(heap_sites))
+    }
+
     pub fn set_sandbox_root(&mut self, root: std::path::PathBuf) {
         self.native_registry.manager.set_sandbox_root(root);
     }
 
+    /// Ativa a verificação em runtime dos tipos anotados em parâmetros, retornos,
+    /// propriedades e `let` tipados; violações geram `DryadError::Type`
+    pub fn set_strict_types(&mut self, strict: bool) {
+        self.strict_types = strict;
+    }
+
     pub fn set_compile_mode(&mut self, compile: bool) {
         self.compile_mode = compile;
     }
@@ -105,6 +159,10 @@ impl Interpreter {
     }
 
     pub fn execute(&mut self, program: &Program) -> Result<String, DryadError> {
+        if let (Some(coverage), Some(file)) = (&mut self.coverage, &self.current_file_path) {
+            coverage.register_program(file, program);
+        }
+
         // Se modo bytecode estiver ativado, usar o compilador de bytecode
         if self.compile_mode {
             return self.execute_bytecode(program);
@@ -133,6 +191,25 @@ impl Interpreter {
         Ok(last_value.to_string())
     }
 
+    /// Executa o corpo de um bloco `test "nome" { ... }`. Usado pelo `dryad test`
+    /// depois que o restante do arquivo (imports, funções auxiliares) já foi executado.
+    pub fn execute_test(&mut self, name: &str, body: &Stmt) -> Result<(), DryadError> {
+        let location = match body {
+            Stmt::Block(_, loc) => SourceLocation {
+                file: self.current_file_path.clone(),
+                ..loc.clone()
+            },
+            _ => SourceLocation::unknown(),
+        };
+        self.current_stack_trace
+            .push_frame(StackFrame::new(format!("test \"{}\"", name), location));
+
+        let result = self.execute_statement(body);
+
+        self.current_stack_trace.frames.pop();
+        result.map(|_| ())
+    }
+
     fn check_visibility(&self, visibility: &Visibility, defining_class: &str) -> bool {
         match visibility {
             Visibility::Public => true,
@@ -208,6 +285,12 @@ impl Interpreter {
 
         // Executa na VM
         let mut vm = VM::new();
+        if self.coverage.is_some() {
+            vm.enable_coverage();
+        }
+        if let Some(profiler) = &self.profiler {
+            vm.enable_profiling(profiler.ticks());
+        }
 
         // Configura modo de debug se necessário
         if std::env::var("DRYAD_DEBUG_VM").is_ok() {
@@ -221,7 +304,42 @@ impl Interpreter {
             }
         }
 
-        match vm.interpret(chunk) {
+        let chunk_lines = chunk.source_lines();
+        let result = vm.interpret(chunk);
+
+        // Cobertura da VM: linhas executáveis vêm da tabela de linhas do chunk
+        if let (Some(coverage), Some(vm_coverage), Some(file)) = (
+            self.coverage.as_mut(),
+            vm.take_coverage(),
+            self.current_file_path.as_ref(),
+        ) {
+            coverage.register_lines(file, chunk_lines);
+            coverage.merge_file(
+                file,
+                &FileCoverage {
+                    lines: vm_coverage.lines,
+                    branches: vm_coverage.branches,
+                },
+            );
+        }
+
+        if let (Some(profiler), Some(vm_profile)) = (self.profiler.as_mut(), vm.take_profile()) {
+            // O chunk principal da VM se chama "script"; no interpretador é "<main>"
+            let main_name = |name: String| if name == "script" { "<main>".to_string() } else { name };
+            for (stack, count) in vm_profile.stacks {
+                profiler.add_stack(stack.into_iter().map(main_name).collect(), count);
+            }
+            for ((function, line), count) in vm_profile.allocations {
+                let site = AllocationSite {
+                    function: main_name(function),
+                    file: self.current_file_path.clone(),
+                    line,
+                };
+                profiler.add_allocations(site, count);
+            }
+        }
+
+        match result {
             BytecodeInterpretResult::Ok => {
                 // Retorna string vazia (ou valor do topo da pilha se houver)
                 Ok(String::new())
@@ -249,6 +367,9 @@ impl Interpreter {
             Value::Null => Ok(dryad_bytecode::Value::Nil),
             Value::Bool(b) => Ok(dryad_bytecode::Value::Boolean(*b)),
             Value::Number(n) => Ok(dryad_bytecode::Value::Number(*n)),
+            Value::Int(i) if i.unsigned_abs() <= numeric::MAX_SAFE_INTEGER as u64 => {
+                Ok(dryad_bytecode::Value::Number(*i as f64))
+            }
             Value::String(s) => Ok(dryad_bytecode::Value::String(s.clone())),
             Value::Array(arr) => {
                 // Converte array de runtime para array de bytecode
@@ -269,33 +390,105 @@ impl Interpreter {
     }
 
     fn poll_native_events(&mut self) {
-        let _ = crate::native_modules::http_server::process_pending_requests(
-            |server_id, method, path, lambda_id| {
-                let args = vec![
-                    Value::String(server_id),
-                    Value::String(method),
-                    Value::String(path),
-                ];
+        for request in crate::native_modules::http_server::take_pending_requests(&self.native_registry.manager) {
+            self.handle_http_request(request);
+        }
+        for event in crate::native_modules::tcp::take_pending_events(&self.native_registry.manager) {
+            let args = event.arguments(&mut self.heap);
+            if !self.run_socket_handler(event.handler(), args, &event.describe()) {
+                event.fail();
+            }
+        }
+        for message in crate::native_modules::udp::take_pending_messages(&self.native_registry.manager) {
+            let args = message.arguments(&mut self.heap);
+            self.run_socket_handler(message.handler(), args, &message.describe());
+        }
+    }
 
-                let location = SourceLocation::unknown();
+    /// Chama um handler de socket (TCP/UDP); erros são registrados e não
+    /// interrompem o script. Retorna se o handler terminou sem erro.
+    fn run_socket_handler(&mut self, handler: &Value, args: Vec<Value>, context: &str) -> bool {
+        let roots_len = self.iteration_roots.len();
+        self.iteration_roots.extend(args.iter().cloned());
+        let result = self.call_function_value(handler, args, &SourceLocation::unknown());
+        self.iteration_roots.truncate(roots_len);
 
-                if let Some(ManagedObject::Lambda {
-                    params,
-                    body,
-                    closure,
-                }) = self.heap.get(lambda_id).cloned()
-                {
-                    match self.call_lambda_values(params, body, closure, args, &location) {
-                        Ok(val) => Ok((200, val.to_string())),
-                        Err(e) => Err(crate::errors::RuntimeError::Generic(e.to_string())),
+        if let Err(e) = &result {
+            eprintln!("❌ Erro no handler {}: {}", context, e);
+        }
+        result.is_ok()
+    }
+
+    /// Atende uma requisição HTTP: monta `req`/`res`, executa os middlewares
+    /// em ordem (um `false` interrompe a cadeia) e depois o handler da rota.
+    /// Um generator como corpo é enviado em chunks conforme produz valores.
+    fn handle_http_request(&mut self, request: crate::native_modules::http_server::PendingRequest) {
+        use crate::native_modules::http_server::ResponseBody;
+
+        let location = SourceLocation::unknown();
+        let req = request.request_object(&mut self.heap);
+        let res = request.response_object(&mut self.heap);
+        let roots_len = self.iteration_roots.len();
+        self.iteration_roots.push(req.clone());
+        self.iteration_roots.push(res.clone());
+
+        let result = match self.run_http_pipeline(&request, &req, &res, &location) {
+            Ok((returned, halted)) => {
+                if let Some(value) = &returned {
+                    self.iteration_roots.push(value.clone());
+                }
+                request.respond(&res, returned, halted, &self.heap).map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+
+        match result {
+            Ok(ResponseBody::Sent) => {}
+            Ok(ResponseBody::Stream(generator)) => loop {
+                match self.resume_generator(generator, Value::Null) {
+                    Ok((_, true)) => {
+                        request.end();
+                        break;
                     }
-                } else {
-                    Err(crate::errors::RuntimeError::Generic(
-                        "Lambda not found in heap".to_string(),
-                    ))
+                    Ok((chunk, false)) => {
+                        if request.send_chunk(&chunk, &self.heap).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
                 }
             },
-        );
+            Err(message) => {
+                eprintln!("❌ Erro no handler HTTP {} {}: {}", request.request.method, request.request.path, message);
+                request.fail("Internal Server Error");
+            }
+        }
+
+        self.iteration_roots.truncate(roots_len);
+    }
+
+    /// Retorna o valor do handler (se houve um) e se um middleware parou a cadeia
+    fn run_http_pipeline(
+        &mut self,
+        request: &crate::native_modules::http_server::PendingRequest,
+        req: &Value,
+        res: &Value,
+        location: &SourceLocation,
+    ) -> Result<(Option<Value>, bool), DryadError> {
+        for middleware in request.middleware() {
+            let outcome = self.call_function_value(middleware, vec![req.clone(), res.clone()], location)?;
+            if matches!(outcome, Value::Bool(false)) {
+                return Ok((None, true));
+            }
+        }
+
+        match request.handler() {
+            Some(handler) => {
+                let returned = self.call_function_value(handler, vec![req.clone(), res.clone()], location)?;
+                Ok((Some(returned), false))
+            }
+            None => Ok((None, false)),
+        }
     }
 
     pub fn execute_and_return_value(&mut self, program: &Program) -> Result<Value, DryadError> {
@@ -359,6 +552,24 @@ impl Interpreter {
             }
         }
 
+        // 7. Iteráveis e geradores em uso por um for-in ou next()
+        for val in &self.iteration_roots {
+            self.collect_value_roots(val, &mut roots);
+        }
+
+        // 8. Handlers e middlewares registrados no servidor HTTP
+        for val in crate::native_modules::http_server::registered_handlers(&self.native_registry.manager) {
+            self.collect_value_roots(&val, &mut roots);
+        }
+
+        // 9. Handlers de servidores TCP/UDP (registrados e na fila de eventos)
+        for val in crate::native_modules::tcp::registered_handlers(&self.native_registry.manager)
+            .into_iter()
+            .chain(crate::native_modules::udp::registered_handlers(&self.native_registry.manager))
+        {
+            self.collect_value_roots(&val, &mut roots);
+        }
+
         roots
     }
 
@@ -369,7 +580,12 @@ impl Interpreter {
             | Value::Lambda(id)
             | Value::Class(id)
             | Value::Instance(id)
-            | Value::Object(id) => {
+            | Value::Object(id)
+            | Value::Enum(id)
+            | Value::Variant(id)
+            | Value::Generator(id)
+            | Value::Map(id)
+            | Value::Set(id) => {
                 roots.push(*id);
             }
             Value::Promise {
@@ -385,6 +601,21 @@ impl Interpreter {
         self.debug_state = Some(state);
     }
 
+    fn coverage_file(&self, location: &SourceLocation) -> Option<PathBuf> {
+        location.file.clone().or_else(|| self.current_file_path.clone())
+    }
+
+    fn record_branch(&mut self, location: &SourceLocation, condition: bool) {
+        if self.coverage.is_none() {
+            return;
+        }
+        if let Some(file) = self.coverage_file(location) {
+            if let Some(coverage) = self.coverage.as_mut() {
+                coverage.hit_branch(&file, location.line, condition);
+            }
+        }
+    }
+
     fn check_debug_hooks(&mut self, location: &SourceLocation) -> Result<(), DryadError> {
         let debug_state_arc = if let Some(state) = &self.debug_state {
             state.clone()
@@ -471,20 +702,22 @@ impl Interpreter {
     }
 
     pub fn get_debug_variables(&self) -> HashMap<String, String> {
+        let options = InspectOptions::default();
         let mut vars = HashMap::new();
         for (name, val) in &self.env.variables {
-            let name_str: String = name.clone();
-            let val_str: String = val.to_string();
-            vars.insert(name_str, val_str);
+            vars.insert(name.clone(), inspect::inspect(val, &self.heap, &options));
         }
         for (name, val) in &self.env.constants {
-            let name_str: String = name.clone();
-            let val_str: String = val.to_string();
-            vars.insert(name_str, val_str);
+            vars.insert(name.clone(), inspect::inspect(val, &self.heap, &options));
         }
         vars
     }
 
+    /// Formata um valor seguindo as referências do heap (arrays, objetos, instâncias...).
+    pub fn inspect_value(&self, value: &Value, options: &InspectOptions) -> String {
+        inspect::inspect(value, &self.heap, options)
+    }
+
     pub fn get_debug_heap(&self) -> Vec<String> {
         // Implementação simplificada para o debugger
         vec![format!("Heap size: {} objects", self.heap.heap_size())]
@@ -536,19 +769,45 @@ impl Interpreter {
             Stmt::Throw(_, loc) => loc,
             Stmt::FunctionDeclaration { location, .. } => location,
             Stmt::ThreadFunctionDeclaration { location, .. } => location,
-            Stmt::ClassDeclaration(_, _, _, _, loc) => loc,
-            Stmt::InterfaceDeclaration(_, _, loc) => loc,
+            Stmt::ClassDeclaration(_, _, _, _, _, _, loc) => loc,
+            Stmt::InterfaceDeclaration(_, _, _, loc) => loc,
+            Stmt::EnumDeclaration(_, _, loc) => loc,
+            Stmt::TypeAlias(_, _, _, loc) => loc,
             Stmt::Return(_, loc) => loc,
             Stmt::NativeDirective(_, loc) => loc,
             Stmt::Export(_, loc) => loc,
             Stmt::Use(_, loc) => loc,
             Stmt::Import(_, _, loc) => loc,
             Stmt::Namespace(_, _, loc) => loc,
+            Stmt::Test(_, _, _, loc) => loc,
         };
 
         // Hook de depuração
         self.check_debug_hooks(location)?;
 
+        // Cobertura: blocos e testes só agrupam statements, não contam como linha
+        if self.coverage.is_some() && !matches!(stmt, Stmt::Block(..) | Stmt::Test(..)) {
+            if let Some(file) = self.coverage_file(location) {
+                if let Some(coverage) = self.coverage.as_mut() {
+                    coverage.hit_line(&file, location.line);
+                }
+            }
+        }
+
+        // Profiler: amostra a pilha e atribui as alocações deste statement
+        let previous_site = match self.profiler.as_mut() {
+            Some(profiler) => {
+                profiler.sample(&self.current_stack_trace.frames);
+                let site = profiler.site(
+                    &self.current_stack_trace.frames,
+                    location,
+                    self.current_file_path.as_ref(),
+                );
+                Some(self.heap.set_allocation_site(Some(site)))
+            }
+            None => None,
+        };
+
         // Poll for native events (like HTTP requests)
         self.poll_native_events();
 
@@ -580,15 +839,27 @@ impl Interpreter {
                 }
             }
             Stmt::Expression(expr, _) => self.evaluate(expr),
-            Stmt::VarDeclaration(pattern, _, initializer, _) => {
+            Stmt::VarDeclaration(pattern, declared_type, initializer, _) => {
                 let value = match initializer {
                     Some(expr) => self.evaluate(expr)?,
                     None => Value::Null,
                 };
+                if initializer.is_some() {
+                    self.check_declared_type(
+                        &value,
+                        declared_type.as_ref(),
+                        || format!("Variável '{}'", pattern.identifier_name().map(String::as_str).unwrap_or("_")),
+                    )?;
+                }
 
                 let mut bindings = HashMap::new();
                 if self.match_pattern(&value, pattern, &mut bindings) {
                     for (name, val) in bindings {
+                        let declared = match pattern {
+                            Pattern::Identifier(_) => declared_type.as_ref(),
+                            _ => None,
+                        };
+                        self.declare_variable_type(&name, declared);
                         self.env.variables.insert(name, val);
                     }
                     Ok(Value::Null)
@@ -596,8 +867,13 @@ impl Interpreter {
                     Err(self.runtime_error(3035, "Padrão de desestruturação não corresponde ao valor na declaração de variável"))
                 }
             }
-            Stmt::ConstDeclaration(pattern, _, expr, _) => {
+            Stmt::ConstDeclaration(pattern, declared_type, expr, _) => {
                 let value = self.evaluate(expr)?;
+                self.check_declared_type(
+                    &value,
+                    declared_type.as_ref(),
+                    || format!("Constante '{}'", pattern.identifier_name().map(String::as_str).unwrap_or("_")),
+                )?;
                 let mut bindings = HashMap::new();
 
                 if self.match_pattern(&value, pattern, &mut bindings) {
@@ -638,6 +914,11 @@ impl Interpreter {
                                 &format!("Variável '{}' não foi declarada", name),
                             ));
                         }
+                        self.check_declared_type(
+                            &val,
+                            self.env.variable_types.get(&name),
+                            || format!("Variável '{}'", name),
+                        )?;
 
                         self.env.variables.insert(name, val);
                     }
@@ -650,175 +931,7 @@ impl Interpreter {
                 }
             }
             Stmt::PropertyAssignment(object_expr, property_name, value_expr, _) => {
-                let value = self.evaluate(value_expr)?;
-                let object = self.evaluate(object_expr)?;
-
-                match object {
-                    Value::Instance(instance_id) => {
-                        // Get class info to check for setter
-                        let class_name = {
-                            let heap_obj = self.heap.get(instance_id).ok_or_else(|| {
-                                DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Instance reference not found", SourceLocation::unknown())
-                            })?;
-                            if let ManagedObject::Instance { class_name, .. } = heap_obj {
-                                class_name.clone()
-                            } else {
-                                return Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Instance", SourceLocation::unknown()));
-                            }
-                        };
-
-                        // Look up setter in class (clone it out of the heap so we don't hold a borrow)
-                        let setter_clone_opt = if let Some(Value::Class(cid)) = self.env.classes.get(&class_name) {
-                            if let Some(ManagedObject::Class { setters, .. }) = self.heap.get(*cid) {
-                                setters.get(property_name).cloned()
-                            } else { None }
-                        } else { None };
-
-                        if let Some(setter) = setter_clone_opt {
-                            // Visibility check
-                            if !self.check_visibility(&setter.visibility, &class_name) {
-                                return Err(DryadError::from_catalog_fmt(
-                                    error_catalog::e3029(),
-                                    &format!("Setter '{}' não é acessível (visibilidade: {:?})", property_name, setter.visibility),
-                                    SourceLocation::unknown(),
-                                    ));
-                            }
-
-                            // Execute setter with 'this' and parameter (no heap borrow held)
-                            let mut setter_env = self.env.clone();
-                            let saved_class = self.env.current_class.clone();
-                            setter_env.variables.insert("this".to_string(), object.clone());
-                            setter_env.current_class = Some(class_name.to_string());
-                            setter_env.variables.insert(setter.param.clone(), value.clone());
-                            let prev_env = std::mem::replace(&mut self.env, setter_env);
-                            let _ = self.execute_statement(&setter.body);
-                            self.env = prev_env;
-                            self.env.current_class = saved_class;
-                            return Ok(value);
-                        }
-
-                        // No setter found — perform visibility checks first (no mutable heap borrow yet)
-                        if let Some(Value::Class(cid)) = self.env.classes.get(&class_name) {
-                            if let Some(ManagedObject::Class { properties: class_props, .. }) = self.heap.get(*cid) {
-                                if let Some(prop_def) = class_props.get(property_name) {
-                                    if !self.check_visibility(&prop_def.visibility, &class_name) {
-                                        return Err(DryadError::from_catalog_fmt(
-                                            error_catalog::e3029(),
-                                            &format!("Propriedade '{}' não é acessível para escrita (visibilidade: {:?})", property_name, prop_def.visibility),
-                                            SourceLocation::unknown(),
-                                            ));
-                                    }
-                                }
-                            }
-                        }
-
-                        // Now acquire mutable access to the instance and set property
-                        let heap_obj = self.heap.get_mut(instance_id).ok_or_else(|| {
-                            DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Instance reference not found", SourceLocation::unknown())
-                        })?;
-                        
-                        if let ManagedObject::Instance { properties, .. } = heap_obj {
-                            properties.insert(property_name.clone(), value.clone());
-                            Ok(value)
-                        } else {
-                            Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Instance", SourceLocation::unknown()))
-                        }
-                    }
-                    Value::Object(id) => {
-                        let heap_obj = self.heap.get_mut(id).ok_or_else(|| {
-                            DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Object reference not found", SourceLocation::unknown())
-                        })?;
-                        
-                        if let ManagedObject::Object { properties, .. } = heap_obj {
-                            properties.insert(property_name.clone(), value.clone());
-                            Ok(value)
-                        } else {
-                            Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Object", SourceLocation::unknown()))
-                        }
-                    }
-                    Value::Class(id) => {
-                        // Clone required data first to avoid holding mutable borrows while executing setters
-                        let (class_name_clone, setter_clone_opt, prop_def_opt) = if let Some(ManagedObject::Class { name, properties, setters, .. }) = self.heap.get(id) {
-                            (
-                                name.clone(),
-                                setters.get(property_name).cloned(),
-                                properties.get(property_name).cloned(),
-                            )
-                        } else {
-                            return Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Class", SourceLocation::unknown()));
-                        };
-
-                        // If there's a static setter, execute it (we have a cloned copy)
-                        if let Some(setter) = setter_clone_opt {
-                            if !setter.is_static {
-                                return Err(DryadError::from_catalog_fmt(
-                                    error_catalog::e3029(),
-                                    &format!("Setter '{}' não é estático", property_name),
-                                    SourceLocation::unknown(),
-                                    ));
-                            }
-
-                            if !self.check_visibility(&setter.visibility, &class_name_clone) {
-                                return Err(DryadError::from_catalog_fmt(
-                                    error_catalog::e3029(),
-                                    &format!("Setter '{}' não é acessível (visibilidade: {:?})", property_name, setter.visibility),
-                                    SourceLocation::unknown(),
-                                    ));
-                            }
-
-                            // Execute setter with 'current_class' set
-                            let mut setter_env = self.env.clone();
-                            let saved_class = self.env.current_class.clone();
-                            setter_env.current_class = Some(class_name_clone.clone());
-                            setter_env.variables.insert(setter.param.clone(), value.clone());
-                            let prev_env = std::mem::replace(&mut self.env, setter_env);
-                            let _ = self.execute_statement(&setter.body);
-                            self.env = prev_env;
-                            self.env.current_class = saved_class;
-                            return Ok(value);
-                        }
-
-                        // If static property exists, validate visibility and update it with a fresh mutable borrow
-                        if let Some(prop_def) = prop_def_opt {
-                            if !prop_def.is_static {
-                                return Err(DryadError::from_catalog_fmt(
-                                    error_catalog::e3029(),
-                                    &format!("Propriedade '{}' não é estática na classe '{}'", property_name, class_name_clone),
-                                    SourceLocation::unknown(),
-                                    ));
-                            }
-
-                            if !self.check_visibility(&prop_def.visibility, &class_name_clone) {
-                                return Err(DryadError::from_catalog_fmt(
-                                    error_catalog::e3029(),
-                                    &format!("Propriedade estática '{}' não é acessível para escrita (visibilidade: {:?})", property_name, prop_def.visibility),
-                                    SourceLocation::unknown(),
-                                    ));
-                            }
-
-                            // Mutate the class property with a mutable borrow
-                            let heap_obj_mut = self.heap.get_mut(id).ok_or_else(|| {
-                                DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Class reference not found", SourceLocation::unknown())
-                            })?;
-
-                            if let ManagedObject::Class { properties, .. } = heap_obj_mut {
-                                if let Some(prop) = properties.get_mut(property_name) {
-                                    prop.default_value = Some(value.clone());
-                                    return Ok(value);
-                                }
-                            }
-
-                            return Err(DryadError::from_catalog_fmt(
-                                error_catalog::e3030(),
-                                &format!("Propriedade estática '{}' não encontrada na classe '{}'", property_name, class_name_clone),
-                                SourceLocation::unknown(),
-                                ));
-                        }
-
-                        Err(DryadError::from_catalog_fmt(error_catalog::e3030(), &format!("Propriedade estática '{}' não encontrada na classe '{}'", property_name, class_name_clone), SourceLocation::unknown()))
-                    }
-                    _ => Err(self.runtime_error(3034, "Tentativa de atribuir propriedade a valor que não é uma instância, classe ou objeto"))
-                }
+                self.execute_property_assignment(object_expr, property_name, value_expr)
             }
             Stmt::IndexAssignment(array_expr, index_expr, value_expr, _) => {
                 let value = self.evaluate(value_expr)?;
@@ -829,28 +942,34 @@ impl Interpreter {
                 Ok(result)
             }
             Stmt::Block(statements, _) => self.execute_block(statements),
-            Stmt::If(condition, then_stmt, _) => {
+            Stmt::If(condition, then_stmt, location) => {
                 let condition_value = self.evaluate(condition)?;
-                if self.is_truthy(&condition_value) {
+                let taken = self.is_truthy(&condition_value);
+                self.record_branch(location, taken);
+                if taken {
                     self.execute_statement(then_stmt)
                 } else {
                     Ok(Value::Null)
                 }
             }
-            Stmt::IfElse(condition, then_stmt, else_stmt, _) => {
+            Stmt::IfElse(condition, then_stmt, else_stmt, location) => {
                 let condition_value = self.evaluate(condition)?;
-                if self.is_truthy(&condition_value) {
+                let taken = self.is_truthy(&condition_value);
+                self.record_branch(location, taken);
+                if taken {
                     self.execute_statement(then_stmt)
                 } else {
                     self.execute_statement(else_stmt)
                 }
             }
-            Stmt::While(condition, body, _) => {
+            Stmt::While(condition, body, location) => {
                 let mut last_value = Value::Null;
 
                 loop {
                     let condition_value = self.evaluate(condition)?;
-                    if !self.is_truthy(&condition_value) {
+                    let taken = self.is_truthy(&condition_value);
+                    self.record_branch(location, taken);
+                    if !taken {
                         break;
                     }
 
@@ -874,7 +993,7 @@ impl Interpreter {
 
                 Ok(last_value)
             }
-            Stmt::DoWhile(body, condition, _) => {
+            Stmt::DoWhile(body, condition, location) => {
                 let mut last_value = Value::Null;
 
                 // Do-while executa o corpo pelo menos uma vez
@@ -897,7 +1016,9 @@ impl Interpreter {
 
                     // Avalia a condição após executar o corpo
                     let condition_value = self.evaluate(condition)?;
-                    if !self.is_truthy(&condition_value) {
+                    let taken = self.is_truthy(&condition_value);
+                    self.record_branch(location, taken);
+                    if !taken {
                         break;
                     }
                 }
@@ -914,8 +1035,8 @@ impl Interpreter {
                 "continue",
                 SourceLocation::unknown(),
             )),
-            Stmt::For(init, condition, update, body, _) => {
-                self.execute_for_loop(init, condition, update, body)
+            Stmt::For(init, condition, update, body, location) => {
+                self.execute_for_loop(init, condition, update, body, location)
             }
             Stmt::ForEach(pattern, iterable, body, _) => {
                 self.execute_foreach_loop(pattern, iterable, body)
@@ -941,15 +1062,25 @@ impl Interpreter {
                 name,
                 params,
                 rest_param,
+                return_type,
                 body,
                 is_async,
+                is_generator,
                 ..
             } => {
                 let params_vec: Vec<(String, Option<Expr>)> = params
                     .iter()
                     .map(|(p, _, d)| (p.clone(), d.clone()))
                     .collect();
-                if *is_async {
+                if *is_generator {
+                    let generator_function = Value::GeneratorFunction {
+                        name: name.clone(),
+                        params: params_vec,
+                        rest_param: rest_param.clone(),
+                        body: (**body).clone(),
+                    };
+                    self.env.variables.insert(name.clone(), generator_function);
+                } else if *is_async {
                     let async_function = Value::AsyncFunction {
                         name: name.clone(),
                         params: params_vec,
@@ -963,6 +1094,7 @@ impl Interpreter {
                         params: params_vec,
                         rest_param: rest_param.clone(),
                         body: (**body).clone(),
+                        signature: Signature::new(params, return_type),
                     };
                     self.env.variables.insert(name.clone(), function);
                 }
@@ -983,139 +1115,36 @@ impl Interpreter {
                 self.env.variables.insert(name.clone(), thread_function);
                 Ok(Value::Null)
             }
-            Stmt::ClassDeclaration(name, parent, interfaces, members, _) => {
-                let mut methods = HashMap::new();
-                let mut properties = HashMap::new();
-                let mut getters = HashMap::new();
-                let mut setters = HashMap::new();
-
-                // Process class members
-                for member in members {
-                    match member {
-                        ClassMember::Method {
-                            visibility,
-                            is_static,
-                            is_async: _,
-                            name: method_name,
-                            params,
-                            body,
-                            ..
-                        } => {
-                            let params_vec: Vec<(String, Option<Expr>)> = params
-                                .iter()
-                                .map(|(p, _, d)| (p.clone(), d.clone()))
-                                .collect();
-                            let method = ClassMethod {
-                                visibility: visibility.clone(),
-                                is_static: *is_static,
-                                params: params_vec,
-                                body: *(*body).clone(),
-                            };
-                            methods.insert(method_name.clone(), method);
-                        }
-                        ClassMember::Property(
-                            visibility,
-                            is_static,
-                            prop_name,
-                            _,
-                            default_value,
-                        ) => {
-                            let default_val = match default_value {
-                                Some(expr) => Some(self.evaluate(&expr)?),
-                                None => None,
-                            };
-                            let property = ClassProperty {
-                                visibility: visibility.clone(),
-                                is_static: *is_static,
-                                default_value: default_val,
-                            };
-                            properties.insert(prop_name.clone(), property);
-                        }
-                        ClassMember::Getter {
-                            visibility,
-                            is_static,
-                            name: getter_name,
-                            body,
-                        } => {
-                            let getter = ClassGetter {
-                                visibility: visibility.clone(),
-                                is_static: *is_static,
-                                name: getter_name.clone(),
-                                body: *(*body).clone(),
-                            };
-                            getters.insert(getter_name.clone(), getter);
-                        }
-                        ClassMember::Setter {
-                            visibility,
-                            is_static,
-                            name: setter_name,
-                            param,
-                            body,
-                        } => {
-                            let setter = ClassSetter {
-                                visibility: visibility.clone(),
-                                is_static: *is_static,
-                                name: setter_name.clone(),
-                                param: param.clone(),
-                                body: *(*body).clone(),
-                            };
-                            setters.insert(setter_name.clone(), setter);
-                        }
-                    }
-                }
-
-                // Verify interfaces are implemented
-                for interface_name in interfaces {
-                    if !self.env.interfaces.contains_key(interface_name) {
-                        return Err(DryadError::from_catalog_fmt(
-                            error_catalog::e3102(),
-                            &format!("Interface '{}' não encontrada", interface_name),
-                            SourceLocation::unknown(),
-                            ));
-                    }
-
-                    // Verify class implements all interface methods
-                    let interface = self.env.interfaces.get(interface_name).unwrap();
-                    for interface_method in interface {
-                        let method_name = match interface_method {
-                            InterfaceMember::Method(m) => &m.name,
-                        };
-
-                        if !methods.contains_key(method_name) {
-                            return Err(DryadError::from_catalog_fmt(
-                                error_catalog::e3103(),
-                                &format!(
-                                    "Classe '{}' deve implementar o método '{}' da interface '{}'",
-                                    name, method_name, interface_name
-                                ),
-                                SourceLocation::unknown(),
-                                ));
-                        }
-                    }
-                }
-
-                let managed_class = ManagedObject::Class {
-                    name: name.clone(),
-                    parent: parent.clone(),
-                    interfaces: interfaces.clone(),
-                    methods,
-                    properties,
-                    getters,
-                    setters,
-                };
-                let class_id = self.heap.allocate(managed_class);
-                self.maybe_collect_garbage();
-                let class = Value::Class(class_id);
-
-                self.env.classes.insert(name.clone(), class.clone());
-                self.env.variables.insert(name.clone(), class); // Também disponível como variável
-                Ok(Value::Null)
+            Stmt::ClassDeclaration(name, _, parent, interfaces, members, is_abstract, location) => {
+                self.execute_class_declaration(name, parent, interfaces, members, *is_abstract, location)
             }
-            Stmt::InterfaceDeclaration(name, members, _) => {
+            Stmt::InterfaceDeclaration(name, _, members, _) => {
                 // Register interface in environment
                 self.env.interfaces.insert(name.clone(), members.clone());
                 Ok(Value::Null)
             }
+            // Aliases de tipo só existem para o checker
+            Stmt::TypeAlias(name, _, aliased, _) => {
+                // Aliases só importam para `--strict-types`; parâmetros de tipo ficam
+                // como nomes desconhecidos e aceitam qualquer valor
+                self.type_aliases.insert(name.clone(), aliased.clone());
+                Ok(Value::Null)
+            }
+            Stmt::EnumDeclaration(name, variants, _) => {
+                let variants = variants
+                    .iter()
+                    .map(|v| {
+                        let fields = v.fields.iter().map(|(field, _)| field.clone()).collect();
+                        (v.name.clone(), fields)
+                    })
+                    .collect();
+                let enum_id = self.heap.allocate(ManagedObject::Enum {
+                    name: name.clone(),
+                    variants,
+                });
+                self.env.variables.insert(name.clone(), Value::Enum(enum_id));
+                Ok(Value::Null)
+            }
             Stmt::Return(expr, _) => {
                 let value = match expr {
                     Some(e) => self.evaluate(e)?,
@@ -1141,6 +1170,8 @@ impl Interpreter {
                 // Importa o módulo com diferentes estratégias
                 self.import_module_with_kind(kind, module_path)
             }
+            // Blocos de teste só são executados pelo `dryad test` (via `execute_test`)
+            Stmt::Test(..) => Ok(Value::Null),
             Stmt::Namespace(name, statements, _) => {
                 // Salva estado atual
                 self.env.call_stack_vars.push(self.env.variables.clone());
@@ -1177,6 +1208,9 @@ impl Interpreter {
         };
 
         self.call_depth -= 1;
+        if let Some(site) = previous_site {
+            self.heap.set_allocation_site(site);
+        }
         result
     }
 
@@ -1195,7 +1229,7 @@ impl Interpreter {
             Expr::Tuple(elements, _) => self.eval_tuple(elements),
             Expr::Index(array_expr, index_expr, _) => self.eval_index(array_expr, index_expr),
             Expr::TupleAccess(tuple_expr, index, _) => self.eval_tuple_access(tuple_expr, *index),
-            Expr::Lambda { params, body, .. } => {
+            Expr::Lambda { params, body, return_type, .. } => {
                 let params_vec: Vec<(String, Option<Expr>)> = params
                     .iter()
                     .map(|(p, _, d)| (p.clone(), d.clone()))
@@ -1204,6 +1238,7 @@ impl Interpreter {
                     params: params_vec,
                     body: *body.clone(),
                     closure: self.env.variables.clone(), // Captura o escopo atual
+                    signature: Signature::new(params, return_type),
                 };
                 let lambda_id = self.heap.allocate(managed_lambda);
                 self.maybe_collect_garbage();
@@ -1244,6 +1279,19 @@ impl Interpreter {
             Expr::Await(expr, _) => self.eval_await(expr),
             Expr::ThreadCall(func_expr, args, _) => self.eval_thread_call(func_expr, args),
             Expr::MutexCreation(_) => self.eval_mutex_creation(),
+            Expr::Range(start, end, inclusive, _) => self.eval_range(start, end, *inclusive),
+            Expr::OptionalChain(inner, _) => Ok(self.eval_chain(inner)?.unwrap_or(Value::Null)),
+            Expr::OptionalPropertyAccess(..) | Expr::OptionalMethodCall(..) | Expr::OptionalIndex(..) => {
+                Ok(self.eval_chain(expr)?.unwrap_or(Value::Null))
+            }
+            Expr::NullishCoalescing(left, right, _) => match self.evaluate(left)? {
+                Value::Null => self.evaluate(right),
+                value => Ok(value),
+            },
+            Expr::Yield(..) => Err(self.runtime_error(
+                3041,
+                "yield só pode aparecer como statement (`yield v;`), em `let x = yield v;` ou em `x = yield v;` dentro de uma function*",
+            )),
             Expr::Try(expr, _) => {
                 let result = self.evaluate(expr)?;
                 match result {
@@ -1265,15 +1313,95 @@ impl Interpreter {
         }
     }
 
+    /// Avalia uma cadeia de acessos com `?.`. `None` indica que um `?.` encontrou
+    /// null e o restante da cadeia foi ignorado.
+    fn eval_chain(&mut self, expr: &Expr) -> Result<Option<Value>, DryadError> {
+        match expr {
+            Expr::OptionalPropertyAccess(object_expr, property_name, _)
+            | Expr::PropertyAccess(object_expr, property_name, _) => {
+                let optional = matches!(expr, Expr::OptionalPropertyAccess(..));
+                match self.eval_chain(object_expr)? {
+                    None => Ok(None),
+                    Some(Value::Null) if optional => Ok(None),
+                    Some(object) => self.property_of(object, property_name).map(Some),
+                }
+            }
+            Expr::OptionalMethodCall(object_expr, method_name, args, location)
+            | Expr::MethodCall(object_expr, method_name, args, location) => {
+                let optional = matches!(expr, Expr::OptionalMethodCall(..));
+                match self.eval_chain(object_expr)? {
+                    None => Ok(None),
+                    Some(Value::Null) if optional => Ok(None),
+                    Some(object) => self
+                        .eval_method_call_on(object, method_name, args, location)
+                        .map(Some),
+                }
+            }
+            Expr::OptionalIndex(array_expr, index_expr, _) | Expr::Index(array_expr, index_expr, _) => {
+                let optional = matches!(expr, Expr::OptionalIndex(..));
+                match self.eval_chain(array_expr)? {
+                    None => Ok(None),
+                    Some(Value::Null) if optional => Ok(None),
+                    Some(array_value) => {
+                        let index_value = self.evaluate(index_expr)?;
+                        self.index_value(array_value, index_value).map(Some)
+                    }
+                }
+            }
+            _ => self.evaluate(expr).map(Some),
+        }
+    }
+
     fn eval_literal(&self, literal: &Literal) -> Result<Value, DryadError> {
         match literal {
             Literal::Number(n) => Ok(Value::Number(*n)),
+            Literal::Int(n) => Ok(Value::Int(*n)),
+            Literal::BigInt(digits) => numeric::to_big(&Value::String(digits.clone()))
+                .map_err(|e| self.numeric_error(e)),
+            Literal::Decimal(text) => numeric::to_dec(&Value::String(text.clone()))
+                .map_err(|e| self.numeric_error(e)),
             Literal::String(s) => Ok(Value::String(s.clone())),
             Literal::Bool(b) => Ok(Value::Bool(*b)),
             Literal::Null => Ok(Value::Null),
+            Literal::Regex(source, flags) => Regexp::compile(source, flags)
+                .map(|regexp| Value::Regex(Box::new(regexp)))
+                .map_err(|e| self.runtime_error(3052, &e)),
         }
     }
 
+    /// Converte falhas de aritmética exata (overflow, divisão por zero) em erros de runtime
+    fn numeric_error(&self, error: NumericError) -> DryadError {
+        let code = match error {
+            NumericError::Overflow(_) => 3045,
+            NumericError::DivisionByZero => 3007,
+            NumericError::Invalid(_) => 3046,
+        };
+        self.runtime_error(code, &error.to_string())
+    }
+
+    /// `int()`, `bigint()`, `decimal()` e `float()`: conversões entre tipos numéricos,
+    /// disponíveis sem diretiva quando o nome não foi definido pelo programa
+    fn call_numeric_builtin(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+    ) -> Option<Result<Value, DryadError>> {
+        let convert: fn(&Value) -> Result<Value, NumericError> = match name {
+            "int" => numeric::to_int,
+            "bigint" => numeric::to_big,
+            "decimal" => numeric::to_dec,
+            "float" => numeric::to_float,
+            _ => return None,
+        };
+        Some(self.evaluate_arguments(args).and_then(|values| match values.as_slice() {
+            [value] => convert(value).map_err(|e| self.numeric_error(e)),
+            _ => Err(self.runtime_error(
+                3046,
+                &format!("{}() espera exatamente 1 argumento", name),
+            )),
+        }))
+    }
+
     fn eval_variable(&self, name: &str) -> Result<Value, DryadError> {
         // Primeiro verifica nas constantes
         if let Some(value) = self.env.constants.get(name) {
@@ -1295,13 +1423,437 @@ impl Interpreter {
         right: &Expr,
     ) -> Result<Value, DryadError> {
         let left_val = self.evaluate(left)?;
+        // Interfaces não são valores: `x instanceof Forma` consulta as interfaces declaradas
+        if operator == "instanceof" {
+            if let Expr::Variable(name, _) = right {
+                if self.env.interfaces.contains_key(name) && !self.env.variables.contains_key(name) {
+                    return Ok(Value::Bool(self.instance_implements(&left_val, name)));
+                }
+            }
+        }
         let right_val = self.evaluate(right)?;
+        if operator == "instanceof" {
+            return self.instance_of(&left_val, &right_val);
+        }
+        if let Some(result) = self.overloaded_binary(operator, &left_val, &right_val)? {
+            return Ok(result);
+        }
+        self.binary_values(operator, left_val, right_val)
+    }
 
-        match operator {
-            "+" => self.add_values(left_val, right_val),
-            "-" => self.subtract_values(left_val, right_val),
-            "*" => self.multiply_values(left_val, right_val),
-            "/" => self.divide_values(left_val, right_val),
+    /// `valor instanceof Classe`: verdadeiro para instâncias da classe ou de subclasses
+    fn instance_of(&self, value: &Value, class: &Value) -> Result<Value, DryadError> {
+        let class_name = match class {
+            Value::Class(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Class { name, .. }) => name,
+                _ => return Err(DryadError::from_catalog(error_catalog::e3048(), SourceLocation::unknown())),
+            },
+            _ => return Err(DryadError::from_catalog(error_catalog::e3048(), SourceLocation::unknown())),
+        };
+        let result = match value {
+            Value::Instance(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Instance { class_name: instance_class, .. }) => {
+                    self.is_subclass_of(instance_class, class_name)
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+        Ok(Value::Bool(result))
+    }
+
+    /// `valor instanceof Interface`: a classe da instância (ou uma superclasse) a implementa
+    fn instance_implements(&self, value: &Value, interface_name: &str) -> bool {
+        match value {
+            Value::Instance(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Instance { class_name, .. }) => {
+                    self.implements_interface(class_name, interface_name)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Com `--strict-types`, verifica o valor contra o tipo anotado; `context`
+    /// descreve a fronteira ("Parâmetro 'x' de 'f'", "Variável 'y'", ...) e só
+    /// é montado quando a verificação roda
+    #[inline(always)]
+    fn check_declared_type<F: FnOnce() -> String>(
+        &self,
+        value: &Value,
+        declared: Option<&Type>,
+        context: F,
+    ) -> Result<(), DryadError> {
+        match declared {
+            Some(expected) if self.strict_types => self.check_value_type(value, expected, context),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fora de linha para não aumentar o frame de `execute_statement` e
+    /// `evaluate`, que limita a profundidade de recursão dos scripts
+    #[inline(never)]
+    fn check_value_type<F: FnOnce() -> String>(
+        &self,
+        value: &Value,
+        expected: &Type,
+        context: F,
+    ) -> Result<(), DryadError> {
+        if self.value_matches_type(value, expected) {
+            return Ok(());
+        }
+        let context = context();
+        let found = self.runtime_type_name(value);
+        let def = error_catalog::e4004();
+        Err(DryadError::type_error(
+            def.code,
+            &format!("{}: esperado {}, encontrado {}", context, expected, found),
+            self.current_stack_trace
+                .frames
+                .last()
+                .map(|frame| frame.location.clone())
+                .unwrap_or_else(SourceLocation::unknown),
+            expected.to_string(),
+            found,
+        ))
+    }
+
+    /// Liga um parâmetro no escopo atual, verificando o tipo declarado
+    fn bind_param(
+        &mut self,
+        name: &str,
+        value: Value,
+        declared: Option<&Type>,
+        function_name: &str,
+    ) -> Result<(), DryadError> {
+        self.check_declared_type(
+            &value,
+            declared,
+            || format!("Parâmetro '{}' de '{}'", name, function_name),
+        )?;
+        self.declare_variable_type(name, declared);
+        self.env.variables.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    fn check_return_type(
+        &self,
+        value: Value,
+        signature: &Signature,
+        function_name: &str,
+    ) -> Result<Value, DryadError> {
+        self.check_declared_type(
+            &value,
+            signature.return_type.as_ref(),
+            || format!("Retorno de '{}'", function_name),
+        )?;
+        Ok(value)
+    }
+
+    /// Registra o tipo declarado de uma variável para as atribuições seguintes;
+    /// uma redeclaração sem anotação descarta o tipo anterior
+    fn declare_variable_type(&mut self, name: &str, declared: Option<&Type>) {
+        if !self.strict_types {
+            return;
+        }
+        match declared {
+            Some(t) => {
+                self.env.variable_types.insert(name.to_string(), t.clone());
+            }
+            None => {
+                self.env.variable_types.remove(name);
+            }
+        }
+    }
+
+    /// Tipo do valor como aparece nas mensagens: o nome da classe para
+    /// instâncias e do enum para variantes
+    fn runtime_type_name(&self, value: &Value) -> String {
+        match value {
+            Value::Instance(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Instance { class_name, .. }) => class_name.clone(),
+                _ => value.type_name().to_string(),
+            },
+            Value::Variant(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Variant { enum_name, .. }) => enum_name.clone(),
+                _ => value.type_name().to_string(),
+            },
+            _ => value.type_name().to_string(),
+        }
+    }
+
+    fn value_matches_type(&self, value: &Value, expected: &Type) -> bool {
+        // Literais numéricos sem sufixo são `number`; aceitos onde o valor é exato
+        let integral = |n: f64| n.fract() == 0.0 && n.abs() < 9.007_199_254_740_992e15;
+        match expected {
+            Type::Any => true,
+            Type::Number => matches!(value, Value::Number(_)),
+            Type::Int => match value {
+                Value::Int(_) => true,
+                Value::Number(n) => integral(*n),
+                _ => false,
+            },
+            Type::BigInt => match value {
+                Value::BigInt(_) | Value::Int(_) => true,
+                Value::Number(n) => integral(*n),
+                _ => false,
+            },
+            Type::Decimal => matches!(value, Value::Decimal(_) | Value::Int(_) | Value::Number(_)),
+            Type::String => matches!(value, Value::String(_)),
+            Type::Bool => matches!(value, Value::Bool(_)),
+            Type::Null => matches!(value, Value::Null),
+            Type::Array(element) => match value {
+                Value::Array(id) => match self.heap.get(*id) {
+                    Some(ManagedObject::Array(items)) => {
+                        items.iter().all(|item| self.value_matches_type(item, element))
+                    }
+                    _ => false,
+                },
+                _ => false,
+            },
+            Type::Tuple(types) => match value {
+                Value::Tuple(id) => match self.heap.get(*id) {
+                    Some(ManagedObject::Tuple(items)) => {
+                        items.len() == types.len()
+                            && items.iter().zip(types).all(|(item, t)| self.value_matches_type(item, t))
+                    }
+                    _ => false,
+                },
+                _ => false,
+            },
+            Type::Function(..) => matches!(
+                value,
+                Value::Function { .. }
+                    | Value::AsyncFunction { .. }
+                    | Value::ThreadFunction { .. }
+                    | Value::GeneratorFunction { .. }
+                    | Value::Lambda(_)
+            ),
+            // Argumentos de tipo são apagados em runtime
+            Type::Class(name) | Type::Generic(name, _) => self.value_matches_named_type(value, name),
+            Type::Union(members) => members.iter().any(|member| self.value_matches_type(value, member)),
+            Type::Literal(literal) => match self.eval_literal(literal) {
+                Ok(expected) => self.values_equal(value, &expected),
+                Err(_) => false,
+            },
+            Type::Object(fields) => {
+                let properties = match value {
+                    Value::Object(id) => match self.heap.get(*id) {
+                        Some(ManagedObject::Object { properties, .. }) => properties,
+                        _ => return false,
+                    },
+                    Value::Instance(id) => match self.heap.get(*id) {
+                        Some(ManagedObject::Instance { properties, .. }) => properties,
+                        _ => return false,
+                    },
+                    _ => return false,
+                };
+                fields.iter().all(|field| match properties.get(&field.name) {
+                    Some(Value::Null) if field.optional => true,
+                    Some(property) => self.value_matches_type(property, &field.field_type),
+                    None => field.optional,
+                })
+            }
+        }
+    }
+
+    /// Nomes em anotações: alias, classe, interface, enum, `Map`/`Set`.
+    /// Nomes desconhecidos (parâmetros de tipo como `T`) aceitam qualquer valor.
+    fn value_matches_named_type(&self, value: &Value, name: &str) -> bool {
+        if let Some(aliased) = self.type_aliases.get(name) {
+            return self.value_matches_type(value, aliased);
+        }
+        match name {
+            "Map" => return matches!(value, Value::Map(_)),
+            "Set" => return matches!(value, Value::Set(_)),
+            "DateTime" => return matches!(value, Value::DateTime(_)),
+            "Date" => return matches!(value, Value::Date(_)),
+            "Duration" => return matches!(value, Value::Duration(_)),
+            "TimeZone" => return matches!(value, Value::TimeZone(_)),
+            "Regex" => return matches!(value, Value::Regex(_)),
+            _ => {}
+        }
+        if self.env.classes.contains_key(name) || self.env.interfaces.contains_key(name) {
+            let Value::Instance(id) = value else {
+                return false;
+            };
+            let Some(ManagedObject::Instance { class_name, .. }) = self.heap.get(*id) else {
+                return false;
+            };
+            return self.is_subclass_of(class_name, name) || self.implements_interface(class_name, name);
+        }
+        if let Some(Value::Enum(_)) = self.env.variables.get(name) {
+            return match value {
+                Value::Variant(id) => matches!(
+                    self.heap.get(*id),
+                    Some(ManagedObject::Variant { enum_name, .. }) if enum_name == name
+                ),
+                _ => false,
+            };
+        }
+        true
+    }
+
+    /// Tipo anotado de uma propriedade, procurado na classe e nas superclasses
+    fn declared_property_type(&self, class_name: &str, property: &str) -> Option<Type> {
+        if !self.strict_types {
+            return None;
+        }
+        let mut current = Some(class_name.to_string());
+        let mut visited = std::collections::HashSet::new();
+        while let Some(name) = current {
+            if !visited.insert(name.clone()) {
+                break;
+            }
+            current = match self.env.classes.get(&name) {
+                Some(Value::Class(id)) => match self.heap.get(*id) {
+                    Some(ManagedObject::Class { parent, properties, .. }) => {
+                        if let Some(prop) = properties.get(property) {
+                            return prop.declared_type.clone();
+                        }
+                        parent.clone()
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+        }
+        None
+    }
+
+    /// Verifica se a classe (ou uma superclasse) declara `implements interface_name`
+    fn implements_interface(&self, class_name: &str, interface_name: &str) -> bool {
+        let mut current = Some(class_name.to_string());
+        let mut visited = std::collections::HashSet::new();
+        while let Some(name) = current {
+            if !visited.insert(name.clone()) {
+                break;
+            }
+            current = match self.env.classes.get(&name) {
+                Some(Value::Class(id)) => match self.heap.get(*id) {
+                    Some(ManagedObject::Class { parent, interfaces, .. }) => {
+                        if interfaces.iter().any(|i| i == interface_name) {
+                            return true;
+                        }
+                        parent.clone()
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+        }
+        false
+    }
+
+    /// Operadores sobrecarregados pela classe do operando esquerdo
+    /// (`operator +(other)`, `operator ==(other)`, ...). `!=`, `>`, `<=` e `>=`
+    /// são derivados de `operator ==` e `operator <` quando não declarados.
+    /// Retorna `None` quando não há sobrecarga e a operação segue normalmente.
+    fn overloaded_binary(
+        &mut self,
+        operator: &str,
+        left: &Value,
+        right: &Value,
+    ) -> Result<Option<Value>, DryadError> {
+        if !matches!(left, Value::Instance(_)) {
+            // "texto" + instância usa o toString() da classe
+            if let (Value::String(text), "+") = (left, operator) {
+                if let Some(right_text) = self.instance_to_string(right)? {
+                    return Ok(Some(Value::String(format!("{}{}", text, right_text))));
+                }
+            }
+            return Ok(None);
+        }
+
+        let method = format!("operator{}", operator);
+        if self.has_method(left, &method) {
+            return self
+                .call_method_values(left.clone(), &method, vec![right.clone()])
+                .map(Some);
+        }
+
+        let result = match operator {
+            "+" => match right {
+                Value::String(text) => self
+                    .instance_to_string(left)?
+                    .map(|left_text| Value::String(format!("{}{}", left_text, text))),
+                _ => None,
+            },
+            "!=" if self.has_method(left, "operator==") => {
+                Some(Value::Bool(!self.operator_truth("==", left, right)?))
+            }
+            ">" | "<=" | ">=" if self.has_method(left, "operator<") => {
+                let less = self.operator_truth("<", left, right)?;
+                let equal = match operator {
+                    ">=" => false,
+                    _ if self.has_method(left, "operator==") => {
+                        self.operator_truth("==", left, right)?
+                    }
+                    _ => self.values_equal(left, right),
+                };
+                Some(Value::Bool(match operator {
+                    ">" => !less && !equal,
+                    "<=" => less || equal,
+                    _ => !less,
+                }))
+            }
+            _ => None,
+        };
+        Ok(result)
+    }
+
+    /// Chama `operator<op>` da instância e interpreta o resultado como booleano
+    fn operator_truth(&mut self, operator: &str, left: &Value, right: &Value) -> Result<bool, DryadError> {
+        let result = self.call_method_values(
+            left.clone(),
+            &format!("operator{}", operator),
+            vec![right.clone()],
+        )?;
+        Ok(self.is_truthy(&result))
+    }
+
+    /// Texto de uma instância pelo `toString()` da classe, se houver
+    fn instance_to_string(&mut self, value: &Value) -> Result<Option<String>, DryadError> {
+        if !matches!(value, Value::Instance(_)) || !self.has_method(value, "toString") {
+            return Ok(None);
+        }
+        Ok(Some(match self.call_method_values(value.clone(), "toString", Vec::new())? {
+            Value::String(text) => text,
+            other => other.to_string(),
+        }))
+    }
+
+    fn binary_values(
+        &self,
+        operator: &str,
+        left_val: Value,
+        right_val: Value,
+    ) -> Result<Value, DryadError> {
+        // DateTime ± Duration, Date - Date, Duration * n, comparações entre datas
+        if let Some(result) = datetime::binary(operator, &left_val, &right_val) {
+            return result.map_err(|e| self.runtime_error(3051, &e));
+        }
+
+        // int/bigint/decimal: promoção para o tipo comum antes de operar
+        if !matches!(operator, "&&" | "||" | "!") {
+            match numeric::promote(&left_val, &right_val) {
+                Ok(None) => {}
+                Ok(Some(Promoted::Float(a, b))) => {
+                    return self.binary_values(operator, Value::Number(a), Value::Number(b))
+                }
+                Ok(Some(operands)) => {
+                    return numeric::apply(operator, operands).map_err(|e| self.numeric_error(e))
+                }
+                Err(e) => return Err(self.numeric_error(e)),
+            }
+        }
+
+        match operator {
+            "+" => self.add_values(left_val, right_val),
+            "-" => self.subtract_values(left_val, right_val),
+            "*" => self.multiply_values(left_val, right_val),
+            "/" => self.divide_values(left_val, right_val),
             "%" => self.modulo_values(left_val, right_val),
             "**" => self.power_values(left_val, right_val),
             "^^" => self.nth_root_values(left_val, right_val),
@@ -1354,7 +1906,17 @@ impl Interpreter {
                 params,
                 rest_param,
                 body,
-            } => self.call_user_function(name, params, rest_param, body, args, location),
+                signature,
+            } => self.call_user_function(name, params, rest_param, body, signature, args, location),
+            Value::GeneratorFunction {
+                name,
+                params,
+                rest_param,
+                body,
+            } => {
+                let arg_values = self.evaluate_arguments(args)?;
+                self.create_generator(name, params, rest_param, body, arg_values)
+            }
             Value::Lambda(id) => {
                 let heap_obj = self.heap.get(id).cloned().ok_or_else(|| {
                     DryadError::from_catalog_fmt(
@@ -1368,9 +1930,10 @@ impl Interpreter {
                     params,
                     body,
                     closure,
+                    signature,
                 } = heap_obj
                 {
-                    self.call_lambda(params, body, closure, args, location)
+                    self.call_lambda(params, body, closure, signature, args, location)
                 } else {
                     Err(DryadError::from_catalog_fmt(
                         error_catalog::e3101(),
@@ -1387,6 +1950,54 @@ impl Interpreter {
         }
     }
 
+    /// `sqlite_transaction(db, fn)` / `pg_transaction(conn_ou_pool, fn)`:
+    /// abre a transação, chama `fn(conn)` e faz commit se ele retornar ou
+    /// rollback (relançando o erro) se ele falhar
+    fn run_db_transaction(
+        &mut self,
+        name: &str,
+        args: Vec<Value>,
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        use crate::native_modules::database::TransactionScope;
+
+        let native_error = |e: crate::errors::RuntimeError| {
+            DryadError::from_catalog_fmt(
+                error_catalog::e3005(),
+                &format!("Erro na função nativa '{}': {}", name, e),
+                SourceLocation::unknown(),
+            )
+        };
+        let (target, callback) = match args.as_slice() {
+            [target, callback] => (target.clone(), callback.clone()),
+            _ => {
+                return Err(native_error(crate::errors::RuntimeError::ArgumentError(
+                    "esperado 2 argumentos (conexão, função)".to_string(),
+                )))
+            }
+        };
+
+        let scope = TransactionScope::begin(name, &target, &mut self.heap).map_err(native_error)?;
+        let connection = scope.connection();
+        let roots_len = self.iteration_roots.len();
+        self.iteration_roots.push(connection.clone());
+        self.iteration_roots.push(callback.clone());
+        let result = self.call_function_value(&callback, vec![connection], location);
+        self.iteration_roots.truncate(roots_len);
+
+        match result {
+            Ok(value) => {
+                scope.finish(true).map_err(native_error)?;
+                Ok(value)
+            }
+            Err(e) => {
+                // O erro do callback prevalece sobre uma falha no rollback
+                let _ = scope.finish(false);
+                Err(e)
+            }
+        }
+    }
+
     fn eval_call_by_name(
         &mut self,
         name: &str,
@@ -1405,6 +2016,18 @@ impl Interpreter {
             for arg in args {
                 arg_values.push(self.evaluate(arg)?);
             }
+            // print/println exibem instâncias pelo toString() da classe
+            if matches!(name, "print" | "println" | "native_print" | "native_println") {
+                for value in arg_values.iter_mut() {
+                    if let Some(text) = self.instance_to_string(value)? {
+                        *value = Value::String(text);
+                    }
+                }
+            }
+            // Transações com callback precisam executar código Dryad
+            if matches!(name, "sqlite_transaction" | "pg_transaction") {
+                return self.run_db_transaction(name, arg_values, location);
+            }
             // Chama a função nativa
             return native_func(&arg_values, &self.native_registry.manager, &mut self.heap)
                 .map_err(|e| {
@@ -1448,14 +2071,25 @@ impl Interpreter {
                     params,
                     rest_param,
                     body,
+                    signature,
                 } => self.call_user_function(
                     name.to_string(),
                     params,
                     rest_param,
                     body,
+                    signature,
                     args,
                     location,
                 ),
+                Value::GeneratorFunction {
+                    name: _,
+                    params,
+                    rest_param,
+                    body,
+                } => {
+                    let arg_values = self.evaluate_arguments(args)?;
+                    self.create_generator(name.to_string(), params, rest_param, body, arg_values)
+                }
                 Value::Lambda(id) => {
                     let heap_obj = self.heap.get(id).ok_or_else(|| {
                         DryadError::from_catalog_fmt(
@@ -1468,12 +2102,14 @@ impl Interpreter {
                         params,
                         body,
                         closure,
+                        signature,
                     } = heap_obj
                     {
                         self.call_lambda(
                             params.clone(),
                             body.clone(),
                             closure.clone(),
+                            signature.clone(),
                             args,
                             location,
                         )
@@ -1492,6 +2128,13 @@ impl Interpreter {
                     )),
             }
         } else {
+            if let Some(result) = self.call_numeric_builtin(name, args) {
+                return result;
+            }
+            if let Some(result) = self.call_collection_builtin(name, args) {
+                return result;
+            }
+
             // Verificar se a função existe em uma categoria nativa inativa
             if self
                 .native_registry
@@ -1526,9 +2169,24 @@ impl Interpreter {
         params: Vec<(String, Option<Expr>)>,
         rest_param: Option<String>,
         body: Stmt,
+        signature: Signature,
         args: &[Expr],
         location: &SourceLocation,
     ) -> Result<Value, DryadError> {
+        let arg_values = self.evaluate_arguments(args)?;
+        self.call_user_function_values(
+            function_name,
+            params,
+            rest_param,
+            body,
+            signature,
+            arg_values,
+            location,
+        )
+    }
+
+    /// Avalia os argumentos de uma chamada, expandindo `...array`
+    fn evaluate_arguments(&mut self, args: &[Expr]) -> Result<Vec<Value>, DryadError> {
         let mut arg_values = Vec::new();
         for arg in args {
             if let Expr::Spread(expr, _) = arg {
@@ -1542,14 +2200,7 @@ impl Interpreter {
                 arg_values.push(self.evaluate(arg)?);
             }
         }
-        self.call_user_function_values(
-            function_name,
-            params,
-            rest_param,
-            body,
-            arg_values,
-            location,
-        )
+        Ok(arg_values)
     }
 
     fn call_user_function_values(
@@ -1558,6 +2209,7 @@ impl Interpreter {
         params: Vec<(String, Option<Expr>)>,
         rest_param: Option<String>,
         body: Stmt,
+        signature: Signature,
         arg_values: Vec<Value>,
         location: &SourceLocation,
     ) -> Result<Value, DryadError> {
@@ -1570,6 +2222,7 @@ impl Interpreter {
 
         // Salvar estado atual das variáveis para escopo e GC roots
         self.env.call_stack_vars.push(self.env.variables.clone());
+        let saved_types = self.env.variable_types.clone();
 
         // Push stack frame for function call
         let frame = StackFrame::new(function_name.clone(), location.clone());
@@ -1591,20 +2244,19 @@ impl Interpreter {
                 ))
             };
 
-            match value_result {
-                Ok(val) => {
-                    self.env.variables.insert(param_name.clone(), val);
+            let bound = value_result.and_then(|val| {
+                self.bind_param(param_name, val, signature.param_type(i), &function_name)
+            });
+            if let Err(err) = bound {
+                self.call_depth -= 1;
+                if !self.current_stack_trace.frames.is_empty() {
+                    self.current_stack_trace.frames.pop();
                 }
-                Err(err) => {
-                    self.call_depth -= 1;
-                    if !self.current_stack_trace.frames.is_empty() {
-                        self.current_stack_trace.frames.pop();
-                    }
-                    if let Some(saved) = self.env.call_stack_vars.pop() {
-                        self.env.variables = saved;
-                    }
-                    return Err(err);
+                if let Some(saved) = self.env.call_stack_vars.pop() {
+                    self.env.variables = saved;
                 }
+                self.env.variable_types = saved_types;
+                return Err(err);
             }
         }
 
@@ -1625,7 +2277,9 @@ impl Interpreter {
             Err(err) => {
                 // Verificar se é um retorno especial
                 if err.code() == 3021 {
-                    self.parse_return_value(err.message())
+                    self.parse_return_value(err.message()).and_then(|value| {
+                        self.check_return_type(value, &signature, &function_name)
+                    })
                 } else {
                     Err(err)
                 }
@@ -1639,6 +2293,7 @@ impl Interpreter {
         if let Some(saved) = self.env.call_stack_vars.pop() {
             self.env.variables = saved;
         }
+        self.env.variable_types = saved_types;
 
         self.call_depth -= 1;
         result
@@ -1649,6 +2304,7 @@ impl Interpreter {
         params: Vec<(String, Option<Expr>)>,
         body: Expr,
         closure: HashMap<String, Value>,
+        signature: Signature,
         args: &[Expr],
         location: &SourceLocation,
     ) -> Result<Value, DryadError> {
@@ -1656,7 +2312,7 @@ impl Interpreter {
         for arg in args {
             arg_values.push(self.evaluate(arg)?);
         }
-        self.call_lambda_values(params, body, closure, arg_values, location)
+        self.call_lambda_values(params, body, closure, signature, arg_values, location)
     }
 
     fn call_lambda_values(
@@ -1664,6 +2320,7 @@ impl Interpreter {
         params: Vec<(String, Option<Expr>)>,
         body: Expr,
         closure: HashMap<String, Value>,
+        signature: Signature,
         arg_values: Vec<Value>,
         location: &SourceLocation,
     ) -> Result<Value, DryadError> {
@@ -1680,6 +2337,8 @@ impl Interpreter {
         // Salvar estado atual das variáveis para escopo e GC roots
         self.env.call_stack_vars.push(self.env.variables.clone());
 
+        let saved_types = self.env.variable_types.clone();
+
         // Restaurar o closure (escopo onde a lambda foi criada)
         self.env.variables = closure;
 
@@ -1700,27 +2359,28 @@ impl Interpreter {
                     ))
             };
 
-            match value_result {
-                Ok(val) => {
-                    self.env.variables.insert(param_name.clone(), val);
-                }
-                Err(err) => {
-                    self.call_depth -= 1;
-                    if let Some(saved) = self.env.call_stack_vars.pop() {
-                        self.env.variables = saved;
-                    }
-                    return Err(err);
+            let bound = value_result
+                .and_then(|val| self.bind_param(param_name, val, signature.param_type(i), "lambda"));
+            if let Err(err) = bound {
+                self.call_depth -= 1;
+                if let Some(saved) = self.env.call_stack_vars.pop() {
+                    self.env.variables = saved;
                 }
+                self.env.variable_types = saved_types;
+                return Err(err);
             }
         }
 
         // Executar corpo da lambda (é uma expressão)
-        let result = self.evaluate(&body);
+        let result = self
+            .evaluate(&body)
+            .and_then(|value| self.check_return_type(value, &signature, "lambda"));
 
         // Restaurar estado das variáveis original
         if let Some(saved) = self.env.call_stack_vars.pop() {
             self.env.variables = saved;
         }
+        self.env.variable_types = saved_types;
 
         self.call_depth -= 1;
         result
@@ -1787,6 +2447,9 @@ impl Interpreter {
     fn values_equal(&self, left: &Value, right: &Value) -> bool {
         match (left, right) {
             (Value::Number(a), Value::Number(b)) => a == b,
+            (a, b) if numeric::is_exact(a) || numeric::is_exact(b) => {
+                numeric::compare(a, b) == Some(std::cmp::Ordering::Equal)
+            }
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Bool(a), Value::Bool(b)) => a == b,
             (Value::Null, Value::Null) => true,
@@ -1796,7 +2459,14 @@ impl Interpreter {
             (Value::Class(a), Value::Class(b)) => a == b,
             (Value::Instance(a), Value::Instance(b)) => a == b,
             (Value::Object(a), Value::Object(b)) => a == b,
-            _ => false,
+            (Value::Enum(a), Value::Enum(b)) => a == b,
+            (Value::Generator(a), Value::Generator(b)) => a == b,
+            (Value::Map(a), Value::Map(b)) | (Value::Set(a), Value::Set(b)) => a == b,
+            (Value::Range { .. }, Value::Range { .. }) => left.deep_equals(right, &self.heap),
+            // Variantes são valores: comparadas pelo conteúdo
+            (Value::Variant(_), Value::Variant(_)) => left.deep_equals(right, &self.heap),
+            (Value::Regex(a), Value::Regex(b)) => a == b,
+            (a, b) => datetime::equals(a, b).unwrap_or(false),
         }
     }
 
@@ -1817,9 +2487,18 @@ impl Interpreter {
     fn eval_unary(&mut self, operator: &str, operand: &Expr) -> Result<Value, DryadError> {
         let value = self.evaluate(operand)?;
 
+        // -instância usa `operator -()` da classe
+        if operator == "-" && self.has_method(&value, "operator neg") {
+            return self.call_method_values(value, "operator neg", Vec::new());
+        }
+
         match operator {
             "-" => match value {
                 Value::Number(n) => Ok(Value::Number(-n)),
+                Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) => numeric::negate(&value)
+                    .unwrap_or_else(|| Ok(Value::Null))
+                    .map_err(|e| self.numeric_error(e)),
+                Value::Duration(_) => Ok(datetime::negate(&value).unwrap_or(Value::Null)),
                 _ => Err(DryadError::from_catalog_fmt(
                     error_catalog::e3005(),
                     "Operação '-' só é válida para números",
@@ -1827,6 +2506,7 @@ impl Interpreter {
                     )),
             },
             "!" => Ok(Value::Bool(!self.is_truthy(&value))),
+            "typeof" => Ok(Value::String(value.type_name().to_string())),
             _ => Err(DryadError::from_catalog_fmt(
                 error_catalog::e3006(),
                 &format!("Operador unário '{}' desconhecido", operator),
@@ -1839,18 +2519,32 @@ impl Interpreter {
         match value {
             Value::Bool(b) => *b,
             Value::Null => false,
-            Value::Number(n) => *n != 0.0,
+            Value::Number(_) | Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) => {
+                value.is_truthy()
+            }
             Value::String(s) => !s.is_empty(),
             Value::Array(_)
             | Value::Tuple(_)
             | Value::Lambda(_)
             | Value::Class(_)
             | Value::Instance(_)
-            | Value::Object(_) => true,
+            | Value::Object(_)
+            | Value::Enum(_)
+            | Value::Variant(_)
+            | Value::Generator(_)
+            | Value::Map(_)
+            | Value::Set(_)
+            | Value::Range { .. }
+            | Value::DateTime(_)
+            | Value::Date(_)
+            | Value::Duration(_)
+            | Value::TimeZone(_)
+            | Value::Regex(_) => true,
             Value::Exception(_) => false,
-            Value::Function { .. } | Value::AsyncFunction { .. } | Value::ThreadFunction { .. } => {
-                true
-            }
+            Value::Function { .. }
+            | Value::AsyncFunction { .. }
+            | Value::ThreadFunction { .. }
+            | Value::GeneratorFunction { .. } => true,
             Value::Thread { is_running, .. } => *is_running,
             Value::Mutex { .. } => true,
             Value::Promise { resolved, .. } => *resolved,
@@ -1871,6 +2565,11 @@ impl Interpreter {
                     // Retorna o valor original
                     Ok(Value::Number(n))
                 }
+                current @ (Value::Int(_) | Value::BigInt(_) | Value::Decimal(_)) => {
+                    let new_value = self.binary_values("+", current.clone(), Value::Number(1.0))?;
+                    self.env.variables.insert(name.clone(), new_value.clone());
+                    Ok(current)
+                }
                 _ => Err(DryadError::from_catalog_fmt(
                     error_catalog::e3007(),
                     "Operador ++ só é válido para números",
@@ -1899,6 +2598,11 @@ impl Interpreter {
                     // Retorna o valor original
                     Ok(Value::Number(n))
                 }
+                current @ (Value::Int(_) | Value::BigInt(_) | Value::Decimal(_)) => {
+                    let new_value = self.binary_values("-", current.clone(), Value::Number(1.0))?;
+                    self.env.variables.insert(name.clone(), new_value.clone());
+                    Ok(current)
+                }
                 _ => Err(DryadError::from_catalog_fmt(
                     error_catalog::e3009(),
                     "Operador -- só é válido para números",
@@ -1928,6 +2632,11 @@ impl Interpreter {
                     // Retorna o novo valor
                     Ok(Value::Number(new_value))
                 }
+                current @ (Value::Int(_) | Value::BigInt(_) | Value::Decimal(_)) => {
+                    let new_value = self.binary_values("+", current.clone(), Value::Number(1.0))?;
+                    self.env.variables.insert(name.clone(), new_value.clone());
+                    Ok(new_value)
+                }
                 _ => Err(DryadError::from_catalog_fmt(
                     error_catalog::e3011(),
                     "Operador ++ só é válido para números",
@@ -1957,6 +2666,11 @@ impl Interpreter {
                     // Retorna o novo valor
                     Ok(Value::Number(new_value))
                 }
+                current @ (Value::Int(_) | Value::BigInt(_) | Value::Decimal(_)) => {
+                    let new_value = self.binary_values("-", current.clone(), Value::Number(1.0))?;
+                    self.env.variables.insert(name.clone(), new_value.clone());
+                    Ok(new_value)
+                }
                 _ => Err(DryadError::from_catalog_fmt(
                     error_catalog::e3013(),
                     "Operador -- só é válido para números",
@@ -2247,6 +2961,7 @@ impl Interpreter {
         condition: &Option<Expr>,
         update: &Option<Box<Stmt>>,
         body: &Box<Stmt>,
+        location: &SourceLocation,
     ) -> Result<Value, DryadError> {
         // Executa inicialização se presente
         if let Some(init_stmt) = init {
@@ -2259,7 +2974,9 @@ impl Interpreter {
             // Verifica condição se presente
             if let Some(condition_expr) = condition {
                 let condition_value = self.evaluate(condition_expr)?;
-                if !self.is_truthy(&condition_value) {
+                let taken = self.is_truthy(&condition_value);
+                self.record_branch(location, taken);
+                if !taken {
                     break;
                 }
             }
@@ -2292,164 +3009,1221 @@ impl Interpreter {
         iterable: &Expr,
         body: &Box<Stmt>,
     ) -> Result<Value, DryadError> {
-        // Extrai o nome da variável do pattern
-        let var_name = match pattern {
-            Pattern::Identifier(name) => name.as_str(),
-            _ => {
-                return Err(DryadError::from_catalog_fmt(
-                    error_catalog::e3101(),
-                    "ForEach loop only supports identifier patterns",
-                    SourceLocation::unknown(),
-                    ))
+        let iterable_value = self.evaluate(iterable)?;
+
+        // Mantém o iterável (e o iterador retornado por iterator()) vivo durante o laço
+        let roots_len = self.iteration_roots.len();
+        self.iteration_roots.push(iterable_value.clone());
+        let result = self.make_iterator(iterable_value).and_then(|mut iter| {
+            if let Some(handle) = iter.handle() {
+                self.iteration_roots.push(handle.clone());
             }
-        };
 
-        // Avalia a expressão iterável
-        let iterable_value = self.evaluate(iterable)?;
+            // Salva o valor anterior das variáveis do padrão (se existirem)
+            let previous: Vec<(String, Option<Value>)> = pattern_names(pattern)
+                .into_iter()
+                .map(|name| {
+                    let value = self.env.variables.get(&name).cloned();
+                    (name, value)
+                })
+                .collect();
+
+            let result = self.run_foreach(pattern, &mut iter, body);
 
-        // Salva o valor anterior da variável de iteração (se existir)
-        let previous_value = self.env.variables.get(var_name).cloned();
+            // Restaura as variáveis do padrão
+            for (name, value) in previous {
+                match value {
+                    Some(value) => self.env.variables.insert(name, value),
+                    None => self.env.variables.remove(&name),
+                };
+            }
+            result
+        });
+        self.iteration_roots.truncate(roots_len);
+        result
+    }
 
+    fn run_foreach(
+        &mut self,
+        pattern: &Pattern,
+        iter: &mut ValueIter,
+        body: &Stmt,
+    ) -> Result<Value, DryadError> {
         let mut last_value = Value::Null;
 
-        // Itera sobre os elementos dependendo do tipo
-        match iterable_value {
-            Value::Array(id) => {
-                let elements = if let Some(ManagedObject::Array(e)) = self.heap.get(id) {
-                    e.clone()
-                } else {
-                    return Err(DryadError::from_catalog_fmt(
-                        error_catalog::e3101(),
-                        "Heap error: Expected Array",
-                        SourceLocation::unknown(),
-                    ));
-                };
+        while let Some(element) = self.iter_next(iter)? {
+            self.bind_loop_pattern(pattern, element)?;
 
-                for element in elements {
-                    // Define a variável de iteração
-                    self.env.variables.insert(var_name.to_string(), element);
+            match self.execute_statement(body) {
+                Ok(value) => last_value = value,
+                Err(err) if err.code() == 3010 => break,
+                Err(err) if err.code() == 3011 => continue,
+                Err(e) => return Err(e),
+            }
+        }
 
-                    // Executa o corpo do loop
-                    match self.execute_statement(body) {
-                        Ok(value) => last_value = value,
-                        Err(err) if err.code() == 3010 => {
-                            // Break statement
-                            break;
-                        }
-                        Err(err) if err.code() == 3011 => {
-                            // Continue statement - continua para próximo elemento
-                            continue;
-                        }
-                        Err(e) => {
-                            // Restaura valor anterior antes de retornar erro
-                            if let Some(prev_val) = previous_value {
-                                self.env.variables.insert(var_name.to_string(), prev_val);
-                            } else {
-                                self.env.variables.remove(var_name);
-                            }
-                            return Err(e);
+        Ok(last_value)
+    }
+
+    /// Atribui o elemento atual às variáveis do padrão do `for-in`
+    fn bind_loop_pattern(&mut self, pattern: &Pattern, element: Value) -> Result<(), DryadError> {
+        if let Pattern::Identifier(name) = pattern {
+            self.env.variables.insert(name.clone(), element);
+            return Ok(());
+        }
+
+        let mut bindings = HashMap::new();
+        if !self.match_pattern(&element, pattern, &mut bindings) {
+            let shown = self.inspect_value(&element, &InspectOptions::default());
+            return Err(self.runtime_error(
+                3043,
+                &format!("Elemento {} não corresponde ao padrão do for-in", shown),
+            ));
+        }
+        self.env.variables.extend(bindings);
+        Ok(())
+    }
+
+    // ===== Iteração =====
+
+    /// Converte um valor iterável em um iterador
+    fn make_iterator(&mut self, value: Value) -> Result<ValueIter, DryadError> {
+        match &value {
+            Value::Array(id) | Value::Tuple(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Array(elements)) | Some(ManagedObject::Tuple(elements)) => {
+                    Ok(ValueIter::from_values(elements.clone()))
+                }
+                _ => Err(DryadError::from_catalog_fmt(
+                    error_catalog::e3100(),
+                    "Heap error: Array/Tuple reference not found",
+                    SourceLocation::unknown(),
+                )),
+            },
+            Value::String(s) => Ok(ValueIter::from_values(
+                s.chars().map(|c| Value::String(c.to_string())).collect(),
+            )),
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => Ok(ValueIter::Range {
+                next: *start,
+                end: *end,
+                inclusive: *inclusive,
+            }),
+            Value::Generator(_) => Ok(ValueIter::Generator(value)),
+            // Map produz tuplas (chave, valor); Set, os próprios elementos
+            Value::Map(id) => {
+                let pairs: Vec<(Value, Value)> = self.map_entries(*id)?.values().cloned().collect();
+                let tuples: Vec<Value> = pairs
+                    .into_iter()
+                    .map(|(key, value)| Value::Tuple(self.heap.allocate(ManagedObject::Tuple(vec![key, value]))))
+                    .collect();
+                // As tuplas só existem no iterador: ficam vivas até o fim do laço
+                let snapshot = self.heap.allocate(ManagedObject::Array(tuples.clone()));
+                self.iteration_roots.push(Value::Array(snapshot));
+                Ok(ValueIter::from_values(tuples))
+            }
+            Value::Set(id) => Ok(ValueIter::from_values(
+                self.set_entries(*id)?.values().cloned().collect(),
+            )),
+            Value::Object(id) | Value::Instance(id) => {
+                let id = *id;
+                if self.has_method(&value, "iterator") {
+                    let iterator = self.call_method_values(value.clone(), "iterator", Vec::new())?;
+                    return match iterator {
+                        Value::Object(_) | Value::Instance(_) => Ok(ValueIter::Protocol(iterator)),
+                        other => self.make_iterator(other),
+                    };
+                }
+                if self.has_method(&value, "next") {
+                    return Ok(ValueIter::Protocol(value));
+                }
+                // Arquivo aberto por `file_open`/`file_lines`: uma linha por volta
+                if crate::native_modules::file_io::is_file_handle(&value, &self.heap) {
+                    return Ok(ValueIter::Native { handle: value, next: crate::native_modules::file_io::next_line });
+                }
+                match self.heap.get(id) {
+                    // Objetos sem o protocolo são percorridos pelas chaves, em ordem alfabética
+                    Some(ManagedObject::Object { properties, .. }) => {
+                        let mut keys: Vec<&String> = properties.keys().collect();
+                        keys.sort();
+                        Ok(ValueIter::from_values(
+                            keys.into_iter().map(|k| Value::String(k.clone())).collect(),
+                        ))
+                    }
+                    Some(ManagedObject::Instance { class_name, .. }) => {
+                        let class_name = class_name.clone();
+                        Err(self.runtime_error(
+                            3043,
+                            &format!(
+                                "Instância de '{}' não é iterável: implemente iterator() ou next() retornando {{ value, done }}",
+                                class_name
+                            ),
+                        ))
+                    }
+                    _ => Err(DryadError::from_catalog_fmt(
+                        error_catalog::e3100(),
+                        "Heap error: Object reference not found",
+                        SourceLocation::unknown(),
+                    )),
+                }
+            }
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3030(),
+                &format!(
+                    "Valor não é iterável: {}",
+                    self.inspect_value(&value, &InspectOptions::default())
+                ),
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    /// Próximo valor do iterador, ou `None` quando terminou
+    fn iter_next(&mut self, iter: &mut ValueIter) -> Result<Option<Value>, DryadError> {
+        if let Some(next) = iter.advance_builtin() {
+            return Ok(next);
+        }
+        match iter {
+            ValueIter::Generator(Value::Generator(id)) => {
+                let (value, done) = self.resume_generator(*id, Value::Null)?;
+                Ok(if done { None } else { Some(value) })
+            }
+            ValueIter::Protocol(receiver) => {
+                let result = self.call_method_values(receiver.clone(), "next", Vec::new())?;
+                self.read_iterator_result(result)
+            }
+            ValueIter::Native { handle, next } => {
+                match next(std::slice::from_ref(handle), &self.native_registry.manager, &mut self.heap) {
+                    Ok(Value::Null) => Ok(None),
+                    Ok(value) => Ok(Some(value)),
+                    Err(e) => Err(DryadError::from_catalog_fmt(
+                        error_catalog::e3005(),
+                        &format!("Erro ao iterar: {}", e),
+                        SourceLocation::unknown(),
+                    )),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Lê o `{ value, done }` retornado por um `next()` do protocolo
+    fn read_iterator_result(&mut self, result: Value) -> Result<Option<Value>, DryadError> {
+        let properties = match &result {
+            Value::Object(id) | Value::Instance(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Object { properties, .. })
+                | Some(ManagedObject::Instance { properties, .. }) => Some(properties),
+                _ => None,
+            },
+            _ => None,
+        };
+        match properties {
+            Some(properties) => {
+                let done = properties.get("done").is_some_and(|done| done.is_truthy());
+                if done {
+                    Ok(None)
+                } else {
+                    Ok(Some(properties.get("value").cloned().unwrap_or(Value::Null)))
+                }
+            }
+            None => {
+                let shown = self.inspect_value(&result, &InspectOptions::default());
+                Err(self.runtime_error(
+                    3043,
+                    &format!("next() deve retornar {{ value, done }}, retornou {}", shown),
+                ))
+            }
+        }
+    }
+
+    /// Cria o objeto `{ value, done }` retornado por `next()` de geradores
+    fn iterator_result(&mut self, value: Value, done: bool) -> Value {
+        let mut properties = HashMap::new();
+        properties.insert("value".to_string(), value);
+        properties.insert("done".to_string(), Value::Bool(done));
+        let id = self.heap.allocate(ManagedObject::Object {
+            properties,
+            methods: HashMap::new(),
+        });
+        Value::Object(id)
+    }
+
+    /// Indica se a instância ou objeto tem um método com esse nome
+    fn has_method(&self, value: &Value, method_name: &str) -> bool {
+        match value {
+            Value::Object(id) => match self.heap.get(*id) {
+                Some(ManagedObject::Object {
+                    properties,
+                    methods,
+                }) => {
+                    methods.contains_key(method_name)
+                        || matches!(properties.get(method_name), Some(Value::Function { .. }))
+                }
+                _ => false,
+            },
+            Value::Instance(id) => {
+                let class_name = match self.heap.get(*id) {
+                    Some(ManagedObject::Instance { class_name, .. }) => class_name,
+                    _ => return false,
+                };
+                match self.env.classes.get(class_name) {
+                    Some(Value::Class(cid)) => matches!(
+                        self.heap.get(*cid),
+                        Some(ManagedObject::Class { methods, .. }) if methods.contains_key(method_name)
+                    ),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// `obj.keys()`, `obj.values()` e `obj.entries()`, em ordem alfabética das chaves
+    fn object_entries(&mut self, properties: &HashMap<String, Value>, method_name: &str) -> Value {
+        let mut keys: Vec<&String> = properties.keys().collect();
+        keys.sort();
+        let items: Vec<Value> = keys
+            .into_iter()
+            .map(|key| match method_name {
+                "keys" => Value::String(key.clone()),
+                "values" => properties[key].clone(),
+                _ => {
+                    let pair = vec![Value::String(key.clone()), properties[key].clone()];
+                    Value::Tuple(self.heap.allocate(ManagedObject::Tuple(pair)))
+                }
+            })
+            .collect();
+        Value::Array(self.heap.allocate(ManagedObject::Array(items)))
+    }
+
+    // ===== Map e Set =====
+
+    /// Chave de Map/Set para um valor (funções, threads etc. não podem ser chave)
+    fn collection_key(&self, value: &Value) -> Result<MapKey, DryadError> {
+        MapKey::of(value, &self.heap).map_err(|message| self.runtime_error(3047, &message))
+    }
+
+    fn map_entries(&mut self, id: HeapId) -> Result<&mut MapEntries, DryadError> {
+        match self.heap.get_mut(id) {
+            Some(ManagedObject::Map(entries)) => Ok(entries),
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3100(),
+                "Heap error: Map reference not found",
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    fn set_entries(&mut self, id: HeapId) -> Result<&mut SetEntries, DryadError> {
+        match self.heap.get_mut(id) {
+            Some(ManagedObject::Set(entries)) => Ok(entries),
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3100(),
+                "Heap error: Set reference not found",
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    fn map_insert(&mut self, id: HeapId, key: Value, value: Value) -> Result<(), DryadError> {
+        let map_key = self.collection_key(&key)?;
+        self.map_entries(id)?.insert(map_key, (key, value));
+        Ok(())
+    }
+
+    /// Adiciona ao Set; um valor já presente mantém a posição original
+    fn set_insert(&mut self, id: HeapId, value: Value) -> Result<(), DryadError> {
+        let key = self.collection_key(&value)?;
+        self.set_entries(id)?.entry(key).or_insert(value);
+        Ok(())
+    }
+
+    /// `Map()`/`new Map(iterável)` e `Set()`/`new Set(iterável)`, disponíveis sem
+    /// diretiva quando o nome não foi definido pelo programa
+    fn call_collection_builtin(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+    ) -> Option<Result<Value, DryadError>> {
+        let collection = match name {
+            "Map" => ManagedObject::Map(MapEntries::new()),
+            "Set" => ManagedObject::Set(SetEntries::new()),
+            _ => return None,
+        };
+        Some(self.evaluate_arguments(args).and_then(|mut values| {
+            if values.len() > 1 {
+                return Err(self.runtime_error(
+                    3047,
+                    &format!("{}() espera no máximo 1 argumento (um iterável)", name),
+                ));
+            }
+            let id = self.heap.allocate(collection);
+            let collection = if name == "Map" { Value::Map(id) } else { Value::Set(id) };
+            let Some(source) = values.pop() else {
+                return Ok(collection);
+            };
+
+            // A coleção e a origem ficam vivas enquanto a origem é percorrida
+            let roots_len = self.iteration_roots.len();
+            self.iteration_roots.push(collection.clone());
+            self.iteration_roots.push(source.clone());
+            let result = self.fill_collection(&collection, source);
+            self.iteration_roots.truncate(roots_len);
+            result.map(|_| collection)
+        }))
+    }
+
+    /// Preenche um Map com pares `[chave, valor]`/`(chave, valor)` (ou com as
+    /// propriedades de um objeto) e um Set com os elementos de um iterável
+    fn fill_collection(&mut self, collection: &Value, source: Value) -> Result<(), DryadError> {
+        if let (Value::Map(id), Value::Object(object_id)) = (collection, &source) {
+            if !self.has_method(&source, "iterator") && !self.has_method(&source, "next") {
+                let mut properties: Vec<(String, Value)> = match self.heap.get(*object_id) {
+                    Some(ManagedObject::Object { properties, .. }) => properties
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                properties.sort_by(|a, b| a.0.cmp(&b.0));
+                for (key, value) in properties {
+                    self.map_insert(*id, Value::String(key), value)?;
+                }
+                return Ok(());
+            }
+        }
+
+        let mut iter = self.make_iterator(source)?;
+        if let Some(handle) = iter.handle() {
+            self.iteration_roots.push(handle.clone());
+        }
+        while let Some(item) = self.iter_next(&mut iter)? {
+            match collection {
+                Value::Map(id) => {
+                    let (key, value) = self.pair_of(&item)?;
+                    self.map_insert(*id, key, value)?;
+                }
+                Value::Set(id) => self.set_insert(*id, item)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn pair_of(&self, item: &Value) -> Result<(Value, Value), DryadError> {
+        if let Value::Array(id) | Value::Tuple(id) = item {
+            if let Some(ManagedObject::Array(items) | ManagedObject::Tuple(items)) = self.heap.get(*id) {
+                if let [key, value] = items.as_slice() {
+                    return Ok((key.clone(), value.clone()));
+                }
+            }
+        }
+        Err(self.runtime_error(
+            3047,
+            &format!(
+                "Entrada de Map deve ser um par [chave, valor], encontrado {}",
+                self.inspect_value(item, &InspectOptions::default())
+            ),
+        ))
+    }
+
+    /// Métodos de `Regex`; `replace`/`replace_all` aceitam uma função que recebe o
+    /// objeto do match e retorna o texto de substituição
+    fn eval_regex_method(
+        &mut self,
+        regexp: Regexp,
+        method_name: &str,
+        args: &[Expr],
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        let arg_values = self.evaluate_arguments(args)?;
+        let callback = match (method_name, arg_values.as_slice()) {
+            ("replace" | "replace_all", [Value::String(_), callback]) if !matches!(callback, Value::String(_)) => {
+                callback.clone()
+            }
+            _ => {
+                return regexp::method(&regexp, method_name, &arg_values, &mut self.heap)
+                    .map_err(|e| self.runtime_error(3052, &e))
+            }
+        };
+        let Value::String(text) = &arg_values[0] else {
+            unreachable!("texto validado no padrão acima");
+        };
+
+        let limit = if method_name == "replace" { Some(1) } else { None };
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for (captures, start) in regexp::matches(&regexp, text, limit) {
+            let whole = captures.get(0).expect("grupo 0 sempre participa do match");
+            let found = regexp::match_object(&regexp, text, &captures, start, &mut self.heap);
+            let replacement = self.call_function_value(&callback, vec![found], location)?;
+            result.push_str(&text[last..whole.start()]);
+            result.push_str(&replacement.to_string());
+            last = whole.end();
+        }
+        result.push_str(&text[last..]);
+        Ok(Value::String(result))
+    }
+
+    fn collection_size(&mut self, collection: &Value) -> Result<Value, DryadError> {
+        let size = match collection {
+            Value::Map(id) => self.map_entries(*id)?.len(),
+            Value::Set(id) => self.set_entries(*id)?.len(),
+            _ => 0,
+        };
+        Ok(Value::Number(size as f64))
+    }
+
+    fn eval_collection_method(
+        &mut self,
+        object: Value,
+        method_name: &str,
+        args: &[Expr],
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        let arg_values = self.evaluate_arguments(args)?;
+        match object {
+            Value::Map(id) => self.apply_map_method(id, method_name, arg_values, location),
+            Value::Set(id) => self.apply_set_method(id, method_name, arg_values, location),
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3028(),
+                "Tentativa de chamar método de Map/Set em outro valor",
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    fn apply_map_method(
+        &mut self,
+        id: HeapId,
+        method_name: &str,
+        arg_values: Vec<Value>,
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        let arg = |index: usize| arg_values.get(index).cloned().unwrap_or(Value::Null);
+        match method_name {
+            // get(chave, padrão?)
+            "get" => {
+                let key = self.collection_key(&arg(0))?;
+                Ok(match self.map_entries(id)?.get(&key) {
+                    Some((_, value)) => value.clone(),
+                    None => arg(1),
+                })
+            }
+            "set" => {
+                self.map_insert(id, arg(0), arg(1))?;
+                Ok(Value::Map(id))
+            }
+            "has" => {
+                let key = self.collection_key(&arg(0))?;
+                Ok(Value::Bool(self.map_entries(id)?.contains_key(&key)))
+            }
+            "delete" => {
+                let key = self.collection_key(&arg(0))?;
+                Ok(Value::Bool(self.map_entries(id)?.shift_remove(&key).is_some()))
+            }
+            "clear" => {
+                self.map_entries(id)?.clear();
+                Ok(Value::Null)
+            }
+            "size" => self.collection_size(&Value::Map(id)),
+            "keys" | "values" | "entries" => {
+                let pairs: Vec<(Value, Value)> = self.map_entries(id)?.values().cloned().collect();
+                let items = pairs
+                    .into_iter()
+                    .map(|(key, value)| match method_name {
+                        "keys" => key,
+                        "values" => value,
+                        _ => Value::Tuple(self.heap.allocate(ManagedObject::Tuple(vec![key, value]))),
+                    })
+                    .collect();
+                Ok(Value::Array(self.heap.allocate(ManagedObject::Array(items))))
+            }
+            "forEach" => {
+                let callback = arg(0);
+                let pairs: Vec<(Value, Value)> = self.map_entries(id)?.values().cloned().collect();
+                for (key, value) in pairs {
+                    self.call_function_value(&callback, vec![value, key, Value::Map(id)], location)?;
+                }
+                Ok(Value::Null)
+            }
+            // Retornam um novo Map; na união, o valor do outro Map prevalece
+            "union" | "intersection" | "difference" => {
+                let other = match arg(0) {
+                    Value::Map(other) => self.map_entries(other)?.clone(),
+                    _ => {
+                        return Err(self.runtime_error(
+                            3047,
+                            &format!("Map.{}() espera outro Map", method_name),
+                        ))
+                    }
+                };
+                let mine = self.map_entries(id)?.clone();
+                let entries: MapEntries = match method_name {
+                    "union" => mine.into_iter().chain(other).collect(),
+                    "intersection" => mine.into_iter().filter(|(key, _)| other.contains_key(key)).collect(),
+                    _ => mine.into_iter().filter(|(key, _)| !other.contains_key(key)).collect(),
+                };
+                Ok(Value::Map(self.heap.allocate(ManagedObject::Map(entries))))
+            }
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3026(),
+                &format!("Método '{}' não encontrado em Map", method_name),
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    fn apply_set_method(
+        &mut self,
+        id: HeapId,
+        method_name: &str,
+        arg_values: Vec<Value>,
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        let arg = |index: usize| arg_values.get(index).cloned().unwrap_or(Value::Null);
+        match method_name {
+            "add" => {
+                self.set_insert(id, arg(0))?;
+                Ok(Value::Set(id))
+            }
+            "has" => {
+                let key = self.collection_key(&arg(0))?;
+                Ok(Value::Bool(self.set_entries(id)?.contains_key(&key)))
+            }
+            "delete" => {
+                let key = self.collection_key(&arg(0))?;
+                Ok(Value::Bool(self.set_entries(id)?.shift_remove(&key).is_some()))
+            }
+            "clear" => {
+                self.set_entries(id)?.clear();
+                Ok(Value::Null)
+            }
+            "size" => self.collection_size(&Value::Set(id)),
+            "values" => {
+                let items = self.set_entries(id)?.values().cloned().collect();
+                Ok(Value::Array(self.heap.allocate(ManagedObject::Array(items))))
+            }
+            "forEach" => {
+                let callback = arg(0);
+                let items: Vec<Value> = self.set_entries(id)?.values().cloned().collect();
+                for item in items {
+                    self.call_function_value(&callback, vec![item, Value::Set(id)], location)?;
+                }
+                Ok(Value::Null)
+            }
+            "union" | "intersection" | "difference" | "isSubsetOf" => {
+                let other = match arg(0) {
+                    Value::Set(other) => self.set_entries(other)?.clone(),
+                    _ => {
+                        return Err(self.runtime_error(
+                            3047,
+                            &format!("Set.{}() espera outro Set", method_name),
+                        ))
+                    }
+                };
+                let mine = self.set_entries(id)?.clone();
+                let entries: SetEntries = match method_name {
+                    "isSubsetOf" => {
+                        return Ok(Value::Bool(mine.keys().all(|key| other.contains_key(key))))
+                    }
+                    "union" => {
+                        let mut entries = mine;
+                        for (key, value) in other {
+                            entries.entry(key).or_insert(value);
                         }
+                        entries
+                    }
+                    "intersection" => mine.into_iter().filter(|(key, _)| other.contains_key(key)).collect(),
+                    _ => mine.into_iter().filter(|(key, _)| !other.contains_key(key)).collect(),
+                };
+                Ok(Value::Set(self.heap.allocate(ManagedObject::Set(entries))))
+            }
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3026(),
+                &format!("Método '{}' não encontrado em Set", method_name),
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    fn eval_range(
+        &mut self,
+        start: &Expr,
+        end: &Expr,
+        inclusive: bool,
+    ) -> Result<Value, DryadError> {
+        let start = self.evaluate(start)?;
+        let end = self.evaluate(end)?;
+        match (&start, &end) {
+            (Value::Number(start), Value::Number(end)) => Ok(Value::Range {
+                start: *start,
+                end: *end,
+                inclusive,
+            }),
+            _ => Err(self.runtime_error(
+                3044,
+                &format!(
+                    "Limites de intervalo devem ser números: {}{}{}",
+                    self.inspect_value(&start, &InspectOptions::default()),
+                    if inclusive { "..=" } else { ".." },
+                    self.inspect_value(&end, &InspectOptions::default())
+                ),
+            )),
+        }
+    }
+
+    // ===== Geradores =====
+
+    /// Chamar uma `function*` não executa o corpo: cria o gerador com os parâmetros ligados
+    fn create_generator(
+        &mut self,
+        function_name: String,
+        params: Vec<(String, Option<Expr>)>,
+        rest_param: Option<String>,
+        body: Stmt,
+        arg_values: Vec<Value>,
+    ) -> Result<Value, DryadError> {
+        // Mesmo escopo de uma chamada comum: variáveis do chamador mais os parâmetros
+        let saved = self.env.variables.clone();
+        for (i, (param_name, default_expr)) in params.iter().enumerate() {
+            let value = if let Some(value) = arg_values.get(i) {
+                value.clone()
+            } else if let Some(expr) = default_expr {
+                match self.evaluate(expr) {
+                    Ok(value) => value,
+                    Err(err) => {
+                        self.env.variables = saved;
+                        return Err(err);
+                    }
+                }
+            } else {
+                self.env.variables = saved;
+                return Err(self.runtime_error(
+                    3004,
+                    &format!(
+                        "Argumento obrigatório '{}' não fornecido na chamada da função '{}'",
+                        param_name, function_name
+                    ),
+                ));
+            };
+            self.env.variables.insert(param_name.clone(), value);
+        }
+        if let Some(rest_name) = rest_param {
+            let rest_elements = arg_values.get(params.len()..).unwrap_or_default().to_vec();
+            let array_id = self.heap.allocate(ManagedObject::Array(rest_elements));
+            self.env.variables.insert(rest_name, Value::Array(array_id));
+        }
+
+        let variables = std::mem::replace(&mut self.env.variables, saved);
+        let state = GeneratorState::new(function_name, variables, &body);
+        let id = self.heap.allocate(ManagedObject::Generator(state));
+        Ok(Value::Generator(id))
+    }
+
+    fn eval_generator_method(
+        &mut self,
+        id: HeapId,
+        method_name: &str,
+        arg_values: Vec<Value>,
+    ) -> Result<Value, DryadError> {
+        match method_name {
+            "next" => {
+                let sent = arg_values.into_iter().next().unwrap_or(Value::Null);
+                let (value, done) = self.resume_generator(id, sent)?;
+                Ok(self.iterator_result(value, done))
+            }
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3026(),
+                &format!("Método '{}' não encontrado no gerador", method_name),
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    fn generator_state(&mut self, id: HeapId) -> Result<&mut GeneratorState, DryadError> {
+        match self.heap.get_mut(id) {
+            Some(ManagedObject::Generator(state)) => Ok(state),
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3100(),
+                "Heap error: Generator reference not found",
+                SourceLocation::unknown(),
+            )),
+        }
+    }
+
+    /// Retoma o gerador até o próximo `yield` ou o fim; retorna `(valor, terminou)`
+    fn resume_generator(&mut self, id: HeapId, sent: Value) -> Result<(Value, bool), DryadError> {
+        let state = self.generator_state(id)?;
+        let target = match &state.status {
+            GeneratorStatus::Done => return Ok((Value::Null, true)),
+            GeneratorStatus::Running => {
+                let name = state.name.clone();
+                return Err(self.runtime_error(
+                    3042,
+                    &format!("Gerador '{}' já está em execução", name),
+                ));
+            }
+            GeneratorStatus::Created => None,
+            GeneratorStatus::Suspended(target) => target.clone(),
+        };
+        state.status = GeneratorStatus::Running;
+        let name = state.name.clone();
+        let variables = std::mem::take(&mut state.variables);
+
+        // Entra no escopo do gerador como em uma chamada de função
+        self.call_depth += 1;
+        if self.call_depth > MAX_RECURSION_DEPTH {
+            self.call_depth -= 1;
+            let state = self.generator_state(id)?;
+            state.variables = variables;
+            state.status = GeneratorStatus::Done;
+            return Err(self.runtime_error(3040, "Stack overflow: limite de recursão excedido"));
+        }
+        let caller_vars = std::mem::replace(&mut self.env.variables, variables);
+        self.env.call_stack_vars.push(caller_vars);
+        self.current_stack_trace
+            .push_frame(StackFrame::new(name, SourceLocation::unknown()));
+        self.iteration_roots.push(Value::Generator(id));
+
+        let result = match target {
+            Some(target) => self.bind_yield_target(id, target, sent),
+            None => Ok(()),
+        }
+        .and_then(|_| self.drive_generator(id));
+
+        self.iteration_roots.pop();
+        self.current_stack_trace.frames.pop();
+        let caller_vars = self.env.call_stack_vars.pop().unwrap_or_default();
+        let variables = std::mem::replace(&mut self.env.variables, caller_vars);
+        self.call_depth -= 1;
+
+        let state = self.generator_state(id)?;
+        state.variables = variables;
+        match result {
+            Ok(GeneratorStep::Yield(value, target)) => {
+                state.status = GeneratorStatus::Suspended(target);
+                Ok((value, false))
+            }
+            Ok(GeneratorStep::Return(value)) => {
+                state.status = GeneratorStatus::Done;
+                state.frames.clear();
+                state.variables.clear();
+                Ok((value, true))
+            }
+            Err(err) => {
+                state.status = GeneratorStatus::Done;
+                state.frames.clear();
+                state.variables.clear();
+                Err(err)
+            }
+        }
+    }
+
+    /// Entrega o valor de `next(v)` ao `yield` em que o gerador estava suspenso
+    fn bind_yield_target(
+        &mut self,
+        id: HeapId,
+        target: YieldTarget,
+        value: Value,
+    ) -> Result<(), DryadError> {
+        match target {
+            YieldTarget::Declare(name) => {
+                self.declare_in_generator(id, &name)?;
+                self.env.variables.insert(name, value);
+            }
+            YieldTarget::Assign(name) => {
+                if self.env.constants.contains_key(&name) {
+                    return Err(self.runtime_error(
+                        3011,
+                        &format!("Não é possível modificar a constante '{}'", name),
+                    ));
+                }
+                self.env.variables.insert(name, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Registra no bloco atual do gerador o valor sombreado por uma declaração
+    fn declare_in_generator(&mut self, id: HeapId, name: &str) -> Result<(), DryadError> {
+        let previous = self.env.variables.get(name).cloned();
+        let state = self.generator_state(id)?;
+        let block = state.frames.iter_mut().rev().find_map(|frame| match frame {
+            GeneratorFrame::Block { shadowed, .. } => Some(shadowed),
+            _ => None,
+        });
+        if let Some(shadowed) = block {
+            if !shadowed.iter().any(|(declared, _)| declared == name) {
+                shadowed.push((name.to_string(), previous));
+            }
+        }
+        Ok(())
+    }
+
+    /// Executa o gerador até um `yield` ou o fim do corpo
+    fn drive_generator(&mut self, id: HeapId) -> Result<GeneratorStep, DryadError> {
+        loop {
+            let step = match self.generator_step(id) {
+                Ok(step) => step,
+                Err(err) => {
+                    let unwind = self.unwind_reason(err);
+                    self.generator_unwind(id, unwind)?
+                }
+            };
+            if let Some(step) = step {
+                return Ok(step);
+            }
+        }
+    }
+
+    fn unwind_reason(&mut self, err: DryadError) -> Unwind {
+        match err.code() {
+            3010 => Unwind::Break,
+            3011 => Unwind::Continue,
+            3021 => match self.parse_return_value(err.message()) {
+                Ok(value) => Unwind::Return(value),
+                Err(err) => Unwind::Error(err),
+            },
+            _ => Unwind::Error(err),
+        }
+    }
+
+    /// Avança um passo no frame do topo
+    fn generator_step(&mut self, id: HeapId) -> Result<Option<GeneratorStep>, DryadError> {
+        let state = self.generator_state(id)?;
+        let Some(frame) = state.frames.last_mut() else {
+            return Ok(Some(GeneratorStep::Return(Value::Null)));
+        };
+
+        match frame {
+            GeneratorFrame::Block {
+                statements, next, ..
+            } => {
+                if let Some(stmt) = statements.get(*next).cloned() {
+                    *next += 1;
+                    self.generator_statement(id, stmt)
+                } else {
+                    self.pop_generator_frame(id)?;
+                    Ok(None)
+                }
+            }
+            GeneratorFrame::While {
+                condition,
+                body,
+                location,
+            } => {
+                let (condition, body, location) = (condition.clone(), body.clone(), location.clone());
+                let condition = self.evaluate(&condition)?;
+                let taken = self.is_truthy(&condition);
+                self.record_branch(&location, taken);
+                self.generator_loop_body(id, taken, body)
+            }
+            GeneratorFrame::DoWhile {
+                body,
+                condition,
+                started,
+            } => {
+                let (body, condition) = (body.clone(), condition.clone());
+                let taken = if std::mem::replace(started, true) {
+                    let condition = self.evaluate(&condition)?;
+                    self.is_truthy(&condition)
+                } else {
+                    true
+                };
+                self.generator_loop_body(id, taken, body)
+            }
+            GeneratorFrame::For {
+                condition,
+                update,
+                body,
+                started,
+            } => {
+                let (condition, update, body) = (condition.clone(), update.clone(), body.clone());
+                if std::mem::replace(started, true) {
+                    if let Some(update) = update {
+                        self.execute_statement(&update)?;
                     }
                 }
+                let taken = match condition {
+                    Some(condition) => {
+                        let condition = self.evaluate(&condition)?;
+                        self.is_truthy(&condition)
+                    }
+                    None => true,
+                };
+                self.generator_loop_body(id, taken, body)
             }
-            Value::Tuple(id) => {
-                let elements = if let Some(ManagedObject::Tuple(e)) = self.heap.get(id) {
-                    e.clone()
+            GeneratorFrame::ForEach {
+                pattern, iter, body, ..
+            } => {
+                let (pattern, body) = (pattern.clone(), body.clone());
+                let element = match iter.advance_builtin() {
+                    Some(element) => element,
+                    None => {
+                        // Gerador, protocolo ou handle: o iterador é só uma referência
+                        let mut handle = iter.clone();
+                        self.iter_next(&mut handle)?
+                    }
+                };
+                match element {
+                    Some(element) => {
+                        self.bind_loop_pattern(&pattern, element)?;
+                        self.generator_statement(id, body)
+                    }
+                    None => {
+                        self.pop_generator_frame(id)?;
+                        Ok(None)
+                    }
+                }
+            }
+            GeneratorFrame::Try { finally, stage, .. } => match stage {
+                // O corpo ou o catch terminou normalmente: executa o finally
+                TryStage::Body | TryStage::Catch if finally.is_some() => {
+                    let finally = finally.take().unwrap();
+                    *stage = TryStage::Finally(None);
+                    self.generator_state(id)?
+                        .frames
+                        .push(GeneratorFrame::block(vec![finally]));
+                    Ok(None)
+                }
+                TryStage::Finally(pending) => {
+                    let pending = pending.take();
+                    self.pop_generator_frame(id)?;
+                    match pending {
+                        Some(unwind) => self.generator_unwind(id, unwind),
+                        None => Ok(None),
+                    }
+                }
+                _ => {
+                    self.pop_generator_frame(id)?;
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    fn generator_loop_body(
+        &mut self,
+        id: HeapId,
+        taken: bool,
+        body: Stmt,
+    ) -> Result<Option<GeneratorStep>, DryadError> {
+        if taken {
+            self.generator_statement(id, body)
+        } else {
+            self.pop_generator_frame(id)?;
+            Ok(None)
+        }
+    }
+
+    /// Executa um statement dentro do gerador: sem `yield` roda direto, com `yield` vira frame
+    fn generator_statement(
+        &mut self,
+        id: HeapId,
+        stmt: Stmt,
+    ) -> Result<Option<GeneratorStep>, DryadError> {
+        if !stmt_contains_yield(&stmt) {
+            if let Stmt::VarDeclaration(pattern, ..) = &stmt {
+                for name in pattern_names(pattern) {
+                    self.declare_in_generator(id, &name)?;
+                }
+            }
+            self.execute_statement(&stmt)?;
+            return Ok(None);
+        }
+
+        let frame = match stmt {
+            Stmt::Expression(Expr::Yield(value, _), _) => {
+                let value = self.eval_yield_value(value.as_deref())?;
+                return Ok(Some(GeneratorStep::Yield(value, None)));
+            }
+            Stmt::VarDeclaration(Pattern::Identifier(name), _, Some(Expr::Yield(value, _)), _) => {
+                let value = self.eval_yield_value(value.as_deref())?;
+                return Ok(Some(GeneratorStep::Yield(value, Some(YieldTarget::Declare(name)))));
+            }
+            Stmt::Assignment(Pattern::Identifier(name), Expr::Yield(value, _), _) => {
+                let value = self.eval_yield_value(value.as_deref())?;
+                return Ok(Some(GeneratorStep::Yield(value, Some(YieldTarget::Assign(name)))));
+            }
+            Stmt::Block(statements, _) => GeneratorFrame::block(statements),
+            Stmt::If(condition, then, location) => {
+                let condition = self.evaluate(&condition)?;
+                let taken = self.is_truthy(&condition);
+                self.record_branch(&location, taken);
+                return if taken {
+                    self.generator_statement(id, *then)
                 } else {
-                    return Err(DryadError::from_catalog_fmt(
-                        error_catalog::e3101(),
-                        "Heap error: Expected Tuple",
-                        SourceLocation::unknown(),
-                    ));
+                    Ok(None)
                 };
+            }
+            Stmt::IfElse(condition, then, otherwise, location) => {
+                let condition = self.evaluate(&condition)?;
+                let taken = self.is_truthy(&condition);
+                self.record_branch(&location, taken);
+                return self.generator_statement(id, if taken { *then } else { *otherwise });
+            }
+            Stmt::While(condition, body, location) => GeneratorFrame::While {
+                condition,
+                body: *body,
+                location,
+            },
+            Stmt::DoWhile(body, condition, _) => GeneratorFrame::DoWhile {
+                body: *body,
+                condition,
+                started: false,
+            },
+            Stmt::For(init, condition, update, body, _) => {
+                if let Some(init) = init {
+                    self.execute_statement(&init)?;
+                }
+                GeneratorFrame::For {
+                    condition,
+                    update: update.map(|update| *update),
+                    body: *body,
+                    started: false,
+                }
+            }
+            Stmt::ForEach(pattern, iterable, body, _) => {
+                let iterable = self.evaluate(&iterable)?;
+                let roots_len = self.iteration_roots.len();
+                self.iteration_roots.push(iterable.clone());
+                let iter = self.make_iterator(iterable);
+                self.iteration_roots.truncate(roots_len);
+                let previous = pattern_names(&pattern)
+                    .into_iter()
+                    .map(|name| {
+                        let value = self.env.variables.get(&name).cloned();
+                        (name, value)
+                    })
+                    .collect();
+                GeneratorFrame::ForEach {
+                    pattern,
+                    iter: iter?,
+                    body: *body,
+                    previous,
+                }
+            }
+            Stmt::Try(body, catch, finally, _) => {
+                self.generator_state(id)?.frames.push(GeneratorFrame::Try {
+                    catch: catch.map(|(name, body)| (name, *body)),
+                    finally: finally.map(|finally| *finally),
+                    stage: TryStage::Body,
+                });
+                return self.generator_statement(id, *body);
+            }
+            _ => {
+                return Err(self.runtime_error(
+                    3041,
+                    "yield só pode aparecer como statement (`yield v;`), em `let x = yield v;` ou em `x = yield v;`",
+                ))
+            }
+        };
+        self.generator_state(id)?.frames.push(frame);
+        Ok(None)
+    }
 
-                for element in elements {
-                    // Define a variável de iteração
-                    self.env.variables.insert(var_name.to_string(), element);
+    fn eval_yield_value(&mut self, value: Option<&Expr>) -> Result<Value, DryadError> {
+        match value {
+            Some(expr) => self.evaluate(expr),
+            None => Ok(Value::Null),
+        }
+    }
 
-                    // Executa o corpo do loop
-                    match self.execute_statement(body) {
-                        Ok(value) => last_value = value,
-                        Err(err) if err.code() == 3010 => {
-                            // Break statement
-                            break;
-                        }
-                        Err(err) if err.code() == 3011 => {
-                            // Continue statement - continua para próximo elemento
-                            continue;
-                        }
-                        Err(e) => {
-                            // Restaura valor anterior antes de retornar erro
-                            if let Some(prev_val) = previous_value {
-                                self.env.variables.insert(var_name.to_string(), prev_val);
-                            } else {
-                                self.env.variables.remove(var_name);
-                            }
-                            return Err(e);
-                        }
-                    }
+    /// Remove o frame do topo, restaurando as variáveis que ele sombreava
+    fn pop_generator_frame(&mut self, id: HeapId) -> Result<Option<GeneratorFrame>, DryadError> {
+        let frame = self.generator_state(id)?.frames.pop();
+        match &frame {
+            Some(GeneratorFrame::Block { shadowed, .. })
+            | Some(GeneratorFrame::ForEach {
+                previous: shadowed, ..
+            }) => {
+                for (name, value) in shadowed {
+                    match value {
+                        Some(value) => self.env.variables.insert(name.clone(), value.clone()),
+                        None => self.env.variables.remove(name),
+                    };
                 }
             }
-            Value::String(s) => {
-                // Itera sobre caracteres da string
-                for char in s.chars() {
-                    let char_value = Value::String(char.to_string());
-                    self.env.variables.insert(var_name.to_string(), char_value);
+            _ => {}
+        }
+        Ok(frame)
+    }
 
-                    // Executa o corpo do loop
-                    match self.execute_statement(body) {
-                        Ok(value) => last_value = value,
-                        Err(err) if err.code() == 3010 => {
-                            // Break statement
-                            break;
-                        }
-                        Err(err) if err.code() == 3011 => {
-                            // Continue statement - continua para próximo caractere
-                            continue;
-                        }
-                        Err(e) => {
-                            // Restaura valor anterior antes de retornar erro
-                            if let Some(prev_val) = previous_value {
-                                self.env.variables.insert(var_name.to_string(), prev_val);
-                            } else {
-                                self.env.variables.remove(var_name);
-                            }
-                            return Err(e);
+    /// Desempilha frames por `break`, `continue`, `return` ou erro, passando por catch/finally
+    fn generator_unwind(
+        &mut self,
+        id: HeapId,
+        mut unwind: Unwind,
+    ) -> Result<Option<GeneratorStep>, DryadError> {
+        loop {
+            let is_loop = match self.generator_state(id)?.frames.last() {
+                Some(frame) => frame.is_loop(),
+                None => {
+                    return match unwind {
+                        Unwind::Return(value) => Ok(Some(GeneratorStep::Return(value))),
+                        Unwind::Error(err) => Err(err),
+                        Unwind::Break => Err(self.runtime_error(3010, "break fora de um laço")),
+                        Unwind::Continue => {
+                            Err(self.runtime_error(3011, "continue fora de um laço"))
                         }
                     }
                 }
+            };
+
+            // `continue` mantém o laço na pilha para a próxima iteração
+            if is_loop && matches!(unwind, Unwind::Continue) {
+                return Ok(None);
             }
-            Value::Number(_)
-            | Value::Bool(_)
-            | Value::Null
-            | Value::Exception(_)
-            | Value::Function { .. }
-            | Value::AsyncFunction { .. }
-            | Value::ThreadFunction { .. }
-            | Value::Lambda(_)
-            | Value::Thread { .. }
-            | Value::Mutex { .. }
-            | Value::Promise { .. }
-            | Value::Class(_)
-            | Value::Instance(_)
-            | Value::Object(_)
-            | Value::Result(_, _) => {
-                return Err(DryadError::from_catalog_fmt(
-                    error_catalog::e3030(),
-                    &format!("Valor não é iterável: {}", iterable_value.to_string()),
-                    SourceLocation::unknown(),
-                    ));
+            let frame = self.pop_generator_frame(id)?;
+            if is_loop && matches!(unwind, Unwind::Break) {
+                return Ok(None);
             }
-        }
 
-        // Restaura o valor anterior da variável (se existia)
-        if let Some(prev_val) = previous_value {
-            self.env.variables.insert(var_name.to_string(), prev_val);
-        } else {
-            self.env.variables.remove(var_name);
+            let Some(GeneratorFrame::Try {
+                catch,
+                finally,
+                stage,
+            }) = frame
+            else {
+                continue;
+            };
+            match (stage, unwind) {
+                (TryStage::Body, Unwind::Error(err)) if catch.is_some() => {
+                    let (catch_var, catch_body) = catch.unwrap();
+                    let previous = self.env.variables.insert(
+                        catch_var.clone(),
+                        Value::Exception(err.message().to_string()),
+                    );
+                    let frames = &mut self.generator_state(id)?.frames;
+                    frames.push(GeneratorFrame::Try {
+                        catch: None,
+                        finally,
+                        stage: TryStage::Catch,
+                    });
+                    frames.push(GeneratorFrame::Block {
+                        statements: vec![catch_body],
+                        next: 0,
+                        shadowed: vec![(catch_var, previous)],
+                    });
+                    return Ok(None);
+                }
+                (TryStage::Body | TryStage::Catch, pending) if finally.is_some() => {
+                    let frames = &mut self.generator_state(id)?.frames;
+                    frames.push(GeneratorFrame::Try {
+                        catch: None,
+                        finally: None,
+                        stage: TryStage::Finally(Some(pending)),
+                    });
+                    frames.push(GeneratorFrame::block(vec![finally.unwrap()]));
+                    return Ok(None);
+                }
+                // Um desvio dentro do finally substitui o pendente
+                (_, pending) => unwind = pending,
+            }
         }
-
-        Ok(last_value)
     }
 
     pub fn get_variable(&self, name: &str) -> Option<Value> {
@@ -2505,6 +4279,18 @@ impl Interpreter {
     fn eval_index(&mut self, array_expr: &Expr, index_expr: &Expr) -> Result<Value, DryadError> {
         let array_value = self.evaluate(array_expr)?;
         let index_value = self.evaluate(index_expr)?;
+        self.index_value(array_value, index_value)
+    }
+
+    fn index_value(&mut self, array_value: Value, index_value: Value) -> Result<Value, DryadError> {
+        if matches!(array_value, Value::Instance(_)) && self.has_method(&array_value, "operator[]") {
+            return self.call_method_values(array_value, "operator[]", vec![index_value]);
+        }
+
+        let index_value = match index_value {
+            Value::Int(i) => Value::Number(i as f64),
+            other => other,
+        };
 
         match array_value {
             Value::Array(id) => {
@@ -2593,7 +4379,17 @@ impl Interpreter {
                     ))
                 }
             }
+            Value::Map(id) => {
+                let key = self.collection_key(&index_value)?;
+                Ok(match self.map_entries(id)?.get(&key) {
+                    Some((_, value)) => value.clone(),
+                    None => Value::Null,
+                })
+            }
             Value::Number(_)
+            | Value::Int(_)
+            | Value::BigInt(_)
+            | Value::Decimal(_)
             | Value::Bool(_)
             | Value::String(_)
             | Value::Null
@@ -2608,7 +4404,18 @@ impl Interpreter {
             | Value::Promise { .. }
             | Value::Class { .. }
             | Value::Instance { .. }
-            | Value::Result(_, _) => Err(DryadError::from_catalog_fmt(
+            | Value::Result(_, _)
+            | Value::Enum(_)
+            | Value::Variant(_)
+            | Value::GeneratorFunction { .. }
+            | Value::Generator(_)
+            | Value::Set(_)
+            | Value::Range { .. }
+            | Value::DateTime(_)
+            | Value::Date(_)
+            | Value::Duration(_)
+            | Value::TimeZone(_)
+            | Value::Regex(_) => Err(DryadError::from_catalog_fmt(
                 error_catalog::e3083(),
                 "Operador [] só pode ser usado em arrays e objetos",
                 SourceLocation::unknown(),
@@ -2651,6 +4458,9 @@ impl Interpreter {
                 }
             }
             Value::Number(_)
+            | Value::Int(_)
+            | Value::BigInt(_)
+            | Value::Decimal(_)
             | Value::Bool(_)
             | Value::String(_)
             | Value::Null
@@ -2666,7 +4476,19 @@ impl Interpreter {
             | Value::Class { .. }
             | Value::Instance { .. }
             | Value::Object { .. }
-            | Value::Result(_, _) => Err(DryadError::from_catalog_fmt(
+            | Value::Result(_, _)
+            | Value::Enum(_)
+            | Value::Variant(_)
+            | Value::GeneratorFunction { .. }
+            | Value::Generator(_)
+            | Value::Map(_)
+            | Value::Set(_)
+            | Value::Range { .. }
+            | Value::DateTime(_)
+            | Value::Date(_)
+            | Value::Duration(_)
+            | Value::TimeZone(_)
+            | Value::Regex(_) => Err(DryadError::from_catalog_fmt(
                 error_catalog::e3085(),
                 "Operador . só pode ser usado em tuplas",
                 SourceLocation::unknown(),
@@ -2763,15 +4585,6 @@ impl Interpreter {
         method_name: &str,
         args: &[Expr],
     ) -> Result<Value, DryadError> {
-        self.call_depth += 1;
-        if self.call_depth > MAX_RECURSION_DEPTH {
-            self.call_depth -= 1;
-            return Err(self.runtime_error(
-                3040,
-                "Stack overflow: limite de recursão excedido em chamada de método",
-            ));
-        }
-
         // Extract location from the object expression
         let location = match object_expr {
             Expr::Variable(_, loc)
@@ -2799,24 +4612,68 @@ impl Interpreter {
             | Expr::Super(loc)
             | Expr::Match(_, _, loc)
             | Expr::Spread(_, loc)
+            | Expr::Range(_, _, _, loc)
+            | Expr::Yield(_, loc)
+            | Expr::OptionalChain(_, loc)
+            | Expr::OptionalPropertyAccess(_, _, loc)
+            | Expr::OptionalMethodCall(_, _, _, loc)
+            | Expr::OptionalIndex(_, _, loc)
+            | Expr::NullishCoalescing(_, _, loc)
             | Expr::Try(_, loc) => loc,
         };
-        let result = self.eval_method_call_internal(object_expr, method_name, args, location);
+        let object = self.evaluate(object_expr)?;
+        self.eval_method_call_on(object, method_name, args, location)
+    }
+
+    /// Chama um método sobre um receptor já avaliado (usado também por `?.`)
+    fn eval_method_call_on(
+        &mut self,
+        object: Value,
+        method_name: &str,
+        args: &[Expr],
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        self.call_depth += 1;
+        if self.call_depth > MAX_RECURSION_DEPTH {
+            self.call_depth -= 1;
+            return Err(self.runtime_error(
+                3040,
+                "Stack overflow: limite de recursão excedido em chamada de método",
+            ));
+        }
+        let result = self.eval_method_call_internal(object, method_name, args, location);
         self.call_depth -= 1;
         result
     }
 
     fn eval_method_call_internal(
         &mut self,
-        object_expr: &Expr,
+        object: Value,
         method_name: &str,
         args: &[Expr],
         location: &SourceLocation,
     ) -> Result<Value, DryadError> {
-        let object = self.evaluate(object_expr)?;
-
         match object {
-            Value::Array(_) => self.eval_array_method(object_expr, method_name, args, location),
+            Value::Enum(id) => {
+                let mut field_values = Vec::new();
+                for arg in args {
+                    field_values.push(self.evaluate(arg)?);
+                }
+                self.construct_variant(id, method_name, field_values)
+            }
+            Value::Array(_) => self.eval_array_method(object, method_name, args, location),
+            Value::Map(_) | Value::Set(_) => {
+                self.eval_collection_method(object, method_name, args, location)
+            }
+            Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) => {
+                let mut arg_values = Vec::new();
+                for arg in args {
+                    arg_values.push(self.evaluate(arg)?);
+                }
+                datetime::method(&object, method_name, &arg_values, &self.heap)
+                    .map_err(|e| self.runtime_error(3051, &e))
+            }
+            Value::Regex(regexp) => self.eval_regex_method(*regexp, method_name, args, location),
             Value::Class(id) => {
                 let heap_obj = self.heap.get(id).cloned().ok_or_else(|| {
                     DryadError::from_catalog_fmt(
@@ -2862,8 +4719,10 @@ impl Interpreter {
                         }
 
                         let saved_vars = self.env.variables.clone();
+                        let saved_types = self.env.variable_types.clone();
                         let saved_instance = self.env.current_instance.clone();
                         let saved_class = self.env.current_class.clone();
+                        let qualified_name = format!("{}.{}", class_name, method_name);
 
                         self.env.current_instance = None;
                         self.env.current_class = Some(class_name.to_string());
@@ -2887,14 +4746,22 @@ impl Interpreter {
                                     SourceLocation::unknown(),
                                     ));
                             };
-                            self.env.variables.insert(param_name.clone(), value);
+                            if let Err(e) = self.bind_param(param_name, value, method.signature.param_type(i), &qualified_name) {
+                                self.env.variables = saved_vars;
+                                self.env.variable_types = saved_types;
+                                self.env.current_instance = saved_instance;
+                                self.env.current_class = saved_class;
+                                return Err(e);
+                            }
                         }
 
                         let result = match self.execute_statement(&method.body) {
                             Ok(value) => Ok(value),
                             Err(e) => {
                                 if e.code() == 3021 {
-                                    self.parse_return_value(e.message())
+                                    self.parse_return_value(e.message()).and_then(|value| {
+                                        self.check_return_type(value, &method.signature, &qualified_name)
+                                    })
                                 } else {
                                     Err(e)
                                 }
@@ -2902,6 +4769,7 @@ impl Interpreter {
                         };
 
                         self.env.variables = saved_vars;
+                        self.env.variable_types = saved_types;
                         self.env.current_instance = saved_instance;
                         self.env.current_class = saved_class;
                         result
@@ -2923,6 +4791,29 @@ impl Interpreter {
                     ))
                 }
             }
+            Value::Instance(_) | Value::Object(_) | Value::Generator(_) => {
+                let mut arg_values = Vec::new();
+                for arg in args {
+                    arg_values.push(self.evaluate(arg)?);
+                }
+                self.call_method_values(object, method_name, arg_values)
+            }
+            _ => Err(DryadError::from_catalog_fmt(
+                error_catalog::e3028(),
+                "Tentativa de chamar método em valor que não é uma instância ou objeto",
+                SourceLocation::unknown(),
+                )),
+        }
+    }
+
+    /// Chama um método de instância, objeto ou gerador com argumentos já avaliados
+    fn call_method_values(
+        &mut self,
+        receiver: Value,
+        method_name: &str,
+        arg_values: Vec<Value>,
+    ) -> Result<Value, DryadError> {
+        match receiver {
             Value::Instance(id) => {
                 let heap_obj = self.heap.get(id).cloned().ok_or_else(|| {
                     DryadError::from_catalog_fmt(
@@ -2961,14 +4852,12 @@ impl Interpreter {
                                         ));
                                 }
 
-                                let mut arg_values = Vec::new();
-                                for arg in args {
-                                    arg_values.push(self.evaluate(arg)?);
-                                }
 
                                 let saved_vars = self.env.variables.clone();
+                                let saved_types = self.env.variable_types.clone();
                                 let saved_instance = self.env.current_instance.clone();
                                 let saved_class = self.env.current_class.clone();
+                                let qualified_name = format!("{}.{}", class_name, method_name);
 
                                 self.env.current_instance = Some(Value::Instance(id));
                                 self.env.current_class = Some(class_name.to_string());
@@ -2994,14 +4883,27 @@ impl Interpreter {
                                             SourceLocation::unknown(),
                                             ));
                                     };
-                                    self.env.variables.insert(param_name.clone(), value);
+                                    if let Err(e) = self.bind_param(
+                                        param_name,
+                                        value,
+                                        method.signature.param_type(i),
+                                        &qualified_name,
+                                    ) {
+                                        self.env.variables = saved_vars;
+                                        self.env.variable_types = saved_types;
+                                        self.env.current_instance = saved_instance;
+                                        self.env.current_class = saved_class;
+                                        return Err(e);
+                                    }
                                 }
 
                                 let result = match self.execute_statement(&method.body) {
                                     Ok(value) => Ok(value),
                                     Err(e) => {
                                         if e.code() == 3021 {
-                                            self.parse_return_value(e.message())
+                                            self.parse_return_value(e.message()).and_then(|value| {
+                                                self.check_return_type(value, &method.signature, &qualified_name)
+                                            })
                                         } else {
                                             Err(e)
                                         }
@@ -3009,6 +4911,7 @@ impl Interpreter {
                                 };
 
                                 self.env.variables = saved_vars;
+                                self.env.variable_types = saved_types;
                                 self.env.current_instance = saved_instance;
                                 self.env.current_class = saved_class;
                                 result
@@ -3063,10 +4966,6 @@ impl Interpreter {
                         let properties = properties.clone();
                         let methods = methods.clone();
 
-                        let mut arg_values = Vec::new();
-                        for arg in args {
-                            arg_values.push(self.evaluate(arg)?);
-                        }
 
                         let saved_vars = self.env.variables.clone();
                         let saved_instance = self.env.current_instance.clone();
@@ -3111,10 +5010,6 @@ impl Interpreter {
                     } else if let Some(func_value) = properties.get(method_name) {
                         match func_value {
                             Value::Function { params, body, .. } => {
-                                let mut arg_values = Vec::new();
-                                for arg in args {
-                                    arg_values.push(self.evaluate(arg)?);
-                                }
 
                                 let saved_vars = self.env.variables.clone();
 
@@ -3158,6 +5053,8 @@ impl Interpreter {
                                 SourceLocation::unknown(),
                                 )),
                         }
+                    } else if matches!(method_name, "keys" | "values" | "entries") {
+                        Ok(self.object_entries(&properties, method_name))
                     } else {
                         Err(DryadError::from_catalog_fmt(
                             error_catalog::e3026(),
@@ -3173,6 +5070,7 @@ impl Interpreter {
                     ))
                 }
             }
+            Value::Generator(id) => self.eval_generator_method(id, method_name, arg_values),
             _ => Err(DryadError::from_catalog_fmt(
                 error_catalog::e3028(),
                 "Tentativa de chamar método em valor que não é uma instância ou objeto",
@@ -3181,14 +5079,93 @@ impl Interpreter {
         }
     }
 
+    /// Cria um valor `Enum.Variante(campos...)`, verificando a variante e o número de campos
+    fn construct_variant(
+        &mut self,
+        enum_id: HeapId,
+        variant_name: &str,
+        values: Vec<Value>,
+    ) -> Result<Value, DryadError> {
+        let (enum_name, field_names) = match self.heap.get(enum_id) {
+            Some(ManagedObject::Enum { name, variants }) => {
+                match variants.iter().find(|(variant, _)| variant == variant_name) {
+                    Some((_, fields)) => (name.clone(), fields.clone()),
+                    None => {
+                        return Err(self.runtime_error(
+                            3026,
+                            &format!("Enum '{}' não tem a variante '{}'", name, variant_name),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err(DryadError::from_catalog_fmt(
+                    error_catalog::e3100(),
+                    "Heap error: Enum reference not found",
+                    SourceLocation::unknown(),
+                ))
+            }
+        };
+
+        if values.len() != field_names.len() {
+            return Err(self.runtime_error(
+                3025,
+                &format!(
+                    "Variante '{}.{}' espera {} campo(s), recebeu {}",
+                    enum_name,
+                    variant_name,
+                    field_names.len(),
+                    values.len()
+                ),
+            ));
+        }
+
+        let variant_id = self.heap.allocate(ManagedObject::Variant {
+            enum_name,
+            variant: variant_name.to_string(),
+            fields: field_names.into_iter().zip(values).collect(),
+        });
+        Ok(Value::Variant(variant_id))
+    }
+
     fn eval_property_access(
         &mut self,
         object_expr: &Expr,
         property_name: &str,
     ) -> Result<Value, DryadError> {
         let object = self.evaluate(object_expr)?;
+        self.property_of(object, property_name)
+    }
+
+    fn property_of(&mut self, object: Value, property_name: &str) -> Result<Value, DryadError> {
 
         match object {
+            // Enum.Variante sem parênteses: variante sem campos
+            Value::Enum(id) => self.construct_variant(id, property_name, Vec::new()),
+            Value::Variant(id) => match self.heap.get(id) {
+                Some(ManagedObject::Variant {
+                    enum_name,
+                    variant,
+                    fields,
+                }) => fields
+                    .iter()
+                    .find(|(name, _)| name == property_name)
+                    .map(|(_, value)| value.clone())
+                    .ok_or_else(|| {
+                        self.runtime_error(
+                            3026,
+                            &format!(
+                                "Variante '{}.{}' não tem o campo '{}'",
+                                enum_name, variant, property_name
+                            ),
+                        )
+                    }),
+                _ => Err(DryadError::from_catalog_fmt(
+                    error_catalog::e3100(),
+                    "Heap error: Variant reference not found",
+                    SourceLocation::unknown(),
+                )),
+            },
             Value::Class(id) => {
                 let heap_obj = self.heap.get(id).ok_or_else(|| {
                     DryadError::from_catalog_fmt(
@@ -3417,6 +5394,28 @@ impl Interpreter {
                     ))
                 }
             }
+            Value::Regex(ref regexp) => regexp::property(regexp, property_name, &mut self.heap).map_err(|e| {
+                DryadError::from_catalog_fmt(error_catalog::e3030(), &e, SourceLocation::unknown())
+            }),
+            Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) => {
+                datetime::property(&object, property_name).map_err(|e| {
+                    DryadError::from_catalog_fmt(error_catalog::e3030(), &e, SourceLocation::unknown())
+                })
+            }
+            Value::Map(_) | Value::Set(_) => {
+                if property_name == "size" {
+                    self.collection_size(&object)
+                } else {
+                    Err(DryadError::from_catalog_fmt(
+                        error_catalog::e3030(),
+                        &format!(
+                            "Propriedade '{}' não encontrada em Map/Set; use get()/has()",
+                            property_name
+                        ),
+                        SourceLocation::unknown(),
+                    ))
+                }
+            }
             Value::Array(id) => {
                 if property_name == "length" {
                     let heap_obj = self.heap.get(id).ok_or_else(|| {
@@ -3490,9 +5489,17 @@ impl Interpreter {
             if let ManagedObject::Class {
                 methods,
                 properties,
+                is_abstract,
                 ..
             } = class_obj
             {
+                if *is_abstract {
+                    return Err(DryadError::from_catalog_fmt(
+                        error_catalog::e3049(),
+                        &format!("Classe abstrata '{}' não pode ser instanciada", class_name),
+                        location.clone(),
+                    ));
+                }
                 let methods = methods.clone();
                 let properties = properties.clone();
 
@@ -3529,8 +5536,10 @@ impl Interpreter {
 
                     // Save current state
                     self.env.call_stack_vars.push(self.env.variables.clone());
+                    let saved_types = self.env.variable_types.clone();
                     let saved_instance = self.env.current_instance.clone();
                     let saved_class = self.env.current_class.clone();
+                    let qualified_name = format!("{}.init", class_name);
 
                     // Set up constructor context
                     self.env.current_instance = Some(instance.clone());
@@ -3553,7 +5562,15 @@ impl Interpreter {
                                 SourceLocation::unknown(),
                                 ));
                         };
-                        self.env.variables.insert(param_name.clone(), value);
+                        if let Err(e) = self.bind_param(param_name, value, init_method.signature.param_type(i), &qualified_name) {
+                            if let Some(saved) = self.env.call_stack_vars.pop() {
+                                self.env.variables = saved;
+                            }
+                            self.env.variable_types = saved_types;
+                            self.env.current_instance = saved_instance;
+                            self.env.current_class = saved_class;
+                            return Err(e);
+                        }
                     }
 
                     // Execute constructor
@@ -3566,6 +5583,7 @@ impl Interpreter {
                                 if let Some(saved) = self.env.call_stack_vars.pop() {
                                     self.env.variables = saved;
                                 }
+                                self.env.variable_types = saved_types;
                                 self.env.current_instance = saved_instance;
                                 self.env.current_class = saved_class;
                                 return Err(e);
@@ -3577,6 +5595,7 @@ impl Interpreter {
                     if let Some(saved) = self.env.call_stack_vars.pop() {
                         self.env.variables = saved;
                     }
+                    self.env.variable_types = saved_types;
                     self.env.current_instance = saved_instance;
                     self.env.current_class = saved_class;
                 } else if !args.is_empty() {
@@ -3686,7 +5705,10 @@ impl Interpreter {
 
         Err(DryadError::from_catalog_fmt(
             error_catalog::e3100(),
-            &format!("Nenhum padrão corresponde ao valor: {}", value.to_string()),
+            &format!(
+                "Nenhum padrão corresponde ao valor: {}",
+                self.inspect_value(&value, &InspectOptions::default())
+            ),
             SourceLocation::unknown(),
             ))
     }
@@ -3703,14 +5725,16 @@ impl Interpreter {
                 bindings.insert(name.clone(), value.clone());
                 true
             }
+            // `/regex/` casa strings em que a regex encontra um trecho
+            Pattern::Literal(Literal::Regex(source, flags)) => match (value, Regexp::compile(source, flags)) {
+                (Value::String(text), Ok(regexp)) => regexp.regex.is_match(text),
+                _ => false,
+            },
             Pattern::Literal(lit) => {
-                let val = match lit {
-                    Literal::Bool(b) => Value::Bool(*b),
-                    Literal::Number(n) => Value::Number(*n),
-                    Literal::String(s) => Value::String(s.clone()),
-                    Literal::Null => Value::Null,
-                };
-                self.values_equal(value, &val)
+                match self.eval_literal(lit) {
+                    Ok(val) => self.values_equal(value, &val),
+                    Err(_) => false,
+                }
             }
             Pattern::Array(patterns) => {
                 if let Value::Array(id) = value {
@@ -3791,6 +5815,33 @@ impl Interpreter {
                     false
                 }
             }
+            Pattern::Constructor(enum_name, variant_name, patterns) => {
+                let (variant_enum, variant, fields) = match value {
+                    Value::Variant(id) => match self.heap.get(*id) {
+                        Some(ManagedObject::Variant {
+                            enum_name,
+                            variant,
+                            fields,
+                        }) => (enum_name.clone(), variant.clone(), fields.clone()),
+                        _ => return false,
+                    },
+                    _ => return false,
+                };
+                if enum_name.as_ref().is_some_and(|name| *name != variant_enum)
+                    || variant != *variant_name
+                {
+                    return false;
+                }
+                // Sem padrões de campo, casa qualquer valor da variante
+                if patterns.is_empty() {
+                    return true;
+                }
+                patterns.len() == fields.len()
+                    && patterns
+                        .iter()
+                        .zip(&fields)
+                        .all(|(p, (_, field))| self.match_pattern(field, p, bindings))
+            }
             Pattern::Rest(name) => {
                 // Pattern::Rest(name) outside of an Array/Object pattern should probably be an error or equivalent to Identifier.
                 // However, the current structure handles it inside Array/Object logic.
@@ -3977,7 +6028,8 @@ impl Interpreter {
         })?;
 
         // 4. Fazer lexing e parsing do módulo
-        let mut lexer = dryad_lexer::lexer::Lexer::new(&source_code);
+        let mut lexer =
+            dryad_lexer::lexer::Lexer::new_with_file(&source_code, resolved_path.clone());
         let mut tokens = Vec::new();
 
         // Coletar todos os tokens
@@ -4048,6 +6100,10 @@ impl Interpreter {
         // Definir contexto do módulo
         self.current_file_path = Some(module_path.clone());
 
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.register_program(module_path, program);
+        }
+
         // Executar todas as declarações do módulo
         let mut exported_symbols = HashMap::new();
 
@@ -4071,11 +6127,16 @@ impl Interpreter {
                                 exported_symbols.insert(name.clone(), value.clone());
                             }
                         }
-                        Stmt::ClassDeclaration(name, _, _, _, _) => {
+                        Stmt::ClassDeclaration(name, _, _, _, _, _, _) => {
                             if let Some(value) = self.env.classes.get(name) {
                                 exported_symbols.insert(name.clone(), value.clone());
                             }
                         }
+                        Stmt::EnumDeclaration(name, _, _) => {
+                            if let Some(value) = self.env.variables.get(name) {
+                                exported_symbols.insert(name.clone(), value.clone());
+                            }
+                        }
                         _ => {} // Outros tipos de export
                     }
                 }
@@ -4146,7 +6207,8 @@ impl Interpreter {
                     )
             })?;
 
-            let mut lexer = dryad_lexer::lexer::Lexer::new(&source_code);
+            let mut lexer =
+                dryad_lexer::lexer::Lexer::new_with_file(&source_code, resolved_path.clone());
             let mut tokens = Vec::new();
 
             loop {
@@ -4282,6 +6344,403 @@ impl Interpreter {
         self.native_registry.manager.list_active_functions()
     }
 
+    /// `objeto.prop = valor`, passando pelo setter quando a classe define um
+    fn execute_property_assignment(
+        &mut self,
+        object_expr: &Expr,
+        property_name: &String,
+        value_expr: &Expr,
+    ) -> Result<Value, DryadError> {
+        let value = self.evaluate(value_expr)?;
+        let object = self.evaluate(object_expr)?;
+
+        match object {
+            Value::Instance(instance_id) => {
+                // Get class info to check for setter
+                let class_name = {
+                    let heap_obj = self.heap.get(instance_id).ok_or_else(|| {
+                        DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Instance reference not found", SourceLocation::unknown())
+                    })?;
+                    if let ManagedObject::Instance { class_name, .. } = heap_obj {
+                        class_name.clone()
+                    } else {
+                        return Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Instance", SourceLocation::unknown()));
+                    }
+                };
+
+                // Look up setter in class (clone it out of the heap so we don't hold a borrow)
+                let setter_clone_opt = if let Some(Value::Class(cid)) = self.env.classes.get(&class_name) {
+                    if let Some(ManagedObject::Class { setters, .. }) = self.heap.get(*cid) {
+                        setters.get(property_name).cloned()
+                    } else { None }
+                } else { None };
+
+                if let Some(setter) = setter_clone_opt {
+                    // Visibility check
+                    if !self.check_visibility(&setter.visibility, &class_name) {
+                        return Err(DryadError::from_catalog_fmt(
+                            error_catalog::e3029(),
+                            &format!("Setter '{}' não é acessível (visibilidade: {:?})", property_name, setter.visibility),
+                            SourceLocation::unknown(),
+                            ));
+                    }
+
+                    // Execute setter with 'this' and parameter (no heap borrow held)
+                    let mut setter_env = self.env.clone();
+                    let saved_class = self.env.current_class.clone();
+                    setter_env.variables.insert("this".to_string(), object.clone());
+                    setter_env.current_class = Some(class_name.to_string());
+                    setter_env.variables.insert(setter.param.clone(), value.clone());
+                    let prev_env = std::mem::replace(&mut self.env, setter_env);
+                    let _ = self.execute_statement(&setter.body);
+                    self.env = prev_env;
+                    self.env.current_class = saved_class;
+                    return Ok(value);
+                }
+
+                // No setter found — perform visibility checks first (no mutable heap borrow yet)
+                if let Some(Value::Class(cid)) = self.env.classes.get(&class_name) {
+                    if let Some(ManagedObject::Class { properties: class_props, .. }) = self.heap.get(*cid) {
+                        if let Some(prop_def) = class_props.get(property_name) {
+                            if !self.check_visibility(&prop_def.visibility, &class_name) {
+                                return Err(DryadError::from_catalog_fmt(
+                                    error_catalog::e3029(),
+                                    &format!("Propriedade '{}' não é acessível para escrita (visibilidade: {:?})", property_name, prop_def.visibility),
+                                    SourceLocation::unknown(),
+                                    ));
+                            }
+                        }
+                    }
+                }
+
+                let declared = self.declared_property_type(&class_name, property_name);
+                self.check_declared_type(
+                    &value,
+                    declared.as_ref(),
+                    || format!("Propriedade '{}' de '{}'", property_name, class_name),
+                )?;
+
+                // Now acquire mutable access to the instance and set property
+                let heap_obj = self.heap.get_mut(instance_id).ok_or_else(|| {
+                    DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Instance reference not found", SourceLocation::unknown())
+                })?;
+                
+                if let ManagedObject::Instance { properties, .. } = heap_obj {
+                    properties.insert(property_name.clone(), value.clone());
+                    Ok(value)
+                } else {
+                    Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Instance", SourceLocation::unknown()))
+                }
+            }
+            Value::Object(id) => {
+                let heap_obj = self.heap.get_mut(id).ok_or_else(|| {
+                    DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Object reference not found", SourceLocation::unknown())
+                })?;
+                
+                if let ManagedObject::Object { properties, .. } = heap_obj {
+                    properties.insert(property_name.clone(), value.clone());
+                    Ok(value)
+                } else {
+                    Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Object", SourceLocation::unknown()))
+                }
+            }
+            Value::Class(id) => {
+                // Clone required data first to avoid holding mutable borrows while executing setters
+                let (class_name_clone, setter_clone_opt, prop_def_opt) = if let Some(ManagedObject::Class { name, properties, setters, .. }) = self.heap.get(id) {
+                    (
+                        name.clone(),
+                        setters.get(property_name).cloned(),
+                        properties.get(property_name).cloned(),
+                    )
+                } else {
+                    return Err(DryadError::from_catalog_fmt(error_catalog::e3101(), "Heap error: Expected Class", SourceLocation::unknown()));
+                };
+
+                // If there's a static setter, execute it (we have a cloned copy)
+                if let Some(setter) = setter_clone_opt {
+                    if !setter.is_static {
+                        return Err(DryadError::from_catalog_fmt(
+                            error_catalog::e3029(),
+                            &format!("Setter '{}' não é estático", property_name),
+                            SourceLocation::unknown(),
+                            ));
+                    }
+
+                    if !self.check_visibility(&setter.visibility, &class_name_clone) {
+                        return Err(DryadError::from_catalog_fmt(
+                            error_catalog::e3029(),
+                            &format!("Setter '{}' não é acessível (visibilidade: {:?})", property_name, setter.visibility),
+                            SourceLocation::unknown(),
+                            ));
+                    }
+
+                    // Execute setter with 'current_class' set
+                    let mut setter_env = self.env.clone();
+                    let saved_class = self.env.current_class.clone();
+                    setter_env.current_class = Some(class_name_clone.clone());
+                    setter_env.variables.insert(setter.param.clone(), value.clone());
+                    let prev_env = std::mem::replace(&mut self.env, setter_env);
+                    let _ = self.execute_statement(&setter.body);
+                    self.env = prev_env;
+                    self.env.current_class = saved_class;
+                    return Ok(value);
+                }
+
+                // If static property exists, validate visibility and update it with a fresh mutable borrow
+                if let Some(prop_def) = prop_def_opt {
+                    if !prop_def.is_static {
+                        return Err(DryadError::from_catalog_fmt(
+                            error_catalog::e3029(),
+                            &format!("Propriedade '{}' não é estática na classe '{}'", property_name, class_name_clone),
+                            SourceLocation::unknown(),
+                            ));
+                    }
+
+                    if !self.check_visibility(&prop_def.visibility, &class_name_clone) {
+                        return Err(DryadError::from_catalog_fmt(
+                            error_catalog::e3029(),
+                            &format!("Propriedade estática '{}' não é acessível para escrita (visibilidade: {:?})", property_name, prop_def.visibility),
+                            SourceLocation::unknown(),
+                            ));
+                    }
+                    self.check_declared_type(
+                        &value,
+                        prop_def.declared_type.as_ref(),
+                        || format!("Propriedade '{}' de '{}'", property_name, class_name_clone),
+                    )?;
+
+                    // Mutate the class property with a mutable borrow
+                    let heap_obj_mut = self.heap.get_mut(id).ok_or_else(|| {
+                        DryadError::from_catalog_fmt(error_catalog::e3100(), "Heap error: Class reference not found", SourceLocation::unknown())
+                    })?;
+
+                    if let ManagedObject::Class { properties, .. } = heap_obj_mut {
+                        if let Some(prop) = properties.get_mut(property_name) {
+                            prop.default_value = Some(value.clone());
+                            return Ok(value);
+                        }
+                    }
+
+                    return Err(DryadError::from_catalog_fmt(
+                        error_catalog::e3030(),
+                        &format!("Propriedade estática '{}' não encontrada na classe '{}'", property_name, class_name_clone),
+                        SourceLocation::unknown(),
+                        ));
+                }
+
+                Err(DryadError::from_catalog_fmt(error_catalog::e3030(), &format!("Propriedade estática '{}' não encontrada na classe '{}'", property_name, class_name_clone), SourceLocation::unknown()))
+            }
+            _ => Err(self.runtime_error(3034, "Tentativa de atribuir propriedade a valor que não é uma instância, classe ou objeto"))
+        }
+    }
+
+    /// Monta a classe (com os membros herdados) e a registra no escopo atual
+    fn execute_class_declaration(
+        &mut self,
+        name: &str,
+        parent: &Option<String>,
+        interfaces: &[String],
+        members: &[ClassMember],
+        is_abstract: bool,
+        location: &SourceLocation,
+    ) -> Result<Value, DryadError> {
+        // Membros herdados são copiados da superclasse e sobrescritos pelos próprios
+        let inherited = parent.as_ref().and_then(|p| match self.env.classes.get(p) {
+            Some(Value::Class(id)) => self.heap.get(*id).cloned(),
+            _ => None,
+        });
+        let (mut methods, mut properties, mut getters, mut setters) = match inherited {
+            Some(ManagedObject::Class { methods, properties, getters, setters, .. }) => {
+                (methods, properties, getters, setters)
+            }
+            _ => (HashMap::new(), HashMap::new(), HashMap::new(), HashMap::new()),
+        };
+
+        // Process class members
+        for member in members {
+            match member {
+                ClassMember::Method {
+                    visibility,
+                    is_static,
+                    is_async: _,
+                    is_abstract,
+                    name: method_name,
+                    params,
+                    body,
+                    return_type,
+                } => {
+                    let params_vec: Vec<(String, Option<Expr>)> = params
+                        .iter()
+                        .map(|(p, _, d)| (p.clone(), d.clone()))
+                        .collect();
+                    let method = ClassMethod {
+                        visibility: visibility.clone(),
+                        is_static: *is_static,
+                        params: params_vec,
+                        body: *(*body).clone(),
+                        signature: Signature::new(params, return_type),
+                        is_abstract: *is_abstract,
+                    };
+                    methods.insert(method_name.clone(), method);
+                }
+                ClassMember::Property(
+                    visibility,
+                    is_static,
+                    prop_name,
+                    declared_type,
+                    default_value,
+                ) => {
+                    let default_val = match default_value {
+                        Some(expr) => Some(self.evaluate(expr)?),
+                        None => None,
+                    };
+                    if let Some(value) = &default_val {
+                        self.check_declared_type(
+                            value,
+                            declared_type.as_ref(),
+                            || format!("Propriedade '{}' de '{}'", prop_name, name),
+                        )?;
+                    }
+                    let property = ClassProperty {
+                        visibility: visibility.clone(),
+                        is_static: *is_static,
+                        default_value: default_val,
+                        declared_type: declared_type.clone(),
+                    };
+                    properties.insert(prop_name.clone(), property);
+                }
+                ClassMember::Getter {
+                    visibility,
+                    is_static,
+                    name: getter_name,
+                    body,
+                } => {
+                    let getter = ClassGetter {
+                        visibility: visibility.clone(),
+                        is_static: *is_static,
+                        name: getter_name.clone(),
+                        body: *(*body).clone(),
+                    };
+                    getters.insert(getter_name.clone(), getter);
+                }
+                ClassMember::Setter {
+                    visibility,
+                    is_static,
+                    name: setter_name,
+                    param,
+                    body,
+                } => {
+                    let setter = ClassSetter {
+                        visibility: visibility.clone(),
+                        is_static: *is_static,
+                        name: setter_name.clone(),
+                        param: param.clone(),
+                        body: *(*body).clone(),
+                    };
+                    setters.insert(setter_name.clone(), setter);
+                }
+            }
+        }
+
+        // Verify interfaces are implemented
+        let mut problems = Vec::new();
+        for interface_name in interfaces {
+            let Some(interface) = self.env.interfaces.get(interface_name).cloned() else {
+                return Err(DryadError::from_catalog_fmt(
+                    error_catalog::e3102(),
+                    &format!("Interface '{}' não encontrada", interface_name),
+                    location.clone(),
+                ));
+            };
+
+            for InterfaceMember::Method(interface_method) in &interface {
+                let expected = interface_method.params.len();
+                match methods.get(&interface_method.name) {
+                    Some(method) => {
+                        let total = method.params.len();
+                        let required = method.params.iter().filter(|(_, d)| d.is_none()).count();
+                        if expected < required || expected > total {
+                            problems.push(format!(
+                                "'{}' de '{}' espera {} parâmetro(s), mas a classe declara {}",
+                                interface_method.name, interface_name, expected, total
+                            ));
+                        }
+                    }
+                    // Implementação padrão da interface é copiada para a classe
+                    None if interface_method.body.is_some() => {
+                        let params = &interface_method.params;
+                        methods.insert(
+                            interface_method.name.clone(),
+                            ClassMethod {
+                                visibility: Visibility::Public,
+                                is_static: false,
+                                params: params.iter().map(|(p, _, d)| (p.clone(), d.clone())).collect(),
+                                body: (**interface_method.body.as_ref().unwrap()).clone(),
+                                signature: Signature::new(params, &interface_method.return_type),
+                                is_abstract: false,
+                            },
+                        );
+                    }
+                    None => problems.push(format!(
+                        "'{}' de '{}' não implementado",
+                        interface_method.name, interface_name
+                    )),
+                }
+            }
+        }
+        if !problems.is_empty() {
+            return Err(DryadError::from_catalog_fmt(
+                error_catalog::e3103(),
+                &format!(
+                    "Classe '{}' não satisfaz suas interfaces: {}",
+                    name,
+                    problems.join("; ")
+                ),
+                location.clone(),
+            ));
+        }
+
+        // Classes concretas precisam implementar todos os métodos abstratos
+        if !is_abstract {
+            let mut missing: Vec<&String> = methods
+                .iter()
+                .filter(|(_, m)| m.is_abstract)
+                .map(|(n, _)| n)
+                .collect();
+            if !missing.is_empty() {
+                missing.sort();
+                let missing: Vec<String> = missing.iter().map(|n| format!("'{}'", n)).collect();
+                return Err(DryadError::from_catalog_fmt(
+                    error_catalog::e3050(),
+                    &format!(
+                        "Classe '{}' não implementa os métodos abstratos: {}",
+                        name,
+                        missing.join(", ")
+                    ),
+                    location.clone(),
+                ));
+            }
+        }
+
+        let managed_class = ManagedObject::Class {
+            name: name.to_string(),
+            parent: parent.clone(),
+            interfaces: interfaces.to_vec(),
+            methods,
+            properties,
+            getters,
+            setters,
+            is_abstract,
+        };
+        let class_id = self.heap.allocate(managed_class);
+        self.maybe_collect_garbage();
+        let class = Value::Class(class_id);
+
+        self.env.classes.insert(name.to_string(), class.clone());
+        self.env.variables.insert(name.to_string(), class); // Também disponível como variável
+        Ok(Value::Null)
+    }
+
     fn execute_index_assignment(
         &mut self,
         array_expr: &Expr,
@@ -4291,6 +6750,10 @@ impl Interpreter {
         let target = self.evaluate(array_expr)?;
 
         match target {
+            Value::Instance(_) if self.has_method(&target, "operator[]=") => {
+                self.call_method_values(target, "operator[]=", vec![index_value, value.clone()])?;
+                Ok(value)
+            }
             Value::Array(id) => {
                 let index = match index_value {
                     Value::Number(n) => {
@@ -4374,6 +6837,10 @@ impl Interpreter {
                     ))
                 }
             }
+            Value::Map(id) => {
+                self.map_insert(id, index_value, value.clone())?;
+                Ok(value)
+            }
             _ => Err(DryadError::from_catalog_fmt(
                 error_catalog::e3085(),
                 "Tentativa de atribuir índice a valor que não é array nem objeto",
@@ -4394,14 +6861,28 @@ impl Interpreter {
                 params,
                 rest_param,
                 body,
+                signature,
             } => self.call_user_function_values(
                 name.clone(),
                 params.clone(),
                 rest_param.clone(),
                 body.clone(),
+                signature.clone(),
                 args,
                 location,
             ),
+            Value::GeneratorFunction {
+                name,
+                params,
+                rest_param,
+                body,
+            } => self.create_generator(
+                name.clone(),
+                params.clone(),
+                rest_param.clone(),
+                body.clone(),
+                args,
+            ),
             Value::Lambda(id) => {
                 let heap_obj = self.heap.get(*id).ok_or_else(|| {
                     DryadError::from_catalog_fmt(
@@ -4415,12 +6896,14 @@ impl Interpreter {
                     params,
                     body,
                     closure,
+                    signature,
                 } = heap_obj
                 {
                     self.call_lambda_values(
                         params.clone(),
                         body.clone(),
                         closure.clone(),
+                        signature.clone(),
                         args,
                         location,
                     )
@@ -4442,7 +6925,7 @@ impl Interpreter {
 
     fn eval_array_method(
         &mut self,
-        object_expr: &Expr,
+        object: Value,
         method_name: &str,
         args: &[Expr],
         location: &SourceLocation,
@@ -4453,8 +6936,6 @@ impl Interpreter {
             arg_values.push(self.evaluate(arg)?);
         }
 
-        let object = self.evaluate(object_expr)?;
-
         if let Value::Array(id) = object {
             // "Take" os elementos do heap temporariamente para satisfazer o borrow checker
             let mut elements = match self.heap.get_mut(id) {
@@ -0,0 +1,360 @@
+// crates/dryad_runtime/src/iteration.rs
+//! Protocolo de iteração e geradores do interpretador.
+//!
+//! O `for-in` percorre qualquer valor convertido em um [`ValueIter`]: arrays,
+//! tuplas, strings, chaves de objetos, intervalos preguiçosos (`0..n`),
+//! geradores, objetos que implementam `iterator()`/`next()` e handles nativos
+//! (como arquivos abertos, percorridos linha a linha).
+//!
+//! Um gerador (`function*`) guarda o próprio estado de execução como uma pilha
+//! de [`GeneratorFrame`] no heap. Statements que não contêm `yield` são
+//! executados normalmente; blocos, `if`, laços e `try` que contêm `yield` viram
+//! frames, o que permite suspender no `yield` e retomar no `next()` seguinte
+//! sem depender da pilha de Rust.
+
+use crate::native_modules::NativeFunction;
+use crate::value::Value;
+use dryad_errors::{DryadError, SourceLocation};
+use dryad_parser::ast::{Expr, MatchArm, ObjectProperty, Pattern, Stmt};
+use std::collections::HashMap;
+
+/// Iterador em andamento de um `for-in` ou de um frame de gerador
+#[derive(Debug, Clone)]
+pub enum ValueIter {
+    /// Valores já materializados (elementos, caracteres, chaves de objeto)
+    Values { values: Vec<Value>, next: usize },
+    /// Intervalo numérico preguiçoso, com passo 1
+    Range { next: f64, end: f64, inclusive: bool },
+    /// Gerador criado por uma `function*` (`Value::Generator`)
+    Generator(Value),
+    /// Objeto cujo `next()` retorna `{ value, done }`
+    Protocol(Value),
+    /// Handle de um módulo nativo; `next(handle)` retorna o próximo valor ou
+    /// `null` no fim
+    Native { handle: Value, next: NativeFunction },
+}
+
+impl ValueIter {
+    pub fn from_values(values: Vec<Value>) -> Self {
+        ValueIter::Values { values, next: 0 }
+    }
+
+    /// Avança iteradores que não executam código Dryad.
+    /// Retorna `None` para geradores, objetos do protocolo e handles nativos.
+    pub fn advance_builtin(&mut self) -> Option<Option<Value>> {
+        match self {
+            ValueIter::Values { values, next } => {
+                let value = values.get(*next).cloned();
+                if value.is_some() {
+                    *next += 1;
+                }
+                Some(value)
+            }
+            ValueIter::Range {
+                next,
+                end,
+                inclusive,
+            } => {
+                let more = if *inclusive { *next <= *end } else { *next < *end };
+                if !more {
+                    return Some(None);
+                }
+                let value = *next;
+                *next += 1.0;
+                Some(Some(Value::Number(value)))
+            }
+            ValueIter::Generator(_) | ValueIter::Protocol(_) | ValueIter::Native { .. } => None,
+        }
+    }
+
+    /// Objeto do qual o iterador depende para avançar (gerador, protocolo ou
+    /// handle nativo)
+    pub fn handle(&self) -> Option<&Value> {
+        match self {
+            ValueIter::Generator(value) | ValueIter::Protocol(value) | ValueIter::Native { handle: value, .. } => {
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    fn values(&self) -> Vec<&Value> {
+        match self {
+            ValueIter::Values { values, next } => values[(*next).min(values.len())..].iter().collect(),
+            ValueIter::Range { .. } => Vec::new(),
+            ValueIter::Generator(value) | ValueIter::Protocol(value) | ValueIter::Native { handle: value, .. } => {
+                vec![value]
+            }
+        }
+    }
+}
+
+/// Estado de um gerador, guardado em `ManagedObject::Generator`
+#[derive(Debug, Clone)]
+pub struct GeneratorState {
+    pub name: String,
+    /// Variáveis do gerador enquanto suspenso
+    pub variables: HashMap<String, Value>,
+    /// Pilha de execução (o topo é o último)
+    pub frames: Vec<GeneratorFrame>,
+    pub status: GeneratorStatus,
+}
+
+impl GeneratorState {
+    pub fn new(name: String, variables: HashMap<String, Value>, body: &Stmt) -> Self {
+        let statements = match body {
+            Stmt::Block(statements, _) => statements.clone(),
+            other => vec![other.clone()],
+        };
+        Self {
+            name,
+            variables,
+            frames: vec![GeneratorFrame::block(statements)],
+            status: GeneratorStatus::Created,
+        }
+    }
+
+    /// Valores alcançáveis a partir do gerador (para o GC)
+    pub fn values(&self) -> Vec<&Value> {
+        let mut values: Vec<&Value> = self.variables.values().collect();
+        for frame in &self.frames {
+            match frame {
+                GeneratorFrame::Block { shadowed, .. } => {
+                    values.extend(shadowed.iter().filter_map(|(_, v)| v.as_ref()));
+                }
+                GeneratorFrame::ForEach { iter, previous, .. } => {
+                    values.extend(iter.values());
+                    values.extend(previous.iter().filter_map(|(_, v)| v.as_ref()));
+                }
+                GeneratorFrame::Try {
+                    stage: TryStage::Finally(Some(Unwind::Return(value))),
+                    ..
+                } => values.push(value),
+                _ => {}
+            }
+        }
+        values
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneratorStatus {
+    /// Criado; o corpo começa no primeiro `next()`
+    Created,
+    /// Suspenso em um `yield`; o valor de `next(v)` vai para o destino, se houver
+    Suspended(Option<YieldTarget>),
+    /// Executando (um `next()` dentro do próprio gerador é erro)
+    Running,
+    /// Terminou (por `return`, fim do corpo ou erro)
+    Done,
+}
+
+/// Destino do valor enviado por `next(v)` ao retomar um `yield`
+#[derive(Debug, Clone, PartialEq)]
+pub enum YieldTarget {
+    /// `let x = yield v;`
+    Declare(String),
+    /// `x = yield v;`
+    Assign(String),
+}
+
+/// Frame da pilha de execução de um gerador
+#[derive(Debug, Clone)]
+pub enum GeneratorFrame {
+    /// Bloco; `shadowed` guarda o valor anterior das variáveis declaradas nele
+    Block {
+        statements: Vec<Stmt>,
+        next: usize,
+        shadowed: Vec<(String, Option<Value>)>,
+    },
+    While {
+        condition: Expr,
+        body: Stmt,
+        location: SourceLocation,
+    },
+    DoWhile {
+        body: Stmt,
+        condition: Expr,
+        started: bool,
+    },
+    For {
+        condition: Option<Expr>,
+        update: Option<Stmt>,
+        body: Stmt,
+        started: bool,
+    },
+    ForEach {
+        pattern: Pattern,
+        iter: ValueIter,
+        body: Stmt,
+        /// Valores anteriores das variáveis do padrão, restaurados ao sair
+        previous: Vec<(String, Option<Value>)>,
+    },
+    Try {
+        catch: Option<(String, Stmt)>,
+        finally: Option<Stmt>,
+        stage: TryStage,
+    },
+}
+
+impl GeneratorFrame {
+    pub fn block(statements: Vec<Stmt>) -> Self {
+        GeneratorFrame::Block {
+            statements,
+            next: 0,
+            shadowed: Vec::new(),
+        }
+    }
+
+    pub fn is_loop(&self) -> bool {
+        matches!(
+            self,
+            GeneratorFrame::While { .. }
+                | GeneratorFrame::DoWhile { .. }
+                | GeneratorFrame::For { .. }
+                | GeneratorFrame::ForEach { .. }
+        )
+    }
+}
+
+/// Parte do `try` em execução
+#[derive(Debug, Clone)]
+pub enum TryStage {
+    Body,
+    Catch,
+    /// Executando o `finally`; ao terminar, continua o desempilhamento pendente
+    Finally(Option<Unwind>),
+}
+
+/// Motivo para desempilhar frames de um gerador
+#[derive(Debug, Clone)]
+pub enum Unwind {
+    Break,
+    Continue,
+    Return(Value),
+    Error(DryadError),
+}
+
+/// Resultado de uma retomada do gerador
+#[derive(Debug, Clone)]
+pub enum GeneratorStep {
+    Yield(Value, Option<YieldTarget>),
+    Return(Value),
+}
+
+/// Nomes ligados por um padrão, na ordem em que aparecem
+pub fn pattern_names(pattern: &Pattern) -> Vec<String> {
+    fn collect(pattern: &Pattern, names: &mut Vec<String>) {
+        match pattern {
+            Pattern::Identifier(name) => names.push(name.clone()),
+            Pattern::Array(items) | Pattern::Tuple(items) => {
+                items.iter().for_each(|item| collect(item, names))
+            }
+            Pattern::Object(fields) => fields.iter().for_each(|(_, item)| collect(item, names)),
+            Pattern::Rest(name) => names.push(name.clone()),
+            Pattern::Constructor(_, _, fields) => {
+                fields.iter().for_each(|item| collect(item, names))
+            }
+            Pattern::Literal(_) | Pattern::Wildcard => {}
+        }
+    }
+    let mut names = Vec::new();
+    collect(pattern, &mut names);
+    names
+}
+
+/// Indica se o statement tem `yield` fora de funções aninhadas, ou seja, se
+/// precisa ser executado como frame do gerador.
+pub fn stmt_contains_yield(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Expression(expr, _) | Stmt::Throw(expr, _) => expr_contains_yield(expr),
+        Stmt::VarDeclaration(_, _, init, _) => init.as_ref().is_some_and(expr_contains_yield),
+        Stmt::ConstDeclaration(_, _, expr, _) | Stmt::Assignment(_, expr, _) => {
+            expr_contains_yield(expr)
+        }
+        Stmt::PropertyAssignment(object, _, value, _) => {
+            expr_contains_yield(object) || expr_contains_yield(value)
+        }
+        Stmt::IndexAssignment(target, index, value, _) => {
+            expr_contains_yield(target) || expr_contains_yield(index) || expr_contains_yield(value)
+        }
+        Stmt::Block(statements, _) => statements.iter().any(stmt_contains_yield),
+        Stmt::If(condition, then, _) => expr_contains_yield(condition) || stmt_contains_yield(then),
+        Stmt::IfElse(condition, then, otherwise, _) => {
+            expr_contains_yield(condition)
+                || stmt_contains_yield(then)
+                || stmt_contains_yield(otherwise)
+        }
+        Stmt::While(condition, body, _) | Stmt::DoWhile(body, condition, _) => {
+            expr_contains_yield(condition) || stmt_contains_yield(body)
+        }
+        Stmt::For(init, condition, update, body, _) => {
+            init.as_deref().is_some_and(stmt_contains_yield)
+                || condition.as_ref().is_some_and(expr_contains_yield)
+                || update.as_deref().is_some_and(stmt_contains_yield)
+                || stmt_contains_yield(body)
+        }
+        Stmt::ForEach(_, iterable, body, _) => {
+            expr_contains_yield(iterable) || stmt_contains_yield(body)
+        }
+        Stmt::Try(body, catch, finally, _) => {
+            stmt_contains_yield(body)
+                || catch.as_ref().is_some_and(|(_, c)| stmt_contains_yield(c))
+                || finally.as_deref().is_some_and(stmt_contains_yield)
+        }
+        Stmt::Return(expr, _) => expr.as_ref().is_some_and(expr_contains_yield),
+        _ => false,
+    }
+}
+
+fn expr_contains_yield(expr: &Expr) -> bool {
+    let any = |exprs: &[Expr]| exprs.iter().any(expr_contains_yield);
+    match expr {
+        Expr::Yield(..) => true,
+        Expr::Binary(left, _, right, _)
+        | Expr::Index(left, right, _)
+        | Expr::OptionalIndex(left, right, _)
+        | Expr::NullishCoalescing(left, right, _) => {
+            expr_contains_yield(left) || expr_contains_yield(right)
+        }
+        Expr::Range(start, end, _, _) => expr_contains_yield(start) || expr_contains_yield(end),
+        Expr::Unary(_, inner, _)
+        | Expr::PostIncrement(inner, _)
+        | Expr::PostDecrement(inner, _)
+        | Expr::PreIncrement(inner, _)
+        | Expr::PreDecrement(inner, _)
+        | Expr::TupleAccess(inner, _, _)
+        | Expr::PropertyAccess(inner, _, _)
+        | Expr::OptionalPropertyAccess(inner, _, _)
+        | Expr::OptionalChain(inner, _)
+        | Expr::Await(inner, _)
+        | Expr::Spread(inner, _)
+        | Expr::Try(inner, _) => expr_contains_yield(inner),
+        Expr::Call(callee, args, _) | Expr::ThreadCall(callee, args, _) => {
+            expr_contains_yield(callee) || any(args)
+        }
+        Expr::MethodCall(object, _, args, _) | Expr::OptionalMethodCall(object, _, args, _) => {
+            expr_contains_yield(object) || any(args)
+        }
+        Expr::Array(items, _) | Expr::Tuple(items, _) | Expr::ClassInstantiation(_, items, _) => {
+            any(items)
+        }
+        Expr::ObjectLiteral(properties, _) => properties.iter().any(|p| match p {
+            ObjectProperty::Property(_, value) => expr_contains_yield(value),
+            ObjectProperty::Method { .. } => false,
+        }),
+        Expr::Match(target, arms, _) => {
+            expr_contains_yield(target)
+                || arms.iter().any(|MatchArm { guard, body, .. }| {
+                    guard.as_ref().is_some_and(expr_contains_yield) || stmt_contains_yield(body)
+                })
+        }
+        // Lambdas são outras funções: um `yield` nelas não pertence ao gerador
+        Expr::Lambda { .. }
+        | Expr::Literal(..)
+        | Expr::Variable(..)
+        | Expr::This(_)
+        | Expr::Super(_)
+        | Expr::MutexCreation(_) => false,
+    }
+}
@@ -5,6 +5,14 @@ pub mod errors;
 pub mod resolver;
 pub mod heap;
 pub mod value;
+pub mod inspect;
+pub mod iteration;
+pub mod collections;
+pub mod numeric;
+pub mod datetime;
+pub mod regexp;
+pub mod coverage;
+pub mod profiler;
 pub mod debug;
 pub mod debug_server;
 pub mod environment;
@@ -149,18 +149,12 @@ fn native_input_timeout(args: &[Value], _manager: &crate::native_modules::Native
 
 /// native_print(data) - Imprime dados sem quebra de linha
 /// Args: data (qualquer tipo)
-fn native_print(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+fn native_print(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("native_print() espera 1 argumento".to_string()));
     }
     
-    let text = match &args[0] {
-        Value::String(s) => s.clone(),
-        Value::Number(n) => n.to_string(),
-        Value::Bool(b) => b.to_string(),
-        Value::Null => "null".to_string(),
-        _ => format!("{:?}", args[0])
-    };
+    let text = crate::inspect::display(&args[0], heap);
     
     print!("{}", text);
     let _ = stdout().flush(); // Força flush automático
@@ -170,18 +164,12 @@ fn native_print(args: &[Value], _manager: &crate::native_modules::NativeModuleMa
 
 /// native_println(data) - Imprime dados com quebra de linha
 /// Args: data (qualquer tipo)
-fn native_println(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+fn native_println(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("native_println() espera 1 argumento".to_string()));
     }
     
-    let text = match &args[0] {
-        Value::String(s) => s.clone(),
-        Value::Number(n) => n.to_string(),
-        Value::Bool(b) => b.to_string(),
-        Value::Null => "null".to_string(),
-        _ => format!("{:?}", args[0])
-    };
+    let text = crate::inspect::display(&args[0], heap);
     
     println!("{}", text);
     
@@ -2,19 +2,25 @@ use crate::errors::RuntimeError;
 use crate::heap::{Heap, ManagedObject};
 use crate::interpreter::Value;
 use crate::native_modules::NativeFunction;
-use rusqlite::{Connection, params};
+use crate::numeric::Decimal;
+use rusqlite::Connection;
 use std::collections::HashMap;
-use hex;
-use std::sync::Mutex;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
 use lazy_static::lazy_static;
+use num_traits::ToPrimitive;
 
-use tokio_postgres::{Client, NoTls};
+use bytes::BytesMut;
+use futures::StreamExt;
+use tokio_postgres::types::{FromSql, IsNull, ToSql, Type};
+use tokio_postgres::{Client, NoTls, RowStream};
 use tokio::runtime::Runtime;
+use std::pin::Pin;
 use std::sync::Arc;
 
 lazy_static! {
-    static ref SQLITE_CONNECTIONS: Mutex<HashMap<String, Connection>> = Mutex::new(HashMap::new());
-    static ref PG_CLIENTS: Mutex<HashMap<String, Arc<Client>>> = Mutex::new(HashMap::new());
+    static ref SQLITE: Mutex<SqliteState> = Mutex::new(SqliteState::default());
+    static ref PG: Mutex<PgState> = Mutex::new(PgState::default());
     static ref RUNTIME: Runtime = Runtime::new().expect("Falha ao criar runtime Tokio para Banco de Dados");
 }
 
@@ -23,18 +29,327 @@ pub fn register_database_functions(functions: &mut HashMap<String, NativeFunctio
     functions.insert("sqlite_close".to_string(), sqlite_close);
     functions.insert("sqlite_execute".to_string(), sqlite_execute);
     functions.insert("sqlite_query".to_string(), sqlite_query);
-    
+    functions.insert("sqlite_prepare".to_string(), sqlite_prepare);
+    functions.insert("sqlite_stmt_execute".to_string(), sqlite_stmt_execute);
+    functions.insert("sqlite_stmt_query".to_string(), sqlite_stmt_query);
+    functions.insert("sqlite_finalize".to_string(), sqlite_finalize);
+    functions.insert("sqlite_cursor".to_string(), sqlite_cursor);
+    functions.insert("sqlite_cursor_next".to_string(), sqlite_cursor_next);
+    functions.insert("sqlite_cursor_fetch".to_string(), sqlite_cursor_fetch);
+    functions.insert("sqlite_cursor_close".to_string(), sqlite_cursor_close);
+    functions.insert("sqlite_begin".to_string(), sqlite_begin);
+    functions.insert("sqlite_commit".to_string(), sqlite_commit);
+    functions.insert("sqlite_rollback".to_string(), sqlite_rollback);
+    functions.insert("sqlite_in_transaction".to_string(), sqlite_in_transaction);
+    functions.insert("sqlite_transaction".to_string(), transaction_outside_interpreter);
+
     // PostgreSQL
     functions.insert("pg_connect".to_string(), pg_connect);
     functions.insert("pg_execute".to_string(), pg_execute);
     functions.insert("pg_query".to_string(), pg_query);
     functions.insert("pg_close".to_string(), pg_close);
+    functions.insert("pg_prepare".to_string(), pg_prepare);
+    functions.insert("pg_stmt_execute".to_string(), pg_stmt_execute);
+    functions.insert("pg_stmt_query".to_string(), pg_stmt_query);
+    functions.insert("pg_finalize".to_string(), pg_finalize);
+    functions.insert("pg_cursor".to_string(), pg_cursor);
+    functions.insert("pg_cursor_next".to_string(), pg_cursor_next);
+    functions.insert("pg_cursor_fetch".to_string(), pg_cursor_fetch);
+    functions.insert("pg_cursor_close".to_string(), pg_cursor_close);
+    functions.insert("pg_begin".to_string(), pg_begin);
+    functions.insert("pg_commit".to_string(), pg_commit);
+    functions.insert("pg_rollback".to_string(), pg_rollback);
+    functions.insert("pg_transaction".to_string(), transaction_outside_interpreter);
+    functions.insert("pg_pool".to_string(), pg_pool);
+    functions.insert("pg_acquire".to_string(), pg_acquire);
+    functions.insert("pg_release".to_string(), pg_release);
+    functions.insert("pg_pool_status".to_string(), pg_pool_status);
+    functions.insert("pg_pool_close".to_string(), pg_pool_close);
+}
+
+// ============================================
+// HANDLES E PARÂMETROS
+// ============================================
+
+/// Aceita o objeto devolvido por `*_open`/`*_prepare`/`*_cursor` ou o seu `id`
+fn handle_id(value: Option<&Value>, heap: &Heap, function: &str, what: &str) -> Result<String, RuntimeError> {
+    match value {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(Value::Object(id)) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => match properties.get("id") {
+                Some(Value::String(s)) => Ok(s.clone()),
+                _ => Err(RuntimeError::TypeError(format!("{}: objeto sem campo 'id' ({})", function, what))),
+            },
+            _ => Err(RuntimeError::HeapError(format!("{}: referência inválida", function))),
+        },
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: esperado handle ou string ({})", function, what))),
+        None => Err(RuntimeError::ArgumentError(format!("{}: argumento '{}' ausente", function, what))),
+    }
+}
+
+fn sql_arg(value: Option<&Value>, function: &str) -> Result<String, RuntimeError> {
+    match value {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: SQL deve ser string", function))),
+        None => Err(RuntimeError::ArgumentError(format!("{}: SQL ausente", function))),
+    }
+}
+
+fn handle_object(heap: &mut Heap, properties: Vec<(&str, Value)>) -> Value {
+    let properties = properties.into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+    Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+}
+
+fn string_array(values: &[String], heap: &mut Heap) -> Value {
+    let items = values.iter().map(|s| Value::String(s.clone())).collect();
+    Value::Array(heap.allocate(ManagedObject::Array(items)))
+}
+
+fn bytes_value(bytes: &[u8], heap: &mut Heap) -> Value {
+    let items = bytes.iter().map(|b| Value::Number(*b as f64)).collect();
+    Value::Array(heap.allocate(ManagedObject::Array(items)))
+}
+
+/// Parâmetros de uma query: array para `?`/`$1` e objeto/Map para nomeados
+enum Params {
+    None,
+    Positional(Vec<Value>),
+    Named(HashMap<String, Value>),
+}
+
+fn params_arg(value: Option<&Value>, heap: &Heap) -> Result<Params, RuntimeError> {
+    match value {
+        None | Some(Value::Null) => Ok(Params::None),
+        Some(Value::Array(id)) | Some(Value::Tuple(id)) => match heap.get(*id) {
+            Some(ManagedObject::Array(items)) | Some(ManagedObject::Tuple(items)) => Ok(Params::Positional(items.clone())),
+            _ => Err(RuntimeError::HeapError("Array de parâmetros não encontrado".to_string())),
+        },
+        Some(Value::Object(id)) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => Ok(Params::Named(properties.clone())),
+            _ => Err(RuntimeError::HeapError("Objeto de parâmetros não encontrado".to_string())),
+        },
+        Some(Value::Map(id)) => match heap.get(*id) {
+            Some(ManagedObject::Map(entries)) => {
+                let mut named = HashMap::new();
+                for (key, value) in entries.values() {
+                    match key {
+                        Value::String(name) => named.insert(name.clone(), value.clone()),
+                        other => return Err(RuntimeError::TypeError(format!("Nome de parâmetro deve ser string: {}", other.to_string()))),
+                    };
+                }
+                Ok(Params::Named(named))
+            }
+            _ => Err(RuntimeError::HeapError("Map de parâmetros não encontrado".to_string())),
+        },
+        Some(other) => Err(RuntimeError::TypeError(format!(
+            "Parâmetros devem ser array (posicionais) ou objeto (nomeados), recebido: {}",
+            other.to_string()
+        ))),
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) if n.fract() == 0.0 && n.abs() < 9.2e18 => Some(*n as i64),
+        Value::Int(i) => Some(*i),
+        Value::BigInt(b) => b.to_i64(),
+        Value::Decimal(d) if d.is_integer() => d.trunc().to_i64(),
+        _ => None,
+    }
+}
+
+/// Array de números 0–255 (a representação de bytes do runtime)
+fn as_bytes(value: &Value, heap: &Heap) -> Option<Vec<u8>> {
+    let Value::Array(id) = value else { return None };
+    let Some(ManagedObject::Array(items)) = heap.get(*id) else { return None };
+    items
+        .iter()
+        .map(|item| match item {
+            Value::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Some(*n as u8),
+            _ => None,
+        })
+        .collect()
+}
+
+fn json_text(value: &Value, heap: &Heap) -> Result<String, RuntimeError> {
+    let json = super::encode_decode::runtime_value_to_json(value, heap)?;
+    serde_json::to_string(&json).map_err(|e| RuntimeError::TypeError(format!("Erro ao serializar parâmetro: {}", e)))
 }
 
 // ============================================
 // SQLITE IMPLEMENTATION
 // ============================================
 
+/// Conexões, statements preparados e cursores SQLite, acessados só com o
+/// lock de `SQLITE`
+#[derive(Default)]
+struct SqliteState {
+    connections: HashMap<String, Connection>,
+    statements: HashMap<String, SqliteStatement>,
+    cursors: HashMap<String, SqliteCursor>,
+}
+
+/// Statement preparado: a versão compilada fica no cache de statements da
+/// conexão e é reutilizada a cada execução
+struct SqliteStatement {
+    sql: String,
+    connection_id: String,
+}
+
+/// Cursor SQLite: o resultado é lido do banco na abertura e as linhas são
+/// convertidas para valores Dryad sob demanda
+struct SqliteCursor {
+    rows: std::vec::IntoIter<Vec<rusqlite::types::Value>>,
+    columns: Vec<String>,
+    connection_id: String,
+}
+
+impl SqliteCursor {
+    fn open(connection: &Connection, connection_id: &str, sql: &str, params: &Params, heap: &Heap) -> Result<Self, RuntimeError> {
+        let mut statement = connection
+            .prepare_cached(sql)
+            .map_err(|e| RuntimeError::IoError(format!("Erro ao preparar query: {}", e)))?;
+        bind_sqlite(&mut statement, params, heap)?;
+        let columns: Vec<String> = statement.column_names().iter().map(|s| s.to_string()).collect();
+        let read_error = |e: rusqlite::Error| RuntimeError::IoError(format!("Erro ao ler linha: {}", e));
+        let mut rows = statement.raw_query();
+        let mut values = Vec::new();
+        while let Some(row) = rows.next().map_err(read_error)? {
+            let row = (0..columns.len()).map(|i| row.get::<_, rusqlite::types::Value>(i)).collect::<Result<_, _>>().map_err(read_error)?;
+            values.push(row);
+        }
+        Ok(SqliteCursor { rows: values.into_iter(), columns, connection_id: connection_id.to_string() })
+    }
+
+    /// Próxima linha; `None` quando o resultado acabou
+    fn next_row(&mut self, heap: &mut Heap) -> Option<Value> {
+        let row = self.rows.next()?;
+        let mut props = HashMap::new();
+        for (name, value) in self.columns.iter().zip(row) {
+            props.insert(name.clone(), sqlite_value_to_dryad((&value).into(), heap));
+        }
+        Some(Value::Object(heap.allocate(ManagedObject::Object { properties: props, methods: HashMap::new() })))
+    }
+}
+
+fn dryad_to_sqlite(value: &Value, heap: &Heap) -> Result<rusqlite::types::Value, RuntimeError> {
+    use rusqlite::types::Value as Sql;
+    Ok(match value {
+        Value::Null => Sql::Null,
+        Value::Bool(b) => Sql::Integer(*b as i64),
+        Value::Number(n) => match as_i64(value) {
+            Some(i) => Sql::Integer(i),
+            None => Sql::Real(*n),
+        },
+        Value::Int(i) => Sql::Integer(*i),
+        Value::BigInt(b) => match b.to_i64() {
+            Some(i) => Sql::Integer(i),
+            None => Sql::Text(b.to_string()),
+        },
+        Value::Decimal(d) => Sql::Text(d.to_string()),
+        Value::String(s) => Sql::Text(s.clone()),
+        Value::Array(_) => match as_bytes(value, heap) {
+            Some(bytes) => Sql::Blob(bytes),
+            None => Sql::Text(json_text(value, heap)?),
+        },
+        Value::Object(_) | Value::Map(_) | Value::Set(_) | Value::Tuple(_) => Sql::Text(json_text(value, heap)?),
+        other => {
+            return Err(RuntimeError::TypeError(format!("Valor não suportado como parâmetro SQL: {}", other.to_string())))
+        }
+    })
+}
+
+/// Liga os parâmetros ao statement: arrays preenchem `?`/`?N` em ordem e
+/// objetos preenchem `:nome`, `@nome` e `$nome`
+fn bind_sqlite(statement: &mut rusqlite::Statement<'_>, params: &Params, heap: &Heap) -> Result<(), RuntimeError> {
+    let expected = statement.parameter_count();
+    statement.clear_bindings();
+    let bind_error = |e: rusqlite::Error| RuntimeError::IoError(format!("Erro ao ligar parâmetro: {}", e));
+
+    match params {
+        Params::None if expected == 0 => Ok(()),
+        Params::None => Err(RuntimeError::ArgumentError(format!("A query espera {} parâmetro(s)", expected))),
+        Params::Positional(values) => {
+            if values.len() != expected {
+                return Err(RuntimeError::ArgumentError(format!(
+                    "A query espera {} parâmetro(s), recebido(s) {}",
+                    expected,
+                    values.len()
+                )));
+            }
+            for (index, value) in values.iter().enumerate() {
+                statement.raw_bind_parameter(index + 1, dryad_to_sqlite(value, heap)?).map_err(bind_error)?;
+            }
+            Ok(())
+        }
+        Params::Named(values) => {
+            for index in 1..=expected {
+                let name = statement
+                    .parameter_name(index)
+                    .filter(|name| !name.starts_with('?'))
+                    .ok_or_else(|| RuntimeError::ArgumentError(format!("O parâmetro {} é posicional; passe um array", index)))?
+                    .to_string();
+                let value = values
+                    .get(&name[1..])
+                    .ok_or_else(|| RuntimeError::ArgumentError(format!("Parâmetro '{}' não informado", name)))?;
+                statement.raw_bind_parameter(index, dryad_to_sqlite(value, heap)?).map_err(bind_error)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn sqlite_value_to_dryad(val: rusqlite::types::ValueRef, heap: &mut Heap) -> Value {
+    match val {
+        rusqlite::types::ValueRef::Null => Value::Null,
+        rusqlite::types::ValueRef::Integer(i) => crate::numeric::from_i64(i),
+        rusqlite::types::ValueRef::Real(f) => Value::Number(f),
+        rusqlite::types::ValueRef::Text(s) => Value::String(String::from_utf8_lossy(s).into_owned()),
+        rusqlite::types::ValueRef::Blob(b) => bytes_value(b, heap),
+    }
+}
+
+fn sqlite_row_to_dryad(row: &rusqlite::Row<'_>, columns: &[String], heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let mut props = HashMap::new();
+    for (i, name) in columns.iter().enumerate() {
+        let value = row
+            .get_ref(i)
+            .map_err(|e| RuntimeError::IoError(format!("Erro ao ler coluna '{}': {}", name, e)))?;
+        props.insert(name.clone(), sqlite_value_to_dryad(value, heap));
+    }
+    Ok(Value::Object(heap.allocate(ManagedObject::Object { properties: props, methods: HashMap::new() })))
+}
+
+fn run_sqlite_execute(statement: &mut rusqlite::Statement<'_>, connection: &Connection, params: &Params, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    bind_sqlite(statement, params, heap)?;
+    let rows_affected = statement
+        .raw_execute()
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao executar SQL: {}", e)))?;
+    let last_id = connection.last_insert_rowid();
+    Ok(handle_object(heap, vec![
+        ("rows_affected", Value::Number(rows_affected as f64)),
+        ("last_insert_id", crate::numeric::from_i64(last_id)),
+    ]))
+}
+
+fn run_sqlite_query(statement: &mut rusqlite::Statement<'_>, params: &Params, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    bind_sqlite(statement, params, heap)?;
+    let columns: Vec<String> = statement.column_names().iter().map(|s| s.to_string()).collect();
+    let mut rows = statement.raw_query();
+    let mut rows_vec = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| RuntimeError::IoError(format!("Erro ao executar query: {}", e)))? {
+        rows_vec.push(sqlite_row_to_dryad(row, &columns, heap)?);
+    }
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(rows_vec))))
+}
+
+fn sqlite_connection<'a>(state: &'a SqliteState, id: &str) -> Result<&'a Connection, RuntimeError> {
+    state
+        .connections
+        .get(id)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("Conexão SQLite não encontrada: {}", id)))
+}
+
 fn sqlite_open(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
@@ -56,45 +371,35 @@ fn sqlite_open(
     }.map_err(|e| RuntimeError::IoError(format!("Erro ao abrir banco SQLite: {}", e)))?;
 
     let connection_id = format!("db_{}", uuid::Uuid::new_v4());
-    SQLITE_CONNECTIONS.lock().unwrap().insert(connection_id.clone(), conn);
-
-    let id = heap.allocate(ManagedObject::Object {
-        properties: {
-            let mut map = HashMap::new();
-            map.insert("_type".to_string(), Value::String("sqlite".to_string()));
-            map.insert("id".to_string(), Value::String(connection_id));
-            map.insert("path".to_string(), Value::String(path));
-            map.insert("connected".to_string(), Value::Bool(true));
-            map
-        },
-        methods: HashMap::new(),
-    });
+    SQLITE.lock().unwrap().connections.insert(connection_id.clone(), conn);
 
-    Ok(Value::Object(id))
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("sqlite".to_string())),
+        ("id", Value::String(connection_id)),
+        ("path", Value::String(path)),
+        ("connected", Value::Bool(true)),
+    ]))
 }
 
+/// sqlite_close(db) -> bool
+/// Fecha a conexão, finalizando seus statements e cursores
 fn sqlite_close(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
-    _heap: &mut Heap,
+    heap: &mut Heap,
 ) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("sqlite_close: esperado 1 argumento (id)".to_string()));
     }
+    let connection_id = handle_id(args.first(), heap, "sqlite_close", "db")?;
 
-    let connection_id = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("sqlite_close: argumento deve ser string (id)".to_string())),
-    };
-
-    let mut conns = SQLITE_CONNECTIONS.lock().unwrap();
-    if conns.remove(connection_id).is_some() {
-        Ok(Value::Bool(true))
-    } else {
-        Ok(Value::Bool(false))
-    }
+    let mut state = SQLITE.lock().unwrap();
+    state.cursors.retain(|_, cursor| cursor.connection_id != connection_id);
+    state.statements.retain(|_, statement| statement.connection_id != connection_id);
+    Ok(Value::Bool(state.connections.remove(&connection_id).is_some()))
 }
 
+/// sqlite_execute(db, sql, params?) -> { rows_affected, last_insert_id }
 fn sqlite_execute(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
@@ -103,39 +408,19 @@ fn sqlite_execute(
     if args.len() < 2 {
         return Err(RuntimeError::ArgumentError("sqlite_execute: esperado pelo menos 2 argumentos (id, sql, [params])".to_string()));
     }
+    let connection_id = handle_id(args.first(), heap, "sqlite_execute", "db")?;
+    let sql = sql_arg(args.get(1), "sqlite_execute")?;
+    let params = params_arg(args.get(2), heap)?;
 
-    let connection_id = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("sqlite_execute: primeiro argumento deve ser string (id)".to_string())),
-    };
-
-    let sql = match &args[1] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("sqlite_execute: segundo argumento deve ser string (SQL)".to_string())),
-    };
-
-    let mut conns = SQLITE_CONNECTIONS.lock().unwrap();
-    let conn = conns.get_mut(connection_id).ok_or_else(|| RuntimeError::ArgumentError(format!("Conexão SQLite não encontrada: {}", connection_id)))?;
-
-    // Implementação básica de execute sem parâmetros por enquanto para simplificar o mapeamento
-    match conn.execute(sql, []) {
-        Ok(rows_affected) => {
-            let last_id = conn.last_insert_rowid();
-            let id = heap.allocate(ManagedObject::Object {
-                properties: {
-                    let mut map = HashMap::new();
-                    map.insert("rows_affected".to_string(), Value::Number(rows_affected as f64));
-                    map.insert("last_insert_id".to_string(), Value::Number(last_id as f64));
-                    map
-                },
-                methods: HashMap::new(),
-            });
-            Ok(Value::Object(id))
-        }
-        Err(e) => Err(RuntimeError::IoError(format!("Erro ao executar SQL: {}", e))),
-    }
+    let state = SQLITE.lock().unwrap();
+    let conn = sqlite_connection(&state, &connection_id)?;
+    let mut statement = conn
+        .prepare_cached(&sql)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao executar SQL: {}", e)))?;
+    run_sqlite_execute(&mut statement, conn, &params, heap)
 }
 
+/// sqlite_query(db, sql, params?) -> array de objetos
 fn sqlite_query(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
@@ -144,254 +429,1464 @@ fn sqlite_query(
     if args.len() < 2 {
         return Err(RuntimeError::ArgumentError("sqlite_query: esperado pelo menos 2 argumentos (id, sql, [params])".to_string()));
     }
+    let connection_id = handle_id(args.first(), heap, "sqlite_query", "db")?;
+    let sql = sql_arg(args.get(1), "sqlite_query")?;
+    let params = params_arg(args.get(2), heap)?;
 
-    let connection_id = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("sqlite_query: primeiro argumento deve ser string (id)".to_string())),
-    };
+    let state = SQLITE.lock().unwrap();
+    let conn = sqlite_connection(&state, &connection_id)?;
+    let mut statement = conn
+        .prepare_cached(&sql)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao preparar query: {}", e)))?;
+    run_sqlite_query(&mut statement, &params, heap)
+}
+
+/// sqlite_prepare(db, sql) -> statement
+/// Compila a query uma vez para ser executada várias vezes
+fn sqlite_prepare(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "sqlite_prepare", "db")?;
+    let sql = sql_arg(args.get(1), "sqlite_prepare")?;
 
-    let sql = match &args[1] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("sqlite_query: segundo argumento deve ser string (SQL)".to_string())),
+    let mut state = SQLITE.lock().unwrap();
+    let (parameters, columns) = {
+        let statement = sqlite_connection(&state, &connection_id)?
+            .prepare_cached(&sql)
+            .map_err(|e| RuntimeError::IoError(format!("Erro ao preparar query: {}", e)))?;
+        let columns: Vec<String> = statement.column_names().iter().map(|s| s.to_string()).collect();
+        (statement.parameter_count(), columns)
     };
 
-    let conns = SQLITE_CONNECTIONS.lock().unwrap();
-    let conn = conns.get(connection_id).ok_or_else(|| RuntimeError::ArgumentError(format!("Conexão SQLite não encontrada: {}", connection_id)))?;
+    let statement_id = format!("stmt_{}", uuid::Uuid::new_v4());
+    state.statements.insert(statement_id.clone(), SqliteStatement { sql: sql.clone(), connection_id });
+    drop(state);
 
-    let mut stmt = conn.prepare(sql).map_err(|e| RuntimeError::IoError(format!("Erro ao preparar query: {}", e)))?;
-    let column_names: Vec<String> = stmt.column_names().iter().map(|s| s.to_string()).collect();
+    let columns = string_array(&columns, heap);
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("sqlite_statement".to_string())),
+        ("id", Value::String(statement_id)),
+        ("sql", Value::String(sql)),
+        ("parameters", Value::Number(parameters as f64)),
+        ("columns", columns),
+    ]))
+}
 
-    let mut rows_data = Vec::new();
-    let mut rows_iter = stmt.query_map([], |row| {
-        let mut row_values = Vec::new();
-        for (i, _) in column_names.iter().enumerate() {
-            row_values.push(row.get::<usize, rusqlite::types::Value>(i)?);
-        }
-        Ok(row_values)
-    }).map_err(|e| RuntimeError::IoError(format!("Erro ao executar query: {}", e)))?;
+/// sqlite_stmt_execute(stmt, params?) -> { rows_affected, last_insert_id }
+fn sqlite_stmt_execute(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let statement_id = handle_id(args.first(), heap, "sqlite_stmt_execute", "stmt")?;
+    let params = params_arg(args.get(1), heap)?;
 
-    for row_result in rows_iter {
-        rows_data.push(row_result.map_err(|e| RuntimeError::IoError(format!("Erro ao ler linha: {}", e)))?);
-    }
+    let state = SQLITE.lock().unwrap();
+    let (conn, mut statement) = prepared_statement(&state, &statement_id)?;
+    run_sqlite_execute(&mut statement, conn, &params, heap)
+}
 
-    let mut rows_vec = Vec::new();
-    for row_values in rows_data {
-        let mut props = HashMap::new();
-        for (i, name) in column_names.iter().enumerate() {
-            let val = &row_values[i];
-            props.insert(name.clone(), sqlite_value_to_dryad_from_value(val, heap));
-        }
-        
-        let obj_id = heap.allocate(ManagedObject::Object {
-            properties: props,
-            methods: HashMap::new(),
-        });
-        rows_vec.push(Value::Object(obj_id));
-    }
+/// sqlite_stmt_query(stmt, params?) -> array de objetos
+fn sqlite_stmt_query(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let statement_id = handle_id(args.first(), heap, "sqlite_stmt_query", "stmt")?;
+    let params = params_arg(args.get(1), heap)?;
 
-    let array_id = heap.allocate(ManagedObject::Array(rows_vec));
-    Ok(Value::Array(array_id))
+    let state = SQLITE.lock().unwrap();
+    let (_, mut statement) = prepared_statement(&state, &statement_id)?;
+    run_sqlite_query(&mut statement, &params, heap)
 }
 
-fn sqlite_value_to_dryad_from_value(val: &rusqlite::types::Value, heap: &mut Heap) -> Value {
-    match val {
-        rusqlite::types::Value::Null => Value::Null,
-        rusqlite::types::Value::Integer(i) => Value::Number(*i as f64),
-        rusqlite::types::Value::Real(f) => Value::Number(*f),
-        rusqlite::types::Value::Text(s) => Value::String(s.clone()),
-        rusqlite::types::Value::Blob(b) => {
-            // Converter Blob para string hex ou similar se necessário
-            Value::String(hex::encode(b))
-        }
-    }
+/// Conexão e statement compilado (do cache da conexão) de um `sqlite_prepare`
+fn prepared_statement<'a>(
+    state: &'a SqliteState,
+    statement_id: &str,
+) -> Result<(&'a Connection, rusqlite::CachedStatement<'a>), RuntimeError> {
+    let statement = state
+        .statements
+        .get(statement_id)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("Statement SQLite não encontrado: {}", statement_id)))?;
+    let conn = sqlite_connection(state, &statement.connection_id)?;
+    let cached = conn
+        .prepare_cached(&statement.sql)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao preparar query: {}", e)))?;
+    Ok((conn, cached))
 }
 
-fn sqlite_value_to_dryad(val: rusqlite::types::ValueRef, heap: &mut Heap) -> Value {
-    match val {
-        rusqlite::types::ValueRef::Null => Value::Null,
-        rusqlite::types::ValueRef::Integer(i) => Value::Number(i as f64),
-        rusqlite::types::ValueRef::Real(f) => Value::Number(f),
-        rusqlite::types::ValueRef::Text(s) => {
-            let s_str = std::str::from_utf8(s).unwrap_or("");
-            Value::String(s_str.to_string())
-        }
-        rusqlite::types::ValueRef::Blob(b) => {
-            Value::String(hex::encode(b))
-        }
-    }
+/// sqlite_finalize(stmt) -> bool
+fn sqlite_finalize(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let statement_id = handle_id(args.first(), heap, "sqlite_finalize", "stmt")?;
+    Ok(Value::Bool(SQLITE.lock().unwrap().statements.remove(&statement_id).is_some()))
 }
 
-// ============================================
-// POSTGRESQL MOCKS
-// ============================================
-
-fn pg_connect(
+/// sqlite_cursor(db, sql, params?) -> cursor
+/// Executa a query e devolve as linhas aos poucos com
+/// `sqlite_cursor_next`/`sqlite_cursor_fetch`, sem criar todos os objetos
+/// Dryad de uma vez
+fn sqlite_cursor(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
     heap: &mut Heap,
 ) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError::ArgumentError("pg_connect: esperado 1 argumento (connection_string)".to_string()));
-    }
+    let connection_id = handle_id(args.first(), heap, "sqlite_cursor", "db")?;
+    let sql = sql_arg(args.get(1), "sqlite_cursor")?;
+    let params = params_arg(args.get(2), heap)?;
 
-    let conn_str = match &args[0] {
-        Value::String(s) => s.clone(),
-        _ => return Err(RuntimeError::TypeError("pg_connect: argumento deve ser string".to_string())),
-    };
+    let mut state = SQLITE.lock().unwrap();
+    let conn = sqlite_connection(&state, &connection_id)?;
+    let cursor = SqliteCursor::open(conn, &connection_id, &sql, &params, heap)?;
+    let columns = cursor.columns.clone();
+    let cursor_id = format!("cursor_{}", uuid::Uuid::new_v4());
+    state.cursors.insert(cursor_id.clone(), cursor);
+    drop(state);
 
-    let result = RUNTIME.block_on(async {
-        let (client, connection) = tokio_postgres::connect(&conn_str, NoTls).await
-            .map_err(|e| RuntimeError::IoError(format!("Erro ao conectar PostgreSQL: {}", e)))?;
+    let columns = string_array(&columns, heap);
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("sqlite_cursor".to_string())),
+        ("id", Value::String(cursor_id)),
+        ("columns", columns),
+    ]))
+}
 
-        // A conexão precisa ser spawnada em segundo plano
-        tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                eprintln!("PostgreSQL connection error: {}", e);
-            }
-        });
+/// sqlite_cursor_next(cursor) -> objeto | null
+fn sqlite_cursor_next(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let cursor_id = handle_id(args.first(), heap, "sqlite_cursor_next", "cursor")?;
+    let mut state = SQLITE.lock().unwrap();
+    let cursor = state
+        .cursors
+        .get_mut(&cursor_id)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("Cursor SQLite não encontrado: {}", cursor_id)))?;
+    Ok(cursor.next_row(heap).unwrap_or(Value::Null))
+}
 
-        Ok(client)
-    });
-
-    match result {
-        Ok(client) => {
-            let connection_id = format!("pg_{}", uuid::Uuid::new_v4());
-            PG_CLIENTS.lock().unwrap().insert(connection_id.clone(), Arc::new(client));
-
-            let id = heap.allocate(ManagedObject::Object {
-                properties: {
-                    let mut map = HashMap::new();
-                    map.insert("_type".to_string(), Value::String("postgres".to_string()));
-                    map.insert("id".to_string(), Value::String(connection_id));
-                    map.insert("conn_str".to_string(), Value::String(conn_str));
-                    map.insert("connected".to_string(), Value::Bool(true));
-                    map
-                },
-                methods: HashMap::new(),
-            });
-
-            Ok(Value::Object(id))
+/// sqlite_cursor_fetch(cursor, count) -> array com até `count` linhas
+fn sqlite_cursor_fetch(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let count = fetch_count(args.get(1), "sqlite_cursor_fetch")?;
+    let cursor_id = handle_id(args.first(), heap, "sqlite_cursor_fetch", "cursor")?;
+    let mut state = SQLITE.lock().unwrap();
+    let cursor = state
+        .cursors
+        .get_mut(&cursor_id)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("Cursor SQLite não encontrado: {}", cursor_id)))?;
+    let mut rows = Vec::new();
+    while rows.len() < count {
+        match cursor.next_row(heap) {
+            Some(row) => rows.push(row),
+            None => break,
         }
-        Err(e) => Err(e),
     }
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(rows))))
 }
 
-fn pg_execute(
+fn fetch_count(value: Option<&Value>, function: &str) -> Result<usize, RuntimeError> {
+    match value.and_then(as_i64) {
+        Some(n) if n > 0 => Ok(n as usize),
+        _ => Err(RuntimeError::ArgumentError(format!("{}: quantidade deve ser um inteiro positivo", function))),
+    }
+}
+
+/// sqlite_cursor_close(cursor) -> bool
+fn sqlite_cursor_close(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
     heap: &mut Heap,
 ) -> Result<Value, RuntimeError> {
-    if args.len() < 2 {
-        return Err(RuntimeError::ArgumentError("pg_execute: esperado pelo menos 2 argumentos (id, sql, [params])".to_string()));
-    }
+    let cursor_id = handle_id(args.first(), heap, "sqlite_cursor_close", "cursor")?;
+    Ok(Value::Bool(SQLITE.lock().unwrap().cursors.remove(&cursor_id).is_some()))
+}
 
-    let connection_id = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("pg_execute: primeiro argumento deve ser string (id)".to_string())),
-    };
+fn sqlite_batch(args: &[Value], heap: &Heap, function: &str, sql: &str) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, function, "db")?;
+    let state = SQLITE.lock().unwrap();
+    sqlite_connection(&state, &connection_id)?
+        .execute_batch(sql)
+        .map_err(|e| RuntimeError::IoError(format!("{}: {}", function, e)))?;
+    Ok(Value::Null)
+}
 
-    let sql = match &args[1] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("pg_execute: segundo argumento deve ser string (SQL)".to_string())),
-    };
+fn sqlite_begin_sql(mode: Option<&Value>) -> Result<&'static str, RuntimeError> {
+    match mode {
+        None | Some(Value::Null) => Ok("BEGIN"),
+        Some(Value::String(s)) => match s.to_ascii_lowercase().as_str() {
+            "deferred" => Ok("BEGIN DEFERRED"),
+            "immediate" => Ok("BEGIN IMMEDIATE"),
+            "exclusive" => Ok("BEGIN EXCLUSIVE"),
+            _ => Err(RuntimeError::ArgumentError(format!("Modo de transação inválido: '{}' (use deferred, immediate ou exclusive)", s))),
+        },
+        Some(_) => Err(RuntimeError::TypeError("Modo de transação deve ser string".to_string())),
+    }
+}
 
-    let clients = PG_CLIENTS.lock().unwrap();
-    let client = clients.get(connection_id).ok_or_else(|| RuntimeError::ArgumentError(format!("Conexão PostgreSQL não encontrada: {}", connection_id)))?.clone();
+/// sqlite_begin(db, mode?) -> null
+/// Inicia uma transação (`deferred`, `immediate` ou `exclusive`)
+fn sqlite_begin(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let sql = sqlite_begin_sql(args.get(1))?;
+    sqlite_batch(args, heap, "sqlite_begin", sql)
+}
 
-    // Implementação básica de execute sem parâmetros complexos por enquanto
-    let rows_affected = RUNTIME.block_on(async {
-        client.execute(sql, &[]).await
-            .map_err(|e| RuntimeError::IoError(format!("Erro ao executar PostgreSQL SQL: {}", e)))
-    })?;
+/// sqlite_commit(db) -> null
+fn sqlite_commit(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    sqlite_batch(args, heap, "sqlite_commit", "COMMIT")
+}
 
-    let id = heap.allocate(ManagedObject::Object {
-        properties: {
-            let mut map = HashMap::new();
-            map.insert("rows_affected".to_string(), Value::Number(rows_affected as f64));
-            map
-        },
-        methods: HashMap::new(),
-    });
-    Ok(Value::Object(id))
+/// sqlite_rollback(db) -> null
+fn sqlite_rollback(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    sqlite_batch(args, heap, "sqlite_rollback", "ROLLBACK")
 }
 
-fn pg_query(
+/// sqlite_in_transaction(db) -> bool
+fn sqlite_in_transaction(
     args: &[Value],
     _manager: &crate::native_modules::NativeModuleManager,
     heap: &mut Heap,
 ) -> Result<Value, RuntimeError> {
-    if args.len() < 2 {
-        return Err(RuntimeError::ArgumentError("pg_query: esperado pelo menos 2 argumentos (id, sql, [params])".to_string()));
-    }
+    let connection_id = handle_id(args.first(), heap, "sqlite_in_transaction", "db")?;
+    let state = SQLITE.lock().unwrap();
+    Ok(Value::Bool(!sqlite_connection(&state, &connection_id)?.is_autocommit()))
+}
 
-    let connection_id = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("pg_query: primeiro argumento deve ser string (id)".to_string())),
-    };
+// ============================================
+// TRANSAÇÕES COM CALLBACK
+// ============================================
 
-    let sql = match &args[1] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("pg_query: segundo argumento deve ser string (SQL)".to_string())),
-    };
+/// `sqlite_transaction`/`pg_transaction` precisam chamar o callback Dryad e
+/// por isso são executadas pelo interpreter (veja `TransactionScope`)
+fn transaction_outside_interpreter(
+    _args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    _heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    Err(RuntimeError::Generic(
+        "Transações com callback só estão disponíveis no interpretador; use *_begin/*_commit/*_rollback".to_string(),
+    ))
+}
 
-    let clients = PG_CLIENTS.lock().unwrap();
-    let client = clients.get(connection_id).ok_or_else(|| RuntimeError::ArgumentError(format!("Conexão PostgreSQL não encontrada: {}", connection_id)))?.clone();
+/// Transação aberta por `sqlite_transaction(db, fn)` ou
+/// `pg_transaction(conn_ou_pool, fn)`. O interpreter chama o callback com
+/// `connection()` e depois `finish(true)` (commit) ou `finish(false)` (rollback).
+pub struct TransactionScope {
+    function: String,
+    connection_id: String,
+    connection: Value,
+    /// Pool de onde a conexão foi adquirida, devolvida ao final
+    pool_id: Option<String>,
+}
 
-    let rows = RUNTIME.block_on(async {
-        client.query(sql, &[]).await
-            .map_err(|e| RuntimeError::IoError(format!("Erro ao executar PostgreSQL query: {}", e)))
-    })?;
+impl TransactionScope {
+    pub fn begin(function: &str, target: &Value, heap: &mut Heap) -> Result<Self, RuntimeError> {
+        if function == "sqlite_transaction" {
+            let connection_id = handle_id(Some(target), heap, function, "db")?;
+            sqlite_batch(&[Value::String(connection_id.clone())], heap, function, "BEGIN")?;
+            return Ok(TransactionScope { function: function.to_string(), connection_id, connection: target.clone(), pool_id: None });
+        }
 
-    let mut rows_vec = Vec::new();
-    for row in rows {
-        let mut props = HashMap::new();
-        for (i, column) in row.columns().iter().enumerate() {
-            let name = column.name().to_string();
-            // TODO: Mapear mais tipos PostgreSQL
-            let val = if let Ok(s) = row.try_get::<usize, String>(i) {
-                Value::String(s)
-            } else if let Ok(n) = row.try_get::<usize, i32>(i) {
-                Value::Number(n as f64)
-            } else if let Ok(n) = row.try_get::<usize, f64>(i) {
-                Value::Number(n)
-            } else if let Ok(b) = row.try_get::<usize, bool>(i) {
-                Value::Bool(b)
-            } else {
-                Value::Null
-            };
-            props.insert(name, val);
+        let target_id = handle_id(Some(target), heap, function, "conn")?;
+        let pool = PG.lock().unwrap().pools.get(&target_id).cloned();
+        let (connection_id, connection, pool_id) = match pool {
+            Some(pool) => {
+                let (connection_id, connection) = acquire_from_pool(&target_id, &pool, heap)?;
+                (connection_id, connection, Some(target_id))
+            }
+            None => (target_id, target.clone(), None),
+        };
+
+        let scope = TransactionScope { function: function.to_string(), connection_id, connection, pool_id };
+        if let Err(e) = pg_batch(&scope.connection_id, "BEGIN") {
+            scope.release();
+            return Err(e);
         }
-        
-        let obj_id = heap.allocate(ManagedObject::Object {
-            properties: props,
-            methods: HashMap::new(),
-        });
-        rows_vec.push(Value::Object(obj_id));
+        Ok(scope)
+    }
+
+    /// Conexão passada ao callback
+    pub fn connection(&self) -> Value {
+        self.connection.clone()
+    }
+
+    pub fn finish(self, commit: bool) -> Result<(), RuntimeError> {
+        let sql = if commit { "COMMIT" } else { "ROLLBACK" };
+        let result = if self.function == "sqlite_transaction" {
+            let state = SQLITE.lock().unwrap();
+            sqlite_connection(&state, &self.connection_id)?
+                .execute_batch(sql)
+                .map_err(|e| RuntimeError::IoError(format!("{}: {}", self.function, e)))
+        } else {
+            pg_batch(&self.connection_id, sql)
+        };
+        self.release();
+        result
     }
 
-    let array_id = heap.allocate(ManagedObject::Array(rows_vec));
-    Ok(Value::Array(array_id))
+    fn release(&self) {
+        if self.pool_id.is_some() {
+            release_pg_connection(&self.connection_id);
+        }
+    }
 }
 
-fn pg_close(
-    args: &[Value],
-    _manager: &crate::native_modules::NativeModuleManager,
-    _heap: &mut Heap,
-) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError::ArgumentError("pg_close: esperado 1 argumento (id)".to_string()));
+// ============================================
+// POSTGRESQL IMPLEMENTATION
+// ============================================
+
+#[derive(Default)]
+struct PgState {
+    connections: HashMap<String, PgConnection>,
+    pools: HashMap<String, Arc<PgPool>>,
+    statements: HashMap<String, PgStatement>,
+    cursors: HashMap<String, PgCursor>,
+}
+
+struct PgConnection {
+    client: Arc<Client>,
+    /// Pool de origem quando adquirida com `pg_acquire`
+    pool_id: Option<String>,
+}
+
+struct PgStatement {
+    connection_id: String,
+    statement: tokio_postgres::Statement,
+    /// Nomes na ordem dos `$n` quando a query usa `:nome`
+    names: Option<Vec<String>>,
+}
+
+struct PgCursor {
+    connection_id: String,
+    stream: Pin<Box<RowStream>>,
+    done: bool,
+}
+
+/// Pool de conexões: reaproveita clientes ociosos e abre novos até
+/// `max_size`; quando todos estão em uso, espera até `acquire_timeout`
+struct PgPool {
+    conn_str: String,
+    max_size: usize,
+    acquire_timeout: Duration,
+    inner: Mutex<PgPoolInner>,
+    available: Condvar,
+}
+
+#[derive(Default)]
+struct PgPoolInner {
+    idle: Vec<Client>,
+    open: usize,
+    closed: bool,
+}
+
+impl PgPool {
+    fn acquire(&self) -> Result<Client, RuntimeError> {
+        let deadline = Instant::now() + self.acquire_timeout;
+        let mut inner = self.inner.lock().unwrap();
+        loop {
+            if inner.closed {
+                return Err(RuntimeError::IoError("Pool PostgreSQL fechado".to_string()));
+            }
+            while let Some(client) = inner.idle.pop() {
+                if client.is_closed() {
+                    inner.open -= 1;
+                    continue;
+                }
+                return Ok(client);
+            }
+            if inner.open < self.max_size {
+                inner.open += 1;
+                drop(inner);
+                return connect_pg(&self.conn_str).inspect_err(|_| {
+                    self.inner.lock().unwrap().open -= 1;
+                    self.available.notify_one();
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RuntimeError::IoError(format!(
+                    "Pool PostgreSQL esgotado: {} conexões em uso após {} ms",
+                    self.max_size,
+                    self.acquire_timeout.as_millis()
+                )));
+            }
+            inner = self.available.wait_timeout(inner, deadline - now).unwrap().0;
+        }
     }
 
-    let connection_id = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("pg_close: argumento deve ser string (id)".to_string())),
-    };
+    fn release(&self, client: Client) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed || client.is_closed() {
+            inner.open = inner.open.saturating_sub(1);
+        } else {
+            inner.idle.push(client);
+        }
+        self.available.notify_one();
+    }
 
-    let mut clients = PG_CLIENTS.lock().unwrap();
-    if clients.remove(connection_id).is_some() {
-        Ok(Value::Bool(true))
-    } else {
-        Ok(Value::Bool(false))
+    fn discard(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.open = inner.open.saturating_sub(1);
+        self.available.notify_one();
     }
 }
 
+fn connect_pg(conn_str: &str) -> Result<Client, RuntimeError> {
+    RUNTIME.block_on(async {
+        let (client, connection) = tokio_postgres::connect(conn_str, NoTls).await
+            .map_err(|e| RuntimeError::IoError(format!("Erro ao conectar PostgreSQL: {}", e)))?;
+
+        // A conexão precisa ser spawnada em segundo plano
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("PostgreSQL connection error: {}", e);
+            }
+        });
+
+        Ok(client)
+    })
+}
+
+fn pg_error(context: &str, e: tokio_postgres::Error) -> RuntimeError {
+    match e.as_db_error() {
+        Some(db) => RuntimeError::IoError(format!("{}: {} ({})", context, db.message(), db.code().code())),
+        None => RuntimeError::IoError(format!("{}: {}", context, e)),
+    }
+}
+
+/// Cliente para uma operação avulsa: conexões diretas são usadas como estão;
+/// pools emprestam um cliente que volta ao pool ao fim da operação
+enum PgLease {
+    Direct(Arc<Client>),
+    Pooled(Arc<PgPool>, Option<Client>),
+}
+
+impl PgLease {
+    fn client(&self) -> &Client {
+        match self {
+            PgLease::Direct(client) => client,
+            PgLease::Pooled(_, client) => client.as_ref().expect("cliente emprestado"),
+        }
+    }
+}
+
+impl Drop for PgLease {
+    fn drop(&mut self) {
+        if let PgLease::Pooled(pool, client) = self {
+            if let Some(client) = client.take() {
+                pool.release(client);
+            }
+        }
+    }
+}
+
+fn pg_lease(id: &str) -> Result<PgLease, RuntimeError> {
+    let pool = {
+        let state = PG.lock().unwrap();
+        if let Some(connection) = state.connections.get(id) {
+            return Ok(PgLease::Direct(connection.client.clone()));
+        }
+        state.pools.get(id).cloned()
+    };
+    match pool {
+        Some(pool) => {
+            let client = pool.acquire()?;
+            Ok(PgLease::Pooled(pool, Some(client)))
+        }
+        None => Err(RuntimeError::ArgumentError(format!("Conexão PostgreSQL não encontrada: {}", id))),
+    }
+}
+
+/// Conexões fixas (não pools), exigidas por transações, statements e cursores
+fn pg_direct_client(id: &str, function: &str) -> Result<Arc<Client>, RuntimeError> {
+    let state = PG.lock().unwrap();
+    match state.connections.get(id) {
+        Some(connection) => Ok(connection.client.clone()),
+        None if state.pools.contains_key(id) => Err(RuntimeError::ArgumentError(format!(
+            "{}: requer uma conexão fixa; use pg_acquire(pool) ou pg_transaction(pool, fn)",
+            function
+        ))),
+        None => Err(RuntimeError::ArgumentError(format!("Conexão PostgreSQL não encontrada: {}", id))),
+    }
+}
+
+fn pg_batch(connection_id: &str, sql: &str) -> Result<(), RuntimeError> {
+    let client = pg_direct_client(connection_id, sql)?;
+    RUNTIME
+        .block_on(client.batch_execute(sql))
+        .map_err(|e| pg_error(&format!("Erro em {}", sql), e))
+}
+
+/// Reescreve `:nome` para `$n`, ignorando literais, identificadores entre
+/// aspas, comentários e casts `::tipo`. Nomes repetidos reutilizam o índice.
+fn rewrite_named_params(sql: &str) -> (String, Vec<String>) {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut out = String::with_capacity(sql.len());
+    let mut names: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' => {
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    i += 1;
+                    if chars[i - 1] == c {
+                        if i < chars.len() && chars[i] == c {
+                            out.push(c);
+                            i += 1;
+                            continue;
+                        }
+                        break;
+                    }
+                }
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(chars[i]);
+                    i += 1;
+                }
+            }
+            ':' if chars.get(i + 1) == Some(&':') => {
+                out.push_str("::");
+                i += 2;
+            }
+            ':' if chars.get(i + 1).map(|n| n.is_alphabetic() || *n == '_').unwrap_or(false) => {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let name: String = chars[start..end].iter().collect();
+                let index = match names.iter().position(|n| *n == name) {
+                    Some(index) => index,
+                    None => {
+                        names.push(name);
+                        names.len() - 1
+                    }
+                };
+                out.push_str(&format!("${}", index + 1));
+                i = end;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    (out, names)
+}
+
+/// Ordena os parâmetros conforme os `$n` da query
+fn pg_ordered_params(params: Params, names: Option<&[String]>) -> Result<Vec<Value>, RuntimeError> {
+    match (params, names) {
+        (Params::None, _) => Ok(Vec::new()),
+        (Params::Positional(values), None) => Ok(values),
+        (Params::Named(values), Some(names)) => names
+            .iter()
+            .map(|name| {
+                values
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| RuntimeError::ArgumentError(format!("Parâmetro ':{}' não informado", name)))
+            })
+            .collect(),
+        (Params::Positional(_), Some(_)) => Err(RuntimeError::ArgumentError(
+            "A query usa parâmetros nomeados (:nome); passe um objeto".to_string(),
+        )),
+        (Params::Named(_), None) => Err(RuntimeError::ArgumentError(
+            "A query usa parâmetros posicionais ($1); passe um array".to_string(),
+        )),
+    }
+}
+
+fn pg_sql(sql: &str) -> (String, Option<Vec<String>>) {
+    let (rewritten, names) = rewrite_named_params(sql);
+    if names.is_empty() {
+        (sql.to_string(), None)
+    } else {
+        (rewritten, Some(names))
+    }
+}
+
+/// Parâmetro PostgreSQL já convertido para o tipo que o servidor espera
+#[derive(Debug)]
+enum PgParam {
+    Null,
+    Bool(bool),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Numeric(String),
+    Text(String),
+    Bytes(Vec<u8>),
+    Json(serde_json::Value),
+    Uuid(uuid::Uuid),
+    Timestamp(chrono::NaiveDateTime),
+    TimestampTz(chrono::DateTime<chrono::Utc>),
+    Date(chrono::NaiveDate),
+    Array(Vec<PgParam>, Type),
+}
+
+impl ToSql for PgParam {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match self {
+            PgParam::Null => Ok(IsNull::Yes),
+            PgParam::Bool(v) => v.to_sql(ty, out),
+            PgParam::I16(v) => v.to_sql(ty, out),
+            PgParam::I32(v) => v.to_sql(ty, out),
+            PgParam::I64(v) => v.to_sql(ty, out),
+            PgParam::F32(v) => v.to_sql(ty, out),
+            PgParam::F64(v) => v.to_sql(ty, out),
+            PgParam::Numeric(text) => {
+                encode_numeric(text, out)?;
+                Ok(IsNull::No)
+            }
+            PgParam::Text(v) => v.to_sql(ty, out),
+            PgParam::Bytes(v) => v.to_sql(ty, out),
+            PgParam::Json(v) => v.to_sql(ty, out),
+            PgParam::Uuid(v) => v.to_sql(ty, out),
+            PgParam::Timestamp(v) => v.to_sql(ty, out),
+            PgParam::TimestampTz(v) => v.to_sql(ty, out),
+            PgParam::Date(v) => v.to_sql(ty, out),
+            PgParam::Array(items, element) => {
+                let element = element.clone();
+                postgres_protocol::types::array_to_sql(
+                    Some(postgres_protocol::types::ArrayDimension { len: items.len() as i32, lower_bound: 1 }),
+                    element.oid(),
+                    items.iter(),
+                    |item, buf| match item.to_sql(&element, buf)? {
+                        IsNull::No => Ok(postgres_protocol::IsNull::No),
+                        IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
+                    },
+                    out,
+                )?;
+                Ok(IsNull::No)
+            }
+        }
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    tokio_postgres::types::to_sql_checked!();
+}
+
+fn numeric_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(n) if n.is_finite() => Decimal::from_f64(*n).map(|d| d.to_string()),
+        Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) => crate::numeric::format(value),
+        Value::String(s) => Decimal::parse(s).map(|d| d.to_string()),
+        _ => None,
+    }
+}
+
+/// Converte um valor Dryad para o tipo do parâmetro inferido pelo servidor
+fn dryad_to_pg(value: &Value, ty: &Type, heap: &Heap) -> Result<PgParam, RuntimeError> {
+    if matches!(value, Value::Null) {
+        return Ok(PgParam::Null);
+    }
+    let mismatch = || RuntimeError::TypeError(format!("Valor '{}' incompatível com parâmetro do tipo {}", value.to_string(), ty.name()));
+    let integer = |min: i64, max: i64| as_i64(value).filter(|i| (min..=max).contains(i)).ok_or_else(mismatch);
+
+    Ok(match *ty {
+        Type::BOOL => match value {
+            Value::Bool(b) => PgParam::Bool(*b),
+            _ => return Err(mismatch()),
+        },
+        Type::INT2 => PgParam::I16(integer(i16::MIN as i64, i16::MAX as i64)? as i16),
+        Type::INT4 => PgParam::I32(integer(i32::MIN as i64, i32::MAX as i64)? as i32),
+        Type::INT8 => PgParam::I64(integer(i64::MIN, i64::MAX)?),
+        Type::FLOAT4 => PgParam::F32(crate::numeric::to_f64(value).ok_or_else(mismatch)? as f32),
+        Type::FLOAT8 => PgParam::F64(crate::numeric::to_f64(value).ok_or_else(mismatch)?),
+        Type::NUMERIC => PgParam::Numeric(numeric_text(value).ok_or_else(mismatch)?),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => match value {
+            Value::String(s) => PgParam::Text(s.clone()),
+            Value::Number(_) | Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) | Value::Bool(_) => PgParam::Text(value.to_string()),
+            _ => PgParam::Text(json_text(value, heap)?),
+        },
+        Type::BYTEA => match value {
+            Value::String(s) => PgParam::Bytes(s.clone().into_bytes()),
+            _ => PgParam::Bytes(as_bytes(value, heap).ok_or_else(mismatch)?),
+        },
+        Type::JSON | Type::JSONB => PgParam::Json(super::encode_decode::runtime_value_to_json(value, heap)?),
+        Type::UUID => match value {
+            Value::String(s) => PgParam::Uuid(uuid::Uuid::parse_str(s).map_err(|_| mismatch())?),
+            _ => return Err(mismatch()),
+        },
+        Type::TIMESTAMP => match value {
+            Value::String(s) => PgParam::Timestamp(parse_naive_datetime(s).ok_or_else(mismatch)?),
+            _ => return Err(mismatch()),
+        },
+        Type::TIMESTAMPTZ => match value {
+            Value::String(s) => PgParam::TimestampTz(
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .map(|dt| dt.with_timezone(&chrono::Utc))
+                    .or_else(|_| parse_naive_datetime(s).map(|dt| dt.and_utc()).ok_or(()))
+                    .map_err(|_| mismatch())?,
+            ),
+            _ => return Err(mismatch()),
+        },
+        Type::DATE => match value {
+            Value::String(s) => PgParam::Date(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| mismatch())?),
+            _ => return Err(mismatch()),
+        },
+        _ => match ty.kind() {
+            tokio_postgres::types::Kind::Array(element) => {
+                let Value::Array(id) = value else { return Err(mismatch()) };
+                let Some(ManagedObject::Array(items)) = heap.get(*id) else { return Err(mismatch()) };
+                let items = items
+                    .iter()
+                    .map(|item| dryad_to_pg(item, element, heap))
+                    .collect::<Result<Vec<_>, _>>()?;
+                PgParam::Array(items, element.clone())
+            }
+            _ => {
+                return Err(RuntimeError::TypeError(format!(
+                    "Tipo PostgreSQL '{}' não suportado em parâmetros; use um cast explícito (ex.: $1::text)",
+                    ty.name()
+                )))
+            }
+        },
+    })
+}
+
+fn parse_naive_datetime(text: &str) -> Option<chrono::NaiveDateTime> {
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().and_then(|d| d.and_hms_opt(0, 0, 0)))
+}
+
+fn pg_bind(statement: &tokio_postgres::Statement, values: &[Value], heap: &Heap) -> Result<Vec<PgParam>, RuntimeError> {
+    let types = statement.params();
+    if types.len() != values.len() {
+        return Err(RuntimeError::ArgumentError(format!(
+            "A query espera {} parâmetro(s), recebido(s) {}",
+            types.len(),
+            values.len()
+        )));
+    }
+    values.iter().zip(types).map(|(value, ty)| dryad_to_pg(value, ty, heap)).collect()
+}
+
+/// Decodifica o formato binário de NUMERIC (dígitos em base 10000)
+fn decode_numeric(raw: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    if raw.len() < 8 {
+        return Err("NUMERIC inválido".into());
+    }
+    let read = |at: usize| u16::from_be_bytes([raw[at], raw[at + 1]]);
+    let ndigits = read(0) as usize;
+    let weight = read(2) as i16 as i32;
+    let sign = read(4);
+    let dscale = read(6) as usize;
+    if sign == 0xC000 {
+        return Ok("NaN".to_string());
+    }
+    if raw.len() < 8 + 2 * ndigits {
+        return Err("NUMERIC inválido".into());
+    }
+    let digit = |index: i32| -> u16 {
+        if index < 0 || index as usize >= ndigits { 0 } else { read(8 + 2 * index as usize) }
+    };
+
+    let mut text = String::new();
+    if sign == 0x4000 {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    } else {
+        for index in 0..=weight {
+            if index == 0 {
+                text.push_str(&digit(index).to_string());
+            } else {
+                text.push_str(&format!("{:04}", digit(index)));
+            }
+        }
+    }
+    if dscale > 0 {
+        let mut fraction = String::new();
+        for group in 0..dscale.div_ceil(4) {
+            fraction.push_str(&format!("{:04}", digit(weight + 1 + group as i32)));
+        }
+        fraction.truncate(dscale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Ok(text)
+}
+
+/// Codifica texto decimal no formato binário de NUMERIC
+fn encode_numeric(text: &str, out: &mut BytesMut) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    use bytes::BufMut;
+
+    if text.eq_ignore_ascii_case("nan") {
+        out.put_slice(&[0, 0, 0, 0, 0xC0, 0, 0, 0]);
+        return Ok(());
+    }
+    let normalized = Decimal::parse(text).ok_or("NUMERIC inválido")?.to_string();
+    let (negative, unsigned) = match normalized.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, normalized.as_str()),
+    };
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let integer = integer.trim_start_matches('0');
+
+    let integer = format!("{:0>width$}", integer, width = integer.len().div_ceil(4) * 4);
+    let fraction_padded = format!("{:0<width$}", fraction, width = fraction.len().div_ceil(4) * 4);
+    let mut groups: Vec<u16> = integer
+        .as_bytes()
+        .chunks(4)
+        .chain(fraction_padded.as_bytes().chunks(4))
+        .map(|chunk| std::str::from_utf8(chunk).unwrap().parse::<u16>().unwrap())
+        .collect();
+    let mut weight = (integer.len() / 4) as i16 - 1;
+    while groups.first() == Some(&0) {
+        groups.remove(0);
+        weight -= 1;
+    }
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+
+    out.put_u16(groups.len() as u16);
+    out.put_i16(weight);
+    out.put_u16(if negative && !groups.is_empty() { 0x4000 } else { 0 });
+    out.put_u16(fraction.len() as u16);
+    for group in groups {
+        out.put_u16(group);
+    }
+    Ok(())
+}
+
+/// Leitura de colunas NUMERIC sem perda de precisão
+struct PgNumeric(String);
+
+impl<'a> FromSql<'a> for PgNumeric {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        decode_numeric(raw).map(PgNumeric)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+/// Leitura de tipos sem conversão dedicada: texto quando UTF-8, senão bytes
+struct PgRaw(Vec<u8>);
+
+impl<'a> FromSql<'a> for PgRaw {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(PgRaw(raw.to_vec()))
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+}
+
+fn pg_column_to_dryad(row: &tokio_postgres::Row, index: usize, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let ty = row.columns()[index].type_().clone();
+    let error = |e: tokio_postgres::Error| RuntimeError::IoError(format!("Erro ao ler coluna '{}': {}", row.columns()[index].name(), e));
+
+    macro_rules! get {
+        ($t:ty) => {
+            row.try_get::<usize, Option<$t>>(index).map_err(error)?
+        };
+    }
+
+    Ok(match ty {
+        Type::BOOL => get!(bool).map(Value::Bool).unwrap_or(Value::Null),
+        Type::INT2 => get!(i16).map(|v| Value::Number(v as f64)).unwrap_or(Value::Null),
+        Type::INT4 => get!(i32).map(|v| Value::Number(v as f64)).unwrap_or(Value::Null),
+        Type::INT8 => get!(i64).map(crate::numeric::from_i64).unwrap_or(Value::Null),
+        Type::OID => get!(u32).map(|v| Value::Number(v as f64)).unwrap_or(Value::Null),
+        Type::FLOAT4 => get!(f32).map(|v| Value::Number(v as f64)).unwrap_or(Value::Null),
+        Type::FLOAT8 => get!(f64).map(Value::Number).unwrap_or(Value::Null),
+        Type::NUMERIC => match get!(PgNumeric) {
+            Some(PgNumeric(text)) => Decimal::parse(&text).map(Value::Decimal).unwrap_or(Value::String(text)),
+            None => Value::Null,
+        },
+        Type::BYTEA => get!(Vec<u8>).map(|b| bytes_value(&b, heap)).unwrap_or(Value::Null),
+        Type::JSON | Type::JSONB => match get!(serde_json::Value) {
+            Some(json) => super::encode_decode::json_to_runtime_value(&json, heap),
+            None => Value::Null,
+        },
+        Type::UUID => get!(uuid::Uuid).map(|u| Value::String(u.to_string())).unwrap_or(Value::Null),
+        Type::TIMESTAMP => get!(chrono::NaiveDateTime)
+            .map(|dt| Value::String(dt.format("%Y-%m-%dT%H:%M:%S%.f").to_string()))
+            .unwrap_or(Value::Null),
+        Type::TIMESTAMPTZ => get!(chrono::DateTime<chrono::Utc>)
+            .map(|dt| Value::String(dt.to_rfc3339()))
+            .unwrap_or(Value::Null),
+        Type::DATE => get!(chrono::NaiveDate).map(|d| Value::String(d.to_string())).unwrap_or(Value::Null),
+        Type::TIME => get!(chrono::NaiveTime).map(|t| Value::String(t.to_string())).unwrap_or(Value::Null),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+            get!(String).map(Value::String).unwrap_or(Value::Null)
+        }
+        Type::INT4_ARRAY | Type::INT8_ARRAY | Type::TEXT_ARRAY | Type::VARCHAR_ARRAY | Type::FLOAT8_ARRAY | Type::BOOL_ARRAY => {
+            let items: Option<Vec<Value>> = match ty {
+                Type::INT4_ARRAY => get!(Vec<Option<i32>>).map(|v| v.into_iter().map(|i| i.map(|i| Value::Number(i as f64)).unwrap_or(Value::Null)).collect()),
+                Type::INT8_ARRAY => get!(Vec<Option<i64>>).map(|v| v.into_iter().map(|i| i.map(crate::numeric::from_i64).unwrap_or(Value::Null)).collect()),
+                Type::FLOAT8_ARRAY => get!(Vec<Option<f64>>).map(|v| v.into_iter().map(|f| f.map(Value::Number).unwrap_or(Value::Null)).collect()),
+                Type::BOOL_ARRAY => get!(Vec<Option<bool>>).map(|v| v.into_iter().map(|b| b.map(Value::Bool).unwrap_or(Value::Null)).collect()),
+                _ => get!(Vec<Option<String>>).map(|v| v.into_iter().map(|s| s.map(Value::String).unwrap_or(Value::Null)).collect()),
+            };
+            match items {
+                Some(items) => Value::Array(heap.allocate(ManagedObject::Array(items))),
+                None => Value::Null,
+            }
+        }
+        _ => match get!(PgRaw) {
+            Some(PgRaw(bytes)) => match String::from_utf8(bytes) {
+                Ok(text) => Value::String(text),
+                Err(e) => bytes_value(e.as_bytes(), heap),
+            },
+            None => Value::Null,
+        },
+    })
+}
+
+fn pg_row_to_dryad(row: &tokio_postgres::Row, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let mut props = HashMap::new();
+    for (i, column) in row.columns().iter().enumerate() {
+        props.insert(column.name().to_string(), pg_column_to_dryad(row, i, heap)?);
+    }
+    Ok(Value::Object(heap.allocate(ManagedObject::Object { properties: props, methods: HashMap::new() })))
+}
+
+fn pg_rows_to_dryad(rows: Vec<tokio_postgres::Row>, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let rows_vec = rows.iter().map(|row| pg_row_to_dryad(row, heap)).collect::<Result<Vec<_>, _>>()?;
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(rows_vec))))
+}
+
+fn param_refs(params: &[PgParam]) -> Vec<&(dyn ToSql + Sync)> {
+    params.iter().map(|p| p as &(dyn ToSql + Sync)).collect()
+}
+
+/// Prepara `sql` (com `:nome` reescritos) e liga os parâmetros
+fn pg_prepare_and_bind(
+    client: &Client,
+    sql: &str,
+    params: Params,
+    heap: &Heap,
+) -> Result<(tokio_postgres::Statement, Vec<PgParam>), RuntimeError> {
+    let (sql, names) = pg_sql(sql);
+    let values = pg_ordered_params(params, names.as_deref())?;
+    let statement = RUNTIME
+        .block_on(client.prepare(&sql))
+        .map_err(|e| pg_error("Erro ao preparar query PostgreSQL", e))?;
+    let bound = pg_bind(&statement, &values, heap)?;
+    Ok((statement, bound))
+}
+
+fn pg_connect(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::ArgumentError("pg_connect: esperado 1 argumento (connection_string)".to_string()));
+    }
+
+    let conn_str = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(RuntimeError::TypeError("pg_connect: argumento deve ser string".to_string())),
+    };
+
+    let client = connect_pg(&conn_str)?;
+    let connection_id = format!("pg_{}", uuid::Uuid::new_v4());
+    PG.lock().unwrap().connections.insert(connection_id.clone(), PgConnection { client: Arc::new(client), pool_id: None });
+
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("postgres".to_string())),
+        ("id", Value::String(connection_id)),
+        ("conn_str", Value::String(conn_str)),
+        ("connected", Value::Bool(true)),
+    ]))
+}
+
+/// pg_execute(conn_ou_pool, sql, params?) -> { rows_affected }
+fn pg_execute(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    if args.len() < 2 {
+        return Err(RuntimeError::ArgumentError("pg_execute: esperado pelo menos 2 argumentos (id, sql, [params])".to_string()));
+    }
+    let connection_id = handle_id(args.first(), heap, "pg_execute", "conn")?;
+    let sql = sql_arg(args.get(1), "pg_execute")?;
+    let params = params_arg(args.get(2), heap)?;
+
+    let lease = pg_lease(&connection_id)?;
+    let (statement, bound) = pg_prepare_and_bind(lease.client(), &sql, params, heap)?;
+    let rows_affected = RUNTIME
+        .block_on(lease.client().execute(&statement, &param_refs(&bound)))
+        .map_err(|e| pg_error("Erro ao executar PostgreSQL SQL", e))?;
+
+    Ok(handle_object(heap, vec![("rows_affected", Value::Number(rows_affected as f64))]))
+}
+
+/// pg_query(conn_ou_pool, sql, params?) -> array de objetos
+fn pg_query(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    if args.len() < 2 {
+        return Err(RuntimeError::ArgumentError("pg_query: esperado pelo menos 2 argumentos (id, sql, [params])".to_string()));
+    }
+    let connection_id = handle_id(args.first(), heap, "pg_query", "conn")?;
+    let sql = sql_arg(args.get(1), "pg_query")?;
+    let params = params_arg(args.get(2), heap)?;
+
+    let lease = pg_lease(&connection_id)?;
+    let (statement, bound) = pg_prepare_and_bind(lease.client(), &sql, params, heap)?;
+    let rows = RUNTIME
+        .block_on(lease.client().query(&statement, &param_refs(&bound)))
+        .map_err(|e| pg_error("Erro ao executar PostgreSQL query", e))?;
+    pg_rows_to_dryad(rows, heap)
+}
+
+/// pg_close(conn) -> bool
+/// Fecha a conexão; conexões adquiridas de um pool voltam para ele
+fn pg_close(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::ArgumentError("pg_close: esperado 1 argumento (id)".to_string()));
+    }
+    let connection_id = handle_id(args.first(), heap, "pg_close", "conn")?;
+    Ok(Value::Bool(release_pg_connection(&connection_id)))
+}
+
+/// Remove a conexão (e seus statements/cursores); se veio de um pool,
+/// devolve o cliente a ele
+fn release_pg_connection(connection_id: &str) -> bool {
+    let (connection, pool) = {
+        let mut state = PG.lock().unwrap();
+        state.cursors.retain(|_, cursor| cursor.connection_id != connection_id);
+        state.statements.retain(|_, statement| statement.connection_id != connection_id);
+        let Some(connection) = state.connections.remove(connection_id) else { return false };
+        let pool = connection.pool_id.as_ref().and_then(|id| state.pools.get(id).cloned());
+        (connection, pool)
+    };
+    if let Some(pool) = pool {
+        match Arc::try_unwrap(connection.client) {
+            Ok(client) => pool.release(client),
+            Err(_) => pool.discard(),
+        }
+    }
+    true
+}
+
+/// pg_prepare(conn, sql) -> statement
+fn pg_prepare(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "pg_prepare", "conn")?;
+    let sql = sql_arg(args.get(1), "pg_prepare")?;
+    let client = pg_direct_client(&connection_id, "pg_prepare")?;
+
+    let (rewritten, names) = pg_sql(&sql);
+    let statement = RUNTIME
+        .block_on(client.prepare(&rewritten))
+        .map_err(|e| pg_error("Erro ao preparar query PostgreSQL", e))?;
+    let parameters = statement.params().len();
+    let columns: Vec<String> = statement.columns().iter().map(|c| c.name().to_string()).collect();
+
+    let statement_id = format!("pgstmt_{}", uuid::Uuid::new_v4());
+    PG.lock().unwrap().statements.insert(statement_id.clone(), PgStatement { connection_id, statement, names });
+
+    let columns = string_array(&columns, heap);
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("pg_statement".to_string())),
+        ("id", Value::String(statement_id)),
+        ("sql", Value::String(sql)),
+        ("parameters", Value::Number(parameters as f64)),
+        ("columns", columns),
+    ]))
+}
+
+fn pg_statement(args: &[Value], heap: &Heap, function: &str) -> Result<(Arc<Client>, tokio_postgres::Statement, Vec<PgParam>), RuntimeError> {
+    let statement_id = handle_id(args.first(), heap, function, "stmt")?;
+    let params = params_arg(args.get(1), heap)?;
+    let (connection_id, statement, names) = {
+        let state = PG.lock().unwrap();
+        let prepared = state
+            .statements
+            .get(&statement_id)
+            .ok_or_else(|| RuntimeError::ArgumentError(format!("Statement PostgreSQL não encontrado: {}", statement_id)))?;
+        (prepared.connection_id.clone(), prepared.statement.clone(), prepared.names.clone())
+    };
+    let client = pg_direct_client(&connection_id, function)?;
+    let values = pg_ordered_params(params, names.as_deref())?;
+    let bound = pg_bind(&statement, &values, heap)?;
+    Ok((client, statement, bound))
+}
+
+/// pg_stmt_execute(stmt, params?) -> { rows_affected }
+fn pg_stmt_execute(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let (client, statement, bound) = pg_statement(args, heap, "pg_stmt_execute")?;
+    let rows_affected = RUNTIME
+        .block_on(client.execute(&statement, &param_refs(&bound)))
+        .map_err(|e| pg_error("Erro ao executar PostgreSQL SQL", e))?;
+    Ok(handle_object(heap, vec![("rows_affected", Value::Number(rows_affected as f64))]))
+}
+
+/// pg_stmt_query(stmt, params?) -> array de objetos
+fn pg_stmt_query(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let (client, statement, bound) = pg_statement(args, heap, "pg_stmt_query")?;
+    let rows = RUNTIME
+        .block_on(client.query(&statement, &param_refs(&bound)))
+        .map_err(|e| pg_error("Erro ao executar PostgreSQL query", e))?;
+    pg_rows_to_dryad(rows, heap)
+}
+
+/// pg_finalize(stmt) -> bool
+fn pg_finalize(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let statement_id = handle_id(args.first(), heap, "pg_finalize", "stmt")?;
+    Ok(Value::Bool(PG.lock().unwrap().statements.remove(&statement_id).is_some()))
+}
+
+/// pg_cursor(conn, sql, params?) -> cursor
+/// As linhas chegam do servidor sob demanda via `pg_cursor_next`/`pg_cursor_fetch`
+fn pg_cursor(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "pg_cursor", "conn")?;
+    let sql = sql_arg(args.get(1), "pg_cursor")?;
+    let params = params_arg(args.get(2), heap)?;
+    let client = pg_direct_client(&connection_id, "pg_cursor")?;
+
+    let (statement, bound) = pg_prepare_and_bind(&client, &sql, params, heap)?;
+    let columns: Vec<String> = statement.columns().iter().map(|c| c.name().to_string()).collect();
+    let stream = RUNTIME
+        .block_on(client.query_raw(&statement, bound.iter().map(|p| p as &dyn ToSql)))
+        .map_err(|e| pg_error("Erro ao executar PostgreSQL query", e))?;
+
+    let cursor_id = format!("pgcursor_{}", uuid::Uuid::new_v4());
+    PG.lock().unwrap().cursors.insert(cursor_id.clone(), PgCursor { connection_id, stream: Box::pin(stream), done: false });
+
+    let columns = string_array(&columns, heap);
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("pg_cursor".to_string())),
+        ("id", Value::String(cursor_id)),
+        ("columns", columns),
+    ]))
+}
+
+fn pg_fetch(args: &[Value], heap: &mut Heap, function: &str, count: usize) -> Result<Vec<Value>, RuntimeError> {
+    let cursor_id = handle_id(args.first(), heap, function, "cursor")?;
+    let mut state = PG.lock().unwrap();
+    let cursor = state
+        .cursors
+        .get_mut(&cursor_id)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("Cursor PostgreSQL não encontrado: {}", cursor_id)))?;
+
+    let mut rows = Vec::new();
+    while rows.len() < count && !cursor.done {
+        match RUNTIME.block_on(cursor.stream.as_mut().next()) {
+            Some(Ok(row)) => rows.push(pg_row_to_dryad(&row, heap)?),
+            Some(Err(e)) => {
+                cursor.done = true;
+                return Err(pg_error("Erro ao ler linha", e));
+            }
+            None => cursor.done = true,
+        }
+    }
+    Ok(rows)
+}
+
+/// pg_cursor_next(cursor) -> objeto | null
+fn pg_cursor_next(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    Ok(pg_fetch(args, heap, "pg_cursor_next", 1)?.pop().unwrap_or(Value::Null))
+}
+
+/// pg_cursor_fetch(cursor, count) -> array com até `count` linhas
+fn pg_cursor_fetch(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let count = fetch_count(args.get(1), "pg_cursor_fetch")?;
+    let rows = pg_fetch(args, heap, "pg_cursor_fetch", count)?;
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(rows))))
+}
+
+/// pg_cursor_close(cursor) -> bool
+fn pg_cursor_close(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let cursor_id = handle_id(args.first(), heap, "pg_cursor_close", "cursor")?;
+    Ok(Value::Bool(PG.lock().unwrap().cursors.remove(&cursor_id).is_some()))
+}
+
+/// pg_begin(conn, isolation?) -> null
+/// `isolation`: "read committed", "repeatable read" ou "serializable"
+fn pg_begin(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "pg_begin", "conn")?;
+    let sql = match args.get(1) {
+        None | Some(Value::Null) => "BEGIN",
+        Some(Value::String(s)) => match s.to_ascii_lowercase().as_str() {
+            "read committed" => "BEGIN ISOLATION LEVEL READ COMMITTED",
+            "repeatable read" => "BEGIN ISOLATION LEVEL REPEATABLE READ",
+            "serializable" => "BEGIN ISOLATION LEVEL SERIALIZABLE",
+            _ => return Err(RuntimeError::ArgumentError(format!("Nível de isolamento inválido: '{}'", s))),
+        },
+        Some(_) => return Err(RuntimeError::TypeError("pg_begin: isolamento deve ser string".to_string())),
+    };
+    pg_direct_client(&connection_id, "pg_begin")?;
+    pg_batch(&connection_id, sql)?;
+    Ok(Value::Null)
+}
+
+/// pg_commit(conn) -> null
+fn pg_commit(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "pg_commit", "conn")?;
+    pg_direct_client(&connection_id, "pg_commit")?;
+    pg_batch(&connection_id, "COMMIT")?;
+    Ok(Value::Null)
+}
+
+/// pg_rollback(conn) -> null
+fn pg_rollback(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "pg_rollback", "conn")?;
+    pg_direct_client(&connection_id, "pg_rollback")?;
+    pg_batch(&connection_id, "ROLLBACK")?;
+    Ok(Value::Null)
+}
+
+/// pg_pool(connection_string, options?) -> pool
+/// `options`: `max_size` (padrão 10) e `acquire_timeout` em ms (padrão 30000).
+/// As conexões são abertas sob demanda.
+fn pg_pool(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let conn_str = match args.first() {
+        Some(Value::String(s)) => s.clone(),
+        _ => return Err(RuntimeError::TypeError("pg_pool: primeiro argumento deve ser string (connection_string)".to_string())),
+    };
+    let mut max_size = 10;
+    let mut acquire_timeout = Duration::from_secs(30);
+    match args.get(1) {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(id)) => {
+            if let Some(ManagedObject::Object { properties, .. }) = heap.get(*id) {
+                if let Some(value) = properties.get("max_size") {
+                    max_size = as_i64(value)
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| RuntimeError::ArgumentError("pg_pool: max_size deve ser inteiro positivo".to_string()))?
+                        as usize;
+                }
+                if let Some(value) = properties.get("acquire_timeout") {
+                    let ms = as_i64(value)
+                        .filter(|n| *n >= 0)
+                        .ok_or_else(|| RuntimeError::ArgumentError("pg_pool: acquire_timeout deve ser inteiro não negativo".to_string()))?;
+                    acquire_timeout = Duration::from_millis(ms as u64);
+                }
+            }
+        }
+        Some(_) => return Err(RuntimeError::TypeError("pg_pool: opções devem ser objeto".to_string())),
+    }
+
+    let pool = PgPool {
+        conn_str,
+        max_size,
+        acquire_timeout,
+        inner: Mutex::new(PgPoolInner::default()),
+        available: Condvar::new(),
+    };
+    let pool_id = format!("pgpool_{}", uuid::Uuid::new_v4());
+    PG.lock().unwrap().pools.insert(pool_id.clone(), Arc::new(pool));
+
+    Ok(handle_object(heap, vec![
+        ("_type", Value::String("pg_pool".to_string())),
+        ("id", Value::String(pool_id)),
+        ("max_size", Value::Number(max_size as f64)),
+    ]))
+}
+
+fn acquire_from_pool(pool_id: &str, pool: &Arc<PgPool>, heap: &mut Heap) -> Result<(String, Value), RuntimeError> {
+    let client = pool.acquire()?;
+    let connection_id = format!("pg_{}", uuid::Uuid::new_v4());
+    PG.lock().unwrap().connections.insert(
+        connection_id.clone(),
+        PgConnection { client: Arc::new(client), pool_id: Some(pool_id.to_string()) },
+    );
+    let connection = handle_object(heap, vec![
+        ("_type", Value::String("postgres".to_string())),
+        ("id", Value::String(connection_id.clone())),
+        ("pool", Value::String(pool_id.to_string())),
+        ("connected", Value::Bool(true)),
+    ]);
+    Ok((connection_id, connection))
+}
+
+fn pool_arg(args: &[Value], heap: &Heap, function: &str) -> Result<(String, Arc<PgPool>), RuntimeError> {
+    let pool_id = handle_id(args.first(), heap, function, "pool")?;
+    let pool = PG
+        .lock()
+        .unwrap()
+        .pools
+        .get(&pool_id)
+        .cloned()
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("Pool PostgreSQL não encontrado: {}", pool_id)))?;
+    Ok((pool_id, pool))
+}
+
+/// pg_acquire(pool) -> conn
+/// Empresta uma conexão fixa do pool (para transações, cursores e statements)
+fn pg_acquire(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let (pool_id, pool) = pool_arg(args, heap, "pg_acquire")?;
+    Ok(acquire_from_pool(&pool_id, &pool, heap)?.1)
+}
+
+/// pg_release(conn) -> bool
+/// Devolve ao pool uma conexão obtida com `pg_acquire`
+fn pg_release(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let connection_id = handle_id(args.first(), heap, "pg_release", "conn")?;
+    Ok(Value::Bool(release_pg_connection(&connection_id)))
+}
+
+/// pg_pool_status(pool) -> { max_size, open, idle, in_use }
+fn pg_pool_status(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let (_, pool) = pool_arg(args, heap, "pg_pool_status")?;
+    let (open, idle) = {
+        let inner = pool.inner.lock().unwrap();
+        (inner.open, inner.idle.len())
+    };
+    Ok(handle_object(heap, vec![
+        ("max_size", Value::Number(pool.max_size as f64)),
+        ("open", Value::Number(open as f64)),
+        ("idle", Value::Number(idle as f64)),
+        ("in_use", Value::Number((open - idle) as f64)),
+    ]))
+}
+
+/// pg_pool_close(pool) -> bool
+/// Fecha as conexões ociosas; as emprestadas são fechadas ao serem devolvidas
+fn pg_pool_close(
+    args: &[Value],
+    _manager: &crate::native_modules::NativeModuleManager,
+    heap: &mut Heap,
+) -> Result<Value, RuntimeError> {
+    let pool_id = handle_id(args.first(), heap, "pg_pool_close", "pool")?;
+    let Some(pool) = PG.lock().unwrap().pools.remove(&pool_id) else { return Ok(Value::Bool(false)) };
+    let mut inner = pool.inner.lock().unwrap();
+    inner.closed = true;
+    inner.open -= inner.idle.len();
+    inner.idle.clear();
+    pool.available.notify_all();
+    Ok(Value::Bool(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_params_are_rewritten_outside_literals_and_casts() {
+        let (sql, names) = rewrite_named_params(
+            "SELECT ':skip', \"col:x\", $$ FROM t WHERE a = :id AND b::text = :nome OR c = :id -- :comentario\n",
+        );
+        assert_eq!(sql, "SELECT ':skip', \"col:x\", $$ FROM t WHERE a = $1 AND b::text = $2 OR c = $1 -- :comentario\n");
+        assert_eq!(names, vec!["id".to_string(), "nome".to_string()]);
+    }
+
+    #[test]
+    fn numeric_binary_round_trips() {
+        for text in ["0", "1", "-1", "12345.678", "0.0001", "-0.5", "10000", "123456789012345678901234567890.12", "1.10"] {
+            let mut buf = BytesMut::new();
+            encode_numeric(text, &mut buf).unwrap();
+            assert_eq!(decode_numeric(&buf).unwrap(), text, "valor {}", text);
+        }
+        // 1234.5 com escala 2: ndigits=2, weight=0, sign=0, dscale=2, [1234, 5000]
+        let raw = [0, 2, 0, 0, 0, 0, 0, 2, 0x04, 0xD2, 0x13, 0x88];
+        assert_eq!(decode_numeric(&raw).unwrap(), "1234.50");
+    }
+}
@@ -1,6 +1,8 @@
 use crate::interpreter::Value;
 use crate::native_modules::NativeFunction;
 use crate::errors::RuntimeError;
+use crate::inspect::{inspect, InspectOptions};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::time::Instant;
 use lazy_static::lazy_static;
@@ -10,19 +12,35 @@ lazy_static! {
     static ref PERF_TIMERS: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
 }
 
-fn native_debug(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+fn native_debug(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("debug espera 1 argumento: mensagem".to_string()));
     }
-    // Usa Debug em vez de Display
-    let msg = format!("{:?}", &args[0]);
+    // Mostra a estrutura completa do valor (strings com aspas)
+    let msg = inspect(&args[0], heap, &InspectOptions::default());
     println!("[DEBUG] {}", msg);
     Ok(Value::Null)
 }
 
+// Formata um valor com as opções informadas: inspect(valor, { depth, maxItems, maxStringLength, breakLength, colors, quoteStrings })
+fn native_inspect(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(RuntimeError::ArgumentError("inspect espera 1 ou 2 argumentos: valor e opções opcionais".to_string()));
+    }
+
+    let options = match args.get(1) {
+        Some(opts) => InspectOptions::from_value(opts, heap).map_err(RuntimeError::TypeError)?,
+        None => InspectOptions::default(),
+    };
+
+    Ok(Value::String(inspect(&args[0], heap, &options)))
+}
+
 pub fn register_debug_functions(functions: &mut HashMap<String, NativeFunction>) {
     functions.insert("debug".to_string(), native_debug);
     functions.insert("log".to_string(), native_log);
+    functions.insert("inspect".to_string(), native_inspect);
+    functions.insert("native_inspect".to_string(), native_inspect);
     functions.insert("native_typeof".to_string(), native_typeof);
     functions.insert("native_memory_usage".to_string(), native_memory_usage);
     functions.insert("native_stack_trace".to_string(), native_stack_trace);
@@ -63,26 +81,7 @@ fn native_typeof(args: &[Value], _manager: &crate::native_modules::NativeModuleM
         return Err(RuntimeError::ArgumentError("typeof espera exatamente 1 argumento".to_string()));
     }
     
-    let type_name = match &args[0] {
-        Value::String(_) => "string",
-        Value::Number(_) => "number",
-        Value::Bool(_) => "boolean",
-        Value::Null => "null",
-        Value::Array(_) => "array",
-        Value::Tuple(_) => "tuple",
-        Value::Exception(_) => "exception",
-        Value::Function { .. } => "function",
-        Value::AsyncFunction { .. } => "async_function",
-        Value::ThreadFunction { .. } => "thread_function",
-        Value::Lambda(_) => "lambda",
-        Value::Thread { .. } => "thread",
-        Value::Mutex { .. } => "mutex",
-        Value::Promise { .. } => "promise",
-        Value::Class(_) => "class",
-        Value::Instance(_) => "instance",
-        Value::Object(_) => "object",
-        Value::Result(_, _) => "result",
-    };
+    let type_name = args[0].type_name();
     
     Ok(Value::String(type_name.to_string()))
 }
@@ -181,8 +180,46 @@ fn native_assert(args: &[Value], _manager: &crate::native_modules::NativeModuleM
     Ok(Value::Bool(true))
 }
 
+/// Valores comparados na última falha de `native_assert_equal`, já formatados.
+/// O `dryad test` usa isso para exibir um diff entre esperado e obtido.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionFailure {
+    pub expected: String,
+    pub actual: String,
+}
+
+thread_local! {
+    static LAST_ASSERTION_FAILURE: RefCell<Option<AssertionFailure>> = const { RefCell::new(None) };
+}
+
+/// Retorna (e limpa) a última falha de asserção registrada nesta thread.
+pub fn take_assertion_failure() -> Option<AssertionFailure> {
+    LAST_ASSERTION_FAILURE.with(|last| last.borrow_mut().take())
+}
+
+fn record_assertion_failure(expected: &Value, actual: &Value, heap: &crate::heap::Heap) {
+    // Largura menor que o padrão para que estruturas grandes fiquem em várias linhas no diff
+    let options = InspectOptions {
+        break_length: 40,
+        ..InspectOptions::default()
+    };
+    let failure = AssertionFailure {
+        expected: inspect(expected, heap, &options),
+        actual: inspect(actual, heap, &options),
+    };
+    LAST_ASSERTION_FAILURE.with(|last| *last.borrow_mut() = Some(failure));
+}
+
+// Números usam tolerância de f64::EPSILON; o resto é comparado estruturalmente
+fn assertion_equal(a: &Value, b: &Value, heap: &crate::heap::Heap) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
+        _ => a.deep_equals(b, heap),
+    }
+}
+
 // Assert igualdade
-fn native_assert_equal(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+fn native_assert_equal(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() < 2 || args.len() > 3 {
         return Err(RuntimeError::ArgumentError("assert_equal espera 2 ou 3 argumentos: esperado, atual e mensagem opcional".to_string()));
     }
@@ -190,22 +227,18 @@ fn native_assert_equal(args: &[Value], _manager: &crate::native_modules::NativeM
     let expected = &args[0];
     let actual = &args[1];
     
-    let equal = match (expected, actual) {
-        (Value::String(a), Value::String(b)) => a == b,
-        (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
-        (Value::Bool(a), Value::Bool(b)) => a == b,
-        (Value::Null, Value::Null) => true,
-        _ => false,
-    };
+    let equal = assertion_equal(expected, actual, heap);
     
     if !equal {
+        record_assertion_failure(expected, actual, heap);
         let message = if args.len() > 2 {
             match &args[2] {
                 Value::String(s) => s.clone(),
                 _ => "Values are not equal".to_string(),
             }
         } else {
-            format!("Expected {:?}, got {:?}", expected, actual)
+            let options = InspectOptions::default();
+            format!("Expected {}, got {}", inspect(expected, heap, &options), inspect(actual, heap, &options))
         };
         
         return Err(RuntimeError::Generic(format!("Assertion Error: {}", message)));
@@ -215,7 +248,7 @@ fn native_assert_equal(args: &[Value], _manager: &crate::native_modules::NativeM
 }
 
 // Assert não igualdade
-fn native_assert_not_equal(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+fn native_assert_not_equal(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() < 2 || args.len() > 3 {
         return Err(RuntimeError::ArgumentError("assert_not_equal espera 2 ou 3 argumentos: primeiro, segundo e mensagem opcional".to_string()));
     }
@@ -223,13 +256,7 @@ fn native_assert_not_equal(args: &[Value], _manager: &crate::native_modules::Nat
     let first = &args[0];
     let second = &args[1];
     
-    let equal = match (first, second) {
-        (Value::String(a), Value::String(b)) => a == b,
-        (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
-        (Value::Bool(a), Value::Bool(b)) => a == b,
-        (Value::Null, Value::Null) => true,
-        _ => false,
-    };
+    let equal = assertion_equal(first, second, heap);
     
     if equal {
         let message = if args.len() > 2 {
@@ -238,7 +265,8 @@ fn native_assert_not_equal(args: &[Value], _manager: &crate::native_modules::Nat
                 _ => "Values should not be equal".to_string(),
             }
         } else {
-            format!("Values {:?} and {:?} should not be equal", first, second)
+            let options = InspectOptions::default();
+            format!("Values {} and {} should not be equal", inspect(first, heap, &options), inspect(second, heap, &options))
         };
         
         return Err(RuntimeError::Generic(format!("Assertion Error: {}", message)));
@@ -313,26 +341,7 @@ fn native_assert_type(args: &[Value], _manager: &crate::native_modules::NativeMo
         _ => return Err(RuntimeError::TypeError("Segundo argumento deve ser uma string (tipo)".to_string())),
     };
     
-    let actual_type = match value {
-        Value::String(_) => "string",
-        Value::Number(_) => "number",
-        Value::Bool(_) => "boolean",
-        Value::Null => "null",
-        Value::Array(_) => "array",
-        Value::Tuple(_) => "tuple",
-        Value::Exception(_) => "exception",
-        Value::Function { .. } => "function",
-        Value::AsyncFunction { .. } => "async_function",
-        Value::ThreadFunction { .. } => "thread_function",
-        Value::Lambda(_) => "lambda",
-        Value::Thread { .. } => "thread",
-        Value::Mutex { .. } => "mutex",
-        Value::Promise { .. } => "promise",
-        Value::Class(_) => "class",
-        Value::Instance(_) => "instance",
-        Value::Object(_) => "object",
-        Value::Result(_, _) => "result",
-    };
+    let actual_type = value.type_name();
     
     if actual_type != expected_type {
         let message = if args.len() > 2 {
@@ -357,7 +366,7 @@ fn native_test_regex(args: &[Value], _manager: &crate::native_modules::NativeMod
     }
     
     let pattern = match &args[0] {
-        Value::String(s) => s.as_str(),
+        Value::String(_) | Value::Regex(_) => crate::regexp::from_value(&args[0]),
         _ => return Err(RuntimeError::TypeError("Primeiro argumento deve ser uma string (padrão regex)".to_string())),
     };
     
@@ -366,8 +375,8 @@ fn native_test_regex(args: &[Value], _manager: &crate::native_modules::NativeMod
         _ => return Err(RuntimeError::TypeError("Segundo argumento deve ser uma string (texto)".to_string())),
     };
     
-    match regex::Regex::new(pattern) {
-        Ok(re) => Ok(Value::Bool(re.is_match(text))),
+    match pattern {
+        Ok(re) => Ok(Value::Bool(re.regex.is_match(text))),
         Err(e) => Err(RuntimeError::Generic(format!("Erro no padrão regex: {}", e))),
     }
 }
@@ -8,6 +8,8 @@ use csv::{Reader, Writer};
 use quick_xml::events::{Event, BytesEnd, BytesStart, BytesText};
 use quick_xml::{Reader as XmlReader, Writer as XmlWriter};
 use std::io::Cursor;
+use serde::de::DeserializeSeed;
+use super::serde_bridge::{SerializeValue, ValueSeed};
 
 /// Registra todas as funções nativas do módulo encode_decode
 pub fn register_encode_decode_functions(functions: &mut HashMap<String, NativeFunction>) {
@@ -17,6 +19,12 @@ pub fn register_encode_decode_functions(functions: &mut HashMap<String, NativeFu
     functions.insert("native_csv_decode".to_string(), native_csv_decode);
     functions.insert("native_xml_encode".to_string(), native_xml_encode);
     functions.insert("native_xml_decode".to_string(), native_xml_decode);
+    functions.insert("native_yaml_encode".to_string(), native_yaml_encode);
+    functions.insert("native_yaml_decode".to_string(), native_yaml_decode);
+    functions.insert("native_toml_encode".to_string(), native_toml_encode);
+    functions.insert("native_toml_decode".to_string(), native_toml_decode);
+    functions.insert("native_cbor_encode".to_string(), native_cbor_encode);
+    functions.insert("native_cbor_decode".to_string(), native_cbor_decode);
 }
 
 // ============================================
@@ -185,13 +193,180 @@ fn native_xml_decode(args: &[Value], _manager: &crate::native_modules::NativeMod
         .map_err(|e| RuntimeError::IoError(format!("Erro ao analisar XML: {}", e)))
 }
 
+// ============================================
+// YAML / TOML / CBOR
+// ============================================
+
+/// Opção `{ ordered: true }` dos decoders: mapeamentos viram `Map` (ordem
+/// do documento preservada) em vez de objetos
+fn ordered_option(options: Option<&Value>, heap: &Heap, function: &str) -> Result<bool, RuntimeError> {
+    match options {
+        None | Some(Value::Null) => Ok(false),
+        Some(Value::Object(id)) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => match properties.get("ordered") {
+                None | Some(Value::Null) => Ok(false),
+                Some(Value::Bool(b)) => Ok(*b),
+                Some(_) => Err(RuntimeError::TypeError(format!("{}: opção 'ordered' deve ser bool", function))),
+            },
+            _ => Err(RuntimeError::HeapError("Objeto de opções não encontrado".to_string())),
+        },
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: opções devem ser objeto", function))),
+    }
+}
+
+fn text_arg<'a>(args: &'a [Value], function: &str) -> Result<&'a str, RuntimeError> {
+    match args.first() {
+        Some(Value::String(s)) => Ok(s),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: primeiro argumento deve ser string", function))),
+        None => Err(RuntimeError::ArgumentError(format!("{}: esperado pelo menos 1 argumento", function))),
+    }
+}
+
+// native_yaml_encode(data) -> string
+fn native_yaml_encode(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::ArgumentError("native_yaml_encode: esperado 1 argumento".to_string()));
+    }
+
+    serde_yaml::to_string(&SerializeValue::new(&args[0], _heap))
+        .map(Value::String)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao codificar YAML: {}", e)))
+}
+
+// native_yaml_decode(yaml_string, options?) -> data
+fn native_yaml_decode(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let yaml_string = text_arg(args, "native_yaml_decode")?;
+    let ordered = ordered_option(args.get(1), _heap, "native_yaml_decode")?;
+
+    ValueSeed { heap: _heap, ordered }
+        .deserialize(serde_yaml::Deserializer::from_str(yaml_string))
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao decodificar YAML: {}", e)))
+}
+
+// native_toml_encode(table) -> string
+fn native_toml_encode(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::ArgumentError("native_toml_encode: esperado 1 argumento".to_string()));
+    }
+    if !matches!(args[0], Value::Object(_) | Value::Map(_)) {
+        return Err(RuntimeError::TypeError("native_toml_encode: o documento TOML deve ser um objeto ou Map".to_string()));
+    }
+
+    toml::to_string(&SerializeValue::new(&args[0], _heap))
+        .map(Value::String)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao codificar TOML: {}", e)))
+}
+
+// native_toml_decode(toml_string, options?) -> data
+fn native_toml_decode(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let toml_string = text_arg(args, "native_toml_decode")?;
+    let ordered = ordered_option(args.get(1), _heap, "native_toml_decode")?;
+
+    ValueSeed { heap: _heap, ordered }
+        .deserialize(toml::Deserializer::new(toml_string))
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao decodificar TOML: {}", e)))
+}
+
+// native_cbor_encode(data) -> array de bytes
+fn native_cbor_encode(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.len() != 1 {
+        return Err(RuntimeError::ArgumentError("native_cbor_encode: esperado 1 argumento".to_string()));
+    }
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&SerializeValue::new(&args[0], _heap), &mut bytes)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao codificar CBOR: {}", e)))?;
+    let items = bytes.into_iter().map(|b| Value::Number(b as f64)).collect();
+    Ok(Value::Array(_heap.allocate(ManagedObject::Array(items))))
+}
+
+// native_cbor_decode(bytes, options?) -> data
+fn native_cbor_decode(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let bytes = match args.first() {
+        Some(Value::Array(id)) => match _heap.get(*id) {
+            Some(ManagedObject::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    Value::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Ok(*n as u8),
+                    _ => Err(RuntimeError::TypeError("native_cbor_decode: array deve conter bytes (0-255)".to_string())),
+                })
+                .collect::<Result<Vec<u8>, _>>()?,
+            _ => return Err(RuntimeError::HeapError("Array reference not found".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError("native_cbor_decode: primeiro argumento deve ser array de bytes".to_string())),
+        None => return Err(RuntimeError::ArgumentError("native_cbor_decode: esperado pelo menos 1 argumento".to_string())),
+    };
+    let ordered = ordered_option(args.get(1), _heap, "native_cbor_decode")?;
+
+    // O ciborium não expõe seu Deserializer para seeds; lê o valor CBOR e
+    // converte em seguida
+    let cbor: ciborium::Value = ciborium::from_reader(bytes.as_slice())
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao decodificar CBOR: {}", e)))?;
+    cbor_to_runtime_value(&cbor, ordered, _heap)
+}
+
+fn cbor_to_runtime_value(value: &ciborium::Value, ordered: bool, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    use ciborium::Value as Cbor;
+
+    Ok(match value {
+        Cbor::Null => Value::Null,
+        Cbor::Bool(b) => Value::Bool(*b),
+        Cbor::Integer(i) => {
+            let i = i128::from(*i);
+            match i64::try_from(i) {
+                Ok(i) => crate::numeric::from_i64(i),
+                Err(_) => Value::BigInt(i.into()),
+            }
+        }
+        Cbor::Float(f) => Value::Number(*f),
+        Cbor::Text(s) => Value::String(s.clone()),
+        Cbor::Bytes(bytes) => {
+            let items = bytes.iter().map(|b| Value::Number(*b as f64)).collect();
+            Value::Array(heap.allocate(ManagedObject::Array(items)))
+        }
+        // Tags (datas, bignums acima de 128 bits...) são lidas sem a tag
+        Cbor::Tag(_, inner) => cbor_to_runtime_value(inner, ordered, heap)?,
+        Cbor::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| cbor_to_runtime_value(item, ordered, heap))
+                .collect::<Result<Vec<_>, _>>()?;
+            Value::Array(heap.allocate(ManagedObject::Array(items)))
+        }
+        Cbor::Map(entries) => {
+            let mut pairs = Vec::with_capacity(entries.len());
+            for (key, value) in entries {
+                pairs.push((cbor_to_runtime_value(key, ordered, heap)?, cbor_to_runtime_value(value, ordered, heap)?));
+            }
+            if ordered {
+                let mut map = crate::collections::MapEntries::new();
+                for (key, value) in pairs {
+                    let normalized = crate::collections::MapKey::of(&key, heap).map_err(RuntimeError::TypeError)?;
+                    map.insert(normalized, (key, value));
+                }
+                Value::Map(heap.allocate(ManagedObject::Map(map)))
+            } else {
+                let properties = pairs
+                    .into_iter()
+                    .map(|(key, value)| (crate::collections::json_key(&key).unwrap_or_else(|| key.to_string()), value))
+                    .collect();
+                Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+            }
+        }
+        _ => return Err(RuntimeError::TypeError("Valor CBOR não suportado".to_string())),
+    })
+}
+
 // ============================================
 // HELPER FUNCTIONS
 // ============================================
 
-fn runtime_value_to_json(value: &Value, heap: &Heap) -> Result<JsonValue, RuntimeError> {
+pub fn runtime_value_to_json(value: &Value, heap: &Heap) -> Result<JsonValue, RuntimeError> {
     match value {
         Value::Number(n) => Ok(JsonValue::Number(serde_json::Number::from_f64(*n).unwrap_or_else(|| serde_json::Number::from(0)))),
+        Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) => crate::numeric::to_json_number(value)
+            .map(JsonValue::Number)
+            .ok_or_else(|| RuntimeError::TypeError("Número não representável em JSON".to_string())),
         Value::String(s) => Ok(JsonValue::String(s.clone())),
         Value::Bool(b) => Ok(JsonValue::Bool(*b)),
         Value::Null => Ok(JsonValue::Null),
@@ -218,13 +393,39 @@ fn runtime_value_to_json(value: &Value, heap: &Heap) -> Result<JsonValue, Runtim
                 Err(RuntimeError::TypeError("Expected object in heap".to_string()))
             }
         }
+        // Map vira objeto (chaves string/número/bool) e Set vira array
+        Value::Map(id) => match heap.get(*id) {
+            Some(ManagedObject::Map(entries)) => {
+                let mut json_obj = Map::new();
+                for (key, val) in entries.values() {
+                    let key = crate::collections::json_key(key).ok_or_else(|| {
+                        RuntimeError::TypeError(format!("Chave de Map não conversível para JSON: {}", key.to_string()))
+                    })?;
+                    json_obj.insert(key, runtime_value_to_json(val, heap)?);
+                }
+                Ok(JsonValue::Object(json_obj))
+            }
+            _ => Err(RuntimeError::HeapError("Map reference not found".to_string())),
+        },
+        Value::Set(id) => match heap.get(*id) {
+            Some(ManagedObject::Set(entries)) => {
+                let json_array: Result<Vec<_>, _> =
+                    entries.values().map(|v| runtime_value_to_json(v, heap)).collect();
+                Ok(JsonValue::Array(json_array?))
+            }
+            _ => Err(RuntimeError::HeapError("Set reference not found".to_string())),
+        },
+        // Datas e durações viram strings ISO 8601; regexes, o literal /padrão/flags
+        Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) | Value::Regex(_) => {
+            Ok(JsonValue::String(value.to_string()))
+        }
         _ => Err(RuntimeError::TypeError("Tipo não suportado para JSON".to_string())),
     }
 }
 
 pub fn json_to_runtime_value(value: &JsonValue, heap: &mut Heap) -> Value {
     match value {
-        JsonValue::Number(n) => Value::Number(n.as_f64().unwrap_or(0.0)),
+        JsonValue::Number(n) => crate::numeric::from_json_number(n),
         JsonValue::String(s) => Value::String(s.clone()),
         JsonValue::Bool(b) => Value::Bool(*b),
         JsonValue::Null => Value::Null,
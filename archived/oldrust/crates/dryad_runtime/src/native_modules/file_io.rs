@@ -1,11 +1,21 @@
 use crate::interpreter::Value;
 use crate::errors::RuntimeError;
+use crate::heap::{Heap, ManagedObject};
+use crate::native_modules::NativeModuleManager;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::fs as tfs;
 use tokio::io::AsyncWriteExt;
 use std::future::Future;
 use std::pin::Pin;
 
+static NEXT_FILE_ID: AtomicU64 = AtomicU64::new(1);
+
 /// Verifica se um caminho de arquivo é seguro (dentro do sandbox)
 /// 
 /// Esta função implementa sandboxing de filesystem com as seguintes regras:
@@ -13,6 +23,8 @@ use std::pin::Pin;
 /// 2. Caminhos relativos são resolvidos contra o sandbox_root
 /// 3. Tentativas de path traversal (../) são bloqueadas
 /// 4. Symlinks são verificados para não escapar do sandbox
+/// 5. Entradas criadas por `tempfile`/`tempdir` deste interpreter fora do
+///    sandbox são permitidas enquanto estiverem em `TempPaths`
 fn is_path_safe(path_str: &str, manager: &crate::native_modules::NativeModuleManager) -> bool {
     // Obter o diretório base do sandbox (diretório atual se não definido)
     let sandbox_root = manager.sandbox_root()
@@ -31,41 +43,19 @@ fn is_path_safe(path_str: &str, manager: &crate::native_modules::NativeModuleMan
     if path_str.contains('\0') {
         return false;
     }
-    
-    // Resolver o caminho completo contra o sandbox_root
-    let resolved_path = if input_path.is_absolute() {
-        // Se for absoluto, verificar se está dentro do sandbox
-        // Primeiro normalizar para remover . e ..
-        match input_path.canonicalize() {
-            Ok(canonical) => canonical,
-            Err(_) => {
-                // Se não conseguir canonicalizar (arquivo não existe), 
-                // verificar pelo menos se o caminho começa com o root
-                return input_path.starts_with(&sandbox_root);
-            }
-        }
+
+    if input_path.is_absolute() && manager.temp_paths().contains(input_path) {
+        return true;
+    }
+
+    // Resolver `.`, `..` e symlinks antes de comparar com o sandbox_root,
+    // inclusive para caminhos que ainda não existem
+    let full_path = if input_path.is_absolute() {
+        input_path.to_path_buf()
     } else {
-        // Caminho relativo - resolver contra o sandbox_root
-        let full_path = sandbox_root.join(input_path);
-        match full_path.canonicalize() {
-            Ok(canonical) => canonical,
-            Err(_) => {
-                // Se não existe, verificar se o caminho normalizado está dentro do sandbox
-                // Remover componentes . e ..
-                let normalized = normalize_path(&full_path);
-                return normalized.starts_with(&sandbox_root);
-            }
-        }
+        sandbox_root.join(input_path)
     };
-    
-    // Verificar se o caminho resolvido está dentro do sandbox_root
-    // Usar canonicalize no sandbox_root também para comparação justa
-    let canonical_root = match sandbox_root.canonicalize() {
-        Ok(root) => root,
-        Err(_) => sandbox_root.clone(),
-    };
-    
-    resolved_path.starts_with(&canonical_root)
+    resolve_path(&full_path).starts_with(resolve_path(&sandbox_root))
 }
 
 /// Normaliza um caminho removendo . e .. sem acessar o filesystem
@@ -115,6 +105,71 @@ fn normalize_path(path: &Path) -> PathBuf {
     result
 }
 
+/// Caminho real: resolve os symlinks do trecho que já existe e normaliza o
+/// restante (que ainda não existe) sem acessar o filesystem
+fn resolve_path(path: &Path) -> PathBuf {
+    for existing in path.ancestors() {
+        if let Ok(real) = existing.canonicalize() {
+            let rest = path.strip_prefix(existing).unwrap_or(Path::new(""));
+            return normalize_path(&real.join(rest));
+        }
+    }
+    normalize_path(path)
+}
+
+/// Arquivos e diretórios criados por `tempfile`/`tempdir` fora do sandbox
+/// (só acontece sem `sandbox_root`, no diretório temporário do sistema).
+/// Cada `NativeModuleManager` tem a sua lista: outro interpreter não herda o
+/// acesso. A entrada sai quando o handle do `tempfile` é fechado ou quando o
+/// caminho é removido.
+#[derive(Default)]
+pub(crate) struct TempPaths {
+    /// Caminho resolvido e, para `tempfile`, o id do handle
+    entries: Mutex<Vec<(PathBuf, Option<String>)>>,
+}
+
+impl TempPaths {
+    fn allow(&self, path: PathBuf, handle: Option<String>) {
+        self.entries.lock().unwrap().push((path, handle));
+    }
+
+    /// O caminho é um dos temporários ou está dentro de um deles
+    fn contains(&self, path: &Path) -> bool {
+        let resolved = resolve_path(path);
+        self.entries.lock().unwrap().iter().any(|(temp, _)| resolved.starts_with(temp))
+    }
+
+    fn forget_handle(&self, id: &str) {
+        self.entries.lock().unwrap().retain(|(_, handle)| handle.as_deref() != Some(id));
+    }
+
+    /// Remove as entradas em `resolved` ou abaixo dele
+    fn forget_path(&self, resolved: &Path) {
+        self.entries.lock().unwrap().retain(|(temp, _)| !temp.starts_with(resolved));
+    }
+}
+
+/// Arquivos abertos por `file_open`/`file_lines`/`tempfile` de um
+/// interpreter, pelo id do handle. Outro interpreter não usa nem fecha esses
+/// arquivos.
+#[derive(Default)]
+pub(crate) struct OpenFiles {
+    files: Mutex<HashMap<String, OpenFile>>,
+}
+
+/// Valida o caminho no sandbox e o resolve: caminhos relativos partem do
+/// `sandbox_root` (ou do diretório atual, se não houver)
+fn sandboxed_path(path_str: &str, manager: &NativeModuleManager) -> Result<PathBuf, RuntimeError> {
+    if !is_path_safe(path_str, manager) {
+        return Err(RuntimeError::SystemError(format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path_str)));
+    }
+    let path = Path::new(path_str);
+    Ok(match manager.sandbox_root() {
+        Some(root) if path.is_relative() => root.join(path),
+        _ => path.to_path_buf(),
+    })
+}
+
 pub fn register_file_io_functions(functions: &mut std::collections::HashMap<String, crate::native_modules::NativeFunction>) {
     functions.insert("native_read_file".to_string(), native_read_file);
     functions.insert("native_write_file".to_string(), native_write_file);
@@ -135,6 +190,24 @@ pub fn register_file_io_functions(functions: &mut std::collections::HashMap<Stri
     functions.insert("mkdir".to_string(), native_mkdir);
     functions.insert("remove_file".to_string(), native_remove_file);
     functions.insert("remove_dir".to_string(), native_remove_dir);
+
+    // Handles de arquivo
+    functions.insert("file_open".to_string(), native_file_open);
+    functions.insert("file_read".to_string(), native_file_read);
+    functions.insert("file_read_line".to_string(), native_file_read_line);
+    functions.insert("file_lines".to_string(), native_file_lines);
+    functions.insert("file_write".to_string(), native_file_write);
+    functions.insert("file_seek".to_string(), native_file_seek);
+    functions.insert("file_tell".to_string(), native_file_tell);
+    functions.insert("file_flush".to_string(), native_file_flush);
+    functions.insert("file_close".to_string(), native_file_close);
+
+    // Diretórios, metadados e temporários
+    functions.insert("walk_dir".to_string(), native_walk_dir);
+    functions.insert("glob".to_string(), native_glob);
+    functions.insert("stat".to_string(), native_stat);
+    functions.insert("tempfile".to_string(), native_tempfile);
+    functions.insert("tempdir".to_string(), native_tempdir);
 }
 
 pub fn register_file_io_async_functions(functions: &mut std::collections::HashMap<String, crate::native_modules::AsyncNativeFunction>) {
@@ -154,11 +227,9 @@ fn native_read_file(args: &[Value], _manager: &crate::native_modules::NativeModu
         _ => return Err(RuntimeError::TypeError("Argumento de caminho deve ser string".to_string())),
     };
     
-    if !is_path_safe(path_str, _manager) {
-        return Err(RuntimeError::SystemError(format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path_str)));
-    }
+    let path = sandboxed_path(path_str, _manager)?;
     
-    match std::fs::read_to_string(path_str) {
+    match std::fs::read_to_string(path) {
         Ok(content) => Ok(Value::String(content)),
         Err(e) => Err(RuntimeError::IoError(format!("Erro ao ler arquivo: {}", e))),
     }
@@ -174,13 +245,11 @@ fn native_write_file(args: &[Value], _manager: &crate::native_modules::NativeMod
         _ => return Err(RuntimeError::TypeError("Argumento de caminho deve ser string".to_string())),
     };
     
-    if !is_path_safe(path_str, _manager) {
-        return Err(RuntimeError::SystemError(format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path_str)));
-    }
+    let path = sandboxed_path(path_str, _manager)?;
     
     let content = args[1].to_string();
     
-    match std::fs::write(path_str, content) {
+    match write_atomic(&path, content.as_bytes()) {
         Ok(_) => Ok(Value::Null),
         Err(e) => Err(RuntimeError::IoError(format!("Erro ao escrever arquivo: {}", e))),
     }
@@ -196,17 +265,14 @@ fn native_append_file(args: &[Value], _manager: &crate::native_modules::NativeMo
         _ => return Err(RuntimeError::TypeError("Argumento de caminho deve ser string".to_string())),
     };
     
-    if !is_path_safe(path_str, _manager) {
-        return Err(RuntimeError::SystemError(format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path_str)));
-    }
+    let path = sandboxed_path(path_str, _manager)?;
     
     let content = args[1].to_string();
     
-    use std::io::Write;
     let mut file = std::fs::OpenOptions::new()
         .create(true)
         .append(true)
-        .open(path_str)
+        .open(path)
         .map_err(|e| RuntimeError::IoError(format!("Erro ao abrir arquivo: {}", e)))?;
         
     file.write_all(content.as_bytes())
@@ -218,23 +284,27 @@ fn native_append_file(args: &[Value], _manager: &crate::native_modules::NativeMo
 fn native_file_exists(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.is_empty() { return Err(RuntimeError::ArgumentError("fileExists espera 1 argumento".to_string())); }
     let path = match &args[0] { Value::String(s) => s, _ => return Err(RuntimeError::TypeError("Caminho deve ser string".to_string())) };
-    if !is_path_safe(path, _manager) { return Ok(Value::Bool(false)); }
-    Ok(Value::Bool(Path::new(path).exists()))
+    match sandboxed_path(path, _manager) {
+        Ok(path) => Ok(Value::Bool(path.exists())),
+        Err(_) => Ok(Value::Bool(false)),
+    }
 }
 
 fn native_is_dir(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.is_empty() { return Err(RuntimeError::ArgumentError("isDir espera 1 argumento".to_string())); }
     let path = match &args[0] { Value::String(s) => s, _ => return Err(RuntimeError::TypeError("Caminho deve ser string".to_string())) };
-    if !is_path_safe(path, _manager) { return Ok(Value::Bool(false)); }
-    Ok(Value::Bool(Path::new(path).is_dir()))
+    match sandboxed_path(path, _manager) {
+        Ok(path) => Ok(Value::Bool(path.is_dir())),
+        Err(_) => Ok(Value::Bool(false)),
+    }
 }
 
 fn native_list_dir(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.is_empty() { return Err(RuntimeError::ArgumentError("listDir espera 1 argumento".to_string())); }
     let path_str = match &args[0] { Value::String(s) => s, _ => return Err(RuntimeError::TypeError("Caminho deve ser string".to_string())) };
-    if !is_path_safe(path_str, _manager) { return Err(RuntimeError::SystemError("Acesso negado".to_string())); }
+    let path = sandboxed_path(path_str, _manager)?;
     
-    let entries = std::fs::read_dir(path_str).map_err(|e| RuntimeError::IoError(e.to_string()))?;
+    let entries = std::fs::read_dir(path).map_err(|e| RuntimeError::IoError(e.to_string()))?;
     let mut file_names = Vec::new();
     for entry in entries {
         if let Ok(e) = entry {
@@ -249,7 +319,7 @@ fn native_list_dir(args: &[Value], _manager: &crate::native_modules::NativeModul
 fn native_mkdir(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.is_empty() { return Err(RuntimeError::ArgumentError("mkdir espera 1 argumento".to_string())); }
     let path = match &args[0] { Value::String(s) => s, _ => return Err(RuntimeError::TypeError("Caminho deve ser string".to_string())) };
-    if !is_path_safe(path, _manager) { return Err(RuntimeError::SystemError("Acesso negado".to_string())); }
+    let path = sandboxed_path(path, _manager)?;
     std::fs::create_dir_all(path).map_err(|e| RuntimeError::IoError(e.to_string()))?;
     Ok(Value::Null)
 }
@@ -257,15 +327,18 @@ fn native_mkdir(args: &[Value], _manager: &crate::native_modules::NativeModuleMa
 fn native_remove_file(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.is_empty() { return Err(RuntimeError::ArgumentError("removeFile espera 1 argumento".to_string())); }
     let path = match &args[0] { Value::String(s) => s, _ => return Err(RuntimeError::TypeError("Caminho deve ser string".to_string())) };
-    if !is_path_safe(path, _manager) { return Err(RuntimeError::SystemError("Acesso negado".to_string())); }
+    let path = sandboxed_path(path, _manager)?;
+    let resolved = resolve_path(&path);
     std::fs::remove_file(path).map_err(|e| RuntimeError::IoError(e.to_string()))?;
+    _manager.temp_paths().forget_path(&resolved);
     Ok(Value::Null)
 }
 
 fn native_remove_dir(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.is_empty() { return Err(RuntimeError::ArgumentError("removeDir espera 1 argumento".to_string())); }
     let path = match &args[0] { Value::String(s) => s, _ => return Err(RuntimeError::TypeError("Caminho deve ser string".to_string())) };
-    if !is_path_safe(path, _manager) { return Err(RuntimeError::SystemError("Acesso negado".to_string())); }
+    let path = sandboxed_path(path, _manager)?;
+    let resolved = resolve_path(&path);
     
     let recursive = args.len() > 1 && match &args[1] { Value::Bool(b) => *b, _ => false };
     if recursive {
@@ -273,6 +346,7 @@ fn native_remove_dir(args: &[Value], _manager: &crate::native_modules::NativeMod
     } else {
         std::fs::remove_dir(path).map_err(|e| RuntimeError::IoError(e.to_string()))?;
     }
+    _manager.temp_paths().forget_path(&resolved);
     Ok(Value::Null)
 }
 
@@ -287,18 +361,15 @@ fn async_read_file(args: Vec<Value>, _manager: &crate::native_modules::NativeMod
         _ => return Box::pin(async { Err(RuntimeError::TypeError("Argumento deve ser uma string".to_string())) })
     };
     
-    if !is_path_safe(&path, _manager) {
-        return Box::pin(async move {
-            Err(RuntimeError::SystemError(
-                format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path)
-            ))
-        });
-    }
+    let path = match sandboxed_path(&path, _manager) {
+        Ok(path) => path,
+        Err(e) => return Box::pin(async move { Err(e) }),
+    };
     
     Box::pin(async move {
         match tfs::read_to_string(&path).await {
             Ok(content) => Ok(Value::String(content)),
-            Err(e) => Err(RuntimeError::IoError(format!("Erro ao ler arquivo '{}': {}", path, e)))
+            Err(e) => Err(RuntimeError::IoError(format!("Erro ao ler arquivo '{}': {}", path.display(), e)))
         }
     })
 }
@@ -313,20 +384,17 @@ fn async_write_file(args: Vec<Value>, _manager: &crate::native_modules::NativeMo
         _ => return Box::pin(async { Err(RuntimeError::TypeError("Primeiro argumento deve ser uma string".to_string())) })
     };
     
-    if !is_path_safe(&path, _manager) {
-        return Box::pin(async move {
-            Err(RuntimeError::SystemError(
-                format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path)
-            ))
-        });
-    }
+    let path = match sandboxed_path(&path, _manager) {
+        Ok(path) => path,
+        Err(e) => return Box::pin(async move { Err(e) }),
+    };
     
     let data = args[1].to_string();
     
     Box::pin(async move {
         match tfs::write(&path, data).await {
             Ok(_) => Ok(Value::Null),
-            Err(e) => Err(RuntimeError::IoError(format!("Erro ao escrever arquivo '{}': {}", path, e)))
+            Err(e) => Err(RuntimeError::IoError(format!("Erro ao escrever arquivo '{}': {}", path.display(), e)))
         }
     })
 }
@@ -341,25 +409,855 @@ fn async_append_file(args: Vec<Value>, _manager: &crate::native_modules::NativeM
         _ => return Box::pin(async { Err(RuntimeError::TypeError("Primeiro argumento deve ser uma string".to_string())) })
     };
     
-    if !is_path_safe(&path, _manager) {
-        return Box::pin(async move {
-            Err(RuntimeError::SystemError(
-                format!("Acesso negado: o caminho '{}' está fora do sandbox permitido.", path)
-            ))
-        });
-    }
+    let path = match sandboxed_path(&path, _manager) {
+        Ok(path) => path,
+        Err(e) => return Box::pin(async move { Err(e) }),
+    };
     
     let data = args[1].to_string();
     
     Box::pin(async move {
         let mut file = match tfs::OpenOptions::new().create(true).append(true).open(&path).await {
             Ok(f) => f,
-            Err(e) => return Err(RuntimeError::IoError(format!("Erro ao abrir arquivo '{}': {}", path, e)))
+            Err(e) => return Err(RuntimeError::IoError(format!("Erro ao abrir arquivo '{}': {}", path.display(), e)))
         };
         
         match file.write_all(data.as_bytes()).await {
             Ok(_) => Ok(Value::Null),
-            Err(e) => Err(RuntimeError::IoError(format!("Erro ao adicionar ao arquivo '{}': {}", path, e)))
+            Err(e) => Err(RuntimeError::IoError(format!("Erro ao adicionar ao arquivo '{}': {}", path.display(), e)))
+        }
+    })
+}
+
+// ========================
+// Escrita atômica e temporários
+// ========================
+
+/// Escreve em um arquivo temporário no mesmo diretório e o renomeia por cima
+/// do destino: quem lê vê o conteúdo antigo ou o novo, nunca um parcial
+fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    // Symlinks são seguidos para substituir o arquivo apontado
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let name = target
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "caminho sem nome de arquivo"))?;
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let (temp_path, mut file) = create_unique_file(&dir, &format!(".{}.", name.to_string_lossy()), ".tmp")?;
+    let result = file
+        .write_all(content)
+        .and_then(|_| match fs::metadata(&target) {
+            Ok(metadata) => fs::set_permissions(&temp_path, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|_| file.sync_all())
+        .and_then(|_| fs::rename(&temp_path, &target));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn unique_name(prefix: &str, suffix: &str) -> String {
+    let random = uuid::Uuid::new_v4().simple().to_string();
+    format!("{}{}{}", prefix, &random[..12], suffix)
+}
+
+/// Cria um arquivo novo com nome aleatório em `dir`
+fn create_unique_file(dir: &Path, prefix: &str, suffix: &str) -> io::Result<(PathBuf, File)> {
+    loop {
+        let path = dir.join(unique_name(prefix, suffix));
+        match OpenOptions::new().read(true).write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Caminho final de um temporário recém-criado; fora do sandbox ele é
+/// liberado só para este interpreter
+fn register_temp_path(path: &Path, handle: Option<String>, manager: &NativeModuleManager) -> PathBuf {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if !is_path_safe(&path.to_string_lossy(), manager) {
+        manager.temp_paths().allow(path.clone(), handle);
+    }
+    path
+}
+
+/// `{ prefix?, suffix?, dir? }` de `tempfile`/`tempdir`; `dir` padrão é o
+/// `sandbox_root` ou, sem sandbox, o diretório temporário do sistema
+fn temp_options(args: &[Value], heap: &Heap, manager: &NativeModuleManager, function: &str) -> Result<(String, String, PathBuf), RuntimeError> {
+    let mut prefix = "dryad_".to_string();
+    let mut suffix = String::new();
+    let mut dir = manager.sandbox_root().cloned().unwrap_or_else(std::env::temp_dir);
+    match args.first() {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(id)) => {
+            let properties = object_properties(*id, heap)?;
+            if let Some(value) = string_option(properties, "prefix", function)? {
+                prefix = value;
+            }
+            if let Some(value) = string_option(properties, "suffix", function)? {
+                suffix = value;
+            }
+            if let Some(value) = string_option(properties, "dir", function)? {
+                dir = sandboxed_path(&value, manager)?;
+            }
+        }
+        Some(_) => return Err(RuntimeError::TypeError(format!("{}: opções devem ser um objeto", function))),
+    }
+    if prefix.contains(['/', '\\']) || suffix.contains(['/', '\\']) {
+        return Err(RuntimeError::ArgumentError(format!("{}: prefix e suffix não podem conter separadores de caminho", function)));
+    }
+    Ok((prefix, suffix, dir))
+}
+
+/// tempfile(opcoes?) -> arquivo
+/// Cria um arquivo vazio com nome único e o abre no modo "w+". O caminho
+/// fica em `arquivo.path`; fora do sandbox ele só é acessível por este
+/// interpreter até `file_close`.
+fn native_tempfile(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let (prefix, suffix, dir) = temp_options(args, heap, manager, "tempfile")?;
+    let (path, file) = create_unique_file(&dir, &prefix, &suffix)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao criar arquivo temporário em '{}': {}", dir.display(), e)))?;
+    let resolved = path.canonicalize().unwrap_or(path);
+    let mode = FileMode::parse("w+").expect("modo válido");
+    let handle = register_file(file, resolved.to_string_lossy().into_owned(), mode, false, manager, heap);
+    register_temp_path(&resolved, Some(file_id(Some(&handle), heap, "tempfile")?), manager);
+    Ok(handle)
+}
+
+/// tempdir(opcoes?) -> caminho
+/// Cria um diretório vazio com nome único; fora do sandbox ele e seu
+/// conteúdo só são acessíveis por este interpreter até a remoção
+fn native_tempdir(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let (prefix, suffix, dir) = temp_options(args, heap, manager, "tempdir")?;
+    loop {
+        let path = dir.join(unique_name(&prefix, &suffix));
+        match fs::create_dir(&path) {
+            Ok(()) => return Ok(Value::String(register_temp_path(&path, None, manager).to_string_lossy().into_owned())),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(RuntimeError::IoError(format!("Erro ao criar diretório temporário em '{}': {}", dir.display(), e)))
+            }
+        }
+    }
+}
+
+// ========================
+// Handles de arquivo
+// ========================
+
+/// Modo de abertura no estilo de `fopen`: "r", "w", "a", "x", com "+" para
+/// leitura e escrita e "b" para ler bytes em vez de texto
+#[derive(Clone, Copy)]
+struct FileMode {
+    text: &'static str,
+    read: bool,
+    write: bool,
+    append: bool,
+    binary: bool,
+}
+
+impl FileMode {
+    fn parse(mode: &str) -> Option<Self> {
+        let binary = mode.contains('b');
+        let base: String = mode.chars().filter(|c| *c != 'b').collect();
+        let (text, read, write, append) = match base.as_str() {
+            "r" => ("r", true, false, false),
+            "r+" => ("r+", true, true, false),
+            "w" => ("w", false, true, false),
+            "w+" => ("w+", true, true, false),
+            "a" => ("a", false, true, true),
+            "a+" => ("a+", true, true, true),
+            "x" => ("x", false, true, false),
+            "x+" => ("x+", true, true, false),
+            _ => return None,
+        };
+        Some(FileMode { text, read, write, append, binary })
+    }
+
+    fn options(&self) -> OpenOptions {
+        let mut options = OpenOptions::new();
+        options.read(self.read).write(self.write && !self.append).append(self.append);
+        match self.text.trim_end_matches('+') {
+            "w" => {
+                options.create(true).truncate(true);
+            }
+            "a" => {
+                options.create(true);
+            }
+            "x" => {
+                options.create_new(true);
+            }
+            _ => {}
+        }
+        options
+    }
+}
+
+struct OpenFile {
+    path: String,
+    mode: FileMode,
+    /// Leituras passam pelo buffer; escritas vão direto ao arquivo, depois de
+    /// reposicionar o cursor lógico
+    reader: BufReader<File>,
+    /// Abertos por `file_lines(caminho)`: fechados ao chegar no fim
+    close_at_eof: bool,
+}
+
+impl OpenFile {
+    fn io_error(&self, action: &str, e: io::Error) -> RuntimeError {
+        RuntimeError::IoError(format!("Erro ao {} '{}': {}", action, self.path, e))
+    }
+
+    fn ensure(&self, allowed: bool, action: &str) -> Result<(), RuntimeError> {
+        if allowed {
+            Ok(())
+        } else {
+            Err(RuntimeError::IoError(format!(
+                "Arquivo '{}' aberto no modo \"{}\" não permite {}",
+                self.path, self.mode.text, action
+            )))
+        }
+    }
+
+    /// Próxima linha sem o terminador (`\n` ou `\r\n`); `None` no fim
+    fn read_line(&mut self) -> Result<Option<Vec<u8>>, RuntimeError> {
+        self.ensure(self.mode.read, "leitura")?;
+        let mut line = Vec::new();
+        let read = self.reader.read_until(b'\n', &mut line).map_err(|e| self.io_error("ler", e))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+        }
+        Ok(Some(line))
+    }
+
+    fn to_value(&self, bytes: Vec<u8>, heap: &mut Heap) -> Result<Value, RuntimeError> {
+        if self.mode.binary {
+            let items = bytes.into_iter().map(|b| Value::Number(b as f64)).collect();
+            return Ok(Value::Array(heap.allocate(ManagedObject::Array(items))));
+        }
+        String::from_utf8(bytes)
+            .map(Value::String)
+            .map_err(|_| RuntimeError::IoError(format!("Arquivo '{}' não contém UTF-8 válido (use o modo \"b\")", self.path)))
+    }
+}
+
+/// Registra o arquivo e devolve o handle `{ _type: "file", id, path, mode }`
+fn register_file(file: File, path: String, mode: FileMode, close_at_eof: bool, manager: &NativeModuleManager, heap: &mut Heap) -> Value {
+    let id = format!("file_{}", NEXT_FILE_ID.fetch_add(1, Ordering::SeqCst));
+    let mut properties = HashMap::new();
+    properties.insert("_type".to_string(), Value::String("file".to_string()));
+    properties.insert("id".to_string(), Value::String(id.clone()));
+    properties.insert("path".to_string(), Value::String(path.clone()));
+    properties.insert("mode".to_string(), Value::String(mode.text.to_string() + if mode.binary { "b" } else { "" }));
+    manager.open_files().files.lock().unwrap().insert(id, OpenFile { path, mode, reader: BufReader::new(file), close_at_eof });
+    Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+}
+
+/// Id do handle devolvido por `file_open`/`file_lines`/`tempfile`
+fn file_id(value: Option<&Value>, heap: &Heap, function: &str) -> Result<String, RuntimeError> {
+    match value {
+        Some(value @ Value::Object(obj)) if is_file_handle(value, heap) => match object_properties(*obj, heap)?.get("id") {
+            Some(Value::String(id)) => Ok(id.clone()),
+            _ => Err(RuntimeError::TypeError(format!("{}: objeto não é um arquivo aberto", function))),
+        },
+        _ => Err(RuntimeError::TypeError(format!("{}: esperado um arquivo aberto", function))),
+    }
+}
+
+fn with_file<T>(
+    args: &[Value],
+    manager: &NativeModuleManager,
+    heap: &Heap,
+    function: &str,
+    f: impl FnOnce(&mut OpenFile) -> Result<T, RuntimeError>,
+) -> Result<T, RuntimeError> {
+    let id = file_id(args.first(), heap, function)?;
+    let mut files = manager.open_files().files.lock().unwrap();
+    let file = files
+        .get_mut(&id)
+        .ok_or_else(|| RuntimeError::IoError(format!("{}: arquivo '{}' não está aberto", function, id)))?;
+    f(file)
+}
+
+/// Se o valor é um handle de arquivo (o `for-in` o percorre linha a linha)
+pub fn is_file_handle(value: &Value, heap: &Heap) -> bool {
+    match value {
+        Value::Object(id) => matches!(
+            heap.get(*id),
+            Some(ManagedObject::Object { properties, .. })
+                if matches!(properties.get("_type"), Some(Value::String(t)) if t == "file")
+        ),
+        _ => false,
+    }
+}
+
+/// Próxima linha do arquivo para o `for-in`; `null` no fim
+pub fn next_line(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let id = file_id(args.first(), heap, "file_lines")?;
+    let mut files = manager.open_files().files.lock().unwrap();
+    let file = files
+        .get_mut(&id)
+        .ok_or_else(|| RuntimeError::IoError(format!("Arquivo '{}' não está aberto", id)))?;
+    match file.read_line()? {
+        Some(line) => file.to_value(line, heap),
+        None => {
+            if file.close_at_eof {
+                files.remove(&id);
+            }
+            Ok(Value::Null)
+        }
+    }
+}
+
+/// file_open(caminho, modo?) -> arquivo
+/// Modos: "r" (padrão), "r+", "w", "w+", "a", "a+", "x", "x+"; "b" lê bytes
+fn native_file_open(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let path_str = string_arg(args, 0, "caminho", "file_open")?;
+    let mode_text = match args.get(1) {
+        None | Some(Value::Null) => "r".to_string(),
+        Some(Value::String(mode)) => mode.clone(),
+        Some(_) => return Err(RuntimeError::TypeError("file_open: modo deve ser string".to_string())),
+    };
+    let mode = FileMode::parse(&mode_text)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("file_open: modo inválido \"{}\"", mode_text)))?;
+    let path = sandboxed_path(&path_str, manager)?;
+    let file = mode
+        .options()
+        .open(&path)
+        .map_err(|e| RuntimeError::IoError(format!("Erro ao abrir '{}': {}", path_str, e)))?;
+    Ok(register_file(file, path_str, mode, false, manager, heap))
+}
+
+/// file_read(arquivo, n?) -> string | bytes | null
+/// Sem `n`, lê até o fim ("" se não houver mais nada); com `n`, até `n`
+/// bytes e `null` no fim do arquivo
+fn native_file_read(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let limit = match args.get(1) {
+        None | Some(Value::Null) => None,
+        Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as u64),
+        Some(_) => return Err(RuntimeError::TypeError("file_read: n deve ser um inteiro não negativo".to_string())),
+    };
+    let (bytes, at_eof, binary, path) = with_file(args, manager, heap, "file_read", |file| {
+        file.ensure(file.mode.read, "leitura")?;
+        let mut bytes = Vec::new();
+        let result = match limit {
+            Some(n) => file.reader.by_ref().take(n).read_to_end(&mut bytes),
+            None => file.reader.read_to_end(&mut bytes),
+        };
+        result.map_err(|e| file.io_error("ler", e))?;
+        Ok((bytes, limit.is_some_and(|n| n > 0), file.mode.binary, file.path.clone()))
+    })?;
+    if at_eof && bytes.is_empty() {
+        return Ok(Value::Null);
+    }
+    if binary {
+        let items = bytes.into_iter().map(|b| Value::Number(b as f64)).collect();
+        return Ok(Value::Array(heap.allocate(ManagedObject::Array(items))));
+    }
+    String::from_utf8(bytes)
+        .map(Value::String)
+        .map_err(|_| RuntimeError::IoError(format!("Arquivo '{}' não contém UTF-8 válido neste trecho (use o modo \"b\")", path)))
+}
+
+/// file_read_line(arquivo) -> string | bytes | null
+/// Linha sem o terminador; `null` no fim do arquivo
+fn native_file_read_line(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let id = file_id(args.first(), heap, "file_read_line")?;
+    let mut files = manager.open_files().files.lock().unwrap();
+    let file = files
+        .get_mut(&id)
+        .ok_or_else(|| RuntimeError::IoError(format!("file_read_line: arquivo '{}' não está aberto", id)))?;
+    match file.read_line()? {
+        Some(line) => file.to_value(line, heap),
+        None => Ok(Value::Null),
+    }
+}
+
+/// file_lines(caminho | arquivo) -> arquivo
+/// Iterável preguiçoso: `for linha in file_lines("log.txt") { ... }` lê uma
+/// linha por volta. Com um caminho, o arquivo é fechado ao chegar no fim.
+fn native_file_lines(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    match args.first() {
+        Some(Value::String(path_str)) => {
+            let path = sandboxed_path(path_str, manager)?;
+            let file = File::open(&path).map_err(|e| RuntimeError::IoError(format!("Erro ao abrir '{}': {}", path_str, e)))?;
+            let mode = FileMode::parse("r").expect("modo válido");
+            Ok(register_file(file, path_str.clone(), mode, true, manager, heap))
+        }
+        Some(value) if is_file_handle(value, heap) => Ok(value.clone()),
+        _ => Err(RuntimeError::TypeError("file_lines: esperado um caminho ou arquivo aberto".to_string())),
+    }
+}
+
+/// file_write(arquivo, dados) -> bytes escritos
+/// Aceita string (UTF-8) ou array de bytes
+fn native_file_write(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let data = super::tcp::data_arg(args.get(1), heap, "file_write")?;
+    with_file(args, manager, heap, "file_write", |file| {
+        file.ensure(file.mode.write, "escrita")?;
+        if !file.mode.append && !file.reader.buffer().is_empty() {
+            // Descarta o que foi lido adiante para escrever na posição lógica
+            let position = file.reader.stream_position().map_err(|e| file.io_error("posicionar", e))?;
+            file.reader.seek(SeekFrom::Start(position)).map_err(|e| file.io_error("posicionar", e))?;
+        }
+        file.reader.get_mut().write_all(&data).map_err(|e| file.io_error("escrever em", e))?;
+        Ok(Value::Number(data.len() as f64))
+    })
+}
+
+/// file_seek(arquivo, offset, origem?) -> nova posição
+/// Origem: "start" (padrão), "current" ou "end"
+fn native_file_seek(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let offset = match args.get(1) {
+        Some(Value::Number(n)) if n.fract() == 0.0 => *n as i64,
+        _ => return Err(RuntimeError::TypeError("file_seek: offset deve ser um inteiro".to_string())),
+    };
+    let target = match args.get(2) {
+        None | Some(Value::Null) => {
+            if offset < 0 {
+                return Err(RuntimeError::ArgumentError("file_seek: offset negativo a partir do início".to_string()));
+            }
+            SeekFrom::Start(offset as u64)
+        }
+        Some(Value::String(s)) if s == "start" => {
+            if offset < 0 {
+                return Err(RuntimeError::ArgumentError("file_seek: offset negativo a partir do início".to_string()));
+            }
+            SeekFrom::Start(offset as u64)
         }
+        Some(Value::String(s)) if s == "current" => SeekFrom::Current(offset),
+        Some(Value::String(s)) if s == "end" => SeekFrom::End(offset),
+        Some(_) => {
+            return Err(RuntimeError::ArgumentError(
+                "file_seek: origem deve ser \"start\", \"current\" ou \"end\"".to_string(),
+            ))
+        }
+    };
+    with_file(args, manager, heap, "file_seek", |file| {
+        let position = file.reader.seek(target).map_err(|e| file.io_error("posicionar", e))?;
+        Ok(Value::Number(position as f64))
     })
 }
+
+/// file_tell(arquivo) -> posição atual
+fn native_file_tell(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    with_file(args, manager, heap, "file_tell", |file| {
+        let position = file.reader.stream_position().map_err(|e| file.io_error("posicionar", e))?;
+        Ok(Value::Number(position as f64))
+    })
+}
+
+/// file_flush(arquivo) -> null
+/// Garante que o que foi escrito chegou ao disco
+fn native_file_flush(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    with_file(args, manager, heap, "file_flush", |file| {
+        let inner = file.reader.get_mut();
+        inner.flush().and_then(|_| inner.sync_data()).map_err(|e| file.io_error("sincronizar", e))?;
+        Ok(Value::Null)
+    })
+}
+
+/// file_close(arquivo) -> null
+/// Fechar um arquivo já fechado não é erro
+fn native_file_close(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let id = file_id(args.first(), heap, "file_close")?;
+    manager.open_files().files.lock().unwrap().remove(&id);
+    manager.temp_paths().forget_handle(&id);
+    Ok(Value::Null)
+}
+
+// ========================
+// Metadados
+// ========================
+
+fn millis_since_epoch(time: io::Result<SystemTime>) -> Value {
+    match time.ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()) {
+        Some(duration) => Value::Number(duration.as_millis() as f64),
+        None => Value::Null,
+    }
+}
+
+#[cfg(unix)]
+fn permission_bits(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn permission_bits(metadata: &fs::Metadata) -> u32 {
+    if metadata.permissions().readonly() { 0o444 } else { 0o666 }
+}
+
+/// "rwxr-xr-x" a partir dos bits de permissão
+fn permission_string(mode: u32) -> String {
+    (0..9)
+        .map(|i| {
+            let bit = 1 << (8 - i);
+            if mode & bit == 0 {
+                '-'
+            } else {
+                ['r', 'w', 'x'][i % 3]
+            }
+        })
+        .collect()
+}
+
+/// stat(caminho) -> { size, is_file, is_dir, is_symlink, modified, accessed,
+/// created, mode, permissions, readonly }
+/// Datas em milissegundos desde a época Unix (`null` se o sistema não
+/// fornece); `mode` são os bits de permissão (ex.: 420 = 0o644)
+fn native_stat(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let path_str = string_arg(args, 0, "caminho", "stat")?;
+    let path = sandboxed_path(&path_str, manager)?;
+    let link = fs::symlink_metadata(&path).map_err(|e| RuntimeError::IoError(format!("Erro ao consultar '{}': {}", path_str, e)))?;
+    // Symlinks quebrados são descritos pelo próprio link
+    let metadata = fs::metadata(&path).unwrap_or_else(|_| link.clone());
+    let mode = permission_bits(&metadata);
+
+    let mut properties = HashMap::new();
+    properties.insert("path".to_string(), Value::String(path_str));
+    properties.insert("size".to_string(), Value::Number(metadata.len() as f64));
+    properties.insert("is_file".to_string(), Value::Bool(metadata.is_file()));
+    properties.insert("is_dir".to_string(), Value::Bool(metadata.is_dir()));
+    properties.insert("is_symlink".to_string(), Value::Bool(link.file_type().is_symlink()));
+    properties.insert("modified".to_string(), millis_since_epoch(metadata.modified()));
+    properties.insert("accessed".to_string(), millis_since_epoch(metadata.accessed()));
+    properties.insert("created".to_string(), millis_since_epoch(metadata.created()));
+    properties.insert("mode".to_string(), Value::Number(mode as f64));
+    properties.insert("permissions".to_string(), Value::String(permission_string(mode)));
+    properties.insert("readonly".to_string(), Value::Bool(metadata.permissions().readonly()));
+    Ok(Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() })))
+}
+
+// ========================
+// walk_dir e glob
+// ========================
+
+/// Casa um nome com um padrão glob de um componente: `*`, `?` e classes
+/// `[abc]`, `[a-z]`, `[!abc]`
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Posição do último `*` e do nome quando ele foi visto, para retroceder
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        let step = match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+                continue;
+            }
+            Some('?') => Some(p + 1),
+            Some('[') => match_class(&pattern, p, name[n]),
+            Some(c) if *c == name[n] => Some(p + 1),
+            _ => None,
+        };
+        match (step, star) {
+            (Some(next), _) => {
+                p = next;
+                n += 1;
+            }
+            (None, Some((star_p, star_n))) => {
+                p = star_p + 1;
+                n = star_n + 1;
+                star = Some((star_p, star_n + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Tenta casar `c` com a classe que começa em `pattern[start] == '['`;
+/// devolve a posição depois do `]`
+fn match_class(pattern: &[char], start: usize, c: char) -> Option<usize> {
+    let mut i = start + 1;
+    let negated = matches!(pattern.get(i), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    while let Some(&current) = pattern.get(i) {
+        if current == ']' && !first {
+            return (matched != negated).then_some(i + 1);
+        }
+        first = false;
+        if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|end| *end != ']') {
+            if (current..=pattern[i + 2]).contains(&c) {
+                matched = true;
+            }
+            i += 3;
+        } else {
+            if current == c {
+                matched = true;
+            }
+            i += 1;
+        }
+    }
+    // `[` sem fechamento é literal
+    (c == '[').then_some(start + 1)
+}
+
+fn has_wildcard(part: &str) -> bool {
+    part.contains(['*', '?', '['])
+}
+
+fn is_hidden(name: &str) -> bool {
+    name.starts_with('.')
+}
+
+/// Entradas de um diretório em ordem alfabética (erros de leitura são ignorados)
+fn sorted_entries(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut entries: Vec<(String, PathBuf)> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| (entry.file_name().to_string_lossy().into_owned(), entry.path()))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    entries.sort();
+    entries
+}
+
+struct WalkOptions {
+    max_depth: Option<usize>,
+    include_dirs: bool,
+    include_files: bool,
+    hidden: bool,
+    follow_links: bool,
+    extensions: Vec<String>,
+    pattern: Option<String>,
+}
+
+impl WalkOptions {
+    fn from_value(value: Option<&Value>, heap: &Heap) -> Result<Self, RuntimeError> {
+        let mut options = WalkOptions {
+            max_depth: None,
+            include_dirs: false,
+            include_files: true,
+            hidden: false,
+            follow_links: false,
+            extensions: Vec::new(),
+            pattern: None,
+        };
+        let properties = match value {
+            None | Some(Value::Null) => return Ok(options),
+            Some(Value::Object(id)) => object_properties(*id, heap)?,
+            Some(_) => return Err(RuntimeError::TypeError("walk_dir: opções devem ser um objeto".to_string())),
+        };
+        let flag = |key: &str, default: bool| match properties.get(key) {
+            None | Some(Value::Null) => Ok(default),
+            Some(Value::Bool(b)) => Ok(*b),
+            Some(_) => Err(RuntimeError::TypeError(format!("walk_dir: opção '{}' deve ser bool", key))),
+        };
+        options.include_dirs = flag("dirs", false)?;
+        options.include_files = flag("files", true)?;
+        options.hidden = flag("hidden", false)?;
+        options.follow_links = flag("follow_links", false)?;
+        options.max_depth = match properties.get("max_depth") {
+            None | Some(Value::Null) => None,
+            Some(Value::Number(n)) if *n >= 0.0 => Some(*n as usize),
+            Some(_) => return Err(RuntimeError::TypeError("walk_dir: max_depth deve ser um número".to_string())),
+        };
+        options.pattern = string_option(properties, "pattern", "walk_dir")?;
+        options.extensions = match properties.get("extensions") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(id)) => match heap.get(*id) {
+                Some(ManagedObject::Array(items)) => items
+                    .iter()
+                    .map(|item| match item {
+                        Value::String(ext) => Ok(ext.trim_start_matches('.').to_string()),
+                        _ => Err(RuntimeError::TypeError("walk_dir: extensions deve conter strings".to_string())),
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => return Err(RuntimeError::HeapError("Array de extensões não encontrado".to_string())),
+            },
+            Some(_) => return Err(RuntimeError::TypeError("walk_dir: extensions deve ser um array".to_string())),
+        };
+        Ok(options)
+    }
+
+    fn accepts(&self, name: &str, path: &Path, is_dir: bool) -> bool {
+        if is_dir && !self.include_dirs || !is_dir && !self.include_files {
+            return false;
+        }
+        if !is_dir && !self.extensions.is_empty() {
+            let extension = path.extension().map(|e| e.to_string_lossy()).unwrap_or_default();
+            if !self.extensions.iter().any(|wanted| wanted.eq_ignore_ascii_case(&extension)) {
+                return false;
+            }
+        }
+        self.pattern.as_deref().is_none_or(|pattern| wildcard_match(pattern, name))
+    }
+}
+
+/// Percorre `dir` em profundidade, em ordem alfabética. Symlinks para
+/// diretórios só são seguidos com `follow_links` e se continuarem no sandbox.
+fn walk(
+    dir: &Path,
+    shown: &Path,
+    depth: usize,
+    options: &WalkOptions,
+    manager: &NativeModuleManager,
+    visited: &mut HashSet<PathBuf>,
+    out: &mut Vec<Value>,
+) {
+    for (name, path) in sorted_entries(dir) {
+        if !options.hidden && is_hidden(&name) {
+            continue;
+        }
+        let Ok(link) = fs::symlink_metadata(&path) else { continue };
+        let is_link = link.file_type().is_symlink();
+        let is_dir = if is_link {
+            options.follow_links && path.is_dir() && is_path_safe(&path.to_string_lossy(), manager)
+        } else {
+            link.is_dir()
+        };
+        let shown_path = shown.join(&name);
+        if options.accepts(&name, &path, is_dir) {
+            out.push(Value::String(shown_path.to_string_lossy().into_owned()));
+        }
+        if is_dir && options.max_depth.is_none_or(|max| depth < max) {
+            // Evita ciclos ao seguir symlinks
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if visited.insert(canonical) {
+                walk(&path, &shown_path, depth + 1, options, manager, visited, out);
+            }
+        }
+    }
+}
+
+/// walk_dir(caminho, opcoes?) -> array de caminhos
+/// Lista arquivos recursivamente (em ordem alfabética, em profundidade).
+/// Opções: `max_depth`, `dirs` (incluir diretórios, padrão false), `files`
+/// (padrão true), `extensions` (ex.: ["rs", ".toml"]), `pattern` (glob do
+/// nome, ex.: "*_test.*"), `hidden` (padrão false) e `follow_links`.
+fn native_walk_dir(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let root_str = string_arg(args, 0, "caminho", "walk_dir")?;
+    let options = WalkOptions::from_value(args.get(1), heap)?;
+    let root = sandboxed_path(&root_str, manager)?;
+    if !root.is_dir() {
+        return Err(RuntimeError::IoError(format!("walk_dir: '{}' não é um diretório", root_str)));
+    }
+
+    let mut visited = HashSet::new();
+    visited.insert(root.canonicalize().unwrap_or_else(|_| root.clone()));
+    let mut out = Vec::new();
+    walk(&root, Path::new(&root_str), 1, &options, manager, &mut visited, &mut out);
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(out))))
+}
+
+/// Diretório em que o glob pode entrar: symlinks só se o destino continuar
+/// no sandbox, como no `walk_dir`
+fn glob_enters(path: &Path, manager: &NativeModuleManager) -> bool {
+    let is_link = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    path.is_dir() && (!is_link || is_path_safe(&path.to_string_lossy(), manager))
+}
+
+/// Casa os componentes restantes do padrão a partir de `dir`
+fn glob_walk(dir: &Path, shown: &Path, parts: &[&str], manager: &NativeModuleManager, out: &mut Vec<String>) {
+    let Some((part, rest)) = parts.split_first() else {
+        out.push(shown.to_string_lossy().into_owned());
+        return;
+    };
+
+    if *part == "**" {
+        // Zero ou mais diretórios (sem entrar em ocultos nem seguir symlinks)
+        glob_walk(dir, shown, rest, manager, out);
+        for (name, path) in sorted_entries(dir) {
+            let is_real_dir = fs::symlink_metadata(&path).is_ok_and(|m| m.is_dir());
+            if is_real_dir && !is_hidden(&name) {
+                glob_walk(&path, &shown.join(&name), parts, manager, out);
+            }
+        }
+    } else if !has_wildcard(part) {
+        let path = dir.join(part);
+        if fs::symlink_metadata(&path).is_ok() && (rest.is_empty() || glob_enters(&path, manager)) {
+            glob_walk(&path, &shown.join(part), rest, manager, out);
+        }
+    } else {
+        for (name, path) in sorted_entries(dir) {
+            // Ocultos só casam com padrões que começam com "."
+            if is_hidden(&name) && !part.starts_with('.') {
+                continue;
+            }
+            if wildcard_match(part, &name) && (rest.is_empty() || glob_enters(&path, manager)) {
+                glob_walk(&path, &shown.join(&name), rest, manager, out);
+            }
+        }
+    }
+}
+
+/// glob(padrao) -> array de caminhos
+/// `*`, `?` e `[a-z]` casam dentro de um componente e `**` com qualquer
+/// número de diretórios (ex.: "src/**/*.rs"). Resultados em ordem alfabética.
+fn native_glob(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let pattern = string_arg(args, 0, "padrao", "glob")?;
+    let absolute = Path::new(&pattern).is_absolute();
+    let parts: Vec<&str> = pattern.split(['/', '\\']).filter(|part| !part.is_empty() && *part != ".").collect();
+    if parts.contains(&"..") && parts.iter().any(|part| has_wildcard(part)) {
+        return Err(RuntimeError::ArgumentError("glob: '..' não é permitido em padrões com curingas".to_string()));
+    }
+
+    // Prefixo sem curingas: o diretório de onde a busca parte
+    let literal = parts.iter().take_while(|part| !has_wildcard(part)).count();
+    let mut base = if absolute { PathBuf::from("/") } else { PathBuf::new() };
+    base.extend(&parts[..literal]);
+    let base_str = base.to_string_lossy().into_owned();
+    let dir = sandboxed_path(if base_str.is_empty() { "." } else { &base_str }, manager)?;
+
+    let mut out = Vec::new();
+    if literal == parts.len() {
+        if fs::symlink_metadata(&dir).is_ok() {
+            out.push(base_str);
+        }
+    } else {
+        glob_walk(&dir, &base, &parts[literal..], manager, &mut out);
+    }
+    out.sort();
+    out.dedup();
+    let items = out.into_iter().map(Value::String).collect();
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(items))))
+}
+
+// ========================
+// Auxiliares de argumentos
+// ========================
+
+fn string_arg(args: &[Value], index: usize, name: &str, function: &str) -> Result<String, RuntimeError> {
+    match args.get(index) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: {} deve ser string", function, name))),
+        None => Err(RuntimeError::ArgumentError(format!("{}: {} ausente", function, name))),
+    }
+}
+
+fn string_option(properties: &HashMap<String, Value>, key: &str, function: &str) -> Result<Option<String>, RuntimeError> {
+    match properties.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: opção '{}' deve ser string", function, key))),
+    }
+}
+
+fn object_properties(id: crate::heap::HeapId, heap: &Heap) -> Result<&HashMap<String, Value>, RuntimeError> {
+    match heap.get(id) {
+        Some(ManagedObject::Object { properties, .. }) => Ok(properties),
+        _ => Err(RuntimeError::HeapError("Objeto não encontrado".to_string())),
+    }
+}
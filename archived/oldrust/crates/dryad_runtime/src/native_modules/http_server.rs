@@ -1,58 +1,122 @@
 use crate::interpreter::Value;
-use crate::native_modules::NativeFunction;
+use crate::native_modules::{NativeFunction, NativeModuleManager};
 use crate::errors::RuntimeError;
-use crate::heap::HeapId;
-use tokio::runtime::Runtime;
+use crate::heap::{Heap, HeapId, ManagedObject};
+use crate::native_modules::tls::{self, NetStream, ServerTls};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use lazy_static::lazy_static;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
 use std::fs;
 use std::path::Path;
 use std::thread;
-use std::time::Duration;
-use std::sync::mpsc;
-
-lazy_static! {
-    static ref RUNTIME: Runtime = Runtime::new().expect("Falha ao criar runtime Tokio para HTTP Server");
-    static ref HTTP_SERVERS: Arc<Mutex<HashMap<String, ServerInstance>>> = Arc::new(Mutex::new(HashMap::new()));
-    static ref ROUTE_HANDLERS: Arc<Mutex<HashMap<String, HashMap<String, RouteHandler>>>> = Arc::new(Mutex::new(HashMap::new()));
-    static ref DYNAMIC_HANDLERS: Arc<Mutex<HashMap<String, HashMap<String, HeapId>>>> = Arc::new(Mutex::new(HashMap::new()));
-    static ref STATIC_CONTENT: Arc<Mutex<HashMap<String, HashMap<String, StaticContent>>>> = Arc::new(Mutex::new(HashMap::new()));
-    static ref SERVER_THREADS: Arc<Mutex<HashMap<String, thread::JoinHandle<()>>>> = Arc::new(Mutex::new(HashMap::new()));
-    
-    // New: A queue for requests that need to be handled by the interpreter
-    static ref PENDING_REQUESTS: Arc<Mutex<Vec<HttpRequestEvent>>> = Arc::new(Mutex::new(Vec::new()));
-}
-
-struct HttpRequestEvent {
-    server_id: String,
-    method: String,
-    path: String,
-    lambda_id: HeapId,
-    // Add request data if needed
-    response_channel: tokio::sync::oneshot::Sender<HttpResponse>,
+use std::time::{Duration, Instant};
+
+/// Servidores HTTP de um interpreter e as requisições que precisam dele
+/// (handlers e middlewares Dryad). As threads de conexão guardam um `Arc`
+/// deste registro, então cada requisição só chega ao interpreter dono do
+/// servidor e os `HeapId` dos handlers nunca passam para outro heap.
+#[derive(Default)]
+pub(crate) struct HttpServers {
+    servers: Mutex<HashMap<String, ServerInstance>>,
+    pending: Mutex<Vec<PendingRequest>>,
+    /// Acorda `native_http_server_wait` quando chega uma requisição
+    ready: Condvar,
 }
 
-struct HttpResponse {
-    status: u16,
-    body: String,
+impl HttpServers {
+    fn with_server<T>(&self, server_id: &str, f: impl FnOnce(&mut ServerInstance) -> Result<T, RuntimeError>) -> Result<T, RuntimeError> {
+        let mut servers = self.servers.lock().unwrap();
+        let server = servers
+            .get_mut(server_id)
+            .ok_or_else(|| RuntimeError::ArgumentError(format!("Servidor '{}' não encontrado", server_id)))?;
+        f(server)
+    }
+
+    fn push(&self, request: PendingRequest) {
+        self.pending.lock().unwrap().push(request);
+        self.ready.notify_all();
+    }
 }
 
+/// Tamanho máximo da linha de requisição e de cada cabeçalho
+const MAX_LINE_BYTES: usize = 8 * 1024;
+/// Tamanho máximo somado de todos os cabeçalhos
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+const MAX_HEADERS: usize = 100;
+/// Intervalo em que threads ociosas verificam se o servidor está parando
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Tempo máximo que uma conexão espera o interpreter responder
+const HANDLER_TIMEOUT: Duration = Duration::from_secs(30);
+/// Conexões simultâneas por servidor, cada uma com sua thread
+const DEFAULT_MAX_CONNECTIONS: usize = 256;
+
 #[derive(Clone, Debug)]
+struct ServerConfig {
+    max_body_size: usize,
+    keep_alive_timeout: Duration,
+    request_timeout: Duration,
+    max_connections: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            max_body_size: 16 * 1024 * 1024,
+            keep_alive_timeout: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(30),
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        }
+    }
+}
+
 struct ServerInstance {
     port: u16,
     host: String,
-    is_running: bool,
-    stop_sender: Option<mpsc::Sender<()>>,
+    config: ServerConfig,
+    routes: Vec<Route>,
+    static_content: HashMap<String, StaticContent>,
+    middleware: Vec<Value>,
+    cors_origin: Option<String>,
+    /// Opção `tls` do create: o servidor atende HTTPS
+    tls: Option<ServerTls>,
+    running: Option<RunningServer>,
 }
 
-#[derive(Clone, Debug)]
-struct RouteHandler {
+/// Estado compartilhado entre a thread de accept e as conexões
+#[derive(Default)]
+struct ServerShared {
+    shutdown: AtomicBool,
+    /// Conexões lendo ou escrevendo uma requisição (exclui as ociosas e as
+    /// que aguardam o interpreter)
+    busy: AtomicUsize,
+    /// Conexões abertas, limitadas por `max_connections`
+    connections: AtomicUsize,
+}
+
+struct RunningServer {
+    shared: Arc<ServerShared>,
+    accept_thread: thread::JoinHandle<()>,
+}
+
+struct Route {
     method: String,
-    path: String,
-    response_body: String,
-    response_headers: HashMap<String, String>,
-    status_code: u16,
+    pattern: Vec<Segment>,
+    target: RouteTarget,
+}
+
+#[derive(Clone)]
+enum RouteTarget {
+    Fixed { body: String, content_type: String, status: u16 },
+    Handler(Box<Value>),
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+    Wildcard,
 }
 
 #[derive(Clone, Debug)]
@@ -61,151 +125,458 @@ struct StaticContent {
     content_type: String,
 }
 
-// Função para inicializar servidor HTTP simples usando threads nativas
-fn start_simple_http_server(server_id: String, host: String, port: u16, stop_receiver: mpsc::Receiver<()>) {
-    use std::net::TcpListener;
-    
-    
-    let addr = format!("{}:{}", host, port);
-    match TcpListener::bind(&addr) {
-        Ok(listener) => {
-            println!("🌐 Servidor HTTP '{}' ouvindo em http://{}", server_id, addr);
-            
-            // Configura para não bloquear indefinidamente
-            listener.set_nonblocking(true).expect("Cannot set nonblocking");
-            
-            loop {
-                // Verifica se deve parar
-                if stop_receiver.try_recv().is_ok() {
-                    println!("🛑 Parando servidor '{}'...", server_id);
-                    break;
-                }
-                
-                // Aceita conexões
-                match listener.accept() {
-                    Ok((stream, _)) => {
-                        let server_id_clone = server_id.clone();
-                        thread::spawn(move || {
-                            handle_http_connection(stream, server_id_clone);
-                        });
-                    }
-                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                        // Não há conexões, aguarda um pouco
-                        thread::sleep(Duration::from_millis(50));
-                        continue;
-                    }
-                    Err(e) => {
-                        eprintln!("❌ Erro ao aceitar conexão: {}", e);
-                    }
-                }
-            }
+/// Requisição HTTP já lida do socket
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub params: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub version: String,
+    pub remote_addr: String,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn wants_keep_alive(&self) -> bool {
+        let connection = self.header("connection").unwrap_or("").to_ascii_lowercase();
+        if self.version == "HTTP/1.0" {
+            connection.contains("keep-alive")
+        } else {
+            !connection.contains("close")
         }
-        Err(e) => {
-            eprintln!("❌ Erro ao iniciar servidor '{}': {}", server_id, e);
-        }
-    }
-}
-
-// Função para lidar com conexões HTTP
-fn handle_http_connection(mut stream: std::net::TcpStream, server_id: String) {
-    use std::io::prelude::*;
-    
-    let mut buffer = [0; 1024];
-    match stream.read(&mut buffer) {
-        Ok(_) => {
-            let request = String::from_utf8_lossy(&buffer[..]);
-            let lines: Vec<&str> = request.lines().collect();
-            
-            if let Some(request_line) = lines.first() {
-                let parts: Vec<&str> = request_line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let method = parts[0];
-                    let path = parts[1];
-                    
-                    println!("📨 {} {} (servidor: {})", method, path, server_id);
-                    
-                    let response = generate_response(&server_id, method, path);
-                    
-                    if stream.write_all(response.as_bytes()).is_err() {
-                        eprintln!("❌ Erro ao enviar resposta");
-                    }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct HttpResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Self {
+        HttpResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+        }
+    }
+
+    fn text(status: u16, body: &str) -> Self {
+        HttpResponse::new(status, "text/plain; charset=utf-8", body)
+    }
+}
+
+/// Partes de resposta enviadas pelo interpreter à thread da conexão
+enum ResponsePart {
+    Complete(HttpResponse),
+    StreamStart { status: u16, headers: Vec<(String, String)> },
+    Chunk(Vec<u8>),
+    End,
+}
+
+/// Requisição aguardando os handlers Dryad; processada em `poll_native_events`
+pub struct PendingRequest {
+    server_id: String,
+    pub request: HttpRequest,
+    handler: Option<Value>,
+    middleware: Vec<Value>,
+    /// Resposta usada quando nenhum handler Dryad atende a rota (rotas fixas,
+    /// arquivos estáticos, 404/405) mas há middlewares registrados
+    fallback: Option<HttpResponse>,
+    reply: mpsc::Sender<ResponsePart>,
+}
+
+/// Como o corpo de uma resposta Dryad deve ser enviado
+pub enum ResponseBody {
+    Sent,
+    Stream(HeapId),
+}
+
+impl PendingRequest {
+    pub fn handler(&self) -> Option<&Value> {
+        self.handler.as_ref()
+    }
+
+    pub fn middleware(&self) -> &[Value] {
+        &self.middleware
+    }
+
+    /// Monta o objeto `req` entregue aos handlers
+    pub fn request_object(&self, heap: &mut Heap) -> Value {
+        let request = &self.request;
+        let query = pairs_to_object(&request.query, heap);
+        let params = pairs_to_object(&request.params, heap);
+
+        let mut header_map: HashMap<String, Value> = HashMap::new();
+        for (name, value) in &request.headers {
+            let key = name.to_ascii_lowercase();
+            let merged = match header_map.remove(&key) {
+                Some(Value::String(previous)) => format!("{}, {}", previous, value),
+                _ => value.clone(),
+            };
+            header_map.insert(key, Value::String(merged));
+        }
+        let headers = heap.allocate(ManagedObject::Object { properties: header_map, methods: HashMap::new() });
+
+        let body = match String::from_utf8(request.body.clone()) {
+            Ok(text) => Value::String(text),
+            Err(e) => {
+                let bytes = e.into_bytes().into_iter().map(|b| Value::Number(b as f64)).collect();
+                Value::Array(heap.allocate(ManagedObject::Array(bytes)))
+            }
+        };
+
+        let is_json = request
+            .header("content-type")
+            .map(|ct| ct.to_ascii_lowercase().contains("json"))
+            .unwrap_or(false);
+        let json = if is_json {
+            serde_json::from_slice::<serde_json::Value>(&request.body)
+                .map(|parsed| super::encode_decode::json_to_runtime_value(&parsed, heap))
+                .unwrap_or(Value::Null)
+        } else {
+            Value::Null
+        };
+
+        let mut properties = HashMap::new();
+        properties.insert("method".to_string(), Value::String(request.method.clone()));
+        properties.insert("url".to_string(), Value::String(request.url.clone()));
+        properties.insert("path".to_string(), Value::String(request.path.clone()));
+        properties.insert("query".to_string(), query);
+        properties.insert("params".to_string(), params);
+        properties.insert("headers".to_string(), Value::Object(headers));
+        properties.insert("body".to_string(), body);
+        properties.insert("json".to_string(), json);
+        properties.insert("version".to_string(), Value::String(request.version.clone()));
+        properties.insert("remote_addr".to_string(), Value::String(request.remote_addr.clone()));
+        properties.insert("server_id".to_string(), Value::String(self.server_id.clone()));
+
+        Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+    }
+
+    /// Monta o objeto `res` (status, headers, body) que handlers podem alterar
+    pub fn response_object(&self, heap: &mut Heap) -> Value {
+        let headers = heap.allocate(ManagedObject::Object { properties: HashMap::new(), methods: HashMap::new() });
+        let mut properties = HashMap::new();
+        properties.insert("status".to_string(), Value::Number(200.0));
+        properties.insert("headers".to_string(), Value::Object(headers));
+        properties.insert("body".to_string(), Value::Null);
+        Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+    }
+
+    /// Envia a resposta descrita por `res`. `returned` é o valor devolvido pelo
+    /// handler (usado como corpo quando `res.body` é null); `halted` indica que
+    /// um middleware interrompeu a cadeia. Um generator como corpo inicia uma
+    /// resposta chunked que o interpreter alimenta com `send_chunk`.
+    pub fn respond(&self, res: &Value, returned: Option<Value>, halted: bool, heap: &Heap) -> Result<ResponseBody, RuntimeError> {
+        let (status, headers, body) = read_response_object(res, heap);
+        let handled = returned.is_some() || halted;
+
+        let body = match body {
+            Value::Null => returned.unwrap_or(Value::Null),
+            other => other,
+        };
+
+        if !handled && matches!(body, Value::Null) {
+            if let Some(fallback) = &self.fallback {
+                let mut response = fallback.clone();
+                merge_headers(&mut response.headers, headers);
+                if status != 200 {
+                    response.status = status;
                 }
+                let _ = self.reply.send(ResponsePart::Complete(response));
+                return Ok(ResponseBody::Sent);
             }
         }
-        Err(e) => {
-            eprintln!("❌ Erro ao ler request: {}", e);
+
+        if let Value::Generator(id) = body {
+            let _ = self.reply.send(ResponsePart::StreamStart { status, headers });
+            return Ok(ResponseBody::Stream(id));
+        }
+
+        let (bytes, default_type) = encode_body(&body, heap)?;
+        let mut response = HttpResponse { status, headers, body: bytes };
+        if let Some(content_type) = default_type {
+            set_default_header(&mut response.headers, "Content-Type", content_type);
         }
+        let _ = self.reply.send(ResponsePart::Complete(response));
+        Ok(ResponseBody::Sent)
+    }
+
+    /// Envia um pedaço de uma resposta chunked
+    pub fn send_chunk(&self, value: &Value, heap: &Heap) -> Result<(), RuntimeError> {
+        let (bytes, _) = encode_body(value, heap)?;
+        if !bytes.is_empty() {
+            let _ = self.reply.send(ResponsePart::Chunk(bytes));
+        }
+        Ok(())
+    }
+
+    /// Encerra uma resposta chunked
+    pub fn end(&self) {
+        let _ = self.reply.send(ResponsePart::End);
+    }
+
+    /// Responde 500 quando um handler lança erro
+    pub fn fail(&self, message: &str) {
+        let _ = self.reply.send(ResponsePart::Complete(HttpResponse::text(500, message)));
     }
 }
 
-// Gera resposta HTTP
-fn generate_response(server_id: &str, method: &str, path: &str) -> String {
-    let route_key = format!("{}:{}", method, path);
-    
-    // Verifica conteúdo dinâmico (lambdas)
-    let dynamic_lambda = {
-        let handlers = (*DYNAMIC_HANDLERS).lock().unwrap();
-        handlers.get(server_id).and_then(|routes| routes.get(&route_key).cloned())
+fn pairs_to_object(pairs: &[(String, String)], heap: &mut Heap) -> Value {
+    let properties = pairs
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect();
+    Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+}
+
+/// Lê `status`, `headers` e `body` de um objeto `res`
+fn read_response_object(res: &Value, heap: &Heap) -> (u16, Vec<(String, String)>, Value) {
+    let properties = match res {
+        Value::Object(id) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => properties,
+            _ => return (200, Vec::new(), Value::Null),
+        },
+        _ => return (200, Vec::new(), Value::Null),
+    };
+
+    let status = match properties.get("status") {
+        Some(Value::Number(n)) if (100.0..1000.0).contains(n) => *n as u16,
+        Some(Value::Int(n)) if (100..1000).contains(n) => *n as u16,
+        _ => 200,
     };
 
-    if let Some(lambda_id) = dynamic_lambda {
-        // Envia para o interpreter e aguarda resposta
-        let (tx, rx) = tokio::sync::oneshot::channel();
-        
-        (*PENDING_REQUESTS).lock().unwrap().push(HttpRequestEvent {
-            server_id: server_id.to_string(),
-            method: method.to_string(),
-            path: path.to_string(),
-            lambda_id,
-            response_channel: tx,
-        });
-
-        // Aguarda a resposta (bloqueia a thread da conexão HTTP)
-        // Como estamos em uma thread de thread::spawn, podemos usar block_on ou similar do Tokio
-        // Mas o tokio está disponível via RUNTIME no websocket.rs/database.rs. 
-        // Vamos usar um loop de espera aqui por simplicidade ou o runtime se disponível.
-        
-        match RUNTIME.block_on(async {
-            tokio::time::timeout(Duration::from_secs(30), rx).await
-        }) {
-            Ok(Ok(resp)) => {
-                return format!(
-                    "HTTP/1.1 {} OK\r\nContent-Type: text/html; charset=utf-8\r\n\r\n{}",
-                    resp.status,
-                    resp.body
-                );
+    let mut headers = Vec::new();
+    if let Some(Value::Object(id)) = properties.get("headers") {
+        if let Some(ManagedObject::Object { properties, .. }) = heap.get(*id) {
+            let mut names: Vec<&String> = properties.keys().collect();
+            names.sort();
+            for name in names {
+                let value = match &properties[name] {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                headers.push((name.clone(), value));
             }
-            _ => {
-                return "HTTP/1.1 500 Internal Server Error\r\n\r\nTimeout no processamento da requisição".to_string();
+        }
+    }
+
+    (status, headers, properties.get("body").cloned().unwrap_or(Value::Null))
+}
+
+/// Converte um valor Dryad em bytes de corpo: strings são enviadas como estão,
+/// arrays de bytes vindos de `native_*` também; o resto vira JSON
+fn encode_body(value: &Value, heap: &Heap) -> Result<(Vec<u8>, Option<&'static str>), RuntimeError> {
+    match value {
+        Value::Null => Ok((Vec::new(), None)),
+        Value::String(s) => Ok((s.clone().into_bytes(), Some("text/html; charset=utf-8"))),
+        Value::Number(_) | Value::Int(_) | Value::Bool(_) | Value::BigInt(_) | Value::Decimal(_) => {
+            Ok((value.to_string().into_bytes(), Some("text/plain; charset=utf-8")))
+        }
+        _ => {
+            let json = super::encode_decode::runtime_value_to_json(value, heap)?;
+            let text = serde_json::to_string(&json)
+                .map_err(|e| RuntimeError::Generic(format!("Erro ao serializar resposta: {}", e)))?;
+            Ok((text.into_bytes(), Some("application/json")))
+        }
+    }
+}
+
+fn has_header(headers: &[(String, String)], name: &str) -> bool {
+    headers.iter().any(|(key, _)| key.eq_ignore_ascii_case(name))
+}
+
+fn set_default_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    if !has_header(headers, name) {
+        headers.push((name.to_string(), value.to_string()));
+    }
+}
+
+/// Cabeçalhos definidos em `res.headers` substituem os da resposta base
+fn merge_headers(base: &mut Vec<(String, String)>, overrides: Vec<(String, String)>) {
+    for (name, value) in overrides {
+        base.retain(|(key, _)| !key.eq_ignore_ascii_case(&name));
+        base.push((name, value));
+    }
+}
+
+/// Remove da fila as requisições pendentes dos servidores deste
+/// interpreter; chamada por ele
+pub fn take_pending_requests(manager: &NativeModuleManager) -> Vec<PendingRequest> {
+    let mut pending = manager.http_servers().pending.lock().unwrap();
+    if pending.is_empty() {
+        return Vec::new();
+    }
+    std::mem::take(&mut *pending)
+}
+
+/// Handlers e middlewares dos servidores deste interpreter, inclusive os de
+/// requisições na fila; o GC os trata como raízes para que lambdas passadas
+/// diretamente às funções nativas não sejam coletadas
+pub fn registered_handlers(manager: &NativeModuleManager) -> Vec<Value> {
+    let registry = manager.http_servers();
+    let mut values = Vec::new();
+    for server in registry.servers.lock().unwrap().values() {
+        values.extend(server.middleware.iter().cloned());
+        for route in &server.routes {
+            if let RouteTarget::Handler(handler) = &route.target {
+                values.push((**handler).clone());
             }
         }
     }
+    for request in registry.pending.lock().unwrap().iter() {
+        values.extend(request.handler.iter().chain(&request.middleware).cloned());
+    }
+    values
+}
 
-    // Verifica rotas estáticas
-    if let Some(handler) = (*ROUTE_HANDLERS).lock().unwrap()
-        .get(server_id)
-        .and_then(|routes| routes.get(&route_key)) {
-        
-        return format!(
-            "HTTP/1.1 {} OK\r\nContent-Type: text/html; charset=utf-8\r\n\r\n{}",
-            handler.status_code,
-            handler.response_body
-        );
+// ========================
+// Rotas
+// ========================
+
+fn parse_pattern(path: &str) -> Vec<Segment> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment == "*" {
+                Segment::Wildcard
+            } else if let Some(name) = segment.strip_prefix(':') {
+                Segment::Param(name.to_string())
+            } else {
+                Segment::Literal(segment.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Compara um caminho com o padrão da rota, devolvendo os parâmetros
+/// capturados (`:id` e o resto do caminho em `*`)
+fn match_pattern(pattern: &[Segment], path: &str) -> Option<Vec<(String, String)>> {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+    let mut params = Vec::new();
+
+    for (index, part) in pattern.iter().enumerate() {
+        match part {
+            Segment::Wildcard => {
+                params.push(("*".to_string(), percent_decode(&segments[index.min(segments.len())..].join("/"), false)));
+                return Some(params);
+            }
+            Segment::Literal(literal) => {
+                if segments.get(index).map(|s| percent_decode(s, false)) != Some(literal.clone()) {
+                    return None;
+                }
+            }
+            Segment::Param(name) => {
+                let value = segments.get(index)?;
+                params.push((name.clone(), percent_decode(value, false)));
+            }
+        }
     }
-    
-    // Verifica conteúdo estático
-    if let Some(static_content) = (*STATIC_CONTENT).lock().unwrap()
-        .get(server_id)
-        .and_then(|content| content.get(path)) {
-        
-        return format!(
-            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\n\r\n{}",
-            static_content.content_type,
-            String::from_utf8_lossy(&static_content.content)
-        );
+
+    if segments.len() == pattern.len() {
+        Some(params)
+    } else {
+        None
+    }
+}
+
+fn method_matches(route_method: &str, method: &str) -> bool {
+    route_method == "*" || route_method == "ALL" || route_method == method || (method == "HEAD" && route_method == "GET")
+}
+
+/// Decodifica `%XX` (e `+` como espaço em query strings)
+fn percent_decode(input: &str, plus_as_space: bool) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok().and_then(|h| u8::from_str_radix(h, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b'+' if plus_as_space => out.push(b' '),
+            byte => out.push(byte),
+        }
+        i += 1;
     }
-    
-    // 404 - Página não encontrada
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key, true), percent_decode(value, true))
+        })
+        .collect()
+}
+
+/// Resultado da resolução de rota feita na thread da conexão
+enum Resolved {
+    Handler(Box<Value>, Vec<(String, String)>),
+    Response(HttpResponse),
+}
+
+fn resolve_route(server: &ServerInstance, server_id: &str, request: &HttpRequest) -> (Resolved, Vec<Value>) {
+    let middleware = server.middleware.clone();
+    let mut allowed = Vec::new();
+
+    for route in &server.routes {
+        let Some(params) = match_pattern(&route.pattern, &request.path) else { continue };
+        if !method_matches(&route.method, &request.method) {
+            allowed.push(route.method.clone());
+            continue;
+        }
+        let resolved = match &route.target {
+            RouteTarget::Handler(handler) => Resolved::Handler(handler.clone(), params),
+            RouteTarget::Fixed { body, content_type, status } => {
+                Resolved::Response(HttpResponse::new(*status, content_type, body.clone()))
+            }
+        };
+        return (resolved, middleware);
+    }
+
+    if matches!(request.method.as_str(), "GET" | "HEAD") {
+        if let Some(content) = server.static_content.get(&request.path) {
+            let response = HttpResponse::new(200, &content.content_type, content.content.clone());
+            return (Resolved::Response(response), middleware);
+        }
+    }
+
+    if !allowed.is_empty() {
+        allowed.dedup();
+        let mut response = HttpResponse::text(405, "Method Not Allowed");
+        response.headers.push(("Allow".to_string(), allowed.join(", ")));
+        return (Resolved::Response(response), middleware);
+    }
+
+    (Resolved::Response(not_found_page(server_id, &request.method, &request.path)), middleware)
+}
+
+fn not_found_page(server_id: &str, method: &str, path: &str) -> HttpResponse {
     let not_found_html = format!(r#"
 <!DOCTYPE html>
 <html>
@@ -224,9 +595,514 @@ fn generate_response(server_id: &str, method: &str, path: &str) -> String {
     <hr>
     <small>Dryad WebServer v1.0</small>
 </body>
-</html>"#, path, server_id, method);
+</html>"#, html_escape(path), html_escape(server_id), html_escape(method));
+
+    HttpResponse::new(404, "text/html; charset=utf-8", not_found_html)
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        100 => "Continue",
+        101 => "Switching Protocols",
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        206 => "Partial Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        505 => "HTTP Version Not Supported",
+        _ => "",
+    }
+}
+
+// ========================
+// Conexões
+// ========================
+
+/// Thread de accept: aceita conexões até o servidor ser parado, com uma
+/// thread por conexão até `max_connections`
+fn accept_loop(
+    listener: TcpListener,
+    registry: Arc<HttpServers>,
+    server_id: String,
+    shared: Arc<ServerShared>,
+    config: ServerConfig,
+    tls_config: Option<ServerTls>,
+) {
+    while !shared.shutdown.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, addr)) => {
+                if shared.connections.load(Ordering::SeqCst) >= config.max_connections {
+                    eprintln!(
+                        "⚠️ Servidor HTTP '{}': limite de conexões ({}) atingido, recusando {}",
+                        server_id, config.max_connections, addr
+                    );
+                    reject_connection(stream, tls_config.is_some());
+                    continue;
+                }
+                let slot = ConnectionSlot::new(shared.clone());
+                let registry = registry.clone();
+                let server_id = server_id.clone();
+                let config = config.clone();
+                let tls_config = tls_config.clone();
+                thread::spawn(move || handle_http_connection(stream, tls_config, registry, server_id, slot, config));
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                eprintln!("❌ Erro ao aceitar conexão: {}", e);
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Falha ao ler uma requisição
+enum RequestError {
+    /// Conexão fechada ou erro de I/O: encerra sem responder
+    Closed,
+    /// Requisição inválida: responde com o status e fecha a conexão
+    Status(u16, &'static str),
+}
+
+impl From<io::Error> for RequestError {
+    fn from(_: io::Error) -> Self {
+        RequestError::Closed
+    }
+}
+
+/// Responde 503 a uma conexão acima do limite, sem abrir uma thread. Com TLS
+/// não há como responder antes do handshake, então a conexão só é fechada.
+fn reject_connection(stream: TcpStream, tls: bool) {
+    if tls {
+        return;
+    }
+    let mut stream = stream;
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_write_timeout(Some(POLL_INTERVAL));
+    let _ = write_response(&mut stream, HttpResponse::text(503, "Service Unavailable"), false, false);
+}
+
+/// Uma das `max_connections` vagas do servidor, liberada quando a thread da
+/// conexão termina
+struct ConnectionSlot(Arc<ServerShared>);
+
+impl ConnectionSlot {
+    fn new(shared: Arc<ServerShared>) -> Self {
+        shared.connections.fetch_add(1, Ordering::SeqCst);
+        ConnectionSlot(shared)
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Mantém `busy` incrementado enquanto vivo
+struct BusyGuard<'a>(&'a ServerShared);
+
+impl<'a> BusyGuard<'a> {
+    fn new(shared: &'a ServerShared) -> Self {
+        shared.busy.fetch_add(1, Ordering::SeqCst);
+        BusyGuard(shared)
+    }
+}
 
-    format!("HTTP/1.1 404 Not Found\r\nContent-Type: text/html; charset=utf-8\r\n\r\n{}", not_found_html)
+impl Drop for BusyGuard<'_> {
+    fn drop(&mut self) {
+        self.0.busy.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Lida com uma conexão HTTP/1.1, atendendo requisições até o cliente fechar,
+// pedir `Connection: close`, ficar ocioso além do keep-alive ou o servidor parar
+fn handle_http_connection(
+    stream: TcpStream,
+    tls_config: Option<ServerTls>,
+    registry: Arc<HttpServers>,
+    server_id: String,
+    slot: ConnectionSlot,
+    config: ServerConfig,
+) {
+    let shared = &slot.0;
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_nodelay(true);
+    let remote_addr = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+    let stream = match tls_config {
+        Some(tls_config) => match tls::accept(&tls_config, stream) {
+            Ok(stream) => NetStream::Tls(Box::new(stream)),
+            Err(e) => {
+                eprintln!("⚠️ Servidor HTTP '{}': handshake TLS com {} falhou: {}", server_id, remote_addr, e);
+                return;
+            }
+        },
+        None => NetStream::Plain(stream),
+    };
+    // Leitura e escrita acontecem nesta thread, sempre alternadas, então a
+    // escrita usa o próprio stream do reader (necessário com TLS)
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        if !wait_for_request(&mut reader, shared, config.keep_alive_timeout) {
+            break;
+        }
+
+        let busy = BusyGuard::new(shared);
+        let _ = reader.get_ref().socket().set_read_timeout(Some(config.request_timeout));
+
+        let request = match read_request(&mut reader, &config, &remote_addr) {
+            Ok(request) => request,
+            Err(RequestError::Closed) => break,
+            Err(RequestError::Status(status, message)) => {
+                let _ = write_response(reader.get_mut(), HttpResponse::text(status, message), false, false);
+                break;
+            }
+        };
+
+        let head_only = request.method == "HEAD";
+        drop(busy);
+        let response = dispatch(&registry, &server_id, shared, request.clone());
+        let _busy = BusyGuard::new(shared);
+        let keep_alive = request.wants_keep_alive() && !shared.shutdown.load(Ordering::SeqCst);
+
+        let written = match response {
+            Dispatched::Complete(response) => write_response(reader.get_mut(), response, keep_alive, head_only),
+            Dispatched::Stream(status, headers, receiver) => {
+                write_stream(reader.get_mut(), status, headers, receiver, keep_alive, head_only)
+            }
+        };
+
+        if written.is_err() || !keep_alive {
+            break;
+        }
+    }
+
+    if let NetStream::Tls(stream) = reader.get_mut() {
+        let _ = stream.shutdown();
+    }
+}
+
+/// Espera o primeiro byte da próxima requisição. Retorna false em EOF,
+/// timeout de keep-alive ou parada do servidor.
+fn wait_for_request(reader: &mut BufReader<NetStream>, shared: &ServerShared, idle_timeout: Duration) -> bool {
+    if !reader.buffer().is_empty() {
+        return true;
+    }
+    let _ = reader.get_ref().socket().set_read_timeout(Some(POLL_INTERVAL));
+    let started = Instant::now();
+    loop {
+        match reader.fill_buf() {
+            Ok(buffer) => return !buffer.is_empty(),
+            Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {
+                if shared.shutdown.load(Ordering::SeqCst) || started.elapsed() >= idle_timeout {
+                    return false;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(_) => return false,
+        }
+    }
+}
+
+/// Lê uma linha terminada em CRLF (ou LF), limitada a `MAX_LINE_BYTES`
+fn read_line(reader: &mut BufReader<NetStream>) -> Result<String, RequestError> {
+    let mut line = Vec::new();
+    let read = reader.by_ref().take(MAX_LINE_BYTES as u64 + 1).read_until(b'\n', &mut line)?;
+    if read == 0 {
+        return Err(RequestError::Closed);
+    }
+    if line.last() != Some(&b'\n') {
+        return Err(RequestError::Status(431, "Request Header Fields Too Large"));
+    }
+    line.pop();
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line).map_err(|_| RequestError::Status(400, "Bad Request"))
+}
+
+fn read_request(
+    reader: &mut BufReader<NetStream>,
+    config: &ServerConfig,
+    remote_addr: &str,
+) -> Result<HttpRequest, RequestError> {
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split(' ');
+    let (method, url, version) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(url), Some(version), None) if !method.is_empty() && url.starts_with(['/', '*']) => {
+            (method.to_string(), url.to_string(), version.to_string())
+        }
+        _ => return Err(RequestError::Status(400, "Bad Request")),
+    };
+    if version != "HTTP/1.1" && version != "HTTP/1.0" {
+        return Err(RequestError::Status(505, "HTTP Version Not Supported"));
+    }
+
+    let mut headers = Vec::new();
+    let mut header_bytes = 0;
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        header_bytes += line.len();
+        if headers.len() >= MAX_HEADERS || header_bytes > MAX_HEADER_BYTES {
+            return Err(RequestError::Status(431, "Request Header Fields Too Large"));
+        }
+        let (name, value) = line.split_once(':').ok_or(RequestError::Status(400, "Bad Request"))?;
+        if name.is_empty() || name.ends_with(' ') {
+            return Err(RequestError::Status(400, "Bad Request"));
+        }
+        headers.push((name.to_string(), value.trim().to_string()));
+    }
+
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query(query)),
+        None => (url.clone(), Vec::new()),
+    };
+    let mut request = HttpRequest {
+        method: method.to_ascii_uppercase(),
+        url,
+        path: percent_decode(&path, false),
+        query,
+        params: Vec::new(),
+        headers,
+        body: Vec::new(),
+        version,
+        remote_addr: remote_addr.to_string(),
+    };
+
+    let chunked = request
+        .header("transfer-encoding")
+        .map(|te| te.to_ascii_lowercase().contains("chunked"))
+        .unwrap_or(false);
+    let content_length = match request.header("content-length") {
+        Some(value) if !chunked => Some(value.parse::<usize>().map_err(|_| RequestError::Status(400, "Bad Request"))?),
+        _ => None,
+    };
+
+    if content_length.map(|len| len > config.max_body_size).unwrap_or(false) {
+        return Err(RequestError::Status(413, "Payload Too Large"));
+    }
+
+    let has_body = chunked || content_length.map(|len| len > 0).unwrap_or(false);
+    if has_body && request.header("expect").map(|e| e.eq_ignore_ascii_case("100-continue")).unwrap_or(false) {
+        reader.get_mut().write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+    }
+
+    if chunked {
+        request.body = read_chunked_body(reader, config.max_body_size)?;
+    } else if let Some(length) = content_length {
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        request.body = body;
+    }
+
+    Ok(request)
+}
+
+fn read_chunked_body(reader: &mut BufReader<NetStream>, max_size: usize) -> Result<Vec<u8>, RequestError> {
+    let mut body = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        let size_text = line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_text, 16).map_err(|_| RequestError::Status(400, "Bad Request"))?;
+        if size == 0 {
+            break;
+        }
+        if body.len() + size > max_size {
+            return Err(RequestError::Status(413, "Payload Too Large"));
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+        if !read_line(reader)?.is_empty() {
+            return Err(RequestError::Status(400, "Bad Request"));
+        }
+    }
+    // Trailers são lidos e descartados
+    while !read_line(reader)?.is_empty() {}
+    Ok(body)
+}
+
+enum Dispatched {
+    Complete(HttpResponse),
+    Stream(u16, Vec<(String, String)>, mpsc::Receiver<ResponsePart>),
+}
+
+/// Resolve a rota e, quando há handler ou middleware Dryad, enfileira a
+/// requisição para o interpreter e aguarda a resposta
+fn dispatch(registry: &HttpServers, server_id: &str, shared: &ServerShared, mut request: HttpRequest) -> Dispatched {
+    let (resolved, middleware, cors_origin) = {
+        let servers = registry.servers.lock().unwrap();
+        let Some(server) = servers.get(server_id) else {
+            return Dispatched::Complete(HttpResponse::text(503, "Service Unavailable"));
+        };
+
+        if request.method == "OPTIONS" {
+            if let Some(origin) = &server.cors_origin {
+                let mut response = HttpResponse { status: 204, headers: Vec::new(), body: Vec::new() };
+                add_cors_headers(&mut response.headers, origin, request.header("access-control-request-headers"));
+                return Dispatched::Complete(response);
+            }
+        }
+
+        let (resolved, middleware) = resolve_route(server, server_id, &request);
+        (resolved, middleware, server.cors_origin.clone())
+    };
+
+    let (handler, fallback) = match resolved {
+        Resolved::Handler(handler, params) => {
+            request.params = params;
+            (Some(*handler), None)
+        }
+        Resolved::Response(response) if middleware.is_empty() => {
+            return Dispatched::Complete(with_cors(response, cors_origin.as_deref()));
+        }
+        Resolved::Response(response) => (None, Some(response)),
+    };
+
+    if shared.shutdown.load(Ordering::SeqCst) {
+        return Dispatched::Complete(HttpResponse::text(503, "Service Unavailable"));
+    }
+
+    let (reply, receiver) = mpsc::channel();
+    registry.push(PendingRequest {
+        server_id: server_id.to_string(),
+        request,
+        handler,
+        middleware,
+        fallback,
+        reply,
+    });
+
+    match receiver.recv_timeout(HANDLER_TIMEOUT) {
+        Ok(ResponsePart::Complete(response)) => Dispatched::Complete(with_cors(response, cors_origin.as_deref())),
+        Ok(ResponsePart::StreamStart { status, mut headers }) => {
+            if let Some(origin) = &cors_origin {
+                add_cors_headers(&mut headers, origin, None);
+            }
+            Dispatched::Stream(status, headers, receiver)
+        }
+        Ok(_) | Err(_) => Dispatched::Complete(HttpResponse::text(504, "Timeout no processamento da requisição")),
+    }
+}
+
+fn add_cors_headers(headers: &mut Vec<(String, String)>, origin: &str, requested_headers: Option<&str>) {
+    set_default_header(headers, "Access-Control-Allow-Origin", origin);
+    if let Some(requested) = requested_headers {
+        set_default_header(headers, "Access-Control-Allow-Methods", "GET, POST, PUT, PATCH, DELETE, OPTIONS");
+        set_default_header(headers, "Access-Control-Allow-Headers", requested);
+    }
+}
+
+fn with_cors(mut response: HttpResponse, origin: Option<&str>) -> HttpResponse {
+    if let Some(origin) = origin {
+        add_cors_headers(&mut response.headers, origin, None);
+    }
+    response
+}
+
+fn write_head(head: &mut Vec<u8>, status: u16, headers: &[(String, String)], keep_alive: bool) {
+    head.extend_from_slice(format!("HTTP/1.1 {} {}\r\n", status, reason_phrase(status)).as_bytes());
+    for (name, value) in headers {
+        if name.eq_ignore_ascii_case("connection")
+            || name.eq_ignore_ascii_case("content-length")
+            || name.eq_ignore_ascii_case("transfer-encoding")
+        {
+            continue;
+        }
+        head.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+    }
+    if !has_header(headers, "server") {
+        head.extend_from_slice(b"Server: Dryad\r\n");
+    }
+    let connection = if keep_alive { "keep-alive" } else { "close" };
+    head.extend_from_slice(format!("Connection: {}\r\n", connection).as_bytes());
+}
+
+fn write_response(writer: &mut impl Write, response: HttpResponse, keep_alive: bool, head_only: bool) -> io::Result<()> {
+    let mut out = Vec::with_capacity(response.body.len() + 256);
+    write_head(&mut out, response.status, &response.headers, keep_alive);
+    let bodyless = response.status == 204 || response.status == 304 || response.status < 200;
+    if !bodyless {
+        out.extend_from_slice(format!("Content-Length: {}\r\n", response.body.len()).as_bytes());
+    }
+    out.extend_from_slice(b"\r\n");
+    if !head_only && !bodyless {
+        out.extend_from_slice(&response.body);
+    }
+    writer.write_all(&out)?;
+    writer.flush()
+}
+
+/// Envia uma resposta `Transfer-Encoding: chunked` com as partes produzidas
+/// pelo generator no interpreter
+fn write_stream(
+    writer: &mut impl Write,
+    status: u16,
+    mut headers: Vec<(String, String)>,
+    receiver: mpsc::Receiver<ResponsePart>,
+    keep_alive: bool,
+    head_only: bool,
+) -> io::Result<()> {
+    set_default_header(&mut headers, "Content-Type", "text/plain; charset=utf-8");
+    let mut head = Vec::new();
+    write_head(&mut head, status, &headers, keep_alive);
+    head.extend_from_slice(b"Transfer-Encoding: chunked\r\n\r\n");
+    writer.write_all(&head)?;
+    writer.flush()?;
+
+    loop {
+        match receiver.recv_timeout(HANDLER_TIMEOUT) {
+            Ok(ResponsePart::Chunk(bytes)) => {
+                if !head_only {
+                    writer.write_all(format!("{:x}\r\n", bytes.len()).as_bytes())?;
+                    writer.write_all(&bytes)?;
+                    writer.write_all(b"\r\n")?;
+                    writer.flush()?;
+                }
+            }
+            Ok(ResponsePart::End) => break,
+            // Handler falhou no meio do stream: não há como sinalizar por
+            // HTTP, então a conexão é encerrada sem o chunk final
+            _ => return Err(io::Error::other("stream interrompido")),
+        }
+    }
+
+    if !head_only {
+        writer.write_all(b"0\r\n\r\n")?;
+    }
+    writer.flush()
 }
 
 pub fn register_http_server_functions(functions: &mut HashMap<String, NativeFunction>) {
@@ -235,7 +1111,8 @@ pub fn register_http_server_functions(functions: &mut HashMap<String, NativeFunc
     functions.insert("native_http_server_start".to_string(), native_http_server_start);
     functions.insert("native_http_server_stop".to_string(), native_http_server_stop);
     functions.insert("native_http_server_status".to_string(), native_http_server_status);
-    
+    functions.insert("native_http_server_wait".to_string(), native_http_server_wait);
+
     // Configuração de rotas
     functions.insert("native_http_server_route".to_string(), native_http_server_route);
     functions.insert("native_http_server_handle".to_string(), native_http_server_handle);
@@ -243,165 +1120,245 @@ pub fn register_http_server_functions(functions: &mut HashMap<String, NativeFunc
     functions.insert("native_http_server_post".to_string(), native_http_server_post);
     functions.insert("native_http_server_put".to_string(), native_http_server_put);
     functions.insert("native_http_server_delete".to_string(), native_http_server_delete);
-    
+
     // Conteúdo estático
     functions.insert("native_http_server_static".to_string(), native_http_server_static);
     functions.insert("native_http_server_file".to_string(), native_http_server_file);
     functions.insert("native_http_server_html".to_string(), native_http_server_html);
     functions.insert("native_http_server_json".to_string(), native_http_server_json);
-    
+
     // Configurações avançadas
     functions.insert("native_http_server_cors".to_string(), native_http_server_cors);
     functions.insert("native_http_server_middleware".to_string(), native_http_server_middleware);
 }
 
+fn server_id_arg(args: &[Value], function: &str) -> Result<String, RuntimeError> {
+    match args.first() {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => Err(RuntimeError::TypeError("Primeiro argumento deve ser string (server_id)".to_string())),
+        None => Err(RuntimeError::ArgumentError(format!("{} espera pelo menos 1 argumento", function))),
+    }
+}
+
+fn is_callable(value: &Value) -> bool {
+    matches!(value, Value::Lambda(_) | Value::Function { .. } | Value::GeneratorFunction { .. })
+}
+
+fn number_option(options: &HashMap<String, Value>, key: &str) -> Result<Option<f64>, RuntimeError> {
+    match options.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) if *n >= 0.0 => Ok(Some(*n)),
+        Some(Value::Int(n)) if *n >= 0 => Ok(Some(*n as f64)),
+        Some(_) => Err(RuntimeError::TypeError(format!("Opção '{}' deve ser um número não negativo", key))),
+    }
+}
+
 // ========================
 // Funções de gerenciamento de servidor
 // ========================
 
-/// native_http_server_create(server_id, host?, port?) -> null
-/// Cria uma nova instância de servidor HTTP
+/// native_http_server_create(server_id, host?, port?, options?) -> null
+/// Cria uma nova instância de servidor HTTP. `options` aceita
+/// `max_body_size` (bytes), `keep_alive_timeout` e `request_timeout` (ms),
+/// `max_connections` (conexões simultâneas, padrão 256) e `tls`
+/// (`{ cert, key, ca?, client_auth?, alpn? }`) para servir HTTPS.
 fn native_http_server_create(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match args.get(0) {
-        Some(Value::String(s)) => s.clone(),
-        Some(_) => return Err(RuntimeError::TypeError("Primeiro argumento deve ser string (server_id)".to_string())),
-        None => return Err(RuntimeError::ArgumentError("native_http_server_create espera pelo menos 1 argumento".to_string())),
-    };
-    
+    let server_id = server_id_arg(args, "native_http_server_create")?;
+
     let host = match args.get(1) {
         Some(Value::String(s)) => s.clone(),
         Some(_) => return Err(RuntimeError::TypeError("Segundo argumento deve ser string (host)".to_string())),
         None => "127.0.0.1".to_string(),
     };
-    
+
     let port = match args.get(2) {
         Some(Value::Number(n)) => *n as u16,
         Some(_) => return Err(RuntimeError::TypeError("Terceiro argumento deve ser número (port)".to_string())),
         None => 8080,
     };
-    
-    let instance = ServerInstance {
+
+    let mut config = ServerConfig::default();
+    let mut tls_config = None;
+    match args.get(3) {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(id)) => {
+            let Some(ManagedObject::Object { properties, .. }) = _heap.get(*id) else {
+                return Err(RuntimeError::HeapError("Objeto de opções não encontrado".to_string()));
+            };
+            if let Some(size) = number_option(properties, "max_body_size")? {
+                config.max_body_size = size as usize;
+            }
+            if let Some(ms) = number_option(properties, "keep_alive_timeout")? {
+                config.keep_alive_timeout = Duration::from_millis(ms as u64);
+            }
+            if let Some(ms) = number_option(properties, "request_timeout")? {
+                config.request_timeout = Duration::from_millis(ms.max(1.0) as u64);
+            }
+            if let Some(max) = number_option(properties, "max_connections")? {
+                config.max_connections = (max as usize).max(1);
+            }
+            if let Some(options) = properties.get("tls").filter(|value| !matches!(value, Value::Null)) {
+                tls_config = Some(tls::server_config(options, _heap, "native_http_server_create")?);
+            }
+        }
+        Some(_) => return Err(RuntimeError::TypeError("Quarto argumento deve ser objeto (options)".to_string())),
+    }
+
+    let mut servers = _manager.http_servers().servers.lock().unwrap();
+    if servers.get(&server_id).map(|server| server.running.is_some()).unwrap_or(false) {
+        return Err(RuntimeError::Generic(format!("Servidor '{}' já está rodando", server_id)));
+    }
+    servers.insert(server_id, ServerInstance {
         port,
         host,
-        is_running: false,
-        stop_sender: None,
-    };
-    
-    (*HTTP_SERVERS).lock().unwrap().insert(server_id.clone(), instance);
-    (*ROUTE_HANDLERS).lock().unwrap().insert(server_id.clone(), HashMap::new());
-    (*STATIC_CONTENT).lock().unwrap().insert(server_id, HashMap::new());
-    
+        config,
+        routes: Vec::new(),
+        static_content: HashMap::new(),
+        middleware: Vec::new(),
+        cors_origin: None,
+        tls: tls_config,
+        running: None,
+    });
+
     Ok(Value::Null)
 }
 
 /// native_http_server_start(server_id) -> null
-/// Inicia o servidor HTTP
+/// Abre a porta e inicia o servidor HTTP em threads separadas. Com porta 0 o
+/// sistema escolhe uma porta livre, consultável via `native_http_server_status`.
 fn native_http_server_start(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match args.get(0) {
-        Some(Value::String(s)) => s.clone(),
-        Some(_) => return Err(RuntimeError::TypeError("Argumento deve ser string (server_id)".to_string())),
-        None => return Err(RuntimeError::ArgumentError("native_http_server_start espera 1 argumento".to_string())),
-    };
-    
-    let (host, port) = {
-        let servers = (*HTTP_SERVERS).lock().unwrap();
-        let server = servers.get(&server_id)
-            .ok_or_else(|| RuntimeError::ArgumentError(format!("Servidor '{}' não encontrado", server_id)))?;
-        
-        if server.is_running {
+    let server_id = server_id_arg(args, "native_http_server_start")?;
+
+    let registry = _manager.http_servers();
+    registry.with_server(&server_id, |server| {
+        if server.running.is_some() {
             return Err(RuntimeError::Generic(format!("Servidor '{}' já está rodando", server_id)));
         }
-        
-        (server.host.clone(), server.port)
-    };
-    
-    // Cria canal para parar o servidor
-    let (stop_sender, stop_receiver) = mpsc::channel();
-    
-    // Inicia servidor em thread separada
-    let server_id_clone = server_id.clone();
-    let handle = thread::spawn(move || {
-        start_simple_http_server(server_id_clone, host, port, stop_receiver);
-    });
-    
-    // Armazena o handle da thread
-    (*SERVER_THREADS).lock().unwrap().insert(server_id.clone(), handle);
-    
-    // Aguarda um pouco para o servidor inicializar
-    thread::sleep(Duration::from_millis(200));
-    
-    // Marca como rodando e armazena o sender
-    {
-        let mut servers = (*HTTP_SERVERS).lock().unwrap();
-        if let Some(server) = servers.get_mut(&server_id) {
-            server.is_running = true;
-            server.stop_sender = Some(stop_sender);
-        }
-    }
-    
-    println!("🚀 Servidor HTTP '{}' iniciado em http://{}:{}", 
-             server_id,
-             (*HTTP_SERVERS).lock().unwrap().get(&server_id).unwrap().host,
-             (*HTTP_SERVERS).lock().unwrap().get(&server_id).unwrap().port);
-    
-    Ok(Value::Null)
+
+        let addr = format!("{}:{}", server.host, server.port);
+        let listener = TcpListener::bind(&addr)
+            .map_err(|e| RuntimeError::NetworkError(format!("Erro ao iniciar servidor '{}' em {}: {}", server_id, addr, e)))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| RuntimeError::NetworkError(e.to_string()))?;
+        if let Ok(local) = listener.local_addr() {
+            server.port = local.port();
+        }
+
+        let shared = Arc::new(ServerShared::default());
+        let accept_thread = {
+            let registry = registry.clone();
+            let server_id = server_id.clone();
+            let shared = shared.clone();
+            let config = server.config.clone();
+            let tls_config = server.tls.clone();
+            thread::spawn(move || accept_loop(listener, registry, server_id, shared, config, tls_config))
+        };
+        server.running = Some(RunningServer { shared, accept_thread });
+
+        let scheme = if server.tls.is_some() { "https" } else { "http" };
+        println!("🚀 Servidor HTTP '{}' iniciado em {}://{}:{}", server_id, scheme, server.host, server.port);
+        Ok(Value::Null)
+    })
 }
 
-/// native_http_server_stop(server_id) -> null
-/// Para o servidor HTTP
+/// native_http_server_stop(server_id, grace_ms?) -> null
+/// Para o servidor de forma graciosa: deixa de aceitar conexões, fecha as
+/// conexões ociosas, responde 503 às requisições ainda na fila e espera até
+/// `grace_ms` (padrão 5000) pelas respostas em andamento.
 fn native_http_server_stop(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match args.get(0) {
-        Some(Value::String(s)) => s.clone(),
-        Some(_) => return Err(RuntimeError::TypeError("Argumento deve ser string (server_id)".to_string())),
-        None => return Err(RuntimeError::ArgumentError("native_http_server_stop espera 1 argumento".to_string())),
+    let server_id = server_id_arg(args, "native_http_server_stop")?;
+    let grace = match args.get(1) {
+        Some(Value::Number(ms)) if *ms >= 0.0 => Duration::from_millis(*ms as u64),
+        Some(_) => return Err(RuntimeError::TypeError("Segundo argumento deve ser número (grace_ms)".to_string())),
+        None => Duration::from_secs(5),
     };
-    
-    // Para o servidor enviando sinal
+
+    let registry = _manager.http_servers();
+    let running = registry.with_server(&server_id, |server| {
+        server
+            .running
+            .take()
+            .ok_or_else(|| RuntimeError::Generic(format!("Servidor '{}' não está rodando", server_id)))
+    })?;
+    running.shared.shutdown.store(true, Ordering::SeqCst);
+
     {
-        let mut servers = (*HTTP_SERVERS).lock().unwrap();
-        if let Some(server) = servers.get_mut(&server_id) {
-            if !server.is_running {
-                return Err(RuntimeError::Generic(format!("Servidor '{}' não está rodando", server_id)));
-            }
-            
-            if let Some(sender) = server.stop_sender.take() {
-                let _ = sender.send(()); // Envia sinal para parar
-            }
-            
-            server.is_running = false;
-        } else {
-            return Err(RuntimeError::ArgumentError(format!("Servidor '{}' não encontrado", server_id)));
+        let mut pending = registry.pending.lock().unwrap();
+        let (queued, others): (Vec<_>, Vec<_>) = std::mem::take(&mut *pending)
+            .into_iter()
+            .partition(|request| request.server_id == server_id);
+        *pending = others;
+        for request in queued {
+            let _ = request.reply.send(ResponsePart::Complete(HttpResponse::text(503, "Service Unavailable")));
         }
+        registry.ready.notify_all();
     }
-    
+
+    let _ = running.accept_thread.join();
+
+    let deadline = Instant::now() + grace;
+    while running.shared.busy.load(Ordering::SeqCst) > 0 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+
     println!("🛑 Servidor HTTP '{}' parado", server_id);
     Ok(Value::Null)
 }
 
+/// native_http_server_wait(server_id, timeout_ms?) -> bool
+/// Bloqueia até chegar uma requisição para o interpreter, o tempo limite
+/// (padrão 1000 ms) expirar ou o servidor parar. Retorna se o servidor ainda
+/// está rodando, permitindo `while (native_http_server_wait(id)) {}`.
+fn native_http_server_wait(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let server_id = server_id_arg(args, "native_http_server_wait")?;
+    let timeout = match args.get(1) {
+        Some(Value::Number(ms)) if *ms >= 0.0 => Duration::from_millis(*ms as u64),
+        Some(_) => return Err(RuntimeError::TypeError("Segundo argumento deve ser número (timeout_ms)".to_string())),
+        None => Duration::from_secs(1),
+    };
+
+    let registry = _manager.http_servers();
+    let shared = registry.with_server(&server_id, |server| Ok(server.running.as_ref().map(|running| running.shared.clone())))?;
+    let Some(shared) = shared else {
+        return Ok(Value::Bool(false));
+    };
+
+    let deadline = Instant::now() + timeout;
+    let mut pending = registry.pending.lock().unwrap();
+    loop {
+        if shared.shutdown.load(Ordering::SeqCst) {
+            return Ok(Value::Bool(false));
+        }
+        let now = Instant::now();
+        if pending.iter().any(|request| request.server_id == server_id) || now >= deadline {
+            return Ok(Value::Bool(true));
+        }
+        pending = registry.ready.wait_timeout(pending, deadline - now).unwrap().0;
+    }
+}
+
 /// native_http_server_status(server_id) -> object
 /// Retorna status do servidor
 fn native_http_server_status(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match args.get(0) {
-        Some(Value::String(s)) => s.clone(),
-        Some(_) => return Err(RuntimeError::TypeError("Argumento deve ser string (server_id)".to_string())),
-        None => return Err(RuntimeError::ArgumentError("native_http_server_status espera 1 argumento".to_string())),
-    };
-    
-    let servers = (*HTTP_SERVERS).lock().unwrap();
-    let server = servers.get(&server_id)
-        .ok_or_else(|| RuntimeError::ArgumentError(format!("Servidor '{}' não encontrado", server_id)))?;
-    
-    let mut status_map = HashMap::new();
-    status_map.insert("server_id".to_string(), Value::String(server_id.clone()));
-    status_map.insert("host".to_string(), Value::String(server.host.clone()));
-    status_map.insert("port".to_string(), Value::Number(server.port as f64));
-    status_map.insert("running".to_string(), Value::Bool(server.is_running));
-    
+    let server_id = server_id_arg(args, "native_http_server_status")?;
+
+    let status_map = _manager.http_servers().with_server(&server_id, |server| {
+        let mut status_map = HashMap::new();
+        status_map.insert("server_id".to_string(), Value::String(server_id.clone()));
+        status_map.insert("host".to_string(), Value::String(server.host.clone()));
+        status_map.insert("port".to_string(), Value::Number(server.port as f64));
+        status_map.insert("running".to_string(), Value::Bool(server.running.is_some()));
+        status_map.insert("tls".to_string(), Value::Bool(server.tls.is_some()));
+        status_map.insert("routes".to_string(), Value::Number(server.routes.len() as f64));
+        Ok(status_map)
+    })?;
+
     let id = _heap.allocate(crate::heap::ManagedObject::Object {
         properties: status_map,
         methods: HashMap::new(),
     });
-    
+
     Ok(Value::Object(id))
 }
 
@@ -409,121 +1366,91 @@ fn native_http_server_status(args: &[Value], _manager: &crate::native_modules::N
 // Funções de configuração de rotas
 // ========================
 
-/// native_http_server_route(server_id, method, path, response_body, status_code?) -> null
-/// Define uma rota genérica
+fn add_route(manager: &NativeModuleManager, server_id: &str, method: String, path: &str, target: RouteTarget) -> Result<Value, RuntimeError> {
+    manager.http_servers().with_server(server_id, |server| {
+        let pattern = parse_pattern(path);
+        server.routes.retain(|route| !(route.method == method && route.pattern == pattern));
+        server.routes.push(Route { method, pattern, target });
+        Ok(Value::Null)
+    })
+}
+
+/// native_http_server_route(server_id, method, path, response, status_code?) -> null
+/// Define uma rota genérica. `path` aceita parâmetros (`/users/:id`) e `*`
+/// no final; `response` é um corpo fixo (string) ou um handler `(req, res)`.
 fn native_http_server_route(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    route_with_content_type(args, _manager, "text/html; charset=utf-8")
+}
+
+fn route_with_content_type(args: &[Value], manager: &NativeModuleManager, content_type: &str) -> Result<Value, RuntimeError> {
     let server_id = match args.get(0) {
         Some(Value::String(s)) => s.clone(),
         _ => return Err(RuntimeError::ArgumentError("Primeiro argumento deve ser string (server_id)".to_string())),
     };
-    
+
     let method = match args.get(1) {
         Some(Value::String(s)) => s.to_uppercase(),
         _ => return Err(RuntimeError::ArgumentError("Segundo argumento deve ser string (method)".to_string())),
     };
-    
+
     let path = match args.get(2) {
         Some(Value::String(s)) => s.clone(),
         _ => return Err(RuntimeError::ArgumentError("Terceiro argumento deve ser string (path)".to_string())),
     };
-    
-    let response_body = match args.get(3) {
-        Some(Value::String(s)) => s.clone(),
-        _ => return Err(RuntimeError::ArgumentError("Quarto argumento deve ser string (response_body)".to_string())),
-    };
-    
-    let status_code = match args.get(4) {
-        Some(Value::Number(n)) => *n as u16,
-        _ => 200,
-    };
-    
-    let route_key = format!("{}:{}", method, path);
-    let handler = RouteHandler {
-        method,
-        path,
-        response_body,
-        response_headers: HashMap::new(),
-        status_code,
+
+    let target = match args.get(3) {
+        Some(Value::String(s)) => {
+            let status = match args.get(4) {
+                Some(Value::Number(n)) => *n as u16,
+                _ => 200,
+            };
+            RouteTarget::Fixed { body: s.clone(), content_type: content_type.to_string(), status }
+        }
+        Some(handler) if is_callable(handler) => RouteTarget::Handler(Box::new(handler.clone())),
+        _ => return Err(RuntimeError::ArgumentError("Quarto argumento deve ser string (response_body) ou função".to_string())),
     };
-    
-    (*ROUTE_HANDLERS).lock().unwrap()
-        .get_mut(&server_id)
-        .ok_or_else(|| RuntimeError::ArgumentError(format!("Servidor '{}' não encontrado", server_id)))?
-        .insert(route_key, handler);
-    
-    Ok(Value::Null)
+
+    add_route(manager, &server_id, method, &path, target)
 }
 
-/// native_http_server_get(server_id, path, response_body) -> null
-/// Define uma rota GET
-fn native_http_server_get(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+fn method_route(args: &[Value], manager: &NativeModuleManager, method: &str, function: &str) -> Result<Value, RuntimeError> {
     if args.len() < 3 {
-        return Err(RuntimeError::ArgumentError("native_http_server_get espera pelo menos 3 argumentos (server_id, path, response_body)".to_string()));
+        return Err(RuntimeError::ArgumentError(format!("{} espera pelo menos 3 argumentos (server_id, path, response_body)", function)));
     }
-    
+
     let new_args = vec![
         args[0].clone(),
-        Value::String("GET".to_string()),
+        Value::String(method.to_string()),
         args[1].clone(),
         args[2].clone(),
         args.get(3).cloned().unwrap_or(Value::Number(200.0)),
     ];
-    
-    native_http_server_route(&new_args, _manager, _heap)
+
+    route_with_content_type(&new_args, manager, "text/html; charset=utf-8")
 }
 
-/// native_http_server_post(server_id, path, response_body) -> null
+/// native_http_server_get(server_id, path, response) -> null
+/// Define uma rota GET
+fn native_http_server_get(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    method_route(args, _manager, "GET", "native_http_server_get")
+}
+
+/// native_http_server_post(server_id, path, response) -> null
 /// Define uma rota POST
 fn native_http_server_post(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    if args.len() < 3 {
-        return Err(RuntimeError::ArgumentError("native_http_server_post espera pelo menos 3 argumentos (server_id, path, response_body)".to_string()));
-    }
-    
-    let new_args = vec![
-        args[0].clone(),
-        Value::String("POST".to_string()),
-        args[1].clone(),
-        args[2].clone(),
-        args.get(3).cloned().unwrap_or(Value::Number(200.0)),
-    ];
-    
-    native_http_server_route(&new_args, _manager, _heap)
+    method_route(args, _manager, "POST", "native_http_server_post")
 }
 
-/// native_http_server_put(server_id, path, response_body) -> null
+/// native_http_server_put(server_id, path, response) -> null
 /// Define uma rota PUT
 fn native_http_server_put(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    if args.len() < 3 {
-        return Err(RuntimeError::ArgumentError("native_http_server_put espera pelo menos 3 argumentos (server_id, path, response_body)".to_string()));
-    }
-    
-    let new_args = vec![
-        args[0].clone(),
-        Value::String("PUT".to_string()),
-        args[1].clone(),
-        args[2].clone(),
-        args.get(3).cloned().unwrap_or(Value::Number(200.0)),
-    ];
-    
-    native_http_server_route(&new_args, _manager, _heap)
+    method_route(args, _manager, "PUT", "native_http_server_put")
 }
 
-/// native_http_server_delete(server_id, path, response_body) -> null
+/// native_http_server_delete(server_id, path, response) -> null
 /// Define uma rota DELETE
 fn native_http_server_delete(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    if args.len() < 3 {
-        return Err(RuntimeError::ArgumentError("native_http_server_delete espera pelo menos 3 argumentos (server_id, path, response_body)".to_string()));
-    }
-    
-    let new_args = vec![
-        args[0].clone(),
-        Value::String("DELETE".to_string()),
-        args[1].clone(),
-        args[2].clone(),
-        args.get(3).cloned().unwrap_or(Value::Number(200.0)),
-    ];
-    
-    native_http_server_route(&new_args, _manager, _heap)
+    method_route(args, _manager, "DELETE", "native_http_server_delete")
 }
 
 // ========================
@@ -536,39 +1463,37 @@ fn native_http_server_static(args: &[Value], _manager: &crate::native_modules::N
     if args.len() != 3 {
         return Err(RuntimeError::ArgumentError("native_http_server_static espera 3 argumentos".to_string()));
     }
-    
+
     let server_id = match &args[0] {
         Value::String(s) => s.clone(),
         _ => return Err(RuntimeError::TypeError("Primeiro argumento deve ser string (server_id)".to_string())),
     };
-    
+
     let path = match &args[1] {
         Value::String(s) => s.clone(),
         _ => return Err(RuntimeError::TypeError("Segundo argumento deve ser string (path)".to_string())),
     };
-    
+
     let file_path = match &args[2] {
         Value::String(s) => s.clone(),
         _ => return Err(RuntimeError::TypeError("Terceiro argumento deve ser string (file_path)".to_string())),
     };
-    
+
     // Lê o arquivo
     let content = fs::read(&file_path)
         .map_err(|e| RuntimeError::IoError(format!("Erro ao ler arquivo '{}': {}", file_path, e)))?;
-    
+
     let content_type = get_content_type(&file_path);
-    
+
     let static_content = StaticContent {
         content,
         content_type,
     };
-    
-    (*STATIC_CONTENT).lock().unwrap()
-        .get_mut(&server_id)
-        .ok_or_else(|| RuntimeError::ArgumentError(format!("Servidor '{}' não encontrado", server_id)))?
-        .insert(path, static_content);
-    
-    Ok(Value::Null)
+
+    _manager.http_servers().with_server(&server_id, |server| {
+        server.static_content.insert(path, static_content);
+        Ok(Value::Null)
+    })
 }
 
 /// native_http_server_file(server_id, path, file_path) -> null
@@ -583,19 +1508,15 @@ fn native_http_server_html(args: &[Value], _manager: &crate::native_modules::Nat
     if args.len() != 3 {
         return Err(RuntimeError::ArgumentError("native_http_server_html espera 3 argumentos".to_string()));
     }
-    
+
     let new_args = vec![
         args[0].clone(),
         Value::String("GET".to_string()),
         args[1].clone(),
         args[2].clone(),
     ];
-    
-    // Define como rota GET primeiro
-    native_http_server_route(&new_args, _manager, _heap)?;
-    
-    // Depois define o content-type como HTML (simplificado por enquanto)
-    Ok(Value::Null)
+
+    route_with_content_type(&new_args, _manager, "text/html; charset=utf-8")
 }
 
 /// native_http_server_json(server_id, path, json_content) -> null
@@ -604,19 +1525,15 @@ fn native_http_server_json(args: &[Value], _manager: &crate::native_modules::Nat
     if args.len() != 3 {
         return Err(RuntimeError::ArgumentError("native_http_server_json espera 3 argumentos".to_string()));
     }
-    
+
     let new_args = vec![
         args[0].clone(),
         Value::String("GET".to_string()),
         args[1].clone(),
         args[2].clone(),
     ];
-    
-    // Define como rota GET primeiro
-    native_http_server_route(&new_args, _manager, _heap)?;
-    
-    // Depois define o content-type como JSON (simplificado por enquanto)
-    Ok(Value::Null)
+
+    route_with_content_type(&new_args, _manager, "application/json")
 }
 
 // ========================
@@ -624,109 +1541,69 @@ fn native_http_server_json(args: &[Value], _manager: &crate::native_modules::Nat
 // ========================
 
 /// native_http_server_cors(server_id, origin?) -> null
-/// Configura CORS
+/// Habilita CORS: adiciona `Access-Control-Allow-Origin` (padrão `*`) a todas
+/// as respostas e responde preflights `OPTIONS` com 204
 fn native_http_server_cors(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    // Por enquanto, apenas aceita os argumentos mas não implementa CORS real
-    if args.is_empty() {
-        return Err(RuntimeError::ArgumentError("native_http_server_cors espera pelo menos 1 argumento".to_string()));
-    }
-    
-    println!("📋 CORS configurado (implementação simplificada)");
-    Ok(Value::Null)
+    let server_id = server_id_arg(args, "native_http_server_cors")?;
+    let origin = match args.get(1) {
+        Some(Value::String(s)) => s.clone(),
+        None | Some(Value::Null) => "*".to_string(),
+        Some(_) => return Err(RuntimeError::TypeError("Segundo argumento deve ser string (origin)".to_string())),
+    };
+
+    _manager.http_servers().with_server(&server_id, |server| {
+        server.cors_origin = Some(origin);
+        Ok(Value::Null)
+    })
 }
 
 /// native_http_server_middleware(server_id, middleware_fn) -> null
-/// Adiciona middleware (futuro)
+/// Adiciona um middleware `(req, res)`, executado na ordem de registro antes
+/// de qualquer rota. Retornar `false` interrompe a cadeia e envia `res`.
 fn native_http_server_middleware(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    // Por enquanto, apenas aceita os argumentos mas não implementa middleware real
     if args.len() != 2 {
         return Err(RuntimeError::ArgumentError("native_http_server_middleware espera 2 argumentos".to_string()));
     }
-    
-    println!("📋 Middleware adicionado (implementação simplificada)");
-    Ok(Value::Null)
-}
-
-// ========================
-// Funções auxiliares (implementação antiga com Tokio - desabilitada)
-// ========================
 
-/*
-/// Inicia o servidor HTTP real (versão Tokio/Hyper - desabilitada)
-async fn start_http_server(server_id: String, host: String, port: u16) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Implementação comentada para evitar conflitos com threads do Tokio
-    // ... código original aqui ...
-}
+    let server_id = server_id_arg(args, "native_http_server_middleware")?;
+    if !is_callable(&args[1]) {
+        return Err(RuntimeError::TypeError("Segundo argumento deve ser uma função (middleware)".to_string()));
+    }
 
-/// Manipula requisições HTTP (versão Tokio/Hyper - desabilitada)  
-async fn handle_request(
-    req: Request<hyper::body::Incoming>,
-    server_id: String,
-) -> Result<Response<Full<Bytes>>, Infallible> {
-    // Implementação comentada para evitar conflitos com threads do Tokio
-    // ... código original aqui ...
+    _manager.http_servers().with_server(&server_id, |server| {
+        server.middleware.push(args[1].clone());
+        Ok(Value::Null)
+    })
 }
-*/
 
-/// native_http_server_handle(server_id, method, path, lambda) -> null
-/// Registra um handler dinâmico (lambda) para uma rota
+/// native_http_server_handle(server_id, method, path, handler) -> null
+/// Registra um handler dinâmico `(req, res)` para uma rota; `method` pode ser
+/// `*` para aceitar qualquer método
 fn native_http_server_handle(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 4 {
         return Err(RuntimeError::ArgumentError("native_http_server_handle espera 4 argumentos (server_id, method, path, lambda)".to_string()));
     }
-    
+
     let server_id = match &args[0] {
         Value::String(s) => s.clone(),
         _ => return Err(RuntimeError::TypeError("Primeiro argumento deve ser string (server_id)".to_string())),
     };
-    
+
     let method = match &args[1] {
         Value::String(s) => s.to_uppercase(),
         _ => return Err(RuntimeError::TypeError("Segundo argumento deve ser string (method)".to_string())),
     };
-    
+
     let path = match &args[2] {
         Value::String(s) => s.clone(),
         _ => return Err(RuntimeError::TypeError("Terceiro argumento deve ser string (path)".to_string())),
     };
-    
-    let lambda_id = match &args[3] {
-        Value::Lambda(id) => *id,
-        _ => return Err(RuntimeError::TypeError("Quarto argumento deve ser uma lambda".to_string())),
-    };
-    
-    let route_key = format!("{}:{}", method, path);
-    (*DYNAMIC_HANDLERS).lock().unwrap()
-        .entry(server_id)
-        .or_insert_with(|| HashMap::new())
-        .insert(route_key, lambda_id);
-    
-    Ok(Value::Null)
-}
 
-/// Função chamada pelo interpreter para processar requisições pendentes
-pub fn process_pending_requests<F>(mut callback: F) -> Result<(), RuntimeError> 
-where F: FnMut(String, String, String, HeapId) -> Result<(u16, String), RuntimeError> {
-    let mut requests = {
-        let mut pending = (*PENDING_REQUESTS).lock().unwrap();
-        if pending.is_empty() {
-            return Ok(());
-        }
-        std::mem::take(&mut *pending)
-    };
-
-    for mut req in requests.drain(..) {
-        match callback(req.server_id, req.method, req.path, req.lambda_id) {
-            Ok((status, body)) => {
-                let _ = req.response_channel.send(HttpResponse { status, body });
-            }
-            Err(e) => {
-                let _ = req.response_channel.send(HttpResponse { status: 500, body: e.to_string() });
-            }
-        }
+    if !is_callable(&args[3]) {
+        return Err(RuntimeError::TypeError("Quarto argumento deve ser uma lambda".to_string()));
     }
 
-    Ok(())
+    add_route(_manager, &server_id, method, &path, RouteTarget::Handler(Box::new(args[3].clone())))
 }
 
 /// Determina tipo de conteúdo baseado na extensão do arquivo
@@ -747,4 +1624,40 @@ fn get_content_type(file_path: &str) -> String {
         Some("md") => "text/markdown; charset=utf-8".to_string(),
         _ => "application/octet-stream".to_string(),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_patterns_capture_params_and_wildcards() {
+        let pattern = parse_pattern("/users/:id/posts/:post");
+        assert_eq!(
+            match_pattern(&pattern, "/users/42/posts/7"),
+            Some(vec![("id".to_string(), "42".to_string()), ("post".to_string(), "7".to_string())])
+        );
+        assert_eq!(match_pattern(&pattern, "/users/42/posts"), None);
+        assert_eq!(match_pattern(&parse_pattern("/"), "/"), Some(vec![]));
+
+        let wildcard = parse_pattern("/assets/*");
+        assert_eq!(
+            match_pattern(&wildcard, "/assets/css/app.css"),
+            Some(vec![("*".to_string(), "css/app.css".to_string())])
+        );
+    }
+
+    #[test]
+    fn query_strings_are_percent_decoded() {
+        assert_eq!(
+            parse_query("q=caf%C3%A9+com+leite&empty&x=%2F"),
+            vec![
+                ("q".to_string(), "café com leite".to_string()),
+                ("empty".to_string(), String::new()),
+                ("x".to_string(), "/".to_string()),
+            ]
+        );
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%zz", false), "%zz");
+    }
+}
@@ -4,6 +4,7 @@ use crate::interpreter::Value;
 use crate::native_modules::NativeFunction;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
+use super::encode_decode::{json_to_runtime_value, runtime_value_to_json};
 
 pub fn register_json_stream_functions(functions: &mut HashMap<String, NativeFunction>) {
     functions.insert("json_parse_incremental".to_string(), json_parse_incremental);
@@ -42,7 +43,7 @@ fn json_parse_incremental(
     };
 
     match serde_json::from_str::<JsonValue>(json_string) {
-        Ok(json_value) => Ok(json_value_to_runtime_value(&json_value, _heap)),
+        Ok(json_value) => Ok(json_to_runtime_value(&json_value, _heap)),
         Err(e) => Err(RuntimeError::IoError(format!(
             "Erro ao analisar JSON: {}",
             e
@@ -95,7 +96,7 @@ fn json_parse_stream(
     }
 
     match serde_json::from_str::<JsonValue>(&combined) {
-        Ok(json_value) => Ok(json_value_to_runtime_value(&json_value, _heap)),
+        Ok(json_value) => Ok(json_to_runtime_value(&json_value, _heap)),
         Err(e) => Err(RuntimeError::IoError(format!(
             "Erro ao analisar JSON stream: {}",
             e
@@ -176,7 +177,7 @@ fn json_parser_feed(
             // Try to parse incrementally
             match serde_json::from_str::<JsonValue>(&new_buffer) {
                 Ok(json_value) => {
-                    return Ok(json_value_to_runtime_value(&json_value, _heap));
+                    return Ok(json_to_runtime_value(&json_value, _heap));
                 }
                 Err(_) => {
                     // Not complete yet, continue buffering
@@ -283,75 +284,3 @@ fn json_encoder_encode(
 
     Ok(Value::String(json_string))
 }
-
-fn json_value_to_runtime_value(value: &JsonValue, heap: &mut Heap) -> Value {
-    match value {
-        JsonValue::Null => Value::Null,
-        JsonValue::Bool(b) => Value::Bool(*b),
-        JsonValue::Number(n) => Value::Number(n.as_f64().unwrap_or(0.0)),
-        JsonValue::String(s) => Value::String(s.clone()),
-        JsonValue::Array(arr) => {
-            let runtime_array: Vec<Value> = arr
-                .iter()
-                .map(|v| json_value_to_runtime_value(v, heap))
-                .collect();
-            let id = heap.allocate(ManagedObject::Array(runtime_array));
-            Value::Array(id)
-        }
-        JsonValue::Object(obj) => {
-            let mut runtime_obj = HashMap::new();
-            for (key, val) in obj {
-                runtime_obj.insert(key.clone(), json_value_to_runtime_value(val, heap));
-            }
-            let id = heap.allocate(ManagedObject::Object {
-                properties: runtime_obj,
-                methods: HashMap::new(),
-            });
-            Value::Object(id)
-        }
-    }
-}
-
-fn runtime_value_to_json(value: &Value, heap: &Heap) -> Result<JsonValue, RuntimeError> {
-    match value {
-        Value::Null => Ok(JsonValue::Null),
-        Value::Bool(b) => Ok(JsonValue::Bool(*b)),
-        Value::Number(n) => Ok(JsonValue::Number(
-            serde_json::Number::from_f64(*n).unwrap_or(serde_json::Number::from(0)),
-        )),
-        Value::String(s) => Ok(JsonValue::String(s.clone())),
-        Value::Array(id) => {
-            let obj = heap
-                .get(*id)
-                .ok_or_else(|| RuntimeError::HeapError("Array reference not found".to_string()))?;
-            if let ManagedObject::Array(arr) = obj {
-                let json_array: Result<Vec<JsonValue>, _> =
-                    arr.iter().map(|v| runtime_value_to_json(v, heap)).collect();
-                Ok(JsonValue::Array(json_array?))
-            } else {
-                Err(RuntimeError::TypeError(
-                    "Expected array in heap".to_string(),
-                ))
-            }
-        }
-        Value::Object(id) => {
-            let obj = heap
-                .get(*id)
-                .ok_or_else(|| RuntimeError::HeapError("Object reference not found".to_string()))?;
-            if let ManagedObject::Object { properties, .. } = obj {
-                let mut json_obj = serde_json::Map::new();
-                for (key, val) in properties {
-                    json_obj.insert(key.clone(), runtime_value_to_json(val, heap)?);
-                }
-                Ok(JsonValue::Object(json_obj))
-            } else {
-                Err(RuntimeError::TypeError(
-                    "Expected object in heap".to_string(),
-                ))
-            }
-        }
-        _ => Err(RuntimeError::TypeError(
-            "Tipo não suportado para JSON".to_string(),
-        )),
-    }
-}
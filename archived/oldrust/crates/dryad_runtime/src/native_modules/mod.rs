@@ -9,13 +9,16 @@ pub mod binary_io;
 pub mod file_io;
 pub mod time;
 pub mod system_env;
+pub mod process;
 pub mod encode_decode;
+pub mod serde_bridge;
 pub mod crypto;
 pub mod debug;
 pub mod utils; 
 pub mod http_client;
 pub mod http_server;
 pub mod tcp;
+pub mod tls;
 pub mod udp;
 pub mod ffi;
 pub mod json_stream;
@@ -32,6 +35,7 @@ use crate::errors::RuntimeError;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 
 /// Tipo para funções nativas
 pub type NativeFunction = fn(&[Value], &NativeModuleManager, &mut Heap) -> Result<Value, RuntimeError>;
@@ -53,6 +57,18 @@ pub struct NativeModuleManager {
     allow_exec: bool,
     /// Diretório raiz para o sandbox de arquivos (se None, usa o diretório atual como base)
     sandbox_root: Option<std::path::PathBuf>,
+    /// Temporários fora do sandbox liberados para este interpreter
+    temp_paths: file_io::TempPaths,
+    /// Arquivos abertos por este interpreter
+    open_files: file_io::OpenFiles,
+    /// Processos criados por `spawn` neste interpreter
+    processes: process::Processes,
+    /// Servidores HTTP deste interpreter e a fila de requisições para ele
+    http_servers: Arc<http_server::HttpServers>,
+    /// Servidores TCP deste interpreter e a fila de eventos para ele
+    tcp_servers: Arc<tcp::TcpServers>,
+    /// Servidores UDP deste interpreter e a fila de mensagens para ele
+    udp_servers: Arc<udp::UdpServers>,
 }
 
 impl NativeModuleManager {
@@ -64,6 +80,12 @@ impl NativeModuleManager {
             allow_unsafe: false,
             allow_exec: false,
             sandbox_root: None,
+            temp_paths: file_io::TempPaths::default(),
+            open_files: file_io::OpenFiles::default(),
+            processes: process::Processes::default(),
+            http_servers: Default::default(),
+            tcp_servers: Default::default(),
+            udp_servers: Default::default(),
         };
         
         // Registra todas as categorias disponíveis
@@ -109,6 +131,7 @@ impl NativeModuleManager {
         // Registra system_env
         let mut system_env_functions = HashMap::new();
         system_env::register_system_env_functions(&mut system_env_functions);
+        process::register_process_functions(&mut system_env_functions);
         self.categories.insert("system_env".to_string(), system_env_functions);
         
         // Registra encode_decode
@@ -244,6 +267,30 @@ impl NativeModuleManager {
     pub fn sandbox_root(&self) -> Option<&std::path::PathBuf> {
         self.sandbox_root.as_ref()
     }
+
+    pub(crate) fn temp_paths(&self) -> &file_io::TempPaths {
+        &self.temp_paths
+    }
+
+    pub(crate) fn open_files(&self) -> &file_io::OpenFiles {
+        &self.open_files
+    }
+
+    pub(crate) fn processes(&self) -> &process::Processes {
+        &self.processes
+    }
+
+    pub(crate) fn http_servers(&self) -> &Arc<http_server::HttpServers> {
+        &self.http_servers
+    }
+
+    pub(crate) fn tcp_servers(&self) -> &Arc<tcp::TcpServers> {
+        &self.tcp_servers
+    }
+
+    pub(crate) fn udp_servers(&self) -> &Arc<udp::UdpServers> {
+        &self.udp_servers
+    }
     
     /// Lista todas as funções ativas (de categorias carregadas)
     pub fn list_active_functions(&self) -> Vec<String> {
@@ -0,0 +1,699 @@
+//! Subprocessos com pipes: `spawn` e as funções `process_*`.
+//!
+//! Cada pipe de saída (stdout/stderr) é lido por uma thread própria para um
+//! buffer, então o filho nunca trava com o pipe cheio, mesmo que o script só
+//! leia um dos dois ou só chame `process_wait`. O script consome o buffer aos
+//! poucos (`process_read`, `process_read_line`) ou de uma vez no `wait`.
+//! Processos são expostos como objetos `{ _type: "process", id, pid, command }`,
+//! registrados no `NativeModuleManager` de quem os criou; as funções só aceitam
+//! esse objeto e todas exigem `--allow-exec`.
+
+use crate::errors::RuntimeError;
+use crate::heap::{Heap, HeapId, ManagedObject};
+use crate::interpreter::Value;
+use crate::native_modules::{NativeFunction, NativeModuleManager};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+static NEXT_PROCESS_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Intervalo entre verificações de término (`wait`, timeouts)
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// Tamanho máximo de cada leitura dos pipes
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Sinais aceitos por nome em `process_kill` e devolvidos em `signal`
+#[cfg(unix)]
+const SIGNALS: &[(&str, i32)] = &[
+    ("SIGHUP", libc::SIGHUP),
+    ("SIGINT", libc::SIGINT),
+    ("SIGQUIT", libc::SIGQUIT),
+    ("SIGABRT", libc::SIGABRT),
+    ("SIGKILL", libc::SIGKILL),
+    ("SIGUSR1", libc::SIGUSR1),
+    ("SIGSEGV", libc::SIGSEGV),
+    ("SIGUSR2", libc::SIGUSR2),
+    ("SIGPIPE", libc::SIGPIPE),
+    ("SIGALRM", libc::SIGALRM),
+    ("SIGTERM", libc::SIGTERM),
+    ("SIGCONT", libc::SIGCONT),
+    ("SIGSTOP", libc::SIGSTOP),
+];
+
+pub fn register_process_functions(functions: &mut HashMap<String, NativeFunction>) {
+    functions.insert("spawn".to_string(), native_spawn);
+    functions.insert("process_write".to_string(), native_process_write);
+    functions.insert("process_close_stdin".to_string(), native_process_close_stdin);
+    functions.insert("process_read".to_string(), native_process_read);
+    functions.insert("process_read_stderr".to_string(), native_process_read_stderr);
+    functions.insert("process_read_line".to_string(), native_process_read_line);
+    functions.insert("process_wait".to_string(), native_process_wait);
+    functions.insert("process_kill".to_string(), native_process_kill);
+    functions.insert("process_status".to_string(), native_process_status);
+}
+
+/// Saída de um pipe acumulada pela thread leitora
+#[derive(Default)]
+struct OutputState {
+    buffer: Vec<u8>,
+    eof: bool,
+    /// Repassado como stdin de outro processo: o script não lê mais
+    forwarded: bool,
+}
+
+#[derive(Default)]
+struct OutputPipe {
+    state: Mutex<OutputState>,
+    ready: Condvar,
+}
+
+impl OutputPipe {
+    /// Inicia a thread que copia `source` para o buffer até o fim
+    fn start(source: impl Read + Send + 'static) -> Arc<Self> {
+        let pipe = Arc::new(OutputPipe::default());
+        let writer = pipe.clone();
+        thread::spawn(move || {
+            let mut source = source;
+            let mut chunk = vec![0u8; CHUNK_SIZE];
+            loop {
+                let read = source.read(&mut chunk).unwrap_or(0);
+                let mut state = writer.state.lock().unwrap();
+                if read == 0 {
+                    state.eof = true;
+                } else {
+                    state.buffer.extend_from_slice(&chunk[..read]);
+                }
+                writer.ready.notify_all();
+                if state.eof {
+                    break;
+                }
+            }
+        });
+        pipe
+    }
+
+    /// Espera até haver dados, o fim do pipe ou o prazo
+    fn wait_for<'a>(
+        &'a self,
+        deadline: Option<Instant>,
+        mut done: impl FnMut(&OutputState) -> bool,
+    ) -> std::sync::MutexGuard<'a, OutputState> {
+        let mut state = self.state.lock().unwrap();
+        while !done(&state) && !state.eof {
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break;
+                    }
+                    state = self.ready.wait_timeout(state, deadline - now).unwrap().0;
+                }
+                None => state = self.ready.wait(state).unwrap(),
+            }
+        }
+        state
+    }
+}
+
+/// Remove até `len` bytes do buffer como texto. Uma sequência UTF-8 cortada no
+/// fim fica para a próxima leitura, a menos que o pipe já tenha terminado.
+fn take_text(state: &mut OutputState, len: usize) -> String {
+    let mut end = len.min(state.buffer.len());
+    if let Err(e) = std::str::from_utf8(&state.buffer[..end]) {
+        if e.error_len().is_none() && !(state.eof && end == state.buffer.len()) {
+            end = e.valid_up_to();
+        }
+    }
+    let bytes: Vec<u8> = state.buffer.drain(..end).collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Processos de um interpreter, por id. A entrada sai no `process_wait`, que
+/// coleta o status e a saída restante e guarda o status final em `exit` no
+/// objeto do processo.
+#[derive(Default)]
+pub(crate) struct Processes {
+    entries: Mutex<HashMap<String, Arc<Process>>>,
+}
+
+impl Processes {
+    fn insert(&self, process: Arc<Process>) {
+        self.entries.lock().unwrap().insert(process.id.clone(), process);
+    }
+
+    fn get(&self, id: &str) -> Option<Arc<Process>> {
+        self.entries.lock().unwrap().get(id).cloned()
+    }
+
+    fn remove(&self, id: &str) {
+        self.entries.lock().unwrap().remove(id);
+    }
+}
+
+struct Process {
+    id: String,
+    pid: u32,
+    command: String,
+    child: Mutex<Child>,
+    stdin: Mutex<Option<ChildStdin>>,
+    stdout: Option<Arc<OutputPipe>>,
+    stderr: Option<Arc<OutputPipe>>,
+    status: Mutex<Option<ExitStatus>>,
+    deadline: Option<Instant>,
+    timed_out: AtomicBool,
+}
+
+impl Process {
+    /// Status de saída, se o processo já terminou. Mata o processo quando o
+    /// `timeout_ms` do `spawn` venceu.
+    fn poll(&self) -> Result<Option<ExitStatus>, RuntimeError> {
+        let mut status = self.status.lock().unwrap();
+        if status.is_none() {
+            let mut child = self.child.lock().unwrap();
+            *status = child.try_wait().map_err(|e| self.error("verificar", e))?;
+            if status.is_none() && self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                self.timed_out.store(true, Ordering::SeqCst);
+                let _ = child.kill();
+                *status = Some(child.wait().map_err(|e| self.error("esperar", e))?);
+            }
+        }
+        Ok(*status)
+    }
+
+    fn error(&self, action: &str, e: std::io::Error) -> RuntimeError {
+        RuntimeError::SystemError(format!("Erro ao {} o processo '{}' ({}): {}", action, self.command, self.pid, e))
+    }
+
+    fn status_properties(&self, status: Option<ExitStatus>) -> HashMap<String, Value> {
+        let mut properties = HashMap::new();
+        properties.insert("id".to_string(), Value::String(self.id.clone()));
+        properties.insert("pid".to_string(), Value::Number(self.pid as f64));
+        properties.insert("running".to_string(), Value::Bool(status.is_none()));
+        let (code, signal) = match status {
+            Some(status) => (status.code().map_or(Value::Null, |code| Value::Number(code as f64)), exit_signal(&status)),
+            None => (Value::Null, Value::Null),
+        };
+        properties.insert("success".to_string(), Value::Bool(status.is_some_and(|s| s.success())));
+        properties.insert("code".to_string(), code);
+        properties.insert("signal".to_string(), signal);
+        properties.insert("timed_out".to_string(), Value::Bool(self.timed_out.load(Ordering::SeqCst)));
+        properties
+    }
+
+    fn output(&self, stream: Stream, function: &str) -> Result<&Arc<OutputPipe>, RuntimeError> {
+        let pipe = match stream {
+            Stream::Stdout => &self.stdout,
+            Stream::Stderr => &self.stderr,
+        };
+        let pipe = pipe.as_ref().ok_or_else(|| {
+            RuntimeError::SystemError(format!("{}: {} do processo não foi aberto com \"pipe\"", function, stream.name()))
+        })?;
+        if pipe.state.lock().unwrap().forwarded {
+            return Err(RuntimeError::SystemError(format!(
+                "{}: {} do processo está ligado a outro processo",
+                function,
+                stream.name()
+            )));
+        }
+        Ok(pipe)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn name(self) -> &'static str {
+        match self {
+            Stream::Stdout => "stdout",
+            Stream::Stderr => "stderr",
+        }
+    }
+}
+
+#[cfg(unix)]
+fn exit_signal(status: &ExitStatus) -> Value {
+    use std::os::unix::process::ExitStatusExt;
+    match status.signal() {
+        Some(number) => match SIGNALS.iter().find(|(_, n)| *n == number) {
+            Some((name, _)) => Value::String(name.to_string()),
+            None => Value::Number(number as f64),
+        },
+        None => Value::Null,
+    }
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: &ExitStatus) -> Value {
+    Value::Null
+}
+
+/// Processo ainda registrado ou, depois do `process_wait`, o objeto com o
+/// status final em `exit`
+enum ProcessRef {
+    Live(Arc<Process>),
+    Finished(HeapId),
+}
+
+/// Resolve o objeto devolvido por `spawn` entre os processos deste interpreter
+fn process_ref(value: Option<&Value>, manager: &NativeModuleManager, heap: &Heap, function: &str) -> Result<ProcessRef, RuntimeError> {
+    if !manager.allow_exec() {
+        return Err(RuntimeError::SystemError(format!("{} está desabilitado. Use --allow-exec para habilitar.", function)));
+    }
+    let (handle, properties) = match value {
+        Some(Value::Object(obj)) => match heap.get(*obj) {
+            Some(ManagedObject::Object { properties, .. }) => (*obj, properties),
+            _ => return Err(RuntimeError::HeapError("Objeto de processo não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError(format!("{}: esperado um processo", function))),
+        None => return Err(RuntimeError::ArgumentError(format!("{}: processo ausente", function))),
+    };
+    let id = match (properties.get("_type"), properties.get("id")) {
+        (Some(Value::String(kind)), Some(Value::String(id))) if kind == "process" => id,
+        _ => return Err(RuntimeError::TypeError(format!("{}: objeto não é um processo", function))),
+    };
+    if let Some(process) = manager.processes().get(id) {
+        return Ok(ProcessRef::Live(process));
+    }
+    match properties.get("exit") {
+        Some(Value::Object(_)) => Ok(ProcessRef::Finished(handle)),
+        _ => Err(RuntimeError::SystemError(format!("Processo '{}' não encontrado", id))),
+    }
+}
+
+/// Processo em execução ou ainda não esperado com `process_wait`
+fn process_arg(value: Option<&Value>, manager: &NativeModuleManager, heap: &Heap, function: &str) -> Result<Arc<Process>, RuntimeError> {
+    match process_ref(value, manager, heap, function)? {
+        ProcessRef::Live(process) => Ok(process),
+        ProcessRef::Finished(_) => Err(RuntimeError::SystemError(format!(
+            "{}: o processo já foi finalizado com process_wait",
+            function
+        ))),
+    }
+}
+
+/// Propriedades de `exit`, guardadas no objeto do processo pelo `process_wait`
+fn finished_properties(handle: HeapId, heap: &Heap) -> HashMap<String, Value> {
+    let property = |id: HeapId, name: &str| match heap.get(id) {
+        Some(ManagedObject::Object { properties, .. }) => properties.get(name).cloned(),
+        _ => None,
+    };
+    let mut properties = match property(handle, "exit").and_then(|exit| match exit {
+        Value::Object(exit) => heap.get(exit),
+        _ => None,
+    }) {
+        Some(ManagedObject::Object { properties, .. }) => properties.clone(),
+        _ => HashMap::new(),
+    };
+    for name in ["id", "pid"] {
+        properties.insert(name.to_string(), property(handle, name).unwrap_or(Value::Null));
+    }
+    properties.insert("running".to_string(), Value::Bool(false));
+    properties
+}
+
+/// Timeout em milissegundos; `null` espera sem limite
+fn timeout_arg(value: Option<&Value>, function: &str) -> Result<Option<Instant>, RuntimeError> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(ms)) if *ms >= 0.0 => Ok(Some(Instant::now() + Duration::from_millis(*ms as u64))),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: timeout_ms deve ser um número não negativo", function))),
+    }
+}
+
+fn string_list(value: Option<&Value>, heap: &Heap) -> Result<Vec<String>, RuntimeError> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(id)) => match heap.get(*id) {
+            Some(ManagedObject::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => Ok(s.clone()),
+                    Value::Number(n) => Ok(n.to_string()),
+                    _ => Err(RuntimeError::TypeError("spawn: argumentos devem ser strings".to_string())),
+                })
+                .collect(),
+            _ => Err(RuntimeError::HeapError("Array de argumentos não encontrado".to_string())),
+        },
+        Some(_) => Err(RuntimeError::TypeError("spawn: argumentos devem ser um array".to_string())),
+    }
+}
+
+/// Destino de um stdio em `spawn`: "pipe", "inherit" ou "null"
+fn stdio_option(value: Option<&Value>, default: &str, name: &str) -> Result<(Stdio, bool), RuntimeError> {
+    let mode = match value {
+        None | Some(Value::Null) => default,
+        Some(Value::String(mode)) => mode.as_str(),
+        Some(_) => return Err(RuntimeError::TypeError(format!("spawn: {} deve ser \"pipe\", \"inherit\" ou \"null\"", name))),
+    };
+    match mode {
+        "pipe" => Ok((Stdio::piped(), true)),
+        "inherit" => Ok((Stdio::inherit(), false)),
+        "null" => Ok((Stdio::null(), false)),
+        _ => Err(RuntimeError::ArgumentError(format!(
+            "spawn: {} inválido \"{}\" (use \"pipe\", \"inherit\" ou \"null\")",
+            name, mode
+        ))),
+    }
+}
+
+/// Copia o stdout de `source` para `stdin` até o fim, depois fecha `stdin`
+fn forward(source: Arc<OutputPipe>, mut stdin: ChildStdin) {
+    thread::spawn(move || loop {
+        let (chunk, eof) = {
+            let mut state = source.wait_for(None, |state| !state.buffer.is_empty());
+            (std::mem::take(&mut state.buffer), state.eof)
+        };
+        if stdin.write_all(&chunk).is_err() || eof {
+            break;
+        }
+    });
+}
+
+/// spawn(comando, args?, opcoes?) -> processo
+/// Executa o programa diretamente (sem shell). Opções:
+/// - `cwd`: diretório de trabalho
+/// - `env`: variáveis a definir (`null` remove); `clear_env: true` parte de um ambiente vazio
+/// - `stdin`: "null" (padrão), "pipe", "inherit" ou outro processo (liga o stdout dele)
+/// - `stdout`/`stderr`: "pipe" (padrão), "inherit" ou "null"
+/// - `timeout_ms`: mata o processo (SIGKILL) se ainda estiver rodando após o prazo
+fn native_spawn(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    if !manager.allow_exec() {
+        return Err(RuntimeError::SystemError("spawn está desabilitado. Use --allow-exec para habilitar.".to_string()));
+    }
+    let program = match args.first() {
+        Some(Value::String(program)) if !program.is_empty() => program.clone(),
+        Some(_) => return Err(RuntimeError::TypeError("spawn: comando deve ser uma string não vazia".to_string())),
+        None => return Err(RuntimeError::ArgumentError("spawn: comando ausente".to_string())),
+    };
+    let arguments = string_list(args.get(1), heap)?;
+    let options = match args.get(2) {
+        None | Some(Value::Null) => HashMap::new(),
+        Some(Value::Object(id)) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => properties.clone(),
+            _ => return Err(RuntimeError::HeapError("Objeto de opções não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError("spawn: opções devem ser um objeto".to_string())),
+    };
+
+    let mut command = Command::new(&program);
+    command.args(&arguments);
+    match options.get("cwd") {
+        None | Some(Value::Null) => {}
+        Some(Value::String(dir)) => {
+            command.current_dir(dir);
+        }
+        Some(_) => return Err(RuntimeError::TypeError("spawn: cwd deve ser uma string".to_string())),
+    }
+    if matches!(options.get("clear_env"), Some(Value::Bool(true))) {
+        command.env_clear();
+    }
+    match options.get("env") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(id)) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => {
+                for (key, value) in properties {
+                    match value {
+                        Value::Null => {
+                            command.env_remove(key);
+                        }
+                        Value::String(s) => {
+                            command.env(key, s);
+                        }
+                        Value::Number(n) => {
+                            command.env(key, n.to_string());
+                        }
+                        Value::Bool(b) => {
+                            command.env(key, b.to_string());
+                        }
+                        _ => return Err(RuntimeError::TypeError(format!("spawn: valor inválido para a variável '{}'", key))),
+                    }
+                }
+            }
+            _ => return Err(RuntimeError::HeapError("Objeto de ambiente não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError("spawn: env deve ser um objeto".to_string())),
+    }
+
+    // stdin ligado ao stdout de outro processo
+    let upstream = match options.get("stdin") {
+        Some(value @ Value::Object(_)) => {
+            let source = process_arg(Some(value), manager, heap, "spawn")?;
+            let pipe = source.output(Stream::Stdout, "spawn")?.clone();
+            Some(pipe)
+        }
+        _ => None,
+    };
+    let stdin_piped = match &upstream {
+        Some(_) => {
+            command.stdin(Stdio::piped());
+            true
+        }
+        None => {
+            let (stdio, piped) = stdio_option(options.get("stdin"), "null", "stdin")?;
+            command.stdin(stdio);
+            piped
+        }
+    };
+    let (stdout, stdout_piped) = stdio_option(options.get("stdout"), "pipe", "stdout")?;
+    let (stderr, stderr_piped) = stdio_option(options.get("stderr"), "pipe", "stderr")?;
+    command.stdout(stdout).stderr(stderr);
+    let deadline = match options.get("timeout_ms") {
+        None | Some(Value::Null) => None,
+        value => timeout_arg(value, "spawn")?,
+    };
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| RuntimeError::SystemError(format!("Erro ao iniciar '{}': {}", program, e)))?;
+    let mut stdin = if stdin_piped { child.stdin.take() } else { None };
+    if let Some(source) = upstream {
+        source.state.lock().unwrap().forwarded = true;
+        if let Some(pipe) = stdin.take() {
+            forward(source, pipe);
+        }
+    }
+
+    let id = format!("proc_{}", NEXT_PROCESS_ID.fetch_add(1, Ordering::SeqCst));
+    let process = Arc::new(Process {
+        id: id.clone(),
+        pid: child.id(),
+        command: program.clone(),
+        stdout: child.stdout.take().filter(|_| stdout_piped).map(OutputPipe::start),
+        stderr: child.stderr.take().filter(|_| stderr_piped).map(OutputPipe::start),
+        stdin: Mutex::new(stdin),
+        child: Mutex::new(child),
+        status: Mutex::new(None),
+        deadline,
+        timed_out: AtomicBool::new(false),
+    });
+    if deadline.is_some() {
+        // Aplica o timeout mesmo que o script nunca consulte o processo
+        let watched = process.clone();
+        thread::spawn(move || {
+            while let Ok(None) = watched.poll() {
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+    }
+    manager.processes().insert(process.clone());
+
+    let mut properties = HashMap::new();
+    properties.insert("_type".to_string(), Value::String("process".to_string()));
+    properties.insert("id".to_string(), Value::String(id));
+    properties.insert("pid".to_string(), Value::Number(process.pid as f64));
+    properties.insert("command".to_string(), Value::String(program));
+    Ok(Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() })))
+}
+
+/// process_write(processo, dados) -> bytes escritos
+/// Escreve no stdin (aberto com `stdin: "pipe"`); aceita string ou bytes
+fn native_process_write(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let process = process_arg(args.first(), manager, heap, "process_write")?;
+    let data = super::tcp::data_arg(args.get(1), heap, "process_write")?;
+    let mut stdin = process.stdin.lock().unwrap();
+    let pipe = stdin.as_mut().ok_or_else(|| {
+        RuntimeError::SystemError("process_write: stdin do processo está fechado ou não foi aberto com \"pipe\"".to_string())
+    })?;
+    pipe.write_all(&data)
+        .and_then(|_| pipe.flush())
+        .map_err(|e| process.error("escrever para", e))?;
+    Ok(Value::Number(data.len() as f64))
+}
+
+/// process_close_stdin(processo) -> null
+/// Fecha o stdin, sinalizando fim de entrada ao filho
+fn native_process_close_stdin(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    if let ProcessRef::Live(process) = process_ref(args.first(), manager, heap, "process_close_stdin")? {
+        process.stdin.lock().unwrap().take();
+    }
+    Ok(Value::Null)
+}
+
+fn read_available(args: &[Value], manager: &NativeModuleManager, heap: &Heap, stream: Stream, function: &str) -> Result<Value, RuntimeError> {
+    let ProcessRef::Live(process) = process_ref(args.first(), manager, heap, function)? else {
+        // O process_wait já devolveu o resto da saída
+        return Ok(Value::Null);
+    };
+    let deadline = timeout_arg(args.get(1), function)?;
+    let pipe = process.output(stream, function)?;
+    let mut state = pipe.wait_for(deadline, |state| !state.buffer.is_empty());
+    if state.buffer.is_empty() && state.eof {
+        return Ok(Value::Null);
+    }
+    let len = state.buffer.len();
+    Ok(Value::String(take_text(&mut state, len)))
+}
+
+/// process_read(processo, timeout_ms?) -> string | null
+/// Devolve o que já saiu no stdout, esperando até haver algo. `null` quando
+/// o stdout terminou; `""` se o timeout venceu sem saída.
+fn native_process_read(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    read_available(args, manager, heap, Stream::Stdout, "process_read")
+}
+
+/// process_read_stderr(processo, timeout_ms?) -> string | null
+/// Como `process_read`, para o stderr
+fn native_process_read_stderr(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    read_available(args, manager, heap, Stream::Stderr, "process_read_stderr")
+}
+
+/// process_read_line(processo) -> string | null
+/// Próxima linha do stdout sem o terminador; no fim, o resto sem `\n` ou `null`
+fn native_process_read_line(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let ProcessRef::Live(process) = process_ref(args.first(), manager, heap, "process_read_line")? else {
+        return Ok(Value::Null);
+    };
+    let pipe = process.output(Stream::Stdout, "process_read_line")?;
+    let mut state = pipe.wait_for(None, |state| state.buffer.contains(&b'\n'));
+    match state.buffer.iter().position(|b| *b == b'\n') {
+        Some(newline) => {
+            let mut line = take_text(&mut state, newline + 1);
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            Ok(Value::String(line))
+        }
+        None if state.buffer.is_empty() => Ok(Value::Null),
+        None => {
+            let len = state.buffer.len();
+            Ok(Value::String(take_text(&mut state, len)))
+        }
+    }
+}
+
+/// process_wait(processo, timeout_ms?) -> { code, signal, success, timed_out, stdout, stderr } | null
+/// Fecha o stdin, espera o processo terminar e devolve o status com a saída
+/// ainda não lida. Com `timeout_ms`, devolve `null` se ele ainda estiver rodando.
+/// Ao terminar, o processo sai do registro e o status fica em `exit` no objeto
+/// do processo; esperar de novo devolve esse status com `stdout`/`stderr` nulos.
+fn native_process_wait(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let process = match process_ref(args.first(), manager, heap, "process_wait")? {
+        ProcessRef::Live(process) => process,
+        ProcessRef::Finished(handle) => {
+            let mut properties = finished_properties(handle, heap);
+            properties.remove("running");
+            properties.insert("stdout".to_string(), Value::Null);
+            properties.insert("stderr".to_string(), Value::Null);
+            return Ok(Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() })));
+        }
+    };
+    let deadline = timeout_arg(args.get(1), "process_wait")?;
+    process.stdin.lock().unwrap().take();
+
+    let status = loop {
+        if let Some(status) = process.poll()? {
+            break status;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Ok(Value::Null);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let mut properties = process.status_properties(Some(status));
+    for (key, pipe) in [("stdout", &process.stdout), ("stderr", &process.stderr)] {
+        // Espera o fim do pipe para não perder a saída final
+        let text = match pipe {
+            Some(pipe) if !pipe.state.lock().unwrap().forwarded => {
+                let mut state = pipe.wait_for(None, |_| false);
+                let len = state.buffer.len();
+                Value::String(take_text(&mut state, len))
+            }
+            _ => Value::Null,
+        };
+        properties.insert(key.to_string(), text);
+    }
+
+    let mut exit = properties.clone();
+    for key in ["id", "pid", "running", "stdout", "stderr"] {
+        exit.remove(key);
+    }
+    let exit = heap.allocate(ManagedObject::Object { properties: exit, methods: HashMap::new() });
+    if let Some(Value::Object(handle)) = args.first() {
+        if let Some(ManagedObject::Object { properties, .. }) = heap.get_mut(*handle) {
+            properties.insert("exit".to_string(), Value::Object(exit));
+        }
+    }
+    manager.processes().remove(&process.id);
+    Ok(Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() })))
+}
+
+/// process_kill(processo, sinal?) -> bool
+/// Envia o sinal ("SIGTERM" por padrão; nome ou número). Devolve `false` se o
+/// processo já tinha terminado. Fora do Unix, sempre encerra o processo.
+fn native_process_kill(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let ProcessRef::Live(process) = process_ref(args.first(), manager, heap, "process_kill")? else {
+        return Ok(Value::Bool(false));
+    };
+    #[cfg(unix)]
+    let signal = match args.get(1) {
+        None | Some(Value::Null) => libc::SIGTERM,
+        Some(Value::Number(n)) if n.fract() == 0.0 && *n > 0.0 => *n as i32,
+        Some(Value::String(name)) => {
+            let wanted = name.to_uppercase();
+            let wanted = if wanted.starts_with("SIG") { wanted } else { format!("SIG{}", wanted) };
+            SIGNALS
+                .iter()
+                .find(|(known, _)| *known == wanted)
+                .map(|(_, number)| *number)
+                .ok_or_else(|| RuntimeError::ArgumentError(format!("process_kill: sinal desconhecido '{}'", name)))?
+        }
+        Some(_) => return Err(RuntimeError::TypeError("process_kill: sinal deve ser um nome ou número".to_string())),
+    };
+
+    // O lock do status impede que o pid seja reaproveitado entre a checagem e o envio
+    let status = process.status.lock().unwrap();
+    let mut child = process.child.lock().unwrap();
+    if status.is_some() || child.try_wait().map_err(|e| process.error("verificar", e))?.is_some() {
+        return Ok(Value::Bool(false));
+    }
+    #[cfg(unix)]
+    {
+        if unsafe { libc::kill(process.pid as libc::pid_t, signal) } != 0 {
+            return Err(process.error("sinalizar", std::io::Error::last_os_error()));
+        }
+    }
+    #[cfg(not(unix))]
+    child.kill().map_err(|e| process.error("encerrar", e))?;
+    Ok(Value::Bool(true))
+}
+
+/// process_status(processo) -> { id, pid, running, code, signal, success, timed_out }
+/// Consulta sem bloquear
+fn native_process_status(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let properties = match process_ref(args.first(), manager, heap, "process_status")? {
+        ProcessRef::Live(process) => process.status_properties(process.poll()?),
+        ProcessRef::Finished(handle) => finished_properties(handle, heap),
+    };
+    Ok(Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() })))
+}
@@ -0,0 +1,247 @@
+//! Ponte entre valores Dryad e o modelo de dados do serde.
+//!
+//! Usada pelos codecs de `#<encode_decode>` que não passam por
+//! `serde_json::Value`: `SerializeValue` percorre o heap durante a
+//! serialização (YAML, TOML e CBOR) e `ValueSeed` aloca os valores lidos
+//! diretamente no heap, sem representação intermediária (YAML e TOML).
+
+use crate::collections::{json_key, MapEntries, MapKey};
+use crate::heap::{Heap, ManagedObject};
+use crate::interpreter::Value;
+use num_traits::ToPrimitive;
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Profundidade máxima ao serializar (protege contra estruturas cíclicas)
+const MAX_DEPTH: usize = 512;
+
+/// Chave com que o `toml` representa datas e horas no modelo do serde
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+/// Valor Dryad serializável por qualquer formato serde.
+///
+/// Números inteiros viram inteiros do formato (`1`, não `1.0`); `decimal` vira
+/// float e `bigint` fora de 128 bits vira string. Objetos são escritos com as
+/// chaves em ordem alfabética; `Map` mantém a ordem de inserção.
+pub struct SerializeValue<'a> {
+    value: &'a Value,
+    heap: &'a Heap,
+    depth: usize,
+}
+
+impl<'a> SerializeValue<'a> {
+    pub fn new(value: &'a Value, heap: &'a Heap) -> Self {
+        SerializeValue { value, heap, depth: 0 }
+    }
+
+    fn child(&self, value: &'a Value) -> Result<Self, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("estrutura muito profunda ou cíclica".to_string());
+        }
+        Ok(SerializeValue { value, heap: self.heap, depth: self.depth + 1 })
+    }
+}
+
+impl Serialize for SerializeValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let heap = self.heap;
+        let child = |value| self.child(value).map_err(ser::Error::custom);
+        let missing = || ser::Error::custom("referência inválida no heap");
+
+        match self.value {
+            Value::Null => serializer.serialize_none(),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::Number(n) if n.fract() == 0.0 && n.abs() <= crate::numeric::MAX_SAFE_INTEGER as f64 => {
+                serializer.serialize_i64(*n as i64)
+            }
+            Value::Number(n) => serializer.serialize_f64(*n),
+            Value::Int(i) => serializer.serialize_i64(*i),
+            Value::BigInt(b) => match (b.to_i64(), b.to_u64(), b.to_i128()) {
+                (Some(i), _, _) => serializer.serialize_i64(i),
+                (_, Some(u), _) => serializer.serialize_u64(u),
+                (_, _, Some(i)) => serializer.serialize_i128(i),
+                _ => serializer.serialize_str(&b.to_string()),
+            },
+            Value::Decimal(d) => serializer.serialize_f64(d.to_f64()),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) | Value::Regex(_) => {
+                serializer.serialize_str(&self.value.to_string())
+            }
+            Value::Array(id) | Value::Tuple(id) => match heap.get(*id) {
+                Some(ManagedObject::Array(items)) | Some(ManagedObject::Tuple(items)) => {
+                    let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                    for item in items {
+                        seq.serialize_element(&child(item)?)?;
+                    }
+                    seq.end()
+                }
+                _ => Err(missing()),
+            },
+            Value::Set(id) => match heap.get(*id) {
+                Some(ManagedObject::Set(entries)) => {
+                    let mut seq = serializer.serialize_seq(Some(entries.len()))?;
+                    for item in entries.values() {
+                        seq.serialize_element(&child(item)?)?;
+                    }
+                    seq.end()
+                }
+                _ => Err(missing()),
+            },
+            Value::Object(id) => match heap.get(*id) {
+                Some(ManagedObject::Object { properties, .. }) => {
+                    let mut keys: Vec<&String> = properties.keys().collect();
+                    keys.sort();
+                    let mut map = serializer.serialize_map(Some(keys.len()))?;
+                    for key in keys {
+                        map.serialize_entry(key, &child(&properties[key])?)?;
+                    }
+                    map.end()
+                }
+                _ => Err(missing()),
+            },
+            Value::Map(id) => match heap.get(*id) {
+                Some(ManagedObject::Map(entries)) => {
+                    let mut map = serializer.serialize_map(Some(entries.len()))?;
+                    for (key, value) in entries.values() {
+                        map.serialize_entry(&child(key)?, &child(value)?)?;
+                    }
+                    map.end()
+                }
+                _ => Err(missing()),
+            },
+            other => Err(ser::Error::custom(format!("tipo não suportado: {}", other.to_string()))),
+        }
+    }
+}
+
+/// Lê um valor de qualquer formato serde direto para o heap.
+///
+/// Mapeamentos viram objetos; com `ordered` viram `Map`, que preserva a ordem
+/// das chaves do documento e chaves que não são strings.
+pub struct ValueSeed<'h> {
+    pub heap: &'h mut Heap,
+    pub ordered: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for ValueSeed<'_> {
+    type Value = Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ValueSeed<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("um valor Dryad")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(crate::numeric::from_i64(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(match i64::try_from(v) {
+            Ok(v) => crate::numeric::from_i64(v),
+            Err(_) => Value::BigInt(v.into()),
+        })
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Value, E> {
+        Ok(match i64::try_from(v) {
+            Ok(v) => crate::numeric::from_i64(v),
+            Err(_) => Value::BigInt(v.into()),
+        })
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Value, E> {
+        Ok(match i64::try_from(v) {
+            Ok(v) => crate::numeric::from_i64(v),
+            Err(_) => Value::BigInt(v.into()),
+        })
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Number(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    /// Strings binárias (CBOR) viram arrays de bytes
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Value, E> {
+        let bytes = v.iter().map(|b| Value::Number(*b as f64)).collect();
+        Ok(Value::Array(self.heap.allocate(ManagedObject::Array(bytes))))
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(item) = seq.next_element_seed(ValueSeed { heap: &mut *self.heap, ordered: self.ordered })? {
+            items.push(item);
+        }
+        Ok(Value::Array(self.heap.allocate(ManagedObject::Array(items))))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let ordered = self.ordered;
+        let mut properties = HashMap::new();
+        let mut entries = MapEntries::new();
+
+        while let Some(key) = map.next_key_seed(ValueSeed { heap: &mut *self.heap, ordered })? {
+            let value = map.next_value_seed(ValueSeed { heap: &mut *self.heap, ordered })?;
+            match key {
+                // Datas do TOML chegam como um mapa de uma entrada com o texto ISO
+                Value::String(ref k) if k == TOML_DATETIME_KEY => return Ok(value),
+                _ if ordered => {
+                    let normalized = MapKey::of(&key, self.heap).map_err(de::Error::custom)?;
+                    entries.insert(normalized, (key, value));
+                }
+                _ => {
+                    let name = json_key(&key).unwrap_or_else(|| key.to_string());
+                    properties.insert(name, value);
+                }
+            }
+        }
+
+        Ok(if ordered {
+            Value::Map(self.heap.allocate(ManagedObject::Map(entries)))
+        } else {
+            Value::Object(self.heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+        })
+    }
+
+    /// Valores com tag (`!tag` no YAML, tags CBOR) são lidos sem a tag
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+        let (_, variant) = data.variant::<de::IgnoredAny>()?;
+        variant.newtype_variant_seed(self)
+    }
+}
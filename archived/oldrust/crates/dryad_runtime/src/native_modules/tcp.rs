@@ -1,301 +1,1143 @@
-use crate::interpreter::Value;
-use crate::native_modules::NativeFunction;
+//! Sockets TCP: servidores com handlers Dryad, clientes e conexões.
+//!
+//! Threads de I/O aceitam conexões e leem dados; a chamada dos handlers
+//! precisa do interpreter, então cada evento entra na fila do `TcpServers`
+//! do interpreter dono do servidor e é executado por ele entre as instruções
+//! do script (como no servidor HTTP).
+//! Conexões são expostas ao script como objetos `{ id, remote_addr, ... }`
+//! e todas as funções `tcp_*` de conexão aceitam o objeto ou o `id`.
+//! Servidores e conexões cliente podem usar TLS (veja `tls.rs`).
+
 use crate::errors::RuntimeError;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use crate::heap::{Heap, ManagedObject};
+use crate::interpreter::Value;
+use crate::native_modules::tls::{self, ClientTls, NetStream, ServerTls, TlsInfo, TlsStream};
+use crate::native_modules::{NativeFunction, NativeModuleManager};
 use lazy_static::lazy_static;
-use std::net::{TcpListener, TcpStream, SocketAddr};
-use std::io::{Read, Write, BufRead, BufReader};
-use std::thread;
-use std::time::Duration;
-use std::sync::mpsc;
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 lazy_static! {
-    static ref TCP_SERVERS: Arc<Mutex<HashMap<String, ServerInstance>>> = Arc::new(Mutex::new(HashMap::new()));
-    static ref TCP_CLIENTS: Arc<Mutex<HashMap<String, ClientInstance>>> = Arc::new(Mutex::new(HashMap::new()));
+    static ref TCP_CLIENTS: Mutex<HashMap<String, ClientInstance>> = Mutex::new(HashMap::new());
+    static ref CONNECTIONS: Mutex<HashMap<String, Arc<Connection>>> = Mutex::new(HashMap::new());
+}
+
+/// Servidores TCP de um interpreter e os eventos que precisam dele (handlers
+/// Dryad). As threads de I/O guardam um `Arc` deste registro, então cada
+/// evento só chega ao interpreter dono do servidor.
+#[derive(Default)]
+pub(crate) struct TcpServers {
+    servers: Mutex<HashMap<String, ServerInstance>>,
+    pending: Mutex<Vec<PendingEvent>>,
+    /// Acorda `tcp_server_wait` quando chega um evento
+    ready: Condvar,
+}
+
+impl TcpServers {
+    fn with_server<T>(&self, server_id: &str, f: impl FnOnce(&mut ServerInstance) -> Result<T, RuntimeError>) -> Result<T, RuntimeError> {
+        let mut servers = self.servers.lock().unwrap();
+        let server = servers
+            .get_mut(server_id)
+            .ok_or_else(|| RuntimeError::ArgumentError(format!("TCP Server '{}' não encontrado", server_id)))?;
+        f(server)
+    }
+}
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Intervalo em que a thread de accept verifica se o servidor está parando
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Tamanho máximo de cada leitura do socket (e de cada evento "data")
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Tamanho máximo de uma linha lida por `tcp_read_line`
+const MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// Handlers Dryad de um servidor
+#[derive(Clone, Default)]
+struct Handlers {
+    connection: Option<Value>,
+    data: Option<Value>,
+    close: Option<Value>,
 }
 
-#[derive(Clone)]
 struct ServerInstance {
     server_id: String,
     host: String,
     port: u16,
-    is_running: bool,
-    stop_sender: Option<mpsc::Sender<()>>,
     max_clients: usize,
+    handlers: Handlers,
+    /// Configuração de `tcp_server_tls`; as conexões passam pelo handshake
+    tls: Option<ServerTls>,
+    running: Option<RunningServer>,
+}
+
+struct RunningServer {
+    shutdown: Arc<AtomicBool>,
+    active: Arc<AtomicUsize>,
+    accept_thread: JoinHandle<()>,
 }
 
 struct ClientInstance {
     client_id: String,
     host: String,
     port: u16,
-    stream: Arc<Mutex<Option<TcpStream>>>,
-    timeout: Option<Duration>,
-}
-
-/// Função para inicializar servidor TCP usando threads nativas
-fn start_tcp_server(server_id: String, host: String, port: u16, max_clients: usize, stop_receiver: mpsc::Receiver<()>) {
-    thread::spawn(move || {
-        let bind_address = format!("{}:{}", host, port);
-        
-        match TcpListener::bind(&bind_address) {
-            Ok(listener) => {
-                println!("🌍 TCP Server '{}' iniciado em {}", server_id, bind_address);
-                
-                if let Err(e) = listener.set_nonblocking(true) {
-                    eprintln!("❌ Erro ao configurar servidor não-bloqueante: {}", e);
-                    return;
+    timeout: Duration,
+    connection: Option<Arc<Connection>>,
+}
+
+/// Uma conexão TCP aberta, do lado servidor ou cliente
+pub struct Connection {
+    id: String,
+    server_id: Option<String>,
+    /// Socket da conexão (timeouts e shutdown, mesmo com TLS)
+    stream: TcpStream,
+    /// Sessão TLS sobre `stream`; leituras e escritas passam por ela
+    tls: Option<(Mutex<TlsStream>, TlsInfo)>,
+    remote_addr: String,
+    local_addr: String,
+    /// Bytes já lidos do socket e ainda não entregues ao script
+    buffer: Mutex<Vec<u8>>,
+    eof: AtomicBool,
+    open: AtomicBool,
+    nonblocking: AtomicBool,
+    /// Conexões de servidores com handler "data" são lidas pela thread de eventos
+    evented: bool,
+    /// Contador de conexões ativas do servidor dono da conexão
+    active: Option<Arc<AtomicUsize>>,
+}
+
+/// Resultado de esperar dados suficientes no buffer
+enum Fill {
+    Ready,
+    Eof,
+    /// Modo não bloqueante sem dados suficientes ainda
+    Pending,
+}
+
+impl Connection {
+    fn new(stream: NetStream, server_id: Option<String>, active: Option<Arc<AtomicUsize>>, evented: bool) -> io::Result<Arc<Self>> {
+        let (stream, tls) = match stream {
+            NetStream::Plain(stream) => (stream, None),
+            NetStream::Tls(tls) => {
+                let info = TlsInfo::of(&tls);
+                (tls.get_ref().try_clone()?, Some((Mutex::new(*tls), info)))
+            }
+        };
+        let remote_addr = stream.peer_addr()?.to_string();
+        let local_addr = stream.local_addr()?.to_string();
+        if let Some(active) = &active {
+            active.fetch_add(1, Ordering::SeqCst);
+        }
+        let connection = Arc::new(Connection {
+            id: format!("tcp_conn_{}", NEXT_CONNECTION_ID.fetch_add(1, Ordering::SeqCst)),
+            server_id,
+            stream,
+            tls,
+            remote_addr,
+            local_addr,
+            buffer: Mutex::new(Vec::new()),
+            eof: AtomicBool::new(false),
+            open: AtomicBool::new(true),
+            nonblocking: AtomicBool::new(false),
+            evented,
+            active,
+        });
+        CONNECTIONS.lock().unwrap().insert(connection.id.clone(), connection.clone());
+        Ok(connection)
+    }
+
+    /// Fecha o socket (uma única vez) e remove a conexão do registro
+    fn close(&self) {
+        if self.open.swap(false, Ordering::SeqCst) {
+            // close_notify só se ninguém estiver usando a sessão agora
+            if let Some(Ok(mut tls)) = self.tls.as_ref().map(|(tls, _)| tls.try_lock()) {
+                let _ = tls.shutdown();
+            }
+            let _ = self.stream.shutdown(Shutdown::Both);
+            if let Some(active) = &self.active {
+                active.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+        CONNECTIONS.lock().unwrap().remove(&self.id);
+    }
+
+    fn to_value(&self, heap: &mut Heap) -> Value {
+        let mut properties = HashMap::new();
+        properties.insert("id".to_string(), Value::String(self.id.clone()));
+        properties.insert("remote_addr".to_string(), Value::String(self.remote_addr.clone()));
+        properties.insert("local_addr".to_string(), Value::String(self.local_addr.clone()));
+        properties.insert(
+            "server_id".to_string(),
+            self.server_id.clone().map(Value::String).unwrap_or(Value::Null),
+        );
+        let tls = self.tls.as_ref().map_or(Value::Null, |(_, info)| info.to_value(heap));
+        properties.insert("tls".to_string(), tls);
+        Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+    }
+
+    fn read_raw(&self, buf: &mut [u8]) -> io::Result<usize> {
+        match &self.tls {
+            Some((tls, _)) => tls.lock().unwrap().read(buf),
+            None => (&self.stream).read(buf),
+        }
+    }
+
+    fn write_raw(&self, data: &[u8]) -> io::Result<usize> {
+        match &self.tls {
+            Some((tls, _)) => tls.lock().unwrap().write(data),
+            None => (&self.stream).write(data),
+        }
+    }
+
+    fn ensure_readable(&self) -> Result<(), RuntimeError> {
+        if !self.open.load(Ordering::SeqCst) {
+            return Err(RuntimeError::NetworkError(format!("Conexão '{}' está fechada", self.id)));
+        }
+        if self.evented {
+            return Err(RuntimeError::NetworkError(format!(
+                "Conexão '{}' é lida pelo handler \"data\" do servidor",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Lê do socket até `ready` aceitar o buffer, o fluxo terminar ou, no
+    /// modo não bloqueante, não haver mais dados disponíveis
+    fn fill_until(&self, buffer: &mut Vec<u8>, ready: impl Fn(&[u8]) -> bool) -> Result<Fill, RuntimeError> {
+        let mut chunk = vec![0u8; CHUNK_SIZE];
+        loop {
+            if ready(buffer) {
+                return Ok(Fill::Ready);
+            }
+            if self.eof.load(Ordering::SeqCst) {
+                return Ok(Fill::Eof);
+            }
+            match self.read_raw(&mut chunk) {
+                Ok(0) => self.eof.store(true, Ordering::SeqCst),
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && self.nonblocking.load(Ordering::SeqCst) => {
+                    return Ok(Fill::Pending);
                 }
-                
-                let mut client_count = 0;
-                
-                loop {
-                    if stop_receiver.try_recv().is_ok() {
-                        println!("🛑 TCP Server '{}' parando...", server_id);
-                        break;
-                    }
-                    
-                    match listener.accept() {
-                        Ok((stream, addr)) => {
-                            if client_count >= max_clients {
-                                println!("⚠️ TCP Server '{}': Limite de clientes ({}) atingido, rejeitando {}", 
-                                        server_id, max_clients, addr);
-                                continue;
-                            }
-                            
-                            client_count += 1;
-                            let server_id_clone = server_id.clone();
-                            
-                            thread::spawn(move || {
-                                handle_tcp_client(stream, addr, server_id_clone, client_count);
-                            });
-                            
-                            println!("🔗 TCP Server '{}': Cliente {} conectado de {}", 
-                                    server_id, client_count, addr);
-                        }
-                        Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                            thread::sleep(Duration::from_millis(100));
-                        }
-                        Err(e) => {
-                            eprintln!("❌ TCP Server '{}': Erro ao aceitar conexão: {}", server_id, e);
-                        }
-                    }
+                Err(e) => return Err(self.io_error("ler", e)),
+            }
+        }
+    }
+
+    /// Até `max` bytes; `None` no fim do fluxo e vazio no modo não bloqueante
+    fn read_some(&self, max: usize) -> Result<Option<Vec<u8>>, RuntimeError> {
+        self.ensure_readable()?;
+        let mut buffer = self.buffer.lock().unwrap();
+        match self.fill_until(&mut buffer, |b| !b.is_empty())? {
+            Fill::Ready => {
+                let n = max.min(buffer.len());
+                Ok(Some(buffer.drain(..n).collect()))
+            }
+            Fill::Eof => Ok(None),
+            Fill::Pending => Ok(Some(Vec::new())),
+        }
+    }
+
+    fn write_all(&self, mut data: &[u8]) -> Result<(), RuntimeError> {
+        if !self.open.load(Ordering::SeqCst) {
+            return Err(RuntimeError::NetworkError(format!("Conexão '{}' está fechada", self.id)));
+        }
+        while !data.is_empty() {
+            match self.write_raw(data) {
+                Ok(0) => return Err(RuntimeError::NetworkError(format!("Conexão '{}' não aceita mais dados", self.id))),
+                Ok(n) => data = &data[n..],
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                // No modo não bloqueante a escrita continua até o fim
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock && self.nonblocking.load(Ordering::SeqCst) => {
+                    thread::sleep(Duration::from_millis(1));
                 }
+                Err(e) => return Err(self.io_error("escrever", e)),
             }
-            Err(e) => {
-                eprintln!("❌ Erro ao iniciar TCP Server '{}' em {}: {}", server_id, bind_address, e);
+        }
+        Ok(())
+    }
+
+    fn io_error(&self, action: &str, e: io::Error) -> RuntimeError {
+        match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+                RuntimeError::NetworkError(format!("Tempo limite esgotado ao {} na conexão '{}'", action, self.id))
             }
+            _ => RuntimeError::NetworkError(format!("Erro ao {} na conexão '{}': {}", action, self.id, e)),
         }
+    }
+}
+
+// ========================
+// Eventos para o interpreter
+// ========================
+
+enum EventKind {
+    Connection,
+    Data(Vec<u8>),
+    Close,
+}
+
+/// Chamada de handler que aguarda o interpreter
+pub struct PendingEvent {
+    server_id: String,
+    handler: Value,
+    connection: Arc<Connection>,
+    kind: EventKind,
+}
+
+impl PendingEvent {
+    pub fn handler(&self) -> &Value {
+        &self.handler
+    }
+
+    /// `(conn)` para "connection" e "close", `(conn, bytes)` para "data"
+    pub fn arguments(&self, heap: &mut Heap) -> Vec<Value> {
+        let connection = self.connection.to_value(heap);
+        match &self.kind {
+            EventKind::Data(bytes) => vec![connection, bytes_value(bytes, heap)],
+            EventKind::Connection | EventKind::Close => vec![connection],
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            EventKind::Connection => "connection",
+            EventKind::Data(_) => "data",
+            EventKind::Close => "close",
+        };
+        format!("TCP '{}' ({}, {})", self.server_id, kind, self.connection.id)
+    }
+
+    /// Um handler que falhou encerra a conexão
+    pub fn fail(&self) {
+        self.connection.close();
+    }
+}
+
+fn push_event(registry: &TcpServers, handler: &Option<Value>, connection: &Arc<Connection>, kind: EventKind) {
+    let (Some(handler), Some(server_id)) = (handler, &connection.server_id) else {
+        return;
+    };
+    registry.pending.lock().unwrap().push(PendingEvent {
+        server_id: server_id.clone(),
+        handler: handler.clone(),
+        connection: connection.clone(),
+        kind,
     });
+    registry.ready.notify_all();
 }
 
-fn handle_tcp_client(mut stream: TcpStream, addr: SocketAddr, server_id: String, client_num: usize) {
-    let mut reader = BufReader::new(stream.try_clone().unwrap());
-    let mut line = String::new();
-    
-    loop {
-        line.clear();
-        match reader.read_line(&mut line) {
-            Ok(0) => {
-                println!("👋 TCP Server '{}': Cliente {} de {} desconectou", server_id, client_num, addr);
-                break;
-            }
-            Ok(_) => {
-                let response = format!("Echo from TCP Server '{}': received {}", server_id, line);
-                if let Err(e) = stream.write_all(response.as_bytes()) {
-                    eprintln!("❌ TCP Server '{}': Erro ao enviar resposta: {}", server_id, e);
-                    break;
-                }
-            }
+/// Remove da fila os eventos dos servidores deste interpreter; chamada por ele
+pub fn take_pending_events(manager: &NativeModuleManager) -> Vec<PendingEvent> {
+    let mut pending = manager.tcp_servers().pending.lock().unwrap();
+    if pending.is_empty() {
+        return Vec::new();
+    }
+    std::mem::take(&mut *pending)
+}
+
+/// Handlers dos servidores deste interpreter, inclusive os de eventos na
+/// fila; o GC os trata como raízes
+pub fn registered_handlers(manager: &NativeModuleManager) -> Vec<Value> {
+    let registry = manager.tcp_servers();
+    let mut values = Vec::new();
+    for server in registry.servers.lock().unwrap().values() {
+        let handlers = &server.handlers;
+        values.extend([&handlers.connection, &handlers.data, &handlers.close].into_iter().flatten().cloned());
+    }
+    for event in registry.pending.lock().unwrap().iter() {
+        values.push(event.handler.clone());
+    }
+    values
+}
+
+// ========================
+// Threads de I/O
+// ========================
+
+fn accept_loop(registry: Arc<TcpServers>, server_id: String, listener: TcpListener, shutdown: Arc<AtomicBool>, active: Arc<AtomicUsize>) {
+    while !shutdown.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, addr)) => accept_connection(&registry, &server_id, stream, addr, &active),
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
             Err(e) => {
-                eprintln!("❌ TCP Server '{}': Erro ao ler dados: {}", server_id, e);
-                break;
+                eprintln!("❌ TCP Server '{}': Erro ao aceitar conexão: {}", server_id, e);
+                thread::sleep(POLL_INTERVAL);
             }
         }
     }
 }
 
+fn accept_connection(registry: &Arc<TcpServers>, server_id: &str, stream: TcpStream, addr: SocketAddr, active: &Arc<AtomicUsize>) {
+    let (max_clients, handlers, tls_config) = match registry.servers.lock().unwrap().get(server_id) {
+        Some(server) => (server.max_clients, server.handlers.clone(), server.tls.clone()),
+        None => return,
+    };
+
+    if handlers.connection.is_none() && handlers.data.is_none() {
+        eprintln!("⚠️ TCP Server '{}': nenhum handler registrado, recusando {}", server_id, addr);
+        return;
+    }
+    if active.load(Ordering::SeqCst) >= max_clients {
+        eprintln!("⚠️ TCP Server '{}': Limite de clientes ({}) atingido, rejeitando {}", server_id, max_clients, addr);
+        return;
+    }
+
+    // Em algumas plataformas o socket herda o modo não bloqueante do listener
+    let _ = stream.set_nonblocking(false);
+    match tls_config {
+        // O handshake não pode segurar a thread de accept
+        Some(config) => {
+            let (registry, server_id, active) = (registry.clone(), server_id.to_string(), active.clone());
+            thread::spawn(move || match tls::accept(&config, stream) {
+                Ok(stream) => open_connection(&registry, &server_id, NetStream::Tls(Box::new(stream)), addr, &active, handlers),
+                Err(e) => eprintln!("⚠️ TCP Server '{}': handshake TLS com {} falhou: {}", server_id, addr, e),
+            });
+        }
+        None => open_connection(registry, server_id, NetStream::Plain(stream), addr, active, handlers),
+    }
+}
+
+fn open_connection(
+    registry: &Arc<TcpServers>,
+    server_id: &str,
+    stream: NetStream,
+    addr: SocketAddr,
+    active: &Arc<AtomicUsize>,
+    handlers: Handlers,
+) {
+    if handlers.data.is_some() {
+        // A thread de eventos precisa soltar a sessão TLS de tempos em tempos
+        // para que o script consiga escrever
+        let _ = stream.socket().set_read_timeout(Some(POLL_INTERVAL));
+    }
+    let connection = match Connection::new(stream, Some(server_id.to_string()), Some(active.clone()), handlers.data.is_some()) {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("❌ TCP Server '{}': Erro ao registrar conexão de {}: {}", server_id, addr, e);
+            return;
+        }
+    };
+
+    push_event(registry, &handlers.connection, &connection, EventKind::Connection);
+    if handlers.data.is_some() {
+        let registry = registry.clone();
+        thread::spawn(move || read_events(registry, connection, handlers));
+    }
+}
+
+/// Lê uma conexão em modo de eventos, enfileirando "data" a cada chunk e
+/// "close" quando o fluxo termina
+fn read_events(registry: Arc<TcpServers>, connection: Arc<Connection>, handlers: Handlers) {
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    loop {
+        match connection.read_raw(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => push_event(&registry, &handlers.data, &connection, EventKind::Data(chunk[..n].to_vec())),
+            Err(e)
+                if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted)
+                    && connection.open.load(Ordering::SeqCst) => {}
+            Err(_) => break,
+        }
+    }
+    connection.eof.store(true, Ordering::SeqCst);
+    connection.close();
+    push_event(&registry, &handlers.close, &connection, EventKind::Close);
+}
+
 pub fn register_tcp_functions(functions: &mut HashMap<String, NativeFunction>) {
     functions.insert("tcp_server_create".to_string(), native_tcp_server_create);
+    functions.insert("tcp_server_on".to_string(), native_tcp_server_on);
+    functions.insert("tcp_server_tls".to_string(), native_tcp_server_tls);
     functions.insert("tcp_server_start".to_string(), native_tcp_server_start);
+    functions.insert("tcp_server_wait".to_string(), native_tcp_server_wait);
     functions.insert("tcp_server_stop".to_string(), native_tcp_server_stop);
     functions.insert("tcp_server_status".to_string(), native_tcp_server_status);
-    
+    functions.insert("tcp_server_set_max_clients".to_string(), native_tcp_server_set_max_clients);
+
+    functions.insert("tcp_connect".to_string(), native_tcp_connect);
+    functions.insert("tcp_read".to_string(), native_tcp_read);
+    functions.insert("tcp_read_line".to_string(), native_tcp_read_line);
+    functions.insert("tcp_read_exact".to_string(), native_tcp_read_exact);
+    functions.insert("tcp_write".to_string(), native_tcp_write);
+    functions.insert("tcp_shutdown".to_string(), native_tcp_shutdown);
+    functions.insert("tcp_close".to_string(), native_tcp_close);
+    functions.insert("tcp_set_timeout".to_string(), native_tcp_set_timeout);
+    functions.insert("tcp_set_nonblocking".to_string(), native_tcp_set_nonblocking);
+    functions.insert("tcp_connection_info".to_string(), native_tcp_connection_info);
+
     functions.insert("tcp_client_create".to_string(), native_tcp_client_create);
     functions.insert("tcp_client_connect".to_string(), native_tcp_client_connect);
     functions.insert("tcp_client_disconnect".to_string(), native_tcp_client_disconnect);
     functions.insert("tcp_client_send".to_string(), native_tcp_client_send);
     functions.insert("tcp_client_receive".to_string(), native_tcp_client_receive);
+    functions.insert("tcp_client_set_timeout".to_string(), native_tcp_client_set_timeout);
     functions.insert("tcp_client_status".to_string(), native_tcp_client_status);
 
     functions.insert("tcp_resolve_hostname".to_string(), native_tcp_resolve_hostname);
     functions.insert("tcp_get_local_ip".to_string(), native_tcp_get_local_ip);
+    functions.insert("tcp_port_available".to_string(), native_tcp_port_available);
+}
+
+// ========================
+// Argumentos e conversões
+// ========================
+
+fn string_arg(args: &[Value], index: usize, name: &str, function: &str) -> Result<String, RuntimeError> {
+    match args.get(index) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: {} deve ser string", function, name))),
+        None => Err(RuntimeError::ArgumentError(format!("{}: argumento '{}' ausente", function, name))),
+    }
+}
+
+fn count_arg(value: Option<&Value>, name: &str, function: &str) -> Result<Option<usize>, RuntimeError> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => Ok(Some(*n as usize)),
+        Some(Value::Int(n)) if *n >= 0 => Ok(Some(*n as usize)),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: {} deve ser um inteiro não negativo", function, name))),
+    }
+}
+
+fn port_arg(value: Option<&Value>, function: &str) -> Result<Option<u16>, RuntimeError> {
+    match count_arg(value, "port", function)? {
+        Some(port) if port > u16::MAX as usize => Err(RuntimeError::ArgumentError(format!("{}: porta inválida: {}", function, port))),
+        port => Ok(port.map(|p| p as u16)),
+    }
+}
+
+/// Timeout em milissegundos; `null` ou `0` desativam
+fn timeout_arg(value: Option<&Value>, function: &str) -> Result<Option<Duration>, RuntimeError> {
+    Ok(count_arg(value, "timeout_ms", function)?
+        .filter(|ms| *ms > 0)
+        .map(|ms| Duration::from_millis(ms as u64)))
+}
+
+/// Conexão a partir do objeto devolvido por `tcp_connect`/handlers ou do seu `id`
+fn connection_arg(args: &[Value], heap: &Heap, function: &str) -> Result<Arc<Connection>, RuntimeError> {
+    let id = match args.first() {
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Object(obj)) => match heap.get(*obj) {
+            Some(ManagedObject::Object { properties, .. }) => match properties.get("id") {
+                Some(Value::String(id)) => id.clone(),
+                _ => return Err(RuntimeError::TypeError(format!("{}: objeto não é uma conexão TCP", function))),
+            },
+            _ => return Err(RuntimeError::HeapError("Objeto de conexão não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError(format!("{}: esperado uma conexão TCP", function))),
+        None => return Err(RuntimeError::ArgumentError(format!("{}: conexão ausente", function))),
+    };
+    CONNECTIONS
+        .lock()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| RuntimeError::NetworkError(format!("Conexão '{}' não encontrada ou já fechada", id)))
+}
+
+/// Dados para envio: string (UTF-8) ou array de bytes 0–255
+pub(super) fn data_arg(value: Option<&Value>, heap: &Heap, function: &str) -> Result<Vec<u8>, RuntimeError> {
+    match value {
+        Some(Value::String(s)) => Ok(s.as_bytes().to_vec()),
+        Some(Value::Array(id)) => match heap.get(*id) {
+            Some(ManagedObject::Array(items)) => items
+                .iter()
+                .map(|item| match item {
+                    Value::Number(n) if n.fract() == 0.0 && (0.0..=255.0).contains(n) => Ok(*n as u8),
+                    _ => Err(RuntimeError::TypeError(format!("{}: array de dados deve conter bytes (0–255)", function))),
+                })
+                .collect(),
+            _ => Err(RuntimeError::HeapError("Array de dados não encontrado".to_string())),
+        },
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: dados devem ser string ou array de bytes", function))),
+        None => Err(RuntimeError::ArgumentError(format!("{}: dados ausentes", function))),
+    }
+}
+
+pub(super) fn bytes_value(bytes: &[u8], heap: &mut Heap) -> Value {
+    let items = bytes.iter().map(|b| Value::Number(*b as f64)).collect();
+    Value::Array(heap.allocate(ManagedObject::Array(items)))
+}
+
+/// String quando os bytes são UTF-8 válido, senão array de bytes
+pub(super) fn text_or_bytes(bytes: Vec<u8>, heap: &mut Heap) -> Value {
+    match String::from_utf8(bytes) {
+        Ok(text) => Value::String(text),
+        Err(e) => bytes_value(e.as_bytes(), heap),
+    }
+}
+
+fn is_callable(value: &Value) -> bool {
+    matches!(value, Value::Lambda(_) | Value::Function { .. })
+}
+
+fn with_client<T>(client_id: &str, f: impl FnOnce(&mut ClientInstance) -> Result<T, RuntimeError>) -> Result<T, RuntimeError> {
+    let mut clients = TCP_CLIENTS.lock().unwrap();
+    let client = clients
+        .get_mut(client_id)
+        .ok_or_else(|| RuntimeError::ArgumentError(format!("TCP Client '{}' não encontrado", client_id)))?;
+    f(client)
+}
+
+fn connect(host: &str, port: u16, timeout: Duration) -> Result<TcpStream, RuntimeError> {
+    let addrs = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| RuntimeError::NetworkError(format!("Erro ao resolver '{}': {}", host, e)))?;
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(RuntimeError::NetworkError(match last_error {
+        Some(e) => format!("Erro ao conectar em {}:{}: {}", host, port, e),
+        None => format!("Nenhum endereço encontrado para '{}'", host),
+    }))
 }
 
 // ========================
 // Funções de servidor TCP
 // ========================
 
+/// tcp_server_create(id, host?, port?, max_clients?) -> null
+/// Porta 0 escolhe uma porta livre ao iniciar (veja `tcp_server_status`)
 fn native_tcp_server_create(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    if args.is_empty() {
-        return Err(RuntimeError::ArgumentError("tcp_server_create(id, [host], [port], [max]) requerido".to_string()));
-    }
-    
-    let server_id = match &args[0] { Value::String(id) => id.clone(), _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let host = if args.len() > 1 { match &args[1] { Value::String(h) => h.clone(), _ => "127.0.0.1".to_string() } } else { "127.0.0.1".to_string() };
-    let port = if args.len() > 2 { match &args[2] { Value::Number(p) => *p as u16, _ => 8080 } } else { 8080 };
-    let max_clients = if args.len() > 3 { match &args[3] { Value::Number(mc) => *mc as usize, _ => 10 } } else { 10 };
-    
-    let server_instance = ServerInstance {
-        server_id: server_id.clone(),
-        host,
-        port,
-        is_running: false,
-        stop_sender: None,
-        max_clients,
+    let server_id = string_arg(args, 0, "id", "tcp_server_create")?;
+    let host = match args.get(1) {
+        Some(Value::String(h)) => h.clone(),
+        _ => "127.0.0.1".to_string(),
     };
-    
-    TCP_SERVERS.lock().unwrap().insert(server_id, server_instance);
+    let port = port_arg(args.get(2), "tcp_server_create")?.unwrap_or(8080);
+    let max_clients = count_arg(args.get(3), "max_clients", "tcp_server_create")?.unwrap_or(10);
+
+    let mut servers = _manager.tcp_servers().servers.lock().unwrap();
+    if servers.get(&server_id).is_some_and(|server| server.running.is_some()) {
+        return Err(RuntimeError::NetworkError(format!("TCP Server '{}' já está rodando", server_id)));
+    }
+    servers.insert(
+        server_id.clone(),
+        ServerInstance { server_id, host, port, max_clients, handlers: Handlers::default(), tls: None, running: None },
+    );
     Ok(Value::Null)
 }
 
+/// tcp_server_on(id, evento, handler) -> null
+/// Eventos: "connection" -> handler(conn), "data" -> handler(conn, bytes) e
+/// "close" -> handler(conn). Com "data" registrado as conexões são lidas em
+/// segundo plano; sem ele, o handler de "connection" lê com `tcp_read*`.
+fn native_tcp_server_on(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let server_id = string_arg(args, 0, "id", "tcp_server_on")?;
+    let event = string_arg(args, 1, "evento", "tcp_server_on")?;
+    let handler = match args.get(2) {
+        Some(value) if is_callable(value) => value.clone(),
+        _ => return Err(RuntimeError::TypeError("tcp_server_on: handler deve ser uma função".to_string())),
+    };
+
+    _manager.tcp_servers().with_server(&server_id, |server| {
+        let slot = match event.as_str() {
+            "connection" => &mut server.handlers.connection,
+            "data" => &mut server.handlers.data,
+            "close" => &mut server.handlers.close,
+            other => {
+                return Err(RuntimeError::ArgumentError(format!(
+                    "tcp_server_on: evento desconhecido '{}' (use \"connection\", \"data\" ou \"close\")",
+                    other
+                )))
+            }
+        };
+        *slot = Some(handler);
+        Ok(Value::Null)
+    })
+}
+
+/// tcp_server_tls(id, opcoes) -> null
+/// Ativa TLS no servidor: `{ cert, key, ca?, client_auth?, alpn? }`. Deve
+/// ser chamada antes de `tcp_server_start`; os handlers só recebem a conexão
+/// depois do handshake.
+fn native_tcp_server_tls(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let server_id = string_arg(args, 0, "id", "tcp_server_tls")?;
+    let options = args
+        .get(1)
+        .ok_or_else(|| RuntimeError::ArgumentError("tcp_server_tls(id, opcoes)".to_string()))?;
+    let config = tls::server_config(options, heap, "tcp_server_tls")?;
+    _manager.tcp_servers().with_server(&server_id, |server| {
+        if server.running.is_some() {
+            return Err(RuntimeError::NetworkError(format!("TCP Server '{}' já está rodando", server_id)));
+        }
+        server.tls = Some(config);
+        Ok(Value::Null)
+    })
+}
+
+/// tcp_server_start(id) -> null
+/// Faz o bind imediatamente (erros de porta são lançados aqui) e aceita
+/// conexões em segundo plano
 fn native_tcp_server_start(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    
-    let mut servers = TCP_SERVERS.lock().unwrap();
-    let server = servers.get_mut(server_id).ok_or_else(|| RuntimeError::ArgumentError("Servidor não encontrado".to_string()))?;
-    
-    if server.is_running { return Ok(Value::Null); }
-    
-    let (stop_sender, stop_receiver) = mpsc::channel();
-    server.stop_sender = Some(stop_sender);
-    server.is_running = true;
-    
-    start_tcp_server(server.server_id.clone(), server.host.clone(), server.port, server.max_clients, stop_receiver);
-    Ok(Value::Null)
+    let server_id = string_arg(args, 0, "id", "tcp_server_start")?;
+
+    let registry = _manager.tcp_servers();
+    registry.with_server(&server_id, |server| {
+        if server.running.is_some() {
+            return Ok(Value::Null);
+        }
+
+        let bind_address = format!("{}:{}", server.host, server.port);
+        let listener = TcpListener::bind(&bind_address)
+            .map_err(|e| RuntimeError::NetworkError(format!("Erro ao iniciar TCP Server '{}' em {}: {}", server_id, bind_address, e)))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| RuntimeError::NetworkError(format!("Erro ao configurar TCP Server '{}': {}", server_id, e)))?;
+        if let Ok(addr) = listener.local_addr() {
+            server.port = addr.port();
+        }
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let active = Arc::new(AtomicUsize::new(0));
+        let accept_thread = {
+            let (registry, server_id, shutdown, active) = (registry.clone(), server_id.clone(), shutdown.clone(), active.clone());
+            thread::spawn(move || accept_loop(registry, server_id, listener, shutdown, active))
+        };
+        server.running = Some(RunningServer { shutdown, active, accept_thread });
+
+        let scheme = if server.tls.is_some() { " (TLS)" } else { "" };
+        println!("🌍 TCP Server '{}' iniciado em {}:{}{}", server_id, server.host, server.port, scheme);
+        Ok(Value::Null)
+    })
 }
 
+/// tcp_server_wait(id, timeout_ms?) -> bool
+/// Bloqueia até chegar um evento para o interpreter, o tempo limite (padrão
+/// 1000 ms) expirar ou o servidor parar. Retorna se o servidor ainda está
+/// rodando, permitindo `while (tcp_server_wait(id)) {}`.
+fn native_tcp_server_wait(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let server_id = string_arg(args, 0, "id", "tcp_server_wait")?;
+    let timeout = match args.get(1) {
+        Some(Value::Number(ms)) if *ms >= 0.0 => Duration::from_millis(*ms as u64),
+        Some(_) => return Err(RuntimeError::TypeError("tcp_server_wait: timeout_ms deve ser um número".to_string())),
+        None => Duration::from_secs(1),
+    };
+
+    let registry = _manager.tcp_servers();
+    let shutdown = registry.with_server(&server_id, |server| Ok(server.running.as_ref().map(|running| running.shutdown.clone())))?;
+    let Some(shutdown) = shutdown else {
+        return Ok(Value::Bool(false));
+    };
+
+    let deadline = Instant::now() + timeout;
+    let mut pending = registry.pending.lock().unwrap();
+    loop {
+        if shutdown.load(Ordering::SeqCst) {
+            return Ok(Value::Bool(false));
+        }
+        let now = Instant::now();
+        if pending.iter().any(|event| event.server_id == server_id) || now >= deadline {
+            return Ok(Value::Bool(true));
+        }
+        pending = registry.ready.wait_timeout(pending, deadline - now).unwrap().0;
+    }
+}
+
+/// tcp_server_stop(id) -> null
+/// Para de aceitar conexões, fecha as abertas e descarta eventos pendentes
 fn native_tcp_server_stop(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    
-    let mut servers = TCP_SERVERS.lock().unwrap();
-    let server = servers.get_mut(server_id).ok_or_else(|| RuntimeError::ArgumentError("Servidor não encontrado".to_string()))?;
-    
-    if let Some(sender) = server.stop_sender.take() {
-        let _ = sender.send(());
-    }
-    server.is_running = false;
+    let server_id = string_arg(args, 0, "id", "tcp_server_stop")?;
+    let registry = _manager.tcp_servers();
+    let Some(running) = registry.with_server(&server_id, |server| Ok(server.running.take()))? else {
+        return Ok(Value::Null);
+    };
+
+    running.shutdown.store(true, Ordering::SeqCst);
+    let _ = running.accept_thread.join();
+
+    let connections: Vec<Arc<Connection>> = CONNECTIONS
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|connection| connection.active.as_ref().is_some_and(|active| Arc::ptr_eq(active, &running.active)))
+        .cloned()
+        .collect();
+    for connection in connections {
+        connection.close();
+    }
+
+    registry.pending.lock().unwrap().retain(|event| event.server_id != server_id);
+    registry.ready.notify_all();
+
+    println!("🛑 TCP Server '{}' parado", server_id);
     Ok(Value::Null)
 }
 
+/// tcp_server_status(id) -> { id, host, port, is_running, tls, max_clients, connections }
 fn native_tcp_server_status(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let server_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    
-    let servers = TCP_SERVERS.lock().unwrap();
-    let server = servers.get(server_id).ok_or_else(|| RuntimeError::ArgumentError("Servidor não encontrado".to_string()))?;
-    
-    let mut props = HashMap::new();
-    props.insert("id".to_string(), Value::String(server.server_id.clone()));
-    props.insert("is_running".to_string(), Value::Bool(server.is_running));
-    props.insert("port".to_string(), Value::Number(server.port as f64));
-    
-    let id = heap.allocate(crate::heap::ManagedObject::Object { properties: props, methods: HashMap::new() });
+    let server_id = string_arg(args, 0, "id", "tcp_server_status")?;
+    let props = _manager.tcp_servers().with_server(&server_id, |server| {
+        let mut props = HashMap::new();
+        props.insert("id".to_string(), Value::String(server.server_id.clone()));
+        props.insert("host".to_string(), Value::String(server.host.clone()));
+        props.insert("port".to_string(), Value::Number(server.port as f64));
+        props.insert("is_running".to_string(), Value::Bool(server.running.is_some()));
+        props.insert("tls".to_string(), Value::Bool(server.tls.is_some()));
+        props.insert("max_clients".to_string(), Value::Number(server.max_clients as f64));
+        let connections = server.running.as_ref().map_or(0, |running| running.active.load(Ordering::SeqCst));
+        props.insert("connections".to_string(), Value::Number(connections as f64));
+        Ok(props)
+    })?;
+
+    let id = heap.allocate(ManagedObject::Object { properties: props, methods: HashMap::new() });
     Ok(Value::Object(id))
 }
 
+/// tcp_server_set_max_clients(id, max) -> null
+/// Conexões além do limite são fechadas logo após o accept
+fn native_tcp_server_set_max_clients(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let server_id = string_arg(args, 0, "id", "tcp_server_set_max_clients")?;
+    let max_clients = count_arg(args.get(1), "max", "tcp_server_set_max_clients")?
+        .ok_or_else(|| RuntimeError::ArgumentError("tcp_server_set_max_clients(id, max)".to_string()))?;
+    _manager.tcp_servers().with_server(&server_id, |server| {
+        server.max_clients = max_clients;
+        Ok(Value::Null)
+    })
+}
+
+// ========================
+// Funções de conexão TCP
+// ========================
+
+/// tcp_connect(host, port, timeout_ms | opcoes?) -> conn
+/// Abre uma conexão cliente (timeout de conexão padrão de 30 s). As opções
+/// são `{ timeout_ms?, tls? }`, com `tls` sendo `true` ou as opções de
+/// cliente TLS (`{ ca?, cert?, key?, alpn?, server_name?, verify? }`).
+fn native_tcp_connect(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let host = string_arg(args, 0, "host", "tcp_connect")?;
+    let port = port_arg(args.get(1), "tcp_connect")?
+        .ok_or_else(|| RuntimeError::ArgumentError("tcp_connect(host, port, timeout_ms | opcoes?)".to_string()))?;
+    let (timeout, client_tls) = match args.get(2) {
+        Some(Value::Object(id)) => {
+            let properties = match heap.get(*id) {
+                Some(ManagedObject::Object { properties, .. }) => properties,
+                _ => return Err(RuntimeError::HeapError("Objeto de opções não encontrado".to_string())),
+            };
+            let timeout = timeout_arg(properties.get("timeout_ms"), "tcp_connect")?;
+            let client_tls = match properties.get("tls") {
+                None | Some(Value::Null) | Some(Value::Bool(false)) => None,
+                Some(options) => Some(ClientTls::from_value(options, heap, "tcp_connect")?),
+            };
+            (timeout, client_tls)
+        }
+        other => (timeout_arg(other, "tcp_connect")?, None),
+    };
+    let timeout = timeout.unwrap_or(Duration::from_secs(30));
+
+    let stream = connect(&host, port, timeout)?;
+    let stream = match client_tls {
+        Some(client_tls) => {
+            // O handshake respeita o mesmo tempo limite da conexão
+            let _ = stream.set_read_timeout(Some(timeout));
+            let _ = stream.set_write_timeout(Some(timeout));
+            let stream = client_tls.connect(&host, stream, "tcp_connect")?;
+            let _ = stream.get_ref().set_read_timeout(None);
+            let _ = stream.get_ref().set_write_timeout(None);
+            NetStream::Tls(Box::new(stream))
+        }
+        None => NetStream::Plain(stream),
+    };
+    let connection = Connection::new(stream, None, None, false)
+        .map_err(|e| RuntimeError::NetworkError(format!("Erro ao registrar conexão com {}:{}: {}", host, port, e)))?;
+    Ok(connection.to_value(heap))
+}
+
+/// tcp_read(conn, max_bytes?) -> array de bytes | null
+/// Espera dados e devolve até `max_bytes` (padrão 65536); `null` quando o
+/// outro lado encerrou e `[]` se não há dados no modo não bloqueante
+fn native_tcp_read(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_read")?;
+    let max = count_arg(args.get(1), "max_bytes", "tcp_read")?.unwrap_or(CHUNK_SIZE).max(1);
+    Ok(match connection.read_some(max)? {
+        Some(bytes) => bytes_value(&bytes, heap),
+        None => Value::Null,
+    })
+}
+
+/// tcp_read_line(conn) -> string | null
+/// Lê até `\n` (removendo `\r\n`/`\n`). No fim do fluxo devolve o que restou,
+/// ou `null`; no modo não bloqueante, `null` enquanto a linha não chega inteira
+fn native_tcp_read_line(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_read_line")?;
+    connection.ensure_readable()?;
+
+    let mut buffer = connection.buffer.lock().unwrap();
+    let fill = connection.fill_until(&mut buffer, |b| b.contains(&b'\n') || b.len() > MAX_LINE_BYTES)?;
+    let line: Vec<u8> = match fill {
+        Fill::Ready => match buffer.iter().position(|b| *b == b'\n') {
+            Some(end) => {
+                let mut line: Vec<u8> = buffer.drain(..=end).collect();
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                line
+            }
+            None => {
+                return Err(RuntimeError::NetworkError(format!(
+                    "tcp_read_line: linha maior que {} bytes na conexão '{}'",
+                    MAX_LINE_BYTES, connection.id
+                )))
+            }
+        },
+        Fill::Eof if !buffer.is_empty() => std::mem::take(&mut *buffer),
+        Fill::Eof | Fill::Pending => return Ok(Value::Null),
+    };
+    Ok(Value::String(String::from_utf8_lossy(&line).into_owned()))
+}
+
+/// tcp_read_exact(conn, n) -> array de bytes | null
+/// Espera exatamente `n` bytes; lança erro se a conexão terminar antes. No
+/// modo não bloqueante devolve `null` enquanto não houver `n` bytes.
+fn native_tcp_read_exact(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_read_exact")?;
+    let n = count_arg(args.get(1), "n", "tcp_read_exact")?
+        .ok_or_else(|| RuntimeError::ArgumentError("tcp_read_exact(conn, n)".to_string()))?;
+    connection.ensure_readable()?;
+
+    let mut buffer = connection.buffer.lock().unwrap();
+    match connection.fill_until(&mut buffer, |b| b.len() >= n)? {
+        Fill::Ready => {
+            let bytes: Vec<u8> = buffer.drain(..n).collect();
+            Ok(bytes_value(&bytes, heap))
+        }
+        Fill::Eof => Err(RuntimeError::NetworkError(format!(
+            "tcp_read_exact: conexão '{}' encerrada após {} de {} bytes",
+            connection.id,
+            buffer.len(),
+            n
+        ))),
+        Fill::Pending => Ok(Value::Null),
+    }
+}
+
+/// tcp_write(conn, dados) -> number
+/// Envia uma string (UTF-8) ou array de bytes por inteiro; retorna os bytes enviados
+fn native_tcp_write(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_write")?;
+    let data = data_arg(args.get(1), heap, "tcp_write")?;
+    connection.write_all(&data)?;
+    Ok(Value::Number(data.len() as f64))
+}
+
+/// tcp_shutdown(conn, direcao?) -> null
+/// Half-close: "write" (padrão) envia FIN mas continua lendo; "read" ou "both"
+fn native_tcp_shutdown(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_shutdown")?;
+    let how = match args.get(1) {
+        None | Some(Value::Null) => Shutdown::Write,
+        Some(Value::String(s)) if s == "write" => Shutdown::Write,
+        Some(Value::String(s)) if s == "read" => Shutdown::Read,
+        Some(Value::String(s)) if s == "both" => Shutdown::Both,
+        Some(_) => {
+            return Err(RuntimeError::ArgumentError(
+                "tcp_shutdown: direção deve ser \"write\", \"read\" ou \"both\"".to_string(),
+            ))
+        }
+    };
+    if let (Some((tls, _)), Shutdown::Write | Shutdown::Both) = (&connection.tls, how) {
+        // Com TLS o fim da escrita é sinalizado por close_notify
+        let _ = tls.lock().unwrap().shutdown();
+    }
+    connection.stream.shutdown(how).map_err(|e| connection.io_error("encerrar", e))?;
+    Ok(Value::Null)
+}
+
+/// tcp_close(conn) -> null
+fn native_tcp_close(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    // Fechar uma conexão já fechada não é erro
+    if let Ok(connection) = connection_arg(args, heap, "tcp_close") {
+        connection.close();
+    }
+    Ok(Value::Null)
+}
+
+/// tcp_set_timeout(conn, timeout_ms) -> null
+/// Tempo limite de leitura e escrita; `null` ou `0` esperam indefinidamente
+fn native_tcp_set_timeout(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_set_timeout")?;
+    let timeout = timeout_arg(args.get(1), "tcp_set_timeout")?;
+    connection
+        .stream
+        .set_read_timeout(timeout)
+        .and_then(|_| connection.stream.set_write_timeout(timeout))
+        .map_err(|e| connection.io_error("configurar", e))?;
+    Ok(Value::Null)
+}
+
+/// tcp_set_nonblocking(conn, ativo) -> null
+/// No modo não bloqueante as leituras retornam na hora quando não há dados
+fn native_tcp_set_nonblocking(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_set_nonblocking")?;
+    let enabled = match args.get(1) {
+        Some(Value::Bool(b)) => *b,
+        _ => return Err(RuntimeError::TypeError("tcp_set_nonblocking(conn, bool)".to_string())),
+    };
+    connection.ensure_readable()?;
+    connection.stream.set_nonblocking(enabled).map_err(|e| connection.io_error("configurar", e))?;
+    connection.nonblocking.store(enabled, Ordering::SeqCst);
+    Ok(Value::Null)
+}
+
+/// tcp_connection_info(conn) -> { id, remote_addr, local_addr, server_id, tls, open, eof, buffered, nonblocking }
+/// `tls` é `null` ou `{ version, cipher, alpn, peer_subject }`
+fn native_tcp_connection_info(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let connection = connection_arg(args, heap, "tcp_connection_info")?;
+    let info = connection.to_value(heap);
+    let buffered = connection.buffer.lock().unwrap().len();
+    if let Value::Object(id) = &info {
+        if let Some(ManagedObject::Object { properties, .. }) = heap.get_mut(*id) {
+            properties.insert("open".to_string(), Value::Bool(connection.open.load(Ordering::SeqCst)));
+            properties.insert("eof".to_string(), Value::Bool(connection.eof.load(Ordering::SeqCst) && buffered == 0));
+            properties.insert("buffered".to_string(), Value::Number(buffered as f64));
+            properties.insert("nonblocking".to_string(), Value::Bool(connection.nonblocking.load(Ordering::SeqCst)));
+        }
+    }
+    Ok(info)
+}
+
 // ========================
 // Funções de cliente TCP
 // ========================
 
 fn native_tcp_client_create(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    if args.is_empty() { return Err(RuntimeError::ArgumentError("tcp_client_create(id, host, port)".to_string())); }
-    let client_id = match &args[0] { Value::String(id) => id.clone(), _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let host = match &args[1] { Value::String(h) => h.clone(), _ => "127.0.0.1".to_string() };
-    let port = match &args[2] { Value::Number(p) => *p as u16, _ => 8080 };
-    
-    let client = ClientInstance {
-        client_id: client_id.clone(),
-        host,
-        port,
-        stream: Arc::new(Mutex::new(None)),
-        timeout: Some(Duration::from_secs(30)),
+    let client_id = string_arg(args, 0, "id", "tcp_client_create")?;
+    let host = match args.get(1) {
+        Some(Value::String(h)) => h.clone(),
+        _ => "127.0.0.1".to_string(),
     };
-    
-    TCP_CLIENTS.lock().unwrap().insert(client_id, client);
+    let port = port_arg(args.get(2), "tcp_client_create")?.unwrap_or(8080);
+
+    let client = ClientInstance { client_id: client_id.clone(), host, port, timeout: Duration::from_secs(30), connection: None };
+    if let Some(previous) = TCP_CLIENTS.lock().unwrap().insert(client_id, client) {
+        if let Some(connection) = previous.connection {
+            connection.close();
+        }
+    }
     Ok(Value::Null)
 }
 
 fn native_tcp_client_connect(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let client_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let mut clients = TCP_CLIENTS.lock().unwrap();
-    let client = clients.get_mut(client_id).ok_or_else(|| RuntimeError::ArgumentError("Cliente não encontrado".to_string()))?;
-    
-    let mut stream_lock = client.stream.lock().unwrap();
-    if stream_lock.is_some() { return Ok(Value::Bool(true)); }
-    
-    let addr = format!("{}:{}", client.host, client.port);
-    match TcpStream::connect_timeout(&addr.parse().map_err(|_| RuntimeError::IoError("Endereço inválido".to_string()))?, client.timeout.unwrap_or(Duration::from_secs(5))) {
-        Ok(stream) => { *stream_lock = Some(stream); Ok(Value::Bool(true)) }
-        Err(_) => Ok(Value::Bool(false)),
-    }
-}
-
-fn native_tcp_client_send(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let client_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let data = match &args[1] { Value::String(s) => s.as_bytes(), _ => return Err(RuntimeError::TypeError("data deve ser string".to_string())) };
-    
-    let clients = TCP_CLIENTS.lock().unwrap();
-    let client = clients.get(client_id).ok_or_else(|| RuntimeError::ArgumentError("Cliente não encontrado".to_string()))?;
-    
-    let mut stream_lock = client.stream.lock().unwrap();
-    if let Some(ref mut stream) = *stream_lock {
-        stream.write_all(data).map(|_| Value::Bool(true)).map_err(|e| RuntimeError::IoError(e.to_string()))
-    } else {
-        Err(RuntimeError::IoError("Não conectado".to_string()))
-    }
-}
-
-fn native_tcp_client_receive(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let client_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let clients = TCP_CLIENTS.lock().unwrap();
-    let client = clients.get(client_id).ok_or_else(|| RuntimeError::ArgumentError("Cliente não encontrado".to_string()))?;
-    
-    let mut stream_lock = client.stream.lock().unwrap();
-    if let Some(ref mut stream) = *stream_lock {
-        let mut buffer = [0; 4096];
-        match stream.read(&mut buffer) {
-            Ok(0) => { *stream_lock = None; Ok(Value::Null) }
-            Ok(n) => Ok(Value::String(String::from_utf8_lossy(&buffer[..n]).to_string())),
-            Err(e) => Err(RuntimeError::IoError(e.to_string())),
-        }
-    } else {
-        Err(RuntimeError::IoError("Não conectado".to_string()))
+    let client_id = string_arg(args, 0, "id", "tcp_client_connect")?;
+    let (host, port, timeout, connected) = with_client(&client_id, |client| {
+        Ok((client.host.clone(), client.port, client.timeout, client.connection.is_some()))
+    })?;
+    if connected {
+        return Ok(Value::Bool(true));
+    }
+
+    let connection = match connect(&host, port, timeout).and_then(|stream| {
+        let _ = stream.set_read_timeout(Some(timeout));
+        let _ = stream.set_write_timeout(Some(timeout));
+        Connection::new(NetStream::Plain(stream), None, None, false).map_err(|e| RuntimeError::NetworkError(e.to_string()))
+    }) {
+        Ok(connection) => connection,
+        Err(_) => return Ok(Value::Bool(false)),
+    };
+    with_client(&client_id, |client| {
+        client.connection = Some(connection);
+        Ok(Value::Bool(true))
+    })
+}
+
+/// tcp_client_send(id, dados) -> bool
+fn native_tcp_client_send(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let client_id = string_arg(args, 0, "id", "tcp_client_send")?;
+    let data = data_arg(args.get(1), heap, "tcp_client_send")?;
+    let connection = client_connection(&client_id)?;
+    connection.write_all(&data)?;
+    Ok(Value::Bool(true))
+}
+
+/// tcp_client_receive(id, max_bytes?) -> string | array de bytes | null
+/// String quando os bytes recebidos são UTF-8 válido, senão array de bytes;
+/// `null` quando o servidor encerrou a conexão
+fn native_tcp_client_receive(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let client_id = string_arg(args, 0, "id", "tcp_client_receive")?;
+    let max = count_arg(args.get(1), "max_bytes", "tcp_client_receive")?.unwrap_or(CHUNK_SIZE).max(1);
+    let connection = client_connection(&client_id)?;
+    match connection.read_some(max)? {
+        Some(bytes) => Ok(text_or_bytes(bytes, heap)),
+        None => {
+            connection.close();
+            with_client(&client_id, |client| {
+                client.connection = None;
+                Ok(Value::Null)
+            })
+        }
     }
 }
 
+fn client_connection(client_id: &str) -> Result<Arc<Connection>, RuntimeError> {
+    with_client(client_id, |client| {
+        client
+            .connection
+            .clone()
+            .ok_or_else(|| RuntimeError::NetworkError(format!("TCP Client '{}' não está conectado", client_id)))
+    })
+}
+
 fn native_tcp_client_disconnect(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let client_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let clients = TCP_CLIENTS.lock().unwrap();
-    if let Some(client) = clients.get(client_id) {
-        let mut stream_lock = client.stream.lock().unwrap();
-        *stream_lock = None;
-        Ok(Value::Bool(true))
-    } else {
-        Ok(Value::Bool(false))
+    let client_id = string_arg(args, 0, "id", "tcp_client_disconnect")?;
+    let connection = TCP_CLIENTS.lock().unwrap().get_mut(&client_id).map(|client| client.connection.take());
+    match connection {
+        Some(connection) => {
+            if let Some(connection) = connection {
+                connection.close();
+            }
+            Ok(Value::Bool(true))
+        }
+        None => Ok(Value::Bool(false)),
     }
 }
 
+/// tcp_client_set_timeout(id, segundos) -> null
+/// Vale para a conexão e para leituras/escritas
+fn native_tcp_client_set_timeout(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let client_id = string_arg(args, 0, "id", "tcp_client_set_timeout")?;
+    let seconds = match args.get(1) {
+        Some(Value::Number(n)) if *n > 0.0 => *n,
+        _ => return Err(RuntimeError::TypeError("tcp_client_set_timeout: segundos deve ser um número positivo".to_string())),
+    };
+    let timeout = Duration::from_secs_f64(seconds);
+    with_client(&client_id, |client| {
+        client.timeout = timeout;
+        if let Some(connection) = &client.connection {
+            let _ = connection.stream.set_read_timeout(Some(timeout));
+            let _ = connection.stream.set_write_timeout(Some(timeout));
+        }
+        Ok(Value::Null)
+    })
+}
+
+/// tcp_client_status(id) -> { id, client_id, host, port, timeout_secs, is_connected }
 fn native_tcp_client_status(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let client_id = match &args[0] { Value::String(id) => id, _ => return Err(RuntimeError::TypeError("id deve ser string".to_string())) };
-    let clients = TCP_CLIENTS.lock().unwrap();
-    let client = clients.get(client_id).ok_or_else(|| RuntimeError::ArgumentError("Cliente não encontrado".to_string()))?;
-    
-    let stream_lock = client.stream.lock().unwrap();
-    let mut props = HashMap::new();
-    props.insert("id".to_string(), Value::String(client.client_id.clone()));
-    props.insert("is_connected".to_string(), Value::Bool(stream_lock.is_some()));
-    
-    let id = heap.allocate(crate::heap::ManagedObject::Object { properties: props, methods: HashMap::new() });
+    let client_id = string_arg(args, 0, "id", "tcp_client_status")?;
+    let props = with_client(&client_id, |client| {
+        let mut props = HashMap::new();
+        props.insert("id".to_string(), Value::String(client.client_id.clone()));
+        props.insert("client_id".to_string(), Value::String(client.client_id.clone()));
+        props.insert("host".to_string(), Value::String(client.host.clone()));
+        props.insert("port".to_string(), Value::Number(client.port as f64));
+        props.insert("timeout_secs".to_string(), Value::Number(client.timeout.as_secs_f64()));
+        let connected = client.connection.as_ref().is_some_and(|c| c.open.load(Ordering::SeqCst));
+        props.insert("is_connected".to_string(), Value::Bool(connected));
+        Ok(props)
+    })?;
+
+    let id = heap.allocate(ManagedObject::Object { properties: props, methods: HashMap::new() });
     Ok(Value::Object(id))
 }
 
@@ -304,8 +1146,8 @@ fn native_tcp_client_status(args: &[Value], _manager: &crate::native_modules::Na
 // ========================
 
 fn native_tcp_resolve_hostname(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
-    let hostname = match &args[0] { Value::String(h) => h, _ => return Err(RuntimeError::TypeError("host deve ser string".to_string())) };
-    match std::net::ToSocketAddrs::to_socket_addrs(&format!("{}:80", hostname)) {
+    let hostname = string_arg(args, 0, "host", "tcp_resolve_hostname")?;
+    match (hostname.as_str(), 80).to_socket_addrs() {
         Ok(mut addrs) => {
             if let Some(addr) = addrs.next() { Ok(Value::String(addr.ip().to_string())) }
             else { Err(RuntimeError::NetworkError("Falha ao resolver".to_string())) }
@@ -319,4 +1161,12 @@ fn native_tcp_get_local_ip(_args: &[Value], _manager: &crate::native_modules::Na
     socket.connect("8.8.8.8:80").map_err(|e| RuntimeError::NetworkError(e.to_string()))?;
     let addr = socket.local_addr().map_err(|e| RuntimeError::NetworkError(e.to_string()))?;
     Ok(Value::String(addr.ip().to_string()))
-}
\ No newline at end of file
+}
+
+/// tcp_port_available(port) -> bool
+/// Verifica se é possível fazer bind na porta em 127.0.0.1
+fn native_tcp_port_available(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let port = port_arg(args.first(), "tcp_port_available")?
+        .ok_or_else(|| RuntimeError::ArgumentError("tcp_port_available(port)".to_string()))?;
+    Ok(Value::Bool(TcpListener::bind(("127.0.0.1", port)).is_ok()))
+}
@@ -4,6 +4,10 @@ use crate::errors::RuntimeError;
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
 use std::thread;
+use crate::datetime::{self, DateTime, Zone};
+use crate::heap::{Heap, ManagedObject};
+use crate::native_modules::NativeModuleManager;
+use chrono::{NaiveDate, NaiveTime, TimeDelta};
 
 /// Registra todas as funções nativas do módulo time
 pub fn register_time_functions(map: &mut HashMap<String, NativeFunction>) {
@@ -15,6 +19,23 @@ pub fn register_time_functions(map: &mut HashMap<String, NativeFunction>) {
     map.insert("native_format_date".to_string(), native_format_date);
     map.insert("native_uptime".to_string(), native_uptime);
     map.insert("current_timestamp".to_string(), native_timestamp); // Alias para native_timestamp
+
+    // Valores de primeira classe: DateTime, Date, Duration e TimeZone
+    map.insert("datetime_now".to_string(), native_datetime_now);
+    map.insert("datetime".to_string(), native_datetime);
+    map.insert("datetime_parse".to_string(), native_datetime_parse);
+    map.insert("datetime_from_timestamp".to_string(), native_datetime_from_timestamp);
+    map.insert("date".to_string(), native_date_new);
+    map.insert("date_today".to_string(), native_date_today);
+    map.insert("date_parse".to_string(), native_date_parse);
+    map.insert("duration".to_string(), native_duration);
+    map.insert("duration_parse".to_string(), native_duration_parse);
+    map.insert("timezone".to_string(), native_timezone);
+    map.insert("timezone_names".to_string(), native_timezone_names);
+    map.insert("is_leap_year".to_string(), native_is_leap_year);
+    map.insert("days_in_month".to_string(), native_days_in_month);
+    map.insert("monotonic".to_string(), native_monotonic);
+    map.insert("elapsed".to_string(), native_elapsed);
 }
 
 // Variável global para armazenar o tempo de início da aplicação
@@ -55,6 +76,12 @@ fn native_sleep(args: &[Value], _manager: &crate::native_modules::NativeModuleMa
             }
             *n as u64
         }
+        Value::Duration(d) => {
+            if *d < chrono::TimeDelta::zero() {
+                return Err(RuntimeError::ArgumentError("Tempo de sleep não pode ser negativo".to_string()));
+            }
+            d.num_milliseconds() as u64
+        }
         _ => return Err(RuntimeError::TypeError("Argumento deve ser um número".to_string())),
     };
 
@@ -173,3 +200,220 @@ fn native_uptime(_args: &[Value], _manager: &crate::native_modules::NativeModule
     let elapsed = start.elapsed();
     Ok(Value::Number(elapsed.as_millis() as f64))
 }
+
+fn time_error(message: String) -> RuntimeError {
+    RuntimeError::ArgumentError(message)
+}
+
+fn zone_at(args: &[Value], index: usize) -> Result<Option<Zone>, RuntimeError> {
+    datetime::zone_arg(args.get(index)).map_err(time_error)
+}
+
+fn text_at<'a>(args: &'a [Value], index: usize, function: &str) -> Result<&'a str, RuntimeError> {
+    match args.get(index) {
+        Some(Value::String(s)) => Ok(s),
+        _ => Err(RuntimeError::TypeError(format!("{}: argumento {} deve ser uma string", function, index + 1))),
+    }
+}
+
+/// Texto opcional; `null` conta como ausente
+fn optional_text(args: &[Value], index: usize, function: &str) -> Result<Option<String>, RuntimeError> {
+    match args.get(index) {
+        None | Some(Value::Null) => Ok(None),
+        Some(_) => text_at(args, index, function).map(|s| Some(s.to_string())),
+    }
+}
+
+fn integer_at(args: &[Value], index: usize, function: &str) -> Result<i64, RuntimeError> {
+    match args.get(index) {
+        Some(Value::Number(n)) if n.fract() == 0.0 => Ok(*n as i64),
+        Some(Value::Int(i)) => Ok(*i),
+        _ => Err(RuntimeError::TypeError(format!("{}: argumento {} deve ser um número inteiro", function, index + 1))),
+    }
+}
+
+/// DateTime atual
+/// Entrada: fuso opcional (nome IANA, "local", "-03:00" ou TimeZone); padrão UTC
+/// Retorna: um DateTime
+fn native_datetime_now(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let zone = zone_at(args, 0)?.unwrap_or(Zone::Utc);
+    Ok(Value::DateTime(DateTime::now(zone)))
+}
+
+/// Monta um DateTime a partir dos campos da hora local
+/// Entrada: ano, mês, dia, e opcionalmente hora, minuto, segundo, milissegundo; por último, o fuso
+/// Retorna: um DateTime
+fn native_datetime(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let zone_index = args.iter().position(|arg| matches!(arg, Value::String(_) | Value::TimeZone(_)));
+    let fields = &args[..zone_index.unwrap_or(args.len())];
+    if fields.len() < 3 || fields.len() > 7 {
+        return Err(RuntimeError::ArgumentError(
+            "datetime espera (ano, mês, dia, hora?, minuto?, segundo?, ms?, fuso?)".to_string(),
+        ));
+    }
+    let zone = datetime::zone_arg(zone_index.map(|i| &args[i])).map_err(time_error)?.unwrap_or(Zone::Utc);
+    let field = |i: usize| if i < fields.len() { integer_at(fields, i, "datetime") } else { Ok(0) };
+    let (year, month, day) = (field(0)?, field(1)?, field(2)?);
+    let date = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+        .ok_or_else(|| time_error(format!("Data inválida: {:04}-{:02}-{:02}", year, month, day)))?;
+    let time = NaiveTime::from_hms_milli_opt(field(3)? as u32, field(4)? as u32, field(5)? as u32, field(6)? as u32)
+        .ok_or_else(|| time_error("Hora inválida".to_string()))?;
+    DateTime::from_local(date.and_time(time), zone).map(Value::DateTime).map_err(time_error)
+}
+
+/// Lê um DateTime de um texto
+/// Entrada: o texto, um formato strftime opcional e um fuso opcional
+/// Retorna: um DateTime (RFC 3339 quando não há formato)
+fn native_datetime_parse(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let text = text_at(args, 0, "datetime_parse")?;
+    // datetime_parse(texto, fuso) também é aceito quando o segundo argumento é um TimeZone
+    let (pattern, zone) = match args.get(1) {
+        Some(Value::TimeZone(zone)) => (None, Some(*zone)),
+        _ => (optional_text(args, 1, "datetime_parse")?, zone_at(args, 2)?),
+    };
+    datetime::parse_datetime(text, pattern.as_deref(), zone).map(Value::DateTime).map_err(time_error)
+}
+
+/// DateTime a partir de milissegundos desde a época Unix
+/// Entrada: o timestamp em milissegundos e um fuso opcional
+/// Retorna: um DateTime
+fn native_datetime_from_timestamp(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let ms = match args.first() {
+        Some(Value::Number(n)) => *n,
+        Some(Value::Int(i)) => *i as f64,
+        _ => return Err(RuntimeError::TypeError("datetime_from_timestamp espera milissegundos".to_string())),
+    };
+    let zone = zone_at(args, 1)?.unwrap_or(Zone::Utc);
+    DateTime::from_timestamp_ms(ms, zone).map(Value::DateTime).map_err(time_error)
+}
+
+/// Monta uma Date
+/// Entrada: ano, mês e dia
+/// Retorna: uma Date
+fn native_date_new(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let (year, month, day) = (integer_at(args, 0, "date")?, integer_at(args, 1, "date")?, integer_at(args, 2, "date")?);
+    NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+        .map(Value::Date)
+        .ok_or_else(|| time_error(format!("Data inválida: {:04}-{:02}-{:02}", year, month, day)))
+}
+
+/// Data de hoje
+/// Entrada: fuso opcional; padrão UTC
+/// Retorna: uma Date
+fn native_date_today(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let zone = zone_at(args, 0)?.unwrap_or(Zone::Utc);
+    Ok(Value::Date(DateTime::now(zone).local().date()))
+}
+
+/// Lê uma Date de um texto
+/// Entrada: o texto e um formato strftime opcional (padrão "%Y-%m-%d")
+/// Retorna: uma Date
+fn native_date_parse(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let text = text_at(args, 0, "date_parse")?;
+    let pattern = optional_text(args, 1, "date_parse")?;
+    datetime::parse_date(text, pattern.as_deref()).map(Value::Date).map_err(time_error)
+}
+
+/// Monta uma Duration
+/// Entrada: milissegundos, ou um objeto { weeks, days, hours, minutes, seconds, ms }
+/// Retorna: uma Duration
+fn native_duration(args: &[Value], _manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let seconds = match args.first() {
+        Some(Value::Number(ms)) => ms / 1000.0,
+        Some(Value::Int(ms)) => *ms as f64 / 1000.0,
+        Some(Value::Object(id)) => {
+            let Some(ManagedObject::Object { properties, .. }) = heap.get(*id) else {
+                return Err(RuntimeError::HeapError("Object reference not found".to_string()));
+            };
+            let mut seconds = 0.0;
+            for (key, value) in properties {
+                let factor = match key.as_str() {
+                    "weeks" => 604_800.0,
+                    "days" => 86_400.0,
+                    "hours" => 3_600.0,
+                    "minutes" => 60.0,
+                    "seconds" => 1.0,
+                    "ms" => 1e-3,
+                    _ => return Err(time_error(format!("duration: campo desconhecido '{}'", key))),
+                };
+                let amount = match value {
+                    Value::Number(n) => *n,
+                    Value::Int(i) => *i as f64,
+                    _ => return Err(RuntimeError::TypeError(format!("duration: '{}' deve ser um número", key))),
+                };
+                seconds += amount * factor;
+            }
+            seconds
+        }
+        _ => {
+            return Err(RuntimeError::TypeError(
+                "duration espera milissegundos ou um objeto { hours, minutes, ... }".to_string(),
+            ))
+        }
+    };
+    datetime::delta_from_secs(seconds).map(Value::Duration).map_err(time_error)
+}
+
+/// Lê uma Duration
+/// Entrada: ISO 8601 ("PT1H30M") ou compacto ("1h30m", "250ms")
+/// Retorna: uma Duration
+fn native_duration_parse(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let text = text_at(args, 0, "duration_parse")?;
+    datetime::parse_duration(text).map(Value::Duration).map_err(time_error)
+}
+
+/// Fuso horário pelo nome
+/// Entrada: nome IANA ("America/Sao_Paulo"), "UTC", "local" ou deslocamento ("-03:00")
+/// Retorna: um TimeZone
+fn native_timezone(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let name = text_at(args, 0, "timezone")?;
+    Zone::parse(name).map(Value::TimeZone).map_err(time_error)
+}
+
+/// Nomes da base IANA embutida
+/// Entrada: nenhum
+/// Retorna: array de strings
+fn native_timezone_names(_args: &[Value], _manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let names = chrono_tz::TZ_VARIANTS.iter().map(|tz| Value::String(tz.name().to_string())).collect();
+    Ok(Value::Array(heap.allocate(ManagedObject::Array(names))))
+}
+
+/// Entrada: ano
+/// Retorna: true se o ano é bissexto
+fn native_is_leap_year(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let year = integer_at(args, 0, "is_leap_year")?;
+    Ok(Value::Bool(datetime::is_leap_year(year as i32)))
+}
+
+/// Entrada: ano e mês (1-12)
+/// Retorna: número de dias do mês
+fn native_days_in_month(args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let (year, month) = (integer_at(args, 0, "days_in_month")?, integer_at(args, 1, "days_in_month")?);
+    u32::try_from(month)
+        .ok()
+        .and_then(|month| datetime::days_in_month(year as i32, month))
+        .map(|days| Value::Number(days as f64))
+        .ok_or_else(|| time_error(format!("Mês inválido: {}", month)))
+}
+
+/// Relógio monotônico para medir intervalos (não muda com o relógio do sistema)
+/// Entrada: nenhum
+/// Retorna: Duration desde o início do programa
+fn native_monotonic(_args: &[Value], _manager: &NativeModuleManager, _heap: &mut Heap) -> Result<Value, RuntimeError> {
+    TimeDelta::from_std(get_start_time().elapsed())
+        .map(Value::Duration)
+        .map_err(|e| RuntimeError::SystemError(e.to_string()))
+}
+
+/// Tempo decorrido desde uma leitura de `monotonic()`
+/// Entrada: a Duration retornada por monotonic()
+/// Retorna: uma Duration
+fn native_elapsed(args: &[Value], manager: &NativeModuleManager, heap: &mut Heap) -> Result<Value, RuntimeError> {
+    let Some(Value::Duration(start)) = args.first() else {
+        return Err(RuntimeError::TypeError("elapsed espera o valor de monotonic()".to_string()));
+    };
+    match native_monotonic(&[], manager, heap)? {
+        Value::Duration(now) => Ok(Value::Duration(now - *start)),
+        other => Ok(other),
+    }
+}
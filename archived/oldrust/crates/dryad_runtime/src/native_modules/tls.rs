@@ -0,0 +1,441 @@
+//! TLS (rustls) compartilhado pelos servidores e clientes de rede.
+//!
+//! As configurações vêm de objetos Dryad:
+//!
+//! - servidor: `{ cert, key, ca?, client_auth?, alpn? }`, com `client_auth`
+//!   `"none"` (padrão), `"optional"` ou `"required"`;
+//! - cliente: `{ ca?, cert?, key?, alpn?, server_name?, verify? }`.
+//!
+//! `cert`, `key` e `ca` aceitam o caminho de um arquivo PEM ou o próprio
+//! conteúdo PEM. `alpn` é a lista de protocolos em ordem de preferência.
+//! Os sockets síncronos (TCP e HTTP) usam `rustls` direto; o WebSocket
+//! assíncrono usa as mesmas configurações via `tokio-rustls`.
+
+use crate::errors::RuntimeError;
+use crate::heap::{Heap, ManagedObject};
+use crate::interpreter::Value;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{
+    ClientConfig, ClientConnection, CommonState, DigitallySignedStruct, ProtocolVersion, RootCertStore,
+    ServerConnection, SignatureScheme, StreamOwned,
+};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Tempo máximo de um handshake do lado servidor
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Configuração TLS de um servidor, compartilhada pelas conexões aceitas
+pub type ServerTls = Arc<rustls::ServerConfig>;
+
+/// Socket de uma conexão aceita ou aberta, com ou sem TLS
+pub enum NetStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream>),
+}
+
+impl NetStream {
+    /// Socket subjacente (timeouts, endereço, shutdown)
+    pub fn socket(&self) -> &TcpStream {
+        match self {
+            NetStream::Plain(stream) => stream,
+            NetStream::Tls(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl Read for NetStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            NetStream::Plain(stream) => stream.read(buf),
+            NetStream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for NetStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            NetStream::Plain(stream) => stream.write(buf),
+            NetStream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            NetStream::Plain(stream) => stream.flush(),
+            NetStream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Sessão TLS já negociada sobre um socket síncrono
+pub enum TlsStream {
+    Server(StreamOwned<ServerConnection, TcpStream>),
+    Client(StreamOwned<ClientConnection, TcpStream>),
+}
+
+impl TlsStream {
+    pub fn get_ref(&self) -> &TcpStream {
+        match self {
+            TlsStream::Server(stream) => stream.get_ref(),
+            TlsStream::Client(stream) => stream.get_ref(),
+        }
+    }
+
+    fn state(&self) -> &CommonState {
+        match self {
+            TlsStream::Server(stream) => &stream.conn,
+            TlsStream::Client(stream) => &stream.conn,
+        }
+    }
+
+    /// Envia close_notify, sinalizando o fim da escrita
+    pub fn shutdown(&mut self) -> io::Result<()> {
+        match self {
+            TlsStream::Server(stream) => stream.conn.send_close_notify(),
+            TlsStream::Client(stream) => stream.conn.send_close_notify(),
+        }
+        self.flush()
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            TlsStream::Server(stream) => stream.read(buf),
+            TlsStream::Client(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            TlsStream::Server(stream) => stream.write(buf),
+            TlsStream::Client(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            TlsStream::Server(stream) => stream.flush(),
+            TlsStream::Client(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Dados da sessão negociada, expostos ao script
+#[derive(Clone, Debug)]
+pub struct TlsInfo {
+    pub version: String,
+    pub cipher: Option<String>,
+    pub alpn: Option<String>,
+    pub peer_subject: Option<String>,
+}
+
+impl TlsInfo {
+    pub fn of(stream: &TlsStream) -> Self {
+        let state = stream.state();
+        let version = match state.protocol_version() {
+            Some(ProtocolVersion::TLSv1_3) => "TLSv1.3".to_string(),
+            Some(ProtocolVersion::TLSv1_2) => "TLSv1.2".to_string(),
+            Some(other) => format!("{:?}", other),
+            None => "desconhecida".to_string(),
+        };
+        TlsInfo {
+            version,
+            cipher: state.negotiated_cipher_suite().map(|suite| {
+                let suite = suite.suite();
+                suite.as_str().map(str::to_string).unwrap_or_else(|| format!("{:?}", suite))
+            }),
+            alpn: state.alpn_protocol().map(|p| String::from_utf8_lossy(p).into_owned()),
+            peer_subject: state.peer_certificates().and_then(|chain| chain.first()).and_then(subject_of),
+        }
+    }
+
+    /// `{ version, cipher, alpn, peer_subject }`
+    pub fn to_value(&self, heap: &mut Heap) -> Value {
+        let optional = |value: &Option<String>| value.clone().map(Value::String).unwrap_or(Value::Null);
+        let mut properties = HashMap::new();
+        properties.insert("version".to_string(), Value::String(self.version.clone()));
+        properties.insert("cipher".to_string(), optional(&self.cipher));
+        properties.insert("alpn".to_string(), optional(&self.alpn));
+        properties.insert("peer_subject".to_string(), optional(&self.peer_subject));
+        Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+    }
+}
+
+/// `CN=cliente, O=Dryad`
+fn subject_of(cert: &CertificateDer<'_>) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(cert).ok()?;
+    Some(cert.subject().to_string())
+}
+
+// ========================
+// Leitura das opções
+// ========================
+
+fn tls_error(function: &str, message: impl std::fmt::Display) -> RuntimeError {
+    RuntimeError::NetworkError(format!("{}: TLS: {}", function, message))
+}
+
+/// Criptografia usada por todas as configurações (independe do provider
+/// padrão do processo)
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn options<'h>(value: &Value, heap: &'h Heap, function: &str) -> Result<&'h HashMap<String, Value>, RuntimeError> {
+    match value {
+        Value::Object(id) => match heap.get(*id) {
+            Some(ManagedObject::Object { properties, .. }) => Ok(properties),
+            _ => Err(RuntimeError::HeapError("Objeto de opções TLS não encontrado".to_string())),
+        },
+        _ => Err(RuntimeError::TypeError(format!("{}: opções TLS devem ser um objeto", function))),
+    }
+}
+
+/// Conteúdo PEM de uma opção: o texto em si ou o arquivo indicado
+fn pem_option(options: &HashMap<String, Value>, key: &str, function: &str) -> Result<Option<Vec<u8>>, RuntimeError> {
+    match options.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(text)) if text.contains("-----BEGIN") => Ok(Some(text.as_bytes().to_vec())),
+        Some(Value::String(path)) => std::fs::read(path)
+            .map(Some)
+            .map_err(|e| tls_error(function, format!("erro ao ler '{}' ({}): {}", key, path, e))),
+        Some(_) => Err(RuntimeError::TypeError(format!("{}: opção TLS '{}' deve ser string", function, key))),
+    }
+}
+
+fn certificates(pem: &[u8], key: &str, function: &str) -> Result<Vec<CertificateDer<'static>>, RuntimeError> {
+    let certs = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| tls_error(function, format!("'{}' inválido: {}", key, e)))?;
+    if certs.is_empty() {
+        return Err(tls_error(function, format!("'{}' não contém certificados", key)));
+    }
+    Ok(certs)
+}
+
+fn private_key(pem: &[u8], function: &str) -> Result<PrivateKeyDer<'static>, RuntimeError> {
+    PrivateKeyDer::from_pem_slice(pem).map_err(|e| tls_error(function, format!("'key' inválida: {}", e)))
+}
+
+/// Adiciona os certificados de `ca` às raízes confiáveis
+fn add_roots(roots: &mut RootCertStore, pem: &[u8], function: &str) -> Result<(), RuntimeError> {
+    for cert in certificates(pem, "ca", function)? {
+        roots
+            .add(cert)
+            .map_err(|e| tls_error(function, format!("'ca' inválido: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Protocolos ALPN, na ordem de preferência
+fn alpn_option(options: &HashMap<String, Value>, heap: &Heap, function: &str) -> Result<Vec<Vec<u8>>, RuntimeError> {
+    let items = match options.get("alpn") {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Array(id)) => match heap.get(*id) {
+            Some(ManagedObject::Array(items)) => items,
+            _ => return Err(RuntimeError::HeapError("Array ALPN não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError(format!("{}: 'alpn' deve ser um array de strings", function))),
+    };
+    items
+        .iter()
+        .map(|item| match item {
+            Value::String(protocol) if !protocol.is_empty() && protocol.len() < 256 => Ok(protocol.as_bytes().to_vec()),
+            _ => Err(RuntimeError::TypeError(format!("{}: protocolo ALPN inválido em 'alpn'", function))),
+        })
+        .collect()
+}
+
+/// Monta a configuração de um servidor a partir das opções TLS
+pub fn server_config(value: &Value, heap: &Heap, function: &str) -> Result<ServerTls, RuntimeError> {
+    let options = options(value, heap, function)?;
+    let cert = pem_option(options, "cert", function)?.ok_or_else(|| tls_error(function, "opção 'cert' é obrigatória"))?;
+    let key = pem_option(options, "key", function)?.ok_or_else(|| tls_error(function, "opção 'key' é obrigatória"))?;
+    let chain = certificates(&cert, "cert", function)?;
+    let key = private_key(&key, function)?;
+
+    let client_auth = match options.get("client_auth") {
+        None | Some(Value::Null) => "none",
+        Some(Value::String(mode)) if matches!(mode.as_str(), "none" | "optional" | "required") => mode.as_str(),
+        Some(_) => {
+            return Err(RuntimeError::ArgumentError(format!(
+                "{}: 'client_auth' deve ser \"none\", \"optional\" ou \"required\"",
+                function
+            )))
+        }
+    };
+    let builder = rustls::ServerConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| tls_error(function, e))?;
+    let builder = if client_auth == "none" {
+        builder.with_no_client_auth()
+    } else {
+        let ca = pem_option(options, "ca", function)?
+            .ok_or_else(|| tls_error(function, "'client_auth' requer a opção 'ca'"))?;
+        let mut roots = RootCertStore::empty();
+        add_roots(&mut roots, &ca, function)?;
+        let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider());
+        let verifier = if client_auth == "optional" { verifier.allow_unauthenticated() } else { verifier };
+        builder.with_client_cert_verifier(verifier.build().map_err(|e| tls_error(function, e))?)
+    };
+
+    let mut config = builder
+        .with_single_cert(chain, key)
+        .map_err(|e| tls_error(function, format!("'key' não corresponde ao certificado: {}", e)))?;
+    // O servidor escolhe o primeiro protocolo da sua lista que o cliente também oferece
+    config.alpn_protocols = alpn_option(options, heap, function)?;
+    Ok(Arc::new(config))
+}
+
+/// Completa o handshake antes de entregar a sessão, para que falhas de
+/// certificado apareçam já no accept/connect
+fn handshake<C, S>(mut stream: StreamOwned<C, TcpStream>) -> io::Result<StreamOwned<C, TcpStream>>
+where
+    C: std::ops::DerefMut + std::ops::Deref<Target = rustls::ConnectionCommon<S>>,
+    S: rustls::SideData,
+{
+    while stream.conn.is_handshaking() {
+        stream.conn.complete_io(&mut stream.sock)?;
+    }
+    if stream.conn.wants_write() {
+        stream.conn.complete_io(&mut stream.sock)?;
+    }
+    Ok(stream)
+}
+
+/// Handshake do lado servidor, limitado por `HANDSHAKE_TIMEOUT`
+pub fn accept(config: &ServerTls, stream: TcpStream) -> io::Result<TlsStream> {
+    let previous = stream.read_timeout().unwrap_or(None);
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let connection = ServerConnection::new(config.clone()).map_err(io::Error::other)?;
+    let stream = handshake(StreamOwned::new(connection, stream))?;
+    stream.get_ref().set_read_timeout(previous)?;
+    Ok(TlsStream::Server(stream))
+}
+
+/// Verificador de `verify: false`: aceita qualquer certificado e nome de
+/// servidor, mas ainda confere as assinaturas do handshake
+#[derive(Debug)]
+struct AcceptAnyServerCert(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyServerCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Configuração TLS de um cliente
+pub struct ClientTls {
+    config: Arc<ClientConfig>,
+    server_name: Option<String>,
+}
+
+impl ClientTls {
+    /// Opções do cliente; `true` usa os padrões (raízes públicas da Mozilla)
+    pub fn from_value(value: &Value, heap: &Heap, function: &str) -> Result<Self, RuntimeError> {
+        let empty = HashMap::new();
+        let options = match value {
+            Value::Bool(true) => &empty,
+            other => options(other, heap, function)?,
+        };
+
+        let provider = provider();
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| tls_error(function, e))?;
+        let builder = if matches!(options.get("verify"), Some(Value::Bool(false))) {
+            builder.dangerous().with_custom_certificate_verifier(Arc::new(AcceptAnyServerCert(provider)))
+        } else {
+            let mut roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+            if let Some(ca) = pem_option(options, "ca", function)? {
+                add_roots(&mut roots, &ca, function)?;
+            }
+            builder.with_root_certificates(roots)
+        };
+        let mut config = match (pem_option(options, "cert", function)?, pem_option(options, "key", function)?) {
+            (Some(cert), Some(key)) => builder
+                .with_client_auth_cert(certificates(&cert, "cert", function)?, private_key(&key, function)?)
+                .map_err(|e| tls_error(function, format!("'key' não corresponde ao certificado: {}", e)))?,
+            (None, None) => builder.with_no_client_auth(),
+            _ => return Err(tls_error(function, "certificado de cliente requer 'cert' e 'key'")),
+        };
+        config.alpn_protocols = alpn_option(options, heap, function)?;
+
+        let server_name = match options.get("server_name") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(name)) => Some(name.clone()),
+            Some(_) => return Err(RuntimeError::TypeError(format!("{}: 'server_name' deve ser string", function))),
+        };
+
+        Ok(ClientTls { config: Arc::new(config), server_name })
+    }
+
+    /// Nome verificado no certificado: `server_name` ou o próprio `host`
+    fn server_name(&self, host: &str, function: &str) -> Result<ServerName<'static>, RuntimeError> {
+        let name = self.server_name.as_deref().unwrap_or(host);
+        ServerName::try_from(name.to_string()).map_err(|_| tls_error(function, format!("nome de servidor inválido: {}", name)))
+    }
+
+    /// Handshake do lado cliente sobre um socket síncrono
+    pub fn connect(&self, host: &str, stream: TcpStream, function: &str) -> Result<TlsStream, RuntimeError> {
+        let connection = ClientConnection::new(self.config.clone(), self.server_name(host, function)?)
+            .map_err(|e| tls_error(function, e))?;
+        let stream = handshake(StreamOwned::new(connection, stream)).map_err(|e| tls_error(function, e))?;
+        Ok(TlsStream::Client(stream))
+    }
+
+    /// Handshake do lado cliente sobre um socket tokio (`wss://`)
+    pub async fn connect_async(
+        &self,
+        host: &str,
+        stream: tokio::net::TcpStream,
+        function: &str,
+    ) -> Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>, RuntimeError> {
+        let name = self.server_name(host, function)?;
+        tokio_rustls::TlsConnector::from(self.config.clone())
+            .connect(name, stream)
+            .await
+            .map_err(|e| tls_error(function, e))
+    }
+}
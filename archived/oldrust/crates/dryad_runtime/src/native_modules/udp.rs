@@ -1,26 +1,56 @@
+//! Sockets UDP: servidores com handler Dryad de mensagens e clientes.
+//!
+//! A thread do servidor recebe os datagramas e enfileira cada um na fila do
+//! `UdpServers` do interpreter dono do servidor; ele chama o handler entre as
+//! instruções do script, como no servidor TCP. Dados são binários: envios aceitam string
+//! ou array de bytes.
+
 use crate::interpreter::Value;
-use crate::native_modules::NativeFunction;
+use crate::heap::{Heap, ManagedObject};
+use crate::native_modules::{NativeFunction, NativeModuleManager};
 use crate::errors::RuntimeError;
+use super::tcp::{bytes_value, data_arg, text_or_bytes};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
 use lazy_static::lazy_static;
-use std::net::{UdpSocket, ToSocketAddrs};
-use std::time::Duration;
-use std::thread;
-use std::sync::mpsc;
+use std::net::{SocketAddr, UdpSocket, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use std::thread::{self, JoinHandle};
 
 lazy_static! {
-    static ref UDP_SERVERS: Arc<Mutex<HashMap<String, ServerInstance>>> = Arc::new(Mutex::new(HashMap::new()));
     static ref UDP_CLIENTS: Arc<Mutex<HashMap<String, ClientInstance>>> = Arc::new(Mutex::new(HashMap::new()));
 }
 
-#[derive(Clone, Debug)]
+/// Servidores UDP de um interpreter e os datagramas que aguardam o handler
+/// dele. A thread de cada servidor guarda um `Arc` deste registro, então as
+/// mensagens só chegam ao interpreter dono do servidor.
+#[derive(Default)]
+pub(crate) struct UdpServers {
+    servers: Mutex<HashMap<String, ServerInstance>>,
+    pending: Mutex<Vec<PendingMessage>>,
+    /// Acorda `udp_server_wait` quando chega uma mensagem
+    ready: Condvar,
+}
+
+/// Intervalo em que a thread do servidor verifica se deve parar
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Maior datagrama UDP possível
+const MAX_DATAGRAM: usize = 65536;
+
 struct ServerInstance {
     server_id: String,
     host: String,
     port: u16,
-    is_running: bool,
-    stop_sender: Option<mpsc::Sender<()>>,
+    /// Handler de "message"
+    on_message: Option<Value>,
+    running: Option<RunningServer>,
+}
+
+struct RunningServer {
+    socket: Arc<UdpSocket>,
+    shutdown: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
 }
 
 #[derive(Clone, Debug)]
@@ -33,55 +63,92 @@ struct ClientInstance {
     is_bound: bool,
 }
 
-/// Função para inicializar servidor UDP usando threads nativas
-fn start_udp_server(server_id: String, host: String, port: u16, stop_receiver: mpsc::Receiver<()>) {
-    let bind_addr = format!("{}:{}", host, port);
-    
-    match UdpSocket::bind(&bind_addr) {
-        Ok(socket) => {
-            println!("🌐 UDP Server '{}' iniciado em {}", server_id, bind_addr);
-            
-            // Configurar timeout para operações não-bloqueantes
-            socket.set_read_timeout(Some(Duration::from_millis(100))).ok();
-            
-            let mut buffer = [0; 1024];
-            
-            loop {
-                // Verificar se deve parar o servidor
-                if stop_receiver.try_recv().is_ok() {
-                    println!("🛑 UDP Server '{}' parando...", server_id);
-                    break;
-                }
-                
-                // Tentar receber dados
-                match socket.recv_from(&mut buffer) {
-                    Ok((size, addr)) => {
-                        let data = String::from_utf8_lossy(&buffer[..size]);
-                        println!("📦 UDP Server '{}': Recebido de {}: {}", server_id, addr, data);
-                        
-                        // Echo - responder com os mesmos dados
-                        let response = format!("Echo: {}", data);
-                        if let Err(e) = socket.send_to(response.as_bytes(), addr) {
-                            eprintln!("❌ UDP Server '{}': Erro ao enviar resposta para {}: {}", server_id, addr, e);
-                        } else {
-                            println!("📤 UDP Server '{}': Enviado para {}: {}", server_id, addr, response);
-                        }
-                    }
-                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
-                        // Timeout normal, continuar
-                        continue;
-                    }
-                    Err(e) => {
-                        eprintln!("❌ UDP Server '{}': Erro ao receber dados: {}", server_id, e);
-                        break;
-                    }
-                }
+/// Datagrama recebido que aguarda o handler do interpreter
+pub struct PendingMessage {
+    server_id: String,
+    handler: Value,
+    data: Vec<u8>,
+    sender: SocketAddr,
+}
+
+impl PendingMessage {
+    pub fn handler(&self) -> &Value {
+        &self.handler
+    }
+
+    /// `(msg)` com `{ data, text, sender, host, port, server_id }`
+    pub fn arguments(&self, heap: &mut Heap) -> Vec<Value> {
+        let mut properties = HashMap::new();
+        properties.insert("data".to_string(), bytes_value(&self.data, heap));
+        let text = std::str::from_utf8(&self.data).map(|s| Value::String(s.to_string())).unwrap_or(Value::Null);
+        properties.insert("text".to_string(), text);
+        properties.insert("sender".to_string(), Value::String(self.sender.to_string()));
+        properties.insert("host".to_string(), Value::String(self.sender.ip().to_string()));
+        properties.insert("port".to_string(), Value::Number(self.sender.port() as f64));
+        properties.insert("server_id".to_string(), Value::String(self.server_id.clone()));
+        vec![Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))]
+    }
+
+    pub fn describe(&self) -> String {
+        format!("UDP '{}' (message de {})", self.server_id, self.sender)
+    }
+}
+
+/// Remove da fila as mensagens dos servidores deste interpreter; chamada
+/// por ele
+pub fn take_pending_messages(manager: &NativeModuleManager) -> Vec<PendingMessage> {
+    let mut pending = manager.udp_servers().pending.lock().unwrap();
+    if pending.is_empty() {
+        return Vec::new();
+    }
+    std::mem::take(&mut *pending)
+}
+
+/// Handlers dos servidores deste interpreter, inclusive os de mensagens na
+/// fila; o GC os trata como raízes
+pub fn registered_handlers(manager: &NativeModuleManager) -> Vec<Value> {
+    let registry = manager.udp_servers();
+    let mut values: Vec<Value> = registry
+        .servers
+        .lock()
+        .unwrap()
+        .values()
+        .filter_map(|server| server.on_message.clone())
+        .collect();
+    for message in registry.pending.lock().unwrap().iter() {
+        values.push(message.handler.clone());
+    }
+    values
+}
+
+/// Recebe datagramas até o servidor parar. Sem handler registrado os
+/// datagramas são descartados.
+fn receive_loop(registry: Arc<UdpServers>, server_id: String, socket: Arc<UdpSocket>, shutdown: Arc<AtomicBool>) {
+    let mut buffer = vec![0u8; MAX_DATAGRAM];
+    while !shutdown.load(Ordering::SeqCst) {
+        match socket.recv_from(&mut buffer) {
+            Ok((size, sender)) => {
+                let handler = registry.servers.lock().unwrap().get(&server_id).and_then(|server| server.on_message.clone());
+                let Some(handler) = handler else {
+                    eprintln!("⚠️ UDP Server '{}': nenhum handler registrado, descartando {} bytes de {}", server_id, size, sender);
+                    continue;
+                };
+                registry.pending.lock().unwrap().push(PendingMessage {
+                    server_id: server_id.clone(),
+                    handler,
+                    data: buffer[..size].to_vec(),
+                    sender,
+                });
+                registry.ready.notify_all();
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
+                // Timeout normal, continuar
+                continue;
+            }
+            Err(e) => {
+                // Erros ICMP de envios anteriores (ex.: porta inalcançável) não encerram o servidor
+                eprintln!("❌ UDP Server '{}': Erro ao receber dados: {}", server_id, e);
             }
-            
-            println!("👋 UDP Server '{}' finalizado", server_id);
-        }
-        Err(e) => {
-            eprintln!("❌ UDP Server '{}': Erro ao fazer bind em {}: {}", server_id, bind_addr, e);
         }
     }
 }
@@ -89,7 +156,10 @@ fn start_udp_server(server_id: String, host: String, port: u16, stop_receiver: m
 pub fn register_udp_functions(functions: &mut HashMap<String, NativeFunction>) {
     // Servidor UDP
     functions.insert("udp_server_create".to_string(), native_udp_server_create);
+    functions.insert("udp_server_on".to_string(), native_udp_server_on);
     functions.insert("udp_server_start".to_string(), native_udp_server_start);
+    functions.insert("udp_server_wait".to_string(), native_udp_server_wait);
+    functions.insert("udp_server_send_to".to_string(), native_udp_server_send_to);
     functions.insert("udp_server_stop".to_string(), native_udp_server_stop);
     functions.insert("udp_server_status".to_string(), native_udp_server_status);
     
@@ -148,19 +218,55 @@ fn native_udp_server_create(args: &[Value], _manager: &crate::native_modules::Na
         server_id: server_id.clone(),
         host: host.clone(),
         port,
-        is_running: false,
-        stop_sender: None,
+        on_message: None,
+        running: None,
     };
 
-    let mut servers = UDP_SERVERS.lock().unwrap();
+    let mut servers = _manager.udp_servers().servers.lock().unwrap();
     servers.insert(server_id.clone(), server);
 
     println!("✅ UDP Server '{}' criado para {}:{}", server_id, host, port);
     Ok(Value::Null)
 }
 
-/// native_udp_server_start(server_id) -> null  
-/// Inicia o servidor UDP especificado
+/// native_udp_server_on(server_id, "message", handler) -> null
+/// Registra `handler(msg)`, chamado pelo interpreter a cada datagrama com
+/// `{ data, text, sender, host, port, server_id }`; `data` é um array de
+/// bytes e `text` a string correspondente (ou null se não for UTF-8)
+fn native_udp_server_on(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.len() != 3 {
+        return Err(RuntimeError::ArgumentError("udp_server_on requer exatamente 3 argumentos: server_id, evento, handler".to_string()));
+    }
+
+    let server_id = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(RuntimeError::TypeError("server_id deve ser uma string".to_string())),
+    };
+
+    match &args[1] {
+        Value::String(event) if event == "message" => {}
+        Value::String(event) => return Err(RuntimeError::ArgumentError(format!("udp_server_on: evento desconhecido '{}' (use \"message\")", event))),
+        _ => return Err(RuntimeError::TypeError("evento deve ser uma string".to_string())),
+    }
+
+    let handler = match &args[2] {
+        handler @ (Value::Lambda(_) | Value::Function { .. }) => handler.clone(),
+        _ => return Err(RuntimeError::TypeError("handler deve ser uma função".to_string())),
+    };
+
+    let mut servers = _manager.udp_servers().servers.lock().unwrap();
+    match servers.get_mut(&server_id) {
+        Some(server) => {
+            server.on_message = Some(handler);
+            Ok(Value::Null)
+        }
+        None => Err(RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id))),
+    }
+}
+
+/// native_udp_server_start(server_id) -> null
+/// Faz o bind imediatamente (porta 0 escolhe uma livre, veja o status) e
+/// recebe datagramas em segundo plano
 fn native_udp_server_start(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("udp_server_start requer exatamente 1 argumento: server_id".to_string()));
@@ -171,35 +277,119 @@ fn native_udp_server_start(args: &[Value], _manager: &crate::native_modules::Nat
         _ => return Err(RuntimeError::TypeError("server_id deve ser uma string".to_string())),
     };
 
-    let (host, port, stop_sender) = {
-        let mut servers = UDP_SERVERS.lock().unwrap();
-        match servers.get_mut(&server_id) {
-            Some(server) => {
-                if server.is_running {
-                    return Err(RuntimeError::NetworkError(format!("UDP Server '{}' já está rodando", server_id)));
-                }
+    let registry = _manager.udp_servers();
+    let mut servers = registry.servers.lock().unwrap();
+    let server = servers
+        .get_mut(&server_id)
+        .ok_or_else(|| RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id)))?;
+    if server.running.is_some() {
+        return Err(RuntimeError::NetworkError(format!("UDP Server '{}' já está rodando", server_id)));
+    }
 
-                let (sender, receiver) = mpsc::channel();
-                server.stop_sender = Some(sender);
-                server.is_running = true;
-                
-                (server.host.clone(), server.port, receiver)
-            }
-            None => return Err(RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id))),
+    let bind_addr = format!("{}:{}", server.host, server.port);
+    let socket = UdpSocket::bind(&bind_addr)
+        .map_err(|e| RuntimeError::NetworkError(format!("UDP Server '{}': Erro ao fazer bind em {}: {}", server_id, bind_addr, e)))?;
+    socket.set_read_timeout(Some(POLL_INTERVAL)).ok();
+    if let Ok(addr) = socket.local_addr() {
+        server.port = addr.port();
+    }
+
+    let socket = Arc::new(socket);
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let (registry, server_id, socket, shutdown) = (registry.clone(), server_id.clone(), socket.clone(), shutdown.clone());
+        thread::spawn(move || receive_loop(registry, server_id, socket, shutdown))
+    };
+    server.running = Some(RunningServer { socket, shutdown, thread });
+
+    println!("🌐 UDP Server '{}' iniciado em {}:{}", server_id, server.host, server.port);
+    Ok(Value::Null)
+}
+
+/// native_udp_server_wait(server_id, timeout_ms?) -> bool
+/// Bloqueia até chegar uma mensagem para o interpreter, o tempo limite
+/// (padrão 1000 ms) expirar ou o servidor parar. Retorna se o servidor ainda
+/// está rodando, permitindo `while (udp_server_wait(id)) {}`.
+fn native_udp_server_wait(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.is_empty() || args.len() > 2 {
+        return Err(RuntimeError::ArgumentError("udp_server_wait requer 1-2 argumentos: server_id, timeout_ms (opcional)".to_string()));
+    }
+
+    let server_id = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(RuntimeError::TypeError("server_id deve ser uma string".to_string())),
+    };
+
+    let timeout = match args.get(1) {
+        Some(Value::Number(ms)) if *ms >= 0.0 => Duration::from_millis(*ms as u64),
+        Some(_) => return Err(RuntimeError::TypeError("timeout_ms deve ser um número".to_string())),
+        None => Duration::from_secs(1),
+    };
+
+    let registry = _manager.udp_servers();
+    let shutdown = match registry.servers.lock().unwrap().get(&server_id) {
+        Some(server) => server.running.as_ref().map(|running| running.shutdown.clone()),
+        None => return Err(RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id))),
+    };
+    let Some(shutdown) = shutdown else {
+        return Ok(Value::Bool(false));
+    };
+
+    let deadline = Instant::now() + timeout;
+    let mut pending = registry.pending.lock().unwrap();
+    loop {
+        if shutdown.load(Ordering::SeqCst) {
+            return Ok(Value::Bool(false));
         }
+        let now = Instant::now();
+        if pending.iter().any(|message| message.server_id == server_id) || now >= deadline {
+            return Ok(Value::Bool(true));
+        }
+        pending = registry.ready.wait_timeout(pending, deadline - now).unwrap().0;
+    }
+}
+
+/// native_udp_server_send_to(server_id, dados, host, port) -> number
+/// Envia pelo socket do servidor (a resposta sai da porta do servidor);
+/// retorna os bytes enviados
+fn native_udp_server_send_to(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    if args.len() != 4 {
+        return Err(RuntimeError::ArgumentError("udp_server_send_to requer exatamente 4 argumentos: server_id, dados, host, port".to_string()));
+    }
+
+    let server_id = match &args[0] {
+        Value::String(s) => s.clone(),
+        _ => return Err(RuntimeError::TypeError("server_id deve ser uma string".to_string())),
     };
+    let data = data_arg(args.get(1), _heap, "udp_server_send_to")?;
+    let (host, port) = address_args(&args[2], &args[3])?;
 
-    // Iniciar servidor em thread separada
-    let server_id_clone = server_id.clone();
-    thread::spawn(move || {
-        start_udp_server(server_id_clone, host, port, stop_sender);
-    });
+    let socket = match _manager.udp_servers().servers.lock().unwrap().get(&server_id) {
+        Some(server) => server.running.as_ref().map(|running| running.socket.clone()),
+        None => return Err(RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id))),
+    };
+    let socket = socket.ok_or_else(|| RuntimeError::NetworkError(format!("UDP Server '{}' não está rodando", server_id)))?;
 
-    Ok(Value::Null)
+    socket
+        .send_to(&data, (host.as_str(), port))
+        .map(|sent| Value::Number(sent as f64))
+        .map_err(|e| RuntimeError::NetworkError(format!("UDP Server '{}': Erro ao enviar para {}:{}: {}", server_id, host, port, e)))
+}
+
+fn address_args(host: &Value, port: &Value) -> Result<(String, u16), RuntimeError> {
+    let host = match host {
+        Value::String(s) => s.clone(),
+        _ => return Err(RuntimeError::TypeError("host deve ser uma string".to_string())),
+    };
+    let port = match port {
+        Value::Number(n) if (0.0..=65535.0).contains(n) => *n as u16,
+        _ => return Err(RuntimeError::TypeError("port deve ser um número entre 0 e 65535".to_string())),
+    };
+    Ok((host, port))
 }
 
 /// native_udp_server_stop(server_id) -> null
-/// Para o servidor UDP especificado
+/// Para o servidor UDP especificado e descarta mensagens pendentes
 fn native_udp_server_stop(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("udp_server_stop requer exatamente 1 argumento: server_id".to_string()));
@@ -210,23 +400,21 @@ fn native_udp_server_stop(args: &[Value], _manager: &crate::native_modules::Nati
         _ => return Err(RuntimeError::TypeError("server_id deve ser uma string".to_string())),
     };
 
-    let mut servers = UDP_SERVERS.lock().unwrap();
-    match servers.get_mut(&server_id) {
-        Some(server) => {
-            if !server.is_running {
-                return Err(RuntimeError::NetworkError(format!("UDP Server '{}' não está rodando", server_id)));
-            }
+    let registry = _manager.udp_servers();
+    let running = match registry.servers.lock().unwrap().get_mut(&server_id) {
+        Some(server) => server.running.take(),
+        None => return Err(RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id))),
+    };
+    let running = running.ok_or_else(|| RuntimeError::NetworkError(format!("UDP Server '{}' não está rodando", server_id)))?;
 
-            if let Some(sender) = server.stop_sender.take() {
-                sender.send(()).ok();
-            }
-            server.is_running = false;
-            
-            println!("🛑 UDP Server '{}' parado", server_id);
-            Ok(Value::Null)
-        }
-        None => Err(RuntimeError::NetworkError(format!("UDP Server '{}' não encontrado", server_id))),
-    }
+    running.shutdown.store(true, Ordering::SeqCst);
+    let _ = running.thread.join();
+
+    registry.pending.lock().unwrap().retain(|message| message.server_id != server_id);
+    registry.ready.notify_all();
+
+    println!("🛑 UDP Server '{}' parado", server_id);
+    Ok(Value::Null)
 }
 
 /// native_udp_server_status(server_id) -> objeto
@@ -241,14 +429,14 @@ fn native_udp_server_status(args: &[Value], _manager: &crate::native_modules::Na
         _ => return Err(RuntimeError::TypeError("server_id deve ser uma string".to_string())),
     };
 
-    let servers = UDP_SERVERS.lock().unwrap();
+    let servers = _manager.udp_servers().servers.lock().unwrap();
     match servers.get(&server_id) {
         Some(server) => {
             let mut status = HashMap::new();
             status.insert("server_id".to_string(), Value::String(server.server_id.clone()));
             status.insert("host".to_string(), Value::String(server.host.clone()));
             status.insert("port".to_string(), Value::Number(server.port as f64));
-            status.insert("is_running".to_string(), Value::Bool(server.is_running));
+            status.insert("is_running".to_string(), Value::Bool(server.running.is_some()));
             
             let id = _heap.allocate(crate::heap::ManagedObject::Object {
                 properties: status,
@@ -357,7 +545,7 @@ fn native_udp_client_bind(args: &[Value], _manager: &crate::native_modules::Nati
 }
 
 /// native_udp_client_send(client_id, message) -> bool
-/// Envia dados para o servidor configurado
+/// Envia dados (string, número, bool ou array de bytes) para o servidor configurado
 fn native_udp_client_send(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(RuntimeError::ArgumentError("udp_client_send requer exatamente 2 argumentos: client_id, message".to_string()));
@@ -369,10 +557,9 @@ fn native_udp_client_send(args: &[Value], _manager: &crate::native_modules::Nati
     };
 
     let message = match &args[1] {
-        Value::String(s) => s.clone(),
-        Value::Number(n) => n.to_string(),
-        Value::Bool(b) => b.to_string(),
-        _ => return Err(RuntimeError::TypeError("message deve ser uma string, número ou bool".to_string())),
+        Value::Number(n) => n.to_string().into_bytes(),
+        Value::Bool(b) => b.to_string().into_bytes(),
+        other => data_arg(Some(other), _heap, "udp_client_send")?,
     };
 
     let clients = UDP_CLIENTS.lock().unwrap();
@@ -380,9 +567,9 @@ fn native_udp_client_send(args: &[Value], _manager: &crate::native_modules::Nati
         Some(client) => {
             if let Some(socket) = &client.socket {
                 let addr = format!("{}:{}", client.host, client.port);
-                match socket.send_to(message.as_bytes(), &addr) {
+                match socket.send_to(&message, &addr) {
                     Ok(_) => {
-                        println!("📤 UDP Client '{}' enviou dados: {}", client_id, message);
+                        println!("📤 UDP Client '{}' enviou {} bytes", client_id, message.len());
                         Ok(Value::Bool(true))
                     }
                     Err(e) => {
@@ -398,8 +585,9 @@ fn native_udp_client_send(args: &[Value], _manager: &crate::native_modules::Nati
     }
 }
 
-/// native_udp_client_receive(client_id) -> string
-/// Recebe dados do socket UDP (do último remetente)
+/// native_udp_client_receive(client_id) -> string | array de bytes
+/// Recebe um datagrama: string se for UTF-8 válido, senão array de bytes;
+/// string vazia quando o timeout expira
 fn native_udp_client_receive(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 1 {
         return Err(RuntimeError::ArgumentError("udp_client_receive requer exatamente 1 argumento: client_id".to_string()));
@@ -414,12 +602,11 @@ fn native_udp_client_receive(args: &[Value], _manager: &crate::native_modules::N
     match clients.get(&client_id) {
         Some(client) => {
             if let Some(socket) = &client.socket {
-                let mut buffer = [0; 1024];
+                let mut buffer = vec![0u8; MAX_DATAGRAM];
                 match socket.recv_from(&mut buffer) {
                     Ok((size, _addr)) => {
-                        let data = String::from_utf8_lossy(&buffer[..size]).to_string();
-                        println!("📥 UDP Client '{}' recebeu dados: {}", client_id, data);
-                        Ok(Value::String(data))
+                        println!("📥 UDP Client '{}' recebeu {} bytes", client_id, size);
+                        Ok(text_or_bytes(buffer[..size].to_vec(), _heap))
                     }
                     Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {
                         Ok(Value::String("".to_string()))
@@ -450,10 +637,9 @@ fn native_udp_client_send_to(args: &[Value], _manager: &crate::native_modules::N
     };
 
     let message = match &args[1] {
-        Value::String(s) => s.clone(),
-        Value::Number(n) => n.to_string(),
-        Value::Bool(b) => b.to_string(),
-        _ => return Err(RuntimeError::TypeError("message deve ser uma string, número ou bool".to_string())),
+        Value::Number(n) => n.to_string().into_bytes(),
+        Value::Bool(b) => b.to_string().into_bytes(),
+        other => data_arg(Some(other), _heap, "udp_client_send_to")?,
     };
 
     let host = match &args[2] {
@@ -471,9 +657,9 @@ fn native_udp_client_send_to(args: &[Value], _manager: &crate::native_modules::N
         Some(client) => {
             if let Some(socket) = &client.socket {
                 let addr = format!("{}:{}", host, port);
-                match socket.send_to(message.as_bytes(), &addr) {
+                match socket.send_to(&message, &addr) {
                     Ok(_) => {
-                        println!("📤 UDP Client '{}' enviou dados para {}: {}", client_id, addr, message);
+                        println!("📤 UDP Client '{}' enviou {} bytes para {}", client_id, message.len(), addr);
                         Ok(Value::Bool(true))
                     }
                     Err(e) => {
@@ -505,17 +691,16 @@ fn native_udp_client_receive_from(args: &[Value], _manager: &crate::native_modul
     match clients.get(&client_id) {
         Some(client) => {
             if let Some(socket) = &client.socket {
-                let mut buffer = [0; 1024];
+                let mut buffer = vec![0u8; MAX_DATAGRAM];
                 match socket.recv_from(&mut buffer) {
                     Ok((size, addr)) => {
-                        let data = String::from_utf8_lossy(&buffer[..size]).to_string();
-                        let addr_str = addr.to_string();
-                        
-                        println!("📥 UDP Client '{}' recebeu de {}: {}", client_id, addr, data);
+                        println!("📥 UDP Client '{}' recebeu {} bytes de {}", client_id, size, addr);
                         
                         let mut result = HashMap::new();
-                        result.insert("data".to_string(), Value::String(data));
-                        result.insert("sender".to_string(), Value::String(addr_str));
+                        result.insert("data".to_string(), text_or_bytes(buffer[..size].to_vec(), _heap));
+                        result.insert("sender".to_string(), Value::String(addr.to_string()));
+                        result.insert("host".to_string(), Value::String(addr.ip().to_string()));
+                        result.insert("port".to_string(), Value::Number(addr.port() as f64));
                         result.insert("success".to_string(), Value::Bool(true));
                         
                         let id = _heap.allocate(crate::heap::ManagedObject::Object {
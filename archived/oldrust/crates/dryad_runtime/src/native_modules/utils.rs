@@ -3,6 +3,7 @@ use crate::native_modules::NativeFunction;
 use crate::errors::RuntimeError;
 use crate::heap::{Heap, ManagedObject};
 use std::collections::HashMap;
+use crate::regexp::{self, Regexp};
 use regex::Regex;
 use rand::{RngCore, SeedableRng, Rng};
 use rand_chacha::ChaCha20Rng;
@@ -32,6 +33,8 @@ pub fn register_utils_functions(functions: &mut HashMap<String, NativeFunction>)
     functions.insert("native_regex_replace".to_string(), native_regex_replace);
     functions.insert("native_regex_split".to_string(), native_regex_split);
     functions.insert("native_regex_test".to_string(), native_regex_test);
+    functions.insert("regex".to_string(), native_regex);
+    functions.insert("regex_escape".to_string(), native_regex_escape);
 }
 
 // ============================================
@@ -367,46 +370,45 @@ fn native_random_seed(args: &[Value], _manager: &crate::native_modules::NativeMo
 // EXPRESSÕES REGULARES
 // ============================================
 
+/// Padrão das funções `native_regex_*`: string (compilada uma vez, via cache) ou `Regex`
+fn regex_arg(value: &Value, function: &str) -> Result<Regex, RuntimeError> {
+    regexp::from_value(value)
+        .map(|compiled| compiled.regex)
+        .map_err(|e| RuntimeError::ArgumentError(format!("{}: {}", function, e)))
+}
+
 /// native_regex_match(pattern, string) -> array ou null
 fn native_regex_match(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
     if args.len() != 2 {
         return Err(RuntimeError::ArgumentError("native_regex_match: esperado 2 argumentos (pattern, string)".to_string()));
     }
     
-    let pattern = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("native_regex_match: primeiro argumento deve ser string".to_string())),
-    };
+    let pattern = regex_arg(&args[0], "native_regex_match")?;
     
     let text = match &args[1] {
         Value::String(s) => s,
         _ => return Err(RuntimeError::TypeError("native_regex_match: segundo argumento deve ser string".to_string())),
     };
     
-    match Regex::new(pattern) {
-        Ok(re) => {
-            if let Some(captures) = re.captures(text) {
-                let mut groups = Vec::new();
-                
-                if let Some(full_match) = captures.get(0) {
-                    groups.push(Value::String(full_match.as_str().to_string()));
-                }
-                
-                for i in 1..captures.len() {
-                    if let Some(group) = captures.get(i) {
-                        groups.push(Value::String(group.as_str().to_string()));
-                    } else {
-                        groups.push(Value::Null);
-                    }
-                }
-                
-                let id = _heap.allocate(ManagedObject::Array(groups));
-                Ok(Value::Array(id))
+    if let Some(captures) = pattern.captures(text) {
+        let mut groups = Vec::new();
+        
+        if let Some(full_match) = captures.get(0) {
+            groups.push(Value::String(full_match.as_str().to_string()));
+        }
+        
+        for i in 1..captures.len() {
+            if let Some(group) = captures.get(i) {
+                groups.push(Value::String(group.as_str().to_string()));
             } else {
-                Ok(Value::Null)
+                groups.push(Value::Null);
             }
-        },
-        Err(e) => Err(RuntimeError::Generic(format!("Erro no padrão regex: {}", e))),
+        }
+        
+        let id = _heap.allocate(ManagedObject::Array(groups));
+        Ok(Value::Array(id))
+    } else {
+        Ok(Value::Null)
     }
 }
 
@@ -416,10 +418,7 @@ fn native_regex_replace(args: &[Value], _manager: &crate::native_modules::Native
         return Err(RuntimeError::ArgumentError("native_regex_replace: esperado 3 argumentos (pattern, replacement, string)".to_string()));
     }
     
-    let pattern = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("native_regex_replace: primeiro argumento deve ser string".to_string())),
-    };
+    let pattern = regex_arg(&args[0], "native_regex_replace")?;
     
     let replacement = match &args[1] {
         Value::String(s) => s,
@@ -431,13 +430,8 @@ fn native_regex_replace(args: &[Value], _manager: &crate::native_modules::Native
         _ => return Err(RuntimeError::TypeError("native_regex_replace: terceiro argumento deve ser string".to_string())),
     };
     
-    match Regex::new(pattern) {
-        Ok(re) => {
-            let result = re.replace_all(text, replacement.as_str()).to_string();
-            Ok(Value::String(result))
-        },
-        Err(e) => Err(RuntimeError::Generic(format!("Erro no padrão regex: {}", e))),
-    }
+    let result = pattern.replace_all(text, replacement.as_str()).to_string();
+    Ok(Value::String(result))
 }
 
 /// native_regex_split(pattern, string) -> array
@@ -446,26 +440,18 @@ fn native_regex_split(args: &[Value], _manager: &crate::native_modules::NativeMo
         return Err(RuntimeError::ArgumentError("native_regex_split: esperado 2 argumentos (pattern, string)".to_string()));
     }
     
-    let pattern = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("native_regex_split: primeiro argumento deve ser string".to_string())),
-    };
+    let pattern = regex_arg(&args[0], "native_regex_split")?;
     
     let text = match &args[1] {
         Value::String(s) => s,
         _ => return Err(RuntimeError::TypeError("native_regex_split: segundo argumento deve ser string".to_string())),
     };
     
-    match Regex::new(pattern) {
-        Ok(re) => {
-            let parts: Vec<Value> = re.split(text)
-                .map(|s| Value::String(s.to_string()))
-                .collect();
-            let id = _heap.allocate(ManagedObject::Array(parts));
-            Ok(Value::Array(id))
-        },
-        Err(e) => Err(RuntimeError::Generic(format!("Erro no padrão regex: {}", e))),
-    }
+    let parts: Vec<Value> = pattern.split(text)
+        .map(|s| Value::String(s.to_string()))
+        .collect();
+    let id = _heap.allocate(ManagedObject::Array(parts));
+    Ok(Value::Array(id))
 }
 
 /// native_regex_test(pattern, string) -> bool
@@ -474,18 +460,36 @@ fn native_regex_test(args: &[Value], _manager: &crate::native_modules::NativeMod
         return Err(RuntimeError::ArgumentError("native_regex_test: esperado 2 argumentos (pattern, string)".to_string()));
     }
     
-    let pattern = match &args[0] {
-        Value::String(s) => s,
-        _ => return Err(RuntimeError::TypeError("native_regex_test: primeiro argumento deve ser string".to_string())),
-    };
+    let pattern = regex_arg(&args[0], "native_regex_test")?;
     
     let text = match &args[1] {
         Value::String(s) => s,
         _ => return Err(RuntimeError::TypeError("native_regex_test: segundo argumento deve ser string".to_string())),
     };
     
-    match Regex::new(pattern) {
-        Ok(re) => Ok(Value::Bool(re.is_match(text))),
-        Err(e) => Err(RuntimeError::Generic(format!("Erro no padrão regex: {}", e))),
+    Ok(Value::Bool(pattern.is_match(text)))
+}
+/// regex(pattern, flags?) -> Regex
+/// Equivale ao literal `/pattern/flags`, para padrões montados em runtime
+fn native_regex(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    let source = match args.first() {
+        Some(Value::String(s)) => s,
+        Some(Value::Regex(existing)) if args.len() == 1 => return Ok(Value::Regex(existing.clone())),
+        _ => return Err(RuntimeError::TypeError("regex: primeiro argumento deve ser string".to_string())),
+    };
+    let flags = match args.get(1) {
+        None | Some(Value::Null) => "",
+        Some(Value::String(flags)) => flags,
+        Some(_) => return Err(RuntimeError::TypeError("regex: flags devem ser uma string, ex.: \"i\"".to_string())),
+    };
+    Regexp::compile(source, flags).map(|regexp| Value::Regex(Box::new(regexp))).map_err(RuntimeError::ArgumentError)
+}
+
+/// regex_escape(string) -> string
+/// Escapa os metacaracteres para casar o texto literalmente
+fn native_regex_escape(args: &[Value], _manager: &crate::native_modules::NativeModuleManager, _heap: &mut crate::heap::Heap) -> Result<Value, RuntimeError> {
+    match args.first() {
+        Some(Value::String(text)) => Ok(Value::String(regex::escape(text))),
+        _ => Err(RuntimeError::TypeError("regex_escape: argumento deve ser string".to_string())),
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,27 @@
 use crate::errors::RuntimeError;
 use crate::heap::{Heap, ManagedObject};
 use crate::interpreter::Value;
+use crate::native_modules::tls::{self, ClientTls, ServerTls};
 use crate::native_modules::NativeFunction;
 use futures::{SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::runtime::Runtime;
+use tokio_rustls::TlsAcceptor;
 use tokio_tungstenite::{
-    connect_async,
+    client_async,
     accept_async,
+    tungstenite::client::IntoClientRequest,
     tungstenite::protocol::Message as WsMessage,
     WebSocketStream,
-    MaybeTlsStream,
 };
 use tokio::net::{TcpStream, TcpListener};
+use tokio_util::either::Either;
 
-type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+/// Conexão cliente: TCP puro (`ws://`) ou sessão rustls (`wss://`)
+type WsStream = WebSocketStream<Either<TcpStream, tokio_rustls::client::TlsStream<TcpStream>>>;
 
 struct WsConnection {
     stream: Arc<Mutex<Option<WsStream>>>,
@@ -34,6 +39,8 @@ struct WsServerInstance {
     port: u16,
     is_running: bool,
     stop_sender: Option<tokio::sync::mpsc::Sender<()>>,
+    /// Opção `tls` do create: o servidor atende `wss://`
+    tls: Option<ServerTls>,
 }
 
 pub fn register_websocket_functions(functions: &mut HashMap<String, NativeFunction>) {
@@ -54,8 +61,8 @@ fn ws_connect(
     _manager: &crate::native_modules::NativeModuleManager,
     heap: &mut Heap,
 ) -> Result<Value, RuntimeError> {
-    if args.len() != 1 {
-        return Err(RuntimeError::ArgumentError("ws_connect: esperado 1 argumento (URL)".to_string()));
+    if args.is_empty() || args.len() > 2 {
+        return Err(RuntimeError::ArgumentError("ws_connect: esperado 1 ou 2 argumentos (URL, [opções])".to_string()));
     }
 
     let url_str = match &args[0] {
@@ -63,37 +70,59 @@ fn ws_connect(
         _ => return Err(RuntimeError::TypeError("ws_connect: argumento deve ser string (URL)".to_string())),
     };
 
-    let url = url_str.clone();
-    let result = RUNTIME.block_on(async move {
-        connect_async(&url).await
-    });
+    // Opções TLS para `wss://` (`{ ca?, cert?, key?, alpn?, server_name?, verify? }`)
+    let tls_options = match args.get(1) {
+        None | Some(Value::Null) => None,
+        Some(Value::Object(options_id)) => match heap.get(*options_id) {
+            Some(ManagedObject::Object { properties, .. }) => {
+                properties.get("tls").filter(|value| !matches!(value, Value::Null)).cloned()
+            }
+            _ => return Err(RuntimeError::HeapError("Objeto de opções não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError("ws_connect: opções devem ser um objeto".to_string())),
+    };
 
-    match result {
-        Ok((stream, _)) => {
-            let connection_id = format!("ws_{}", uuid::Uuid::new_v4());
-            let conn = Arc::new(WsConnection {
-                stream: Arc::new(Mutex::new(Some(stream))),
-                url: url_str.clone(),
-            });
+    let connect_error = |e: &dyn std::fmt::Display| RuntimeError::IoError(format!("Erro ao conectar WebSocket: {}", e));
+    let request = url_str.as_str().into_client_request().map_err(|e| connect_error(&e))?;
+    let secure = request.uri().scheme_str() == Some("wss");
+    let host = request.uri().host().unwrap_or_default().trim_matches(['[', ']']).to_string();
+    let port = request.uri().port_u16().unwrap_or(if secure { 443 } else { 80 });
+    // Sem opções, `wss://` verifica o servidor com as raízes públicas
+    let client_tls = match secure {
+        true => Some(ClientTls::from_value(&tls_options.unwrap_or(Value::Bool(true)), heap, "ws_connect")?),
+        false => None,
+    };
 
-            WS_CONNECTIONS.lock().unwrap().insert(connection_id.clone(), conn);
-
-            let id = heap.allocate(ManagedObject::Object {
-                properties: {
-                    let mut map = HashMap::new();
-                    map.insert("_type".to_string(), Value::String("websocket".to_string()));
-                    map.insert("id".to_string(), Value::String(connection_id));
-                    map.insert("url".to_string(), Value::String(url_str));
-                    map.insert("connected".to_string(), Value::Bool(true));
-                    map
-                },
-                methods: HashMap::new(),
-            });
+    let (stream, _) = RUNTIME.block_on(async move {
+        let stream = TcpStream::connect((host.as_str(), port)).await.map_err(|e| connect_error(&e))?;
+        let stream = match client_tls {
+            Some(client_tls) => Either::Right(client_tls.connect_async(&host, stream, "ws_connect").await?),
+            None => Either::Left(stream),
+        };
+        client_async(request, stream).await.map_err(|e| connect_error(&e))
+    })?;
 
-            Ok(Value::Object(id))
-        }
-        Err(e) => Err(RuntimeError::IoError(format!("Erro ao conectar WebSocket: {}", e))),
-    }
+    let connection_id = format!("ws_{}", uuid::Uuid::new_v4());
+    let conn = Arc::new(WsConnection {
+        stream: Arc::new(Mutex::new(Some(stream))),
+        url: url_str.clone(),
+    });
+
+    WS_CONNECTIONS.lock().unwrap().insert(connection_id.clone(), conn);
+
+    let id = heap.allocate(ManagedObject::Object {
+        properties: {
+            let mut map = HashMap::new();
+            map.insert("_type".to_string(), Value::String("websocket".to_string()));
+            map.insert("id".to_string(), Value::String(connection_id));
+            map.insert("url".to_string(), Value::String(url_str));
+            map.insert("connected".to_string(), Value::Bool(true));
+            map
+        },
+        methods: HashMap::new(),
+    });
+
+    Ok(Value::Object(id))
 }
 
 fn ws_send(
@@ -260,7 +289,7 @@ fn ws_server_create(
     _heap: &mut Heap,
 ) -> Result<Value, RuntimeError> {
     if args.len() < 1 {
-        return Err(RuntimeError::ArgumentError("ws_server_create: esperado pelo menos 1 argumento (id, [host], [port])".to_string()));
+        return Err(RuntimeError::ArgumentError("ws_server_create: esperado pelo menos 1 argumento (id, [host], [port], [opções])".to_string()));
     }
 
     let id = match &args[0] {
@@ -271,11 +300,25 @@ fn ws_server_create(
     let host = args.get(1).and_then(|v| if let Value::String(s) = v { Some(s.clone()) } else { None }).unwrap_or_else(|| "127.0.0.1".to_string());
     let port = args.get(2).and_then(|v| if let Value::Number(n) = v { Some(*n as u16) } else { None }).unwrap_or(8080);
 
+    // Opções: `{ tls: { cert, key, ca?, client_auth?, alpn? } }` para wss://
+    let tls = match args.get(3) {
+        None | Some(Value::Null) => None,
+        Some(Value::Object(options_id)) => match _heap.get(*options_id) {
+            Some(ManagedObject::Object { properties, .. }) => match properties.get("tls") {
+                None | Some(Value::Null) => None,
+                Some(options) => Some(tls::server_config(options, _heap, "ws_server_create")?),
+            },
+            _ => return Err(RuntimeError::HeapError("Objeto de opções não encontrado".to_string())),
+        },
+        Some(_) => return Err(RuntimeError::TypeError("ws_server_create: opções devem ser um objeto".to_string())),
+    };
+
     let server = WsServerInstance {
         host,
         port,
         is_running: false,
         stop_sender: None,
+        tls,
     };
 
     WS_SERVERS.lock().unwrap().insert(id, server);
@@ -303,23 +346,26 @@ fn ws_server_start(
         return Ok(Value::Null);
     }
 
+    // O bind é feito aqui para que erros de porta sejam lançados e a porta 0
+    // seja resolvida antes de retornar
+    let addr = format!("{}:{}", server.host, server.port);
+    let listener = RUNTIME
+        .block_on(TcpListener::bind(&addr))
+        .map_err(|e| RuntimeError::NetworkError(format!("Erro ao iniciar servidor WS '{}' em {}: {}", id, addr, e)))?;
+    if let Ok(local) = listener.local_addr() {
+        server.port = local.port();
+    }
+
     let (stop_sender, mut stop_receiver) = tokio::sync::mpsc::channel(1);
     server.stop_sender = Some(stop_sender);
     server.is_running = true;
 
-    let addr = format!("{}:{}", server.host, server.port);
+    let scheme = if server.tls.is_some() { "wss" } else { "ws" };
+    println!("🌍 Servidor WebSocket '{}' ouvindo em {}://{}:{}", id, scheme, server.host, server.port);
+    let acceptor = server.tls.clone().map(TlsAcceptor::from);
     let server_id = id.clone();
 
     RUNTIME.spawn(async move {
-        let listener = match TcpListener::bind(&addr).await {
-            Ok(l) => l,
-            Err(e) => {
-                eprintln!("Erro ao iniciar servidor WS '{}' em {}: {}", server_id, addr, e);
-                return;
-            }
-        };
-
-        println!("🌍 Servidor WebSocket '{}' ouvindo em ws://{}", server_id, addr);
 
         loop {
             tokio::select! {
@@ -330,8 +376,31 @@ fn ws_server_start(
                 accept_res = listener.accept() => {
                     if let Ok((stream, _)) = accept_res {
                         let server_id_clone = server_id.clone();
+                        if let Some(acceptor) = acceptor.clone() {
+                            tokio::spawn(async move {
+                                let stream = match tokio::time::timeout(tls::HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                                    Ok(Ok(stream)) => stream,
+                                    Ok(Err(e)) => {
+                                        eprintln!("⚠️ Servidor WS '{}': handshake TLS falhou: {}", server_id_clone, e);
+                                        return;
+                                    }
+                                    Err(_) => {
+                                        eprintln!("⚠️ Servidor WS '{}': handshake TLS excedeu o tempo limite", server_id_clone);
+                                        return;
+                                    }
+                                };
+                                match accept_async(stream).await {
+                                    Ok(ws_stream) => {
+                                        println!("🔗 Servidor WS '{}': Nova conexão estabelecida (TLS)", server_id_clone);
+                                        handle_server_ws_connection(ws_stream, server_id_clone).await;
+                                    }
+                                    Err(e) => eprintln!("⚠️ Servidor WS '{}': handshake WebSocket falhou: {}", server_id_clone, e),
+                                }
+                            });
+                            continue;
+                        }
                         tokio::spawn(async move {
-                            if let Ok(ws_stream) = accept_async(MaybeTlsStream::Plain(stream)).await {
+                            if let Ok(ws_stream) = accept_async(stream).await {
                                 // Por enquanto, apenas logar conexão. 
                                 // Futuro: Adicionar à lista de conexões do servidor e permitir handlers.
                                 println!("🔗 Servidor WS '{}': Nova conexão estabelecida", server_id_clone);
@@ -347,7 +416,10 @@ fn ws_server_start(
     Ok(Value::Null)
 }
 
-async fn handle_server_ws_connection(mut ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>, server_id: String) {
+async fn handle_server_ws_connection<S>(mut ws_stream: WebSocketStream<S>, server_id: String)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     while let Some(msg) = ws_stream.next().await {
         match msg {
             Ok(WsMessage::Text(t)) => {
@@ -415,6 +487,7 @@ fn ws_server_status(
                 map.insert("host".to_string(), Value::String(server.host.clone()));
                 map.insert("port".to_string(), Value::Number(server.port as f64));
                 map.insert("running".to_string(), Value::Bool(server.is_running));
+                map.insert("tls".to_string(), Value::Bool(server.tls.is_some()));
                 map
             },
             methods: HashMap::new(),
@@ -0,0 +1,653 @@
+// crates/dryad_runtime/src/numeric.rs
+//! Tipos numéricos exatos (`int`, `bigint`, `decimal`) e as regras de promoção
+//! usadas pelos operadores aritméticos, bitwise e de comparação.
+//!
+//! Promoção: `int` < `bigint` < `decimal`. Um `number` (f64) com valor inteiro
+//! exato (|n| <= 2^53) se comporta como `int`; com parte fracionária ele vira
+//! `decimal` ao lado de um decimal (pela representação mais curta, `0.1` -> `0.1`)
+//! e faz a operação cair para f64 ao lado de `int`/`bigint`.
+
+use crate::value::Value;
+use num_bigint_dig::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Pow, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Casas decimais usadas quando uma divisão de `decimal` não é exata
+pub const DIVISION_SCALE: u32 = 28;
+
+/// Maior inteiro representado exatamente por um f64 (2^53)
+pub const MAX_SAFE_INTEGER: i64 = 1 << 53;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumericError {
+    Overflow(String),
+    DivisionByZero,
+    Invalid(String),
+}
+
+impl fmt::Display for NumericError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumericError::Overflow(msg) => write!(f, "Overflow de inteiro: {}", msg),
+            NumericError::DivisionByZero => write!(f, "Divisão por zero"),
+            NumericError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+/// Número decimal exato: `mantissa / 10^scale`
+#[derive(Debug, Clone)]
+pub struct Decimal {
+    mantissa: BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(mantissa: BigInt, scale: u32) -> Self {
+        Self { mantissa, scale }
+    }
+
+    pub fn from_bigint(value: BigInt) -> Self {
+        Self::new(value, 0)
+    }
+
+    /// Lê `-12.345`, `1e-3` ou `2.5E+2`
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (number, exponent) = match text.find(['e', 'E']) {
+            Some(pos) => (&text[..pos], text[pos + 1..].parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (negative, digits) = match number.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (int_part, frac_part) = match digits.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (digits, ""),
+        };
+        if int_part.is_empty() && frac_part.is_empty()
+            || !int_part.chars().chain(frac_part.chars()).all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+        let mut mantissa: BigInt = format!("{}{}", int_part, frac_part).parse().ok()?;
+        if negative {
+            mantissa = -mantissa;
+        }
+        let scale = frac_part.len() as i64 - exponent;
+        if scale >= 0 {
+            Some(Self::new(mantissa, u32::try_from(scale).ok()?))
+        } else {
+            Some(Self::new(mantissa * pow10(u32::try_from(-scale).ok()?), 0))
+        }
+    }
+
+    /// Converte pela representação decimal mais curta do f64 (`0.1` -> `0.1`)
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if value.is_finite() {
+            Self::parse(&format!("{}", value))
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.mantissa.is_multiple_of(&pow10(self.scale))
+    }
+
+    /// Parte inteira (truncada em direção a zero)
+    pub fn trunc(&self) -> BigInt {
+        &self.mantissa / pow10(self.scale)
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    fn rescale(&self, scale: u32) -> BigInt {
+        &self.mantissa * pow10(scale - self.scale)
+    }
+
+    fn aligned(&self, other: &Decimal) -> (BigInt, BigInt, u32) {
+        let scale = self.scale.max(other.scale);
+        (self.rescale(scale), other.rescale(scale), scale)
+    }
+
+    pub fn add(&self, other: &Decimal) -> Decimal {
+        let (a, b, scale) = self.aligned(other);
+        Decimal::new(a + b, scale)
+    }
+
+    pub fn sub(&self, other: &Decimal) -> Decimal {
+        let (a, b, scale) = self.aligned(other);
+        Decimal::new(a - b, scale)
+    }
+
+    pub fn mul(&self, other: &Decimal) -> Decimal {
+        Decimal::new(&self.mantissa * &other.mantissa, self.scale + other.scale)
+    }
+
+    /// Divisão exata quando possível; senão arredonda (meio para o par) em
+    /// `DIVISION_SCALE` casas e remove zeros à direita
+    pub fn div(&self, other: &Decimal) -> Result<Decimal, NumericError> {
+        if other.is_zero() {
+            return Err(NumericError::DivisionByZero);
+        }
+        let keep = self.scale.max(other.scale);
+        let target = keep + DIVISION_SCALE;
+        // (a / 10^sa) / (b / 10^sb) = a * 10^(sb + target) / (b * 10^sa) / 10^target
+        let numerator = &self.mantissa * pow10(other.scale + target);
+        let denominator = &other.mantissa * pow10(self.scale);
+        let mantissa = round_half_even(&numerator, &denominator);
+        Ok(Decimal::new(mantissa, target).normalized(keep))
+    }
+
+    pub fn rem(&self, other: &Decimal) -> Result<Decimal, NumericError> {
+        if other.is_zero() {
+            return Err(NumericError::DivisionByZero);
+        }
+        let (a, b, scale) = self.aligned(other);
+        Ok(Decimal::new(a % b, scale))
+    }
+
+    pub fn pow(&self, exponent: u32) -> Decimal {
+        Decimal::new(
+            Pow::pow(&self.mantissa, exponent),
+            self.scale * exponent,
+        )
+    }
+
+    pub fn neg(&self) -> Decimal {
+        Decimal::new(-&self.mantissa, self.scale)
+    }
+
+    /// Arredonda para `places` casas (meio para o par)
+    pub fn round(&self, places: u32) -> Decimal {
+        if places >= self.scale {
+            return Decimal::new(self.rescale(places), places);
+        }
+        let mantissa = round_half_even(&self.mantissa, &pow10(self.scale - places));
+        Decimal::new(mantissa, places)
+    }
+
+    /// Remove zeros à direita sem descer abaixo de `min_scale` casas
+    fn normalized(mut self, min_scale: u32) -> Decimal {
+        let ten = BigInt::from(10);
+        while self.scale > min_scale && self.mantissa.is_multiple_of(&ten) {
+            self.mantissa = &self.mantissa / &ten;
+            self.scale -= 1;
+        }
+        self
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, _) = self.aligned(other);
+        a.cmp(&b)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.abs().to_string();
+        let sign = if self.mantissa.is_negative() { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let padded = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = padded.split_at(padded.len() - scale);
+        write!(f, "{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+fn pow10(exponent: u32) -> BigInt {
+    Pow::pow(&BigInt::from(10), exponent)
+}
+
+/// `numerator / denominator` arredondado para o inteiro mais próximo (empate -> par)
+fn round_half_even(numerator: &BigInt, denominator: &BigInt) -> BigInt {
+    let (quotient, remainder) = numerator.div_rem(denominator);
+    let twice: BigInt = remainder.abs() * BigInt::from(2);
+    let away = match twice.cmp(&denominator.abs()) {
+        Ordering::Greater => true,
+        Ordering::Equal => quotient.is_odd(),
+        Ordering::Less => false,
+    };
+    if !away {
+        quotient
+    } else if numerator.is_negative() != denominator.is_negative() {
+        quotient - BigInt::one()
+    } else {
+        quotient + BigInt::one()
+    }
+}
+
+/// Converte um inteiro vindo de JSON/banco de dados sem perder precisão:
+/// `number` enquanto for exato em f64, `int` acima de 2^53
+pub fn from_i64(value: i64) -> Value {
+    if value.unsigned_abs() <= MAX_SAFE_INTEGER as u64 {
+        Value::Number(value as f64)
+    } else {
+        Value::Int(value)
+    }
+}
+
+/// Como `from_i64`, mas para o texto de um inteiro de qualquer tamanho
+pub fn from_integer_text(text: &str) -> Option<Value> {
+    if let Ok(value) = text.parse::<i64>() {
+        return Some(from_i64(value));
+    }
+    text.parse::<BigInt>().ok().map(Value::BigInt)
+}
+
+/// Número JSON para valor Dryad: inteiros fora de 2^53 viram `int`/`bigint`
+/// em vez de serem arredondados para f64
+pub fn from_json_number(number: &serde_json::Number) -> Value {
+    if let Some(value) = number.as_i64() {
+        return from_i64(value);
+    }
+    let text = number.to_string();
+    if !text.contains(['.', 'e', 'E']) {
+        if let Some(value) = from_integer_text(&text) {
+            return value;
+        }
+    }
+    Value::Number(number.as_f64().unwrap_or(0.0))
+}
+
+/// Valor exato para número JSON, escrito com todos os dígitos
+pub fn to_json_number(value: &Value) -> Option<serde_json::Number> {
+    match value {
+        Value::Int(i) => Some(serde_json::Number::from(*i)),
+        Value::BigInt(_) | Value::Decimal(_) => format(value)?.parse().ok(),
+        _ => None,
+    }
+}
+
+pub fn is_exact(value: &Value) -> bool {
+    matches!(value, Value::Int(_) | Value::BigInt(_) | Value::Decimal(_))
+}
+
+pub fn is_numeric(value: &Value) -> bool {
+    matches!(value, Value::Number(_)) || is_exact(value)
+}
+
+/// Operandos convertidos para o tipo comum
+pub enum Promoted {
+    Int(i64, i64),
+    Big(BigInt, BigInt),
+    Dec(Decimal, Decimal),
+    Float(f64, f64),
+}
+
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+enum Rank {
+    Int,
+    Big,
+    Dec,
+}
+
+fn rank(value: &Value) -> Option<Rank> {
+    match value {
+        Value::Int(_) => Some(Rank::Int),
+        Value::BigInt(_) => Some(Rank::Big),
+        Value::Decimal(_) => Some(Rank::Dec),
+        Value::Number(n) if is_safe_integer(*n) => Some(Rank::Int),
+        _ => None,
+    }
+}
+
+fn is_safe_integer(n: f64) -> bool {
+    n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER as f64
+}
+
+pub fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => Some(*n),
+        Value::Int(i) => Some(*i as f64),
+        Value::BigInt(b) => Some(b.to_f64().unwrap_or(f64::NAN)),
+        Value::Decimal(d) => Some(d.to_f64()),
+        _ => None,
+    }
+}
+
+fn to_bigint(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Int(i) => Some(BigInt::from(*i)),
+        Value::BigInt(b) => Some(b.clone()),
+        Value::Number(n) if is_safe_integer(*n) => Some(BigInt::from(*n as i64)),
+        _ => None,
+    }
+}
+
+pub fn to_decimal(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::Decimal(d) => Some(d.clone()),
+        Value::Number(n) => Decimal::from_f64(*n),
+        other => to_bigint(other).map(Decimal::from_bigint),
+    }
+}
+
+/// Leva dois operandos numéricos ao tipo comum. `None` quando nenhum é exato
+/// (o interpretador segue com a aritmética f64 de sempre).
+pub fn promote(left: &Value, right: &Value) -> Result<Option<Promoted>, NumericError> {
+    if !(is_numeric(left) && is_numeric(right)) || !(is_exact(left) || is_exact(right)) {
+        return Ok(None);
+    }
+    let target = match (rank(left), rank(right)) {
+        (Some(a), Some(b)) => {
+            if a > b {
+                a
+            } else {
+                b
+            }
+        }
+        // Um f64 fracionário: decimal o absorve, int/bigint caem para f64
+        (a, b) => {
+            if a == Some(Rank::Dec) || b == Some(Rank::Dec) {
+                Rank::Dec
+            } else {
+                let (a, b) = (to_f64(left), to_f64(right));
+                return Ok(Some(Promoted::Float(a.unwrap_or(f64::NAN), b.unwrap_or(f64::NAN))));
+            }
+        }
+    };
+    let promoted = match target {
+        Rank::Int => match (left, right) {
+            (Value::Int(_) | Value::Number(_), Value::Int(_) | Value::Number(_)) => {
+                let int = |v: &Value| match v {
+                    Value::Int(i) => *i,
+                    Value::Number(n) => *n as i64,
+                    _ => 0,
+                };
+                Promoted::Int(int(left), int(right))
+            }
+            _ => unreachable_promotion()?,
+        },
+        Rank::Big => match (to_bigint(left), to_bigint(right)) {
+            (Some(a), Some(b)) => Promoted::Big(a, b),
+            _ => unreachable_promotion()?,
+        },
+        Rank::Dec => match (to_decimal(left), to_decimal(right)) {
+            (Some(a), Some(b)) => Promoted::Dec(a, b),
+            _ => {
+                return Err(NumericError::Invalid(
+                    "NaN e infinito não podem ser convertidos para decimal".to_string(),
+                ))
+            }
+        },
+    };
+    Ok(Some(promoted))
+}
+
+fn unreachable_promotion() -> Result<Promoted, NumericError> {
+    Err(NumericError::Invalid("Promoção numérica inválida".to_string()))
+}
+
+/// Compara dois valores numéricos quaisquer (None se algum é NaN)
+pub fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match promote(left, right).ok()?? {
+        Promoted::Int(a, b) => Some(a.cmp(&b)),
+        Promoted::Big(a, b) => Some(a.cmp(&b)),
+        Promoted::Dec(a, b) => Some(a.cmp(&b)),
+        Promoted::Float(a, b) => a.partial_cmp(&b),
+    }
+}
+
+fn overflow(a: impl fmt::Display, op: &str, b: impl fmt::Display) -> NumericError {
+    NumericError::Overflow(format!(
+        "{} {} {} excede o intervalo de int (i64); use bigint (sufixo n)",
+        a, op, b
+    ))
+}
+
+fn shift_amount(b: &BigInt) -> Result<usize, NumericError> {
+    if b.is_negative() {
+        return Err(NumericError::Invalid(
+            "Não é possível fazer shift com número negativo".to_string(),
+        ));
+    }
+    b.to_usize()
+        .ok_or_else(|| NumericError::Invalid("Deslocamento grande demais".to_string()))
+}
+
+fn exponent_u32(b: &BigInt) -> Result<u32, NumericError> {
+    if b.is_negative() {
+        return Err(NumericError::Invalid(
+            "Expoente negativo não é suportado para int/bigint/decimal".to_string(),
+        ));
+    }
+    b.to_u32()
+        .ok_or_else(|| NumericError::Invalid("Expoente grande demais".to_string()))
+}
+
+fn ordering_result(op: &str, ordering: Ordering) -> Option<Value> {
+    let result = match op {
+        "==" => ordering == Ordering::Equal,
+        "!=" => ordering != Ordering::Equal,
+        "<" => ordering == Ordering::Less,
+        ">" => ordering == Ordering::Greater,
+        "<=" => ordering != Ordering::Greater,
+        ">=" => ordering != Ordering::Less,
+        _ => return None,
+    };
+    Some(Value::Bool(result))
+}
+
+/// Aplica `op` a operandos exatos já promovidos (`Promoted::Float` não é tratado aqui)
+pub fn apply(op: &str, operands: Promoted) -> Result<Value, NumericError> {
+    match operands {
+        Promoted::Int(a, b) => apply_int(op, a, b),
+        Promoted::Big(a, b) => apply_big(op, a, b),
+        Promoted::Dec(a, b) => apply_dec(op, a, b),
+        Promoted::Float(..) => Err(NumericError::Invalid("Promoção numérica inválida".to_string())),
+    }
+}
+
+fn apply_int(op: &str, a: i64, b: i64) -> Result<Value, NumericError> {
+    if let Some(result) = ordering_result(op, a.cmp(&b)) {
+        return Ok(result);
+    }
+    let result = match op {
+        "+" => a.checked_add(b).ok_or_else(|| overflow(a, op, b))?,
+        "-" => a.checked_sub(b).ok_or_else(|| overflow(a, op, b))?,
+        "*" => a.checked_mul(b).ok_or_else(|| overflow(a, op, b))?,
+        "/" | "%" | "%%" if b == 0 => return Err(NumericError::DivisionByZero),
+        "/" => a.checked_div(b).ok_or_else(|| overflow(a, op, b))?,
+        "%" => a.checked_rem(b).ok_or_else(|| overflow(a, op, b))?,
+        "%%" => a.checked_rem_euclid(b).ok_or_else(|| overflow(a, op, b))?,
+        "**" => {
+            let exponent = exponent_u32(&BigInt::from(b))?;
+            a.checked_pow(exponent).ok_or_else(|| overflow(a, op, b))?
+        }
+        "##" => {
+            let exponent = exponent_u32(&BigInt::from(b))?;
+            10i64
+                .checked_pow(exponent)
+                .and_then(|p| a.checked_mul(p))
+                .ok_or_else(|| overflow(a, op, b))?
+        }
+        "&" => a & b,
+        "|" => a | b,
+        "^" => a ^ b,
+        "<<" | "<<<" => {
+            let amount = shift_amount(&BigInt::from(b))?;
+            let shifted = if amount >= 64 { 0 } else { a << amount };
+            if amount >= 64 && a != 0 || amount < 64 && shifted >> amount != a {
+                return Err(overflow(a, op, b));
+            }
+            shifted
+        }
+        ">>" | ">>>" => {
+            let amount = shift_amount(&BigInt::from(b))?;
+            a >> amount.min(63)
+        }
+        _ => return Err(unsupported(op, "int")),
+    };
+    Ok(Value::Int(result))
+}
+
+fn apply_big(op: &str, a: BigInt, b: BigInt) -> Result<Value, NumericError> {
+    if let Some(result) = ordering_result(op, a.cmp(&b)) {
+        return Ok(result);
+    }
+    let result = match op {
+        "+" => a + b,
+        "-" => a - b,
+        "*" => a * b,
+        "/" | "%" | "%%" if b.is_zero() => return Err(NumericError::DivisionByZero),
+        "/" => a / b,
+        "%" => a % b,
+        "%%" => {
+            let modulus = b.abs();
+            ((a % &modulus) + &modulus) % modulus
+        }
+        "**" => Pow::pow(&a, exponent_u32(&b)?),
+        "##" => a * pow10(exponent_u32(&b)?),
+        "&" => a & b,
+        "|" => a | b,
+        "^" => a ^ b,
+        "<<" | "<<<" => a << shift_amount(&b)?,
+        ">>" | ">>>" => a >> shift_amount(&b)?,
+        _ => return Err(unsupported(op, "bigint")),
+    };
+    Ok(Value::BigInt(result))
+}
+
+fn apply_dec(op: &str, a: Decimal, b: Decimal) -> Result<Value, NumericError> {
+    if let Some(result) = ordering_result(op, a.cmp(&b)) {
+        return Ok(result);
+    }
+    let result = match op {
+        "+" => a.add(&b),
+        "-" => a.sub(&b),
+        "*" => a.mul(&b),
+        "/" => a.div(&b)?,
+        "%" => a.rem(&b)?,
+        "**" if b.is_integer() => a.pow(exponent_u32(&b.trunc())?),
+        "**" => {
+            return Err(NumericError::Invalid(
+                "Expoente de decimal deve ser um inteiro não negativo".to_string(),
+            ))
+        }
+        _ => return Err(unsupported(op, "decimal")),
+    };
+    Ok(Value::Decimal(result))
+}
+
+fn unsupported(op: &str, type_name: &str) -> NumericError {
+    NumericError::Invalid(format!("Operação '{}' não é válida para {}", op, type_name))
+}
+
+/// `-x` para valores exatos
+pub fn negate(value: &Value) -> Option<Result<Value, NumericError>> {
+    Some(match value {
+        Value::Int(i) => i
+            .checked_neg()
+            .map(Value::Int)
+            .ok_or_else(|| NumericError::Overflow(format!("-({}) excede o intervalo de int (i64)", i))),
+        Value::BigInt(b) => Ok(Value::BigInt(-b)),
+        Value::Decimal(d) => Ok(Value::Decimal(d.neg())),
+        _ => return None,
+    })
+}
+
+/// Texto usado por `to_string`/`print` (sem sufixo; decimal mantém a escala)
+pub fn format(value: &Value) -> Option<String> {
+    match value {
+        Value::Int(i) => Some(i.to_string()),
+        Value::BigInt(b) => Some(b.to_string()),
+        Value::Decimal(d) => Some(d.to_string()),
+        _ => None,
+    }
+}
+
+fn conversion_error(target: &str, value: &Value) -> NumericError {
+    NumericError::Invalid(format!(
+        "Não é possível converter '{}' para {}",
+        value.to_string(),
+        target
+    ))
+}
+
+/// `int(x)`: trunca números fracionários e valida o intervalo de i64
+pub fn to_int(value: &Value) -> Result<Value, NumericError> {
+    let big = match value {
+        Value::Int(i) => return Ok(Value::Int(*i)),
+        Value::Number(n) if n.is_finite() => {
+            if n.trunc().abs() < 9.3e18 {
+                return Ok(Value::Int(n.trunc() as i64));
+            }
+            Decimal::from_f64(*n).map(|d| d.trunc())
+        }
+        Value::BigInt(b) => Some(b.clone()),
+        Value::Decimal(d) => Some(d.trunc()),
+        Value::String(s) => Decimal::parse(s).map(|d| d.trunc()),
+        _ => None,
+    }
+    .ok_or_else(|| conversion_error("int", value))?;
+    big.to_i64()
+        .map(Value::Int)
+        .ok_or_else(|| NumericError::Overflow(format!("{} excede o intervalo de int (i64)", big)))
+}
+
+/// `bigint(x)`: trunca números fracionários
+pub fn to_big(value: &Value) -> Result<Value, NumericError> {
+    match value {
+        Value::Int(i) => Some(BigInt::from(*i)),
+        Value::BigInt(b) => Some(b.clone()),
+        Value::Number(n) => Decimal::from_f64(*n).map(|d| d.trunc()),
+        Value::Decimal(d) => Some(d.trunc()),
+        Value::String(s) => Decimal::parse(s).map(|d| d.trunc()),
+        _ => None,
+    }
+    .map(Value::BigInt)
+    .ok_or_else(|| conversion_error("bigint", value))
+}
+
+/// `decimal(x)`: strings são lidas exatamente como escritas
+pub fn to_dec(value: &Value) -> Result<Value, NumericError> {
+    match value {
+        Value::String(s) => Decimal::parse(s),
+        other => to_decimal(other),
+    }
+    .map(Value::Decimal)
+    .ok_or_else(|| conversion_error("decimal", value))
+}
+
+/// `float(x)`: volta para `number` (f64), possivelmente com perda
+pub fn to_float(value: &Value) -> Result<Value, NumericError> {
+    match value {
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        other => to_f64(other),
+    }
+    .map(Value::Number)
+    .ok_or_else(|| conversion_error("number", value))
+}
@@ -0,0 +1,279 @@
+// crates/dryad_runtime/src/profiler.rs
+//! Profiler por amostragem dos scripts Dryad (`dryad run --profile`).
+//!
+//! Uma thread amostradora incrementa um contador de ticks a cada intervalo. O
+//! interpretador (a cada statement) e a VM (a cada instrução) consomem os ticks
+//! pendentes e registram a pilha de chamadas atual com peso igual ao número de
+//! ticks, o que mantém a execução em uma única thread. As alocações são contadas
+//! no `Heap::allocate` e atribuídas ao statement em execução.
+
+use dryad_errors::{SourceLocation, StackFrame};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Local de alocação: função e linha do statement que alocou
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AllocationSite {
+    pub function: String,
+    pub file: Option<PathBuf>,
+    pub line: usize,
+}
+
+/// Tempo próprio e total de uma função, em amostras
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionTime {
+    pub name: String,
+    /// Amostras em que a função estava no topo da pilha
+    pub self_samples: u64,
+    /// Amostras em que a função estava em qualquer ponto da pilha
+    pub total_samples: u64,
+}
+
+/// Resultado de uma execução com profiler
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    pub interval: Duration,
+    /// Pilha (da base para o topo) -> número de amostras
+    pub stacks: HashMap<Vec<String>, u64>,
+    /// Local -> número de objetos alocados
+    pub allocations: HashMap<AllocationSite, u64>,
+}
+
+impl Profile {
+    pub fn total_samples(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// Pilhas no formato "folded" (`main;f;g 12`), aceito por flamegraph.pl e inferno
+    pub fn folded(&self) -> String {
+        let mut lines: Vec<String> = self
+            .stacks
+            .iter()
+            .map(|(stack, count)| {
+                let names: Vec<String> = stack
+                    .iter()
+                    .map(|name| name.replace([';', ' '], "_"))
+                    .collect();
+                format!("{} {}", names.join(";"), count)
+            })
+            .collect();
+        lines.sort();
+        let mut out = lines.join("\n");
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Tempo próprio e total por função, do maior tempo próprio para o menor
+    pub fn function_times(&self) -> Vec<FunctionTime> {
+        let mut times: HashMap<&str, (u64, u64)> = HashMap::new();
+        for (stack, &count) in &self.stacks {
+            if let Some(top) = stack.last() {
+                times.entry(top).or_default().0 += count;
+            }
+            // Recursão conta uma vez por amostra no tempo total
+            let mut seen: Vec<&str> = Vec::new();
+            for name in stack {
+                if !seen.contains(&name.as_str()) {
+                    seen.push(name);
+                    times.entry(name).or_default().1 += count;
+                }
+            }
+        }
+
+        let mut result: Vec<FunctionTime> = times
+            .into_iter()
+            .map(|(name, (self_samples, total_samples))| FunctionTime {
+                name: name.to_string(),
+                self_samples,
+                total_samples,
+            })
+            .collect();
+        result.sort_by(|a, b| {
+            b.self_samples
+                .cmp(&a.self_samples)
+                .then(b.total_samples.cmp(&a.total_samples))
+                .then(a.name.cmp(&b.name))
+        });
+        result
+    }
+
+    /// Tabela de tempo por função seguida das alocações por local
+    pub fn report(&self, max_rows: usize) -> String {
+        let total = self.total_samples().max(1) as f64;
+        let millis = |samples: u64| samples as f64 * self.interval.as_secs_f64() * 1000.0;
+
+        let mut out = format!(
+            "perfil: {} amostras a cada {:?}\n\n{:<40} {:>12} {:>8} {:>12} {:>8}\n",
+            self.total_samples(),
+            self.interval,
+            "função",
+            "próprio (ms)",
+            "%",
+            "total (ms)",
+            "%"
+        );
+        for time in self.function_times().iter().take(max_rows) {
+            out.push_str(&format!(
+                "{:<40} {:>12.1} {:>7.1}% {:>12.1} {:>7.1}%\n",
+                time.name,
+                millis(time.self_samples),
+                time.self_samples as f64 * 100.0 / total,
+                millis(time.total_samples),
+                time.total_samples as f64 * 100.0 / total
+            ));
+        }
+
+        if !self.allocations.is_empty() {
+            let mut sites: Vec<(&AllocationSite, &u64)> = self.allocations.iter().collect();
+            sites.sort_by(|a, b| {
+                b.1.cmp(a.1)
+                    .then(a.0.file.cmp(&b.0.file))
+                    .then(a.0.line.cmp(&b.0.line))
+            });
+            out.push_str(&format!(
+                "\n{:<40} {:>30} {:>12}\n",
+                "alocações", "local", "objetos"
+            ));
+            for (site, count) in sites.into_iter().take(max_rows) {
+                let file = site
+                    .file
+                    .as_ref()
+                    .map(|f| f.display().to_string())
+                    .unwrap_or_else(|| "<desconhecido>".to_string());
+                out.push_str(&format!(
+                    "{:<40} {:>30} {:>12}\n",
+                    site.function,
+                    format!("{}:{}", file, site.line),
+                    count
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Profiler ativo durante a execução
+pub struct Profiler {
+    profile: Profile,
+    ticks: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    sampler: Option<JoinHandle<()>>,
+    sites: Vec<AllocationSite>,
+    /// Arquivo (vazio se desconhecido) -> linha -> índice em `sites`
+    site_ids: HashMap<PathBuf, HashMap<usize, usize>>,
+}
+
+impl Profiler {
+    /// Inicia a thread amostradora com o intervalo informado
+    pub fn start(interval: Duration) -> Self {
+        let ticks = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let sampler = {
+            let ticks = Arc::clone(&ticks);
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(interval);
+                    ticks.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        };
+
+        Self {
+            profile: Profile {
+                interval,
+                ..Profile::default()
+            },
+            ticks,
+            stop,
+            sampler: Some(sampler),
+            sites: Vec::new(),
+            site_ids: HashMap::new(),
+        }
+    }
+
+    /// Contador compartilhado com a VM no modo bytecode
+    pub fn ticks(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.ticks)
+    }
+
+    /// Registra a pilha atual se houver ticks pendentes
+    pub fn sample(&mut self, frames: &[StackFrame]) {
+        if self.ticks.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let weight = self.ticks.swap(0, Ordering::Relaxed);
+        let stack = frames
+            .iter()
+            .map(|frame| frame.function_name.clone())
+            .collect();
+        self.add_stack(stack, weight);
+    }
+
+    pub fn add_stack(&mut self, stack: Vec<String>, weight: u64) {
+        *self.profile.stacks.entry(stack).or_insert(0) += weight;
+    }
+
+    /// Índice do local de alocação de um statement (repassado ao `Heap`).
+    /// `default_file` é usado quando a localização não tem arquivo.
+    pub fn site(
+        &mut self,
+        frames: &[StackFrame],
+        location: &SourceLocation,
+        default_file: Option<&PathBuf>,
+    ) -> usize {
+        let file = location.file.as_ref().or(default_file);
+        let key = file.map(PathBuf::as_path).unwrap_or(Path::new(""));
+        if let Some(&id) = self
+            .site_ids
+            .get(key)
+            .and_then(|lines| lines.get(&location.line))
+        {
+            return id;
+        }
+        let id = self.sites.len();
+        self.sites.push(AllocationSite {
+            function: frames
+                .last()
+                .map(|frame| frame.function_name.clone())
+                .unwrap_or_else(|| "<main>".to_string()),
+            file: file.cloned(),
+            line: location.line,
+        });
+        self.site_ids
+            .entry(key.to_path_buf())
+            .or_default()
+            .insert(location.line, id);
+        id
+    }
+
+    pub fn add_allocations(&mut self, site: AllocationSite, count: u64) {
+        *self.profile.allocations.entry(site).or_insert(0) += count;
+    }
+
+    /// Para a thread amostradora e monta o perfil com as contagens do heap
+    pub fn finish(mut self, heap_sites: HashMap<usize, u64>) -> Profile {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(sampler) = self.sampler.take() {
+            let _ = sampler.join();
+        }
+        for (id, count) in heap_sites {
+            if let Some(site) = self.sites.get(id).cloned() {
+                self.add_allocations(site, count);
+            }
+        }
+        std::mem::take(&mut self.profile)
+    }
+}
+
+impl Drop for Profiler {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,261 @@
+// crates/dryad_runtime/src/regexp.rs
+//! Expressões regulares de primeira classe: o valor `Regex`, criado pelo
+//! literal `/padrão/flags` ou por `regex(padrão, flags?)`.
+//!
+//! Padrões são compilados uma vez e guardados em um cache global por
+//! (padrão, flags): um literal dentro de um laço e as funções
+//! `native_regex_*` reaproveitam a mesma compilação. Posições (`start`,
+//! `end`, `spans`) contam caracteres, como a indexação de strings.
+
+use crate::heap::{Heap, ManagedObject};
+use crate::value::Value;
+use regex::{Captures, Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Flags aceitas: `i` (ignora caixa), `m` (`^`/`$` por linha), `s` (`.` casa
+/// `\n`), `x` (ignora espaços e comentários no padrão), `U` (inverte a gula)
+pub const FLAGS: &str = "imsxU";
+
+/// Limite de padrões no cache; ao atingi-lo o cache é esvaziado
+const CACHE_LIMIT: usize = 256;
+
+fn cache() -> &'static Mutex<HashMap<(String, String), Regex>> {
+    static CACHE: OnceLock<Mutex<HashMap<(String, String), Regex>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Regex compilada com o texto e as flags de origem
+#[derive(Debug, Clone)]
+pub struct Regexp {
+    pub regex: Regex,
+    pub source: String,
+    pub flags: String,
+}
+
+impl PartialEq for Regexp {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.flags == other.flags
+    }
+}
+
+impl Regexp {
+    /// Compila (ou reaproveita do cache) `source` com `flags`
+    pub fn compile(source: &str, flags: &str) -> Result<Regexp, String> {
+        let mut normalized = String::new();
+        for flag in flags.chars() {
+            if !FLAGS.contains(flag) {
+                return Err(format!("Flag de regex inválida '{}' (use i, m, s, x ou U)", flag));
+            }
+            if !normalized.contains(flag) {
+                normalized.push(flag);
+            }
+        }
+
+        let key = (source.to_string(), normalized);
+        let mut cache = cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(regex) = cache.get(&key) {
+            return Ok(Regexp { regex: regex.clone(), source: key.0, flags: key.1 });
+        }
+
+        let flag = |c| key.1.contains(c);
+        let regex = RegexBuilder::new(source)
+            .case_insensitive(flag('i'))
+            .multi_line(flag('m'))
+            .dot_matches_new_line(flag('s'))
+            .ignore_whitespace(flag('x'))
+            .swap_greed(flag('U'))
+            .build()
+            .map_err(|e| format!("Regex inválida /{}/: {}", source, e))?;
+        if cache.len() >= CACHE_LIMIT {
+            cache.clear();
+        }
+        cache.insert(key.clone(), regex.clone());
+        Ok(Regexp { regex, source: key.0, flags: key.1 })
+    }
+
+    /// Forma de literal, `/a\/b/i`: barras do padrão são escapadas
+    pub fn literal(&self) -> String {
+        let mut out = String::from("/");
+        let mut escaped = false;
+        for c in self.source.chars() {
+            if c == '/' && !escaped {
+                out.push('\\');
+            }
+            escaped = c == '\\' && !escaped;
+            out.push(c);
+        }
+        out.push('/');
+        out.push_str(&self.flags);
+        out
+    }
+
+    /// Nomes dos grupos nomeados, na ordem em que aparecem
+    pub fn group_names(&self) -> Vec<&str> {
+        self.regex.capture_names().flatten().collect()
+    }
+}
+
+/// Padrão aceito pelas funções `native_regex_*`: string ou `Regex`
+pub fn from_value(value: &Value) -> Result<Regexp, String> {
+    match value {
+        Value::Regex(regexp) => Ok((**regexp).clone()),
+        Value::String(source) => Regexp::compile(source, ""),
+        other => Err(format!("Padrão deve ser uma string ou Regex, encontrado {}", other.type_name())),
+    }
+}
+
+/// Posição em caracteres de um índice em bytes de `text`
+fn char_offset(text: &str, byte: usize) -> usize {
+    text[..byte].chars().count()
+}
+
+/// Índice em bytes da posição `chars` (ou o fim do texto)
+fn byte_offset(text: &str, chars: usize) -> usize {
+    text.char_indices().nth(chars).map(|(i, _)| i).unwrap_or(text.len())
+}
+
+fn allocate_object(heap: &mut Heap, properties: HashMap<String, Value>) -> Value {
+    Value::Object(heap.allocate(ManagedObject::Object { properties, methods: HashMap::new() }))
+}
+
+/// Objeto de um match: `{ text, start, end, groups, named, spans }`.
+/// `groups` e `spans` listam os grupos 1..n (`null` se o grupo não participou);
+/// `named` mapeia os grupos nomeados. `start_char` é a posição em caracteres do
+/// início do match, já calculada por quem percorre o texto.
+pub fn match_object(regexp: &Regexp, text: &str, captures: &Captures, start_char: usize, heap: &mut Heap) -> Value {
+    let whole = captures.get(0).expect("grupo 0 sempre participa do match");
+    // Grupos ficam dentro do match: conta só a partir do início dele
+    let position = |byte: usize| start_char + char_offset(&text[whole.start()..], byte - whole.start());
+
+    let mut groups = Vec::new();
+    let mut spans = Vec::new();
+    for group in captures.iter().skip(1) {
+        match group {
+            Some(group) => {
+                groups.push(Value::String(group.as_str().to_string()));
+                let span = vec![
+                    Value::Number(position(group.start()) as f64),
+                    Value::Number(position(group.end()) as f64),
+                ];
+                spans.push(Value::Tuple(heap.allocate(ManagedObject::Tuple(span))));
+            }
+            None => {
+                groups.push(Value::Null);
+                spans.push(Value::Null);
+            }
+        }
+    }
+    let named: HashMap<String, Value> = regexp
+        .group_names()
+        .into_iter()
+        .map(|name| {
+            let value = captures
+                .name(name)
+                .map(|group| Value::String(group.as_str().to_string()))
+                .unwrap_or(Value::Null);
+            (name.to_string(), value)
+        })
+        .collect();
+
+    let mut properties = HashMap::new();
+    properties.insert("text".to_string(), Value::String(whole.as_str().to_string()));
+    properties.insert("start".to_string(), Value::Number(start_char as f64));
+    properties.insert("end".to_string(), Value::Number(position(whole.end()) as f64));
+    properties.insert("groups".to_string(), Value::Array(heap.allocate(ManagedObject::Array(groups))));
+    properties.insert("spans".to_string(), Value::Array(heap.allocate(ManagedObject::Array(spans))));
+    properties.insert("named".to_string(), allocate_object(heap, named));
+    allocate_object(heap, properties)
+}
+
+/// Matches sem sobreposição, até `limit`, com a posição em caracteres de cada um
+pub fn matches<'t>(regexp: &Regexp, text: &'t str, limit: Option<usize>) -> Vec<(Captures<'t>, usize)> {
+    let mut found = Vec::new();
+    let (mut byte, mut chars) = (0, 0);
+    for captures in regexp.regex.captures_iter(text).take(limit.unwrap_or(usize::MAX)) {
+        let start = captures.get(0).map(|m| m.start()).unwrap_or(byte);
+        chars += char_offset(&text[byte..], start - byte);
+        byte = start;
+        found.push((captures, chars));
+    }
+    found
+}
+
+pub fn property(regexp: &Regexp, name: &str, heap: &mut Heap) -> Result<Value, String> {
+    match name {
+        "source" => Ok(Value::String(regexp.source.clone())),
+        "flags" => Ok(Value::String(regexp.flags.clone())),
+        "group_count" => Ok(Value::Number((regexp.regex.captures_len() - 1) as f64)),
+        "group_names" => {
+            let names = regexp.group_names().into_iter().map(|n| Value::String(n.to_string())).collect();
+            Ok(Value::Array(heap.allocate(ManagedObject::Array(names))))
+        }
+        _ => Err(format!("Propriedade '{}' não encontrada em regex", name)),
+    }
+}
+
+fn text_arg<'a>(args: &'a [Value], method: &str) -> Result<&'a str, String> {
+    match args.first() {
+        Some(Value::String(text)) => Ok(text),
+        Some(other) => Err(format!("{}() espera uma string, encontrado {}", method, other.type_name())),
+        None => Err(format!("{}() espera uma string", method)),
+    }
+}
+
+fn count_arg(args: &[Value], index: usize, method: &str) -> Result<Option<usize>, String> {
+    match args.get(index) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => Ok(Some(*n as usize)),
+        Some(Value::Int(i)) if *i >= 0 => Ok(Some(*i as usize)),
+        Some(_) => Err(format!("{}(): argumento {} deve ser um inteiro não negativo", method, index + 1)),
+    }
+}
+
+/// Métodos que não chamam código Dryad; `replace`/`replace_all` com uma função
+/// de substituição são tratados pelo interpretador
+pub fn method(regexp: &Regexp, name: &str, args: &[Value], heap: &mut Heap) -> Result<Value, String> {
+    match name {
+        "test" => Ok(Value::Bool(regexp.regex.is_match(text_arg(args, name)?))),
+        // find(texto, início?): primeiro match a partir da posição `início`
+        "find" => {
+            let text = text_arg(args, name)?;
+            let from = count_arg(args, 1, name)?.unwrap_or(0);
+            let start = byte_offset(text, from);
+            Ok(match regexp.regex.captures_at(text, start) {
+                Some(captures) => {
+                    let start_char = from + char_offset(&text[start..], captures.get(0).map_or(start, |m| m.start()) - start);
+                    match_object(regexp, text, &captures, start_char, heap)
+                }
+                None => Value::Null,
+            })
+        }
+        "find_all" => {
+            let text = text_arg(args, name)?;
+            let limit = count_arg(args, 1, name)?;
+            let found = matches(regexp, text, limit)
+                .iter()
+                .map(|(captures, start)| match_object(regexp, text, captures, *start, heap))
+                .collect();
+            Ok(Value::Array(heap.allocate(ManagedObject::Array(found))))
+        }
+        "replace" | "replace_all" => {
+            let text = text_arg(args, name)?;
+            let replacement = match args.get(1) {
+                Some(Value::String(replacement)) => replacement.as_str(),
+                _ => return Err(format!("{}() espera uma string de substituição ou uma função", name)),
+            };
+            let limit = if name == "replace" { 1 } else { 0 };
+            Ok(Value::String(regexp.regex.replacen(text, limit, replacement).into_owned()))
+        }
+        "split" => {
+            let text = text_arg(args, name)?;
+            let parts: Vec<Value> = match count_arg(args, 1, name)? {
+                Some(limit) => regexp.regex.splitn(text, limit).map(|s| Value::String(s.to_string())).collect(),
+                None => regexp.regex.split(text).map(|s| Value::String(s.to_string())).collect(),
+            };
+            Ok(Value::Array(heap.allocate(ManagedObject::Array(parts))))
+        }
+        "to_string" => Ok(Value::String(regexp.literal())),
+        _ => Err(format!("Método '{}' não encontrado em regex", name)),
+    }
+}
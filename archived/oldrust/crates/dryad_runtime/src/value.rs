@@ -1,5 +1,10 @@
-use crate::heap::HeapId;
-use dryad_parser::ast::{Expr, Stmt, Visibility};
+use crate::heap::{Heap, HeapId, ManagedObject};
+use crate::datetime;
+use crate::regexp;
+use crate::numeric::{self, Decimal};
+use num_bigint_dig::BigInt;
+use num_traits::Zero;
+use dryad_parser::ast::{Expr, Stmt, Type, Visibility};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -12,6 +17,9 @@ pub enum FlowControl {
 #[derive(Debug, Clone)]
 pub enum Value {
     Number(f64),
+    Int(i64),          // 42i: inteiro de 64 bits com detecção de overflow
+    BigInt(BigInt),    // 42n: inteiro de precisão arbitrária
+    Decimal(Decimal),  // 1.50d: decimal exato
     String(String),
     Bool(bool),
     Null,
@@ -23,6 +31,7 @@ pub enum Value {
         params: Vec<(String, Option<Expr>)>,
         rest_param: Option<String>,
         body: Stmt,
+        signature: Signature,
     },
     AsyncFunction {
         name: String,
@@ -53,6 +62,27 @@ pub enum Value {
     Instance(HeapId),
     Object(HeapId),
     Result(bool, Box<Value>), // (is_ok, value/error)
+    Enum(HeapId),             // enum Nome { ... }
+    Variant(HeapId),          // Nome.Variante(campos...)
+    GeneratorFunction {
+        name: String,
+        params: Vec<(String, Option<Expr>)>,
+        rest_param: Option<String>,
+        body: Stmt,
+    },
+    Generator(HeapId), // resultado de chamar uma function*
+    Map(HeapId),       // Map(): chaves de qualquer valor, em ordem de inserção
+    Set(HeapId),       // Set(): valores únicos, em ordem de inserção
+    Range {
+        start: f64,
+        end: f64,
+        inclusive: bool,
+    }, // início..fim, avaliado sob demanda
+    DateTime(datetime::DateTime), // instante + fuso de exibição
+    Date(chrono::NaiveDate),      // dia do calendário, sem hora nem fuso
+    Duration(chrono::TimeDelta),  // intervalo exato, em nanossegundos
+    TimeZone(datetime::Zone),     // fuso IANA, deslocamento fixo, UTC ou local
+    Regex(Box<regexp::Regexp>),   // /padrão/flags, compilada uma vez
 }
 
 #[derive(Debug, Clone)]
@@ -61,12 +91,35 @@ pub struct ObjectMethod {
     pub body: Stmt,
 }
 
+/// Tipos declarados de uma função ou método, verificados com `--strict-types`
+#[derive(Debug, Clone, Default)]
+pub struct Signature {
+    pub param_types: Vec<Option<Type>>,
+    pub return_type: Option<Type>,
+}
+
+impl Signature {
+    pub fn new(params: &[(String, Option<Type>, Option<Expr>)], return_type: &Option<Type>) -> Self {
+        Signature {
+            param_types: params.iter().map(|(_, t, _)| t.clone()).collect(),
+            return_type: return_type.clone(),
+        }
+    }
+
+    pub fn param_type(&self, index: usize) -> Option<&Type> {
+        self.param_types.get(index).and_then(|t| t.as_ref())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ClassMethod {
     pub visibility: Visibility,
     pub is_static: bool,
     pub params: Vec<(String, Option<Expr>)>,
     pub body: Stmt,
+    pub signature: Signature,
+    /// `abstract function` — precisa ser implementado por uma subclasse concreta
+    pub is_abstract: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +127,7 @@ pub struct ClassProperty {
     pub visibility: Visibility,
     pub is_static: bool,
     pub default_value: Option<Value>,
+    pub declared_type: Option<Type>,
 }
 
 #[derive(Debug, Clone)]
@@ -94,6 +148,45 @@ pub struct ClassSetter {
 }
 
 impl Value {
+    /// Nome do tipo em runtime, como retornado por `typeof`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Number(_) => "number",
+            Value::Int(_) => "int",
+            Value::BigInt(_) => "bigint",
+            Value::Decimal(_) => "decimal",
+            Value::Bool(_) => "boolean",
+            Value::Null => "null",
+            Value::Array(_) => "array",
+            Value::Tuple(_) => "tuple",
+            Value::Exception(_) => "exception",
+            Value::Function { .. } => "function",
+            Value::AsyncFunction { .. } => "async_function",
+            Value::ThreadFunction { .. } => "thread_function",
+            Value::Lambda(_) => "lambda",
+            Value::Thread { .. } => "thread",
+            Value::Mutex { .. } => "mutex",
+            Value::Promise { .. } => "promise",
+            Value::Class(_) => "class",
+            Value::Instance(_) => "instance",
+            Value::Object(_) => "object",
+            Value::Result(_, _) => "result",
+            Value::Enum(_) => "enum",
+            Value::Variant(_) => "variant",
+            Value::GeneratorFunction { .. } => "generator_function",
+            Value::Generator(_) => "generator",
+            Value::Map(_) => "map",
+            Value::Set(_) => "set",
+            Value::Range { .. } => "range",
+            Value::DateTime(_) => "datetime",
+            Value::Date(_) => "date",
+            Value::Duration(_) => "duration",
+            Value::TimeZone(_) => "timezone",
+            Value::Regex(_) => "regex",
+        }
+    }
+
     pub fn to_string(&self) -> String {
         match self {
             Value::Number(n) => {
@@ -103,6 +196,9 @@ impl Value {
                     format!("{}", n)
                 }
             }
+            Value::Int(i) => i.to_string(),
+            Value::BigInt(b) => b.to_string(),
+            Value::Decimal(d) => d.to_string(),
             Value::String(s) => s.clone(),
             Value::Bool(b) => format!("{}", b),
             Value::Null => "null".to_string(),
@@ -125,6 +221,26 @@ impl Value {
             Value::Class(id) => format!("class (heap:{})", id),
             Value::Instance(id) => format!("instance (heap:{})", id),
             Value::Object(id) => format!("object (heap:{})", id),
+            Value::Enum(id) => format!("enum (heap:{})", id),
+            Value::Variant(id) => format!("variant (heap:{})", id),
+            Value::GeneratorFunction { name, .. } => format!("function* {}", name),
+            Value::Generator(id) => format!("generator (heap:{})", id),
+            Value::Map(id) => format!("map (heap:{})", id),
+            Value::Set(id) => format!("set (heap:{})", id),
+            Value::Range {
+                start,
+                end,
+                inclusive,
+            } => format!(
+                "{}{}{}",
+                Value::Number(*start).to_string(),
+                if *inclusive { "..=" } else { ".." },
+                Value::Number(*end).to_string()
+            ),
+            Value::DateTime(_) | Value::Date(_) | Value::Duration(_) | Value::TimeZone(_) => {
+                datetime::to_text(self).unwrap_or_default()
+            }
+            Value::Regex(regexp) => regexp.literal(),
             Value::Result(ok, val) => {
                 if *ok {
                     format!("Ok({})", val.to_string())
@@ -140,21 +256,171 @@ impl Value {
             Value::Bool(b) => *b,
             Value::Null => false,
             Value::Number(n) => *n != 0.0,
+            Value::Int(i) => *i != 0,
+            Value::BigInt(b) => !b.is_zero(),
+            Value::Decimal(d) => !d.is_zero(),
             Value::String(s) => !s.is_empty(),
             Value::Array(_)
             | Value::Tuple(_)
             | Value::Lambda(_)
             | Value::Class(_)
             | Value::Instance(_)
-            | Value::Object(_) => true,
+            | Value::Object(_)
+            | Value::Enum(_)
+            | Value::Variant(_)
+            | Value::Generator(_)
+            | Value::Map(_)
+            | Value::Set(_)
+            | Value::Range { .. }
+            | Value::DateTime(_)
+            | Value::Date(_)
+            | Value::Duration(_)
+            | Value::TimeZone(_)
+            | Value::Regex(_) => true,
             Value::Exception(_) => false,
-            Value::Function { .. } | Value::AsyncFunction { .. } | Value::ThreadFunction { .. } => {
-                true
-            }
+            Value::Function { .. }
+            | Value::AsyncFunction { .. }
+            | Value::ThreadFunction { .. }
+            | Value::GeneratorFunction { .. } => true,
             Value::Thread { is_running, .. } => *is_running,
             Value::Mutex { .. } => true,
             Value::Promise { resolved, .. } => *resolved,
             Value::Result(ok, _) => *ok,
         }
     }
+
+    /// Igualdade estrutural: compara arrays, tuplas, objetos e instâncias pelo conteúdo
+    /// em vez da identidade no heap. Usada pelas asserções e pelo `dryad test`.
+    pub fn deep_equals(&self, other: &Value, heap: &Heap) -> bool {
+        let mut visiting = Vec::new();
+        deep_equals_inner(self, other, heap, &mut visiting)
+    }
+}
+
+fn deep_equals_inner(
+    left: &Value,
+    right: &Value,
+    heap: &Heap,
+    visiting: &mut Vec<(HeapId, HeapId)>,
+) -> bool {
+    let (a, b) = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => return a == b,
+        (a, b) if numeric::is_exact(a) || numeric::is_exact(b) => {
+            return numeric::compare(a, b) == Some(std::cmp::Ordering::Equal)
+        }
+        (Value::String(a), Value::String(b)) => return a == b,
+        (Value::Bool(a), Value::Bool(b)) => return a == b,
+        (Value::Null, Value::Null) => return true,
+        (Value::Exception(a), Value::Exception(b)) => return a == b,
+        (Value::Regex(a), Value::Regex(b)) => return a == b,
+        (a, b) if datetime::is_temporal(a) && datetime::is_temporal(b) => {
+            return datetime::equals(a, b).unwrap_or(false)
+        }
+        (Value::Result(ok_a, a), Value::Result(ok_b, b)) => {
+            return ok_a == ok_b && deep_equals_inner(a, b, heap, visiting)
+        }
+        (Value::Array(a), Value::Array(b))
+        | (Value::Tuple(a), Value::Tuple(b))
+        | (Value::Instance(a), Value::Instance(b))
+        | (Value::Object(a), Value::Object(b))
+        | (Value::Variant(a), Value::Variant(b))
+        | (Value::Map(a), Value::Map(b))
+        | (Value::Set(a), Value::Set(b)) => (*a, *b),
+        (Value::Lambda(a), Value::Lambda(b))
+        | (Value::Class(a), Value::Class(b))
+        | (Value::Enum(a), Value::Enum(b))
+        | (Value::Generator(a), Value::Generator(b)) => return a == b,
+        (
+            Value::Range {
+                start: sa,
+                end: ea,
+                inclusive: ia,
+            },
+            Value::Range {
+                start: sb,
+                end: eb,
+                inclusive: ib,
+            },
+        ) => return sa == sb && ea == eb && ia == ib,
+        (Value::Function { name: a, .. }, Value::Function { name: b, .. }) => return a == b,
+        _ => return false,
+    };
+
+    // Referências idênticas ou um par já em comparação (estrutura cíclica)
+    if a == b || visiting.contains(&(a, b)) {
+        return true;
+    }
+
+    visiting.push((a, b));
+    let equal = match (heap.get(a), heap.get(b)) {
+        (Some(ManagedObject::Array(xs)), Some(ManagedObject::Array(ys)))
+        | (Some(ManagedObject::Tuple(xs)), Some(ManagedObject::Tuple(ys))) => {
+            xs.len() == ys.len()
+                && xs
+                    .iter()
+                    .zip(ys)
+                    .all(|(x, y)| deep_equals_inner(x, y, heap, visiting))
+        }
+        (
+            Some(ManagedObject::Instance {
+                class_name: ca,
+                properties: pa,
+            }),
+            Some(ManagedObject::Instance {
+                class_name: cb,
+                properties: pb,
+            }),
+        ) => ca == cb && properties_equal(pa, pb, heap, visiting),
+        (
+            Some(ManagedObject::Object { properties: pa, .. }),
+            Some(ManagedObject::Object { properties: pb, .. }),
+        ) => properties_equal(pa, pb, heap, visiting),
+        (
+            Some(ManagedObject::Variant {
+                enum_name: ea,
+                variant: va,
+                fields: fa,
+            }),
+            Some(ManagedObject::Variant {
+                enum_name: eb,
+                variant: vb,
+                fields: fb,
+            }),
+        ) => {
+            ea == eb
+                && va == vb
+                && fa.len() == fb.len()
+                && fa
+                    .iter()
+                    .zip(fb)
+                    .all(|((_, x), (_, y))| deep_equals_inner(x, y, heap, visiting))
+        }
+        // Mesmas chaves (em qualquer ordem) com valores iguais
+        (Some(ManagedObject::Map(xs)), Some(ManagedObject::Map(ys))) => {
+            xs.len() == ys.len()
+                && xs.iter().all(|(key, (_, x))| {
+                    ys.get(key)
+                        .is_some_and(|(_, y)| deep_equals_inner(x, y, heap, visiting))
+                })
+        }
+        (Some(ManagedObject::Set(xs)), Some(ManagedObject::Set(ys))) => {
+            xs.len() == ys.len() && xs.keys().all(|key| ys.contains_key(key))
+        }
+        _ => false,
+    };
+    visiting.pop();
+    equal
+}
+
+fn properties_equal(
+    a: &HashMap<String, Value>,
+    b: &HashMap<String, Value>,
+    heap: &Heap,
+    visiting: &mut Vec<(HeapId, HeapId)>,
+) -> bool {
+    a.len() == b.len()
+        && a.iter().all(|(key, x)| match b.get(key) {
+            Some(y) => deep_equals_inner(x, y, heap, visiting),
+            None => false,
+        })
 }
@@ -0,0 +1,93 @@
+// crates/dryad_runtime/tests/codec_tests.rs
+use dryad_errors::DryadError;
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::Interpreter;
+
+fn run(input: &str) -> Result<String, DryadError> {
+    let source = format!("#<encode_decode>\n{}", input);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.execute_and_return_value(&program)?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn eval(input: &str) -> String {
+    run(input).unwrap_or_else(|e| panic!("erro ao executar {:?}: {:?}", input, e))
+}
+
+#[test]
+fn test_yaml_encode_keeps_integers_and_map_order() {
+    assert_eq!(
+        eval(r#"native_yaml_encode(Map([["zeta", 1], ["alfa", [true, null, 1.5]]]))"#),
+        r#""zeta: 1\nalfa:\n- true\n- null\n- 1.5\n""#
+    );
+    // Objetos são escritos em ordem alfabética
+    assert_eq!(eval(r#"native_yaml_encode({ b: "x", a: 2 })"#), r#""a: 2\nb: x\n""#);
+}
+
+#[test]
+fn test_yaml_decode() {
+    let doc = r#"let doc = "nome: dryad\nversao: 2\npi: 3.14\ntags: [a, b]\nbig: 9007199254740993\nvazio: ~\n";"#;
+    assert_eq!(eval(&format!("{}\nnative_yaml_decode(doc).tags", doc)), r#"["a", "b"]"#);
+    assert_eq!(eval(&format!("{}\nnative_yaml_decode(doc).versao", doc)), "2");
+    assert_eq!(eval(&format!("{}\nnative_yaml_decode(doc).pi", doc)), "3.14");
+    assert_eq!(eval(&format!("{}\ntypeof(native_yaml_decode(doc).big)", doc)), r#""int""#);
+    assert_eq!(eval(&format!("{}\nnative_yaml_decode(doc).vazio", doc)), "null");
+    assert!(run(r#"native_yaml_decode("a: [1, 2")"#).is_err());
+}
+
+#[test]
+fn test_yaml_ordered_round_trip() {
+    let input = r#"
+        let texto = "zeta: 1\nalfa:\n  y: 2\n  x: 3\n";
+        native_yaml_encode(native_yaml_decode(texto, { ordered: true })) == texto
+    "#;
+    assert_eq!(eval(input), "true");
+    assert_eq!(eval(r#"native_yaml_decode("1: um\n2: dois", { ordered: true }).get(2)"#), r#""dois""#);
+}
+
+#[test]
+fn test_toml_round_trip() {
+    let input = r#"
+        let texto = "titulo = \"app\"\nporta = 8080\n\n[banco]\nhost = \"localhost\"\nreplicas = [1, 2]\n";
+        let config = native_toml_decode(texto, { ordered: true });
+        native_toml_encode(config) == texto
+    "#;
+    assert_eq!(eval(input), "true");
+    assert_eq!(eval(r#"native_toml_decode("[a]\nb = 1").a.b"#), "1");
+    // Datas viram strings ISO
+    assert_eq!(eval(r#"native_toml_decode("d = 2024-05-01T10:00:00Z").d"#), r#""2024-05-01T10:00:00Z""#);
+    // Campos null são omitidos; a raiz precisa ser uma tabela
+    assert_eq!(eval(r#"native_toml_encode({ a: 1, b: null })"#), r#""a = 1\n""#);
+    assert!(run("native_toml_encode([1, 2])").is_err());
+    assert!(run(r#"native_toml_decode("a = ")"#).is_err());
+}
+
+#[test]
+fn test_cbor_round_trip() {
+    // {"a": 1} em CBOR: mapa de 1 par, texto "a", inteiro 1
+    assert_eq!(eval(r#"native_cbor_encode({ a: 1 })"#), "[161, 97, 97, 1]");
+    let input = r#"
+        let dados = Map([["n", 1.5], ["lista", [1, "dois", null]], ["big", 18446744073709551615n]]);
+        let volta = native_cbor_decode(native_cbor_encode(dados), { ordered: true });
+        [volta.keys(), volta.get("lista"), volta.get("big") == 18446744073709551615n, volta.get("n")]
+    "#;
+    assert_eq!(eval(input), r#"[["n", "lista", "big"], [1, "dois", null], true, 1.5]"#);
+    assert!(run("native_cbor_decode([255, 0])").is_err());
+}
+
+#[test]
+fn test_codecs_reject_unsupported_values() {
+    assert!(run("function f() {}\nnative_yaml_encode({ f: f })").is_err());
+    assert!(run("let a = [1];\na.push(a);\nnative_cbor_encode(a)").is_err());
+}
@@ -0,0 +1,167 @@
+// crates/dryad_runtime/tests/collections_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::collections::{MapEntries, MapKey, SetEntries};
+use dryad_runtime::heap::{Heap, ManagedObject};
+use dryad_runtime::interpreter::{Interpreter, Value};
+use std::collections::HashMap;
+
+fn run(input: &str) -> Result<String, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    let value = interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn eval(input: &str) -> String {
+    run(input).unwrap()
+}
+
+#[test]
+fn test_map_with_non_string_keys() {
+    let input = r#"
+        let m = new Map();
+        m.set(1, "número").set((1, 2), "tupla").set(true, "bool");
+        [m.get(1), m.get((1, 2)), m.get(true), m.get("1"), m.size]
+    "#;
+    assert_eq!(eval(input), r#"["número", "tupla", "bool", null, 3]"#);
+}
+
+#[test]
+fn test_numeric_keys_are_unified() {
+    // 1, 1i, 1n e 1.0d são a mesma chave, como em `1 == 1i`
+    assert_eq!(eval("let m = Map(); m.set(1, \"a\"); m.set(1n, \"b\"); [m.size, m.get(1.0d)]"), r#"[1, "b"]"#);
+    assert_eq!(eval("Set([0.1, 0.10d, 0.2]).size"), "2");
+}
+
+#[test]
+fn test_objects_are_keyed_by_identity() {
+    let input = r#"
+        let a = [1, 2];
+        let m = Map([[a, "a"]]);
+        [m.has(a), m.has([1, 2])]
+    "#;
+    assert_eq!(eval(input), "[true, false]");
+}
+
+#[test]
+fn test_map_preserves_insertion_order() {
+    let input = r#"
+        let m = Map();
+        m.set("z", 1).set("a", 2).set("m", 3);
+        m.set("z", 10);
+        m.delete("a");
+        m.set("a", 4);
+        m.entries()
+    "#;
+    assert_eq!(eval(input), r#"[("z", 10), ("m", 3), ("a", 4)]"#);
+}
+
+#[test]
+fn test_map_index_syntax() {
+    assert_eq!(eval("let m = Map(); m[(0, 0)] = \"origem\"; [m[(0, 0)], m[(1, 1)]]"), r#"["origem", null]"#);
+}
+
+#[test]
+fn test_map_get_default_and_delete() {
+    assert_eq!(eval("let m = Map({ a: 1 }); [m.get(\"b\", 0), m.delete(\"a\"), m.delete(\"a\"), m.size]"), "[0, true, false, 0]");
+}
+
+#[test]
+fn test_set_operations() {
+    let input = r#"
+        let a = Set([1, 2, 2, 3]);
+        let b = new Set([3, 4]);
+        [a.size, a.union(b), a.intersection(b), a.difference(b), Set([2]).isSubsetOf(a)]
+    "#;
+    assert_eq!(eval(input), "[3, Set(4) { 1, 2, 3, 4 }, Set(1) { 3 }, Set(2) { 1, 2 }, true]");
+}
+
+#[test]
+fn test_map_union_and_intersection() {
+    let input = r#"
+        let a = Map([["x", 1], ["y", 2]]);
+        let b = Map([["y", 20], ["z", 30]]);
+        [a.union(b), a.intersection(b)]
+    "#;
+    assert_eq!(
+        eval(input),
+        r#"[Map(3) { "x" => 1, "y" => 20, "z" => 30 }, Map(1) { "y" => 2 }]"#
+    );
+}
+
+#[test]
+fn test_for_in_over_map_and_set() {
+    let input = r#"
+        let m = Map([["a", 1], ["b", 2]]);
+        let total = 0;
+        let keys = "";
+        for ((k, v) in m) {
+            keys = keys + k;
+            total = total + v;
+        }
+        for (x in Set([10, 10, 20])) {
+            total = total + x;
+        }
+        keys + total
+    "#;
+    assert_eq!(eval(input), r#""ab33""#);
+}
+
+#[test]
+fn test_invalid_keys_and_entries() {
+    let error = run("function f() {} Map().set(f, 1)").unwrap_err();
+    assert!(error.contains("E3047"), "{}", error);
+    assert!(run("Map([1, 2])").unwrap_err().contains("E3047"));
+    assert!(run("Set([1]).union([2])").unwrap_err().contains("E3047"));
+}
+
+#[test]
+fn test_json_conversion() {
+    let input = r#"
+        #<encode_decode>
+        let m = Map([["b", Set([1, 2])], ["a", 1]]);
+        native_json_encode(m)
+    "#;
+    assert_eq!(eval(input), r#""{\"a\":1.0,\"b\":[1.0,2.0]}""#);
+    assert!(run("#<encode_decode>\nnative_json_encode(Map([[(1, 2), 3]]))").is_err());
+}
+
+#[test]
+fn test_gc_traces_map_and_set_contents() {
+    let mut heap = Heap::new();
+    let key = heap.allocate(ManagedObject::Array(vec![Value::Number(1.0)]));
+    let value = heap.allocate(ManagedObject::Object {
+        properties: HashMap::new(),
+        methods: HashMap::new(),
+    });
+    let element = heap.allocate(ManagedObject::Tuple(Vec::new()));
+
+    let mut map = MapEntries::new();
+    map.insert(MapKey::Identity(key), (Value::Array(key), Value::Object(value)));
+    let map_id = heap.allocate(ManagedObject::Map(map));
+    let mut set = SetEntries::new();
+    set.insert(MapKey::Tuple(Vec::new()), Value::Tuple(element));
+    let set_id = heap.allocate(ManagedObject::Set(set));
+
+    heap.collect(&[map_id, set_id]);
+    assert!(heap.get(key).is_some());
+    assert!(heap.get(value).is_some());
+    assert!(heap.get(element).is_some());
+
+    heap.collect(&[]);
+    assert!(heap.get(map_id).is_none());
+    assert!(heap.get(key).is_none());
+    assert!(heap.get(element).is_none());
+}
@@ -0,0 +1,90 @@
+// crates/dryad_runtime/tests/coverage_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::coverage::Coverage;
+use dryad_runtime::interpreter::Interpreter;
+use std::path::{Path, PathBuf};
+
+fn run_with_coverage(source: &str) -> Coverage {
+    let file = PathBuf::from("cobertura.dryad");
+    let mut lexer = Lexer::new_with_file(source, file.clone());
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_current_file(file);
+    interpreter.enable_coverage();
+    interpreter.execute(&program).unwrap();
+    interpreter.take_coverage().unwrap()
+}
+
+const SOURCE: &str = "function dobro(x) {\n    return x * 2;\n}\nfunction nunca() {\n    return 0;\n}\nlet i = 0;\nwhile (i < 2) {\n    i = dobro(i) + 1;\n}\nif (i > 100) {\n    i = 0;\n}\n";
+
+#[test]
+fn test_coverage_counts_executed_lines() {
+    let coverage = run_with_coverage(SOURCE);
+    let file = &coverage.files[Path::new("cobertura.dryad")];
+
+    assert_eq!(file.lines.get(&2), Some(&2));
+    assert_eq!(file.lines.get(&7), Some(&1));
+    assert_eq!(file.lines.get(&9), Some(&2));
+}
+
+#[test]
+fn test_coverage_reports_unexecuted_lines_with_zero() {
+    let coverage = run_with_coverage(SOURCE);
+    let file = &coverage.files[Path::new("cobertura.dryad")];
+
+    assert_eq!(file.lines.get(&5), Some(&0));
+    assert_eq!(file.lines.get(&12), Some(&0));
+    // Linhas sem statements (fechamento de bloco) não são executáveis
+    assert_eq!(file.lines.get(&3), None);
+}
+
+#[test]
+fn test_coverage_counts_branches() {
+    let coverage = run_with_coverage(SOURCE);
+    let file = &coverage.files[Path::new("cobertura.dryad")];
+
+    assert_eq!(file.branches.get(&8), Some(&[2, 1]));
+    assert_eq!(file.branches.get(&11), Some(&[0, 1]));
+    assert_eq!(file.branches_found(), 4);
+    assert_eq!(file.branches_hit(), 3);
+}
+
+#[test]
+fn test_coverage_lcov_output() {
+    let mut coverage = Coverage::new();
+    let file = Path::new("a.dryad");
+    coverage.register_lines(file, [1, 2, 3]);
+    coverage.hit_line(file, 1);
+    coverage.hit_line(file, 1);
+    coverage.hit_branch(file, 2, true);
+
+    assert_eq!(
+        coverage.to_lcov(),
+        "TN:\nSF:a.dryad\nBRDA:2,0,0,1\nBRDA:2,0,1,0\nBRF:2\nBRH:1\nDA:1,2\nDA:2,0\nDA:3,0\nLF:3\nLH:1\nend_of_record\n"
+    );
+}
+
+#[test]
+fn test_coverage_merge_sums_counts() {
+    let file = Path::new("a.dryad");
+    let mut first = Coverage::new();
+    first.hit_line(file, 1);
+    first.hit_branch(file, 1, false);
+    let mut second = Coverage::new();
+    second.hit_line(file, 1);
+    second.hit_branch(file, 1, true);
+
+    first.merge(&second);
+    assert_eq!(first.files[file].lines.get(&1), Some(&2));
+    assert_eq!(first.files[file].branches.get(&1), Some(&[1, 1]));
+}
@@ -168,3 +168,208 @@ fn test_sqlite_file_based() {
     }
 }
 
+
+fn run(code: &str) -> (Interpreter, Value) {
+    let mut interpreter = Interpreter::new();
+    interpreter.activate_native_category("database").unwrap();
+    let result = execute_dryad_code(&mut interpreter, code).expect("Execução falhou");
+    (interpreter, result)
+}
+
+fn rows_of(interpreter: &Interpreter, value: &Value) -> Vec<std::collections::HashMap<String, Value>> {
+    let Value::Array(id) = value else { panic!("Esperado Array, recebido: {:?}", value) };
+    let Some(ManagedObject::Array(rows)) = interpreter.heap.get(*id) else { panic!("Array inválido") };
+    rows.iter()
+        .map(|row| match row {
+            Value::Object(row_id) => match interpreter.heap.get(*row_id) {
+                Some(ManagedObject::Object { properties, .. }) => properties.clone(),
+                _ => panic!("Objeto inválido"),
+            },
+            other => panic!("Esperado objeto na linha, recebido: {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_sqlite_positional_and_named_params() {
+    let (interpreter, result) = run(r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_execute(db, "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)");
+        sqlite_execute(db, "INSERT INTO users (name, age) VALUES (?, ?)", ["O'Brien; DROP TABLE users", 30]);
+        sqlite_execute(db, "INSERT INTO users (name, age) VALUES (:name, :age)", { name: "Ana", age: 25 });
+        let rows = sqlite_query(db, "SELECT name, age FROM users WHERE age >= ? ORDER BY age", [20]);
+        sqlite_close(db);
+        rows
+    "#);
+
+    let rows = rows_of(&interpreter, &result);
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["name"], Value::String("Ana".to_string()));
+    assert_eq!(rows[1]["name"], Value::String("O'Brien; DROP TABLE users".to_string()));
+    assert_eq!(rows[1]["age"], Value::Number(30.0));
+}
+
+#[test]
+fn test_sqlite_param_count_mismatch_is_error() {
+    let mut interpreter = Interpreter::new();
+    interpreter.activate_native_category("database").unwrap();
+    let result = execute_dryad_code(&mut interpreter, r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_query(db, "SELECT ? + ?", [1])
+    "#);
+    assert!(result.is_err(), "Deveria falhar com parâmetros faltando");
+}
+
+#[test]
+fn test_sqlite_typed_columns() {
+    let (interpreter, result) = run(r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_execute(db, "CREATE TABLE t (big INTEGER, data BLOB, ratio REAL)");
+        sqlite_execute(db, "INSERT INTO t VALUES (?, ?, ?)", [9007199254740993i, [0, 255, 16], 0.5]);
+        sqlite_query(db, "SELECT big, data, ratio FROM t")
+    "#);
+
+    let rows = rows_of(&interpreter, &result);
+    assert!(matches!(rows[0]["big"], Value::Int(9007199254740993)), "big: {:?}", rows[0]["big"]);
+    assert_eq!(rows[0]["ratio"], Value::Number(0.5));
+    let Value::Array(bytes_id) = rows[0]["data"] else { panic!("Blob deveria ser array de bytes") };
+    let Some(ManagedObject::Array(bytes)) = interpreter.heap.get(bytes_id) else { panic!("Array inválido") };
+    assert_eq!(bytes, &vec![Value::Number(0.0), Value::Number(255.0), Value::Number(16.0)]);
+}
+
+#[test]
+fn test_sqlite_transaction_commits_and_rolls_back() {
+    let (interpreter, result) = run(r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_execute(db, "CREATE TABLE contas (nome TEXT, saldo INTEGER)");
+
+        function inserir(conn) {
+            sqlite_execute(conn, "INSERT INTO contas VALUES (?, ?)", ["ok", 10]);
+            return "feito";
+        }
+        function falhar(conn) {
+            sqlite_execute(conn, "INSERT INTO contas VALUES (?, ?)", ["perdida", 20]);
+            throw "saldo insuficiente";
+        }
+
+        let retorno = sqlite_transaction(db, inserir);
+        let erro = null;
+        try {
+            sqlite_transaction(db, falhar);
+        } catch (e) {
+            erro = e;
+        }
+        [retorno, erro, sqlite_in_transaction(db), sqlite_query(db, "SELECT nome FROM contas")]
+    "#);
+
+    let Value::Array(id) = result else { panic!("Esperado Array") };
+    let Some(ManagedObject::Array(items)) = interpreter.heap.get(id) else { panic!() };
+    assert_eq!(items[0], Value::String("feito".to_string()));
+    assert!(matches!(&items[1], Value::Exception(s) if s.contains("saldo insuficiente")), "erro: {:?}", items[1]);
+    assert_eq!(items[2], Value::Bool(false));
+    let rows = rows_of(&interpreter, &items[3]);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["nome"], Value::String("ok".to_string()));
+}
+
+#[test]
+fn test_sqlite_manual_transaction() {
+    let (interpreter, result) = run(r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_execute(db, "CREATE TABLE t (v INTEGER)");
+        sqlite_begin(db, "immediate");
+        sqlite_execute(db, "INSERT INTO t VALUES (1)");
+        let dentro = sqlite_in_transaction(db);
+        sqlite_rollback(db);
+        [dentro, sqlite_query(db, "SELECT v FROM t")]
+    "#);
+
+    let Value::Array(id) = result else { panic!("Esperado Array") };
+    let Some(ManagedObject::Array(items)) = interpreter.heap.get(id) else { panic!() };
+    assert_eq!(items[0], Value::Bool(true));
+    assert!(rows_of(&interpreter, &items[1]).is_empty());
+}
+
+#[test]
+fn test_sqlite_prepared_statement_and_cursor() {
+    let (interpreter, result) = run(r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_execute(db, "CREATE TABLE n (v INTEGER)");
+        let insert = sqlite_prepare(db, "INSERT INTO n VALUES (?)");
+        let i = 1;
+        while (i <= 5) {
+            sqlite_stmt_execute(insert, [i]);
+            i = i + 1;
+        }
+        sqlite_finalize(insert);
+
+        let cursor = sqlite_cursor(db, "SELECT v FROM n WHERE v > :min ORDER BY v", { min: 1 });
+        let primeira = sqlite_cursor_next(cursor);
+        let lote = sqlite_cursor_fetch(cursor, 10);
+        let fim = sqlite_cursor_next(cursor);
+        sqlite_cursor_close(cursor);
+        sqlite_close(db);
+        [primeira.v, lote, fim]
+    "#);
+
+    let Value::Array(id) = result else { panic!("Esperado Array") };
+    let Some(ManagedObject::Array(items)) = interpreter.heap.get(id) else { panic!() };
+    assert_eq!(items[0], Value::Number(2.0));
+    let lote = rows_of(&interpreter, &items[1]);
+    assert_eq!(lote.iter().map(|r| r["v"].clone()).collect::<Vec<_>>(), vec![Value::Number(3.0), Value::Number(4.0), Value::Number(5.0)]);
+    assert_eq!(items[2], Value::Null);
+}
+
+#[test]
+fn test_sqlite_statement_and_cursor_survive_other_queries() {
+    let (interpreter, result) = run(r#"
+        #<database>
+        let db = sqlite_open(":memory:");
+        sqlite_execute(db, "CREATE TABLE n (v INTEGER)");
+        sqlite_execute(db, "INSERT INTO n VALUES (1), (2), (3)");
+        let contar = sqlite_prepare(db, "SELECT count(*) AS total FROM n");
+        let cursor = sqlite_cursor(db, "SELECT v FROM n ORDER BY v");
+        let primeira = sqlite_cursor_next(cursor);
+
+        // Muitas queries diferentes na mesma conexão, inclusive escritas
+        let i = 0;
+        while (i < 40) {
+            sqlite_query(db, "SELECT " + i + " AS x");
+            i = i + 1;
+        }
+        sqlite_execute(db, "DELETE FROM n WHERE v = 3");
+
+        let resto = sqlite_cursor_fetch(cursor, 10);
+        let total = sqlite_stmt_query(contar)[0].total;
+        sqlite_close(db);
+        let fechado = false;
+        try { sqlite_cursor_next(cursor); } catch (e) { fechado = true; }
+        [primeira.v, resto, total, fechado]
+    "#);
+
+    let Value::Array(id) = result else { panic!("Esperado Array") };
+    let Some(ManagedObject::Array(items)) = interpreter.heap.get(id) else { panic!() };
+    assert_eq!(items[0], Value::Number(1.0));
+    let resto = rows_of(&interpreter, &items[1]);
+    assert_eq!(resto.iter().map(|r| r["v"].clone()).collect::<Vec<_>>(), vec![Value::Number(2.0), Value::Number(3.0)]);
+    assert_eq!(items[2], Value::Number(2.0));
+    assert_eq!(items[3], Value::Bool(true));
+}
+
+#[test]
+fn test_pg_pool_reports_connection_errors() {
+    let mut interpreter = Interpreter::new();
+    interpreter.activate_native_category("database").unwrap();
+    let result = execute_dryad_code(&mut interpreter, r#"
+        #<database>
+        let pool = pg_pool("host=127.0.0.1 port=1 user=x connect_timeout=1", { max_size: 2, acquire_timeout: 100 });
+        pg_query(pool, "SELECT 1")
+    "#);
+    assert!(result.is_err(), "Conexão recusada deveria virar erro");
+}
@@ -0,0 +1,194 @@
+use dryad_lexer::Lexer;
+use dryad_parser::Parser;
+use dryad_runtime::Interpreter;
+
+fn try_eval(code: &str) -> Result<String, String> {
+    let mut interpreter = Interpreter::new();
+    interpreter.activate_native_category("time").unwrap();
+    let mut lexer = Lexer::new(code);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().expect("Erro léxico");
+        let is_eof = matches!(token.token, dryad_lexer::Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    let program = Parser::new(tokens).parse().expect("Erro de parsing");
+    let value = interpreter.execute_and_return_value(&program).map_err(|e| e.to_string())?;
+    Ok(single_line(&interpreter.inspect_value(&value, &Default::default())))
+}
+
+/// Junta a saída do inspect, que quebra listas longas em várias linhas
+fn single_line(text: &str) -> String {
+    text.lines().map(str::trim).fold(String::new(), |mut out, line| {
+        if out.ends_with(',') {
+            out.push(' ');
+        }
+        out.push_str(line);
+        out
+    })
+}
+
+fn eval(code: &str) -> String {
+    try_eval(code).unwrap_or_else(|e| panic!("Erro de runtime: {}", e))
+}
+
+#[test]
+fn test_parse_rfc3339_keeps_offset() {
+    let result = eval(
+        r#"
+        let dt = datetime_parse("2024-03-10T09:30:15.250-03:00");
+        [dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second, dt.millisecond, dt.to_iso()]
+    "#,
+    );
+    assert_eq!(result, r#"[2024, 3, 10, 9, 30, 15, 250, "2024-03-10T09:30:15.250-03:00"]"#);
+}
+
+#[test]
+fn test_parse_custom_format_and_zone() {
+    let result = eval(
+        r#"
+        let dt = datetime_parse("10/03/2024 14:05", "%d/%m/%Y %H:%M", "America/Sao_Paulo");
+        [dt.to_iso(), dt.format("%d de %B, %H:%M %Z"), typeof dt]
+    "#,
+    );
+    assert_eq!(result, r#"["2024-03-10T14:05:00-03:00", "10 de March, 14:05 -03", "datetime"]"#);
+}
+
+#[test]
+fn test_timezone_conversion_follows_dst() {
+    // 10/03/2024 é o início do horário de verão em Nova York (02:00 -> 03:00)
+    let result = eval(
+        r#"
+        let antes = datetime(2024, 3, 10, 1, 30, 0, "America/New_York");
+        let depois = antes + duration_parse("1h");
+        let sp = depois.to_timezone("America/Sao_Paulo");
+        [antes.to_iso(), depois.to_iso(), sp.to_iso(), depois == sp, timezone("America/New_York").offset_at(depois).hours]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"["2024-03-10T01:30:00-05:00", "2024-03-10T03:30:00-04:00", "2024-03-10T04:30:00-03:00", true, -4]"#
+    );
+}
+
+#[test]
+fn test_nonexistent_local_time_is_an_error() {
+    let error = try_eval(r#"datetime(2024, 3, 10, 2, 30, "America/New_York")"#).unwrap_err();
+    assert!(error.contains("não existe"), "{}", error);
+}
+
+#[test]
+fn test_arithmetic_and_comparison() {
+    let result = eval(
+        r#"
+        let inicio = datetime(2024, 1, 31, 12, 0);
+        let fim = inicio.add_months(1);
+        let d = fim - inicio;
+        [fim.to_iso(), d, d.days, inicio < fim, fim - duration({ days: 29 }) == inicio, d * 2, d / duration(86400000)]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"["2024-02-29T12:00:00Z", Duration(P29D), 29, true, true, Duration(P58D), 29]"#
+    );
+}
+
+#[test]
+fn test_date_calendar_helpers() {
+    let result = eval(
+        r#"
+        let d = date(2021, 1, 3);
+        let semana = d.start_of("week");
+        [d.weekday, d.iso_week, d.iso_year, semana, d.add_days(1) - d, date_parse("29/02/2024", "%d/%m/%Y").is_leap_year,
+         days_in_month(2023, 2), is_leap_year(1900), d.at(9, 30, "Europe/Lisbon").to_iso()]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"[7, 53, 2020, Date(2020-12-28), Duration(P1D), true, 28, false, "2021-01-03T09:30:00Z"]"#
+    );
+}
+
+#[test]
+fn test_duration_parse_and_format() {
+    let result = eval(
+        r#"
+        [duration_parse("PT1H30M"), duration_parse("1h30m").minutes, duration_parse("250ms").to_iso(),
+         duration_parse("-2d 4h"), -duration_parse("P1W"), duration(1500).seconds, duration_parse("PT0S")]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"[Duration(PT1H30M), 90, "PT0.25S", Duration(-P2DT4H), Duration(-P7D), 1.5, Duration(PT0S)]"#
+    );
+    let error = try_eval(r#"duration_parse("P1M")"#).unwrap_err();
+    assert!(error.contains("meses"), "{}", error);
+}
+
+#[test]
+fn test_datetime_inspect_and_concatenation() {
+    let result = eval(
+        r#"
+        let dt = datetime_from_timestamp(0, "America/Sao_Paulo");
+        [dt, dt.timestamp, dt.offset, "em " + dt.date, timezone("UTC")]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"[DateTime(1969-12-31T21:00:00-03:00 America/Sao_Paulo), 0, Duration(-PT3H), "em 1969-12-31", TimeZone(UTC)]"#
+    );
+}
+
+#[test]
+fn test_monotonic_timer() {
+    let result = eval(
+        r#"
+        let inicio = monotonic();
+        native_sleep(duration(20));
+        let gasto = elapsed(inicio);
+        [gasto.ms >= 20, gasto < duration_parse("5s")]
+    "#,
+    );
+    assert_eq!(result, "[true, true]");
+}
+
+#[test]
+fn test_invalid_inputs_report_errors() {
+    for code in [
+        r#"datetime_parse("ontem")"#,
+        r#"timezone("Marte/Olympus")"#,
+        r#"date(2023, 2, 29)"#,
+        r#"date(2024, 1, 1) + duration_parse("1h")"#,
+    ] {
+        assert!(try_eval(code).is_err(), "{} deveria falhar", code);
+    }
+}
+
+#[test]
+fn test_calendar_arithmetic_out_of_range() {
+    for code in [
+        "datetime_now().add_years(1000000000000000000)",
+        "date(2024, 1, 1).add_years(-1000000000000000000)",
+        "date(2024, 1, 1).add_months(1000000000000000000)",
+        "datetime_now().add_days(1000000000000000000)",
+    ] {
+        let error = try_eval(code).expect_err(code);
+        assert!(error.contains("Data fora do intervalo suportado"), "{}: {}", code, error);
+    }
+}
+
+#[test]
+fn test_dates_as_map_keys() {
+    let result = eval(
+        r#"
+        let dias = Set([date(2024, 5, 1), date_parse("2024-05-01"), date(2024, 5, 2)]);
+        let vistos = Map();
+        vistos.set(datetime_parse("2024-05-01T12:00:00Z"), "utc");
+        [dias.size, vistos.get(datetime_parse("2024-05-01T09:00:00-03:00"))]
+    "#,
+    );
+    assert_eq!(result, r#"[2, "utc"]"#);
+}
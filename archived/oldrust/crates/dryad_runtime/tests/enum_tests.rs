@@ -0,0 +1,109 @@
+// crates/dryad_runtime/tests/enum_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::inspect::InspectOptions;
+use dryad_runtime::interpreter::{Interpreter, Value};
+
+fn run(input: &str) -> Result<(Interpreter, Value), String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    let value = interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())?;
+    Ok((interpreter, value))
+}
+
+fn eval(input: &str) -> Value {
+    run(input).unwrap().1
+}
+
+const SHAPE: &str = "enum Shape { Circle(r), Rect(w, h), Empty }\n";
+
+#[test]
+fn test_match_on_constructor_patterns() {
+    let input = format!(
+        "{}{}",
+        SHAPE,
+        r#"
+        function area(s) {
+            return match s {
+                Shape.Circle(r) => r * r,
+                Rect(w, h) => w * h,
+                Shape.Empty => 0
+            };
+        }
+        area(Shape.Circle(3)) + area(Shape.Rect(2, 5)) + area(Shape.Empty)
+        "#
+    );
+    assert_eq!(eval(&input), Value::Number(19.0));
+}
+
+#[test]
+fn test_nested_patterns_and_guards() {
+    let input = format!(
+        "{}{}",
+        SHAPE,
+        r#"
+        let s = Shape.Rect(2, 2);
+        match s {
+            Shape.Rect(w, h) if (w != h) => "retângulo",
+            Shape.Rect(_, 2) => "quadrado 2",
+            _ => "outro"
+        }
+        "#
+    );
+    assert_eq!(eval(&input), Value::String("quadrado 2".to_string()));
+}
+
+#[test]
+fn test_pattern_without_fields_matches_any_payload() {
+    let input = format!(
+        "{}{}",
+        SHAPE,
+        "match Shape.Circle(1) { Shape.Rect => 1, Shape.Circle => 2 }"
+    );
+    assert_eq!(eval(&input), Value::Number(2.0));
+}
+
+#[test]
+fn test_variant_fields_and_equality() {
+    assert_eq!(eval(&format!("{}Shape.Rect(3, 4).h", SHAPE)), Value::Number(4.0));
+    assert_eq!(
+        eval(&format!("{}Shape.Rect(1, 2) == Shape.Rect(1, 2)", SHAPE)),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        eval(&format!("{}Shape.Empty == Shape.Circle(1)", SHAPE)),
+        Value::Bool(false)
+    );
+}
+
+#[test]
+fn test_inspect_variants() {
+    let (interpreter, value) = run(&format!("{}[Shape.Circle(2), Shape.Empty, Shape]", SHAPE)).unwrap();
+    assert_eq!(
+        interpreter.inspect_value(&value, &InspectOptions::default()),
+        "[Shape.Circle(2), Shape.Empty, [enum Shape]]"
+    );
+}
+
+#[test]
+fn test_constructor_errors() {
+    let err = run(&format!("{}Shape.Triangle(1)", SHAPE)).err().unwrap();
+    assert!(err.contains("não tem a variante 'Triangle'"), "{}", err);
+
+    let err = run(&format!("{}Shape.Rect(1)", SHAPE)).err().unwrap();
+    assert!(err.contains("espera 2 campo(s), recebeu 1"), "{}", err);
+
+    let err = run(&format!("{}Shape.Circle", SHAPE)).err().unwrap();
+    assert!(err.contains("espera 1 campo(s), recebeu 0"), "{}", err);
+}
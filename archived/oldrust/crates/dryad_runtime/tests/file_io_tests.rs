@@ -0,0 +1,374 @@
+use dryad_lexer::Lexer;
+use dryad_parser::Parser;
+use dryad_runtime::Interpreter;
+use std::path::{Path, PathBuf};
+
+/// Diretório vazio exclusivo do teste, usado como `sandbox_root`
+fn sandbox(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dryad_file_io_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write(root: &Path, relative: &str, content: &str) {
+    let path = root.join(relative);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+/// Executa o código com o sandbox em `root` e devolve o último valor formatado
+fn eval_in(root: &Path, code: &str) -> Result<String, String> {
+    let mut interpreter = Interpreter::new();
+    interpreter.set_sandbox_root(root.to_path_buf());
+    run(&mut interpreter, code)
+}
+
+/// Executa o código num interpreter existente (que mantém seu estado)
+fn run(interpreter: &mut Interpreter, code: &str) -> Result<String, String> {
+    interpreter.activate_native_category("file_io").unwrap();
+    let mut lexer = Lexer::new(code);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().expect("Erro léxico");
+        let is_eof = matches!(token.token, dryad_lexer::Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    let program = Parser::new(tokens).parse().expect("Erro de parsing");
+    let value = interpreter.execute_and_return_value(&program).map_err(|e| e.to_string())?;
+    Ok(single_line(&interpreter.inspect_value(&value, &Default::default())))
+}
+
+/// Junta a saída de `inspect`, que quebra linhas em valores longos
+fn single_line(text: &str) -> String {
+    text.lines().map(str::trim).fold(String::new(), |mut out, line| {
+        if out.ends_with(',') {
+            out.push(' ');
+        }
+        out.push_str(line);
+        out
+    })
+}
+
+#[test]
+fn test_file_handle_read_write_and_seek() {
+    let root = sandbox("handle");
+    let result = eval_in(
+        &root,
+        r#"
+        let f = file_open("dados.txt", "w+");
+        file_write(f, "primeira\r\nsegunda\nterceira");
+        file_seek(f, 0);
+        let linhas = [file_read_line(f), file_read_line(f)];
+        let pos = file_tell(f);
+        let resto = file_read(f);
+        file_seek(f, -4, "end");
+        file_write(f, "TRES");
+        file_seek(f, 0);
+        let inicio = file_read(f, 8);
+        file_seek(f, 0, "end");
+        let fim = [file_read_line(f), file_read(f, 1), file_read(f)];
+        file_flush(f);
+        file_close(f);
+        file_close(f);
+        [linhas, pos, resto, inicio, fim, read_file("dados.txt")]
+    "#,
+    );
+
+    assert_eq!(
+        result.unwrap(),
+        r#"[["primeira", "segunda"], 18, "terceira", "primeira", [null, null, ""], "primeira\r\nsegunda\ntercTRES"]"#
+    );
+}
+
+#[test]
+fn test_file_modes_are_enforced() {
+    let root = sandbox("modes");
+    write(&root, "existente.txt", "abc");
+    let result = eval_in(
+        &root,
+        r#"
+        let erros = [];
+        let r = file_open("existente.txt");
+        try { file_write(r, "x"); } catch (e) { erros.push("r"); }
+        file_close(r);
+        try { file_open("existente.txt", "x"); } catch (e) { erros.push("x"); }
+        try { file_open("existente.txt", "q"); } catch (e) { erros.push("q"); }
+        try { file_read_line(r); } catch (e) { erros.push("fechado"); }
+
+        let a = file_open("existente.txt", "a+");
+        file_write(a, "def");
+        file_seek(a, 0);
+        let lido = file_read(a);
+        file_close(a);
+
+        let b = file_open("existente.txt", "rb");
+        let bytes = file_read(b, 2);
+        file_close(b);
+        [erros, lido, bytes]
+    "#,
+    );
+
+    assert_eq!(result.unwrap(), r#"[["r", "x", "q", "fechado"], "abcdef", [97, 98]]"#);
+}
+
+#[test]
+fn test_for_in_over_file_lines_is_lazy() {
+    let root = sandbox("lines");
+    write(&root, "log.txt", "um\ndois\ntres\n");
+    let result = eval_in(
+        &root,
+        r#"
+        let vistas = [];
+        for (linha in file_lines("log.txt")) {
+            vistas.push(linha);
+        }
+        let f = file_open("log.txt");
+        let primeira = file_read_line(f);
+        let resto = [];
+        for (linha in f) {
+            resto.push(linha);
+        }
+        file_close(f);
+        [vistas, primeira, resto]
+    "#,
+    );
+
+    assert_eq!(result.unwrap(), r#"[["um", "dois", "tres"], "um", ["dois", "tres"]]"#);
+}
+
+#[test]
+fn test_walk_dir_with_filters() {
+    let root = sandbox("walk");
+    write(&root, "proj/b.rs", "");
+    write(&root, "proj/a.toml", "");
+    write(&root, "proj/src/main.rs", "");
+    write(&root, "proj/src/util/mod.rs", "");
+    write(&root, "proj/src/util_test.rs", "");
+    write(&root, "proj/.git/config", "");
+    let result = eval_in(
+        &root,
+        r#"
+        [
+            walk_dir("proj"),
+            walk_dir("proj", { extensions: [".rs"], max_depth: 2 }),
+            walk_dir("proj", { dirs: true, files: false, hidden: true }),
+            walk_dir("proj", { pattern: "*_test.*" })
+        ]
+    "#,
+    );
+
+    assert_eq!(
+        result.unwrap(),
+        concat!(
+            r#"[["proj/a.toml", "proj/b.rs", "proj/src/main.rs", "proj/src/util/mod.rs", "proj/src/util_test.rs"], "#,
+            r#"["proj/b.rs", "proj/src/main.rs", "proj/src/util_test.rs"], "#,
+            r#"["proj/.git", "proj/src", "proj/src/util"], "#,
+            r#"["proj/src/util_test.rs"]]"#
+        )
+    );
+}
+
+#[test]
+fn test_glob_patterns() {
+    let root = sandbox("glob");
+    write(&root, "src/lib.rs", "");
+    write(&root, "src/a/b/deep.rs", "");
+    write(&root, "src/notes.md", "");
+    write(&root, "src/.hidden.rs", "");
+    write(&root, "data1.csv", "");
+    write(&root, "data2.csv", "");
+    write(&root, "data9.txt", "");
+    let result = eval_in(
+        &root,
+        r#"
+        [glob("src/**/*.rs"), glob("data[0-5].*"), glob("*.csv"), glob("src/*.md"), glob("nada/*"), glob("src/.*")]
+    "#,
+    );
+
+    assert_eq!(
+        result.unwrap(),
+        r#"[["src/a/b/deep.rs", "src/lib.rs"], ["data1.csv", "data2.csv"], ["data1.csv", "data2.csv"], ["src/notes.md"], [], ["src/.hidden.rs"]]"#
+    );
+}
+
+#[test]
+fn test_stat_reports_metadata() {
+    let root = sandbox("stat");
+    write(&root, "cinco.txt", "12345");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(root.join("cinco.txt"), std::fs::Permissions::from_mode(0o640)).unwrap();
+    }
+    let result = eval_in(
+        &root,
+        r#"
+        let s = stat("cinco.txt");
+        let d = stat(".");
+        [s.size, s.is_file, s.is_dir, s.is_symlink, s.modified > 0, d.is_dir]
+    "#,
+    );
+    assert_eq!(result.unwrap(), "[5, true, false, false, true, true]");
+
+    #[cfg(unix)]
+    assert_eq!(eval_in(&root, r#"let s = stat("cinco.txt"); [s.mode, s.permissions]"#).unwrap(), r#"[416, "rw-r-----"]"#);
+}
+
+#[test]
+fn test_write_file_is_atomic_and_keeps_permissions() {
+    let root = sandbox("atomic");
+    write(&root, "config.json", "{}");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(root.join("config.json"), std::fs::Permissions::from_mode(0o600)).unwrap();
+    }
+    let result = eval_in(&root, r#"write_file("config.json", "{\"a\": 1}"); read_file("config.json")"#);
+    assert_eq!(result.unwrap(), r#""{\"a\": 1}""#);
+
+    // Nenhum arquivo temporário fica para trás
+    let entries: Vec<_> = std::fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(entries, vec![std::ffi::OsString::from("config.json")]);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(root.join("config.json")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
+
+#[test]
+fn test_tempfile_and_tempdir_inside_sandbox() {
+    let root = sandbox("temp");
+    let result = eval_in(
+        &root,
+        r#"
+        let t = tempfile({ prefix: "relatorio_", suffix: ".csv" });
+        file_write(t, "a,b\n1,2\n");
+        file_seek(t, 0);
+        let cabecalho = file_read_line(t);
+        file_close(t);
+
+        let d = tempdir();
+        write_file(d + "/nota.txt", "ok");
+        let sub = tempfile({ dir: "." });
+        file_close(sub);
+        [cabecalho, read_file(t.path), read_file(d + "/nota.txt"), file_exists(sub.path), stat(d).is_dir, t.path, d]
+    "#,
+    );
+
+    let result = result.unwrap();
+    assert!(result.starts_with(r#"["a,b", "a,b\n1,2\n", "ok", true, true, "#), "{}", result);
+    // Com sandbox os temporários são criados dentro dele
+    let root = root.canonicalize().unwrap().to_string_lossy().into_owned();
+    assert_eq!(result.matches(&format!("\"{}/", root)).count(), 2, "{}", result);
+}
+
+#[test]
+fn test_temp_paths_are_private_to_the_interpreter() {
+    // Sem sandbox os temporários vão para o diretório temporário do sistema,
+    // fora da raiz (diretório atual)
+    let mut first = Interpreter::new();
+    let mut second = Interpreter::new();
+    let created = run(
+        &mut first,
+        r#"
+        let t = tempfile();
+        file_write(t, "segredo");
+        let d = tempdir();
+        write_file(d + "/nota.txt", "ok");
+        [t.path, d, read_file(t.path), read_file(d + "/nota.txt")]
+    "#,
+    )
+    .unwrap();
+    let paths: Vec<&str> = created.split('"').skip(1).step_by(2).collect();
+    let (file, dir) = (paths[0], paths[1]);
+    assert_eq!(&paths[2..], ["segredo", "ok"]);
+
+    for code in [format!(r#"read_file("{}")"#, file), format!(r#"file_open("{}/nota.txt", "r")"#, dir)] {
+        let error = run(&mut second, &code).expect_err(&code);
+        assert!(error.contains("Acesso negado"), "{}: {}", code, error);
+    }
+
+    // Fechar o handle ou remover o diretório encerra o acesso do próprio dono
+    run(&mut first, &format!(r#"file_close(t); remove_dir("{}", true);"#, dir)).unwrap();
+    for code in [format!(r#"read_file("{}")"#, file), format!(r#"write_file("{}/nota.txt", "x")"#, dir)] {
+        let error = run(&mut first, &code).expect_err(&code);
+        assert!(error.contains("Acesso negado"), "{}: {}", code, error);
+    }
+    let _ = std::fs::remove_file(file);
+}
+
+#[test]
+fn test_paths_outside_sandbox_are_rejected() {
+    let root = sandbox("reject");
+    write(&root, "dentro.txt", "ok");
+    for code in [
+        r#"file_open("../fora.txt", "w")"#,
+        r#"file_lines("/etc/passwd")"#,
+        r#"walk_dir("..")"#,
+        r#"glob("/etc/*")"#,
+        r#"glob("../*")"#,
+        r#"stat("/etc/hostname")"#,
+        r#"tempdir({ dir: "/" })"#,
+    ] {
+        let error = eval_in(&root, code).expect_err(code);
+        assert!(error.contains("Acesso negado") || error.contains("não é permitido"), "{}: {}", code, error);
+    }
+    assert_eq!(eval_in(&root, r#"read_file("dentro.txt")"#).unwrap(), r#""ok""#);
+}
+
+#[test]
+fn test_dotdot_and_symlinks_cannot_escape_sandbox() {
+    let root = sandbox("escape");
+    let outside = sandbox("escape_outside");
+    write(&outside, "segredo.txt", "não");
+    std::os::unix::fs::symlink(&outside, root.join("fora")).unwrap();
+    write(&root, "dentro/a.txt", "ok");
+
+    let root_str = root.to_string_lossy();
+    for code in [
+        format!(r#"file_open("{}/../../novo.txt", "w")"#, root_str),
+        format!(r#"write_file("{}/dentro/../../novo.txt", "x")"#, root_str),
+        r#"write_file("fora/novo.txt", "x")"#.to_string(),
+        r#"glob("fora/*")"#.to_string(),
+    ] {
+        let error = eval_in(&root, &code).expect_err(&code);
+        assert!(error.contains("Acesso negado"), "{}: {}", code, error);
+    }
+    assert!(!outside.join("novo.txt").exists());
+
+    // Curingas não entram no diretório apontado pelo symlink
+    assert_eq!(eval_in(&root, r#"glob("*/*.txt")"#).unwrap(), r#"["dentro/a.txt"]"#);
+    assert_eq!(eval_in(&root, r#"glob("*/segredo.txt")"#).unwrap(), "[]");
+    assert_eq!(
+        eval_in(&root, &format!(r#"write_file("{}/dentro/../novo.txt", "x"); read_file("novo.txt")"#, root_str)).unwrap(),
+        r#""x""#
+    );
+}
+
+#[test]
+fn test_file_handles_belong_to_their_interpreter() {
+    let root = sandbox("handles");
+    write(&root, "a.txt", "conteudo");
+    let mut first = Interpreter::new();
+    first.set_sandbox_root(root.clone());
+    let id = run(&mut first, r#"let f = file_open("a.txt"); f.id"#).unwrap();
+
+    let mut second = Interpreter::new();
+    second.set_sandbox_root(root.clone());
+    for (code, message) in [
+        (format!("file_read({})", id), "esperado um arquivo aberto"),
+        (format!(r#"file_read({{ _type: "file", id: {} }})"#, id), "não está aberto"),
+    ] {
+        let error = run(&mut second, &code).expect_err(&code);
+        assert!(error.contains(message), "{}: {}", code, error);
+    }
+    run(&mut second, &format!(r#"file_close({{ _type: "file", id: {} }})"#, id)).unwrap();
+
+    assert_eq!(run(&mut first, "file_read(f)").unwrap(), r#""conteudo""#);
+}
@@ -248,4 +248,289 @@ fn test_http_server_multiple_instances() {
         }
         _ => panic!("Esperado String, recebido: {:?}", result),
     }
-}
\ No newline at end of file
+}
+// ========================
+// Testes contra localhost
+// ========================
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+/// Executa o script em outra thread; ele serve requisições até o servidor parar
+fn run_server_script(code: String) -> thread::JoinHandle<String> {
+    thread::Builder::new()
+        .stack_size(16 * 1024 * 1024)
+        .spawn(move || {
+            let mut interpreter = Interpreter::new();
+            interpreter.activate_native_category("http_server").unwrap();
+            let mut lexer = Lexer::new(&code);
+            let mut parser = Parser::new_from_lexer(&mut lexer).expect("Criação do parser falhou");
+            let program = parser.parse().expect("Parsing falhou");
+            match interpreter.execute_and_return_value(&program) {
+                Ok(value) => value.to_string(),
+                Err(e) => format!("erro: {}", e),
+            }
+        })
+        .unwrap()
+}
+
+fn connect(port: u16) -> BufReader<TcpStream> {
+    for _ in 0..200 {
+        if let Ok(stream) = TcpStream::connect(("127.0.0.1", port)) {
+            stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+            return BufReader::new(stream);
+        }
+        thread::sleep(Duration::from_millis(25));
+    }
+    panic!("Servidor não respondeu na porta {}", port);
+}
+
+fn send(conn: &mut BufReader<TcpStream>, raw: &[u8]) {
+    conn.get_mut().write_all(raw).unwrap();
+}
+
+struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Response {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+fn read_line(conn: &mut BufReader<TcpStream>) -> String {
+    let mut line = String::new();
+    conn.read_line(&mut line).unwrap();
+    line.trim_end_matches(['\r', '\n']).to_string()
+}
+
+fn read_response(conn: &mut BufReader<TcpStream>) -> Response {
+    let status_line = read_line(conn);
+    let status = status_line.split(' ').nth(1).expect("linha de status").parse().unwrap();
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(conn);
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').unwrap();
+        headers.push((name.to_string(), value.trim().to_string()));
+    }
+    let mut response = Response { status, headers, body: String::new() };
+
+    let mut body = Vec::new();
+    if response.header("transfer-encoding") == Some("chunked") {
+        loop {
+            let size = usize::from_str_radix(&read_line(conn), 16).unwrap();
+            let mut chunk = vec![0; size + 2];
+            conn.read_exact(&mut chunk).unwrap();
+            if size == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..size]);
+        }
+    } else if let Some(length) = response.header("content-length") {
+        body.resize(length.parse().unwrap(), 0);
+        conn.read_exact(&mut body).unwrap();
+    }
+    response.body = String::from_utf8(body).unwrap();
+    response
+}
+
+fn request(conn: &mut BufReader<TcpStream>, raw: &str) -> Response {
+    send(conn, raw.as_bytes());
+    read_response(conn)
+}
+
+#[test]
+fn test_http_server_handles_requests_over_localhost() {
+    let port = free_port();
+    let server = run_server_script(format!(r#"
+        #<http_server>
+        native_http_server_create("localhost_api", "127.0.0.1", {port}, {{ max_body_size: 4096 }});
+
+        function autenticar(req, res) {{
+            let headers = res.headers;
+            headers["x-powered-by"] = "dryad";
+            req.user = req.headers["authorization"];
+            if (req.path == "/privado" && req.user == null) {{
+                res.status = 401;
+                res.body = "não autorizado";
+                return false;
+            }}
+        }}
+        native_http_server_middleware("localhost_api", autenticar);
+
+        native_http_server_handle("localhost_api", "GET", "/users/:id", (req, res) => ({{ id: req.params.id, busca: req.query.q, user: req.user }}));
+        native_http_server_handle("localhost_api", "POST", "/soma", (req, res) => req.json.a + req.json.b);
+
+        function eco(req, res) {{
+            res.status = 201;
+            let headers = res.headers;
+            headers["content-type"] = "text/plain";
+            return req.method + " " + req.body;
+        }}
+        native_http_server_handle("localhost_api", "POST", "/eco", eco);
+        native_http_server_get("localhost_api", "/fixo", "conteúdo fixo");
+
+        function* partes() {{ yield "um,"; yield "dois,"; yield "três"; }}
+        function stream(req, res) {{ res.body = partes(); }}
+        native_http_server_handle("localhost_api", "GET", "/stream", stream);
+
+        function desligar(req, res) {{
+            native_http_server_stop("localhost_api");
+            return "tchau";
+        }}
+        native_http_server_handle("localhost_api", "POST", "/desligar", desligar);
+
+        native_http_server_start("localhost_api");
+        while (native_http_server_wait("localhost_api", 100)) {{ }}
+        "parado"
+    "#));
+
+    // Todas as requisições abaixo usam a mesma conexão keep-alive
+    let mut conn = connect(port);
+
+    let res = request(&mut conn, "GET /users/42?q=caf%C3%A9+com+leite HTTP/1.1\r\nHost: x\r\nAuthorization: token\r\n\r\n");
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("content-type"), Some("application/json"));
+    assert_eq!(res.header("x-powered-by"), Some("dryad"));
+    assert_eq!(res.header("connection"), Some("keep-alive"));
+    assert!(res.body.contains(r#""id":"42""#) && res.body.contains(r#""busca":"café com leite""#) && res.body.contains(r#""user":"token""#));
+
+    let res = request(&mut conn, "POST /soma HTTP/1.1\r\nHost: x\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"a\":2,\"b\":3}");
+    assert_eq!((res.status, res.body.as_str()), (200, "5"));
+
+    let res = request(&mut conn, "POST /eco HTTP/1.1\r\nHost: x\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n");
+    assert_eq!((res.status, res.body.as_str()), (201, "POST hello world"));
+    assert_eq!(res.header("content-type"), Some("text/plain"));
+
+    let res = request(&mut conn, "GET /stream HTTP/1.1\r\nHost: x\r\n\r\n");
+    assert_eq!(res.header("transfer-encoding"), Some("chunked"));
+    assert_eq!(res.body, "um,dois,três");
+
+    let res = request(&mut conn, "GET /fixo HTTP/1.1\r\nHost: x\r\n\r\n");
+    assert_eq!((res.status, res.body.as_str()), (200, "conteúdo fixo"));
+    assert_eq!(res.header("x-powered-by"), Some("dryad"));
+
+    let res = request(&mut conn, "GET /privado HTTP/1.1\r\nHost: x\r\n\r\n");
+    assert_eq!((res.status, res.body.as_str()), (401, "não autorizado"));
+
+    let res = request(&mut conn, "DELETE /fixo HTTP/1.1\r\nHost: x\r\n\r\n");
+    assert_eq!(res.status, 405);
+    assert_eq!(res.header("allow"), Some("GET"));
+
+    let res = request(&mut conn, "GET /nada HTTP/1.1\r\nHost: x\r\n\r\n");
+    assert_eq!(res.status, 404);
+
+    // Corpo acima de max_body_size é recusado antes de ser lido
+    let mut big = connect(port);
+    let res = request(&mut big, "POST /eco HTTP/1.1\r\nHost: x\r\nContent-Length: 100000\r\nExpect: 100-continue\r\n\r\n");
+    assert_eq!(res.status, 413);
+    assert_eq!(res.header("connection"), Some("close"));
+
+    let res = request(&mut conn, "POST /desligar HTTP/1.1\r\nHost: x\r\nContent-Length: 0\r\n\r\n");
+    assert_eq!((res.status, res.body.as_str()), (200, "tchau"));
+    assert_eq!(res.header("connection"), Some("close"));
+
+    assert_eq!(server.join().unwrap(), "parado");
+    assert!(TcpStream::connect(("127.0.0.1", port)).is_err(), "porta deveria estar fechada após o stop");
+}
+
+#[test]
+fn test_http_server_uploads_and_expect_continue() {
+    let port = free_port();
+    let server = run_server_script(format!(r#"
+        #<http_server>
+        native_http_server_create("upload_api", "127.0.0.1", {port});
+        native_http_server_handle("upload_api", "PUT", "/arquivos/*", (req, res) => req.params["*"] + ":" + req.body);
+        native_http_server_handle("upload_api", "POST", "/parar", (req, res) => native_http_server_stop("upload_api"));
+        native_http_server_start("upload_api");
+        while (native_http_server_wait("upload_api", 100)) {{ }}
+        "parado"
+    "#));
+
+    let mut conn = connect(port);
+    let payload = "x".repeat(2 * 1024 * 1024);
+    send(&mut conn, format!("PUT /arquivos/a/b.txt HTTP/1.1\r\nHost: x\r\nContent-Length: {}\r\nExpect: 100-continue\r\n\r\n", payload.len()).as_bytes());
+    assert_eq!(read_line(&mut conn), "HTTP/1.1 100 Continue");
+    assert_eq!(read_line(&mut conn), "");
+    send(&mut conn, payload.as_bytes());
+    let res = read_response(&mut conn);
+    assert_eq!(res.status, 200);
+    assert_eq!(res.body.len(), "a/b.txt:".len() + payload.len());
+    assert!(res.body.starts_with("a/b.txt:xxx"));
+
+    let res = request(&mut conn, "POST /parar HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n");
+    assert_eq!(res.status, 200);
+    assert_eq!(server.join().unwrap(), "parado");
+}
+
+#[test]
+fn test_http_servers_belong_to_their_interpreter() {
+    let port = free_port();
+    let server = run_server_script(format!(r#"
+        #<http_server>
+        native_http_server_create("iso_api", "127.0.0.1", {port});
+        native_http_server_handle("iso_api", "GET", "/quem", (req, res) => "dono");
+        native_http_server_handle("iso_api", "POST", "/parar", (req, res) => native_http_server_stop("iso_api"));
+        native_http_server_start("iso_api");
+        while (native_http_server_wait("iso_api", 100)) {{ }}
+        "parado"
+    "#));
+    let mut conn = connect(port);
+
+    // Outro interpreter não enxerga o servidor e, enquanto executa, não
+    // consome as requisições dele
+    let other = run_server_script(r#"
+        #<http_server>
+        let erro = "nenhum";
+        try { native_http_server_handle("iso_api", "GET", "/quem", (req, res) => "intruso"); } catch (e) { erro = "não encontrado"; }
+        let i = 0;
+        while (i < 20000) { i = i + 1; }
+        erro
+    "#.to_string());
+    for _ in 0..20 {
+        let res = request(&mut conn, "GET /quem HTTP/1.1\r\nHost: x\r\n\r\n");
+        assert_eq!((res.status, res.body.as_str()), (200, "dono"));
+    }
+    assert_eq!(other.join().unwrap(), "não encontrado");
+
+    let res = request(&mut conn, "POST /parar HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n");
+    assert_eq!(res.status, 200);
+    assert_eq!(server.join().unwrap(), "parado");
+}
+
+#[test]
+fn test_http_server_limits_concurrent_connections() {
+    let port = free_port();
+    let server = run_server_script(format!(r#"
+        #<http_server>
+        native_http_server_create("limit_api", "127.0.0.1", {port}, {{ max_connections: 1 }});
+        native_http_server_handle("limit_api", "GET", "/", (req, res) => "ok");
+        native_http_server_handle("limit_api", "POST", "/parar", (req, res) => native_http_server_stop("limit_api"));
+        native_http_server_start("limit_api");
+        while (native_http_server_wait("limit_api", 100)) {{ }}
+        "parado"
+    "#));
+
+    // A primeira conexão fica aberta (keep-alive) e ocupa a única vaga
+    let mut first = connect(port);
+    assert_eq!(request(&mut first, "GET / HTTP/1.1\r\nHost: x\r\n\r\n").body, "ok");
+    let mut second = connect(port);
+    let res = read_response(&mut second);
+    assert_eq!(res.status, 503);
+
+    let res = request(&mut first, "POST /parar HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n");
+    assert_eq!(res.status, 200);
+    assert_eq!(server.join().unwrap(), "parado");
+}
@@ -0,0 +1,178 @@
+// crates/dryad_runtime/tests/inspect_tests.rs
+use dryad_runtime::inspect::InspectOptions;
+use dryad_runtime::interpreter::{Interpreter, Value};
+use dryad_parser::Parser;
+use dryad_lexer::{Lexer, token::Token};
+
+fn run(input: &str) -> (Interpreter, Value) {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse().unwrap();
+
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.execute_and_return_value(&program).unwrap();
+    (interpreter, value)
+}
+
+fn inspect_with(input: &str, options: &InspectOptions) -> String {
+    let (interpreter, value) = run(input);
+    interpreter.inspect_value(&value, options)
+}
+
+fn inspect(input: &str) -> String {
+    inspect_with(input, &InspectOptions::default())
+}
+
+#[test]
+fn test_inspect_primitives() {
+    assert_eq!(inspect("42"), "42");
+    assert_eq!(inspect("1.5"), "1.5");
+    assert_eq!(inspect("true"), "true");
+    assert_eq!(inspect("null"), "null");
+    assert_eq!(inspect("\"oi\""), "\"oi\"");
+}
+
+#[test]
+fn test_inspect_nested_array() {
+    assert_eq!(inspect("[1, \"a\", [2, [3]]]"), "[1, \"a\", [2, [3]]]");
+    assert_eq!(inspect("[]"), "[]");
+}
+
+#[test]
+fn test_inspect_tuple() {
+    assert_eq!(inspect("(1, \"a\")"), "(1, \"a\")");
+}
+
+#[test]
+fn test_inspect_object_sorted_keys() {
+    assert_eq!(
+        inspect("let o = { b: 2, a: [1], \"c d\": null }; o"),
+        "{ a: [1], b: 2, \"c d\": null }"
+    );
+    assert_eq!(inspect("let o = {}; o"), "{}");
+}
+
+#[test]
+fn test_inspect_instance_with_class_name() {
+    let input = r#"
+        class Ponto { x = 1; y = 2; }
+        new Ponto()
+    "#;
+    assert_eq!(inspect(input), "Ponto { x: 1, y: 2 }");
+}
+
+#[test]
+fn test_inspect_class() {
+    let input = r#"
+        class Animal {}
+        class Gato extends Animal {}
+        Gato
+    "#;
+    assert_eq!(inspect(input), "[class Gato extends Animal]");
+}
+
+#[test]
+fn test_inspect_detects_cycles() {
+    let input = r#"
+        let o = { nome: "a" };
+        o.eu = o;
+        o
+    "#;
+    assert_eq!(inspect(input), "{ eu: [Circular], nome: \"a\" }");
+}
+
+#[test]
+fn test_inspect_shared_reference_is_not_cycle() {
+    let input = r#"
+        let x = [1];
+        [x, x]
+    "#;
+    assert_eq!(inspect(input), "[[1], [1]]");
+}
+
+#[test]
+fn test_inspect_depth_limit() {
+    let options = InspectOptions {
+        depth: 1,
+        ..InspectOptions::default()
+    };
+    assert_eq!(inspect_with("[[1], { a: 1 }]", &options), "[[Array], {...}]");
+}
+
+#[test]
+fn test_inspect_width_limit() {
+    let options = InspectOptions {
+        max_items: 2,
+        ..InspectOptions::default()
+    };
+    assert_eq!(inspect_with("[1, 2, 3, 4]", &options), "[1, 2, ... 2 mais]");
+}
+
+#[test]
+fn test_inspect_breaks_long_collections() {
+    let options = InspectOptions {
+        break_length: 10,
+        ..InspectOptions::default()
+    };
+    assert_eq!(
+        inspect_with("[\"abcdef\", [\"ghijkl\"]]", &options),
+        "[\n  \"abcdef\",\n  [\n    \"ghijkl\"\n  ]\n]"
+    );
+}
+
+#[test]
+fn test_inspect_colors() {
+    let options = InspectOptions {
+        colors: true,
+        ..InspectOptions::default()
+    };
+    assert_eq!(inspect_with("[1]", &options), "[\x1b[33m1\x1b[0m]");
+}
+
+#[test]
+fn test_inspect_display_unquotes_top_level_string() {
+    let (interpreter, value) = run("\"oi\"");
+    assert_eq!(interpreter.inspect_value(&value, &InspectOptions::display()), "oi");
+    let (interpreter, value) = run("[\"oi\"]");
+    assert_eq!(interpreter.inspect_value(&value, &InspectOptions::display()), "[\"oi\"]");
+}
+
+#[test]
+fn test_inspect_native_with_options() {
+    let input = r#"
+        #<debug>
+        inspect([1, { a: "x" }], { maxItems: 1 })
+    "#;
+    let (_, value) = run(input);
+    assert_eq!(value, Value::String("[1, ... 1 mais]".to_string()));
+}
+
+#[test]
+fn test_inspect_native_rejects_bad_options() {
+    let input = r#"
+        #<debug>
+        inspect([1], { depth: "x" })
+    "#;
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    assert!(interpreter.execute_and_return_value(&program).is_err());
+}
@@ -0,0 +1,98 @@
+// crates/dryad_runtime/tests/interface_abstract_tests.rs
+use dryad_errors::DryadError;
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::Interpreter;
+
+const PRELUDE: &str = r#"
+    interface Shape {
+        function area(): number;
+        function describe(): string {
+            return "área " + this.area();
+        }
+    }
+    abstract class Polygon implements Shape {
+        abstract function area(): number;
+        function sides() { return 0; }
+    }
+    class Square extends Polygon {
+        side = 2;
+        function init(side) { this.side = side; }
+        function area() { return this.side * this.side; }
+        function sides() { return 4; }
+    }
+"#;
+
+fn run(input: &str) -> Result<String, DryadError> {
+    let source = format!("{}\n{}", PRELUDE, input);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    let value = interpreter.execute_and_return_value(&program)?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn error(input: &str) -> (u16, String) {
+    match run(input) {
+        Err(e) => (e.code(), e.message().to_string()),
+        Ok(value) => panic!("Esperado erro, encontrado {}", value),
+    }
+}
+
+#[test]
+fn test_default_and_inherited_methods() {
+    assert_eq!(run("new Square(3).describe()").unwrap(), r#""área 9""#);
+    assert_eq!(run("new Square(3).sides()").unwrap(), "4");
+    assert_eq!(
+        run("class Tri extends Polygon { function area() { return 1; } }\nnew Tri().sides()").unwrap(),
+        "0"
+    );
+}
+
+#[test]
+fn test_abstract_class_cannot_be_instantiated() {
+    let (code, message) = error("new Polygon()");
+    assert_eq!(code, 3049);
+    assert!(message.contains("Polygon"), "{}", message);
+}
+
+#[test]
+fn test_missing_abstract_methods_are_listed() {
+    let (code, message) = error(
+        "abstract class A { abstract function f(); abstract function g(x); }\nclass B extends A { function g(x) { return x; } }",
+    );
+    assert_eq!(code, 3050);
+    assert!(message.contains("'f'") && !message.contains("'g'"), "{}", message);
+}
+
+#[test]
+fn test_interface_conformance_lists_all_problems() {
+    let (code, message) = error(
+        "interface I { function f(a, b); function g(); }\nclass C implements I { function f(a) { return a; } }",
+    );
+    assert_eq!(code, 3103);
+    assert!(message.contains("'f' de 'I' espera 2"), "{}", message);
+    assert!(message.contains("'g' de 'I' não implementado"), "{}", message);
+
+    // Parâmetros com default satisfazem aridades menores
+    assert!(run("interface J { function f(a); }\nclass D implements J { function f(a, b = 1) { return a + b; } }\nnew D().f(1)").is_ok());
+}
+
+#[test]
+fn test_instanceof_interface() {
+    assert_eq!(run("new Square(1) instanceof Shape").unwrap(), "true");
+    assert_eq!(run("new Square(1) instanceof Polygon").unwrap(), "true");
+    assert_eq!(run("5 instanceof Shape").unwrap(), "false");
+    assert_eq!(
+        run("interface Other { function x(); }\nnew Square(1) instanceof Other").unwrap(),
+        "false"
+    );
+}
@@ -0,0 +1,165 @@
+// crates/dryad_runtime/tests/iteration_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::{Interpreter, Value};
+
+fn run(input: &str) -> Result<Value, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())
+}
+
+fn eval(input: &str) -> Value {
+    run(input).unwrap()
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_lazy_ranges() {
+    let input = r#"
+        let out = "";
+        for (i in 0..4) { out = out + i; }
+        out = out + "|";
+        let n = 3;
+        for (i in 1..=n) { out = out + i; }
+        out
+    "#;
+    assert_eq!(eval(input), text("0123|123"));
+
+    let err = run("for (i in 0..\"a\") { }").unwrap_err();
+    assert!(err.contains("Limites de intervalo devem ser números"), "{}", err);
+}
+
+#[test]
+fn test_object_keys_and_entries() {
+    let input = r#"
+        let obj = { b: 2, a: 1, c: 3 };
+        let keys = "";
+        for (k in obj) { keys = keys + k; }
+        let total = 0;
+        for ((k, v) in obj.entries()) { total = total + v; }
+        keys + total + obj.keys().length + obj.values()[2]
+    "#;
+    assert_eq!(eval(input), text("abc633"));
+}
+
+#[test]
+fn test_class_iterator_protocol() {
+    let input = r#"
+        class Countdown {
+            function init(inicio) { this.current = inicio; }
+            function iterator() { return this; }
+            function next() {
+                if (this.current <= 0) { return { done: true }; }
+                this.current = this.current - 1;
+                return { value: this.current + 1, done: false };
+            }
+        }
+        let out = "";
+        for (n in Countdown(3)) { out = out + n; }
+        out
+    "#;
+    assert_eq!(eval(input), text("321"));
+
+    let err = run("class Vazio { }\nfor (x in Vazio()) { }").unwrap_err();
+    assert!(err.contains("não é iterável"), "{}", err);
+}
+
+#[test]
+fn test_generator_in_for_in() {
+    let input = r#"
+        function* doubled(xs) {
+            for (x in xs) { yield x * 2; }
+        }
+        let total = 0;
+        for (v in doubled([1, 2, 3])) { total = total + v; }
+        total
+    "#;
+    assert_eq!(eval(input), Value::Number(12.0));
+}
+
+#[test]
+fn test_infinite_generator_with_break() {
+    let input = r#"
+        function* naturals() {
+            let n = 0;
+            while (true) {
+                yield n;
+                n = n + 1;
+            }
+        }
+        let sum = 0;
+        for (n in naturals()) {
+            if (n > 4) { break; }
+            sum = sum + n;
+        }
+        sum
+    "#;
+    assert_eq!(eval(input), Value::Number(10.0));
+}
+
+#[test]
+fn test_next_sends_values_and_returns() {
+    let input = r#"
+        function* acumulador() {
+            let total = 0;
+            while (total < 20) {
+                let x = yield total;
+                total = total + x;
+            }
+            return "fim";
+        }
+        let g = acumulador();
+        g.next();
+        g.next(5);
+        let a = g.next(10).value;
+        let last = g.next(10);
+        let after = g.next();
+        a + "," + last.value + "," + last.done + "," + after.value + "," + after.done
+    "#;
+    assert_eq!(eval(input), text("15,fim,true,null,true"));
+}
+
+#[test]
+fn test_generator_try_catch_finally() {
+    let input = r#"
+        function* passos() {
+            try {
+                yield "a";
+                throw "falhou";
+            } catch (e) {
+                yield "catch";
+            } finally {
+                yield "finally";
+            }
+            yield "depois";
+        }
+        let out = "";
+        for (p in passos()) { out = out + p + " "; }
+        out
+    "#;
+    assert_eq!(eval(input), text("a catch finally depois "));
+}
+
+#[test]
+fn test_yield_outside_statement_position() {
+    let err = run("function* g() { print(yield 1); }\ng().next();").unwrap_err();
+    assert!(err.contains("yield só pode aparecer"), "{}", err);
+
+    let err = run("yield 1;").unwrap_err();
+    assert!(err.contains("yield só pode aparecer"), "{}", err);
+}
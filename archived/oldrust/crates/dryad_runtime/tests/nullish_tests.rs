@@ -0,0 +1,105 @@
+// crates/dryad_runtime/tests/nullish_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::{Interpreter, Value};
+
+fn run(input: &str) -> Result<Value, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())
+}
+
+fn eval(input: &str) -> Value {
+    run(input).unwrap()
+}
+
+fn text(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_optional_property_access() {
+    let input = r#"
+        let user = { profile: { name: "Ana" } };
+        let vazio = null;
+        let semPerfil = { profile: null };
+        user?.profile?.name + "," + vazio?.profile.name + "," + semPerfil.profile?.name
+    "#;
+    assert_eq!(eval(input), text("Ana,null,null"));
+
+    // Sem `?.`, acessar propriedade de null continua sendo erro
+    assert!(run("let x = null; x.nome").is_err());
+}
+
+#[test]
+fn test_optional_chain_short_circuits_rest_of_chain() {
+    // Os argumentos e o restante da cadeia não são avaliados quando a base é null
+    let input = r#"
+        function explode() { throw "avaliado"; }
+        let x = null;
+        let r = x?.metodo(explode()).outro[explode()];
+        "" + r
+    "#;
+    assert_eq!(eval(input), text("null"));
+}
+
+#[test]
+fn test_optional_method_call_and_index() {
+    let input = r#"
+        class Conta {
+            function init(saldo) { this.saldo = saldo; }
+            function total() { return this.saldo; }
+        }
+        let c = Conta(5);
+        let nada = null;
+        let lista = [10, 20];
+        let semLista = null;
+        "" + c?.total() + nada?.total() + lista?.[1] + semLista?.[0] + [1, 2]?.length
+    "#;
+    assert_eq!(eval(input), text("5null20null2"));
+}
+
+#[test]
+fn test_nullish_coalescing_keeps_falsy_values() {
+    let input = r#"
+        let a = null ?? "padrão";
+        let b = 0 ?? 1;
+        let c = false ?? true;
+        let d = "" ?? "x";
+        a + "," + b + "," + c + "," + d + "|"
+    "#;
+    assert_eq!(eval(input), text("padrão,0,false,|"));
+
+    // O lado direito só é avaliado quando necessário
+    let input = r#"
+        function explode() { throw "avaliado"; }
+        function fallback() { return 2; }
+        let v = 1 ?? explode();
+        let w = null ?? fallback();
+        v + w
+    "#;
+    assert_eq!(eval(input), Value::Number(3.0));
+}
+
+#[test]
+fn test_nullish_assignment() {
+    let input = r#"
+        let config = null;
+        config ??= "default";
+        let porta = 8080;
+        porta ??= 80;
+        config + porta
+    "#;
+    assert_eq!(eval(input), text("default8080"));
+}
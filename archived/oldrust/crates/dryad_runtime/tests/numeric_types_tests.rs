@@ -0,0 +1,119 @@
+// crates/dryad_runtime/tests/numeric_types_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::{Interpreter, Value};
+
+fn run(input: &str) -> Result<Value, String> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())
+}
+
+/// Texto do resultado, como `print` mostraria
+fn eval(input: &str) -> String {
+    run(input).unwrap().to_string()
+}
+
+#[test]
+fn test_int_arithmetic_is_exact_beyond_2_53() {
+    assert_eq!(eval("9007199254740993i + 1"), "9007199254740994");
+    assert_eq!(eval("7i / 2i"), "3");
+    assert_eq!(eval("-7i % 3i"), "-1");
+    assert!(matches!(run("2i * 3").unwrap(), Value::Int(6)));
+}
+
+#[test]
+fn test_int_overflow_is_an_error() {
+    let error = run("9223372036854775807i + 1i").unwrap_err();
+    assert!(error.contains("E3045"), "{}", error);
+    assert!(run("-9223372036854775807i - 2i").is_err());
+    assert!(run("4611686018427387904i * 2i").is_err());
+    assert_eq!(eval("9223372036854775806i + 1i"), "9223372036854775807");
+}
+
+#[test]
+fn test_bigint_arithmetic() {
+    assert_eq!(eval("2n ** 100n"), "1267650600228229401496703205376");
+    assert_eq!(
+        eval("9223372036854775807n + 1n"),
+        "9223372036854775808"
+    );
+    assert_eq!(eval("10n / 3n"), "3");
+    assert!(matches!(run("1n + 1i").unwrap(), Value::BigInt(_)));
+}
+
+#[test]
+fn test_decimal_arithmetic() {
+    assert_eq!(eval("0.1d + 0.2d == 0.3d"), "true");
+    assert_eq!(eval("0.1d + 0.2d"), "0.3");
+    assert_eq!(eval("1.50d * 2"), "3.00");
+    assert_eq!(eval("1d / 3d"), "0.3333333333333333333333333333");
+    assert!(matches!(run("1.5d + 1n").unwrap(), Value::Decimal(_)));
+}
+
+#[test]
+fn test_division_by_zero() {
+    assert!(run("1i / 0i").is_err());
+    assert!(run("1n % 0n").is_err());
+    assert!(run("1d / 0d").is_err());
+}
+
+#[test]
+fn test_cross_type_comparison() {
+    assert_eq!(eval("1i == 1"), "true");
+    assert_eq!(eval("1n == 1.0d"), "true");
+    assert_eq!(eval("2i < 2.5"), "true");
+    assert_eq!(eval("10n > 9223372036854775807i"), "false");
+}
+
+#[test]
+fn test_mixing_with_fractional_number_falls_back_to_float() {
+    assert!(matches!(run("1i + 0.5").unwrap(), Value::Number(n) if n == 1.5));
+}
+
+#[test]
+fn test_conversion_builtins() {
+    assert!(matches!(run("int(3.9)").unwrap(), Value::Int(3)));
+    assert!(matches!(run("int(\"42\")").unwrap(), Value::Int(42)));
+    assert_eq!(eval("bigint(\"123456789012345678901234567890\") + 1n"), "123456789012345678901234567891");
+    assert_eq!(eval("decimal(\"1.10\")"), "1.10");
+    assert!(matches!(run("float(3i)").unwrap(), Value::Number(n) if n == 3.0));
+    assert!(run("int(9223372036854775808n)").unwrap_err().contains("E3045"));
+    assert!(run("int(\"abc\")").unwrap_err().contains("E3046"));
+}
+
+#[test]
+fn test_json_round_trip_keeps_large_integers() {
+    let input = r#"
+        #<encode_decode>
+        let data = native_json_decode("{\"id\": 9007199254740993, \"big\": 123456789012345678901234567890, \"price\": 1.50}");
+        native_json_encode({ id: data.id + 0, big: data.big, total: 1.50d });
+    "#;
+    let encoded = eval(input);
+    assert!(encoded.contains("\"id\":9007199254740993"), "{}", encoded);
+    assert!(encoded.contains("\"big\":123456789012345678901234567890"), "{}", encoded);
+    assert!(encoded.contains("\"total\":1.50"), "{}", encoded);
+
+    // Inteiros pequenos continuam sendo `number`
+    let small = run("#<encode_decode>\nnative_json_decode(\"42\")").unwrap();
+    assert!(matches!(small, Value::Number(n) if n == 42.0));
+}
+
+#[test]
+fn test_bitwise_operations_on_int_are_exact() {
+    assert_eq!(eval("9007199254740993i & 9007199254740993i"), "9007199254740993");
+    assert_eq!(eval("1i << 62i"), "4611686018427387904");
+    assert!(run("1i << 63i").unwrap_err().contains("E3045"));
+    assert_eq!(eval("1n << 100n"), "1267650600228229401496703205376");
+}
@@ -0,0 +1,113 @@
+// crates/dryad_runtime/tests/operator_overloading_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::Interpreter;
+
+const MONEY: &str = r#"
+    class Money {
+        function init(cents) { this.cents = cents; }
+        operator +(other) { return new Money(this.cents + other.cents); }
+        operator -(other) { return new Money(this.cents - other.cents); }
+        operator *(factor) { return new Money(this.cents * factor); }
+        operator -() { return new Money(-this.cents); }
+        operator ==(other) { return this.cents == other.cents; }
+        operator <(other) { return this.cents < other.cents; }
+        function toString() { return "$" + this.cents; }
+    }
+
+    class Grid {
+        function init() { this.cells = new Map(); }
+        operator [](key) { return this.cells.get(key, 0); }
+        operator []=(key, value) { this.cells.set(key, value); }
+    }
+"#;
+
+fn run(input: &str) -> Result<String, String> {
+    let source = format!("{}\n{}", MONEY, input);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    let value = interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn eval(input: &str) -> String {
+    run(input).unwrap()
+}
+
+#[test]
+fn test_arithmetic_operators() {
+    let input = r#"
+        let a = new Money(150);
+        let b = new Money(50);
+        [(a + b).cents, (a - b).cents, (a * 3).cents, (-a).cents]
+    "#;
+    assert_eq!(eval(input), "[200, 100, 450, -150]");
+}
+
+#[test]
+fn test_compound_assignment_uses_operator() {
+    let input = r#"
+        let total = new Money(0);
+        for (cents in [10, 20, 30]) {
+            total += new Money(cents);
+        }
+        total.cents
+    "#;
+    assert_eq!(eval(input), "60");
+}
+
+#[test]
+fn test_comparisons_derive_from_equal_and_less() {
+    let input = r#"
+        let a = new Money(100);
+        let b = new Money(200);
+        [a == new Money(100), a != b, a < b, a > b, a <= new Money(100), b >= a]
+    "#;
+    assert_eq!(eval(input), "[true, true, true, false, true, true]");
+}
+
+#[test]
+fn test_equality_without_operator_is_identity() {
+    let input = r#"
+        let g = new Grid();
+        [g == g, g == new Grid(), g != new Grid()]
+    "#;
+    assert_eq!(eval(input), "[true, false, true]");
+}
+
+#[test]
+fn test_index_operators() {
+    let input = r#"
+        let g = new Grid();
+        g["a1"] = 5;
+        g["b2"] = g["a1"] + 1;
+        [g["a1"], g["b2"], g["c3"]]
+    "#;
+    assert_eq!(eval(input), "[5, 6, 0]");
+}
+
+#[test]
+fn test_to_string_in_concatenation_and_templates() {
+    let input = r#"
+        let price = new Money(250);
+        ["Total: " + price, `Preço: ${price}`, "" + (price + new Money(50))]
+    "#;
+    assert_eq!(eval(input), r#"["Total: $250", "Preço: $250", "$300"]"#);
+}
+
+#[test]
+fn test_missing_operator_keeps_type_error() {
+    let error = run("new Grid() * 2").unwrap_err();
+    assert!(error.contains("3006"), "{}", error);
+}
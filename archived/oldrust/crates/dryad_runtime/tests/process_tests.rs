@@ -0,0 +1,176 @@
+//! Os testes usam `sh`, `cat`, `sort` e `sleep`, então só rodam em Unix
+#![cfg(unix)]
+
+use dryad_lexer::Lexer;
+use dryad_parser::Parser;
+use dryad_runtime::Interpreter;
+
+fn interpreter(allow_exec: bool) -> Interpreter {
+    let mut interpreter = Interpreter::new();
+    interpreter.activate_native_category("system_env").unwrap();
+    interpreter.set_allow_exec(allow_exec);
+    interpreter
+}
+
+fn eval_with(allow_exec: bool, code: &str) -> Result<String, String> {
+    run(&mut interpreter(allow_exec), code)
+}
+
+fn run(interpreter: &mut Interpreter, code: &str) -> Result<String, String> {
+    let mut lexer = Lexer::new(code);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().expect("Erro léxico");
+        let is_eof = matches!(token.token, dryad_lexer::Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    let program = Parser::new(tokens).parse().expect("Erro de parsing");
+    let value = interpreter.execute_and_return_value(&program).map_err(|e| e.to_string())?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn eval(code: &str) -> String {
+    eval_with(true, code).unwrap_or_else(|e| panic!("Erro de runtime: {}", e))
+}
+
+#[test]
+fn test_spawn_requires_allow_exec() {
+    let error = eval_with(false, r#"spawn("echo", ["oi"])"#).unwrap_err();
+    assert!(error.contains("--allow-exec"), "{}", error);
+}
+
+#[test]
+fn test_process_functions_require_allow_exec() {
+    let forged = r#"{ _type: "process", id: "proc_1", pid: 1 }"#;
+    for function in ["process_status", "process_kill", "process_read", "process_wait"] {
+        let error = eval_with(false, &format!("{}({})", function, forged)).unwrap_err();
+        assert!(error.contains(function) && error.contains("--allow-exec"), "{}", error);
+    }
+}
+
+#[test]
+fn test_processes_belong_to_their_interpreter() {
+    let mut owner = interpreter(true);
+    let id = run(&mut owner, r#"let p = spawn("sleep", ["5"]); p.id"#).unwrap();
+
+    // Outro interpreter não enxerga o processo, nem pelo id nem forjando o objeto
+    let mut other = interpreter(true);
+    let forged = format!(r#"process_kill({{ _type: "process", id: {}, pid: 1 }})"#, id);
+    let error = run(&mut other, &forged).unwrap_err();
+    assert!(error.contains("não encontrado"), "{}", error);
+    let error = run(&mut other, &format!("process_kill({})", id)).unwrap_err();
+    assert!(error.contains("esperado um processo"), "{}", error);
+
+    assert_eq!(run(&mut owner, "[process_kill(p, \"KILL\"), process_wait(p).signal]").unwrap(), r#"[true, "SIGKILL"]"#);
+}
+
+#[test]
+fn test_wait_releases_process_and_keeps_exit_status() {
+    let result = eval(
+        r#"
+        let p = spawn("sh", ["-c", "exit 4"]);
+        let r = process_wait(p);
+        let erro = null;
+        try { process_write(p, "x"); } catch (e) { erro = "finalizado"; }
+        [p.exit.code, process_status(p).code, process_status(p).id == p.id, process_wait(p).code, process_wait(p).stdout, erro]
+    "#,
+    );
+    assert_eq!(result, r#"[4, 4, true, 4, null, "finalizado"]"#);
+}
+
+#[test]
+fn test_spawn_collects_output_and_exit_code() {
+    let result = eval(
+        r#"
+        let p = spawn("sh", ["-c", "echo saida; echo erro >&2; exit 3"]);
+        let r = process_wait(p);
+        [r.code, r.success, r.signal, r.stdout, r.stderr, process_status(p).running]
+    "#,
+    );
+    assert_eq!(result, r#"[3, false, null, "saida\n", "erro\n", false]"#);
+}
+
+#[test]
+fn test_stdin_pipe_and_incremental_reads() {
+    let result = eval(
+        r#"
+        let p = spawn("cat", [], { stdin: "pipe" });
+        process_write(p, "primeira\nsegunda\n");
+        let a = process_read_line(p);
+        let b = process_read_line(p);
+        process_write(p, "sem quebra");
+        process_close_stdin(p);
+        let c = process_read_line(p);
+        let fim = process_read_line(p);
+        [a, b, c, fim, process_wait(p).code]
+    "#,
+    );
+    assert_eq!(result, r#"["primeira", "segunda", "sem quebra", null, 0]"#);
+}
+
+#[test]
+fn test_env_and_cwd_overrides() {
+    let dir = std::env::temp_dir();
+    let code = format!(
+        r#"
+        let p = spawn("sh", ["-c", "echo $DRYAD_TESTE:$HOME; pwd"], {{
+            env: {{ DRYAD_TESTE: "valor", HOME: null }},
+            cwd: "{}"
+        }});
+        process_wait(p).stdout
+    "#,
+        dir.display()
+    );
+    let expected = format!("\"valor:\\n{}\\n\"", dir.canonicalize().unwrap().display());
+    assert_eq!(eval(&code), expected);
+}
+
+#[test]
+fn test_timeout_and_kill() {
+    let result = eval(
+        r#"
+        let lento = spawn("sleep", ["5"], { timeout_ms: 100 });
+        let r = process_wait(lento);
+
+        let outro = spawn("sleep", ["5"]);
+        let ainda = process_wait(outro, 50);
+        let enviado = process_kill(outro, "TERM");
+        let s = process_wait(outro);
+        [r.timed_out, r.signal, ainda, enviado, s.signal, s.code, process_kill(outro)]
+    "#,
+    );
+    assert_eq!(result, r#"[true, "SIGKILL", null, true, "SIGTERM", null, false]"#);
+}
+
+#[test]
+fn test_pipe_one_process_into_another() {
+    let result = eval(
+        r#"
+        let origem = spawn("sh", ["-c", "printf 'pera\nbanana\nmaçã\n'"]);
+        let ordenado = spawn("sort", [], { stdin: origem, env: { LC_ALL: "C" } });
+        let r = process_wait(ordenado);
+        let erro = null;
+        try { process_read(origem); } catch (e) { erro = "ligado"; }
+        [r.stdout, process_wait(origem).stdout, erro]
+    "#,
+    );
+    assert_eq!(result, r#"["banana\nmaçã\npera\n", null, "ligado"]"#);
+}
+
+#[test]
+fn test_read_with_timeout_returns_empty_string() {
+    let result = eval(
+        r#"
+        let p = spawn("sh", ["-c", "sleep 0.3; echo tarde"]);
+        let cedo = process_read(p, 20);
+        let depois = process_read(p);
+        let fim = process_read(p);
+        process_wait(p);
+        [cedo, depois, fim, process_read_stderr(p)]
+    "#,
+    );
+    assert_eq!(result, r#"["", "tarde\n", null, null]"#);
+}
@@ -0,0 +1,88 @@
+// crates/dryad_runtime/tests/profiler_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::Interpreter;
+use dryad_runtime::profiler::{AllocationSite, Profile};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn run_with_profile(source: &str) -> Profile {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+
+    let mut interpreter = Interpreter::new();
+    interpreter.set_current_file(PathBuf::from("perfil.dryad"));
+    interpreter.enable_profiling(Duration::from_millis(1));
+    interpreter.execute(&program).unwrap();
+    interpreter.take_profile().unwrap()
+}
+
+#[test]
+fn test_profile_samples_call_stack() {
+    let source =
+        "#<time>\nfunction espera() {\n    native_sleep(30);\n    return 1;\n}\nespera();\n";
+    let profile = run_with_profile(source);
+
+    let inside: u64 = profile
+        .stacks
+        .iter()
+        .filter(|(stack, _)| stack.as_slice() == ["<main>", "espera"])
+        .map(|(_, count)| count)
+        .sum();
+    assert!(inside >= 5, "amostras em espera: {}", inside);
+
+    let times = profile.function_times();
+    assert_eq!(times[0].name, "espera");
+    let main = times.iter().find(|t| t.name == "<main>").unwrap();
+    assert_eq!(main.total_samples, profile.total_samples());
+}
+
+#[test]
+fn test_profile_counts_allocations_per_site() {
+    let source = "function cria(n) {\n    let i = 0;\n    while (i < n) {\n        let xs = [i];\n        i = i + 1;\n    }\n}\ncria(7);\nlet o = [1, 2];\n";
+    let profile = run_with_profile(source);
+
+    let site = |function: &str, line| AllocationSite {
+        function: function.to_string(),
+        file: Some(PathBuf::from("perfil.dryad")),
+        line,
+    };
+    assert_eq!(profile.allocations.get(&site("cria", 4)), Some(&7));
+    assert_eq!(profile.allocations.get(&site("<main>", 9)), Some(&1));
+}
+
+#[test]
+fn test_profile_folded_and_function_times() {
+    let mut stacks = HashMap::new();
+    stacks.insert(vec!["<main>".to_string(), "f".to_string()], 3);
+    stacks.insert(
+        vec!["<main>".to_string(), "f".to_string(), "f".to_string()],
+        2,
+    );
+    stacks.insert(vec!["<main>".to_string(), "g h".to_string()], 1);
+    let profile = Profile {
+        interval: Duration::from_millis(1),
+        stacks,
+        allocations: HashMap::new(),
+    };
+
+    assert_eq!(profile.folded(), "<main>;f 3\n<main>;f;f 2\n<main>;g_h 1\n");
+
+    let times = profile.function_times();
+    assert_eq!(times[0].name, "f");
+    assert_eq!(times[0].self_samples, 5);
+    // Recursão não conta duas vezes no tempo total
+    assert_eq!(times[0].total_samples, 5);
+    assert_eq!(times.last().unwrap().name, "<main>");
+    assert_eq!(times.last().unwrap().total_samples, 6);
+    assert!(profile.report(10).contains("6 amostras"));
+}
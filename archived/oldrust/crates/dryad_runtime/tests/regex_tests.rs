@@ -0,0 +1,148 @@
+use dryad_lexer::Lexer;
+use dryad_parser::Parser;
+use dryad_runtime::Interpreter;
+
+fn try_eval(code: &str) -> Result<String, String> {
+    let mut interpreter = Interpreter::new();
+    interpreter.activate_native_category("utils").unwrap();
+    let mut lexer = Lexer::new(code);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().map_err(|e| e.to_string())?;
+        let is_eof = matches!(token.token, dryad_lexer::Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    let program = Parser::new(tokens).parse().expect("Erro de parsing");
+    let value = interpreter.execute_and_return_value(&program).map_err(|e| e.to_string())?;
+    Ok(single_line(&interpreter.inspect_value(&value, &Default::default())))
+}
+
+/// Junta a saída do inspect, que quebra listas longas em várias linhas
+fn single_line(text: &str) -> String {
+    text.lines().map(str::trim).fold(String::new(), |mut out, line| {
+        if out.ends_with(',') || out.ends_with('{') {
+            out.push(' ');
+        }
+        out.push_str(line);
+        out
+    })
+}
+
+fn eval(code: &str) -> String {
+    try_eval(code).unwrap_or_else(|e| panic!("Erro de runtime: {}", e))
+}
+
+#[test]
+fn test_literal_test_and_flags() {
+    let result = eval(
+        r#"
+        let r = /^olá,?\s+MUNDO$/i;
+        [r.test("Olá mundo"), r.test("olá"), r.source, r.flags, typeof r, r]
+    "#,
+    );
+    assert_eq!(result, r#"[true, false, "^olá,?\\s+MUNDO$", "i", "regex", /^olá,?\s+MUNDO$/i]"#);
+}
+
+#[test]
+fn test_find_returns_match_with_groups_and_spans() {
+    let result = eval(
+        r#"
+        let m = /(?<user>\w+)@(?<host>[\w.]+)|(x)/.find("contato: ana@exemplo.com");
+        [m.text, m.start, m.end, m.groups, m.named.user, m.named.host, m.spans[0], m.spans[2]]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"["ana@exemplo.com", 9, 24, ["ana", "exemplo.com", null], "ana", "exemplo.com", (9, 12), null]"#
+    );
+}
+
+#[test]
+fn test_positions_count_characters() {
+    let result = eval(
+        r#"
+        let found = [];
+        for (m in /ç\w*/.find_all("maçã e açúcar com ção")) {
+            found.push((m.text, m.start, m.end));
+        }
+        [found, /a/.find("banana", 2).start, /z/.find("banana"), /a/.find_all("banana", 2).length]
+    "#,
+    );
+    assert_eq!(result, r#"[[("çã", 2, 4), ("çúcar", 8, 13), ("ção", 18, 21)], 3, null, 2]"#);
+}
+
+#[test]
+fn test_replace_with_templates_and_callbacks() {
+    let result = eval(
+        r#"
+        let data = /(?<d>\d{2})\/(?<m>\d{2})\/(?<a>\d{4})/;
+        [
+            data.replace("em 10/03/2024 e 11/03/2024", "${a}-${m}-${d}"),
+            data.replace_all("em 10/03/2024 e 11/03/2024", "$a-$m-$d"),
+            /\d+/.replace_all("1 + 20 = 21", (m) => "<" + m.text + ">"),
+            /(\w)(\w*)/.replace("dryad lang", (m) => m.groups[0] + "!" + m.groups[1])
+        ]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"["em 2024-03-10 e 11/03/2024", "em 2024-03-10 e 2024-03-11", "<1> + <20> = <21>", "d!ryad lang"]"#
+    );
+}
+
+#[test]
+fn test_split_properties_and_constructor() {
+    let result = eval(
+        r#"
+        let r = regex("[,;]\\s*", "");
+        let nomes = regex("(?<a>x)(y)(?<b>z)");
+        [r.split("a, b;c"), r.split("a, b;c", 2), nomes.group_count, nomes.group_names,
+         regex(regex_escape("1+1=2")).test("1+1=2"), regex("ABC", "i") == /ABC/i, /a\/b/]
+    "#,
+    );
+    assert_eq!(
+        result,
+        r#"[["a", "b", "c"], ["a", "b;c"], 3, ["a", "b"], true, true, /a\/b/]"#
+    );
+}
+
+#[test]
+fn test_regex_patterns_in_match() {
+    let result = eval(
+        r#"
+        function tipo(s) {
+            return match s {
+                /^\d+$/ => "número",
+                /^[a-z]+$/i => "palavra",
+                _ => "outro"
+            };
+        }
+        [tipo("123"), tipo("Dryad"), tipo("a-1"), tipo(42)]
+    "#,
+    );
+    assert_eq!(result, r#"["número", "palavra", "outro", "outro"]"#);
+}
+
+#[test]
+fn test_legacy_functions_accept_regex_values() {
+    let result = eval(
+        r#"
+        [native_regex_test(/^a/i, "Abc"), native_regex_split(/\s+/, "a  b c"), native_regex_replace("o", "0", "foo")]
+    "#,
+    );
+    assert_eq!(result, r#"[true, ["a", "b", "c"], "f00"]"#);
+}
+
+#[test]
+fn test_invalid_patterns_report_errors() {
+    let error = try_eval(r#"regex("(abc")"#).unwrap_err();
+    assert!(error.contains("Regex inválida"), "{}", error);
+    let error = try_eval(r#"/(abc/.test("abc")"#).unwrap_err();
+    assert!(error.contains("E3052"), "{}", error);
+    let error = try_eval(r#"/abc/g"#).unwrap_err();
+    assert!(error.contains("E1007"), "{}", error);
+    assert!(try_eval(r#"regex("a", "g")"#).is_err());
+}
@@ -0,0 +1,103 @@
+// crates/dryad_runtime/tests/strict_types_tests.rs
+use dryad_errors::DryadError;
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::Interpreter;
+
+const PRELUDE: &str = r#"
+    type Id = number | string;
+    interface Shape {
+        function area(): number;
+    }
+    class Square implements Shape {
+        side: number = 1;
+        function init(side: number) { this.side = side; }
+        function area(): number { return this.side * this.side; }
+    }
+    function greet(name: string): string { return "Olá " + name; }
+    function broken(): number { return "x"; }
+    function first<T>(xs: T[]): T { return xs[0]; }
+"#;
+
+fn run(input: &str, strict: bool) -> Result<String, DryadError> {
+    let source = format!("{}\n{}", PRELUDE, input);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    interpreter.set_strict_types(strict);
+    let value = interpreter.execute_and_return_value(&program)?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn type_error(input: &str) -> (String, String, String) {
+    match run(input, true) {
+        Err(DryadError::Type { code, message, expected_type, found_type, .. }) => {
+            assert_eq!(code, 4004);
+            (message, expected_type, found_type)
+        }
+        other => panic!("Esperado erro de tipo, encontrado {:?}", other),
+    }
+}
+
+#[test]
+fn test_annotations_are_ignored_by_default() {
+    assert_eq!(run("greet(42)", false).unwrap(), r#""Olá 42""#);
+    assert_eq!(run("broken()", false).unwrap(), r#""x""#);
+}
+
+#[test]
+fn test_parameters_and_returns() {
+    assert_eq!(run(r#"greet("Ana")"#, true).unwrap(), r#""Olá Ana""#);
+
+    let (message, expected, found) = type_error("greet(42)");
+    assert!(message.contains("Parâmetro 'name' de 'greet'"), "{}", message);
+    assert_eq!((expected.as_str(), found.as_str()), ("string", "number"));
+
+    let (message, _, _) = type_error("broken()");
+    assert!(message.contains("Retorno de 'broken'"), "{}", message);
+
+    let (message, _, _) = type_error(r#"let double = (n: number): number => n * 2; double("2")"#);
+    assert!(message.contains("lambda"), "{}", message);
+}
+
+#[test]
+fn test_typed_variables() {
+    assert_eq!(run(r#"let id: Id = 1; id = "abc"; id"#, true).unwrap(), r#""abc""#);
+    assert_eq!(run(r#"let free = 1; free = "ok"; free"#, true).unwrap(), r#""ok""#);
+
+    let (message, expected, found) = type_error("let id: Id = 1; id = true;");
+    assert!(message.contains("Variável 'id'"), "{}", message);
+    assert_eq!((expected.as_str(), found.as_str()), ("Id", "boolean"));
+
+    type_error(r#"let xs: number[] = [1, "dois"];"#);
+    type_error(r#"let p: { name: string, age?: number } = { age: 3 };"#);
+}
+
+#[test]
+fn test_classes_and_properties() {
+    assert_eq!(run("let s: Shape = new Square(3); s.area()", true).unwrap(), "9");
+
+    let (message, _, found) = type_error(r#"new Square("3")"#);
+    assert!(message.contains("Square.init"), "{}", message);
+    assert_eq!(found, "string");
+
+    let (message, _, _) = type_error(r#"let s = new Square(2); s.side = "grande";"#);
+    assert!(message.contains("Propriedade 'side' de 'Square'"), "{}", message);
+
+    let (_, expected, found) = type_error("let s: Square = 5;");
+    assert_eq!((expected.as_str(), found.as_str()), ("Square", "number"));
+}
+
+#[test]
+fn test_type_parameters_are_erased() {
+    assert_eq!(run(r#"first(["a", "b"])"#, true).unwrap(), r#""a""#);
+    assert_eq!(run("first([1, 2])", true).unwrap(), "1");
+}
@@ -47,6 +47,30 @@ mod tests {
         Ok(())
     }
 
+    /// Executa o código e devolve o último valor formatado
+    fn eval_dryad_code(code: &str) -> String {
+        eval_in(&mut setup_interpreter_with_tcp(), code)
+    }
+
+    /// Como `eval_dryad_code`, mas num interpreter que continua vivo
+    fn eval_in(interpreter: &mut Interpreter, code: &str) -> String {
+        let mut lexer = Lexer::new(code);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token().expect("Erro léxico");
+            let is_eof = matches!(token.token, dryad_lexer::Token::Eof);
+            tokens.push(token);
+            if is_eof {
+                break;
+            }
+        }
+        let program = Parser::new(tokens).parse().expect("Erro de parsing");
+        let value = interpreter
+            .execute_and_return_value(&program)
+            .unwrap_or_else(|e| panic!("Erro de runtime: {}", e));
+        interpreter.inspect_value(&value, &Default::default())
+    }
+
     #[test]
     fn test_tcp_server_creation() {
         let code = r#"
@@ -178,6 +202,170 @@ mod tests {
         execute_dryad_code(code).expect("Falha no tratamento de erros TCP");
     }
 
+    #[test]
+    fn test_tcp_connection_handler_reads_and_writes_bytes() {
+        let code = r#"
+            tcp_server_create("conn_server", "127.0.0.1", 0);
+            function atender(conn) {
+                let linha = tcp_read_line(conn);
+                let corpo = tcp_read_exact(conn, 3);
+                tcp_write(conn, linha + "\n");
+                tcp_write(conn, corpo);
+                tcp_close(conn);
+            }
+            tcp_server_on("conn_server", "connection", atender);
+            tcp_server_start("conn_server");
+
+            let c = tcp_connect("127.0.0.1", tcp_server_status("conn_server").port);
+            tcp_set_timeout(c, 2000);
+            tcp_write(c, "ola\r\n");
+            tcp_write(c, [0, 255, 10]);
+            tcp_shutdown(c, "write");
+            tcp_server_wait("conn_server", 2000);
+
+            let resposta = [tcp_read_line(c), tcp_read_exact(c, 3), tcp_read(c)];
+            tcp_close(c);
+            tcp_server_stop("conn_server");
+            resposta
+        "#;
+
+        assert_eq!(eval_dryad_code(code), r#"["ola", [0, 255, 10], null]"#);
+    }
+
+    #[test]
+    fn test_tcp_data_and_close_events() {
+        let code = r#"
+            tcp_server_create("data_server", "127.0.0.1", 0);
+            let eventos = [];
+            function ecoar(conn, bytes) {
+                eventos.push(bytes.length);
+                tcp_write(conn, bytes);
+            }
+            function fechou(conn) { eventos.push("close"); }
+            tcp_server_on("data_server", "data", ecoar);
+            tcp_server_on("data_server", "close", fechou);
+            tcp_server_start("data_server");
+
+            let c = tcp_connect("localhost", tcp_server_status("data_server").port);
+            tcp_set_timeout(c, 2000);
+            tcp_write(c, [1, 2, 3, 4]);
+            // Os handlers rodam entre as instruções: espera o eco antes de ler
+            let n = 0;
+            while (n < 40 && eventos.length < 1) {
+                tcp_server_wait("data_server", 50);
+                n = n + 1;
+            }
+            let eco = tcp_read_exact(c, 4);
+            tcp_close(c);
+
+            while (n < 80 && eventos.length < 2) {
+                tcp_server_wait("data_server", 50);
+                n = n + 1;
+            }
+            tcp_server_stop("data_server");
+            [eco, eventos]
+        "#;
+
+        assert_eq!(eval_dryad_code(code), r#"[[1, 2, 3, 4], [4, "close"]]"#);
+    }
+
+    #[test]
+    fn test_tcp_timeout_and_nonblocking_reads() {
+        let code = r#"
+            tcp_server_create("quiet_server", "127.0.0.1", 0);
+            function calado(conn) { }
+            tcp_server_on("quiet_server", "connection", calado);
+            tcp_server_start("quiet_server");
+
+            let c = tcp_connect("127.0.0.1", tcp_server_status("quiet_server").port);
+            tcp_set_timeout(c, 100);
+            let expirou = false;
+            try {
+                tcp_read(c);
+            } catch (e) {
+                expirou = true;
+            }
+            tcp_set_nonblocking(c, true);
+            let vazio = tcp_read(c);
+            let linha = tcp_read_line(c);
+            tcp_close(c);
+            tcp_server_stop("quiet_server");
+            [expirou, vazio, linha]
+        "#;
+
+        assert_eq!(eval_dryad_code(code), "[true, [], null]");
+    }
+
+    #[test]
+    fn test_tcp_read_exact_fails_on_early_eof() {
+        let code = r#"
+            tcp_server_create("short_server", "127.0.0.1", 0);
+            function curto(conn) {
+                tcp_write(conn, "ab");
+                tcp_close(conn);
+            }
+            tcp_server_on("short_server", "connection", curto);
+            tcp_server_start("short_server");
+
+            let c = tcp_connect("127.0.0.1", tcp_server_status("short_server").port);
+            tcp_set_timeout(c, 2000);
+            tcp_server_wait("short_server", 2000);
+            let erro = false;
+            try {
+                tcp_read_exact(c, 5);
+            } catch (e) {
+                erro = true;
+            }
+            tcp_close(c);
+            tcp_server_stop("short_server");
+            erro
+        "#;
+
+        assert_eq!(eval_dryad_code(code), "true");
+    }
+
+    #[test]
+    fn test_tcp_servers_belong_to_their_interpreter() {
+        let mut dono = setup_interpreter_with_tcp();
+        eval_in(&mut dono, r#"
+            tcp_server_create("iso_server", "127.0.0.1", 0);
+            let eventos = [];
+            function chegou(conn) { eventos.push("dono"); tcp_close(conn); }
+            tcp_server_on("iso_server", "connection", chegou);
+            tcp_server_start("iso_server");
+            let c = tcp_connect("127.0.0.1", tcp_server_status("iso_server").port);
+        "#);
+
+        // Outro interpreter na mesma thread não enxerga o servidor nem
+        // consome os eventos dele, mesmo com um servidor de mesmo id
+        let mut outro = setup_interpreter_with_tcp();
+        let visto = eval_in(&mut outro, r#"
+            let erro = "nenhum";
+            try { tcp_server_status("iso_server"); } catch (e) { erro = "não encontrado"; }
+            tcp_server_create("iso_server", "127.0.0.1", 0);
+            let eventos = [];
+            function intruso(conn) { eventos.push("intruso"); }
+            tcp_server_on("iso_server", "connection", intruso);
+            tcp_server_start("iso_server");
+            tcp_server_wait("iso_server", 300);
+            tcp_server_stop("iso_server");
+            [erro, eventos]
+        "#);
+        assert_eq!(visto, r#"["não encontrado", []]"#);
+
+        let eventos = eval_in(&mut dono, r#"
+            let n = 0;
+            while (n < 40 && eventos.length < 1) {
+                tcp_server_wait("iso_server", 50);
+                n = n + 1;
+            }
+            tcp_close(c);
+            tcp_server_stop("iso_server");
+            eventos
+        "#);
+        assert_eq!(eventos, r#"["dono"]"#);
+    }
+
     #[test]
     #[ignore = "Teste de integração TCP com problemas de timing"]
     fn test_tcp_client_server_integration() {
@@ -0,0 +1,217 @@
+use dryad_lexer::Lexer;
+use dryad_parser::Parser;
+use dryad_runtime::Interpreter;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair,
+};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Certificados gerados uma vez por execução: uma CA, o servidor
+/// (`localhost`/`127.0.0.1`) e um cliente, todos com chaves P-256
+struct TestCerts {
+    ca: String,
+    server_cert: String,
+    server_key: String,
+    client_cert: String,
+    client_key: String,
+}
+
+fn params(common_name: &str, subject_alt_names: &[&str]) -> CertificateParams {
+    let names = subject_alt_names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    let mut params = CertificateParams::new(names).unwrap();
+    let mut name = DistinguishedName::new();
+    name.push(DnType::CommonName, common_name);
+    params.distinguished_name = name;
+    params
+}
+
+fn issue(params: CertificateParams, key: &KeyPair, ca: &Certificate, ca_key: &KeyPair) -> Certificate {
+    params.signed_by(key, ca, ca_key).unwrap()
+}
+
+fn certs() -> &'static TestCerts {
+    static CERTS: OnceLock<TestCerts> = OnceLock::new();
+    CERTS.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("dryad_tls_tests_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, pem: String| -> String {
+            let path: PathBuf = dir.join(name);
+            std::fs::write(&path, pem).unwrap();
+            path.to_string_lossy().into_owned()
+        };
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = params("Dryad Test CA", &[]);
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let mut server_params = params("localhost", &["localhost", "127.0.0.1"]);
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let server = issue(server_params, &server_key, &ca, &ca_key);
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = params("cliente-dryad", &[]);
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client = issue(client_params, &client_key, &ca, &ca_key);
+
+        TestCerts {
+            ca: write("ca.pem", ca.pem()),
+            server_cert: write("server.pem", server.pem()),
+            server_key: write("server.key", server_key.serialize_pem()),
+            client_cert: write("client.pem", client.pem()),
+            client_key: write("client.key", client_key.serialize_pem()),
+        }
+    })
+}
+
+/// Substitui `{ca}`, `{server_cert}`, ... pelos caminhos dos certificados
+fn with_certs(code: &str) -> String {
+    let certs = certs();
+    code.replace("{ca}", &certs.ca)
+        .replace("{server_cert}", &certs.server_cert)
+        .replace("{server_key}", &certs.server_key)
+        .replace("{client_cert}", &certs.client_cert)
+        .replace("{client_key}", &certs.client_key)
+}
+
+/// Executa o código e devolve o último valor formatado
+fn eval_dryad_code(code: &str) -> String {
+    let mut interpreter = Interpreter::new();
+    for category in ["tcp", "http_server", "websocket", "console_io"] {
+        interpreter.activate_native_category(category).unwrap();
+    }
+    let mut lexer = Lexer::new(code);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().expect("Erro léxico");
+        let is_eof = matches!(token.token, dryad_lexer::Token::Eof);
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    let program = Parser::new(tokens).parse().expect("Erro de parsing");
+    let value = interpreter
+        .execute_and_return_value(&program)
+        .unwrap_or_else(|e| panic!("Erro de runtime: {}", e));
+    interpreter.inspect_value(&value, &Default::default())
+}
+
+#[test]
+fn test_tcp_tls_client_certificate_and_alpn() {
+    let code = with_certs(
+        r#"
+        tcp_server_create("tls_echo", "127.0.0.1", 0);
+        tcp_server_tls("tls_echo", {
+            cert: "{server_cert}", key: "{server_key}", ca: "{ca}",
+            client_auth: "required", alpn: ["dryad/1", "h2"]
+        });
+        let clientes = [];
+        function ecoar(conn, bytes) {
+            clientes.push(conn.tls.peer_subject);
+            tcp_write(conn, bytes);
+        }
+        tcp_server_on("tls_echo", "data", ecoar);
+        tcp_server_start("tls_echo");
+
+        let c = tcp_connect("localhost", tcp_server_status("tls_echo").port, {
+            timeout_ms: 2000,
+            tls: { ca: "{ca}", cert: "{client_cert}", key: "{client_key}", alpn: ["http/1.1", "dryad/1"] }
+        });
+        tcp_set_timeout(c, 2000);
+        tcp_write(c, "segredo");
+        let n = 0;
+        while (n < 40 && clientes.length < 1) {
+            tcp_server_wait("tls_echo", 50);
+            n = n + 1;
+        }
+        let eco = tcp_read_exact(c, 7);
+        let info = tcp_connection_info(c).tls;
+        tcp_close(c);
+        tcp_server_stop("tls_echo");
+        [eco.length, info.alpn, info.peer_subject, clientes[0]]
+    "#,
+    );
+
+    assert_eq!(eval_dryad_code(&code), r#"[7, "dryad/1", "CN=localhost", "CN=cliente-dryad"]"#);
+}
+
+#[test]
+fn test_tcp_tls_rejects_unknown_certificate_authority() {
+    let code = with_certs(
+        r#"
+        tcp_server_create("tls_untrusted", "127.0.0.1", 0);
+        tcp_server_tls("tls_untrusted", { cert: "{server_cert}", key: "{server_key}" });
+        function nada(conn) { tcp_close(conn); }
+        tcp_server_on("tls_untrusted", "connection", nada);
+        tcp_server_start("tls_untrusted");
+        let port = tcp_server_status("tls_untrusted").port;
+
+        let erro = null;
+        try {
+            tcp_connect("localhost", port, { timeout_ms: 2000, tls: true });
+        } catch (e) {
+            erro = "falhou";
+        }
+        // Sem verificação o handshake completa
+        let c = tcp_connect("localhost", port, { timeout_ms: 2000, tls: { verify: false } });
+        let versao = tcp_connection_info(c).tls.version;
+        tcp_close(c);
+        tcp_server_stop("tls_untrusted");
+        [erro, versao]
+    "#,
+    );
+
+    assert_eq!(eval_dryad_code(&code), r#"["falhou", "TLSv1.3"]"#);
+}
+
+#[test]
+fn test_https_server_serves_fixed_route() {
+    let code = with_certs(
+        r#"
+        native_http_server_create("https_api", "127.0.0.1", 0, { tls: { cert: "{server_cert}", key: "{server_key}" } });
+        native_http_server_get("https_api", "/", "seguro\n");
+        native_http_server_start("https_api");
+        let status = native_http_server_status("https_api");
+
+        let c = tcp_connect("localhost", status.port, { timeout_ms: 2000, tls: { ca: "{ca}" } });
+        tcp_set_timeout(c, 2000);
+        tcp_write(c, "GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+        let linha = tcp_read_line(c);
+        let resposta = [linha];
+        while (linha != "") {
+            linha = tcp_read_line(c);
+        }
+        resposta.push(tcp_read_line(c));
+        tcp_close(c);
+        native_http_server_stop("https_api", 0);
+        resposta.push(status.tls);
+        resposta
+    "#,
+    );
+
+    assert_eq!(eval_dryad_code(&code), r#"["HTTP/1.1 200 OK", "seguro", true]"#);
+}
+
+#[test]
+fn test_wss_server_echo() {
+    let code = with_certs(
+        r#"
+        ws_server_create("wss_echo", "127.0.0.1", 0, { tls: { cert: "{server_cert}", key: "{server_key}" } });
+        ws_server_start("wss_echo");
+        let port = ws_server_status("wss_echo").port;
+
+        let ws = ws_connect("wss://localhost:" + port, { tls: { ca: "{ca}" } });
+        ws_send(ws.id, "oi");
+        let msg = ws_receive(ws.id);
+        ws_close(ws.id);
+        ws_server_stop("wss_echo");
+        [msg.data, ws_server_status("wss_echo").tls]
+    "#,
+    );
+
+    assert_eq!(eval_dryad_code(&code), r#"["Echo: oi", true]"#);
+}
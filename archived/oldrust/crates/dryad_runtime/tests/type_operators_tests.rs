@@ -0,0 +1,66 @@
+// crates/dryad_runtime/tests/type_operators_tests.rs
+use dryad_lexer::{token::Token, Lexer};
+use dryad_parser::Parser;
+use dryad_runtime::interpreter::Interpreter;
+
+const SHAPES: &str = r#"
+    class Shape {}
+    class Circle extends Shape {}
+    class Square extends Shape {}
+"#;
+
+fn run(input: &str) -> Result<String, String> {
+    let source = format!("{}\n{}", SHAPES, input);
+    let mut lexer = Lexer::new(&source);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        match token.token {
+            Token::Eof => break,
+            _ => tokens.push(token),
+        }
+    }
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut interpreter = Interpreter::new();
+    let value = interpreter
+        .execute_and_return_value(&program)
+        .map_err(|e| e.to_string())?;
+    Ok(interpreter.inspect_value(&value, &Default::default()))
+}
+
+fn eval(input: &str) -> String {
+    run(input).unwrap()
+}
+
+#[test]
+fn test_typeof_tags() {
+    let input = r#"
+        [typeof 1, typeof "a", typeof true, typeof null, typeof [1], typeof new Circle()]
+    "#;
+    assert_eq!(eval(input), r#"["number", "string", "boolean", "null", "array", "instance"]"#);
+}
+
+#[test]
+fn test_instanceof_follows_inheritance() {
+    let input = r#"
+        let c = new Circle();
+        [c instanceof Circle, c instanceof Shape, c instanceof Square, 1 instanceof Shape]
+    "#;
+    assert_eq!(eval(input), "[true, true, false, false]");
+}
+
+#[test]
+fn test_instanceof_requires_class() {
+    let error = run("new Circle() instanceof 42").unwrap_err();
+    assert!(error.contains("instanceof"), "{}", error);
+}
+
+#[test]
+fn test_type_alias_is_erased() {
+    let input = r#"
+        type Id = number | string;
+        let id: Id = "abc";
+        typeof id
+    "#;
+    assert_eq!(eval(input), r#""string""#);
+}
@@ -153,4 +153,89 @@ mod udp_tests {
         
         assert!(execute_dryad_code(code).is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_udp_message_handler_replies_with_bytes() {
+        let code = r#"
+            udp_server_create("handler_server", "127.0.0.1", 0);
+            let textos = [];
+            function responder(msg) {
+                textos.push(msg.text);
+                udp_server_send_to("handler_server", [msg.data.length, 255], msg.host, msg.port);
+            }
+            udp_server_on("handler_server", "message", responder);
+            udp_server_start("handler_server");
+
+            udp_client_create("handler_client", "127.0.0.1", udp_server_status("handler_server").port);
+            udp_client_bind("handler_client", 0);
+            udp_client_set_timeout("handler_client", 2);
+            udp_client_send("handler_client", "ping");
+            udp_server_wait("handler_server", 2000);
+            let resposta = udp_client_receive("handler_client");
+            udp_server_stop("handler_server");
+            [textos, resposta]
+        "#;
+
+        assert_eq!(eval_in(&mut setup_interpreter_with_udp(), code), r#"[["ping"], [4, 255]]"#);
+    }
+
+    #[test]
+    fn test_udp_servers_belong_to_their_interpreter() {
+        let mut dono = setup_interpreter_with_udp();
+        eval_in(&mut dono, r#"
+            udp_server_create("iso_server", "127.0.0.1", 0);
+            let textos = [];
+            function chegou(msg) { textos.push(msg.text); }
+            udp_server_on("iso_server", "message", chegou);
+            udp_server_start("iso_server");
+            udp_client_create("iso_client", "127.0.0.1", udp_server_status("iso_server").port);
+            udp_client_bind("iso_client", 0);
+            udp_client_send("iso_client", "ping");
+        "#);
+
+        // Outro interpreter na mesma thread não enxerga o servidor nem
+        // consome as mensagens dele, mesmo com um servidor de mesmo id
+        let mut outro = setup_interpreter_with_udp();
+        let visto = eval_in(&mut outro, r#"
+            let erro = "nenhum";
+            try { udp_server_status("iso_server"); } catch (e) { erro = "não encontrado"; }
+            udp_server_create("iso_server", "127.0.0.1", 0);
+            let textos = [];
+            function intruso(msg) { textos.push("intruso"); }
+            udp_server_on("iso_server", "message", intruso);
+            udp_server_start("iso_server");
+            udp_server_wait("iso_server", 300);
+            udp_server_stop("iso_server");
+            [erro, textos]
+        "#);
+        assert_eq!(visto, r#"["não encontrado", []]"#);
+
+        let textos = eval_in(&mut dono, r#"
+            let n = 0;
+            while (n < 40 && textos.length < 1) {
+                udp_server_wait("iso_server", 50);
+                n = n + 1;
+            }
+            udp_server_stop("iso_server");
+            textos
+        "#);
+        assert_eq!(textos, r#"["ping"]"#);
+    }
+
+    /// Executa o código e devolve o último valor formatado
+    fn eval_in(interpreter: &mut Interpreter, code: &str) -> String {
+        let mut lexer = Lexer::new(code);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token().unwrap();
+            let is_eof = matches!(token.token, Token::Eof);
+            tokens.push(token);
+            if is_eof {
+                break;
+            }
+        }
+        let program = Parser::new(tokens).parse().unwrap();
+        let value = interpreter.execute_and_return_value(&program).unwrap();
+        interpreter.inspect_value(&value, &Default::default())
+    }
+}